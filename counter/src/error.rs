@@ -0,0 +1,43 @@
+use num_derive::FromPrimitive;
+use num_traits::FromPrimitive;
+use solana_program::{
+    decode_error::DecodeError,
+    msg,
+    program_error::{PrintProgramError, ProgramError},
+};
+use thiserror::Error;
+
+#[derive(Clone, Copy, Debug, Eq, Error, FromPrimitive, PartialEq)]
+pub enum CounterError {
+    #[error("Account not initialized yet")]
+    UninitializedAccount,
+    #[error("PDA derived does not equal PDA passed in")]
+    InvalidPDA,
+    #[error("Signer is not the counter authority")]
+    InvalidAuthority,
+    #[error("Counter overflowed")]
+    Overflow,
+    #[error("Counter underflowed")]
+    Underflow,
+}
+
+impl From<CounterError> for ProgramError {
+    fn from(e: CounterError) -> Self {
+        ProgramError::Custom(e as u32)
+    }
+}
+
+impl<T> DecodeError<T> for CounterError {
+    fn type_of() -> &'static str {
+        "CounterError"
+    }
+}
+
+impl PrintProgramError for CounterError {
+    fn print<E>(&self)
+    where
+        E: 'static + std::error::Error + DecodeError<E> + PrintProgramError + FromPrimitive,
+    {
+        msg!(&self.to_string());
+    }
+}
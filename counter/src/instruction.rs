@@ -0,0 +1,21 @@
+use solana_program::program_error::ProgramError;
+
+pub enum CounterInstruction {
+    Initialize,
+    Increment,
+    Decrement,
+}
+
+impl CounterInstruction {
+    pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
+        let (&variant, _rest) = input
+            .split_first()
+            .ok_or(ProgramError::InvalidInstructionData)?;
+        Ok(match variant {
+            0 => Self::Initialize,
+            1 => Self::Increment,
+            2 => Self::Decrement,
+            _ => return Err(ProgramError::InvalidInstructionData),
+        })
+    }
+}
@@ -0,0 +1,135 @@
+use borsh::BorshSerialize;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    borsh1::try_from_slice_unchecked,
+    entrypoint::ProgramResult,
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+use soldev_program_utils::{
+    account::create_pda_account,
+    assert::{assert_initialized, assert_owned_by, assert_signer, assert_uninitialized},
+    pda::assert_pda,
+};
+
+use crate::error::CounterError;
+use crate::instruction::CounterInstruction;
+use crate::state::CounterState;
+
+pub fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    let instruction = CounterInstruction::unpack(instruction_data)?;
+
+    match instruction {
+        CounterInstruction::Initialize => {
+            initialize(program_id, accounts)?;
+        }
+        CounterInstruction::Increment => {
+            increment(program_id, accounts)?;
+        }
+        CounterInstruction::Decrement => {
+            decrement(program_id, accounts)?;
+        }
+    }
+    Ok(())
+}
+
+pub fn initialize(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let authority = next_account_info(account_info_iter)?;
+    let pda_counter = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+
+    // Check if the instruction is signed
+    assert_signer(authority)?;
+
+    // Derive counter PDA
+    let bump_seed = assert_pda(
+        pda_counter,
+        &[authority.key.as_ref(), CounterState::SEED.as_bytes()],
+        program_id,
+        CounterError::InvalidPDA,
+    )?;
+
+    msg!("creating counter pda account");
+    create_pda_account(
+        authority,
+        pda_counter,
+        system_program,
+        CounterState::get_account_size(),
+        program_id,
+        &[
+            authority.key.as_ref(),
+            CounterState::SEED.as_bytes(),
+            &[bump_seed],
+        ],
+    )?;
+    msg!("counter PDA created at: {}", pda_counter.key);
+
+    let mut counter_data = try_from_slice_unchecked::<CounterState>(&pda_counter.data.borrow())?;
+
+    assert_uninitialized(&counter_data, ProgramError::AccountAlreadyInitialized)?;
+
+    counter_data.discriminator = CounterState::DISCRIMINATOR.to_string();
+    counter_data.is_initialized = true;
+    counter_data.authority = *authority.key;
+    counter_data.count = 0;
+
+    counter_data.serialize(&mut *pda_counter.data.borrow_mut())?;
+    msg!("counter account initialized");
+
+    Ok(())
+}
+
+pub fn increment(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let (pda_counter, mut counter_data) = load_counter_for_authority(program_id, accounts)?;
+
+    counter_data.count = counter_data
+        .count
+        .checked_add(1)
+        .ok_or(CounterError::Overflow)?;
+    msg!("counter incremented to {}", counter_data.count);
+
+    counter_data.serialize(&mut *pda_counter.data.borrow_mut())?;
+    Ok(())
+}
+
+pub fn decrement(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let (pda_counter, mut counter_data) = load_counter_for_authority(program_id, accounts)?;
+
+    counter_data.count = counter_data
+        .count
+        .checked_sub(1)
+        .ok_or(CounterError::Underflow)?;
+    msg!("counter decremented to {}", counter_data.count);
+
+    counter_data.serialize(&mut *pda_counter.data.borrow_mut())?;
+    Ok(())
+}
+
+fn load_counter_for_authority<'a, 'b>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'b>],
+) -> Result<(&'a AccountInfo<'b>, CounterState), ProgramError> {
+    let account_info_iter = &mut accounts.iter();
+    let authority = next_account_info(account_info_iter)?;
+    let pda_counter = next_account_info(account_info_iter)?;
+
+    assert_signer(authority)?;
+    assert_owned_by(pda_counter, program_id)?;
+
+    let counter_data = try_from_slice_unchecked::<CounterState>(&pda_counter.data.borrow())?;
+
+    assert_initialized(&counter_data, CounterError::UninitializedAccount)?;
+
+    if counter_data.authority != *authority.key {
+        msg!("Signer is not the counter authority");
+        return Err(CounterError::InvalidAuthority.into());
+    }
+
+    Ok((pda_counter, counter_data))
+}
@@ -0,0 +1,28 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::program_pack::{IsInitialized, Sealed};
+use solana_program::pubkey::Pubkey;
+
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct CounterState {
+    pub discriminator: String,
+    pub is_initialized: bool,
+    pub authority: Pubkey,
+    pub count: u64,
+}
+
+impl Sealed for CounterState {}
+
+impl IsInitialized for CounterState {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+impl CounterState {
+    pub const DISCRIMINATOR: &'static str = "counter";
+    pub const SEED: &'static str = "counter";
+
+    pub fn get_account_size() -> usize {
+        (4 + CounterState::DISCRIMINATOR.len()) + 1 + 32 + 8
+    }
+}
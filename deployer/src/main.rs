@@ -0,0 +1,378 @@
+//! Sets up the movie review program and its rewards distributor on a cluster
+//! and records the accounts it created in a TOML manifest, or checks that an
+//! existing deployment still matches one.
+//!
+//! `deploy` runs each step only if its account is missing, so it can be
+//! rerun after a failure or against a partial deployment:
+//!
+//! 1. the review program's config PDA, with the signer as authority
+//! 2. the distributor, which creates the reward mint and treasury
+//! 3. the reward mint's metadata
+//! 4. the distributor as the config's tip program
+//!
+//! Usage:
+//! deployer deploy --review-program KEY --distributor-program KEY [--keypair PATH]
+//! [--rpc-url URL] [--manifest PATH] [--weekly-budget N] [--symbol S] [--uri URI]
+//!
+//! deployer verify [--rpc-url URL] [--manifest PATH]
+
+use std::{collections::HashMap, path::PathBuf, process};
+
+use movie_review::{
+    client::instructions as review_instructions,
+    decoder::{decode_account, MovieReviewAccount},
+    state::ProgramConfig,
+};
+use rewards_distributor::{
+    client::instructions as distributor_instructions, instruction::RewardMintConfig,
+    state::DistributorState,
+};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::{
+    account::Account,
+    borsh1::try_from_slice_unchecked,
+    commitment_config::CommitmentConfig,
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signature::{read_keypair_file, Keypair},
+    signer::Signer,
+    transaction::Transaction,
+};
+
+mod manifest;
+
+use manifest::{parse_key, Manifest};
+
+/// Flags after the subcommand, by name without the leading dashes.
+fn parse_flags(mut args: impl Iterator<Item = String>) -> Result<HashMap<String, String>, String> {
+    let mut flags = HashMap::new();
+    while let Some(flag) = args.next() {
+        let name = flag
+            .strip_prefix("--")
+            .ok_or_else(|| format!("unexpected argument {}", flag))?;
+        let value = args
+            .next()
+            .ok_or_else(|| format!("missing value for {}", flag))?;
+        flags.insert(name.to_string(), value);
+    }
+    Ok(flags)
+}
+
+struct Deployer {
+    rpc: RpcClient,
+    authority: Keypair,
+}
+
+impl Deployer {
+    async fn account(&self, address: &Pubkey) -> Result<Option<Account>, String> {
+        Ok(self
+            .rpc
+            .get_account_with_commitment(address, self.rpc.commitment())
+            .await
+            .map_err(|e| e.to_string())?
+            .value)
+    }
+
+    async fn send(&self, step: &str, instruction: Instruction) -> Result<(), String> {
+        let blockhash = self
+            .rpc
+            .get_latest_blockhash()
+            .await
+            .map_err(|e| e.to_string())?;
+        let transaction = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&self.authority.pubkey()),
+            &[&self.authority],
+            blockhash,
+        );
+        let signature = self
+            .rpc
+            .send_and_confirm_transaction(&transaction)
+            .await
+            .map_err(|e| format!("{} failed: {}", step, e))?;
+        println!("{}: {}", step, signature);
+        Ok(())
+    }
+}
+
+/// The account at `address`, or a mismatch if it is missing or not owned by
+/// `owner`. Only RPC failures are errors.
+async fn fetch_owned(
+    rpc: &RpcClient,
+    name: &str,
+    address: Pubkey,
+    owner: Pubkey,
+) -> Result<Result<Account, String>, String> {
+    let account = rpc
+        .get_account_with_commitment(&address, rpc.commitment())
+        .await
+        .map_err(|e| e.to_string())?
+        .value;
+    Ok(match account {
+        Some(account) if account.owner == owner => Ok(account),
+        Some(account) => Err(format!("{} is owned by {}", name, account.owner)),
+        None => Err(format!("{} {} does not exist", name, address)),
+    })
+}
+
+fn read_config(account: &Account) -> Result<ProgramConfig, String> {
+    match decode_account(&account.data) {
+        Ok(MovieReviewAccount::Config(config)) => Ok(*config),
+        _ => Err("config account does not hold a config".to_string()),
+    }
+}
+
+async fn deploy(flags: HashMap<String, String>) -> Result<(), String> {
+    let flag = |name: &str| {
+        flags
+            .get(name)
+            .ok_or_else(|| format!("--{} is required", name))
+    };
+    let review_program = parse_key("--review-program", flag("review-program")?)?;
+    let distributor_program = parse_key("--distributor-program", flag("distributor-program")?)?;
+    let keypair_path = flags.get("keypair").cloned().unwrap_or_else(|| {
+        let home = std::env::var("HOME").unwrap_or_default();
+        format!("{}/.config/solana/id.json", home)
+    });
+    let authority = read_keypair_file(&keypair_path)
+        .map_err(|e| format!("cannot read {}: {}", keypair_path, e))?;
+    let weekly_budget = match flags.get("weekly-budget") {
+        Some(value) => value
+            .parse()
+            .map_err(|e| format!("invalid --weekly-budget: {}", e))?,
+        None => 1_000_000_000,
+    };
+    let symbol = flags.get("symbol").map_or("MRT", String::as_str);
+    let uri = flags.get("uri").map_or("", String::as_str);
+
+    let manifest = Manifest::derive(&authority.pubkey(), &review_program, &distributor_program);
+    let deployer = Deployer {
+        rpc: rpc_client(&flags),
+        authority,
+    };
+    let authority = deployer.authority.pubkey();
+
+    let config_address = parse_key("config", &manifest.accounts.config)?;
+    match deployer.account(&config_address).await? {
+        Some(account) => {
+            let config = read_config(&account)?;
+            if config.authority != authority {
+                return Err(format!("config is owned by {}", config.authority));
+            }
+            println!("config: exists");
+        }
+        None => {
+            deployer
+                .send(
+                    "config",
+                    review_instructions::initialize_config(
+                        &review_program,
+                        &authority,
+                        false,
+                        false,
+                    ),
+                )
+                .await?
+        }
+    }
+
+    let distributor_address = parse_key("distributor", &manifest.accounts.distributor)?;
+    if deployer.account(&distributor_address).await?.is_some() {
+        println!("distributor: exists");
+    } else {
+        let reward_mint = parse_key("reward_mint", &manifest.accounts.reward_mint)?;
+        deployer
+            .send(
+                "distributor",
+                distributor_instructions::initialize(
+                    &distributor_program,
+                    &authority,
+                    &review_program,
+                    weekly_budget,
+                    &reward_mint,
+                    &spl_token_2022::ID,
+                    Some(RewardMintConfig {
+                        transfer_hook_program: None,
+                        interest_rate: None,
+                        confidential_transfers: false,
+                    }),
+                ),
+            )
+            .await?;
+    }
+
+    let metadata_address = parse_key("reward_metadata", &manifest.accounts.reward_metadata)?;
+    if deployer.account(&metadata_address).await?.is_some() {
+        println!("reward metadata: exists");
+    } else {
+        deployer
+            .send(
+                "reward metadata",
+                distributor_instructions::create_reward_metadata(
+                    &distributor_program,
+                    &authority,
+                    symbol,
+                    uri,
+                ),
+            )
+            .await?;
+    }
+
+    let config = match deployer.account(&config_address).await? {
+        Some(account) => read_config(&account)?,
+        None => return Err("config disappeared".to_string()),
+    };
+    if config.tip_program == distributor_program {
+        println!("tip program: set");
+    } else {
+        deployer
+            .send(
+                "tip program",
+                review_instructions::set_tip_program(
+                    &review_program,
+                    &authority,
+                    &distributor_program,
+                ),
+            )
+            .await?;
+    }
+
+    let path = manifest_path(&flags);
+    manifest.write(&path)?;
+    println!("wrote {}", path.display());
+    Ok(())
+}
+
+async fn verify(flags: HashMap<String, String>) -> Result<(), String> {
+    let manifest = Manifest::read(&manifest_path(&flags))?;
+    let authority = parse_key("authority", &manifest.authority)?;
+    let review_program = parse_key("programs.movie_review", &manifest.programs.movie_review)?;
+    let distributor_program = parse_key(
+        "programs.rewards_distributor",
+        &manifest.programs.rewards_distributor,
+    )?;
+    if Manifest::derive(&authority, &review_program, &distributor_program) != manifest {
+        return Err("manifest accounts are not the PDAs of its programs".to_string());
+    }
+
+    let rpc = rpc_client(&flags);
+    let mut mismatches = Vec::new();
+    for (name, program) in [
+        ("movie_review", review_program),
+        ("rewards_distributor", distributor_program),
+    ] {
+        match fetch_owned(&rpc, name, program, solana_sdk::bpf_loader_upgradeable::ID).await? {
+            Ok(account) if account.executable => {}
+            Ok(_) => mismatches.push(format!("{} is not executable", name)),
+            Err(e) => mismatches.push(e),
+        }
+    }
+
+    let config_address = parse_key("config", &manifest.accounts.config)?;
+    match fetch_owned(&rpc, "config", config_address, review_program).await? {
+        Ok(account) => {
+            let config = read_config(&account)?;
+            if config.authority != authority {
+                mismatches.push(format!("config authority is {}", config.authority));
+            }
+            if config.tip_program != distributor_program {
+                mismatches.push(format!("config tip program is {}", config.tip_program));
+            }
+        }
+        Err(e) => mismatches.push(e),
+    }
+
+    let reward_mint = parse_key("reward_mint", &manifest.accounts.reward_mint)?;
+    let distributor_address = parse_key("distributor", &manifest.accounts.distributor)?;
+    match fetch_owned(
+        &rpc,
+        "distributor",
+        distributor_address,
+        distributor_program,
+    )
+    .await?
+    {
+        Ok(account) => {
+            let distributor = try_from_slice_unchecked::<DistributorState>(&account.data)
+                .map_err(|e| format!("invalid distributor: {}", e))?;
+            if distributor.authority != authority {
+                mismatches.push(format!(
+                    "distributor authority is {}",
+                    distributor.authority
+                ));
+            }
+            if distributor.review_program != review_program {
+                mismatches.push(format!(
+                    "distributor review program is {}",
+                    distributor.review_program
+                ));
+            }
+            if distributor.reward_mint != reward_mint {
+                mismatches.push(format!(
+                    "distributor reward mint is {}",
+                    distributor.reward_mint
+                ));
+            }
+        }
+        Err(e) => mismatches.push(e),
+    }
+
+    let treasury = parse_key("treasury", &manifest.accounts.treasury)?;
+    let metadata = parse_key("reward_metadata", &manifest.accounts.reward_metadata)?;
+    for (name, address, owner) in [
+        ("reward_mint", reward_mint, spl_token_2022::ID),
+        ("treasury", treasury, spl_token_2022::ID),
+        ("reward_metadata", metadata, mpl_token_metadata::ID),
+    ] {
+        if let Err(e) = fetch_owned(&rpc, name, address, owner).await? {
+            mismatches.push(e);
+        }
+    }
+
+    if mismatches.is_empty() {
+        println!("deployment matches the manifest");
+        Ok(())
+    } else {
+        Err(mismatches.join("\n"))
+    }
+}
+
+fn rpc_client(flags: &HashMap<String, String>) -> RpcClient {
+    let url = flags
+        .get("rpc-url")
+        .cloned()
+        .unwrap_or_else(|| "https://api.devnet.solana.com".to_string());
+    RpcClient::new_with_commitment(url, CommitmentConfig::confirmed())
+}
+
+fn manifest_path(flags: &HashMap<String, String>) -> PathBuf {
+    PathBuf::from(
+        flags
+            .get("manifest")
+            .map_or("deployment.toml", String::as_str),
+    )
+}
+
+#[tokio::main]
+async fn main() {
+    let mut args = std::env::args().skip(1);
+    let command = args.next();
+    let result = match parse_flags(args) {
+        Ok(flags) => match command.as_deref() {
+            Some("deploy") => deploy(flags).await,
+            Some("verify") => verify(flags).await,
+            _ => {
+                eprintln!("usage: deployer deploy|verify [FLAGS]");
+                process::exit(2);
+            }
+        },
+        Err(e) => {
+            eprintln!("error: {}", e);
+            process::exit(2);
+        }
+    };
+    if let Err(e) = result {
+        eprintln!("error: {}", e);
+        process::exit(1);
+    }
+}
@@ -0,0 +1,77 @@
+//! The TOML manifest a deployment is recorded in. Every account is a PDA of
+//! the two program ids, so `Manifest::derive` always produces the same
+//! manifest for the same programs and authority.
+
+use std::{fs, path::Path};
+
+use movie_review::client::pda::find_config_address;
+use mpl_token_metadata::accounts::Metadata;
+use rewards_distributor::client::pda::{
+    find_distributor_address, find_reward_mint_address, find_treasury_address,
+};
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+
+#[derive(Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Manifest {
+    pub authority: String,
+    pub programs: Programs,
+    pub accounts: Accounts,
+}
+
+#[derive(Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Programs {
+    pub movie_review: String,
+    pub rewards_distributor: String,
+}
+
+#[derive(Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Accounts {
+    pub config: String,
+    pub distributor: String,
+    pub reward_mint: String,
+    pub reward_metadata: String,
+    pub treasury: String,
+}
+
+impl Manifest {
+    pub fn derive(
+        authority: &Pubkey,
+        review_program: &Pubkey,
+        distributor_program: &Pubkey,
+    ) -> Self {
+        let reward_mint = find_reward_mint_address(distributor_program).0;
+        Self {
+            authority: authority.to_string(),
+            programs: Programs {
+                movie_review: review_program.to_string(),
+                rewards_distributor: distributor_program.to_string(),
+            },
+            accounts: Accounts {
+                config: find_config_address(review_program).0.to_string(),
+                distributor: find_distributor_address(distributor_program).0.to_string(),
+                reward_mint: reward_mint.to_string(),
+                reward_metadata: Metadata::find_pda(&reward_mint).0.to_string(),
+                treasury: find_treasury_address(distributor_program).0.to_string(),
+            },
+        }
+    }
+
+    pub fn read(path: &Path) -> Result<Self, String> {
+        let contents = fs::read_to_string(path)
+            .map_err(|e| format!("cannot read {}: {}", path.display(), e))?;
+        toml::from_str(&contents).map_err(|e| format!("invalid {}: {}", path.display(), e))
+    }
+
+    pub fn write(&self, path: &Path) -> Result<(), String> {
+        let contents = toml::to_string_pretty(self).map_err(|e| e.to_string())?;
+        fs::write(path, contents).map_err(|e| format!("cannot write {}: {}", path.display(), e))
+    }
+}
+
+/// Parses a key from a flag or manifest field, naming it if it is invalid.
+pub fn parse_key(name: &str, value: &str) -> Result<Pubkey, String> {
+    value
+        .parse()
+        .map_err(|_| format!("{} is not a valid key: {}", name, value))
+}
@@ -0,0 +1,43 @@
+use num_derive::FromPrimitive;
+use num_traits::FromPrimitive;
+use solana_program::{
+    decode_error::DecodeError,
+    msg,
+    program_error::{PrintProgramError, ProgramError},
+};
+use thiserror::Error;
+
+#[derive(Clone, Copy, Debug, Eq, Error, FromPrimitive, PartialEq)]
+pub enum EscrowError {
+    #[error("Account not initialized yet")]
+    UninitializedAccount,
+    #[error("PDA derived does not equal PDA passed in")]
+    InvalidPDA,
+    #[error("Signer is not the escrow maker")]
+    InvalidMaker,
+    #[error("Token account mint does not match the escrow")]
+    InvalidMint,
+    #[error("Escrow amounts must be greater than zero")]
+    InvalidAmount,
+}
+
+impl From<EscrowError> for ProgramError {
+    fn from(e: EscrowError) -> Self {
+        ProgramError::Custom(e as u32)
+    }
+}
+
+impl<T> DecodeError<T> for EscrowError {
+    fn type_of() -> &'static str {
+        "EscrowError"
+    }
+}
+
+impl PrintProgramError for EscrowError {
+    fn print<E>(&self)
+    where
+        E: 'static + std::error::Error + DecodeError<E> + PrintProgramError + FromPrimitive,
+    {
+        msg!(&self.to_string());
+    }
+}
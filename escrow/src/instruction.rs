@@ -0,0 +1,41 @@
+use borsh::BorshDeserialize;
+use solana_program::program_error::ProgramError;
+
+pub enum EscrowInstruction {
+    MakeOffer {
+        id: u64,
+        offered_amount: u64,
+        wanted_amount: u64,
+    },
+    TakeOffer,
+    CancelOffer,
+}
+
+#[derive(BorshDeserialize)]
+struct MakeOfferPayload {
+    id: u64,
+    offered_amount: u64,
+    wanted_amount: u64,
+}
+
+impl EscrowInstruction {
+    pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
+        let (&variant, rest) = input
+            .split_first()
+            .ok_or(ProgramError::InvalidInstructionData)?;
+        Ok(match variant {
+            0 => {
+                let payload = MakeOfferPayload::try_from_slice(rest)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                Self::MakeOffer {
+                    id: payload.id,
+                    offered_amount: payload.offered_amount,
+                    wanted_amount: payload.wanted_amount,
+                }
+            }
+            1 => Self::TakeOffer,
+            2 => Self::CancelOffer,
+            _ => return Err(ProgramError::InvalidInstructionData),
+        })
+    }
+}
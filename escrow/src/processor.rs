@@ -0,0 +1,349 @@
+use borsh::BorshSerialize;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    borsh1::try_from_slice_unchecked,
+    entrypoint::ProgramResult,
+    msg,
+    program::{invoke, invoke_signed},
+    program_error::ProgramError,
+    program_pack::Pack,
+    pubkey::Pubkey,
+};
+use soldev_program_errors::CommonError;
+use soldev_program_utils::{
+    account::{assert_not_closed, close_account, create_pda_account},
+    assert::{
+        assert_initialized, assert_owned_by, assert_program_id, assert_signer, assert_uninitialized,
+    },
+    pda::{assert_pda, assert_pda_with_bump},
+};
+use spl_token::state::Account as TokenAccount;
+
+use crate::error::EscrowError;
+use crate::instruction::EscrowInstruction;
+use crate::state::EscrowState;
+
+pub fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    let instruction = EscrowInstruction::unpack(instruction_data)?;
+
+    match instruction {
+        EscrowInstruction::MakeOffer {
+            id,
+            offered_amount,
+            wanted_amount,
+        } => {
+            make_offer(program_id, accounts, id, offered_amount, wanted_amount)?;
+        }
+        EscrowInstruction::TakeOffer => {
+            take_offer(program_id, accounts)?;
+        }
+        EscrowInstruction::CancelOffer => {
+            cancel_offer(program_id, accounts)?;
+        }
+    }
+    Ok(())
+}
+
+pub fn make_offer(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    id: u64,
+    offered_amount: u64,
+    wanted_amount: u64,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let maker = next_account_info(account_info_iter)?;
+    let pda_escrow = next_account_info(account_info_iter)?;
+    let pda_vault = next_account_info(account_info_iter)?;
+    let mint_a = next_account_info(account_info_iter)?;
+    let mint_b = next_account_info(account_info_iter)?;
+    let maker_token_a = next_account_info(account_info_iter)?;
+    let token_program = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+
+    // Check if the instruction is signed
+    assert_signer(maker)?;
+    assert_program_id(token_program, &spl_token::ID)?;
+
+    if offered_amount == 0 || wanted_amount == 0 {
+        msg!("Escrow amounts must be greater than zero");
+        return Err(EscrowError::InvalidAmount.into());
+    }
+
+    let escrow_bump = assert_pda(
+        pda_escrow,
+        &[
+            EscrowState::SEED.as_bytes(),
+            maker.key.as_ref(),
+            id.to_le_bytes().as_ref(),
+        ],
+        program_id,
+        EscrowError::InvalidPDA,
+    )?;
+
+    let vault_bump = assert_pda(
+        pda_vault,
+        &[EscrowState::VAULT_SEED.as_bytes(), pda_escrow.key.as_ref()],
+        program_id,
+        EscrowError::InvalidPDA,
+    )?;
+
+    msg!("creating escrow state account");
+    create_pda_account(
+        maker,
+        pda_escrow,
+        system_program,
+        EscrowState::get_account_size(),
+        program_id,
+        &[
+            EscrowState::SEED.as_bytes(),
+            maker.key.as_ref(),
+            id.to_le_bytes().as_ref(),
+            &[escrow_bump],
+        ],
+    )?;
+
+    // The vault is a token account at a PDA whose token authority is the
+    // escrow PDA, so only this program can move the deposited tokens
+    msg!("creating vault token account");
+    create_pda_account(
+        maker,
+        pda_vault,
+        system_program,
+        TokenAccount::LEN,
+        token_program.key,
+        &[
+            EscrowState::VAULT_SEED.as_bytes(),
+            pda_escrow.key.as_ref(),
+            &[vault_bump],
+        ],
+    )?;
+    invoke(
+        &spl_token::instruction::initialize_account3(
+            token_program.key,
+            pda_vault.key,
+            mint_a.key,
+            pda_escrow.key,
+        )?,
+        &[pda_vault.clone(), mint_a.clone()],
+    )?;
+
+    msg!("depositing {} tokens into vault", offered_amount);
+    invoke(
+        &spl_token::instruction::transfer(
+            token_program.key,
+            maker_token_a.key,
+            pda_vault.key,
+            maker.key,
+            &[],
+            offered_amount,
+        )?,
+        &[
+            maker_token_a.clone(),
+            pda_vault.clone(),
+            maker.clone(),
+            token_program.clone(),
+        ],
+    )?;
+
+    let mut escrow_data = try_from_slice_unchecked::<EscrowState>(&pda_escrow.data.borrow())?;
+
+    assert_uninitialized(&escrow_data, ProgramError::AccountAlreadyInitialized)?;
+
+    escrow_data.discriminator = EscrowState::DISCRIMINATOR.to_string();
+    escrow_data.is_initialized = true;
+    escrow_data.maker = *maker.key;
+    escrow_data.id = id;
+    escrow_data.mint_a = *mint_a.key;
+    escrow_data.mint_b = *mint_b.key;
+    escrow_data.offered_amount = offered_amount;
+    escrow_data.wanted_amount = wanted_amount;
+    escrow_data.bump = escrow_bump;
+    escrow_data.vault_bump = vault_bump;
+
+    escrow_data.serialize(&mut *pda_escrow.data.borrow_mut())?;
+    msg!("escrow created at: {}", pda_escrow.key);
+
+    Ok(())
+}
+
+pub fn take_offer(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let taker = next_account_info(account_info_iter)?;
+    let maker = next_account_info(account_info_iter)?;
+    let pda_escrow = next_account_info(account_info_iter)?;
+    let pda_vault = next_account_info(account_info_iter)?;
+    let taker_token_a = next_account_info(account_info_iter)?;
+    let taker_token_b = next_account_info(account_info_iter)?;
+    let maker_token_b = next_account_info(account_info_iter)?;
+    let token_program = next_account_info(account_info_iter)?;
+
+    assert_signer(taker)?;
+
+    let escrow_data = load_escrow(program_id, pda_escrow, pda_vault, token_program)?;
+
+    if escrow_data.maker != *maker.key {
+        msg!("Maker account does not match the escrow");
+        return Err(EscrowError::InvalidMaker.into());
+    }
+
+    // The maker must receive the mint they asked for
+    let maker_token_b_data = TokenAccount::unpack(&maker_token_b.data.borrow())?;
+    if maker_token_b_data.mint != escrow_data.mint_b || maker_token_b_data.owner != *maker.key {
+        msg!("Maker token account does not match the escrow");
+        return Err(EscrowError::InvalidMint.into());
+    }
+
+    msg!("sending {} tokens to maker", escrow_data.wanted_amount);
+    invoke(
+        &spl_token::instruction::transfer(
+            token_program.key,
+            taker_token_b.key,
+            maker_token_b.key,
+            taker.key,
+            &[],
+            escrow_data.wanted_amount,
+        )?,
+        &[
+            taker_token_b.clone(),
+            maker_token_b.clone(),
+            taker.clone(),
+            token_program.clone(),
+        ],
+    )?;
+
+    release_vault(
+        pda_escrow,
+        pda_vault,
+        taker_token_a,
+        maker,
+        token_program,
+        &escrow_data,
+    )?;
+    close_escrow(pda_escrow, maker)?;
+
+    Ok(())
+}
+
+pub fn cancel_offer(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let maker = next_account_info(account_info_iter)?;
+    let pda_escrow = next_account_info(account_info_iter)?;
+    let pda_vault = next_account_info(account_info_iter)?;
+    let maker_token_a = next_account_info(account_info_iter)?;
+    let token_program = next_account_info(account_info_iter)?;
+
+    assert_signer(maker)?;
+
+    let escrow_data = load_escrow(program_id, pda_escrow, pda_vault, token_program)?;
+
+    if escrow_data.maker != *maker.key {
+        msg!("Signer is not the escrow maker");
+        return Err(EscrowError::InvalidMaker.into());
+    }
+
+    release_vault(
+        pda_escrow,
+        pda_vault,
+        maker_token_a,
+        maker,
+        token_program,
+        &escrow_data,
+    )?;
+    close_escrow(pda_escrow, maker)?;
+
+    Ok(())
+}
+
+fn load_escrow(
+    program_id: &Pubkey,
+    pda_escrow: &AccountInfo,
+    pda_vault: &AccountInfo,
+    token_program: &AccountInfo,
+) -> Result<EscrowState, ProgramError> {
+    assert_owned_by(pda_escrow, program_id)?;
+    assert_not_closed(pda_escrow, CommonError::AccountClosed)?;
+    assert_program_id(token_program, &spl_token::ID)?;
+
+    let escrow_data = try_from_slice_unchecked::<EscrowState>(&pda_escrow.data.borrow())?;
+
+    assert_initialized(&escrow_data, EscrowError::UninitializedAccount)?;
+
+    assert_pda_with_bump(
+        pda_vault,
+        &[EscrowState::VAULT_SEED.as_bytes(), pda_escrow.key.as_ref()],
+        escrow_data.vault_bump,
+        program_id,
+        EscrowError::InvalidPDA,
+    )?;
+
+    Ok(escrow_data)
+}
+
+/// Moves the vault balance to `destination` and closes the vault, returning
+/// its rent to the maker. Both CPIs are signed by the escrow PDA.
+fn release_vault<'a>(
+    pda_escrow: &AccountInfo<'a>,
+    pda_vault: &AccountInfo<'a>,
+    destination: &AccountInfo<'a>,
+    maker: &AccountInfo<'a>,
+    token_program: &AccountInfo<'a>,
+    escrow_data: &EscrowState,
+) -> ProgramResult {
+    let escrow_seeds: &[&[u8]] = &[
+        EscrowState::SEED.as_bytes(),
+        escrow_data.maker.as_ref(),
+        &escrow_data.id.to_le_bytes(),
+        &[escrow_data.bump],
+    ];
+
+    let vault_amount = TokenAccount::unpack(&pda_vault.data.borrow())?.amount;
+
+    msg!("releasing {} tokens from vault", vault_amount);
+    invoke_signed(
+        &spl_token::instruction::transfer(
+            token_program.key,
+            pda_vault.key,
+            destination.key,
+            pda_escrow.key,
+            &[],
+            vault_amount,
+        )?,
+        &[
+            pda_vault.clone(),
+            destination.clone(),
+            pda_escrow.clone(),
+            token_program.clone(),
+        ],
+        &[escrow_seeds],
+    )?;
+
+    msg!("closing vault");
+    invoke_signed(
+        &spl_token::instruction::close_account(
+            token_program.key,
+            pda_vault.key,
+            maker.key,
+            pda_escrow.key,
+            &[],
+        )?,
+        &[
+            pda_vault.clone(),
+            maker.clone(),
+            pda_escrow.clone(),
+            token_program.clone(),
+        ],
+        &[escrow_seeds],
+    )?;
+
+    Ok(())
+}
+
+fn close_escrow(pda_escrow: &AccountInfo, maker: &AccountInfo) -> ProgramResult {
+    msg!("closing escrow account");
+    close_account(pda_escrow, maker)
+}
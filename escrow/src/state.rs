@@ -0,0 +1,35 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::program_pack::{IsInitialized, Sealed};
+use solana_program::pubkey::Pubkey;
+
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct EscrowState {
+    pub discriminator: String,
+    pub is_initialized: bool,
+    pub maker: Pubkey,
+    pub id: u64,
+    pub mint_a: Pubkey,
+    pub mint_b: Pubkey,
+    pub offered_amount: u64,
+    pub wanted_amount: u64,
+    pub bump: u8,
+    pub vault_bump: u8,
+}
+
+impl Sealed for EscrowState {}
+
+impl IsInitialized for EscrowState {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+impl EscrowState {
+    pub const DISCRIMINATOR: &'static str = "escrow";
+    pub const SEED: &'static str = "escrow";
+    pub const VAULT_SEED: &'static str = "vault";
+
+    pub fn get_account_size() -> usize {
+        (4 + EscrowState::DISCRIMINATOR.len()) + 1 + 32 + 8 + 32 + 32 + 8 + 8 + 1 + 1
+    }
+}
@@ -0,0 +1,252 @@
+//! Seeds an in-process validator with reviewers, reviews and comments and
+//! dumps every resulting account as a `solana-test-validator --account` JSON
+//! file, so frontend lessons can start from realistic data.
+//!
+//! The same `--seed` always produces the same keys and contents.
+//!
+//! Usage: fixtures [--seed N] [--reviewers N] [--reviews N] [--comments N] [--out DIR]
+
+use std::{collections::BTreeMap, fs, path::PathBuf, process};
+
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use movie_review::client::{
+    instructions,
+    pda::{find_comment_address, find_comment_counter_address, find_review_address},
+};
+use rand::{seq::SliceRandom, Rng, RngCore, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use serde_json::json;
+use solana_program_test::{processor, BanksClient, ProgramTest};
+use solana_sdk::{
+    account::Account,
+    instruction::Instruction,
+    native_token::LAMPORTS_PER_SOL,
+    pubkey::Pubkey,
+    signature::{keypair_from_seed, Keypair},
+    signer::Signer,
+    transaction::Transaction,
+};
+
+const WORDS: &[&str] = &[
+    "gripping",
+    "slow",
+    "beautiful",
+    "confusing",
+    "heartfelt",
+    "overlong",
+    "clever",
+    "loud",
+    "charming",
+    "forgettable",
+    "bold",
+    "tense",
+    "funny",
+    "bleak",
+    "stylish",
+    "uneven",
+];
+
+struct Options {
+    seed: u64,
+    reviewers: usize,
+    reviews: usize,
+    comments: usize,
+    out: PathBuf,
+}
+
+fn parse_options() -> Result<Options, String> {
+    let mut options = Options {
+        seed: 42,
+        reviewers: 5,
+        reviews: 20,
+        comments: 40,
+        out: PathBuf::from("fixtures-out"),
+    };
+    let mut args = std::env::args().skip(1);
+    while let Some(flag) = args.next() {
+        let value = args
+            .next()
+            .ok_or_else(|| format!("missing value for {}", flag))?;
+        let parse_err = |e: std::num::ParseIntError| format!("invalid {}: {}", flag, e);
+        match flag.as_str() {
+            "--seed" => options.seed = value.parse().map_err(parse_err)?,
+            "--reviewers" => options.reviewers = value.parse().map_err(parse_err)?,
+            "--reviews" => options.reviews = value.parse().map_err(parse_err)?,
+            "--comments" => options.comments = value.parse().map_err(parse_err)?,
+            "--out" => options.out = PathBuf::from(value),
+            _ => return Err(format!("unknown flag {}", flag)),
+        }
+    }
+    if options.reviewers == 0 {
+        return Err("--reviewers must be at least 1".to_string());
+    }
+    Ok(options)
+}
+
+fn keypair_from_rng(rng: &mut ChaCha8Rng) -> Keypair {
+    let mut seed = [0u8; 32];
+    rng.fill_bytes(&mut seed);
+    keypair_from_seed(&seed).unwrap()
+}
+
+fn sentence(rng: &mut ChaCha8Rng, words: usize) -> String {
+    (0..words)
+        .map(|_| *WORDS.choose(rng).unwrap())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+async fn send(
+    banks_client: &mut BanksClient,
+    payer: &Keypair,
+    signer: &Keypair,
+    instruction: Instruction,
+) -> Result<(), String> {
+    let blockhash = banks_client
+        .get_latest_blockhash()
+        .await
+        .map_err(|e| e.to_string())?;
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction],
+        Some(&payer.pubkey()),
+        &[payer, signer],
+        blockhash,
+    );
+    banks_client
+        .process_transaction(transaction)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+fn account_json(address: &Pubkey, account: &Account) -> serde_json::Value {
+    json!({
+        "pubkey": address.to_string(),
+        "account": {
+            "lamports": account.lamports,
+            "data": [BASE64.encode(&account.data), "base64"],
+            "owner": account.owner.to_string(),
+            "executable": account.executable,
+            "rentEpoch": 0,
+            "space": account.data.len(),
+        }
+    })
+}
+
+async fn run(options: Options) -> Result<(), String> {
+    let mut rng = ChaCha8Rng::seed_from_u64(options.seed);
+    let program_id = keypair_from_rng(&mut rng).pubkey();
+
+    let mut program_test = ProgramTest::new(
+        "movie_review",
+        program_id,
+        processor!(movie_review::processor::process_instruction),
+    );
+    let reviewers: Vec<Keypair> = (0..options.reviewers)
+        .map(|_| keypair_from_rng(&mut rng))
+        .collect();
+    for reviewer in &reviewers {
+        program_test.add_account(
+            reviewer.pubkey(),
+            Account::new(100 * LAMPORTS_PER_SOL, 0, &solana_sdk::system_program::ID),
+        );
+    }
+    let (mut banks_client, payer, _) = program_test.start().await;
+
+    // Ordered so the dump is byte-for-byte reproducible
+    let mut addresses = BTreeMap::new();
+    for reviewer in &reviewers {
+        addresses.insert(reviewer.pubkey(), "reviewer");
+    }
+
+    let mut reviews = Vec::new();
+    for i in 0..options.reviews {
+        let reviewer = reviewers.choose(&mut rng).unwrap();
+        let title = format!("Movie #{}", i);
+        let rating = rng.gen_range(1..=5);
+        let description_len = rng.gen_range(3..12);
+        let description = sentence(&mut rng, description_len);
+
+        let instruction = instructions::add_movie_review(
+            &program_id,
+            &reviewer.pubkey(),
+            &title,
+            rating,
+            &description,
+        );
+        send(&mut banks_client, &payer, reviewer, instruction).await?;
+
+        let (review, _) = find_review_address(&program_id, &reviewer.pubkey(), &title);
+        let (counter, _) = find_comment_counter_address(&program_id, &review);
+        addresses.insert(review, "review");
+        addresses.insert(counter, "comment_counter");
+        reviews.push((review, reviewer.pubkey(), 0u64));
+    }
+
+    if !reviews.is_empty() {
+        for _ in 0..options.comments {
+            let commenter = reviewers.choose(&mut rng).unwrap();
+            let (review, reviewer, count) = reviews.choose_mut(&mut rng).unwrap();
+            let comment_len = rng.gen_range(2..8);
+            let comment = sentence(&mut rng, comment_len);
+
+            let instruction = instructions::add_comment(
+                &program_id,
+                &commenter.pubkey(),
+                review,
+                reviewer,
+                *count,
+                &comment,
+                None,
+            );
+            send(&mut banks_client, &payer, commenter, instruction).await?;
+
+            let (comment_address, _) = find_comment_address(&program_id, review, *count);
+            addresses.insert(comment_address, "comment");
+            *count += 1;
+        }
+    }
+
+    fs::create_dir_all(&options.out).map_err(|e| e.to_string())?;
+    let mut manifest = Vec::new();
+    for (address, kind) in &addresses {
+        let account = banks_client
+            .get_account(*address)
+            .await
+            .map_err(|e| e.to_string())?
+            .ok_or_else(|| format!("account {} missing after seeding", address))?;
+        let path = options.out.join(format!("{}.json", address));
+        let contents = serde_json::to_string_pretty(&account_json(address, &account)).unwrap();
+        fs::write(&path, contents).map_err(|e| e.to_string())?;
+        manifest.push(json!({ "pubkey": address.to_string(), "kind": kind }));
+    }
+    let manifest = json!({
+        "seed": options.seed,
+        "program_id": program_id.to_string(),
+        "accounts": manifest,
+    });
+    fs::write(
+        options.out.join("manifest.json"),
+        serde_json::to_string_pretty(&manifest).unwrap(),
+    )
+    .map_err(|e| e.to_string())?;
+
+    println!(
+        "wrote {} accounts for program {} to {}",
+        addresses.len(),
+        program_id,
+        options.out.display()
+    );
+    Ok(())
+}
+
+#[tokio::main]
+async fn main() {
+    let options = parse_options().unwrap_or_else(|e| {
+        eprintln!("error: {}", e);
+        process::exit(2);
+    });
+    if let Err(e) = run(options).await {
+        eprintln!("error: {}", e);
+        process::exit(1);
+    }
+}
@@ -0,0 +1,343 @@
+//! Drives concurrent reviewers and commenters against a local validator and
+//! reports confirmation latency and how often comments lost the race for a
+//! review's counter.
+//!
+//! Each reviewer writes one review up front, then reviewers keep writing new
+//! reviews and commenters keep commenting at their configured rates. With
+//! `--pattern hot` every comment targets the first review, so commenters
+//! contend for a single counter; with `--pattern spread` they pick a review at
+//! random. A comment built against a counter another transaction already
+//! advanced fails its PDA check and is rebuilt from the fresh counter, which
+//! is counted as a retry.
+//!
+//! Usage: loadtest --program-id KEY [--rpc-url URL] [--reviewers N]
+//! [--commenters N] [--review-rate TX/S] [--comment-rate TX/S]
+//! [--duration SECS] [--pattern hot|spread] [--max-retries N]
+
+use std::{
+    process,
+    str::FromStr,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use movie_review::{
+    client::{instructions, pda::find_review_address, stats::fetch_comment_counter},
+    error::ReviewError,
+};
+use rand::{seq::SliceRandom, thread_rng};
+use solana_client::{client_error::ClientError, nonblocking::rpc_client::RpcClient};
+use solana_sdk::{
+    commitment_config::CommitmentConfig,
+    instruction::{Instruction, InstructionError},
+    native_token::LAMPORTS_PER_SOL,
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    transaction::{Transaction, TransactionError},
+};
+use tokio::time::{interval, sleep, MissedTickBehavior};
+
+#[derive(Clone, Copy, PartialEq)]
+enum Pattern {
+    Hot,
+    Spread,
+}
+
+struct Options {
+    program_id: Pubkey,
+    rpc_url: String,
+    reviewers: usize,
+    commenters: usize,
+    review_rate: f64,
+    comment_rate: f64,
+    duration: Duration,
+    pattern: Pattern,
+    max_retries: u32,
+}
+
+fn parse_options() -> Result<Options, String> {
+    let mut program_id = None;
+    let mut options = Options {
+        program_id: Pubkey::default(),
+        rpc_url: "http://127.0.0.1:8899".to_string(),
+        reviewers: 4,
+        commenters: 16,
+        review_rate: 0.2,
+        comment_rate: 1.0,
+        duration: Duration::from_secs(30),
+        pattern: Pattern::Hot,
+        max_retries: 5,
+    };
+    let mut args = std::env::args().skip(1);
+    while let Some(flag) = args.next() {
+        let value = args
+            .next()
+            .ok_or_else(|| format!("missing value for {}", flag))?;
+        let parse_err = |e: &dyn std::fmt::Display| format!("invalid {}: {}", flag, e);
+        match flag.as_str() {
+            "--program-id" => {
+                program_id = Some(Pubkey::from_str(&value).map_err(|e| parse_err(&e))?)
+            }
+            "--rpc-url" => options.rpc_url = value,
+            "--reviewers" => options.reviewers = value.parse().map_err(|e| parse_err(&e))?,
+            "--commenters" => options.commenters = value.parse().map_err(|e| parse_err(&e))?,
+            "--review-rate" => options.review_rate = value.parse().map_err(|e| parse_err(&e))?,
+            "--comment-rate" => options.comment_rate = value.parse().map_err(|e| parse_err(&e))?,
+            "--duration" => {
+                options.duration = Duration::from_secs(value.parse().map_err(|e| parse_err(&e))?)
+            }
+            "--pattern" => {
+                options.pattern = match value.as_str() {
+                    "hot" => Pattern::Hot,
+                    "spread" => Pattern::Spread,
+                    _ => return Err(format!("--pattern must be hot or spread, not {}", value)),
+                }
+            }
+            "--max-retries" => options.max_retries = value.parse().map_err(|e| parse_err(&e))?,
+            _ => return Err(format!("unknown flag {}", flag)),
+        }
+    }
+    options.program_id = program_id.ok_or("--program-id is required")?;
+    if options.reviewers == 0 {
+        return Err("--reviewers must be at least 1".to_string());
+    }
+    if options.review_rate <= 0.0 || options.comment_rate <= 0.0 {
+        return Err("rates must be positive".to_string());
+    }
+    Ok(options)
+}
+
+/// Outcomes of one kind of transaction.
+#[derive(Default)]
+struct Stats {
+    latencies: Vec<Duration>,
+    failed: usize,
+    retries: usize,
+}
+
+impl Stats {
+    fn report(&mut self, name: &str, elapsed: Duration) {
+        self.latencies.sort();
+        let confirmed = self.latencies.len();
+        println!(
+            "{}: {} confirmed ({:.1}/s), {} failed, {} counter retries",
+            name,
+            confirmed,
+            confirmed as f64 / elapsed.as_secs_f64(),
+            self.failed,
+            self.retries
+        );
+        if confirmed > 0 {
+            let percentile = |p: usize| self.latencies[(confirmed - 1) * p / 100];
+            println!(
+                "  latency p50 {:?}, p90 {:?}, p99 {:?}, max {:?}",
+                percentile(50),
+                percentile(90),
+                percentile(99),
+                self.latencies[confirmed - 1]
+            );
+        }
+    }
+}
+
+struct Review {
+    address: Pubkey,
+    reviewer: Pubkey,
+}
+
+async fn fund(rpc: &RpcClient, keypair: &Keypair) -> Result<(), ClientError> {
+    let signature = rpc
+        .request_airdrop(&keypair.pubkey(), 10 * LAMPORTS_PER_SOL)
+        .await?;
+    while !rpc.confirm_transaction(&signature).await? {
+        sleep(Duration::from_millis(200)).await;
+    }
+    Ok(())
+}
+
+/// Sends `instruction` paid for by `signer`, returning the time from
+/// submission to confirmation.
+async fn send(
+    rpc: &RpcClient,
+    signer: &Keypair,
+    instruction: Instruction,
+) -> Result<Duration, ClientError> {
+    let blockhash = rpc.get_latest_blockhash().await?;
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction],
+        Some(&signer.pubkey()),
+        &[signer],
+        blockhash,
+    );
+    let started = Instant::now();
+    rpc.send_and_confirm_transaction(&transaction).await?;
+    Ok(started.elapsed())
+}
+
+/// Whether the comment was built against a counter another comment advanced
+/// first.
+fn lost_counter_race(error: &ClientError) -> bool {
+    matches!(
+        error.get_transaction_error(),
+        Some(TransactionError::InstructionError(_, InstructionError::Custom(code)))
+            if code == ReviewError::InvalidPDA as u32
+    )
+}
+
+async fn comment(
+    rpc: &RpcClient,
+    options: &Options,
+    commenter: &Keypair,
+    review: &Review,
+    text: &str,
+    stats: &Mutex<Stats>,
+) {
+    for attempt in 0..=options.max_retries {
+        let result = match fetch_comment_counter(rpc, &options.program_id, &review.address).await {
+            Ok(Some(counter)) => {
+                let instruction = instructions::add_comment(
+                    &options.program_id,
+                    &commenter.pubkey(),
+                    &review.address,
+                    &review.reviewer,
+                    counter.total_comments_created,
+                    text,
+                    None,
+                );
+                send(rpc, commenter, instruction).await
+            }
+            Ok(None) => break,
+            Err(e) => Err(e),
+        };
+        let mut stats = stats.lock().unwrap();
+        match result {
+            Ok(latency) => {
+                stats.latencies.push(latency);
+                return;
+            }
+            Err(e) if lost_counter_race(&e) && attempt < options.max_retries => {
+                stats.retries += 1;
+            }
+            Err(_) => break,
+        }
+    }
+    stats.lock().unwrap().failed += 1;
+}
+
+async fn run(options: Options) -> Result<(), String> {
+    let options = Arc::new(options);
+    let rpc = Arc::new(RpcClient::new_with_commitment(
+        options.rpc_url.clone(),
+        CommitmentConfig::confirmed(),
+    ));
+
+    let reviewers: Vec<_> = (0..options.reviewers).map(|_| Keypair::new()).collect();
+    let commenters: Vec<_> = (0..options.commenters).map(|_| Keypair::new()).collect();
+    for keypair in reviewers.iter().chain(&commenters) {
+        fund(&rpc, keypair).await.map_err(|e| e.to_string())?;
+    }
+
+    let mut reviews = Vec::new();
+    for (i, reviewer) in reviewers.iter().enumerate() {
+        let title = format!("loadtest {} 0", i);
+        let instruction = instructions::add_movie_review(
+            &options.program_id,
+            &reviewer.pubkey(),
+            &title,
+            4,
+            "warming up",
+        );
+        send(&rpc, reviewer, instruction)
+            .await
+            .map_err(|e| e.to_string())?;
+        reviews.push(Review {
+            address: find_review_address(&options.program_id, &reviewer.pubkey(), &title).0,
+            reviewer: reviewer.pubkey(),
+        });
+    }
+    let reviews = Arc::new(reviews);
+    println!(
+        "{} reviewers, {} commenters, {} contention for {:?}",
+        options.reviewers,
+        options.commenters,
+        if options.pattern == Pattern::Hot {
+            "hot"
+        } else {
+            "spread"
+        },
+        options.duration
+    );
+
+    let review_stats = Arc::new(Mutex::new(Stats::default()));
+    let comment_stats = Arc::new(Mutex::new(Stats::default()));
+    let started = Instant::now();
+    let mut tasks = Vec::new();
+
+    for (i, reviewer) in reviewers.into_iter().enumerate() {
+        let (rpc, options, stats) = (rpc.clone(), options.clone(), review_stats.clone());
+        tasks.push(tokio::spawn(async move {
+            let mut ticks = interval(Duration::from_secs_f64(1.0 / options.review_rate));
+            ticks.set_missed_tick_behavior(MissedTickBehavior::Delay);
+            let mut n = 1;
+            while started.elapsed() < options.duration {
+                ticks.tick().await;
+                let instruction = instructions::add_movie_review(
+                    &options.program_id,
+                    &reviewer.pubkey(),
+                    &format!("loadtest {} {}", i, n),
+                    3,
+                    "under load",
+                );
+                n += 1;
+                let result = send(&rpc, &reviewer, instruction).await;
+                let mut stats = stats.lock().unwrap();
+                match result {
+                    Ok(latency) => stats.latencies.push(latency),
+                    Err(_) => stats.failed += 1,
+                }
+            }
+        }));
+    }
+
+    for commenter in commenters {
+        let (rpc, options, reviews, stats) = (
+            rpc.clone(),
+            options.clone(),
+            reviews.clone(),
+            comment_stats.clone(),
+        );
+        tasks.push(tokio::spawn(async move {
+            let mut ticks = interval(Duration::from_secs_f64(1.0 / options.comment_rate));
+            ticks.set_missed_tick_behavior(MissedTickBehavior::Delay);
+            while started.elapsed() < options.duration {
+                ticks.tick().await;
+                let review = match options.pattern {
+                    Pattern::Hot => &reviews[0],
+                    Pattern::Spread => reviews.choose(&mut thread_rng()).unwrap(),
+                };
+                comment(&rpc, &options, &commenter, review, "under load", &stats).await;
+            }
+        }));
+    }
+
+    for task in tasks {
+        task.await.map_err(|e| e.to_string())?;
+    }
+    let elapsed = started.elapsed();
+    review_stats.lock().unwrap().report("reviews", elapsed);
+    comment_stats.lock().unwrap().report("comments", elapsed);
+    Ok(())
+}
+
+#[tokio::main]
+async fn main() {
+    let options = parse_options().unwrap_or_else(|e| {
+        eprintln!("error: {}", e);
+        process::exit(2);
+    });
+    if let Err(e) = run(options).await {
+        eprintln!("error: {}", e);
+        process::exit(1);
+    }
+}
@@ -0,0 +1,76 @@
+//! Moves a review's body off-chain: uploads a file to Arweave through Irys,
+//! or to a Shadow Drive storage account, and commits the review to its hash
+//! with `UpdateBodyHash`.
+//!
+//! `cargo run --example publish_body --features storage -- PROGRAM_ID KEYPAIR
+//! TITLE FILE [STORE] [RPC_URL]`
+//!
+//! `KEYPAIR` is the reviewer's keypair file and `TITLE` the title of their
+//! review. `STORE` is `irys` (the default) or `shdw:STORAGE_ACCOUNT`, and
+//! `RPC_URL` defaults to the local test validator.
+
+use std::{env, fs, path::Path, process, str::FromStr};
+
+use movie_review::client::{
+    pda::find_review_address,
+    storage::{publish_body, BodyStore, IRYS_NODE},
+};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::{pubkey::Pubkey, signature::read_keypair_file, signer::Signer};
+
+const USAGE: &str =
+    "usage: publish_body PROGRAM_ID KEYPAIR TITLE FILE [irys|shdw:STORAGE_ACCOUNT] [RPC_URL]";
+
+fn parse_store(store: &str, file: &Path) -> Result<BodyStore, String> {
+    if store == "irys" {
+        return Ok(BodyStore::Irys {
+            node: IRYS_NODE.to_string(),
+        });
+    }
+    let storage_account = store
+        .strip_prefix("shdw:")
+        .ok_or_else(|| format!("unknown store {}", store))?;
+    let file_name = file
+        .file_name()
+        .and_then(|name| name.to_str())
+        .ok_or_else(|| format!("{} has no file name", file.display()))?;
+    Ok(BodyStore::ShadowDrive {
+        storage_account: Pubkey::from_str(storage_account)
+            .map_err(|e| format!("invalid STORAGE_ACCOUNT: {}", e))?,
+        file_name: file_name.to_string(),
+    })
+}
+
+async fn run(mut args: impl Iterator<Item = String>) -> Result<(), String> {
+    let mut next = || args.next().ok_or_else(|| USAGE.to_string());
+    let program_id =
+        Pubkey::from_str(&next()?).map_err(|e| format!("invalid PROGRAM_ID: {}", e))?;
+    let keypair_path = next()?;
+    let reviewer = read_keypair_file(&keypair_path)
+        .map_err(|e| format!("failed to read {}: {}", keypair_path, e))?;
+    let title = next()?;
+    let file = next()?;
+    let store = next().unwrap_or_else(|_| "irys".to_string());
+    let rpc_url = next().unwrap_or_else(|_| "http://127.0.0.1:8899".to_string());
+
+    let body = fs::read(&file).map_err(|e| format!("failed to read {}: {}", file, e))?;
+    let store = parse_store(&store, Path::new(&file))?;
+    let (review, _) = find_review_address(&program_id, &reviewer.pubkey(), &title);
+
+    let rpc = RpcClient::new(rpc_url);
+    let http = reqwest::Client::new();
+    let (uri, signature) =
+        publish_body(&rpc, &http, &store, &program_id, &reviewer, &review, &body)
+            .await
+            .map_err(|e| e.to_string())?;
+    println!("review {} now points at {} ({})", review, uri, signature);
+    Ok(())
+}
+
+#[tokio::main]
+async fn main() {
+    if let Err(e) = run(env::args().skip(1)).await {
+        eprintln!("error: {}", e);
+        process::exit(if e == USAGE { 2 } else { 1 });
+    }
+}
@@ -0,0 +1,123 @@
+//! Posts a summary of every new or edited review to a Discord or Telegram
+//! channel, watching the program through `client::subscribe`.
+//!
+//! Set `DISCORD_WEBHOOK_URL`, or `TELEGRAM_BOT_TOKEN` and `TELEGRAM_CHAT_ID`,
+//! then run
+//!
+//! `cargo run --example review_bot --features rpc -- PROGRAM_ID [WS_URL]`
+//!
+//! `WS_URL` defaults to the local test validator.
+
+use std::{collections::HashSet, env, process, str::FromStr};
+
+use futures_util::StreamExt;
+use movie_review::{
+    client::subscribe::{subscribe_reviews, ReviewUpdate},
+    state::MovieAccountState,
+};
+use serde_json::json;
+use solana_client::nonblocking::pubsub_client::PubsubClient;
+use solana_sdk::pubkey::Pubkey;
+
+/// Longest description quoted in a summary, in characters.
+const EXCERPT_CHARS: usize = 200;
+
+enum Channel {
+    Discord { webhook_url: String },
+    Telegram { token: String, chat_id: String },
+}
+
+impl Channel {
+    fn from_env() -> Result<Self, String> {
+        if let Ok(webhook_url) = env::var("DISCORD_WEBHOOK_URL") {
+            return Ok(Channel::Discord { webhook_url });
+        }
+        match (env::var("TELEGRAM_BOT_TOKEN"), env::var("TELEGRAM_CHAT_ID")) {
+            (Ok(token), Ok(chat_id)) => Ok(Channel::Telegram { token, chat_id }),
+            _ => Err(
+                "set DISCORD_WEBHOOK_URL, or TELEGRAM_BOT_TOKEN and TELEGRAM_CHAT_ID".to_string(),
+            ),
+        }
+    }
+
+    async fn post(&self, http: &reqwest::Client, text: &str) -> Result<(), reqwest::Error> {
+        let request = match self {
+            Channel::Discord { webhook_url } => {
+                http.post(webhook_url).json(&json!({ "content": text }))
+            }
+            Channel::Telegram { token, chat_id } => http
+                .post(format!("https://api.telegram.org/bot{}/sendMessage", token))
+                .json(&json!({ "chat_id": chat_id, "text": text })),
+        };
+        request.send().await?.error_for_status()?;
+        Ok(())
+    }
+}
+
+fn summary(update: &ReviewUpdate, edited: bool) -> String {
+    let MovieAccountState {
+        reviewer,
+        rating,
+        title,
+        description,
+        ..
+    } = &update.review;
+    let mut excerpt: String = description.chars().take(EXCERPT_CHARS).collect();
+    if excerpt.len() < description.len() {
+        excerpt.push('…');
+    }
+    format!(
+        "{} review of {}, rated {}, by {}\n{}\n(review {}, slot {})",
+        if edited { "Edited" } else { "New" },
+        title,
+        rating,
+        reviewer,
+        excerpt,
+        update.address,
+        update.slot
+    )
+}
+
+async fn run(program_id: Pubkey, ws_url: &str, channel: Channel) -> Result<(), String> {
+    let client = PubsubClient::new(ws_url).await.map_err(|e| e.to_string())?;
+    let (mut reviews, unsubscribe) = subscribe_reviews(&client, &program_id)
+        .await
+        .map_err(|e| e.to_string())?;
+    let http = reqwest::Client::new();
+    println!("watching reviews of {}", program_id);
+
+    // Reviews written before the bot started are reported as new the first
+    // time they change
+    let mut seen = HashSet::new();
+    while let Some(update) = reviews.next().await {
+        let edited = !seen.insert(update.address);
+        if let Err(e) = channel.post(&http, &summary(&update, edited)).await {
+            eprintln!("failed to post review {}: {}", update.address, e);
+        }
+    }
+    unsubscribe().await;
+    Err("subscription closed".to_string())
+}
+
+#[tokio::main]
+async fn main() {
+    let mut args = env::args().skip(1);
+    let program_id = args
+        .next()
+        .ok_or_else(|| "usage: review_bot PROGRAM_ID [WS_URL]".to_string())
+        .and_then(|arg| Pubkey::from_str(&arg).map_err(|e| format!("invalid PROGRAM_ID: {}", e)));
+    let ws_url = args
+        .next()
+        .unwrap_or_else(|| "ws://127.0.0.1:8900".to_string());
+    let (program_id, channel) = match (program_id, Channel::from_env()) {
+        (Ok(program_id), Ok(channel)) => (program_id, channel),
+        (Err(e), _) | (_, Err(e)) => {
+            eprintln!("error: {}", e);
+            process::exit(2);
+        }
+    };
+    if let Err(e) = run(program_id, &ws_url, channel).await {
+        eprintln!("error: {}", e);
+        process::exit(1);
+    }
+}
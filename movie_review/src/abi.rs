@@ -0,0 +1,553 @@
+//! Byte layouts of the program's accounts, for consumers that read account
+//! data in place rather than deserializing it, such as Geyser plugins and C
+//! or TypeScript clients.
+//!
+//! Each account has a module with one `Field` per field, named after it in
+//! upper case, and `LEN`, the bytes those fields span. An account with a
+//! string or vector only lists the fields before it, whose offsets hold for
+//! every instance; the length prefix of the first variable field starts at
+//! `LEN`. Layouts only ever grow by appending fields, so published offsets
+//! never move, though accounts created before a field was appended are
+//! shorter than `LEN`.
+//!
+//! Every `layout!` below names its struct's fields in order and fails to
+//! compile if they stop matching the struct.
+
+use solana_program::pubkey::Pubkey;
+
+use crate::state::{
+    self, AdminAction, AppealStatus, AuditEntry, AuditLog, CommentVoteKind, ContentLimits,
+    FeeSchedule, HotEntry, HotFeed, ProgramConfig, RatingScale,
+};
+
+/// A field's position in an account's data.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Field {
+    pub offset: usize,
+    pub len: usize,
+}
+
+impl Field {
+    pub const fn end(&self) -> usize {
+        self.offset + self.len
+    }
+
+    /// The field's bytes in `data`, `None` if `data` is too short to hold it.
+    pub fn read<'a>(&self, data: &'a [u8]) -> Option<&'a [u8]> {
+        data.get(self.offset..self.end())
+    }
+}
+
+/// Types with a fixed Borsh encoding of `LEN` bytes.
+pub trait FixedSize {
+    const LEN: usize;
+}
+
+macro_rules! fixed_size {
+    ($($ty:ty => $len:expr),* $(,)?) => {
+        $(impl FixedSize for $ty {
+            const LEN: usize = $len;
+        })*
+    };
+}
+
+fixed_size! {
+    u8 => 1,
+    bool => 1,
+    u16 => 2,
+    u32 => 4,
+    u64 => 8,
+    i64 => 8,
+    Pubkey => 32,
+    AdminAction => 1,
+    AppealStatus => 1,
+    CommentVoteKind => 1,
+}
+
+impl<T: FixedSize, const N: usize> FixedSize for [T; N] {
+    const LEN: usize = T::LEN * N;
+}
+
+macro_rules! layout {
+    (
+        $(#[$doc:meta])*
+        $module:ident: $account:ident { $($field:ident: $ty:ty => $name:ident,)* }
+    ) => {
+        layout!(@module $(#[$doc])* $module: $account { $($field: $ty => $name,)* } {});
+
+        impl FixedSize for state::$account {
+            const LEN: usize = $module::LEN;
+        }
+    };
+    (
+        $(#[$doc:meta])*
+        $module:ident: $account:ident { $($field:ident: $ty:ty => $name:ident,)* .. }
+    ) => {
+        layout!(@module $(#[$doc])* $module: $account { $($field: $ty => $name,)* } { .. });
+    };
+    (
+        @module $(#[$doc:meta])*
+        $module:ident: $account:ident { $($field:ident: $ty:ty => $name:ident,)* }
+        { $($rest:tt)* }
+    ) => {
+        $(#[$doc])*
+        pub mod $module {
+            use super::*;
+
+            layout!(@fields $($ty => $name,)*);
+
+            #[allow(dead_code)]
+            fn matches_struct(account: &state::$account) {
+                let state::$account { $($field: _,)* $($rest)* } = account;
+                $(let _: &$ty = &account.$field;)*
+            }
+        }
+    };
+    (@fields $ty:ty => $name:ident, $($rest:tt)*) => {
+        pub const $name: Field = Field {
+            offset: 0,
+            len: <$ty as FixedSize>::LEN,
+        };
+        layout!(@after $name; $($rest)*);
+    };
+    (@after $prev:ident; $ty:ty => $name:ident, $($rest:tt)*) => {
+        pub const $name: Field = Field {
+            offset: $prev.end(),
+            len: <$ty as FixedSize>::LEN,
+        };
+        layout!(@after $name; $($rest)*);
+    };
+    (@after $prev:ident;) => {
+        pub const LEN: usize = $prev.end();
+    };
+}
+
+layout! {
+    /// `ContentLimits`, embedded in the config.
+    content_limits: ContentLimits {
+        max_review_size: u32 => MAX_REVIEW_SIZE,
+        max_comment_size: u32 => MAX_COMMENT_SIZE,
+    }
+}
+
+layout! {
+    /// `RatingScale`, embedded in the config.
+    rating_scale: RatingScale {
+        min: u16 => MIN,
+        max: u16 => MAX,
+        decimals: u8 => DECIMALS,
+    }
+}
+
+layout! {
+    /// `FeeSchedule`, embedded in the config.
+    fee_schedule: FeeSchedule {
+        add_review: u64 => ADD_REVIEW,
+        add_comment: u64 => ADD_COMMENT,
+        vote: u64 => VOTE,
+        boost: u64 => BOOST,
+    }
+}
+
+layout! {
+    /// `MovieAccountState`, including anonymous and imported reviews.
+    review: MovieAccountState {
+        discriminator: u8 => DISCRIMINATOR,
+        is_initialized: bool => IS_INITIALIZED,
+        reviewer: Pubkey => REVIEWER,
+        rating: u16 => RATING,
+        ..
+    }
+}
+
+layout! {
+    /// `MovieCommentCounter`.
+    comment_counter: MovieCommentCounter {
+        discriminator: u8 => DISCRIMINATOR,
+        is_initialized: bool => IS_INITIALIZED,
+        total_comments_created: u64 => TOTAL_COMMENTS_CREATED,
+        page: u64 => PAGE,
+        active_comments: u64 => ACTIVE_COMMENTS,
+    }
+}
+
+layout! {
+    /// `MovieComment`.
+    comment: MovieComment {
+        discriminator: u8 => DISCRIMINATOR,
+        is_initialized: bool => IS_INITIALIZED,
+        reviewer: Pubkey => REVIEWER,
+        commenter: Pubkey => COMMENTER,
+        ..
+    }
+}
+
+layout! {
+    /// `CommentPage`.
+    comment_page: CommentPage {
+        discriminator: u8 => DISCRIMINATOR,
+        is_initialized: bool => IS_INITIALIZED,
+        review: Pubkey => REVIEW,
+        page: u64 => PAGE,
+        ..
+    }
+}
+
+layout! {
+    /// `ProgramConfig`.
+    config: ProgramConfig {
+        discriminator: u8 => DISCRIMINATOR,
+        is_initialized: bool => IS_INITIALIZED,
+        authority: Pubkey => AUTHORITY,
+        allow_self_comments: bool => ALLOW_SELF_COMMENTS,
+        require_reviewer_pass: bool => REQUIRE_REVIEWER_PASS,
+        review_collection: Pubkey => REVIEW_COLLECTION,
+        collection_generation: u32 => COLLECTION_GENERATION,
+        release_oracle: Pubkey => RELEASE_ORACLE,
+        release_feed_id: [u8; 32] => RELEASE_FEED_ID,
+        vrf_program: Pubkey => VRF_PROGRAM,
+        wormhole_program: Pubkey => WORMHOLE_PROGRAM,
+        foreign_emitter_chain: u16 => FOREIGN_EMITTER_CHAIN,
+        foreign_emitter: [u8; 32] => FOREIGN_EMITTER,
+        fees: FeeSchedule => FEES,
+        fee_exempt_mint: Pubkey => FEE_EXEMPT_MINT,
+        fee_exempt_min_balance: u64 => FEE_EXEMPT_MIN_BALANCE,
+        tip_program: Pubkey => TIP_PROGRAM,
+        limits: ContentLimits => LIMITS,
+        rating_scale: RatingScale => RATING_SCALE,
+        cpi_callers: [Pubkey; ProgramConfig::MAX_CPI_CALLERS] => CPI_CALLERS,
+        features: u64 => FEATURES,
+        removal_window: u64 => REMOVAL_WINDOW,
+        min_wallet_age: u64 => MIN_WALLET_AGE,
+        age_attester: Pubkey => AGE_ATTESTER,
+        hide_threshold: i64 => HIDE_THRESHOLD,
+        edit_burn_mint: Pubkey => EDIT_BURN_MINT,
+        edit_burn_amount: u64 => EDIT_BURN_AMOUNT,
+    }
+}
+
+layout! {
+    /// `Movie`.
+    movie: Movie {
+        discriminator: u8 => DISCRIMINATOR,
+        is_initialized: bool => IS_INITIALIZED,
+        ..
+    }
+}
+
+layout! {
+    /// `ReviewTranslation`.
+    translation: ReviewTranslation {
+        discriminator: u8 => DISCRIMINATOR,
+        is_initialized: bool => IS_INITIALIZED,
+        review: Pubkey => REVIEW,
+        ..
+    }
+}
+
+layout! {
+    /// `ExternalIdIndex`.
+    external_id_index: ExternalIdIndex {
+        discriminator: u8 => DISCRIMINATOR,
+        is_initialized: bool => IS_INITIALIZED,
+        movie: Pubkey => MOVIE,
+    }
+}
+
+layout! {
+    /// `Follow`.
+    follow: Follow {
+        discriminator: u8 => DISCRIMINATOR,
+        is_initialized: bool => IS_INITIALIZED,
+        follower: Pubkey => FOLLOWER,
+        reviewer: Pubkey => REVIEWER,
+    }
+}
+
+layout! {
+    /// `Mute`.
+    mute: Mute {
+        discriminator: u8 => DISCRIMINATOR,
+        is_initialized: bool => IS_INITIALIZED,
+        reviewer: Pubkey => REVIEWER,
+        muted: Pubkey => MUTED,
+    }
+}
+
+layout! {
+    /// `Session`.
+    session: Session {
+        discriminator: u8 => DISCRIMINATOR,
+        is_initialized: bool => IS_INITIALIZED,
+        authority: Pubkey => AUTHORITY,
+        session_key: Pubkey => SESSION_KEY,
+        expires_slot: u64 => EXPIRES_SLOT,
+        scopes: u8 => SCOPES,
+    }
+}
+
+layout! {
+    /// `AuditEntry`, embedded in the audit log.
+    audit_entry: AuditEntry {
+        sequence: u64 => SEQUENCE,
+        slot: u64 => SLOT,
+        authority: Pubkey => AUTHORITY,
+        action: AdminAction => ACTION,
+        target: Pubkey => TARGET,
+    }
+}
+
+layout! {
+    /// `AuditLog`.
+    audit_log: AuditLog {
+        discriminator: u8 => DISCRIMINATOR,
+        is_initialized: bool => IS_INITIALIZED,
+        next_sequence: u64 => NEXT_SEQUENCE,
+        entries: [AuditEntry; AuditLog::CAPACITY] => ENTRIES,
+    }
+}
+
+layout! {
+    /// `HotEntry`, embedded in the hot feed.
+    hot_entry: HotEntry {
+        review: Pubkey => REVIEW,
+        score: u64 => SCORE,
+        slot: u64 => SLOT,
+    }
+}
+
+layout! {
+    /// `HotFeed`.
+    hot_feed: HotFeed {
+        discriminator: u8 => DISCRIMINATOR,
+        is_initialized: bool => IS_INITIALIZED,
+        entries: [HotEntry; HotFeed::ENTRIES] => ENTRIES,
+    }
+}
+
+layout! {
+    /// `ReviewEdits`.
+    review_edits: ReviewEdits {
+        discriminator: u8 => DISCRIMINATOR,
+        is_initialized: bool => IS_INITIALIZED,
+        review: Pubkey => REVIEW,
+        edits: u64 => EDITS,
+    }
+}
+
+layout! {
+    /// `ReviewCoAuthors`.
+    co_authors: ReviewCoAuthors {
+        discriminator: u8 => DISCRIMINATOR,
+        is_initialized: bool => IS_INITIALIZED,
+        review: Pubkey => REVIEW,
+        ..
+    }
+}
+
+layout! {
+    /// `TranslationBounty`.
+    translation_bounty: TranslationBounty {
+        discriminator: u8 => DISCRIMINATOR,
+        is_initialized: bool => IS_INITIALIZED,
+        review: Pubkey => REVIEW,
+        reviewer: Pubkey => REVIEWER,
+        amount: u64 => AMOUNT,
+        ..
+    }
+}
+
+layout! {
+    /// `TranslationSubmission`.
+    translation_submission: TranslationSubmission {
+        discriminator: u8 => DISCRIMINATOR,
+        is_initialized: bool => IS_INITIALIZED,
+        bounty: Pubkey => BOUNTY,
+        translator: Pubkey => TRANSLATOR,
+        ..
+    }
+}
+
+layout! {
+    /// `RatingIndex`. The review keys follow `LEN` grouped by fifth, see
+    /// `RatingIndex::bucket_range`.
+    rating_index: RatingIndex {
+        discriminator: u8 => DISCRIMINATOR,
+        is_initialized: bool => IS_INITIALIZED,
+        movie: Pubkey => MOVIE,
+        counts: [u32; 5] => COUNTS,
+        ..
+    }
+}
+
+layout! {
+    /// `TitleFilter`.
+    title_filter: TitleFilter {
+        discriminator: u8 => DISCRIMINATOR,
+        is_initialized: bool => IS_INITIALIZED,
+        reviewer: Pubkey => REVIEWER,
+        titles: u32 => TITLES,
+        bits: [u64; 16] => BITS,
+    }
+}
+
+layout! {
+    /// `RentPayer`.
+    rent_payer: RentPayer {
+        discriminator: u8 => DISCRIMINATOR,
+        is_initialized: bool => IS_INITIALIZED,
+        account: Pubkey => ACCOUNT,
+        payer: Pubkey => PAYER,
+    }
+}
+
+layout! {
+    /// `PendingRemoval`.
+    pending_removal: PendingRemoval {
+        discriminator: u8 => DISCRIMINATOR,
+        is_initialized: bool => IS_INITIALIZED,
+        review: Pubkey => REVIEW,
+        author: Pubkey => AUTHOR,
+        proposed_slot: u64 => PROPOSED_SLOT,
+        appeal_deadline: u64 => APPEAL_DEADLINE,
+        appeal: AppealStatus => APPEAL,
+    }
+}
+
+layout! {
+    /// `BodyCommitment`.
+    body_commitment: BodyCommitment {
+        discriminator: u8 => DISCRIMINATOR,
+        is_initialized: bool => IS_INITIALIZED,
+        review: Pubkey => REVIEW,
+        hash: [u8; 32] => HASH,
+    }
+}
+
+layout! {
+    /// `ReviewFingerprint`.
+    review_fingerprint: ReviewFingerprint {
+        discriminator: u8 => DISCRIMINATOR,
+        is_initialized: bool => IS_INITIALIZED,
+        review: Pubkey => REVIEW,
+        fingerprint: u64 => FINGERPRINT,
+        slot: u64 => SLOT,
+    }
+}
+
+layout! {
+    /// `DuplicateChallenge`.
+    duplicate_challenge: DuplicateChallenge {
+        discriminator: u8 => DISCRIMINATOR,
+        is_initialized: bool => IS_INITIALIZED,
+        challenger: Pubkey => CHALLENGER,
+        review: Pubkey => REVIEW,
+        original: Pubkey => ORIGINAL,
+        distance: u8 => DISTANCE,
+        slot: u64 => SLOT,
+    }
+}
+
+layout! {
+    /// `CommentVote`.
+    comment_vote: CommentVote {
+        discriminator: u8 => DISCRIMINATOR,
+        is_initialized: bool => IS_INITIALIZED,
+        comment: Pubkey => COMMENT,
+        voter: Pubkey => VOTER,
+        kind: CommentVoteKind => KIND,
+    }
+}
+
+layout! {
+    /// `CommentKarma`.
+    comment_karma: CommentKarma {
+        discriminator: u8 => DISCRIMINATOR,
+        is_initialized: bool => IS_INITIALIZED,
+        comment: Pubkey => COMMENT,
+        likes: u64 => LIKES,
+        reports: u64 => REPORTS,
+        hidden: bool => HIDDEN,
+    }
+}
+
+layout! {
+    /// `GlobalStats`.
+    global_stats: GlobalStats {
+        discriminator: u8 => DISCRIMINATOR,
+        is_initialized: bool => IS_INITIALIZED,
+        total_reviews: u64 => TOTAL_REVIEWS,
+        total_comments: u64 => TOTAL_COMMENTS,
+        total_tips: u64 => TOTAL_TIPS,
+        unique_reviewers: u64 => UNIQUE_REVIEWERS,
+        total_tip_lamports: u64 => TOTAL_TIP_LAMPORTS,
+    }
+}
+
+layout! {
+    /// `ReviewerProfile`.
+    reviewer_profile: ReviewerProfile {
+        discriminator: u8 => DISCRIMINATOR,
+        is_initialized: bool => IS_INITIALIZED,
+        reviewer: Pubkey => REVIEWER,
+        review_count: u64 => REVIEW_COUNT,
+        ratings: [u32; 5] => RATINGS,
+        relay_nonce: u64 => RELAY_NONCE,
+        last_active_epoch: u64 => LAST_ACTIVE_EPOCH,
+        streak: u32 => STREAK,
+        authority_program: Pubkey => AUTHORITY_PROGRAM,
+        authority: Pubkey => AUTHORITY,
+    }
+}
+
+layout! {
+    /// `MovieStats`.
+    movie_stats: MovieStats {
+        discriminator: u8 => DISCRIMINATOR,
+        is_initialized: bool => IS_INITIALIZED,
+        movie: Pubkey => MOVIE,
+        review_count: u64 => REVIEW_COUNT,
+        rating_sum: u64 => RATING_SUM,
+        recompute_cursor: Pubkey => RECOMPUTE_CURSOR,
+        recompute_count: u64 => RECOMPUTE_COUNT,
+        recompute_sum: u64 => RECOMPUTE_SUM,
+    }
+}
+
+layout! {
+    /// `ReviewSnapshot` in its current version.
+    snapshot: ReviewSnapshot {
+        discriminator: u8 => DISCRIMINATOR,
+        is_initialized: bool => IS_INITIALIZED,
+        version: u8 => VERSION,
+        slot: u64 => SLOT,
+        written_slot: u64 => WRITTEN_SLOT,
+        review_count: u64 => REVIEW_COUNT,
+        comment_count: u64 => COMMENT_COUNT,
+        average_rating: u32 => AVERAGE_RATING,
+        average_comments: u64 => AVERAGE_COMMENTS,
+        ..
+    }
+}
+
+layout! {
+    /// `ReviewLink`.
+    review_link: ReviewLink {
+        discriminator: u8 => DISCRIMINATOR,
+        is_initialized: bool => IS_INITIALIZED,
+        review: Pubkey => REVIEW,
+    }
+}
+
+layout! {
+    /// `FeaturedReview`.
+    featured_review: FeaturedReview {
+        discriminator: u8 => DISCRIMINATOR,
+        is_initialized: bool => IS_INITIALIZED,
+        day: i64 => DAY,
+        review: Pubkey => REVIEW,
+        pending: bool => PENDING,
+        randomness: Pubkey => RANDOMNESS,
+        candidates_hash: [u8; 32] => CANDIDATES_HASH,
+    }
+}
@@ -0,0 +1,1028 @@
+//! The accounts each instruction takes, in order, as returned by
+//! `GetRequiredAccounts` so wallets and tools can build transactions without
+//! a client of their own.
+//!
+//! Accounts and seeds are referred to by the names given here. A seed that
+//! reads another account's state names it as `account.field`, e.g.
+//! `counter.page`. Integers are seeded little-endian and strings as UTF-8
+//! unless the seed says otherwise.
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{pubkey::Pubkey, system_program, sysvar};
+
+use crate::programs::{associated_token, token_2022, token_metadata};
+use crate::state::{
+    AuditLog, BodyCommitment, CommentKarma, CommentPage, CommentVote, DuplicateChallenge,
+    FeaturedReview, Follow, GlobalStats, HotFeed, Movie, MovieAccountState, MovieStats, Mute,
+    PendingRemoval, ProgramConfig, RatingIndex, RentPayer, ReviewCoAuthors, ReviewEdits,
+    ReviewFingerprint, ReviewSnapshot, ReviewTranslation, ReviewerProfile, Session, TitleFilter,
+    TranslationBounty, TranslationSubmission,
+};
+
+/// One seed of a PDA of this program.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, Eq, PartialEq)]
+pub enum Seed {
+    Literal(String),
+    /// The key of the account at this index.
+    Account(u8),
+    /// The named instruction argument.
+    Argument(String),
+    /// The SHA-256 hash of the named string argument.
+    ArgumentHash(String),
+    /// A field of another account's state.
+    Field(String),
+    /// A comment index held in another account's state, encoded by
+    /// `comment_seed_bytes`.
+    CommentIndex(String),
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, Eq, PartialEq)]
+pub struct AccountSpec {
+    pub name: String,
+    pub is_signer: bool,
+    pub is_writable: bool,
+    /// The account may be left out. Only ever the last one listed.
+    pub is_optional: bool,
+    /// The only address accepted, for the programs invoked.
+    pub address: Option<Pubkey>,
+    /// Seeds of the account's address under this program. Empty when the
+    /// caller chooses the account or it is derived some other way.
+    pub seeds: Vec<Seed>,
+}
+
+impl AccountSpec {
+    fn new(name: &str, is_signer: bool, is_writable: bool, seeds: Vec<Seed>) -> Self {
+        Self {
+            name: name.to_string(),
+            is_signer,
+            is_writable,
+            is_optional: false,
+            address: None,
+            seeds,
+        }
+    }
+
+    fn optional(mut self) -> Self {
+        self.is_optional = true;
+        self
+    }
+}
+
+/// The accounts an instruction takes, as returned by `GetRequiredAccounts`.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, Eq, PartialEq)]
+pub struct RequiredAccounts {
+    pub instruction: String,
+    pub accounts: Vec<AccountSpec>,
+    /// What may be passed after the listed accounts, if anything.
+    pub remaining: Option<String>,
+}
+
+fn literal(seed: &str) -> Seed {
+    Seed::Literal(seed.to_string())
+}
+
+fn argument(name: &str) -> Seed {
+    Seed::Argument(name.to_string())
+}
+
+fn field(name: &str) -> Seed {
+    Seed::Field(name.to_string())
+}
+
+fn signer(name: &str) -> AccountSpec {
+    AccountSpec::new(name, true, true, Vec::new())
+}
+
+fn readonly_signer(name: &str) -> AccountSpec {
+    AccountSpec::new(name, true, false, Vec::new())
+}
+
+fn writable(name: &str, seeds: Vec<Seed>) -> AccountSpec {
+    AccountSpec::new(name, false, true, seeds)
+}
+
+fn readonly(name: &str, seeds: Vec<Seed>) -> AccountSpec {
+    AccountSpec::new(name, false, false, seeds)
+}
+
+fn program(name: &str, address: Pubkey) -> AccountSpec {
+    AccountSpec {
+        address: Some(address),
+        ..readonly(name, Vec::new())
+    }
+}
+
+fn system() -> AccountSpec {
+    program("system_program", system_program::ID)
+}
+
+fn config_seeds() -> Vec<Seed> {
+    vec![literal(ProgramConfig::SEED)]
+}
+
+fn audit_log() -> AccountSpec {
+    writable("audit_log", vec![literal(AuditLog::SEED)])
+}
+
+fn global_stats_seeds() -> Vec<Seed> {
+    vec![literal(GlobalStats::SEED)]
+}
+
+fn hot_feed_seeds() -> Vec<Seed> {
+    vec![literal(HotFeed::SEED)]
+}
+
+fn profile_seeds(reviewer: u8) -> Vec<Seed> {
+    vec![literal(ReviewerProfile::SEED), Seed::Account(reviewer)]
+}
+
+fn counter_seeds(review: u8) -> Vec<Seed> {
+    vec![Seed::Account(review), literal("comment")]
+}
+
+fn pending_removal_seeds(review: u8) -> Vec<Seed> {
+    vec![literal(PendingRemoval::SEED), Seed::Account(review)]
+}
+
+fn body_commitment_seeds(review: u8) -> Vec<Seed> {
+    vec![literal(BodyCommitment::SEED), Seed::Account(review)]
+}
+
+fn rent_payer_seeds(account: u8) -> Vec<Seed> {
+    vec![literal(RentPayer::SEED), Seed::Account(account)]
+}
+
+fn fingerprint_seeds(review: u8) -> Vec<Seed> {
+    vec![literal(ReviewFingerprint::SEED), Seed::Account(review)]
+}
+
+fn movie_title_seeds() -> Vec<Seed> {
+    vec![
+        literal(Movie::SEED),
+        Seed::ArgumentHash("title".to_string()),
+    ]
+}
+
+fn rating_index_seeds(movie: u8) -> Vec<Seed> {
+    vec![literal(RatingIndex::SEED), Seed::Account(movie)]
+}
+
+fn mute_seeds() -> Vec<Seed> {
+    vec![
+        literal(Mute::SEED),
+        field("review.reviewer"),
+        Seed::Account(0),
+    ]
+}
+
+fn proofs() -> Option<String> {
+    Some("accounts proving a fee exemption, the comment policy or a session".to_string())
+}
+
+fn review_proofs(reviewer: &str) -> Option<String> {
+    Some(format!(
+        "the {reviewer}'s reviewer pass, then a token account proving a fee exemption and \
+         an attestation or stake account proving the {reviewer}'s wallet age"
+    ))
+}
+
+/// `review_proofs`, then the reviewer's `TitleFilter` if the title is to be
+/// checked against it.
+fn authored_review_proofs(reviewer: &str) -> Option<String> {
+    review_proofs(reviewer).map(|proofs| {
+        format!(
+            "{proofs}, then the {reviewer}'s title filter [\"{}\", {reviewer}]",
+            TitleFilter::SEED
+        )
+    })
+}
+
+/// `[authority, config, system_program, audit_log]` of the instructions that
+/// set a config field, growing configs created before it.
+fn config_update() -> Vec<AccountSpec> {
+    vec![
+        signer("authority"),
+        writable("config", config_seeds()),
+        system(),
+        audit_log(),
+    ]
+}
+
+fn add_movie_review() -> (Vec<AccountSpec>, Option<String>) {
+    (
+        vec![
+            signer("reviewer"),
+            writable("review", vec![Seed::Account(0), argument("title")]),
+            writable("counter", counter_seeds(1)),
+            system(),
+            writable("config", config_seeds()),
+            writable("global_stats", global_stats_seeds()),
+            writable("profile", profile_seeds(0)),
+        ],
+        authored_review_proofs("reviewer"),
+    )
+}
+
+fn update_movie_review() -> (Vec<AccountSpec>, Option<String>) {
+    (
+        vec![
+            signer("reviewer"),
+            writable("review", Vec::new()),
+            writable("profile", profile_seeds(0)),
+            readonly("config", config_seeds()),
+            writable("edits", vec![literal(ReviewEdits::SEED), Seed::Account(1)]),
+            system(),
+            readonly("movie", movie_title_seeds()).optional(),
+        ],
+        Some(
+            "with the movie, its rating index; then for paid edits, the edit burn token account, \
+             mint and token program"
+                .to_string(),
+        ),
+    )
+}
+
+/// `[tip_authority, config, global_stats, payer, system_program, review,
+/// hot_feed]` of the instructions the tip program records tips with.
+fn record_tip() -> Vec<AccountSpec> {
+    vec![
+        readonly_signer("tip_authority"),
+        readonly("config", config_seeds()),
+        writable("global_stats", global_stats_seeds()),
+        signer("payer"),
+        system(),
+        readonly("review", Vec::new()),
+        writable("hot_feed", hot_feed_seeds()),
+    ]
+}
+
+/// `[commenter, review, counter, comment, system_program, config, mute,
+/// (parent), global_stats, hot_feed]`.
+fn comment_accounts(is_reply: bool) -> Vec<AccountSpec> {
+    let mut accounts = vec![
+        signer("commenter"),
+        readonly("review", Vec::new()),
+        writable("counter", counter_seeds(1)),
+        writable(
+            "comment",
+            vec![
+                Seed::Account(1),
+                Seed::CommentIndex("counter.total_comments_created".to_string()),
+            ],
+        ),
+        system(),
+        writable("config", config_seeds()),
+        readonly("mute", mute_seeds()),
+    ];
+    if is_reply {
+        accounts.push(writable("parent", Vec::new()));
+    }
+    accounts.push(writable("global_stats", global_stats_seeds()));
+    accounts.push(writable("hot_feed", hot_feed_seeds()));
+    accounts
+}
+
+fn translation_seeds() -> Vec<Seed> {
+    vec![
+        literal(ReviewTranslation::SEED),
+        Seed::Account(1),
+        argument("language"),
+    ]
+}
+
+fn translation_accounts() -> Vec<AccountSpec> {
+    vec![
+        signer("reviewer"),
+        readonly("review", Vec::new()),
+        writable("translation", translation_seeds()),
+    ]
+}
+
+fn translation_bounty_seeds() -> Vec<Seed> {
+    vec![
+        literal(TranslationBounty::SEED),
+        Seed::Account(1),
+        argument("language"),
+    ]
+}
+
+/// Seeds of the submission of the translator at `translator` for the bounty
+/// at `bounty`.
+fn translation_submission_seeds(bounty: u8, translator: u8) -> Vec<Seed> {
+    vec![
+        literal(TranslationSubmission::SEED),
+        Seed::Account(bounty),
+        Seed::Account(translator),
+    ]
+}
+
+/// The accounts of the instruction with tag `tag`, or `None` if there is no
+/// such instruction.
+pub fn required_accounts(tag: u8) -> Option<RequiredAccounts> {
+    let (instruction, accounts, remaining) = match tag {
+        0 => {
+            let (accounts, remaining) = add_movie_review();
+            ("AddMovieReview", accounts, remaining)
+        }
+        1 => {
+            let (accounts, remaining) = update_movie_review();
+            ("UpdateMovieReview", accounts, remaining)
+        }
+        2 => ("AddComment", comment_accounts(false), proofs()),
+        3 => (
+            "MigrateAccount",
+            vec![writable("account", Vec::new())],
+            None,
+        ),
+        4 => (
+            "InitializeConfig",
+            vec![
+                signer("authority"),
+                writable("config", config_seeds()),
+                system(),
+                audit_log(),
+            ],
+            None,
+        ),
+        5 => ("UpdateConfig", config_update(), None),
+        6 => (
+            "RegisterMovie",
+            vec![
+                signer("payer"),
+                writable("movie", movie_title_seeds()),
+                system(),
+            ],
+            None,
+        ),
+        7 => (
+            "AddReviewForMovie",
+            vec![
+                signer("reviewer"),
+                readonly("movie", Vec::new()),
+                writable("review", vec![Seed::Account(0), Seed::Account(1)]),
+                readonly(
+                    "legacy_review",
+                    vec![Seed::Account(0), field("movie.title")],
+                ),
+                writable("counter", counter_seeds(2)),
+                system(),
+                writable("config", config_seeds()),
+                writable("global_stats", global_stats_seeds()),
+                writable("profile", profile_seeds(0)),
+                writable(
+                    "movie_stats",
+                    vec![literal(MovieStats::SEED), Seed::Account(1)],
+                ),
+                writable("rating_index", rating_index_seeds(1)),
+            ],
+            authored_review_proofs("reviewer"),
+        ),
+        8 => (
+            "LinkLegacyReview",
+            vec![
+                signer("reviewer"),
+                readonly("movie", Vec::new()),
+                readonly(
+                    "legacy_review",
+                    vec![Seed::Account(0), field("movie.title")],
+                ),
+                writable("link", vec![Seed::Account(0), Seed::Account(1)]),
+                system(),
+            ],
+            None,
+        ),
+        9 => (
+            "AddAnonymousReview",
+            vec![
+                signer("payer"),
+                writable("review", vec![argument("commitment"), argument("title")]),
+                writable("counter", counter_seeds(1)),
+                system(),
+                writable("config", config_seeds()),
+                writable("global_stats", global_stats_seeds()),
+            ],
+            review_proofs("payer"),
+        ),
+        10 => (
+            "RevealAuthorship",
+            vec![readonly_signer("reviewer"), writable("review", Vec::new())],
+            None,
+        ),
+        11 => (
+            "InitializePassMint",
+            vec![
+                signer("authority"),
+                readonly("config", config_seeds()),
+                writable("pass_mint", vec![literal(ProgramConfig::PASS_MINT_SEED)]),
+                program("token_2022_program", token_2022::ID),
+                system(),
+                audit_log(),
+            ],
+            None,
+        ),
+        12 => (
+            "IssueReviewerPass",
+            vec![
+                signer("authority"),
+                readonly("config", config_seeds()),
+                writable("pass_mint", vec![literal(ProgramConfig::PASS_MINT_SEED)]),
+                readonly("reviewer", Vec::new()),
+                writable("reviewer_pass", Vec::new()),
+                program("token_2022_program", token_2022::ID),
+                program("ata_program", associated_token::ID),
+                system(),
+                audit_log(),
+            ],
+            None,
+        ),
+        13 => (
+            "RotateReviewCollection",
+            vec![
+                signer("authority"),
+                writable("config", config_seeds()),
+                readonly(
+                    "collection_authority",
+                    vec![literal(ProgramConfig::COLLECTION_AUTHORITY_SEED)],
+                ),
+                writable(
+                    "collection_mint",
+                    vec![
+                        literal(ProgramConfig::COLLECTION_SEED),
+                        field("config.collection_generation"),
+                    ],
+                ),
+                writable("collection_ata", Vec::new()),
+                writable("metadata", Vec::new()),
+                writable("master_edition", Vec::new()),
+                program("token_program", spl_token::ID),
+                program("ata_program", associated_token::ID),
+                program("metadata_program", token_metadata::ID),
+                system(),
+                audit_log(),
+            ],
+            None,
+        ),
+        14 => (
+            "MintReviewNft",
+            vec![
+                signer("reviewer"),
+                readonly("review", Vec::new()),
+                readonly("config", config_seeds()),
+                readonly(
+                    "collection_authority",
+                    vec![literal(ProgramConfig::COLLECTION_AUTHORITY_SEED)],
+                ),
+                writable(
+                    "nft_mint",
+                    vec![literal(ProgramConfig::REVIEW_NFT_SEED), Seed::Account(1)],
+                ),
+                writable("nft_ata", Vec::new()),
+                writable("metadata", Vec::new()),
+                writable("master_edition", Vec::new()),
+                readonly("collection_mint", Vec::new()),
+                writable("collection_metadata", Vec::new()),
+                readonly("collection_master_edition", Vec::new()),
+                program("token_program", spl_token::ID),
+                program("ata_program", associated_token::ID),
+                program("metadata_program", token_metadata::ID),
+                system(),
+            ],
+            None,
+        ),
+        15 => ("SetReleaseOracle", config_update(), None),
+        16 => (
+            "MarkVerifiedRelease",
+            vec![
+                signer("payer"),
+                writable("movie", Vec::new()),
+                readonly("config", config_seeds()),
+                readonly("attestation", Vec::new()),
+                system(),
+            ],
+            None,
+        ),
+        17 => (
+            "RequestFeaturedDraw",
+            vec![
+                signer("payer"),
+                writable("featured", vec![literal(FeaturedReview::SEED)]),
+                readonly("config", config_seeds()),
+                readonly("randomness", Vec::new()),
+                system(),
+            ],
+            Some("the candidate reviews".to_string()),
+        ),
+        18 => (
+            "FulfillFeaturedDraw",
+            vec![
+                signer("cranker"),
+                writable("featured", vec![literal(FeaturedReview::SEED)]),
+                readonly("config", config_seeds()),
+                readonly("randomness", Vec::new()),
+            ],
+            Some("the candidate reviews of the request, in order".to_string()),
+        ),
+        19 => ("SetVrfProgram", config_update(), None),
+        20 => ("SetForeignEmitter", config_update(), None),
+        21 => (
+            "ImportForeignReview",
+            vec![
+                signer("payer"),
+                readonly("posted_vaa", Vec::new()),
+                readonly("config", config_seeds()),
+                writable(
+                    "review",
+                    vec![
+                        literal(MovieAccountState::FOREIGN_SEED),
+                        field("posted_vaa.emitter_chain"),
+                        field("posted_vaa.sequence"),
+                    ],
+                ),
+                writable("counter", counter_seeds(3)),
+                system(),
+                writable("global_stats", global_stats_seeds()),
+            ],
+            None,
+        ),
+        22 => (
+            "GetUpgradeAuthorityStatus",
+            vec![
+                readonly("program_data", Vec::new()),
+                readonly("config", config_seeds()),
+            ],
+            None,
+        ),
+        23 => (
+            "TopUpAccount",
+            vec![signer("payer"), writable("account", Vec::new()), system()],
+            None,
+        ),
+        24 => (
+            "WithdrawExcessLamports",
+            vec![
+                signer("authority"),
+                writable("account", Vec::new()),
+                readonly("rent_payer", rent_payer_seeds(1)),
+                system().optional(),
+                audit_log().optional(),
+            ],
+            None,
+        ),
+        25 => ("SetFeeSchedule", config_update(), None),
+        26 => (
+            "SetMovieExternalId",
+            vec![
+                signer("authority"),
+                writable("movie", Vec::new()),
+                readonly("config", config_seeds()),
+                writable(
+                    "index",
+                    vec![
+                        literal(Movie::EXTERNAL_ID_SEED),
+                        Seed::ArgumentHash("external_id".to_string()),
+                    ],
+                ),
+                system(),
+                audit_log(),
+            ],
+            None,
+        ),
+        27 => {
+            let mut accounts = translation_accounts();
+            accounts.push(system());
+            ("AddReviewTranslation", accounts, None)
+        }
+        28 => ("UpdateReviewTranslation", translation_accounts(), None),
+        29 => ("DeleteReviewTranslation", translation_accounts(), None),
+        30 => (
+            "SetReviewBodyFormat",
+            vec![readonly_signer("reviewer"), writable("review", Vec::new())],
+            None,
+        ),
+        31 => (
+            "FollowReviewer",
+            vec![
+                signer("follower"),
+                writable(
+                    "follow",
+                    vec![
+                        literal(Follow::SEED),
+                        Seed::Account(0),
+                        argument("reviewer"),
+                    ],
+                ),
+                system(),
+            ],
+            None,
+        ),
+        32 => (
+            "UnfollowReviewer",
+            vec![signer("follower"), writable("follow", Vec::new())],
+            None,
+        ),
+        33 => (
+            "SetCommentSettings",
+            vec![readonly_signer("reviewer"), writable("review", Vec::new())],
+            None,
+        ),
+        34 => (
+            "MuteUser",
+            vec![
+                signer("reviewer"),
+                writable(
+                    "mute",
+                    vec![literal(Mute::SEED), Seed::Account(0), argument("user")],
+                ),
+                system(),
+            ],
+            None,
+        ),
+        35 => (
+            "UnmuteUser",
+            vec![signer("reviewer"), writable("mute", Vec::new())],
+            None,
+        ),
+        36 => ("ReplyToComment", comment_accounts(true), proofs()),
+        37 => (
+            "AddPagedComment",
+            vec![
+                signer("commenter"),
+                readonly("review", Vec::new()),
+                writable("counter", counter_seeds(1)),
+                writable(
+                    "page",
+                    vec![
+                        literal(CommentPage::SEED),
+                        Seed::Account(1),
+                        field("counter.page"),
+                    ],
+                ),
+                system(),
+                writable("config", config_seeds()),
+                readonly("mute", mute_seeds()),
+                writable("global_stats", global_stats_seeds()),
+                writable("hot_feed", hot_feed_seeds()),
+            ],
+            proofs(),
+        ),
+        38 => (
+            "WriteSnapshot",
+            vec![
+                signer("authority"),
+                readonly("config", config_seeds()),
+                writable(
+                    "snapshot",
+                    vec![literal(ReviewSnapshot::SEED), argument("slot")],
+                ),
+                system(),
+            ],
+            Some("(review, counter) pairs in ascending review order".to_string()),
+        ),
+        39 => ("RecordTip", record_tip(), None),
+        40 => ("SetTipProgram", config_update(), None),
+        41 => (
+            "RecomputeStats",
+            vec![
+                signer("payer"),
+                readonly("movie", Vec::new()),
+                writable(
+                    "movie_stats",
+                    vec![literal(MovieStats::SEED), Seed::Account(1)],
+                ),
+                system(),
+            ],
+            Some("the movie's reviews in ascending order".to_string()),
+        ),
+        42 => (
+            "UpdateRating",
+            vec![
+                readonly_signer("reviewer"),
+                writable("review", Vec::new()),
+                writable("profile", profile_seeds(0)),
+                readonly("config", config_seeds()),
+            ],
+            Some(
+                "for a review keyed by a registered movie, its rating index; with a session key \
+                 as the reviewer, the session"
+                    .to_string(),
+            ),
+        ),
+        43 => (
+            "AppendToDescription",
+            vec![signer("reviewer"), writable("review", Vec::new()), system()],
+            None,
+        ),
+        44 => ("SetContentLimits", config_update(), None),
+        45 => ("SetRatingScale", config_update(), None),
+        46 => (
+            "DeleteReview",
+            vec![
+                signer("reviewer"),
+                writable("review", Vec::new()),
+                writable("counter", counter_seeds(1)),
+                writable("profile", profile_seeds(0)),
+                readonly("config", config_seeds()),
+                writable("global_stats", global_stats_seeds()),
+                writable("movie_stats", Vec::new()),
+                writable("rating_index", Vec::new()),
+                system(),
+            ],
+            Some("(comment, commenter) pairs to close with cascade".to_string()),
+        ),
+        47 => (
+            "RepairCommentAddress",
+            vec![
+                signer("payer"),
+                readonly("review", Vec::new()),
+                writable("legacy_comment", Vec::new()),
+                writable(
+                    "comment",
+                    vec![
+                        Seed::Account(1),
+                        Seed::CommentIndex("legacy_comment.count".to_string()),
+                    ],
+                ),
+                system(),
+            ],
+            None,
+        ),
+        48 => {
+            let (accounts, remaining) = add_movie_review();
+            ("AddMovieReviewIdempotent", accounts, remaining)
+        }
+        49 => ("GetRequiredAccounts", Vec::new(), None),
+        50 => ("SetCpiCallers", config_update(), None),
+        51 => (
+            "CreateSession",
+            vec![
+                signer("authority"),
+                writable(
+                    "session",
+                    vec![
+                        literal(Session::SEED),
+                        Seed::Account(0),
+                        argument("session_key"),
+                    ],
+                ),
+                system(),
+                readonly("config", config_seeds()),
+            ],
+            None,
+        ),
+        52 => (
+            "RevokeSession",
+            vec![signer("authority"), writable("session", Vec::new())],
+            None,
+        ),
+        53 => (
+            "AddRelayedReview",
+            vec![
+                signer("relayer"),
+                readonly("reviewer", Vec::new()),
+                program("instructions_sysvar", sysvar::instructions::ID),
+                writable("review", vec![Seed::Account(1), argument("title")]),
+                writable("counter", counter_seeds(3)),
+                system(),
+                writable("config", config_seeds()),
+                writable("global_stats", global_stats_seeds()),
+                writable("profile", profile_seeds(1)),
+                writable("rent_payer", rent_payer_seeds(3)),
+            ],
+            authored_review_proofs("reviewer"),
+        ),
+        54 => ("SetFeatures", config_update(), None),
+        55 => ("SetRemovalWindow", config_update(), None),
+        56 => (
+            "ProposeRemoval",
+            vec![
+                signer("authority"),
+                readonly("config", config_seeds()),
+                writable("review", Vec::new()),
+                writable("pending_removal", pending_removal_seeds(2)),
+                system(),
+                audit_log(),
+            ],
+            None,
+        ),
+        57 => (
+            "AppealRemoval",
+            vec![
+                signer("author"),
+                readonly("review", Vec::new()),
+                writable("pending_removal", pending_removal_seeds(1)),
+            ],
+            None,
+        ),
+        58 => (
+            "ResolveAppeal",
+            vec![
+                signer("authority"),
+                readonly("config", config_seeds()),
+                readonly("review", Vec::new()),
+                writable("pending_removal", pending_removal_seeds(2)),
+                system(),
+                audit_log(),
+            ],
+            None,
+        ),
+        59 => (
+            "FinalizeRemoval",
+            vec![
+                signer("authority"),
+                writable("pending_removal", pending_removal_seeds(4)),
+                audit_log(),
+                writable("author", Vec::new()),
+                writable("review", Vec::new()),
+                writable("counter", counter_seeds(4)),
+                writable("profile", profile_seeds(3)),
+                readonly("config", config_seeds()),
+                writable("global_stats", global_stats_seeds()),
+                writable("movie_stats", Vec::new()),
+                writable("rating_index", Vec::new()),
+                system(),
+            ],
+            Some("(comment, commenter) pairs to close with cascade".to_string()),
+        ),
+        60 => (
+            "UpdateBodyHash",
+            vec![
+                signer("reviewer"),
+                writable("review", Vec::new()),
+                writable("body_commitment", body_commitment_seeds(1)),
+                system(),
+            ],
+            None,
+        ),
+        61 => (
+            "RecordFingerprint",
+            vec![
+                signer("payer"),
+                writable("review", Vec::new()),
+                writable("fingerprint", fingerprint_seeds(1)),
+                system(),
+            ],
+            None,
+        ),
+        62 => (
+            "ChallengeDuplicate",
+            vec![
+                signer("challenger"),
+                writable("review", Vec::new()),
+                readonly("review_fingerprint", fingerprint_seeds(1)),
+                writable("original", Vec::new()),
+                readonly("original_fingerprint", fingerprint_seeds(3)),
+                writable(
+                    "challenge",
+                    vec![
+                        literal(DuplicateChallenge::SEED),
+                        Seed::Account(1),
+                        Seed::Account(3),
+                    ],
+                ),
+                system(),
+            ],
+            None,
+        ),
+        63 => ("SetAgeGate", config_update(), None),
+        64 => (
+            "VoteOnComment",
+            vec![
+                signer("voter"),
+                readonly("comment", Vec::new()),
+                writable(
+                    "vote",
+                    vec![
+                        literal(CommentVote::SEED),
+                        Seed::Account(1),
+                        Seed::Account(0),
+                    ],
+                ),
+                writable("karma", vec![literal(CommentKarma::SEED), Seed::Account(1)]),
+                writable("config", config_seeds()),
+                system(),
+                writable("hot_feed", hot_feed_seeds()),
+            ],
+            Some("token accounts proving a fee exemption".to_string()),
+        ),
+        65 => ("SetHideThreshold", config_update(), None),
+        66 => ("SetEditBurn", config_update(), None),
+        67 => (
+            "SetCoAuthors",
+            vec![
+                signer("reviewer"),
+                writable("review", Vec::new()),
+                writable(
+                    "co_authors",
+                    vec![literal(ReviewCoAuthors::SEED), Seed::Account(1)],
+                ),
+                system(),
+            ],
+            None,
+        ),
+        68 => (
+            "AcceptCoAuthorship",
+            vec![
+                readonly_signer("co_author"),
+                writable("co_authors", Vec::new()),
+            ],
+            None,
+        ),
+        69 => (
+            "SetCriticAuthority",
+            vec![
+                signer("reviewer"),
+                writable("profile", profile_seeds(0)),
+                system(),
+            ],
+            None,
+        ),
+        70 => (
+            "FundTranslationBounty",
+            vec![
+                signer("reviewer"),
+                readonly("review", Vec::new()),
+                readonly("translation", translation_seeds()),
+                writable("bounty", translation_bounty_seeds()),
+                system(),
+            ],
+            None,
+        ),
+        71 => (
+            "SubmitTranslation",
+            vec![
+                signer("translator"),
+                readonly("review", Vec::new()),
+                writable("bounty", translation_bounty_seeds()),
+                writable("submission", translation_submission_seeds(2, 0)),
+                system(),
+            ],
+            None,
+        ),
+        72 => {
+            let mut accounts = translation_accounts();
+            accounts.push(writable("bounty", translation_bounty_seeds()));
+            accounts.push(writable("submission", translation_submission_seeds(3, 5)));
+            accounts.push(writable("translator", Vec::new()));
+            accounts.push(system());
+            ("ApproveTranslation", accounts, None)
+        }
+        73 => (
+            "CancelTranslationBounty",
+            vec![
+                signer("reviewer"),
+                readonly("review", Vec::new()),
+                writable("bounty", translation_bounty_seeds()),
+            ],
+            None,
+        ),
+        74 => (
+            "WithdrawTranslationSubmission",
+            vec![
+                signer("translator"),
+                readonly("review", Vec::new()),
+                readonly("bounty", translation_bounty_seeds()),
+                writable("submission", translation_submission_seeds(2, 0)),
+            ],
+            None,
+        ),
+        75 => (
+            "SetMovieReleaseDate",
+            vec![
+                signer("authority"),
+                writable("movie", Vec::new()),
+                readonly("config", config_seeds()),
+                system(),
+                audit_log(),
+            ],
+            None,
+        ),
+        76 => (
+            "IndexMovieReview",
+            vec![
+                signer("payer"),
+                readonly("movie", Vec::new()),
+                readonly("review", vec![field("review.reviewer"), Seed::Account(1)]),
+                readonly("config", config_seeds()),
+                writable("rating_index", rating_index_seeds(1)),
+                system(),
+            ],
+            None,
+        ),
+        77 => {
+            let (accounts, remaining) = add_movie_review();
+            ("AddMovieReviewV2", accounts, remaining)
+        }
+        78 => {
+            let (accounts, remaining) = update_movie_review();
+            ("UpdateMovieReviewV2", accounts, remaining)
+        }
+        79 => ("RecordSolTip", record_tip(), None),
+        _ => return None,
+    };
+
+    Some(RequiredAccounts {
+        instruction: instruction.to_string(),
+        accounts,
+        remaining,
+    })
+}
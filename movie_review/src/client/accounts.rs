@@ -0,0 +1,68 @@
+//! Lists the program's reviews and comments through `getProgramAccounts`,
+//! for backends that serve them without running an indexer.
+
+use solana_client::{
+    client_error::ClientError,
+    nonblocking::rpc_client::RpcClient,
+    rpc_filter::{Memcmp, RpcFilterType},
+};
+use solana_program::pubkey::Pubkey;
+
+use super::subscribe::{discriminator_filter, program_config};
+use crate::abi;
+use crate::decoder::{decode_account, MovieReviewAccount};
+use crate::state::{MovieAccountState, MovieComment};
+
+/// Every review under `program_id`, optionally only those written by
+/// `reviewer`, with their addresses.
+pub async fn fetch_reviews(
+    rpc: &RpcClient,
+    program_id: &Pubkey,
+    reviewer: Option<&Pubkey>,
+) -> Result<Vec<(Pubkey, MovieAccountState)>, ClientError> {
+    let mut filters = vec![discriminator_filter(MovieAccountState::DISCRIMINATOR)];
+    if let Some(reviewer) = reviewer {
+        filters.push(RpcFilterType::Memcmp(Memcmp::new_base58_encoded(
+            abi::review::REVIEWER.offset,
+            reviewer.as_ref(),
+        )));
+    }
+    let accounts = rpc
+        .get_program_accounts_with_config(program_id, program_config(filters))
+        .await?;
+    Ok(accounts
+        .into_iter()
+        .filter_map(|(address, account)| match decode_account(&account.data) {
+            Ok(MovieReviewAccount::Review(review)) => Some((address, review)),
+            _ => None,
+        })
+        .collect())
+}
+
+/// The comments on `review` with their addresses, in the order they were
+/// written.
+pub async fn fetch_comments(
+    rpc: &RpcClient,
+    program_id: &Pubkey,
+    review: &Pubkey,
+) -> Result<Vec<(Pubkey, MovieComment)>, ClientError> {
+    let filters = vec![
+        discriminator_filter(MovieComment::DISCRIMINATOR),
+        RpcFilterType::Memcmp(Memcmp::new_base58_encoded(
+            abi::comment::REVIEWER.offset,
+            review.as_ref(),
+        )),
+    ];
+    let accounts = rpc
+        .get_program_accounts_with_config(program_id, program_config(filters))
+        .await?;
+    let mut comments: Vec<_> = accounts
+        .into_iter()
+        .filter_map(|(address, account)| match decode_account(&account.data) {
+            Ok(MovieReviewAccount::Comment(comment)) => Some((address, comment)),
+            _ => None,
+        })
+        .collect();
+    comments.sort_by_key(|(_, comment)| comment.count);
+    Ok(comments)
+}
@@ -0,0 +1,55 @@
+//! Hashing and verification of review bodies stored off-chain, whose review
+//! only keeps the URI in its description and the hash in a `BodyCommitment`.
+
+use solana_program::{hash::hash, pubkey::Pubkey};
+use thiserror::Error;
+
+use crate::state::{BodyCommitment, BodyFormat, MovieAccountState};
+
+#[derive(Clone, Debug, Eq, Error, PartialEq)]
+pub enum BodyError {
+    #[error("review body is stored on-chain")]
+    NotOffChain,
+    #[error("commitment belongs to review {0}")]
+    WrongReview(Pubkey),
+    #[error("body hashes to {actual:?}, the review committed to {expected:?}")]
+    HashMismatch {
+        expected: [u8; 32],
+        actual: [u8; 32],
+    },
+}
+
+/// The hash `UpdateBodyHash` commits to for `body`.
+pub fn hash_body(body: &[u8]) -> [u8; 32] {
+    hash(body).to_bytes()
+}
+
+/// Where the body of an off-chain review is stored, `None` for a review
+/// whose body is its description.
+pub fn body_uri(review: &MovieAccountState) -> Option<&str> {
+    (review.body_format == BodyFormat::OffChain).then_some(review.description.as_str())
+}
+
+/// Checks that `body`, fetched from the review's `body_uri`, is the one the
+/// review at `address` committed to.
+pub fn verify_body(
+    address: &Pubkey,
+    review: &MovieAccountState,
+    commitment: &BodyCommitment,
+    body: &[u8],
+) -> Result<(), BodyError> {
+    if body_uri(review).is_none() {
+        return Err(BodyError::NotOffChain);
+    }
+    if commitment.review != *address {
+        return Err(BodyError::WrongReview(commitment.review));
+    }
+    let actual = hash_body(body);
+    if actual != commitment.hash {
+        return Err(BodyError::HashMismatch {
+            expected: commitment.hash,
+            actual,
+        });
+    }
+    Ok(())
+}
@@ -0,0 +1,1956 @@
+use solana_program::{
+    bpf_loader_upgradeable,
+    clock::UnixTimestamp,
+    ed25519_program,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    system_program, sysvar,
+};
+
+use super::pda::{
+    find_audit_log_address, find_body_commitment_address, find_co_authors_address,
+    find_collection_authority_address, find_comment_address, find_comment_counter_address,
+    find_comment_karma_address, find_comment_page_address, find_comment_vote_address,
+    find_config_address, find_duplicate_challenge_address, find_external_id_address,
+    find_featured_review_address, find_fingerprint_address, find_follow_address,
+    find_foreign_review_address, find_global_stats_address, find_hot_feed_address,
+    find_movie_address, find_movie_review_address, find_movie_stats_address, find_mute_address,
+    find_pass_mint_address, find_pending_removal_address, find_rating_index_address,
+    find_rent_payer_address, find_review_address, find_review_collection_address,
+    find_review_edits_address, find_review_nft_mint_address, find_reviewer_pass_address,
+    find_reviewer_profile_address, find_session_address, find_snapshot_address,
+    find_title_filter_address, find_translation_address, find_translation_bounty_address,
+    find_translation_submission_address,
+};
+use crate::instruction::{MovieInstruction, DRY_RUN_FLAG, INSTRUCTION_DISCRIMINATORS};
+use crate::programs::{
+    associated_token, associated_token_address, master_edition_address, metadata_address,
+    token_2022, token_metadata,
+};
+use crate::state::{
+    BodyFormat, CommentPolicy, CommentVoteKind, ContentLimits, FeeSchedule, RatingScale,
+};
+
+/// To skip the fee without a pass, append the reviewer's token account of the
+/// fee exemption mint.
+pub fn add_movie_review(
+    program_id: &Pubkey,
+    reviewer: &Pubkey,
+    title: &str,
+    rating: u16,
+    description: &str,
+) -> Instruction {
+    let (review, _) = find_review_address(program_id, reviewer, title);
+    let (counter, _) = find_comment_counter_address(program_id, &review);
+    Instruction::new_with_bytes(
+        *program_id,
+        &MovieInstruction::AddMovieReviewV2 {
+            title: title.to_string(),
+            rating,
+            description: description.to_string(),
+        }
+        .pack(),
+        vec![
+            AccountMeta::new(*reviewer, true),
+            AccountMeta::new(review, false),
+            AccountMeta::new(counter, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new(find_config_address(program_id).0, false),
+            AccountMeta::new(find_global_stats_address(program_id).0, false),
+            AccountMeta::new(find_reviewer_profile_address(program_id, reviewer).0, false),
+            AccountMeta::new_readonly(find_reviewer_pass_address(program_id, reviewer), false),
+        ],
+    )
+}
+
+/// `add_movie_review` that is safe to resend: it succeeds without effect if
+/// the same review already landed.
+pub fn add_movie_review_idempotent(
+    program_id: &Pubkey,
+    reviewer: &Pubkey,
+    title: &str,
+    rating: u16,
+    description: &str,
+) -> Instruction {
+    let mut instruction = add_movie_review(program_id, reviewer, title, rating, description);
+    instruction.data = MovieInstruction::AddMovieReviewIdempotent {
+        title: title.to_string(),
+        rating,
+        description: description.to_string(),
+    }
+    .pack();
+    instruction
+}
+
+/// The review a reviewer signed for `add_relayed_review`.
+pub struct RelayedReviewArgs<'a> {
+    pub title: &'a str,
+    pub rating: u16,
+    pub description: &'a str,
+    /// The reviewer's profile `relay_nonce`, or 0 before their first review.
+    pub nonce: u64,
+    pub expires_slot: u64,
+}
+
+/// `add_movie_review` paid for by `relayer`. Place it right after
+/// `verify_reviewer_signature` over the same review. To skip the fee without
+/// a pass, append the relayer's token account of the fee exemption mint.
+pub fn add_relayed_review(
+    program_id: &Pubkey,
+    relayer: &Pubkey,
+    reviewer: &Pubkey,
+    args: &RelayedReviewArgs,
+) -> Instruction {
+    let (review, _) = find_review_address(program_id, reviewer, args.title);
+    let (counter, _) = find_comment_counter_address(program_id, &review);
+    Instruction::new_with_bytes(
+        *program_id,
+        &MovieInstruction::AddRelayedReview {
+            title: args.title.to_string(),
+            rating: args.rating,
+            description: args.description.to_string(),
+            nonce: args.nonce,
+            expires_slot: args.expires_slot,
+        }
+        .pack(),
+        vec![
+            AccountMeta::new(*relayer, true),
+            AccountMeta::new_readonly(*reviewer, false),
+            AccountMeta::new_readonly(sysvar::instructions::ID, false),
+            AccountMeta::new(review, false),
+            AccountMeta::new(counter, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new(find_config_address(program_id).0, false),
+            AccountMeta::new(find_global_stats_address(program_id).0, false),
+            AccountMeta::new(find_reviewer_profile_address(program_id, reviewer).0, false),
+            AccountMeta::new(find_rent_payer_address(program_id, &review).0, false),
+            AccountMeta::new_readonly(find_reviewer_pass_address(program_id, reviewer), false),
+        ],
+    )
+}
+
+/// The ed25519 program instruction verifying `signature`, the reviewer's
+/// signature over `MovieAccountState::relayed_review_message`.
+pub fn verify_reviewer_signature(
+    reviewer: &Pubkey,
+    signature: &[u8; 64],
+    message: &[u8],
+) -> Instruction {
+    // The offsets header, then the key, signature and message it points to
+    let key_offset: u16 = 16;
+    let signature_offset = key_offset + 32;
+    let message_offset = signature_offset + 64;
+    let mut data = vec![1, 0];
+    for offset in [
+        signature_offset,
+        u16::MAX,
+        key_offset,
+        u16::MAX,
+        message_offset,
+        message.len() as u16,
+        u16::MAX,
+    ] {
+        data.extend_from_slice(&offset.to_le_bytes());
+    }
+    data.extend_from_slice(reviewer.as_ref());
+    data.extend_from_slice(signature);
+    data.extend_from_slice(message);
+    Instruction::new_with_bytes(ed25519_program::ID, &data, Vec::new())
+}
+
+/// Edits after the first burn tokens when the config sets an edit burn: for
+/// those, append the reviewer's token account of the burn mint and the mint,
+/// both writable, and the mint's token program.
+pub fn update_movie_review(
+    program_id: &Pubkey,
+    reviewer: &Pubkey,
+    title: &str,
+    rating: u16,
+    description: &str,
+) -> Instruction {
+    let (review, _) = find_review_address(program_id, reviewer, title);
+    Instruction::new_with_bytes(
+        *program_id,
+        &MovieInstruction::UpdateMovieReviewV2 {
+            title: title.to_string(),
+            rating,
+            description: description.to_string(),
+        }
+        .pack(),
+        vec![
+            AccountMeta::new(*reviewer, true),
+            AccountMeta::new(review, false),
+            AccountMeta::new(find_reviewer_profile_address(program_id, reviewer).0, false),
+            AccountMeta::new_readonly(find_config_address(program_id).0, false),
+            AccountMeta::new(find_review_edits_address(program_id, &review).0, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+    )
+}
+
+/// `review` is the reviewer's review keyed by title; see
+/// `update_rating_for_movie` for reviews keyed by a registered movie.
+pub fn update_rating(
+    program_id: &Pubkey,
+    reviewer: &Pubkey,
+    review: &Pubkey,
+    rating: u16,
+) -> Instruction {
+    Instruction::new_with_bytes(
+        *program_id,
+        &MovieInstruction::UpdateRating { rating }.pack(),
+        vec![
+            AccountMeta::new_readonly(*reviewer, true),
+            AccountMeta::new(*review, false),
+            AccountMeta::new(find_reviewer_profile_address(program_id, reviewer).0, false),
+            AccountMeta::new_readonly(find_config_address(program_id).0, false),
+        ],
+    )
+}
+
+/// `title` must be the registered title of the movie, whose rating index is
+/// appended.
+pub fn update_rating_for_movie(
+    program_id: &Pubkey,
+    reviewer: &Pubkey,
+    title: &str,
+    rating: u16,
+) -> Instruction {
+    let (movie, _) = find_movie_address(program_id, title);
+    let (review, _) = find_movie_review_address(program_id, reviewer, &movie);
+    let mut instruction = update_rating(program_id, reviewer, &review, rating);
+    instruction.accounts.push(AccountMeta::new(
+        find_rating_index_address(program_id, &movie).0,
+        false,
+    ));
+    instruction
+}
+
+pub fn append_to_description(
+    program_id: &Pubkey,
+    reviewer: &Pubkey,
+    review: &Pubkey,
+    text: &str,
+) -> Instruction {
+    Instruction::new_with_bytes(
+        *program_id,
+        &MovieInstruction::AppendToDescription {
+            text: text.to_string(),
+        }
+        .pack(),
+        vec![
+            AccountMeta::new(*reviewer, true),
+            AccountMeta::new(*review, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+    )
+}
+
+/// `comment_count` is the review's current counter value, i.e. the index the
+/// new comment will be stored at, and `reviewer` its author. Append the
+/// accounts the review's comment policy asks for, see `set_comment_settings`.
+pub fn add_comment(
+    program_id: &Pubkey,
+    commenter: &Pubkey,
+    review: &Pubkey,
+    reviewer: &Pubkey,
+    comment_count: u64,
+    comment: &str,
+    media_uri: Option<&str>,
+) -> Instruction {
+    Instruction::new_with_bytes(
+        *program_id,
+        &MovieInstruction::AddComment {
+            comment: comment.to_string(),
+            media_uri: media_uri.map(str::to_string),
+        }
+        .pack(),
+        comment_accounts(program_id, commenter, review, reviewer, comment_count, None),
+    )
+}
+
+/// Like `add_comment`, replying to the comment at `parent_index`.
+#[allow(clippy::too_many_arguments)]
+pub fn reply_to_comment(
+    program_id: &Pubkey,
+    commenter: &Pubkey,
+    review: &Pubkey,
+    reviewer: &Pubkey,
+    comment_count: u64,
+    parent_index: u64,
+    comment: &str,
+    media_uri: Option<&str>,
+) -> Instruction {
+    Instruction::new_with_bytes(
+        *program_id,
+        &MovieInstruction::ReplyToComment {
+            comment: comment.to_string(),
+            media_uri: media_uri.map(str::to_string),
+        }
+        .pack(),
+        comment_accounts(
+            program_id,
+            commenter,
+            review,
+            reviewer,
+            comment_count,
+            Some(parent_index),
+        ),
+    )
+}
+
+/// `page` is the review's current comment page, read from its counter.
+pub fn add_paged_comment(
+    program_id: &Pubkey,
+    commenter: &Pubkey,
+    review: &Pubkey,
+    reviewer: &Pubkey,
+    page: u64,
+    comment: &str,
+) -> Instruction {
+    let (counter, _) = find_comment_counter_address(program_id, review);
+    let (page_pda, _) = find_comment_page_address(program_id, review, page);
+    Instruction::new_with_bytes(
+        *program_id,
+        &MovieInstruction::AddPagedComment {
+            comment: comment.to_string(),
+        }
+        .pack(),
+        vec![
+            AccountMeta::new(*commenter, true),
+            AccountMeta::new_readonly(*review, false),
+            AccountMeta::new(counter, false),
+            AccountMeta::new(page_pda, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new(find_config_address(program_id).0, false),
+            AccountMeta::new_readonly(find_mute_address(program_id, reviewer, commenter).0, false),
+            AccountMeta::new(find_global_stats_address(program_id).0, false),
+            AccountMeta::new(find_hot_feed_address(program_id).0, false),
+        ],
+    )
+}
+
+fn comment_accounts(
+    program_id: &Pubkey,
+    commenter: &Pubkey,
+    review: &Pubkey,
+    reviewer: &Pubkey,
+    comment_count: u64,
+    parent_index: Option<u64>,
+) -> Vec<AccountMeta> {
+    let (counter, _) = find_comment_counter_address(program_id, review);
+    let (comment_pda, _) = find_comment_address(program_id, review, comment_count);
+    let mut accounts = vec![
+        AccountMeta::new(*commenter, true),
+        AccountMeta::new_readonly(*review, false),
+        AccountMeta::new(counter, false),
+        AccountMeta::new(comment_pda, false),
+        AccountMeta::new_readonly(system_program::ID, false),
+        AccountMeta::new(find_config_address(program_id).0, false),
+        AccountMeta::new_readonly(find_mute_address(program_id, reviewer, commenter).0, false),
+    ];
+    if let Some(parent_index) = parent_index {
+        // Writable so a v1 parent can be upgraded in place
+        accounts.push(AccountMeta::new(
+            find_comment_address(program_id, review, parent_index).0,
+            false,
+        ));
+    }
+    accounts.push(AccountMeta::new(
+        find_global_stats_address(program_id).0,
+        false,
+    ));
+    accounts.push(AccountMeta::new(find_hot_feed_address(program_id).0, false));
+    accounts
+}
+
+pub fn migrate_account(program_id: &Pubkey, account: &Pubkey) -> Instruction {
+    Instruction::new_with_bytes(
+        *program_id,
+        &MovieInstruction::MigrateAccount.pack(),
+        vec![AccountMeta::new(*account, false)],
+    )
+}
+
+/// Moves comment `count` on `review` from its little-endian address to the
+/// one `find_comment_address` derives.
+pub fn repair_comment_address(
+    program_id: &Pubkey,
+    payer: &Pubkey,
+    review: &Pubkey,
+    count: u64,
+) -> Instruction {
+    let (legacy_comment, _) =
+        Pubkey::find_program_address(&[review.as_ref(), &count.to_le_bytes()], program_id);
+    Instruction::new_with_bytes(
+        *program_id,
+        &MovieInstruction::RepairCommentAddress.pack(),
+        vec![
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(*review, false),
+            AccountMeta::new(legacy_comment, false),
+            AccountMeta::new(find_comment_address(program_id, review, count).0, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+    )
+}
+
+pub fn initialize_config(
+    program_id: &Pubkey,
+    authority: &Pubkey,
+    allow_self_comments: bool,
+    require_reviewer_pass: bool,
+) -> Instruction {
+    Instruction::new_with_bytes(
+        *program_id,
+        &MovieInstruction::InitializeConfig {
+            allow_self_comments,
+            require_reviewer_pass,
+        }
+        .pack(),
+        vec![
+            AccountMeta::new(*authority, true),
+            AccountMeta::new(find_config_address(program_id).0, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new(find_audit_log_address(program_id).0, false),
+        ],
+    )
+}
+
+pub fn update_config(
+    program_id: &Pubkey,
+    authority: &Pubkey,
+    allow_self_comments: bool,
+    require_reviewer_pass: bool,
+) -> Instruction {
+    Instruction::new_with_bytes(
+        *program_id,
+        &MovieInstruction::UpdateConfig {
+            allow_self_comments,
+            require_reviewer_pass,
+        }
+        .pack(),
+        // The authority pays to grow configs created by older versions
+        vec![
+            AccountMeta::new(*authority, true),
+            AccountMeta::new(find_config_address(program_id).0, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new(find_audit_log_address(program_id).0, false),
+        ],
+    )
+}
+
+pub fn register_movie(program_id: &Pubkey, payer: &Pubkey, title: &str) -> Instruction {
+    let (movie, _) = find_movie_address(program_id, title);
+    Instruction::new_with_bytes(
+        *program_id,
+        &MovieInstruction::RegisterMovie {
+            title: title.to_string(),
+        }
+        .pack(),
+        vec![
+            AccountMeta::new(*payer, true),
+            AccountMeta::new(movie, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+    )
+}
+
+/// `title` must be the registered title of the movie.
+pub fn add_review_for_movie(
+    program_id: &Pubkey,
+    reviewer: &Pubkey,
+    title: &str,
+    rating: u16,
+    description: &str,
+) -> Instruction {
+    let (movie, _) = find_movie_address(program_id, title);
+    let (review, _) = find_movie_review_address(program_id, reviewer, &movie);
+    let (legacy_review, _) = find_review_address(program_id, reviewer, title);
+    let (counter, _) = find_comment_counter_address(program_id, &review);
+    Instruction::new_with_bytes(
+        *program_id,
+        &MovieInstruction::AddReviewForMovie {
+            rating,
+            description: description.to_string(),
+        }
+        .pack(),
+        vec![
+            AccountMeta::new(*reviewer, true),
+            AccountMeta::new_readonly(movie, false),
+            AccountMeta::new(review, false),
+            AccountMeta::new_readonly(legacy_review, false),
+            AccountMeta::new(counter, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new(find_config_address(program_id).0, false),
+            AccountMeta::new(find_global_stats_address(program_id).0, false),
+            AccountMeta::new(find_reviewer_profile_address(program_id, reviewer).0, false),
+            AccountMeta::new(find_movie_stats_address(program_id, &movie).0, false),
+            AccountMeta::new(find_rating_index_address(program_id, &movie).0, false),
+            AccountMeta::new_readonly(find_reviewer_pass_address(program_id, reviewer), false),
+        ],
+    )
+}
+
+/// Paid edits append the burn accounts, see `update_movie_review`.
+pub fn update_review_for_movie(
+    program_id: &Pubkey,
+    reviewer: &Pubkey,
+    title: &str,
+    rating: u16,
+    description: &str,
+) -> Instruction {
+    let (movie, _) = find_movie_address(program_id, title);
+    let (review, _) = find_movie_review_address(program_id, reviewer, &movie);
+    Instruction::new_with_bytes(
+        *program_id,
+        &MovieInstruction::UpdateMovieReviewV2 {
+            title: title.to_string(),
+            rating,
+            description: description.to_string(),
+        }
+        .pack(),
+        vec![
+            AccountMeta::new(*reviewer, true),
+            AccountMeta::new(review, false),
+            AccountMeta::new(find_reviewer_profile_address(program_id, reviewer).0, false),
+            AccountMeta::new_readonly(find_config_address(program_id).0, false),
+            AccountMeta::new(find_review_edits_address(program_id, &review).0, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new_readonly(movie, false),
+            AccountMeta::new(find_rating_index_address(program_id, &movie).0, false),
+        ],
+    )
+}
+
+/// Files `reviewer`'s review of the registered movie `title` in the movie's
+/// rating index, at `payer`'s expense.
+pub fn index_movie_review(
+    program_id: &Pubkey,
+    payer: &Pubkey,
+    reviewer: &Pubkey,
+    title: &str,
+) -> Instruction {
+    let (movie, _) = find_movie_address(program_id, title);
+    Instruction::new_with_bytes(
+        *program_id,
+        &MovieInstruction::IndexMovieReview.pack(),
+        vec![
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(movie, false),
+            AccountMeta::new_readonly(
+                find_movie_review_address(program_id, reviewer, &movie).0,
+                false,
+            ),
+            AccountMeta::new_readonly(find_config_address(program_id).0, false),
+            AccountMeta::new(find_rating_index_address(program_id, &movie).0, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+    )
+}
+
+pub fn link_legacy_review(program_id: &Pubkey, reviewer: &Pubkey, title: &str) -> Instruction {
+    let (movie, _) = find_movie_address(program_id, title);
+    let (legacy_review, _) = find_review_address(program_id, reviewer, title);
+    let (link, _) = find_movie_review_address(program_id, reviewer, &movie);
+    Instruction::new_with_bytes(
+        *program_id,
+        &MovieInstruction::LinkLegacyReview.pack(),
+        vec![
+            AccountMeta::new(*reviewer, true),
+            AccountMeta::new_readonly(movie, false),
+            AccountMeta::new_readonly(legacy_review, false),
+            AccountMeta::new(link, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+    )
+}
+
+/// `commitment` is `MovieAccountState::authorship_commitment(reviewer, salt)`;
+/// keep the salt to reveal authorship later.
+pub fn add_anonymous_review(
+    program_id: &Pubkey,
+    payer: &Pubkey,
+    commitment: &Pubkey,
+    title: &str,
+    rating: u16,
+    description: &str,
+) -> Instruction {
+    let (review, _) = find_review_address(program_id, commitment, title);
+    let (counter, _) = find_comment_counter_address(program_id, &review);
+    Instruction::new_with_bytes(
+        *program_id,
+        &MovieInstruction::AddAnonymousReview {
+            commitment: *commitment,
+            title: title.to_string(),
+            rating,
+            description: description.to_string(),
+        }
+        .pack(),
+        vec![
+            AccountMeta::new(*payer, true),
+            AccountMeta::new(review, false),
+            AccountMeta::new(counter, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new(find_config_address(program_id).0, false),
+            AccountMeta::new(find_global_stats_address(program_id).0, false),
+            AccountMeta::new_readonly(find_reviewer_pass_address(program_id, payer), false),
+        ],
+    )
+}
+
+pub fn reveal_authorship(
+    program_id: &Pubkey,
+    reviewer: &Pubkey,
+    review: &Pubkey,
+    salt: [u8; 32],
+) -> Instruction {
+    Instruction::new_with_bytes(
+        *program_id,
+        &MovieInstruction::RevealAuthorship { salt }.pack(),
+        vec![
+            AccountMeta::new_readonly(*reviewer, true),
+            AccountMeta::new(*review, false),
+        ],
+    )
+}
+
+pub fn initialize_pass_mint(program_id: &Pubkey, authority: &Pubkey) -> Instruction {
+    Instruction::new_with_bytes(
+        *program_id,
+        &MovieInstruction::InitializePassMint.pack(),
+        vec![
+            AccountMeta::new(*authority, true),
+            AccountMeta::new_readonly(find_config_address(program_id).0, false),
+            AccountMeta::new(find_pass_mint_address(program_id).0, false),
+            AccountMeta::new_readonly(token_2022::ID, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new(find_audit_log_address(program_id).0, false),
+        ],
+    )
+}
+
+pub fn issue_reviewer_pass(
+    program_id: &Pubkey,
+    authority: &Pubkey,
+    reviewer: &Pubkey,
+) -> Instruction {
+    Instruction::new_with_bytes(
+        *program_id,
+        &MovieInstruction::IssueReviewerPass.pack(),
+        vec![
+            AccountMeta::new(*authority, true),
+            AccountMeta::new_readonly(find_config_address(program_id).0, false),
+            AccountMeta::new(find_pass_mint_address(program_id).0, false),
+            AccountMeta::new_readonly(*reviewer, false),
+            AccountMeta::new(find_reviewer_pass_address(program_id, reviewer), false),
+            AccountMeta::new_readonly(token_2022::ID, false),
+            AccountMeta::new_readonly(associated_token::ID, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new(find_audit_log_address(program_id).0, false),
+        ],
+    )
+}
+
+/// Creates the collection for `generation`, the config's current
+/// `collection_generation`.
+pub fn rotate_review_collection(
+    program_id: &Pubkey,
+    authority: &Pubkey,
+    generation: u32,
+    name: &str,
+    symbol: &str,
+    uri: &str,
+) -> Instruction {
+    let (collection_authority, _) = find_collection_authority_address(program_id);
+    let (collection_mint, _) = find_review_collection_address(program_id, generation);
+    Instruction::new_with_bytes(
+        *program_id,
+        &MovieInstruction::RotateReviewCollection {
+            name: name.to_string(),
+            symbol: symbol.to_string(),
+            uri: uri.to_string(),
+        }
+        .pack(),
+        vec![
+            AccountMeta::new(*authority, true),
+            AccountMeta::new(find_config_address(program_id).0, false),
+            AccountMeta::new_readonly(collection_authority, false),
+            AccountMeta::new(collection_mint, false),
+            AccountMeta::new(
+                associated_token_address(&collection_authority, &collection_mint, &spl_token::ID),
+                false,
+            ),
+            AccountMeta::new(metadata_address(&collection_mint), false),
+            AccountMeta::new(master_edition_address(&collection_mint), false),
+            AccountMeta::new_readonly(spl_token::ID, false),
+            AccountMeta::new_readonly(associated_token::ID, false),
+            AccountMeta::new_readonly(token_metadata::ID, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new(find_audit_log_address(program_id).0, false),
+        ],
+    )
+}
+
+/// Mints an NFT of `review` into `collection_mint`, the config's current
+/// `review_collection`.
+pub fn mint_review_nft(
+    program_id: &Pubkey,
+    reviewer: &Pubkey,
+    review: &Pubkey,
+    collection_mint: &Pubkey,
+    uri: &str,
+) -> Instruction {
+    let (nft_mint, _) = find_review_nft_mint_address(program_id, review);
+    Instruction::new_with_bytes(
+        *program_id,
+        &MovieInstruction::MintReviewNft {
+            uri: uri.to_string(),
+        }
+        .pack(),
+        vec![
+            AccountMeta::new(*reviewer, true),
+            AccountMeta::new_readonly(*review, false),
+            AccountMeta::new_readonly(find_config_address(program_id).0, false),
+            AccountMeta::new_readonly(find_collection_authority_address(program_id).0, false),
+            AccountMeta::new(nft_mint, false),
+            AccountMeta::new(
+                associated_token_address(reviewer, &nft_mint, &spl_token::ID),
+                false,
+            ),
+            AccountMeta::new(metadata_address(&nft_mint), false),
+            AccountMeta::new(master_edition_address(&nft_mint), false),
+            AccountMeta::new_readonly(*collection_mint, false),
+            AccountMeta::new(metadata_address(collection_mint), false),
+            AccountMeta::new_readonly(master_edition_address(collection_mint), false),
+            AccountMeta::new_readonly(spl_token::ID, false),
+            AccountMeta::new_readonly(associated_token::ID, false),
+            AccountMeta::new_readonly(token_metadata::ID, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+    )
+}
+
+pub fn set_release_oracle(
+    program_id: &Pubkey,
+    authority: &Pubkey,
+    oracle: &Pubkey,
+    feed_id: [u8; 32],
+) -> Instruction {
+    Instruction::new_with_bytes(
+        *program_id,
+        &MovieInstruction::SetReleaseOracle {
+            oracle: *oracle,
+            feed_id,
+        }
+        .pack(),
+        vec![
+            AccountMeta::new(*authority, true),
+            AccountMeta::new(find_config_address(program_id).0, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new(find_audit_log_address(program_id).0, false),
+        ],
+    )
+}
+
+pub fn mark_verified_release(
+    program_id: &Pubkey,
+    payer: &Pubkey,
+    title: &str,
+    attestation: &Pubkey,
+) -> Instruction {
+    Instruction::new_with_bytes(
+        *program_id,
+        &MovieInstruction::MarkVerifiedRelease.pack(),
+        vec![
+            AccountMeta::new(*payer, true),
+            AccountMeta::new(find_movie_address(program_id, title).0, false),
+            AccountMeta::new_readonly(find_config_address(program_id).0, false),
+            AccountMeta::new_readonly(*attestation, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+    )
+}
+
+fn push_candidates(accounts: &mut Vec<AccountMeta>, candidates: &[Pubkey]) {
+    accounts.extend(
+        candidates
+            .iter()
+            .map(|candidate| AccountMeta::new_readonly(*candidate, false)),
+    );
+}
+
+/// `randomness` is an unfulfilled request of the configured VRF program and
+/// `candidates` the reviews to draw from, e.g. the current leaderboard page.
+pub fn request_featured_draw(
+    program_id: &Pubkey,
+    payer: &Pubkey,
+    randomness: &Pubkey,
+    candidates: &[Pubkey],
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(*payer, true),
+        AccountMeta::new(find_featured_review_address(program_id).0, false),
+        AccountMeta::new_readonly(find_config_address(program_id).0, false),
+        AccountMeta::new_readonly(*randomness, false),
+        AccountMeta::new_readonly(system_program::ID, false),
+    ];
+    push_candidates(&mut accounts, candidates);
+    Instruction::new_with_bytes(
+        *program_id,
+        &MovieInstruction::RequestFeaturedDraw.pack(),
+        accounts,
+    )
+}
+
+/// `candidates` must be the ones passed to `request_featured_draw`, in order.
+/// `cranker` receives the escrowed bounty.
+pub fn fulfill_featured_draw(
+    program_id: &Pubkey,
+    cranker: &Pubkey,
+    randomness: &Pubkey,
+    candidates: &[Pubkey],
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(*cranker, true),
+        AccountMeta::new(find_featured_review_address(program_id).0, false),
+        AccountMeta::new_readonly(find_config_address(program_id).0, false),
+        AccountMeta::new_readonly(*randomness, false),
+    ];
+    push_candidates(&mut accounts, candidates);
+    Instruction::new_with_bytes(
+        *program_id,
+        &MovieInstruction::FulfillFeaturedDraw.pack(),
+        accounts,
+    )
+}
+
+pub fn set_vrf_program(
+    program_id: &Pubkey,
+    authority: &Pubkey,
+    vrf_program: &Pubkey,
+) -> Instruction {
+    Instruction::new_with_bytes(
+        *program_id,
+        &MovieInstruction::SetVrfProgram {
+            vrf_program: *vrf_program,
+        }
+        .pack(),
+        vec![
+            AccountMeta::new(*authority, true),
+            AccountMeta::new(find_config_address(program_id).0, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new(find_audit_log_address(program_id).0, false),
+        ],
+    )
+}
+
+pub fn set_foreign_emitter(
+    program_id: &Pubkey,
+    authority: &Pubkey,
+    wormhole_program: &Pubkey,
+    emitter_chain: u16,
+    emitter_address: [u8; 32],
+) -> Instruction {
+    Instruction::new_with_bytes(
+        *program_id,
+        &MovieInstruction::SetForeignEmitter {
+            wormhole_program: *wormhole_program,
+            emitter_chain,
+            emitter_address,
+        }
+        .pack(),
+        vec![
+            AccountMeta::new(*authority, true),
+            AccountMeta::new(find_config_address(program_id).0, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new(find_audit_log_address(program_id).0, false),
+        ],
+    )
+}
+
+/// `emitter_chain` and `sequence` are those of the VAA posted at
+/// `posted_vaa`.
+pub fn import_foreign_review(
+    program_id: &Pubkey,
+    payer: &Pubkey,
+    posted_vaa: &Pubkey,
+    emitter_chain: u16,
+    sequence: u64,
+) -> Instruction {
+    let (review, _) = find_foreign_review_address(program_id, emitter_chain, sequence);
+    let (counter, _) = find_comment_counter_address(program_id, &review);
+    Instruction::new_with_bytes(
+        *program_id,
+        &MovieInstruction::ImportForeignReview.pack(),
+        vec![
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(*posted_vaa, false),
+            AccountMeta::new_readonly(find_config_address(program_id).0, false),
+            AccountMeta::new(review, false),
+            AccountMeta::new(counter, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new(find_global_stats_address(program_id).0, false),
+        ],
+    )
+}
+
+/// Turns an `add_movie_review` or `add_comment` instruction into a dry run.
+/// Simulate it and Borsh-decode the return data as a `CostPreview`.
+pub fn dry_run(mut instruction: Instruction) -> Instruction {
+    instruction.data[0] |= DRY_RUN_FLAG;
+    instruction
+}
+
+/// Leads `instruction` with its 8-byte discriminator instead of its tag, for
+/// tooling that only speaks interface-style instructions.
+pub fn with_discriminator(mut instruction: Instruction) -> Instruction {
+    let (_, discriminator) = INSTRUCTION_DISCRIMINATORS[instruction.data[0] as usize];
+    instruction.data.splice(..1, discriminator);
+    instruction
+}
+
+/// Simulate this and Borsh-decode the return data as an
+/// `UpgradeAuthorityStatus`.
+pub fn get_upgrade_authority_status(program_id: &Pubkey) -> Instruction {
+    let (program_data, _) =
+        Pubkey::find_program_address(&[program_id.as_ref()], &bpf_loader_upgradeable::ID);
+    Instruction::new_with_bytes(
+        *program_id,
+        &MovieInstruction::GetUpgradeAuthorityStatus.pack(),
+        vec![
+            AccountMeta::new_readonly(program_data, false),
+            AccountMeta::new_readonly(find_config_address(program_id).0, false),
+        ],
+    )
+}
+
+/// Simulate this and Borsh-decode the return data as the
+/// `RequiredAccounts` of the instruction with tag `instruction_tag`.
+pub fn get_required_accounts(program_id: &Pubkey, instruction_tag: u8) -> Instruction {
+    Instruction::new_with_bytes(
+        *program_id,
+        &MovieInstruction::GetRequiredAccounts { instruction_tag }.pack(),
+        Vec::new(),
+    )
+}
+
+pub fn top_up_account(program_id: &Pubkey, payer: &Pubkey, account: &Pubkey) -> Instruction {
+    Instruction::new_with_bytes(
+        *program_id,
+        &MovieInstruction::TopUpAccount.pack(),
+        vec![
+            AccountMeta::new(*payer, true),
+            AccountMeta::new(*account, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+    )
+}
+
+/// `authority` is whoever paid for `account`: the relayer of a relayed
+/// review, or otherwise its reviewer, commenter or config authority.
+pub fn withdraw_excess_lamports(
+    program_id: &Pubkey,
+    authority: &Pubkey,
+    account: &Pubkey,
+) -> Instruction {
+    Instruction::new_with_bytes(
+        *program_id,
+        &MovieInstruction::WithdrawExcessLamports.pack(),
+        vec![
+            AccountMeta::new(*authority, true),
+            AccountMeta::new(*account, false),
+            AccountMeta::new_readonly(find_rent_payer_address(program_id, account).0, false),
+            // Only used to record a withdrawal from the config
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new(find_audit_log_address(program_id).0, false),
+        ],
+    )
+}
+
+pub fn set_fee_schedule(
+    program_id: &Pubkey,
+    authority: &Pubkey,
+    fees: FeeSchedule,
+    fee_exempt_mint: &Pubkey,
+    fee_exempt_min_balance: u64,
+) -> Instruction {
+    Instruction::new_with_bytes(
+        *program_id,
+        &MovieInstruction::SetFeeSchedule {
+            fees,
+            fee_exempt_mint: *fee_exempt_mint,
+            fee_exempt_min_balance,
+        }
+        .pack(),
+        vec![
+            AccountMeta::new(*authority, true),
+            AccountMeta::new(find_config_address(program_id).0, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new(find_audit_log_address(program_id).0, false),
+        ],
+    )
+}
+
+pub fn set_content_limits(
+    program_id: &Pubkey,
+    authority: &Pubkey,
+    limits: ContentLimits,
+) -> Instruction {
+    Instruction::new_with_bytes(
+        *program_id,
+        &MovieInstruction::SetContentLimits { limits }.pack(),
+        vec![
+            AccountMeta::new(*authority, true),
+            AccountMeta::new(find_config_address(program_id).0, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new(find_audit_log_address(program_id).0, false),
+        ],
+    )
+}
+
+pub fn set_rating_scale(
+    program_id: &Pubkey,
+    authority: &Pubkey,
+    scale: RatingScale,
+) -> Instruction {
+    Instruction::new_with_bytes(
+        *program_id,
+        &MovieInstruction::SetRatingScale { scale }.pack(),
+        vec![
+            AccountMeta::new(*authority, true),
+            AccountMeta::new(find_config_address(program_id).0, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new(find_audit_log_address(program_id).0, false),
+        ],
+    )
+}
+
+/// Once set, programs invoking state-changing instructions through CPI pass
+/// the instructions sysvar as the last account, preceded by the config if the
+/// instruction does not take it.
+pub fn set_cpi_callers(program_id: &Pubkey, authority: &Pubkey, callers: &[Pubkey]) -> Instruction {
+    Instruction::new_with_bytes(
+        *program_id,
+        &MovieInstruction::SetCpiCallers {
+            callers: callers.to_vec(),
+        }
+        .pack(),
+        vec![
+            AccountMeta::new(*authority, true),
+            AccountMeta::new(find_config_address(program_id).0, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new(find_audit_log_address(program_id).0, false),
+        ],
+    )
+}
+
+/// `title` must be the registered title of the movie.
+pub fn set_movie_external_id(
+    program_id: &Pubkey,
+    authority: &Pubkey,
+    title: &str,
+    external_id: &str,
+) -> Instruction {
+    Instruction::new_with_bytes(
+        *program_id,
+        &MovieInstruction::SetMovieExternalId {
+            external_id: external_id.to_string(),
+        }
+        .pack(),
+        vec![
+            AccountMeta::new(*authority, true),
+            AccountMeta::new(find_movie_address(program_id, title).0, false),
+            AccountMeta::new_readonly(find_config_address(program_id).0, false),
+            AccountMeta::new(find_external_id_address(program_id, external_id).0, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new(find_audit_log_address(program_id).0, false),
+        ],
+    )
+}
+
+/// Sets the release date of the movie registered as `title`, zero to clear
+/// it.
+pub fn set_movie_release_date(
+    program_id: &Pubkey,
+    authority: &Pubkey,
+    title: &str,
+    release_date: UnixTimestamp,
+) -> Instruction {
+    Instruction::new_with_bytes(
+        *program_id,
+        &MovieInstruction::SetMovieReleaseDate { release_date }.pack(),
+        vec![
+            AccountMeta::new(*authority, true),
+            AccountMeta::new(find_movie_address(program_id, title).0, false),
+            AccountMeta::new_readonly(find_config_address(program_id).0, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new(find_audit_log_address(program_id).0, false),
+        ],
+    )
+}
+
+/// Sets the program whose tips are counted in the global stats.
+pub fn set_tip_program(
+    program_id: &Pubkey,
+    authority: &Pubkey,
+    tip_program: &Pubkey,
+) -> Instruction {
+    Instruction::new_with_bytes(
+        *program_id,
+        &MovieInstruction::SetTipProgram {
+            tip_program: *tip_program,
+        }
+        .pack(),
+        vec![
+            AccountMeta::new(*authority, true),
+            AccountMeta::new(find_config_address(program_id).0, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new(find_audit_log_address(program_id).0, false),
+        ],
+    )
+}
+
+/// Replaces the config's `features` with the `ProgramConfig::FEATURE_*`
+/// bits in `features`.
+pub fn set_features(program_id: &Pubkey, authority: &Pubkey, features: u64) -> Instruction {
+    Instruction::new_with_bytes(
+        *program_id,
+        &MovieInstruction::SetFeatures { features }.pack(),
+        vec![
+            AccountMeta::new(*authority, true),
+            AccountMeta::new(find_config_address(program_id).0, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new(find_audit_log_address(program_id).0, false),
+        ],
+    )
+}
+
+/// Built by the tip program for its CPI, with its tip authority PDA as
+/// signer and `payer` funding the stats on first use.
+pub fn record_tip(
+    program_id: &Pubkey,
+    tip_authority: &Pubkey,
+    payer: &Pubkey,
+    review: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    Instruction::new_with_bytes(
+        *program_id,
+        &MovieInstruction::RecordTip { amount }.pack(),
+        vec![
+            AccountMeta::new_readonly(*tip_authority, true),
+            AccountMeta::new_readonly(find_config_address(program_id).0, false),
+            AccountMeta::new(find_global_stats_address(program_id).0, false),
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new_readonly(*review, false),
+            AccountMeta::new(find_hot_feed_address(program_id).0, false),
+        ],
+    )
+}
+
+/// `record_tip` for a tip of `lamports`.
+pub fn record_sol_tip(
+    program_id: &Pubkey,
+    tip_authority: &Pubkey,
+    payer: &Pubkey,
+    review: &Pubkey,
+    lamports: u64,
+) -> Instruction {
+    let mut instruction = record_tip(program_id, tip_authority, payer, review, 0);
+    instruction.data = MovieInstruction::RecordSolTip { lamports }.pack();
+    instruction
+}
+
+/// Snapshots `reviews` at `slot`, which must not be ahead of the slot the
+/// transaction lands in. The reviews are sorted and deduplicated as the
+/// program requires.
+pub fn write_snapshot(
+    program_id: &Pubkey,
+    authority: &Pubkey,
+    slot: u64,
+    reviews: &[Pubkey],
+) -> Instruction {
+    let mut reviews = reviews.to_vec();
+    reviews.sort();
+    reviews.dedup();
+
+    let mut accounts = vec![
+        AccountMeta::new(*authority, true),
+        AccountMeta::new_readonly(find_config_address(program_id).0, false),
+        AccountMeta::new(find_snapshot_address(program_id, slot).0, false),
+        AccountMeta::new_readonly(system_program::ID, false),
+    ];
+    for review in &reviews {
+        accounts.push(AccountMeta::new_readonly(*review, false));
+        accounts.push(AccountMeta::new_readonly(
+            find_comment_counter_address(program_id, review).0,
+            false,
+        ));
+    }
+    Instruction::new_with_bytes(
+        *program_id,
+        &MovieInstruction::WriteSnapshot { slot }.pack(),
+        accounts,
+    )
+}
+
+/// Counts one page of a movie's reviews, at most
+/// `MovieStats::MAX_RECOMPUTE_REVIEWS`, into its stats' recompute pass.
+/// Pages must be sent in ascending key order; each page is sorted and
+/// deduplicated here.
+pub fn recompute_stats(
+    program_id: &Pubkey,
+    payer: &Pubkey,
+    title: &str,
+    reviews: &[Pubkey],
+    restart: bool,
+    finish: bool,
+) -> Instruction {
+    let (movie, _) = find_movie_address(program_id, title);
+    let mut reviews = reviews.to_vec();
+    reviews.sort();
+    reviews.dedup();
+
+    let mut accounts = vec![
+        AccountMeta::new(*payer, true),
+        AccountMeta::new_readonly(movie, false),
+        AccountMeta::new(find_movie_stats_address(program_id, &movie).0, false),
+        AccountMeta::new_readonly(system_program::ID, false),
+    ];
+    accounts.extend(
+        reviews
+            .iter()
+            .map(|review| AccountMeta::new_readonly(*review, false)),
+    );
+    Instruction::new_with_bytes(
+        *program_id,
+        &MovieInstruction::RecomputeStats { restart, finish }.pack(),
+        accounts,
+    )
+}
+
+fn translation_accounts(
+    program_id: &Pubkey,
+    reviewer: &Pubkey,
+    review: &Pubkey,
+    language: &str,
+) -> Vec<AccountMeta> {
+    vec![
+        AccountMeta::new(*reviewer, true),
+        AccountMeta::new_readonly(*review, false),
+        AccountMeta::new(
+            find_translation_address(program_id, review, language).0,
+            false,
+        ),
+    ]
+}
+
+pub fn add_review_translation(
+    program_id: &Pubkey,
+    reviewer: &Pubkey,
+    review: &Pubkey,
+    language: &str,
+    description: &str,
+) -> Instruction {
+    let mut accounts = translation_accounts(program_id, reviewer, review, language);
+    accounts.push(AccountMeta::new_readonly(system_program::ID, false));
+    Instruction::new_with_bytes(
+        *program_id,
+        &MovieInstruction::AddReviewTranslation {
+            language: language.to_string(),
+            description: description.to_string(),
+        }
+        .pack(),
+        accounts,
+    )
+}
+
+pub fn update_review_translation(
+    program_id: &Pubkey,
+    reviewer: &Pubkey,
+    review: &Pubkey,
+    language: &str,
+    description: &str,
+) -> Instruction {
+    Instruction::new_with_bytes(
+        *program_id,
+        &MovieInstruction::UpdateReviewTranslation {
+            language: language.to_string(),
+            description: description.to_string(),
+        }
+        .pack(),
+        translation_accounts(program_id, reviewer, review, language),
+    )
+}
+
+pub fn delete_review_translation(
+    program_id: &Pubkey,
+    reviewer: &Pubkey,
+    review: &Pubkey,
+    language: &str,
+) -> Instruction {
+    Instruction::new_with_bytes(
+        *program_id,
+        &MovieInstruction::DeleteReviewTranslation {
+            language: language.to_string(),
+        }
+        .pack(),
+        translation_accounts(program_id, reviewer, review, language),
+    )
+}
+
+/// `review` is the review's address, which for reviews of a registered movie
+/// is not title-seeded.
+pub fn set_review_body_format(
+    program_id: &Pubkey,
+    reviewer: &Pubkey,
+    review: &Pubkey,
+    body_format: BodyFormat,
+) -> Instruction {
+    Instruction::new_with_bytes(
+        *program_id,
+        &MovieInstruction::SetReviewBodyFormat { body_format }.pack(),
+        vec![
+            AccountMeta::new_readonly(*reviewer, true),
+            AccountMeta::new(*review, false),
+        ],
+    )
+}
+
+pub fn follow_reviewer(program_id: &Pubkey, follower: &Pubkey, reviewer: &Pubkey) -> Instruction {
+    Instruction::new_with_bytes(
+        *program_id,
+        &MovieInstruction::FollowReviewer {
+            reviewer: *reviewer,
+        }
+        .pack(),
+        vec![
+            AccountMeta::new(*follower, true),
+            AccountMeta::new(find_follow_address(program_id, follower, reviewer).0, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+    )
+}
+
+pub fn unfollow_reviewer(program_id: &Pubkey, follower: &Pubkey, reviewer: &Pubkey) -> Instruction {
+    Instruction::new_with_bytes(
+        *program_id,
+        &MovieInstruction::UnfollowReviewer.pack(),
+        vec![
+            AccountMeta::new(*follower, true),
+            AccountMeta::new(find_follow_address(program_id, follower, reviewer).0, false),
+        ],
+    )
+}
+
+/// Under `FollowersOnly`, commenters append their `find_follow_address` of
+/// the reviewer to `add_comment`; under `TokenHoldersOnly`, a token account
+/// of theirs holding the mint.
+pub fn set_comment_settings(
+    program_id: &Pubkey,
+    reviewer: &Pubkey,
+    review: &Pubkey,
+    comments_enabled: bool,
+    comment_policy: CommentPolicy,
+) -> Instruction {
+    Instruction::new_with_bytes(
+        *program_id,
+        &MovieInstruction::SetCommentSettings {
+            comments_enabled,
+            comment_policy,
+        }
+        .pack(),
+        vec![
+            AccountMeta::new_readonly(*reviewer, true),
+            AccountMeta::new(*review, false),
+        ],
+    )
+}
+
+pub fn mute_user(program_id: &Pubkey, reviewer: &Pubkey, user: &Pubkey) -> Instruction {
+    Instruction::new_with_bytes(
+        *program_id,
+        &MovieInstruction::MuteUser { user: *user }.pack(),
+        vec![
+            AccountMeta::new(*reviewer, true),
+            AccountMeta::new(find_mute_address(program_id, reviewer, user).0, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+    )
+}
+
+pub fn unmute_user(program_id: &Pubkey, reviewer: &Pubkey, user: &Pubkey) -> Instruction {
+    Instruction::new_with_bytes(
+        *program_id,
+        &MovieInstruction::UnmuteUser.pack(),
+        vec![
+            AccountMeta::new(*reviewer, true),
+            AccountMeta::new(find_mute_address(program_id, reviewer, user).0, false),
+        ],
+    )
+}
+
+/// `scopes` is a set of `Session` scope bits.
+pub fn create_session(
+    program_id: &Pubkey,
+    authority: &Pubkey,
+    session_key: &Pubkey,
+    expires_slot: u64,
+    scopes: u8,
+) -> Instruction {
+    Instruction::new_with_bytes(
+        *program_id,
+        &MovieInstruction::CreateSession {
+            session_key: *session_key,
+            expires_slot,
+            scopes,
+        }
+        .pack(),
+        vec![
+            AccountMeta::new(*authority, true),
+            AccountMeta::new(
+                find_session_address(program_id, authority, session_key).0,
+                false,
+            ),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new_readonly(find_config_address(program_id).0, false),
+        ],
+    )
+}
+
+pub fn revoke_session(
+    program_id: &Pubkey,
+    authority: &Pubkey,
+    session_key: &Pubkey,
+) -> Instruction {
+    Instruction::new_with_bytes(
+        *program_id,
+        &MovieInstruction::RevokeSession.pack(),
+        vec![
+            AccountMeta::new(*authority, true),
+            AccountMeta::new(
+                find_session_address(program_id, authority, session_key).0,
+                false,
+            ),
+        ],
+    )
+}
+
+/// Has `session_key` sign `instruction` built for `authority`, by making it
+/// the signer and payer and appending the session. Sessions can sign
+/// `add_comment`, `reply_to_comment` and `add_paged_comment` with
+/// `Session::SCOPE_COMMENT`, and `update_rating`, `set_review_body_format`
+/// and `set_comment_settings` with `Session::SCOPE_UPDATE`.
+pub fn with_session(
+    mut instruction: Instruction,
+    authority: &Pubkey,
+    session_key: &Pubkey,
+) -> Instruction {
+    let (session, _) = find_session_address(&instruction.program_id, authority, session_key);
+    instruction.accounts[0] = AccountMeta::new(*session_key, true);
+    instruction
+        .accounts
+        .push(AccountMeta::new_readonly(session, false));
+    instruction
+}
+
+/// Has the critic authority of `reviewer` sign `instruction` built for them,
+/// by making it the signer and payer and appending the reviewer's profile.
+/// It can sign the instructions `with_session` lists, from a CPI of its
+/// program that signs for it with `invoke_signed`.
+pub fn with_critic_authority(
+    mut instruction: Instruction,
+    reviewer: &Pubkey,
+    authority: &Pubkey,
+) -> Instruction {
+    let (profile, _) = find_reviewer_profile_address(&instruction.program_id, reviewer);
+    instruction.accounts[0] = AccountMeta::new(*authority, true);
+    instruction
+        .accounts
+        .push(AccountMeta::new_readonly(profile, false));
+    instruction
+}
+
+/// Has `instruction`, an `add_movie_review`, `add_review_for_movie` or
+/// `add_relayed_review` by `reviewer`, check the title against the
+/// reviewer's `TitleFilter` and add it, by appending the filter after its
+/// proofs.
+pub fn with_title_filter(mut instruction: Instruction, reviewer: &Pubkey) -> Instruction {
+    let (filter, _) = find_title_filter_address(&instruction.program_id, reviewer);
+    instruction.accounts.push(AccountMeta::new(filter, false));
+    instruction
+}
+
+/// `review` is the review's address and `title` its title. With `cascade`,
+/// `comments` pairs each comment or comment page to close with the commenter
+/// refunded: the comment's author, or a page's first commenter. The review's
+/// translations, co-authors, fingerprint and edits can be paired with the
+/// reviewer to close them too, and a relayed review's rent payer record with
+/// its relayer. Send pages of comments until the review has
+/// none left; that call closes the review.
+pub fn delete_review(
+    program_id: &Pubkey,
+    reviewer: &Pubkey,
+    review: &Pubkey,
+    title: &str,
+    cascade: bool,
+    comments: &[(Pubkey, Pubkey)],
+) -> Instruction {
+    let (movie, _) = find_movie_address(program_id, title);
+    let mut accounts = vec![
+        AccountMeta::new(*reviewer, true),
+        AccountMeta::new(*review, false),
+        AccountMeta::new(find_comment_counter_address(program_id, review).0, false),
+        AccountMeta::new(find_reviewer_profile_address(program_id, reviewer).0, false),
+        AccountMeta::new_readonly(find_config_address(program_id).0, false),
+        AccountMeta::new(find_global_stats_address(program_id).0, false),
+        AccountMeta::new(find_movie_stats_address(program_id, &movie).0, false),
+        AccountMeta::new(find_rating_index_address(program_id, &movie).0, false),
+        AccountMeta::new_readonly(system_program::ID, false),
+    ];
+    for (comment, commenter) in comments {
+        accounts.push(AccountMeta::new(*comment, false));
+        accounts.push(AccountMeta::new(*commenter, false));
+    }
+    Instruction::new_with_bytes(
+        *program_id,
+        &MovieInstruction::DeleteReview { cascade }.pack(),
+        accounts,
+    )
+}
+
+/// Sets the slots authors have to appeal a proposed removal, zero for the
+/// default.
+pub fn set_removal_window(program_id: &Pubkey, authority: &Pubkey, slots: u64) -> Instruction {
+    Instruction::new_with_bytes(
+        *program_id,
+        &MovieInstruction::SetRemovalWindow { slots }.pack(),
+        vec![
+            AccountMeta::new(*authority, true),
+            AccountMeta::new(find_config_address(program_id).0, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new(find_audit_log_address(program_id).0, false),
+        ],
+    )
+}
+
+/// Sets the slots a wallet must have existed for to post a review, zero to
+/// switch the gate off, and the program whose attestations prove a wallet's
+/// age.
+pub fn set_age_gate(
+    program_id: &Pubkey,
+    authority: &Pubkey,
+    min_wallet_age: u64,
+    age_attester: &Pubkey,
+) -> Instruction {
+    Instruction::new_with_bytes(
+        *program_id,
+        &MovieInstruction::SetAgeGate {
+            min_wallet_age,
+            age_attester: *age_attester,
+        }
+        .pack(),
+        vec![
+            AccountMeta::new(*authority, true),
+            AccountMeta::new(find_config_address(program_id).0, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new(find_audit_log_address(program_id).0, false),
+        ],
+    )
+}
+
+pub fn propose_removal(program_id: &Pubkey, authority: &Pubkey, review: &Pubkey) -> Instruction {
+    Instruction::new_with_bytes(
+        *program_id,
+        &MovieInstruction::ProposeRemoval.pack(),
+        vec![
+            AccountMeta::new(*authority, true),
+            AccountMeta::new_readonly(find_config_address(program_id).0, false),
+            AccountMeta::new(*review, false),
+            AccountMeta::new(find_pending_removal_address(program_id, review).0, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new(find_audit_log_address(program_id).0, false),
+        ],
+    )
+}
+
+pub fn appeal_removal(program_id: &Pubkey, author: &Pubkey, review: &Pubkey) -> Instruction {
+    Instruction::new_with_bytes(
+        *program_id,
+        &MovieInstruction::AppealRemoval.pack(),
+        vec![
+            AccountMeta::new_readonly(*author, true),
+            AccountMeta::new_readonly(*review, false),
+            AccountMeta::new(find_pending_removal_address(program_id, review).0, false),
+        ],
+    )
+}
+
+pub fn resolve_appeal(
+    program_id: &Pubkey,
+    authority: &Pubkey,
+    review: &Pubkey,
+    uphold: bool,
+) -> Instruction {
+    Instruction::new_with_bytes(
+        *program_id,
+        &MovieInstruction::ResolveAppeal { uphold }.pack(),
+        vec![
+            AccountMeta::new(*authority, true),
+            AccountMeta::new_readonly(find_config_address(program_id).0, false),
+            AccountMeta::new_readonly(*review, false),
+            AccountMeta::new(find_pending_removal_address(program_id, review).0, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new(find_audit_log_address(program_id).0, false),
+        ],
+    )
+}
+
+/// `delete_review` on the author's behalf, once the appeal window is over.
+pub fn finalize_removal(
+    program_id: &Pubkey,
+    authority: &Pubkey,
+    author: &Pubkey,
+    review: &Pubkey,
+    title: &str,
+    cascade: bool,
+    comments: &[(Pubkey, Pubkey)],
+) -> Instruction {
+    let mut instruction = delete_review(program_id, author, review, title, cascade, comments);
+    instruction.data = MovieInstruction::FinalizeRemoval { cascade }.pack();
+    instruction.accounts[0].is_signer = false;
+    instruction.accounts.splice(
+        0..0,
+        [
+            AccountMeta::new(*authority, true),
+            AccountMeta::new(find_pending_removal_address(program_id, review).0, false),
+            AccountMeta::new(find_audit_log_address(program_id).0, false),
+        ],
+    );
+    instruction
+}
+
+/// `hash` is the SHA-256 of the body stored at `uri`, see `body::hash_body`.
+pub fn update_body_hash(
+    program_id: &Pubkey,
+    reviewer: &Pubkey,
+    review: &Pubkey,
+    hash: [u8; 32],
+    uri: &str,
+) -> Instruction {
+    Instruction::new_with_bytes(
+        *program_id,
+        &MovieInstruction::UpdateBodyHash {
+            hash,
+            uri: uri.to_string(),
+        }
+        .pack(),
+        vec![
+            AccountMeta::new(*reviewer, true),
+            AccountMeta::new(*review, false),
+            AccountMeta::new(find_body_commitment_address(program_id, review).0, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+    )
+}
+
+pub fn record_fingerprint(program_id: &Pubkey, payer: &Pubkey, review: &Pubkey) -> Instruction {
+    Instruction::new_with_bytes(
+        *program_id,
+        &MovieInstruction::RecordFingerprint.pack(),
+        vec![
+            AccountMeta::new(*payer, true),
+            AccountMeta::new(*review, false),
+            AccountMeta::new(find_fingerprint_address(program_id, review).0, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+    )
+}
+
+/// Likes or reports `comment`, or switches the voter's vote on it. Append
+/// token accounts proving a fee exemption if a vote fee is set.
+pub fn vote_on_comment(
+    program_id: &Pubkey,
+    voter: &Pubkey,
+    comment: &Pubkey,
+    kind: CommentVoteKind,
+) -> Instruction {
+    Instruction::new_with_bytes(
+        *program_id,
+        &MovieInstruction::VoteOnComment { kind }.pack(),
+        vec![
+            AccountMeta::new(*voter, true),
+            AccountMeta::new_readonly(*comment, false),
+            AccountMeta::new(
+                find_comment_vote_address(program_id, comment, voter).0,
+                false,
+            ),
+            AccountMeta::new(find_comment_karma_address(program_id, comment).0, false),
+            AccountMeta::new(find_config_address(program_id).0, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new(find_hot_feed_address(program_id).0, false),
+        ],
+    )
+}
+
+/// Sets the score below which comments are flagged hidden, zero to switch
+/// hiding off.
+pub fn set_hide_threshold(program_id: &Pubkey, authority: &Pubkey, threshold: i64) -> Instruction {
+    Instruction::new_with_bytes(
+        *program_id,
+        &MovieInstruction::SetHideThreshold { threshold }.pack(),
+        vec![
+            AccountMeta::new(*authority, true),
+            AccountMeta::new(find_config_address(program_id).0, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new(find_audit_log_address(program_id).0, false),
+        ],
+    )
+}
+
+/// Sets how many tokens of `mint` each edit of a review after the first
+/// burns, zero to make every edit free.
+pub fn set_edit_burn(
+    program_id: &Pubkey,
+    authority: &Pubkey,
+    mint: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    Instruction::new_with_bytes(
+        *program_id,
+        &MovieInstruction::SetEditBurn {
+            mint: *mint,
+            amount,
+        }
+        .pack(),
+        vec![
+            AccountMeta::new(*authority, true),
+            AccountMeta::new(find_config_address(program_id).0, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new(find_audit_log_address(program_id).0, false),
+        ],
+    )
+}
+
+/// Lists `co_authors` on the reviewer's `review`, each with their share of
+/// its tips and rewards in basis points.
+pub fn set_co_authors(
+    program_id: &Pubkey,
+    reviewer: &Pubkey,
+    review: &Pubkey,
+    co_authors: Vec<(Pubkey, u16)>,
+) -> Instruction {
+    Instruction::new_with_bytes(
+        *program_id,
+        &MovieInstruction::SetCoAuthors { co_authors }.pack(),
+        vec![
+            AccountMeta::new(*reviewer, true),
+            AccountMeta::new(*review, false),
+            AccountMeta::new(find_co_authors_address(program_id, review).0, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+    )
+}
+
+pub fn accept_co_authorship(
+    program_id: &Pubkey,
+    co_author: &Pubkey,
+    review: &Pubkey,
+    share_bps: u16,
+) -> Instruction {
+    Instruction::new_with_bytes(
+        *program_id,
+        &MovieInstruction::AcceptCoAuthorship { share_bps }.pack(),
+        vec![
+            AccountMeta::new_readonly(*co_author, true),
+            AccountMeta::new(find_co_authors_address(program_id, review).0, false),
+        ],
+    )
+}
+
+/// Lets `authority`, a PDA of `authority_program`, sign for `reviewer`, see
+/// `with_critic_authority`. Default keys switch it off.
+pub fn set_critic_authority(
+    program_id: &Pubkey,
+    reviewer: &Pubkey,
+    authority: &Pubkey,
+    authority_program: &Pubkey,
+) -> Instruction {
+    Instruction::new_with_bytes(
+        *program_id,
+        &MovieInstruction::SetCriticAuthority {
+            authority: *authority,
+            authority_program: *authority_program,
+        }
+        .pack(),
+        vec![
+            AccountMeta::new(*reviewer, true),
+            AccountMeta::new(find_reviewer_profile_address(program_id, reviewer).0, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+    )
+}
+
+/// Flags `review` as a copy of `original`. Both need fingerprints recorded
+/// since their last edit, see `record_fingerprint`.
+pub fn challenge_duplicate(
+    program_id: &Pubkey,
+    challenger: &Pubkey,
+    review: &Pubkey,
+    original: &Pubkey,
+) -> Instruction {
+    Instruction::new_with_bytes(
+        *program_id,
+        &MovieInstruction::ChallengeDuplicate.pack(),
+        vec![
+            AccountMeta::new(*challenger, true),
+            AccountMeta::new(*review, false),
+            AccountMeta::new_readonly(find_fingerprint_address(program_id, review).0, false),
+            AccountMeta::new(*original, false),
+            AccountMeta::new_readonly(find_fingerprint_address(program_id, original).0, false),
+            AccountMeta::new(
+                find_duplicate_challenge_address(program_id, review, original).0,
+                false,
+            ),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+    )
+}
+
+/// Escrows `amount` lamports for a translation of `review` into `language`.
+pub fn fund_translation_bounty(
+    program_id: &Pubkey,
+    reviewer: &Pubkey,
+    review: &Pubkey,
+    language: &str,
+    amount: u64,
+) -> Instruction {
+    Instruction::new_with_bytes(
+        *program_id,
+        &MovieInstruction::FundTranslationBounty {
+            language: language.to_string(),
+            amount,
+        }
+        .pack(),
+        vec![
+            AccountMeta::new(*reviewer, true),
+            AccountMeta::new_readonly(*review, false),
+            AccountMeta::new_readonly(
+                find_translation_address(program_id, review, language).0,
+                false,
+            ),
+            AccountMeta::new(
+                find_translation_bounty_address(program_id, review, language).0,
+                false,
+            ),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+    )
+}
+
+pub fn submit_translation(
+    program_id: &Pubkey,
+    translator: &Pubkey,
+    review: &Pubkey,
+    language: &str,
+    description: &str,
+) -> Instruction {
+    let (bounty, _) = find_translation_bounty_address(program_id, review, language);
+    Instruction::new_with_bytes(
+        *program_id,
+        &MovieInstruction::SubmitTranslation {
+            language: language.to_string(),
+            description: description.to_string(),
+        }
+        .pack(),
+        vec![
+            AccountMeta::new(*translator, true),
+            AccountMeta::new_readonly(*review, false),
+            AccountMeta::new(bounty, false),
+            AccountMeta::new(
+                find_translation_submission_address(program_id, &bounty, translator).0,
+                false,
+            ),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+    )
+}
+
+/// Approves `translator`'s submission for the `language` bounty on `review`.
+pub fn approve_translation(
+    program_id: &Pubkey,
+    reviewer: &Pubkey,
+    review: &Pubkey,
+    language: &str,
+    translator: &Pubkey,
+) -> Instruction {
+    let (bounty, _) = find_translation_bounty_address(program_id, review, language);
+    let mut accounts = translation_accounts(program_id, reviewer, review, language);
+    accounts.extend([
+        AccountMeta::new(bounty, false),
+        AccountMeta::new(
+            find_translation_submission_address(program_id, &bounty, translator).0,
+            false,
+        ),
+        AccountMeta::new(*translator, false),
+        AccountMeta::new_readonly(system_program::ID, false),
+    ]);
+    Instruction::new_with_bytes(
+        *program_id,
+        &MovieInstruction::ApproveTranslation {
+            language: language.to_string(),
+        }
+        .pack(),
+        accounts,
+    )
+}
+
+pub fn cancel_translation_bounty(
+    program_id: &Pubkey,
+    reviewer: &Pubkey,
+    review: &Pubkey,
+    language: &str,
+) -> Instruction {
+    Instruction::new_with_bytes(
+        *program_id,
+        &MovieInstruction::CancelTranslationBounty {
+            language: language.to_string(),
+        }
+        .pack(),
+        vec![
+            AccountMeta::new(*reviewer, true),
+            AccountMeta::new_readonly(*review, false),
+            AccountMeta::new(
+                find_translation_bounty_address(program_id, review, language).0,
+                false,
+            ),
+        ],
+    )
+}
+
+pub fn withdraw_translation_submission(
+    program_id: &Pubkey,
+    translator: &Pubkey,
+    review: &Pubkey,
+    language: &str,
+) -> Instruction {
+    let (bounty, _) = find_translation_bounty_address(program_id, review, language);
+    Instruction::new_with_bytes(
+        *program_id,
+        &MovieInstruction::WithdrawTranslationSubmission {
+            language: language.to_string(),
+        }
+        .pack(),
+        vec![
+            AccountMeta::new(*translator, true),
+            AccountMeta::new_readonly(*review, false),
+            AccountMeta::new_readonly(bounty, false),
+            AccountMeta::new(
+                find_translation_submission_address(program_id, &bounty, translator).0,
+                false,
+            ),
+        ],
+    )
+}
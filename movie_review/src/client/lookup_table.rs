@@ -0,0 +1,117 @@
+//! Address lookup tables and v0 transactions. Batches of comments repeat the
+//! same program, review and counter keys in every instruction; referencing
+//! them through a lookup table shrinks each key from 32 bytes to one.
+
+use solana_client::{
+    client_error::{ClientError, ClientErrorKind},
+    nonblocking::rpc_client::RpcClient,
+};
+use solana_sdk::{
+    address_lookup_table::{instruction, state::AddressLookupTable, AddressLookupTableAccount},
+    commitment_config::CommitmentConfig,
+    hash::Hash,
+    instruction::Instruction,
+    message::{v0, VersionedMessage},
+    pubkey::Pubkey,
+    signature::Signature,
+    signer::Signer,
+    system_program,
+    transaction::{Transaction, VersionedTransaction},
+};
+
+use super::pda::find_comment_counter_address;
+
+/// Addresses extended per transaction, keeping each extend well under the
+/// packet size limit.
+const EXTEND_CHUNK_LEN: usize = 20;
+
+/// The keys every comment on `review` references.
+pub fn review_lookup_addresses(program_id: &Pubkey, review: &Pubkey) -> Vec<Pubkey> {
+    let (counter, _) = find_comment_counter_address(program_id, review);
+    vec![*program_id, system_program::ID, *review, counter]
+}
+
+/// Creates an empty lookup table owned by `authority` and returns its address.
+pub async fn create_lookup_table(
+    rpc: &RpcClient,
+    payer: &dyn Signer,
+    authority: &dyn Signer,
+) -> Result<(Pubkey, Signature), ClientError> {
+    let recent_slot = rpc
+        .get_slot_with_commitment(CommitmentConfig::finalized())
+        .await?;
+    let (create_ix, table) =
+        instruction::create_lookup_table(authority.pubkey(), payer.pubkey(), recent_slot);
+
+    let blockhash = rpc.get_latest_blockhash().await?;
+    let transaction = Transaction::new_signed_with_payer(
+        &[create_ix],
+        Some(&payer.pubkey()),
+        &[payer, authority],
+        blockhash,
+    );
+    let signature = rpc.send_and_confirm_transaction(&transaction).await?;
+    Ok((table, signature))
+}
+
+/// Appends `addresses` to `table`, one transaction per chunk.
+pub async fn extend_lookup_table(
+    rpc: &RpcClient,
+    payer: &dyn Signer,
+    authority: &dyn Signer,
+    table: &Pubkey,
+    addresses: &[Pubkey],
+) -> Result<Vec<Signature>, ClientError> {
+    let mut signatures = Vec::new();
+    for chunk in addresses.chunks(EXTEND_CHUNK_LEN) {
+        let extend_ix = instruction::extend_lookup_table(
+            *table,
+            authority.pubkey(),
+            Some(payer.pubkey()),
+            chunk.to_vec(),
+        );
+        let blockhash = rpc.get_latest_blockhash().await?;
+        let transaction = Transaction::new_signed_with_payer(
+            &[extend_ix],
+            Some(&payer.pubkey()),
+            &[payer, authority],
+            blockhash,
+        );
+        signatures.push(rpc.send_and_confirm_transaction(&transaction).await?);
+    }
+    Ok(signatures)
+}
+
+pub async fn fetch_lookup_table(
+    rpc: &RpcClient,
+    table: &Pubkey,
+) -> Result<AddressLookupTableAccount, ClientError> {
+    let account = rpc.get_account(table).await?;
+    let lookup_table = AddressLookupTable::deserialize(&account.data)
+        .map_err(|e| ClientErrorKind::Custom(format!("invalid lookup table {}: {}", table, e)))?;
+    Ok(AddressLookupTableAccount {
+        key: *table,
+        addresses: lookup_table.addresses.to_vec(),
+    })
+}
+
+/// Compiles `instructions` into a signed v0 transaction that resolves keys
+/// through `tables` where possible. The error is boxed, as `ClientError` is
+/// large.
+pub fn build_v0_transaction(
+    instructions: &[Instruction],
+    payer: &dyn Signer,
+    signers: &[&dyn Signer],
+    tables: &[AddressLookupTableAccount],
+    blockhash: Hash,
+) -> Result<VersionedTransaction, Box<ClientError>> {
+    let message = v0::Message::try_compile(&payer.pubkey(), instructions, tables, blockhash)
+        .map_err(|e| {
+            Box::new(ClientErrorKind::Custom(format!("failed to compile v0 message: {}", e)).into())
+        })?;
+
+    let mut all_signers = vec![payer];
+    all_signers.extend_from_slice(signers);
+    VersionedTransaction::try_new(VersionedMessage::V0(message), &all_signers)
+        .map_err(|e| Box::new(ClientErrorKind::SigningError(e).into()))
+}
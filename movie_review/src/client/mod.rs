@@ -0,0 +1,32 @@
+//! Instruction and PDA builders for off-chain callers.
+//!
+//! `instructions`, `pda` and `body` only depend on `solana-program`, `borsh`
+//! and `spl-token`, so with default features off they build for
+//! `wasm32-unknown-unknown` as well as native targets:
+//!
+//! ```text
+//! cargo check --target wasm32-unknown-unknown --no-default-features --features wasm,no-entrypoint
+//! ```
+//!
+//! The RPC-backed helpers need the `rpc` feature, and `storage`, which
+//! uploads off-chain review bodies, the `storage` feature.
+
+#[cfg(feature = "rpc")]
+pub mod accounts;
+pub mod body;
+pub mod instructions;
+#[cfg(feature = "rpc")]
+pub mod lookup_table;
+#[cfg(feature = "rpc")]
+pub mod nonce;
+pub mod pda;
+#[cfg(feature = "rpc")]
+pub mod stats;
+#[cfg(feature = "storage")]
+pub mod storage;
+#[cfg(feature = "rpc")]
+pub mod subscribe;
+#[cfg(feature = "rpc")]
+pub mod transaction;
+#[cfg(feature = "wasm")]
+pub mod wasm;
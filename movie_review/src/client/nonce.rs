@@ -0,0 +1,105 @@
+//! Durable nonce support: review and comment transactions built against a
+//! nonce account instead of a recent blockhash, so they can be signed offline
+//! and submitted later without expiring.
+
+use solana_client::{
+    client_error::{ClientError, ClientErrorKind},
+    nonblocking::rpc_client::RpcClient,
+};
+use solana_sdk::{
+    account_utils::StateMut,
+    hash::Hash,
+    instruction::Instruction,
+    message::Message,
+    nonce::{
+        state::{State, Versions},
+        State as NonceState,
+    },
+    pubkey::Pubkey,
+    signature::Signature,
+    signer::Signer,
+    system_instruction,
+    transaction::Transaction,
+};
+
+/// Creates and initializes a rent-exempt nonce account at `nonce`'s address
+/// controlled by `authority`.
+pub async fn create_nonce_account(
+    rpc: &RpcClient,
+    payer: &dyn Signer,
+    nonce: &dyn Signer,
+    authority: &Pubkey,
+) -> Result<Signature, ClientError> {
+    let lamports = rpc
+        .get_minimum_balance_for_rent_exemption(NonceState::size())
+        .await?;
+    let instructions = system_instruction::create_nonce_account(
+        &payer.pubkey(),
+        &nonce.pubkey(),
+        authority,
+        lamports,
+    );
+    let blockhash = rpc.get_latest_blockhash().await?;
+    let transaction = Transaction::new_signed_with_payer(
+        &instructions,
+        Some(&payer.pubkey()),
+        &[payer, nonce],
+        blockhash,
+    );
+    rpc.send_and_confirm_transaction(&transaction).await
+}
+
+/// Advances the nonce on its own, invalidating any transaction already signed
+/// against the current value.
+pub async fn advance_nonce_account(
+    rpc: &RpcClient,
+    payer: &dyn Signer,
+    nonce: &Pubkey,
+    authority: &dyn Signer,
+) -> Result<Signature, ClientError> {
+    let blockhash = rpc.get_latest_blockhash().await?;
+    let transaction = Transaction::new_signed_with_payer(
+        &[system_instruction::advance_nonce_account(
+            nonce,
+            &authority.pubkey(),
+        )],
+        Some(&payer.pubkey()),
+        &[payer, authority],
+        blockhash,
+    );
+    rpc.send_and_confirm_transaction(&transaction).await
+}
+
+/// Reads the nonce value currently stored in `nonce`, to be used in place of
+/// a recent blockhash.
+pub async fn fetch_nonce(rpc: &RpcClient, nonce: &Pubkey) -> Result<Hash, ClientError> {
+    let account = rpc.get_account(nonce).await?;
+    let versions: Versions = account
+        .state()
+        .map_err(|e| ClientErrorKind::Custom(format!("invalid nonce account {}: {}", nonce, e)))?;
+    match versions.state() {
+        State::Initialized(data) => Ok(data.blockhash()),
+        State::Uninitialized => Err(ClientErrorKind::Custom(format!(
+            "nonce account {} is not initialized",
+            nonce
+        ))
+        .into()),
+    }
+}
+
+/// Builds an unsigned transaction for `instructions` that consumes the nonce.
+/// The runtime requires `advance_nonce_account` to come first, which
+/// `Message::new_with_nonce` takes care of. Signers add their signatures with
+/// `Transaction::partial_sign`, possibly on different machines.
+pub fn build_nonce_transaction(
+    instructions: &[Instruction],
+    payer: &Pubkey,
+    nonce: &Pubkey,
+    nonce_authority: &Pubkey,
+    nonce_hash: Hash,
+) -> Transaction {
+    let mut message =
+        Message::new_with_nonce(instructions.to_vec(), Some(payer), nonce, nonce_authority);
+    message.recent_blockhash = nonce_hash;
+    Transaction::new_unsigned(message)
+}
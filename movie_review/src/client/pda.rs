@@ -0,0 +1,329 @@
+use solana_program::pubkey::Pubkey;
+
+use crate::programs::{associated_token_address, token_2022};
+use crate::state::{
+    comment_seed_bytes, AuditLog, BodyCommitment, CommentKarma, CommentPage, CommentVote,
+    DuplicateChallenge, FeaturedReview, Follow, GlobalStats, HotFeed, Movie, MovieAccountState,
+    MovieStats, Mute, PendingRemoval, ProgramConfig, RatingIndex, RentPayer, ReviewCoAuthors,
+    ReviewEdits, ReviewFingerprint, ReviewSnapshot, ReviewTranslation, ReviewerProfile, Session,
+    TitleFilter, TranslationBounty, TranslationSubmission,
+};
+
+pub fn find_review_address(program_id: &Pubkey, reviewer: &Pubkey, title: &str) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[reviewer.as_ref(), title.as_bytes()], program_id)
+}
+
+pub fn find_comment_counter_address(program_id: &Pubkey, review: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[review.as_ref(), "comment".as_ref()], program_id)
+}
+
+pub fn find_movie_address(program_id: &Pubkey, title: &str) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[Movie::SEED.as_bytes(), Movie::title_hash(title).as_ref()],
+        program_id,
+    )
+}
+
+/// `ExternalIdIndex` of `external_id`, which holds the movie it resolves to.
+pub fn find_external_id_address(program_id: &Pubkey, external_id: &str) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            Movie::EXTERNAL_ID_SEED.as_bytes(),
+            Movie::external_id_hash(external_id).as_ref(),
+        ],
+        program_id,
+    )
+}
+
+/// Review address of a registered movie. Holds either the review itself or a
+/// `ReviewLink` to a title-seeded review.
+pub fn find_movie_review_address(
+    program_id: &Pubkey,
+    reviewer: &Pubkey,
+    movie: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[reviewer.as_ref(), movie.as_ref()], program_id)
+}
+
+pub fn find_config_address(program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[ProgramConfig::SEED.as_bytes()], program_id)
+}
+
+pub fn find_global_stats_address(program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[GlobalStats::SEED.as_bytes()], program_id)
+}
+
+pub fn find_hot_feed_address(program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[HotFeed::SEED.as_bytes()], program_id)
+}
+
+pub fn find_movie_stats_address(program_id: &Pubkey, movie: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[MovieStats::SEED.as_bytes(), movie.as_ref()], program_id)
+}
+
+pub fn find_reviewer_profile_address(program_id: &Pubkey, reviewer: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[ReviewerProfile::SEED.as_bytes(), reviewer.as_ref()],
+        program_id,
+    )
+}
+
+pub fn find_pass_mint_address(program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[ProgramConfig::PASS_MINT_SEED.as_bytes()], program_id)
+}
+
+/// The reviewer's Token-2022 associated token account for the pass mint.
+pub fn find_reviewer_pass_address(program_id: &Pubkey, reviewer: &Pubkey) -> Pubkey {
+    associated_token_address(
+        reviewer,
+        &find_pass_mint_address(program_id).0,
+        &token_2022::ID,
+    )
+}
+
+pub fn find_collection_authority_address(program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[ProgramConfig::COLLECTION_AUTHORITY_SEED.as_bytes()],
+        program_id,
+    )
+}
+
+/// Mint of the review collection created by the `generation`th
+/// `RotateReviewCollection`, counting from zero.
+pub fn find_review_collection_address(program_id: &Pubkey, generation: u32) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            ProgramConfig::COLLECTION_SEED.as_bytes(),
+            &generation.to_le_bytes(),
+        ],
+        program_id,
+    )
+}
+
+pub fn find_review_nft_mint_address(program_id: &Pubkey, review: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[ProgramConfig::REVIEW_NFT_SEED.as_bytes(), review.as_ref()],
+        program_id,
+    )
+}
+
+pub fn find_featured_review_address(program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[FeaturedReview::SEED.as_bytes()], program_id)
+}
+
+/// Address of the review imported from VAA `sequence` of the trusted
+/// emitter on `emitter_chain`.
+pub fn find_foreign_review_address(
+    program_id: &Pubkey,
+    emitter_chain: u16,
+    sequence: u64,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            MovieAccountState::FOREIGN_SEED.as_bytes(),
+            &emitter_chain.to_le_bytes(),
+            &sequence.to_le_bytes(),
+        ],
+        program_id,
+    )
+}
+
+pub fn find_comment_address(program_id: &Pubkey, review: &Pubkey, count: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[review.as_ref(), comment_seed_bytes(count).as_ref()],
+        program_id,
+    )
+}
+
+pub fn find_comment_page_address(program_id: &Pubkey, review: &Pubkey, page: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            CommentPage::SEED.as_bytes(),
+            review.as_ref(),
+            &page.to_le_bytes(),
+        ],
+        program_id,
+    )
+}
+
+pub fn find_snapshot_address(program_id: &Pubkey, slot: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[ReviewSnapshot::SEED.as_bytes(), &slot.to_le_bytes()],
+        program_id,
+    )
+}
+
+pub fn find_mute_address(program_id: &Pubkey, reviewer: &Pubkey, muted: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[Mute::SEED.as_bytes(), reviewer.as_ref(), muted.as_ref()],
+        program_id,
+    )
+}
+
+pub fn find_follow_address(
+    program_id: &Pubkey,
+    follower: &Pubkey,
+    reviewer: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            Follow::SEED.as_bytes(),
+            follower.as_ref(),
+            reviewer.as_ref(),
+        ],
+        program_id,
+    )
+}
+
+pub fn find_translation_address(
+    program_id: &Pubkey,
+    review: &Pubkey,
+    language: &str,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            ReviewTranslation::SEED.as_bytes(),
+            review.as_ref(),
+            language.as_bytes(),
+        ],
+        program_id,
+    )
+}
+
+pub fn find_audit_log_address(program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[AuditLog::SEED.as_bytes()], program_id)
+}
+
+pub fn find_pending_removal_address(program_id: &Pubkey, review: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[PendingRemoval::SEED.as_bytes(), review.as_ref()],
+        program_id,
+    )
+}
+
+/// `BodyCommitment` of a review whose body is stored off-chain.
+pub fn find_body_commitment_address(program_id: &Pubkey, review: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[BodyCommitment::SEED.as_bytes(), review.as_ref()],
+        program_id,
+    )
+}
+
+pub fn find_fingerprint_address(program_id: &Pubkey, review: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[ReviewFingerprint::SEED.as_bytes(), review.as_ref()],
+        program_id,
+    )
+}
+
+/// `DuplicateChallenge` flagging `review` as a copy of `original`.
+pub fn find_duplicate_challenge_address(
+    program_id: &Pubkey,
+    review: &Pubkey,
+    original: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            DuplicateChallenge::SEED.as_bytes(),
+            review.as_ref(),
+            original.as_ref(),
+        ],
+        program_id,
+    )
+}
+
+/// `CommentVote` of `voter` on `comment`.
+pub fn find_comment_vote_address(
+    program_id: &Pubkey,
+    comment: &Pubkey,
+    voter: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            CommentVote::SEED.as_bytes(),
+            comment.as_ref(),
+            voter.as_ref(),
+        ],
+        program_id,
+    )
+}
+
+pub fn find_comment_karma_address(program_id: &Pubkey, comment: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[CommentKarma::SEED.as_bytes(), comment.as_ref()],
+        program_id,
+    )
+}
+
+pub fn find_review_edits_address(program_id: &Pubkey, review: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[ReviewEdits::SEED.as_bytes(), review.as_ref()], program_id)
+}
+
+pub fn find_co_authors_address(program_id: &Pubkey, review: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[ReviewCoAuthors::SEED.as_bytes(), review.as_ref()],
+        program_id,
+    )
+}
+
+pub fn find_translation_bounty_address(
+    program_id: &Pubkey,
+    review: &Pubkey,
+    language: &str,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            TranslationBounty::SEED.as_bytes(),
+            review.as_ref(),
+            language.as_bytes(),
+        ],
+        program_id,
+    )
+}
+
+pub fn find_translation_submission_address(
+    program_id: &Pubkey,
+    bounty: &Pubkey,
+    translator: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            TranslationSubmission::SEED.as_bytes(),
+            bounty.as_ref(),
+            translator.as_ref(),
+        ],
+        program_id,
+    )
+}
+
+pub fn find_title_filter_address(program_id: &Pubkey, reviewer: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[TitleFilter::SEED.as_bytes(), reviewer.as_ref()],
+        program_id,
+    )
+}
+
+/// `RentPayer` of an account paid for by someone other than its controller.
+pub fn find_rent_payer_address(program_id: &Pubkey, account: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[RentPayer::SEED.as_bytes(), account.as_ref()], program_id)
+}
+
+/// `RatingIndex` of a registered movie's reviews.
+pub fn find_rating_index_address(program_id: &Pubkey, movie: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[RatingIndex::SEED.as_bytes(), movie.as_ref()], program_id)
+}
+
+pub fn find_session_address(
+    program_id: &Pubkey,
+    authority: &Pubkey,
+    session_key: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            Session::SEED.as_bytes(),
+            authority.as_ref(),
+            session_key.as_ref(),
+        ],
+        program_id,
+    )
+}
@@ -0,0 +1,91 @@
+//! Fetches the program-wide and per-review counters for dashboards.
+
+use solana_client::{
+    client_error::{ClientError, ClientErrorKind},
+    nonblocking::rpc_client::RpcClient,
+};
+use solana_program::pubkey::Pubkey;
+
+use super::pda::{
+    find_comment_counter_address, find_global_stats_address, find_hot_feed_address,
+    find_reviewer_profile_address,
+};
+use crate::decoder::{decode_account, MovieReviewAccount};
+use crate::state::{GlobalStats, HotFeed, MovieCommentCounter, ReviewerProfile};
+
+/// Reads the global stats, or all zeros if nothing has updated them yet.
+pub async fn fetch_global_stats(
+    rpc: &RpcClient,
+    program_id: &Pubkey,
+) -> Result<GlobalStats, ClientError> {
+    let (address, _) = find_global_stats_address(program_id);
+    let Some(account) = rpc
+        .get_account_with_commitment(&address, rpc.commitment())
+        .await?
+        .value
+    else {
+        return Ok(GlobalStats::default());
+    };
+    match decode_account(&account.data) {
+        Ok(MovieReviewAccount::GlobalStats(stats)) => Ok(stats),
+        _ => Err(ClientErrorKind::Custom(format!("invalid global stats {}", address)).into()),
+    }
+}
+
+/// Reads the hot feed, empty if nothing has been commented, liked or tipped
+/// yet. Rank it with `HotFeed::ranked` at the current slot.
+pub async fn fetch_hot_feed(rpc: &RpcClient, program_id: &Pubkey) -> Result<HotFeed, ClientError> {
+    let (address, _) = find_hot_feed_address(program_id);
+    let Some(account) = rpc
+        .get_account_with_commitment(&address, rpc.commitment())
+        .await?
+        .value
+    else {
+        return Ok(HotFeed::default());
+    };
+    match decode_account(&account.data) {
+        Ok(MovieReviewAccount::HotFeed(feed)) => Ok(*feed),
+        _ => Err(ClientErrorKind::Custom(format!("invalid hot feed {}", address)).into()),
+    }
+}
+
+/// Reads `reviewer`'s profile, `None` until their first review.
+pub async fn fetch_reviewer_profile(
+    rpc: &RpcClient,
+    program_id: &Pubkey,
+    reviewer: &Pubkey,
+) -> Result<Option<ReviewerProfile>, ClientError> {
+    let (address, _) = find_reviewer_profile_address(program_id, reviewer);
+    let Some(account) = rpc
+        .get_account_with_commitment(&address, rpc.commitment())
+        .await?
+        .value
+    else {
+        return Ok(None);
+    };
+    match decode_account(&account.data) {
+        Ok(MovieReviewAccount::ReviewerProfile(profile)) => Ok(Some(profile)),
+        _ => Err(ClientErrorKind::Custom(format!("invalid reviewer profile {}", address)).into()),
+    }
+}
+
+/// Reads `review`'s comment counter, with both its active comments and every
+/// comment ever created. `None` once the review is deleted.
+pub async fn fetch_comment_counter(
+    rpc: &RpcClient,
+    program_id: &Pubkey,
+    review: &Pubkey,
+) -> Result<Option<MovieCommentCounter>, ClientError> {
+    let (address, _) = find_comment_counter_address(program_id, review);
+    let Some(account) = rpc
+        .get_account_with_commitment(&address, rpc.commitment())
+        .await?
+        .value
+    else {
+        return Ok(None);
+    };
+    match decode_account(&account.data) {
+        Ok(MovieReviewAccount::CommentCounter(counter)) => Ok(Some(counter)),
+        _ => Err(ClientErrorKind::Custom(format!("invalid comment counter {}", address)).into()),
+    }
+}
@@ -0,0 +1,219 @@
+//! Uploads of off-chain review bodies, see `BodyFormat::OffChain`, to
+//! Arweave through an Irys node or to a Shadow Drive storage account, and
+//! `publish_body`, which uploads a body and commits the review to it.
+//!
+//! Both stores authenticate uploads with the reviewer's key, so no other
+//! wallet is involved. Irys uploads below `IRYS_FREE_BYTES` cost nothing;
+//! larger ones need the node funded beforehand. Shadow Drive uploads need a
+//! storage account owned by the reviewer with room for the body.
+
+use reqwest::multipart::{Form, Part};
+use serde::Deserialize;
+use sha2::{Digest, Sha384};
+use solana_client::{
+    client_error::{ClientError, ClientErrorKind},
+    nonblocking::rpc_client::RpcClient,
+};
+use solana_sdk::{
+    hash::hash, pubkey::Pubkey, signature::Signature, signer::Signer, transaction::Transaction,
+};
+
+use super::{body::hash_body, instructions::update_body_hash};
+use crate::state::MovieComment;
+
+pub const IRYS_NODE: &str = "https://node1.irys.xyz";
+/// Largest upload Irys nodes accept unpaid.
+pub const IRYS_FREE_BYTES: usize = 100 * 1024;
+pub const SHADOW_DRIVE_ENDPOINT: &str = "https://shadow-storage.genesysgo.net";
+
+/// ANS-104 signature type of ed25519 keys, which Solana wallets sign with.
+const ED25519_SIGNATURE_TYPE: u16 = 2;
+
+/// Where review bodies are uploaded.
+pub enum BodyStore {
+    /// An Irys node, such as `IRYS_NODE`. Bodies are bundled into Arweave and
+    /// addressed as `ar://<id>`.
+    Irys { node: String },
+    /// A Shadow Drive storage account, with bodies stored as `file_name`.
+    ShadowDrive {
+        storage_account: Pubkey,
+        file_name: String,
+    },
+}
+
+#[derive(Deserialize)]
+struct IrysReceipt {
+    id: String,
+}
+
+#[derive(Deserialize)]
+struct ShadowDriveReceipt {
+    finalized_locations: Vec<String>,
+    #[serde(default)]
+    upload_errors: Vec<ShadowDriveUploadError>,
+}
+
+#[derive(Deserialize)]
+struct ShadowDriveUploadError {
+    #[serde(default)]
+    file: String,
+    #[serde(default)]
+    error: String,
+}
+
+/// Uploads `body` signed by `signer` and returns the URI it is stored at.
+pub async fn upload_body(
+    http: &reqwest::Client,
+    store: &BodyStore,
+    signer: &dyn Signer,
+    body: &[u8],
+) -> Result<String, ClientError> {
+    match store {
+        BodyStore::Irys { node } => {
+            let receipt: IrysReceipt = http
+                .post(format!("{}/tx/solana", node.trim_end_matches('/')))
+                .header("Content-Type", "application/octet-stream")
+                .body(data_item(signer, body))
+                .send()
+                .await?
+                .error_for_status()?
+                .json()
+                .await?;
+            Ok(format!("ar://{}", receipt.id))
+        }
+        BodyStore::ShadowDrive {
+            storage_account,
+            file_name,
+        } => {
+            let message = format!(
+                "Shadow Drive Signed Message:\nStorage Account: {}\nUpload files with hash: {}",
+                storage_account,
+                hex(&hash(file_name.as_bytes()).to_bytes()),
+            );
+            let form = Form::new()
+                .part(
+                    "file",
+                    Part::bytes(body.to_vec()).file_name(file_name.clone()),
+                )
+                .text(
+                    "message",
+                    signer.sign_message(message.as_bytes()).to_string(),
+                )
+                .text("signer", signer.pubkey().to_string())
+                .text("storage_account", storage_account.to_string())
+                .text("fileNames", file_name.clone());
+            let receipt: ShadowDriveReceipt = http
+                .post(format!("{}/upload", SHADOW_DRIVE_ENDPOINT))
+                .multipart(form)
+                .send()
+                .await?
+                .error_for_status()?
+                .json()
+                .await?;
+            if let Some(error) = receipt.upload_errors.first() {
+                return Err(ClientErrorKind::Custom(format!(
+                    "shadow drive rejected {}: {}",
+                    error.file, error.error
+                ))
+                .into());
+            }
+            receipt
+                .finalized_locations
+                .into_iter()
+                .next()
+                .ok_or_else(|| {
+                    ClientErrorKind::Custom(format!("shadow drive did not store {}", file_name))
+                        .into()
+                })
+        }
+    }
+}
+
+/// Uploads `body` and sends `UpdateBodyHash` committing `review` to it, with
+/// the reviewer paying. Returns the URI the body is stored at.
+pub async fn publish_body(
+    rpc: &RpcClient,
+    http: &reqwest::Client,
+    store: &BodyStore,
+    program_id: &Pubkey,
+    reviewer: &dyn Signer,
+    review: &Pubkey,
+    body: &[u8],
+) -> Result<(String, Signature), ClientError> {
+    let uri = upload_body(http, store, reviewer, body).await?;
+    if !MovieComment::is_valid_media_uri(&uri) {
+        return Err(ClientErrorKind::Custom(format!("unusable body URI {}", uri)).into());
+    }
+    let instruction = update_body_hash(
+        program_id,
+        &reviewer.pubkey(),
+        review,
+        hash_body(body),
+        &uri,
+    );
+    let blockhash = rpc.get_latest_blockhash().await?;
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction],
+        Some(&reviewer.pubkey()),
+        &[reviewer],
+        blockhash,
+    );
+    let signature = rpc.send_and_confirm_transaction(&transaction).await?;
+    Ok((uri, signature))
+}
+
+/// An ANS-104 data item holding `data`, without target, anchor or tags.
+fn data_item(signer: &dyn Signer, data: &[u8]) -> Vec<u8> {
+    let owner = signer.pubkey().to_bytes();
+    let signature_type = ED25519_SIGNATURE_TYPE.to_string();
+    let fields: [&[u8]; 8] = [
+        b"dataitem",
+        b"1",
+        signature_type.as_bytes(),
+        &owner,
+        // Target, anchor and tags, all empty
+        b"",
+        b"",
+        b"",
+        data,
+    ];
+    let message = deep_hash_list(&fields);
+    let signature = signer.sign_message(&message);
+
+    let mut item = Vec::with_capacity(2 + 64 + 32 + 2 + 16 + data.len());
+    item.extend_from_slice(&ED25519_SIGNATURE_TYPE.to_le_bytes());
+    item.extend_from_slice(signature.as_ref());
+    item.extend_from_slice(&owner);
+    // Target and anchor absent, then zero tags taking zero bytes
+    item.extend_from_slice(&[0, 0]);
+    item.extend_from_slice(&0u64.to_le_bytes());
+    item.extend_from_slice(&0u64.to_le_bytes());
+    item.extend_from_slice(data);
+    item
+}
+
+/// Arweave's deep hash of a list of blobs.
+fn deep_hash_list(chunks: &[&[u8]]) -> [u8; 48] {
+    let mut acc = sha384(&[format!("list{}", chunks.len()).as_bytes()]);
+    for chunk in chunks {
+        acc = sha384(&[&acc, &deep_hash_blob(chunk)]);
+    }
+    acc
+}
+
+fn deep_hash_blob(blob: &[u8]) -> [u8; 48] {
+    let tag = sha384(&[format!("blob{}", blob.len()).as_bytes()]);
+    sha384(&[&tag, &sha384(&[blob])])
+}
+
+fn sha384(parts: &[&[u8]]) -> [u8; 48] {
+    let mut hasher = Sha384::new();
+    for part in parts {
+        hasher.update(part);
+    }
+    hasher.finalize().into()
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
@@ -0,0 +1,132 @@
+//! Typed websocket subscriptions over `programSubscribe` and
+//! `accountSubscribe`.
+
+use futures_util::{future::BoxFuture, stream::BoxStream, StreamExt};
+use solana_account_decoder::{UiAccount, UiAccountEncoding};
+use solana_client::{
+    nonblocking::pubsub_client::{PubsubClient, PubsubClientError},
+    rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig},
+    rpc_filter::{Memcmp, RpcFilterType},
+};
+use solana_program::pubkey::Pubkey;
+
+use crate::decoder::{decode_account, MovieReviewAccount};
+use crate::state::{MovieAccountState, MovieComment};
+
+pub type Unsubscribe = Box<dyn FnOnce() -> BoxFuture<'static, ()> + Send>;
+
+pub struct ReviewUpdate {
+    pub address: Pubkey,
+    pub slot: u64,
+    pub review: MovieAccountState,
+}
+
+pub struct CommentAdded {
+    pub address: Pubkey,
+    pub slot: u64,
+    pub comment: MovieComment,
+}
+
+/// Streams every review created or updated under `program_id`.
+pub async fn subscribe_reviews<'a>(
+    client: &'a PubsubClient,
+    program_id: &Pubkey,
+) -> Result<(BoxStream<'a, ReviewUpdate>, Unsubscribe), PubsubClientError> {
+    let config = program_config(vec![discriminator_filter(MovieAccountState::DISCRIMINATOR)]);
+    let (stream, unsubscribe) = client.program_subscribe(program_id, Some(config)).await?;
+
+    let reviews = stream.filter_map(|response| async move {
+        let address = response.value.pubkey.parse().ok()?;
+        match decode_ui_account(&response.value.account)? {
+            MovieReviewAccount::Review(review) => Some(ReviewUpdate {
+                address,
+                slot: response.context.slot,
+                review,
+            }),
+            _ => None,
+        }
+    });
+    Ok((reviews.boxed(), unsubscribe))
+}
+
+/// Streams comments added under `program_id`, optionally narrowed to a
+/// single review.
+pub async fn subscribe_comments<'a>(
+    client: &'a PubsubClient,
+    program_id: &Pubkey,
+    review: Option<&Pubkey>,
+) -> Result<(BoxStream<'a, CommentAdded>, Unsubscribe), PubsubClientError> {
+    let mut filters = vec![discriminator_filter(MovieComment::DISCRIMINATOR)];
+    if let Some(review) = review {
+        // The review key follows the discriminator and the is_initialized flag
+        let offset = 1 + 1;
+        filters.push(RpcFilterType::Memcmp(Memcmp::new_base58_encoded(
+            offset,
+            review.as_ref(),
+        )));
+    }
+    let (stream, unsubscribe) = client
+        .program_subscribe(program_id, Some(program_config(filters)))
+        .await?;
+
+    let comments = stream.filter_map(|response| async move {
+        let address = response.value.pubkey.parse().ok()?;
+        match decode_ui_account(&response.value.account)? {
+            MovieReviewAccount::Comment(comment) => Some(CommentAdded {
+                address,
+                slot: response.context.slot,
+                comment,
+            }),
+            _ => None,
+        }
+    });
+    Ok((comments.boxed(), unsubscribe))
+}
+
+/// Streams updates to a single review account.
+pub async fn subscribe_review<'a>(
+    client: &'a PubsubClient,
+    review: &Pubkey,
+) -> Result<(BoxStream<'a, ReviewUpdate>, Unsubscribe), PubsubClientError> {
+    let address = *review;
+    let (stream, unsubscribe) = client
+        .account_subscribe(review, Some(account_config()))
+        .await?;
+
+    let updates = stream.filter_map(move |response| async move {
+        match decode_ui_account(&response.value)? {
+            MovieReviewAccount::Review(review) => Some(ReviewUpdate {
+                address,
+                slot: response.context.slot,
+                review,
+            }),
+            _ => None,
+        }
+    });
+    Ok((updates.boxed(), unsubscribe))
+}
+
+/// Matches accounts tagged with `discriminator`. Accounts still in the v1
+/// layout do not match until they are migrated.
+pub(super) fn discriminator_filter(discriminator: u8) -> RpcFilterType {
+    RpcFilterType::Memcmp(Memcmp::new_base58_encoded(0, &[discriminator]))
+}
+
+fn account_config() -> RpcAccountInfoConfig {
+    RpcAccountInfoConfig {
+        encoding: Some(UiAccountEncoding::Base64),
+        ..RpcAccountInfoConfig::default()
+    }
+}
+
+pub(super) fn program_config(filters: Vec<RpcFilterType>) -> RpcProgramAccountsConfig {
+    RpcProgramAccountsConfig {
+        filters: Some(filters),
+        account_config: account_config(),
+        ..RpcProgramAccountsConfig::default()
+    }
+}
+
+fn decode_ui_account(account: &UiAccount) -> Option<MovieReviewAccount> {
+    decode_account(&account.data.decode()?).ok()
+}
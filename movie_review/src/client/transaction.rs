@@ -0,0 +1,156 @@
+//! Transaction construction that prepends compute-budget instructions.
+//!
+//! The compute unit limit is sized from a simulation of the transaction unless
+//! given explicitly, and the priority fee is either fixed or taken from recent
+//! fees paid for the accounts the transaction writes to.
+
+use solana_client::{
+    client_error::{ClientError, ClientErrorKind},
+    nonblocking::rpc_client::RpcClient,
+    rpc_config::RpcSimulateTransactionConfig,
+};
+use solana_sdk::{
+    compute_budget::ComputeBudgetInstruction, instruction::Instruction, message::Message,
+    pubkey::Pubkey, signer::Signer, transaction::Transaction,
+};
+
+/// Upper bound the runtime allows for a single transaction.
+pub const MAX_COMPUTE_UNIT_LIMIT: u32 = 1_400_000;
+
+pub enum PriorityFee {
+    /// No `set_compute_unit_price` instruction.
+    None,
+    /// A fixed price in micro-lamports per compute unit.
+    Fixed(u64),
+    /// The median of recent prioritization fees for the written accounts.
+    RecentMedian,
+}
+
+pub struct ComputeBudgetConfig {
+    /// Explicit compute unit limit. When `None`, the transaction is simulated
+    /// and the limit is set to the units consumed plus `margin_percent`.
+    pub unit_limit: Option<u32>,
+    pub margin_percent: u32,
+    pub priority_fee: PriorityFee,
+}
+
+impl Default for ComputeBudgetConfig {
+    fn default() -> Self {
+        Self {
+            unit_limit: None,
+            margin_percent: 10,
+            priority_fee: PriorityFee::None,
+        }
+    }
+}
+
+/// Builds and signs a transaction for `instructions` with compute-budget
+/// instructions prepended according to `config`.
+pub async fn build_transaction(
+    rpc: &RpcClient,
+    instructions: &[Instruction],
+    payer: &dyn Signer,
+    signers: &[&dyn Signer],
+    config: &ComputeBudgetConfig,
+) -> Result<Transaction, ClientError> {
+    let unit_price = match config.priority_fee {
+        PriorityFee::None => None,
+        PriorityFee::Fixed(price) => Some(price),
+        PriorityFee::RecentMedian => Some(recent_median_fee(rpc, instructions).await?),
+    };
+
+    let unit_limit = match config.unit_limit {
+        Some(limit) => limit,
+        None => {
+            let consumed = simulate_units(rpc, instructions, &payer.pubkey(), unit_price).await?;
+            let with_margin = consumed * (100 + config.margin_percent as u64) / 100;
+            with_margin.min(MAX_COMPUTE_UNIT_LIMIT as u64) as u32
+        }
+    };
+
+    let blockhash = rpc.get_latest_blockhash().await?;
+    let mut all_signers = vec![payer];
+    all_signers.extend_from_slice(signers);
+
+    Ok(Transaction::new_signed_with_payer(
+        &with_compute_budget(instructions, Some(unit_limit), unit_price),
+        Some(&payer.pubkey()),
+        &all_signers,
+        blockhash,
+    ))
+}
+
+/// Returns `instructions` with `set_compute_unit_limit`/`set_compute_unit_price`
+/// prepended for whichever values are given.
+pub fn with_compute_budget(
+    instructions: &[Instruction],
+    unit_limit: Option<u32>,
+    unit_price: Option<u64>,
+) -> Vec<Instruction> {
+    let mut all = Vec::with_capacity(instructions.len() + 2);
+    if let Some(limit) = unit_limit {
+        all.push(ComputeBudgetInstruction::set_compute_unit_limit(limit));
+    }
+    if let Some(price) = unit_price {
+        all.push(ComputeBudgetInstruction::set_compute_unit_price(price));
+    }
+    all.extend_from_slice(instructions);
+    all
+}
+
+async fn simulate_units(
+    rpc: &RpcClient,
+    instructions: &[Instruction],
+    payer: &Pubkey,
+    unit_price: Option<u64>,
+) -> Result<u64, ClientError> {
+    // Simulate at the maximum limit so the measurement is never cut short
+    let message = Message::new(
+        &with_compute_budget(instructions, Some(MAX_COMPUTE_UNIT_LIMIT), unit_price),
+        Some(payer),
+    );
+    let simulation = rpc
+        .simulate_transaction_with_config(
+            &Transaction::new_unsigned(message),
+            RpcSimulateTransactionConfig {
+                sig_verify: false,
+                replace_recent_blockhash: true,
+                ..RpcSimulateTransactionConfig::default()
+            },
+        )
+        .await?
+        .value;
+
+    if let Some(err) = simulation.err {
+        return Err(ClientErrorKind::TransactionError(err).into());
+    }
+    simulation.units_consumed.ok_or_else(|| {
+        ClientErrorKind::Custom("simulation did not report units consumed".into()).into()
+    })
+}
+
+async fn recent_median_fee(
+    rpc: &RpcClient,
+    instructions: &[Instruction],
+) -> Result<u64, ClientError> {
+    let mut writable: Vec<Pubkey> = instructions
+        .iter()
+        .flat_map(|ix| ix.accounts.iter())
+        .filter(|meta| meta.is_writable)
+        .map(|meta| meta.pubkey)
+        .collect();
+    writable.sort();
+    writable.dedup();
+
+    let mut fees: Vec<u64> = rpc
+        .get_recent_prioritization_fees(&writable)
+        .await?
+        .into_iter()
+        .map(|fee| fee.prioritization_fee)
+        .collect();
+    if fees.is_empty() {
+        return Ok(0);
+    }
+    fees.sort_unstable();
+    Ok(fees[fees.len() / 2])
+}
@@ -0,0 +1,69 @@
+//! `wasm-bindgen` exports of the builders. Pubkeys cross the boundary as
+//! base58 strings and instruction data as byte arrays.
+
+use std::str::FromStr;
+
+use solana_program::pubkey::Pubkey;
+use wasm_bindgen::prelude::*;
+
+use super::pda;
+use crate::instruction::MovieInstruction;
+
+fn parse_pubkey(key: &str) -> Result<Pubkey, JsError> {
+    Pubkey::from_str(key).map_err(|e| JsError::new(&format!("invalid pubkey {}: {}", key, e)))
+}
+
+#[wasm_bindgen(js_name = findReviewAddress)]
+pub fn find_review_address(
+    program_id: &str,
+    reviewer: &str,
+    title: &str,
+) -> Result<String, JsError> {
+    let (address, _) =
+        pda::find_review_address(&parse_pubkey(program_id)?, &parse_pubkey(reviewer)?, title);
+    Ok(address.to_string())
+}
+
+#[wasm_bindgen(js_name = findCommentCounterAddress)]
+pub fn find_comment_counter_address(program_id: &str, review: &str) -> Result<String, JsError> {
+    let (address, _) =
+        pda::find_comment_counter_address(&parse_pubkey(program_id)?, &parse_pubkey(review)?);
+    Ok(address.to_string())
+}
+
+#[wasm_bindgen(js_name = findCommentAddress)]
+pub fn find_comment_address(program_id: &str, review: &str, count: u64) -> Result<String, JsError> {
+    let (address, _) =
+        pda::find_comment_address(&parse_pubkey(program_id)?, &parse_pubkey(review)?, count);
+    Ok(address.to_string())
+}
+
+#[wasm_bindgen(js_name = addMovieReviewData)]
+pub fn add_movie_review_data(title: String, rating: u16, description: String) -> Vec<u8> {
+    MovieInstruction::AddMovieReviewV2 {
+        title,
+        rating,
+        description,
+    }
+    .pack()
+}
+
+#[wasm_bindgen(js_name = updateMovieReviewData)]
+pub fn update_movie_review_data(title: String, rating: u16, description: String) -> Vec<u8> {
+    MovieInstruction::UpdateMovieReviewV2 {
+        title,
+        rating,
+        description,
+    }
+    .pack()
+}
+
+#[wasm_bindgen(js_name = updateRatingData)]
+pub fn update_rating_data(rating: u16) -> Vec<u8> {
+    MovieInstruction::UpdateRating { rating }.pack()
+}
+
+#[wasm_bindgen(js_name = addCommentData)]
+pub fn add_comment_data(comment: String, media_uri: Option<String>) -> Vec<u8> {
+    MovieInstruction::AddComment { comment, media_uri }.pack()
+}
@@ -0,0 +1,172 @@
+//! Decodes raw program accounts into their typed state by dispatching on the
+//! discriminator each account starts with.
+
+use borsh::BorshDeserialize;
+use solana_program::{borsh1::try_from_slice_unchecked, program_error::ProgramError};
+
+use crate::migration::{decode_counter, decode_legacy, decode_snapshot_v1};
+use crate::state::{
+    AuditLog, BodyCommitment, CommentKarma, CommentPage, CommentVote, DuplicateChallenge,
+    ExternalIdIndex, FeaturedReview, Follow, ForeignReviewOrigin, GlobalStats, HotFeed, Movie,
+    MovieAccountState, MovieComment, MovieCommentCounter, MovieStats, Mute, PendingRemoval,
+    ProgramConfig, RatingIndex, RentPayer, ReviewCoAuthors, ReviewEdits, ReviewFingerprint,
+    ReviewLink, ReviewSnapshot, ReviewTranslation, ReviewerProfile, Session, TitleFilter,
+    TranslationBounty, TranslationSubmission,
+};
+
+pub enum MovieReviewAccount {
+    Review(MovieAccountState),
+    CommentCounter(MovieCommentCounter),
+    Comment(MovieComment),
+    Config(Box<ProgramConfig>),
+    Movie(Movie),
+    ReviewLink(ReviewLink),
+    Featured(FeaturedReview),
+    ForeignReview(MovieAccountState, ForeignReviewOrigin),
+    ExternalIdIndex(ExternalIdIndex),
+    Translation(ReviewTranslation),
+    Follow(Follow),
+    Mute(Mute),
+    CommentPage(CommentPage),
+    Snapshot(ReviewSnapshot),
+    GlobalStats(GlobalStats),
+    ReviewerProfile(ReviewerProfile),
+    MovieStats(MovieStats),
+    Session(Session),
+    AuditLog(Box<AuditLog>),
+    PendingRemoval(PendingRemoval),
+    BodyCommitment(BodyCommitment),
+    Fingerprint(ReviewFingerprint),
+    DuplicateChallenge(DuplicateChallenge),
+    CommentVote(CommentVote),
+    CommentKarma(CommentKarma),
+    HotFeed(Box<HotFeed>),
+    ReviewEdits(ReviewEdits),
+    CoAuthors(ReviewCoAuthors),
+    TranslationBounty(TranslationBounty),
+    TranslationSubmission(TranslationSubmission),
+    RatingIndex(RatingIndex),
+    TitleFilter(TitleFilter),
+    RentPayer(RentPayer),
+}
+
+/// Accounts are allocated at their max size, so trailing zero padding after
+/// the serialized state is expected and ignored. Accounts still in an earlier
+/// layout are decoded into the current types.
+pub fn decode_account(data: &[u8]) -> Result<MovieReviewAccount, ProgramError> {
+    Ok(match data.first() {
+        Some(&MovieAccountState::DISCRIMINATOR | &MovieAccountState::ANONYMOUS_DISCRIMINATOR) => {
+            MovieReviewAccount::Review(try_from_slice_unchecked(data)?)
+        }
+        Some(&MovieAccountState::FOREIGN_DISCRIMINATOR) => {
+            let review: MovieAccountState = try_from_slice_unchecked(data)?;
+            let offset = MovieAccountState::get_account_size(&review.title, &review.description);
+            let mut origin_data = data.get(offset..).ok_or(ProgramError::InvalidAccountData)?;
+            let origin = ForeignReviewOrigin::deserialize(&mut origin_data)?;
+            MovieReviewAccount::ForeignReview(review, origin)
+        }
+        Some(&MovieCommentCounter::DISCRIMINATOR) => {
+            MovieReviewAccount::CommentCounter(decode_counter(data)?)
+        }
+        Some(&MovieComment::DISCRIMINATOR) => {
+            MovieReviewAccount::Comment(try_from_slice_unchecked(data)?)
+        }
+        Some(&ProgramConfig::DISCRIMINATOR) => {
+            // Configs created before the newest fields were appended are
+            // shorter and read those fields as zero
+            let mut data = data.to_vec();
+            data.resize(data.len().max(ProgramConfig::get_account_size()), 0);
+            MovieReviewAccount::Config(Box::new(try_from_slice_unchecked(&data)?))
+        }
+        Some(&Movie::DISCRIMINATOR) => {
+            let mut data = data.to_vec();
+            data.resize(data.len() + Movie::LEGACY_PADDING_LEN, 0);
+            MovieReviewAccount::Movie(try_from_slice_unchecked(&data)?)
+        }
+        Some(&ReviewLink::DISCRIMINATOR) => {
+            MovieReviewAccount::ReviewLink(try_from_slice_unchecked(data)?)
+        }
+        Some(&FeaturedReview::DISCRIMINATOR) => {
+            MovieReviewAccount::Featured(try_from_slice_unchecked(data)?)
+        }
+        Some(&ExternalIdIndex::DISCRIMINATOR) => {
+            MovieReviewAccount::ExternalIdIndex(try_from_slice_unchecked(data)?)
+        }
+        Some(&ReviewTranslation::DISCRIMINATOR) => {
+            MovieReviewAccount::Translation(try_from_slice_unchecked(data)?)
+        }
+        Some(&Follow::DISCRIMINATOR) => MovieReviewAccount::Follow(try_from_slice_unchecked(data)?),
+        Some(&Mute::DISCRIMINATOR) => MovieReviewAccount::Mute(try_from_slice_unchecked(data)?),
+        Some(&CommentPage::DISCRIMINATOR) => {
+            MovieReviewAccount::CommentPage(try_from_slice_unchecked(data)?)
+        }
+        Some(&ReviewSnapshot::DISCRIMINATOR) => MovieReviewAccount::Snapshot(match data.get(2) {
+            Some(1) => decode_snapshot_v1(data)?,
+            _ => try_from_slice_unchecked(data)?,
+        }),
+        Some(&GlobalStats::DISCRIMINATOR) => {
+            // Stats created before the lamport total read it as zero
+            let mut data = data.to_vec();
+            data.resize(data.len().max(GlobalStats::get_account_size()), 0);
+            MovieReviewAccount::GlobalStats(try_from_slice_unchecked(&data)?)
+        }
+        Some(&ReviewerProfile::DISCRIMINATOR) => {
+            // Profiles created before the relay nonce read it as zero
+            let mut data = data.to_vec();
+            data.resize(data.len().max(ReviewerProfile::get_account_size()), 0);
+            MovieReviewAccount::ReviewerProfile(try_from_slice_unchecked(&data)?)
+        }
+        Some(&MovieStats::DISCRIMINATOR) => {
+            MovieReviewAccount::MovieStats(try_from_slice_unchecked(data)?)
+        }
+        Some(&Session::DISCRIMINATOR) => {
+            MovieReviewAccount::Session(try_from_slice_unchecked(data)?)
+        }
+        Some(&AuditLog::DISCRIMINATOR) => {
+            MovieReviewAccount::AuditLog(Box::new(try_from_slice_unchecked(data)?))
+        }
+        Some(&PendingRemoval::DISCRIMINATOR) => {
+            MovieReviewAccount::PendingRemoval(try_from_slice_unchecked(data)?)
+        }
+        Some(&BodyCommitment::DISCRIMINATOR) => {
+            MovieReviewAccount::BodyCommitment(try_from_slice_unchecked(data)?)
+        }
+        Some(&ReviewFingerprint::DISCRIMINATOR) => {
+            MovieReviewAccount::Fingerprint(try_from_slice_unchecked(data)?)
+        }
+        Some(&DuplicateChallenge::DISCRIMINATOR) => {
+            MovieReviewAccount::DuplicateChallenge(try_from_slice_unchecked(data)?)
+        }
+        Some(&CommentVote::DISCRIMINATOR) => {
+            MovieReviewAccount::CommentVote(try_from_slice_unchecked(data)?)
+        }
+        Some(&CommentKarma::DISCRIMINATOR) => {
+            MovieReviewAccount::CommentKarma(try_from_slice_unchecked(data)?)
+        }
+        Some(&HotFeed::DISCRIMINATOR) => {
+            MovieReviewAccount::HotFeed(Box::new(try_from_slice_unchecked(data)?))
+        }
+        Some(&ReviewEdits::DISCRIMINATOR) => {
+            MovieReviewAccount::ReviewEdits(try_from_slice_unchecked(data)?)
+        }
+        Some(&ReviewCoAuthors::DISCRIMINATOR) => {
+            MovieReviewAccount::CoAuthors(try_from_slice_unchecked(data)?)
+        }
+        Some(&TranslationBounty::DISCRIMINATOR) => {
+            MovieReviewAccount::TranslationBounty(try_from_slice_unchecked(data)?)
+        }
+        Some(&TranslationSubmission::DISCRIMINATOR) => {
+            MovieReviewAccount::TranslationSubmission(try_from_slice_unchecked(data)?)
+        }
+        Some(&RatingIndex::DISCRIMINATOR) => {
+            MovieReviewAccount::RatingIndex(try_from_slice_unchecked(data)?)
+        }
+        Some(&TitleFilter::DISCRIMINATOR) => {
+            MovieReviewAccount::TitleFilter(try_from_slice_unchecked(data)?)
+        }
+        Some(&RentPayer::DISCRIMINATOR) => {
+            MovieReviewAccount::RentPayer(try_from_slice_unchecked(data)?)
+        }
+        _ => decode_legacy(data)?.ok_or(ProgramError::InvalidAccountData)?,
+    })
+}
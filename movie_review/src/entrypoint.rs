@@ -2,6 +2,7 @@ use solana_program::{
     account_info::AccountInfo, entrypoint, entrypoint::ProgramResult, msg, pubkey::Pubkey,
 };
 
+use crate::error::ReviewError;
 use crate::processor;
 
 entrypoint!(process_instruction);
@@ -11,13 +12,23 @@ fn process_instruction(
     accounts: &[AccountInfo],
     instruction_data: &[u8],
 ) -> ProgramResult {
+    // Log the data length rather than the bytes: formatting a near-1KB payload
+    // with `{:?}` costs several KB of the 32KB heap
     msg!(
-        "process_instruction: {}: {} accounts, data={:?}",
+        "process_instruction: {}: {} accounts, {} bytes of data",
         program_id,
         accounts.len(),
-        instruction_data
+        instruction_data.len()
     );
 
-    processor::process_instruction(program_id, accounts, instruction_data)?;
+    let result = processor::process_instruction(program_id, accounts, instruction_data);
+
+    #[cfg(feature = "heap-stats")]
+    msg!("heap used: {} bytes", crate::heap::heap_used());
+
+    if let Err(error) = result {
+        soldev_program_errors::print_error::<ReviewError>(&error);
+        return Err(error);
+    }
     Ok(())
 }
@@ -1,7 +1,13 @@
-use solana_program::program_error::ProgramError;
+use num_derive::FromPrimitive;
+use num_traits::FromPrimitive;
+use solana_program::{
+    decode_error::DecodeError,
+    msg,
+    program_error::{PrintProgramError, ProgramError},
+};
 use thiserror::Error;
 
-#[derive(Debug, Error)]
+#[derive(Clone, Copy, Debug, Eq, Error, FromPrimitive, PartialEq)]
 pub enum ReviewError {
     #[error("Account not initialized yet")]
     UninitializedAccount,
@@ -11,6 +17,146 @@ pub enum ReviewError {
     InvalidDataLength,
     #[error("Rating greater than 5 or less than 1")]
     InvalidRating,
+    #[error("Account discriminator does not match the expected account type")]
+    InvalidDiscriminator,
+    #[error("Reviewers cannot comment on their own review")]
+    SelfComment,
+    #[error("Reviewer has already reviewed this movie")]
+    DuplicateReview,
+    #[error("Salt and signer do not match the authorship commitment")]
+    InvalidAuthorshipProof,
+    #[error("Posting requires a reviewer pass")]
+    MissingReviewerPass,
+    #[error("Reviewer already holds a pass")]
+    PassAlreadyIssued,
+    #[error("Review collection missing or not the current one")]
+    InvalidReviewCollection,
+    #[error("Release attestation missing, untrusted or not for this movie")]
+    InvalidReleaseAttestation,
+    #[error("No candidate reviews to draw from")]
+    NoFeaturedCandidates,
+    #[error("Today's featured review has already been drawn")]
+    FeaturedAlreadyDrawn,
+    #[error("Randomness account untrusted, already fulfilled or not the one requested")]
+    InvalidRandomness,
+    #[error("Requested randomness has not been fulfilled yet")]
+    RandomnessNotFulfilled,
+    #[error("No featured review draw is pending")]
+    NoPendingDraw,
+    #[error("Candidates do not match the ones the draw was requested for")]
+    FeaturedCandidatesMismatch,
+    #[error("VAA missing, unverified or not from the trusted review emitter")]
+    InvalidForeignMessage,
+    #[error("External ID must be namespace:value in lowercase letters and digits")]
+    InvalidExternalId,
+    #[error("External ID already assigned")]
+    ExternalIdTaken,
+    #[error("Language must be a lowercase BCP-47 tag")]
+    InvalidLanguageTag,
+    #[error("Review already has a translation in this language")]
+    DuplicateTranslation,
+    #[error("Review body is not valid in its format")]
+    InvalidRichText,
+    #[error("Media URI must be a short https, ipfs or ar URI")]
+    InvalidMediaUri,
+    #[error("Comments are disabled on this review")]
+    CommentsDisabled,
+    #[error("Commenter does not meet the review's comment policy")]
+    CommentPolicyNotMet,
+    #[error("Reviewer has muted this commenter")]
+    Muted,
+    #[error("Parent comment is not a comment on this review")]
+    InvalidParentComment,
+    #[error("Replies cannot be nested any deeper")]
+    ThreadTooDeep,
+    #[error("Comment page does not match the review's counter")]
+    InvalidCommentPage,
+    #[error("A snapshot was already written for this slot")]
+    SnapshotExists,
+    #[error("Snapshot slot is in the future")]
+    InvalidSnapshotSlot,
+    #[error("Snapshot reviews must be distinct and in ascending key order")]
+    UnorderedSnapshotReviews,
+    #[error("Signer is not the configured tip program's tip authority")]
+    InvalidTipAuthority,
+    #[error("Too many reviews for a single recompute call")]
+    RecomputePageTooLarge,
+    #[error("Recompute reviews must be distinct and in ascending key order")]
+    UnorderedRecomputeReviews,
+    #[error("Recompute pass counted fewer reviews than the movie is known to have")]
+    IncompleteRecompute,
+    #[error("Content limits must fit an empty account and stay within the growable size")]
+    InvalidContentLimits,
+    #[error("Rating scale must span at least two values with at most 4 decimals")]
+    InvalidRatingScale,
+    #[error("Review still has comments; delete them with a cascade first")]
+    ReviewHasComments,
+    #[error("Refund recipient is not the account that paid the comment's rent")]
+    WrongRefundRecipient,
+    #[error("Comment is already at the address its seed bytes derive")]
+    CommentNotMisplaced,
+    #[error("Too many programs in the CPI allowlist")]
+    TooManyCpiCallers,
+    #[error("Calling program is not in the CPI allowlist")]
+    CpiCallerNotAllowed,
+    #[error("Session must expire after the current slot and within the maximum duration")]
+    InvalidSessionExpiry,
+    #[error("Session scopes must be a non-empty set of known scopes")]
+    InvalidSessionScope,
+    #[error("Session has expired or does not cover this instruction")]
+    SessionNotValid,
+    #[error("Relayed review is not signed by its reviewer")]
+    InvalidRelayedSignature,
+    #[error("Relayed review signature has expired")]
+    RelayedSignatureExpired,
+    #[error("Relayed review does not sign the reviewer's next nonce")]
+    InvalidRelayNonce,
+    #[error("Feature is switched off in the program config")]
+    FeatureDisabled,
+    #[error("Features must be a set of known feature bits")]
+    UnknownFeature,
+    #[error("Review already has a pending removal")]
+    RemovalPending,
+    #[error("Appeal window has closed")]
+    AppealWindowClosed,
+    #[error("Appeal window is still open")]
+    AppealWindowOpen,
+    #[error("Removal has already been appealed")]
+    AlreadyAppealed,
+    #[error("Removal has no appeal to resolve")]
+    NoAppealFiled,
+    #[error("Removal has an unresolved appeal")]
+    AppealPending,
+    #[error("Body URI is too long or has an unsupported scheme")]
+    InvalidBodyUri,
+    #[error("Off-chain bodies need a committed hash")]
+    BodyHashRequired,
+    #[error("Fingerprint does not match the review's current description")]
+    StaleFingerprint,
+    #[error("Reviews are not close enough to be duplicates")]
+    NotDuplicate,
+    #[error("Wallet is not proven older than the configured minimum age")]
+    WalletTooNew,
+    #[error("Commenter cannot vote on their own comment")]
+    SelfVote,
+    #[error("Voter already cast this vote")]
+    AlreadyVoted,
+    #[error("Mint is not the config's edit burn mint")]
+    InvalidBurnMint,
+    #[error("Co-authors must be at most 3 distinct keys other than the reviewer, with positive shares summing to at most 10000 basis points")]
+    InvalidCoAuthors,
+    #[error("Signer is not listed at this share as a co-author of the review")]
+    NotCoAuthor,
+    #[error("Critic authority must be a program address of its program")]
+    InvalidCriticAuthority,
+    #[error("Translation bounty must be positive")]
+    InvalidBountyAmount,
+    #[error("Release date must not be negative")]
+    InvalidReleaseDate,
+    #[error("Movie has not been released yet")]
+    MovieNotReleased,
+    #[error("Translation bounty has a submission under review")]
+    BountyUnderReview,
 }
 
 impl From<ReviewError> for ProgramError {
@@ -18,3 +164,18 @@ impl From<ReviewError> for ProgramError {
         ProgramError::Custom(e as u32)
     }
 }
+
+impl<T> DecodeError<T> for ReviewError {
+    fn type_of() -> &'static str {
+        "ReviewError"
+    }
+}
+
+impl PrintProgramError for ReviewError {
+    fn print<E>(&self)
+    where
+        E: 'static + std::error::Error + DecodeError<E> + PrintProgramError + FromPrimitive,
+    {
+        msg!(&self.to_string());
+    }
+}
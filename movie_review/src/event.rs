@@ -0,0 +1,141 @@
+//! Typed events for indexers, logged with `sol_log_data`. Each event is one
+//! data entry holding its Borsh encoding, so the first byte names the variant.
+//!
+//! Notifications start with the key they are for, so notification indexers
+//! can match a user against the bytes at `RECIPIENT_OFFSET` without decoding.
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{log::sol_log_data, pubkey::Pubkey};
+
+use crate::state::{AdminAction, FeeKind};
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, Eq, PartialEq)]
+pub enum ReviewEvent {
+    /// A fee was paid into the config PDA.
+    FeeCharged {
+        payer: Pubkey,
+        kind: FeeKind,
+        amount: u64,
+    },
+    /// Someone commented on the recipient's review.
+    ReviewCommented {
+        recipient: Pubkey,
+        review: Pubkey,
+        comment: Pubkey,
+        commenter: Pubkey,
+    },
+    /// Someone replied to the recipient's comment.
+    CommentReplied {
+        recipient: Pubkey,
+        comment: Pubkey,
+        reply: Pubkey,
+        replier: Pubkey,
+    },
+    /// The recipient was tipped for their review. `amount` is their share,
+    /// which may vest.
+    TipReceived {
+        recipient: Pubkey,
+        review: Pubkey,
+        tipper: Pubkey,
+        amount: u64,
+    },
+    /// The recipient was mentioned in a comment.
+    Mentioned {
+        recipient: Pubkey,
+        comment: Pubkey,
+        author: Pubkey,
+    },
+    /// A review's comments were added or deleted. `total_comments_created`
+    /// never drops, so comment indexes are not reused.
+    CommentCountChanged {
+        review: Pubkey,
+        active_comments: u64,
+        total_comments_created: u64,
+    },
+    /// An idempotent create found `account` already holding what it would
+    /// have written, and left it unchanged.
+    AlreadyExists { account: Pubkey },
+    /// The config authority proposed removing the recipient's review, which
+    /// they may appeal until `appeal_deadline`.
+    RemovalProposed {
+        recipient: Pubkey,
+        review: Pubkey,
+        appeal_deadline: u64,
+    },
+    /// The author of `review` appealed its removal.
+    RemovalAppealed { review: Pubkey },
+    /// The config authority ruled on the recipient's appeal. An upheld appeal
+    /// keeps the review up.
+    AppealResolved {
+        recipient: Pubkey,
+        review: Pubkey,
+        upheld: bool,
+    },
+    /// The config authority took `action` on `target`, recorded in the audit
+    /// log under `sequence`.
+    AdminAction {
+        sequence: u64,
+        authority: Pubkey,
+        action: AdminAction,
+        target: Pubkey,
+    },
+    /// `challenger` flagged `review` as a copy of `original`, their
+    /// fingerprints `distance` bits apart.
+    DuplicateFlagged {
+        challenger: Pubkey,
+        review: Pubkey,
+        original: Pubkey,
+        distance: u8,
+    },
+    /// Votes moved the recipient's comment across the config's hide
+    /// threshold, flagging it hidden or clearing the flag.
+    CommentVisibilityChanged {
+        recipient: Pubkey,
+        comment: Pubkey,
+        hidden: bool,
+        score: i64,
+    },
+    /// The recipient's title filter matched the title of their new `review`,
+    /// so they have probably reviewed the movie under another spelling.
+    PossibleDuplicateTitle {
+        recipient: Pubkey,
+        review: Pubkey,
+        title: String,
+    },
+}
+
+/// Where the recipient of a notification starts in its encoding, right after
+/// the variant byte.
+pub const RECIPIENT_OFFSET: usize = 1;
+
+impl ReviewEvent {
+    /// Who the event notifies, if it is a notification.
+    pub fn recipient(&self) -> Option<&Pubkey> {
+        match self {
+            ReviewEvent::FeeCharged { .. }
+            | ReviewEvent::CommentCountChanged { .. }
+            | ReviewEvent::AlreadyExists { .. }
+            | ReviewEvent::AdminAction { .. }
+            | ReviewEvent::RemovalAppealed { .. }
+            | ReviewEvent::DuplicateFlagged { .. } => None,
+            ReviewEvent::ReviewCommented { recipient, .. }
+            | ReviewEvent::CommentReplied { recipient, .. }
+            | ReviewEvent::TipReceived { recipient, .. }
+            | ReviewEvent::Mentioned { recipient, .. }
+            | ReviewEvent::RemovalProposed { recipient, .. }
+            | ReviewEvent::AppealResolved { recipient, .. }
+            | ReviewEvent::CommentVisibilityChanged { recipient, .. }
+            | ReviewEvent::PossibleDuplicateTitle { recipient, .. } => Some(recipient),
+        }
+    }
+}
+
+pub fn emit(event: &ReviewEvent) {
+    sol_log_data(&[&borsh::to_vec(event).unwrap()]);
+}
+
+/// Decodes one `Program data:` entry of a transaction log, already base64
+/// decoded. Returns `None` for data this program did not log.
+pub fn decode_event(data: &[u8]) -> Option<ReviewEvent> {
+    ReviewEvent::try_from_slice(data).ok()
+}
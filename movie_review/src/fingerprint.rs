@@ -0,0 +1,58 @@
+//! SimHash fingerprints of review descriptions, for spotting copy-pasted
+//! reviews.
+//!
+//! Words are runs of alphanumeric characters, compared ASCII
+//! case-insensitively. Every word and every pair of adjacent words votes on
+//! each of the 64 bits with its FNV-1a hash, so descriptions differing in a
+//! few words land a few bits apart, and bodies sharing no wording about half
+//! of them. Two reviews within `MAX_DUPLICATE_DISTANCE` bits can be flagged
+//! with `ChallengeDuplicate`.
+
+pub const MAX_DUPLICATE_DISTANCE: u32 = 3;
+
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+fn word_hash(word: &str) -> u64 {
+    word.bytes().fold(FNV_OFFSET, |hash, byte| {
+        (hash ^ byte.to_ascii_lowercase() as u64).wrapping_mul(FNV_PRIME)
+    })
+}
+
+/// The SimHash of `text`, zero for text without words.
+pub fn simhash(text: &str) -> u64 {
+    let mut votes = [0i32; 64];
+    let mut vote = |feature: u64| {
+        for (bit, count) in votes.iter_mut().enumerate() {
+            if (feature >> bit) & 1 == 1 {
+                *count += 1;
+            } else {
+                *count -= 1;
+            }
+        }
+    };
+
+    let mut previous: Option<u64> = None;
+    for word in text.split(|c: char| !c.is_alphanumeric()) {
+        if word.is_empty() {
+            continue;
+        }
+        let hash = word_hash(word);
+        vote(hash);
+        if let Some(previous) = previous {
+            vote((previous ^ hash.rotate_left(32)).wrapping_mul(FNV_PRIME));
+        }
+        previous = Some(hash);
+    }
+
+    votes
+        .iter()
+        .enumerate()
+        .filter(|(_, &count)| count > 0)
+        .fold(0, |fingerprint, (bit, _)| fingerprint | (1 << bit))
+}
+
+/// Bits in which two fingerprints differ.
+pub fn distance(a: u64, b: u64) -> u32 {
+    (a ^ b).count_ones()
+}
@@ -0,0 +1,48 @@
+//! Heap accounting and allocator tuning.
+//!
+//! The runtime's bump allocator never frees, and keeps its current position in
+//! the first word of the heap region, so the bytes allocated so far are also
+//! the instruction's heap high-water mark. `heap-stats` logs that figure after
+//! each instruction.
+//!
+//! `large-heap` swaps in a bump allocator that uses a 256KB region instead of
+//! the default 32KB. Transactions must then request the larger frame with
+//! `ComputeBudgetInstruction::request_heap_frame(LARGE_HEAP_LENGTH)`.
+
+#[cfg(target_os = "solana")]
+use solana_program::entrypoint::HEAP_START_ADDRESS;
+
+pub const LARGE_HEAP_LENGTH: usize = 256 * 1024;
+
+#[cfg(feature = "large-heap")]
+pub const ACTIVE_HEAP_LENGTH: usize = LARGE_HEAP_LENGTH;
+#[cfg(not(feature = "large-heap"))]
+pub const ACTIVE_HEAP_LENGTH: usize = solana_program::entrypoint::HEAP_LENGTH;
+
+#[cfg(all(feature = "large-heap", target_os = "solana"))]
+#[global_allocator]
+static ALLOCATOR: solana_program::entrypoint::BumpAllocator =
+    solana_program::entrypoint::BumpAllocator {
+        start: HEAP_START_ADDRESS as usize,
+        len: LARGE_HEAP_LENGTH,
+    };
+
+/// Bytes allocated so far by the current instruction. Always zero off-chain.
+pub fn heap_used() -> usize {
+    #[cfg(target_os = "solana")]
+    {
+        // SAFETY: the bump allocator owns the first word of the heap region
+        // and stores its current (downward-growing) position there, or zero
+        // before the first allocation
+        let pos = unsafe { *(HEAP_START_ADDRESS as *const usize) };
+        if pos == 0 {
+            0
+        } else {
+            HEAP_START_ADDRESS as usize + ACTIVE_HEAP_LENGTH - pos
+        }
+    }
+    #[cfg(not(target_os = "solana"))]
+    {
+        0
+    }
+}
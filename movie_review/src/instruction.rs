@@ -1,5 +1,9 @@
 use borsh::BorshDeserialize;
-use solana_program::program_error::ProgramError;
+use solana_program::{clock::UnixTimestamp, program_error::ProgramError, pubkey::Pubkey};
+
+use crate::state::{
+    BodyFormat, CommentPolicy, CommentVoteKind, ContentLimits, FeeSchedule, RatingScale,
+};
 
 pub enum MovieInstruction {
     AddMovieReview {
@@ -14,6 +18,389 @@ pub enum MovieInstruction {
     },
     AddComment {
         comment: String,
+        /// Optional `https://`, `ipfs://` or `ar://` attachment.
+        media_uri: Option<String>,
+    },
+    /// Rewrites a v1-layout account in the current layout. Permissionless and
+    /// a no-op for accounts that are already current.
+    MigrateAccount,
+    /// Creates the config PDA. The signer becomes its authority.
+    InitializeConfig {
+        allow_self_comments: bool,
+        require_reviewer_pass: bool,
+    },
+    UpdateConfig {
+        allow_self_comments: bool,
+        require_reviewer_pass: bool,
+    },
+    RegisterMovie {
+        title: String,
+    },
+    /// Reviews a registered movie; the title is taken from the movie account.
+    AddReviewForMovie {
+        rating: u16,
+        description: String,
+    },
+    /// Points a registered movie's review address at an existing
+    /// title-seeded review by the same reviewer.
+    LinkLegacyReview,
+    /// Posts a review whose author is recorded only as `commitment`. Any
+    /// account can pay for it, so the author's wallet need not appear.
+    AddAnonymousReview {
+        commitment: Pubkey,
+        title: String,
+        rating: u16,
+        description: String,
+    },
+    /// Replaces the commitment with the signer's key once the signer shows
+    /// the salt behind it.
+    RevealAuthorship {
+        salt: [u8; 32],
+    },
+    /// Creates the non-transferable reviewer pass mint. Config authority only.
+    InitializePassMint,
+    /// Mints a pass to a verified reviewer. Config authority only.
+    IssueReviewerPass,
+    /// Creates a new sized Metaplex collection and verifies review NFTs into
+    /// it from now on. NFTs already minted stay in their collection. Config
+    /// authority only.
+    RotateReviewCollection {
+        name: String,
+        symbol: String,
+        uri: String,
+    },
+    /// Mints the reviewer an NFT of their review, verified into the current
+    /// review collection. `uri` points at the off-chain JSON metadata.
+    MintReviewNft {
+        uri: String,
+    },
+    /// Sets the oracle program and feed whose attestations verify releases.
+    /// Config authority only.
+    SetReleaseOracle {
+        oracle: Pubkey,
+        feed_id: [u8; 32],
+    },
+    /// Marks a registered movie as a verified theatrical release, given an
+    /// attestation from the configured oracle feed. Permissionless.
+    MarkVerifiedRelease,
+    /// Commits today's featured review draw to the candidate reviews passed
+    /// after the fixed accounts and to an unfulfilled VRF randomness account,
+    /// escrowing a bounty for the fulfillment crank. Permissionless, and only
+    /// the first request of a day succeeds.
+    RequestFeaturedDraw,
+    /// Picks the winner of the pending draw once its randomness is fulfilled.
+    /// A permissionless crank that pays the signer the escrowed bounty.
+    FulfillFeaturedDraw,
+    /// Sets the VRF program trusted for featured review draws. Config
+    /// authority only.
+    SetVrfProgram {
+        vrf_program: Pubkey,
+    },
+    /// Sets the Wormhole bridge and the emitter whose review messages are
+    /// imported. Config authority only.
+    SetForeignEmitter {
+        wormhole_program: Pubkey,
+        emitter_chain: u16,
+        emitter_address: [u8; 32],
+    },
+    /// Materializes a review announced by a verified VAA from the trusted
+    /// emitter. Permissionless, and each VAA imports once.
+    ImportForeignReview,
+    /// Reads the program's `ProgramData` account and returns its
+    /// `UpgradeAuthorityStatus` as Borsh return data. Read-only.
+    GetUpgradeAuthorityStatus,
+    /// Sets the fee of each instruction kind and who is exempt from them.
+    /// Config authority only.
+    SetFeeSchedule {
+        fees: FeeSchedule,
+        fee_exempt_mint: Pubkey,
+        fee_exempt_min_balance: u64,
+    },
+    /// Adds lamports to any program account that fell below rent exemption,
+    /// e.g. after a rent change. Anyone may pay.
+    TopUpAccount,
+    /// Returns the lamports a program account holds above rent exemption to
+    /// the key that controls it: a review's reviewer, a comment's commenter
+    /// or the config authority.
+    WithdrawExcessLamports,
+    /// Assigns a registered movie its external ID and indexes it. Config
+    /// authority only, and each movie and ID can be assigned once.
+    SetMovieExternalId {
+        external_id: String,
+    },
+    /// Adds the reviewer's description of their review in `language`.
+    AddReviewTranslation {
+        language: String,
+        description: String,
+    },
+    UpdateReviewTranslation {
+        language: String,
+        description: String,
+    },
+    /// Closes a translation, refunding its rent to the reviewer.
+    DeleteReviewTranslation {
+        language: String,
+    },
+    /// Sets how the review's description is rendered. Later updates must be
+    /// valid in the same format.
+    SetReviewBodyFormat {
+        body_format: BodyFormat,
+    },
+    /// Records that the signer follows `reviewer`.
+    FollowReviewer {
+        reviewer: Pubkey,
+    },
+    /// Closes a follow, refunding its rent to the follower.
+    UnfollowReviewer,
+    /// Sets whether and by whom the review can be commented on.
+    SetCommentSettings {
+        comments_enabled: bool,
+        comment_policy: CommentPolicy,
+    },
+    /// Blocks `user` from commenting on any of the signer's reviews.
+    MuteUser {
+        user: Pubkey,
+    },
+    /// Closes a mute, refunding its rent to the reviewer.
+    UnmuteUser,
+    /// Adds a comment in reply to another comment on the same review.
+    ReplyToComment {
+        comment: String,
+        media_uri: Option<String>,
+    },
+    /// Adds a comment to the review's current comment page.
+    AddPagedComment {
+        comment: String,
+    },
+    /// Writes a `ReviewSnapshot` of the reviews passed, keyed by `slot`.
+    /// Config authority only, and each slot can be written once.
+    WriteSnapshot {
+        slot: u64,
+    },
+    /// Adds `amount` to the global tip total. Only callable by the configured
+    /// tip program.
+    RecordTip {
+        amount: u64,
+    },
+    /// Sets the program allowed to record tips. Config authority only.
+    SetTipProgram {
+        tip_program: Pubkey,
+    },
+    /// Recounts a movie's `MovieStats` from a page of its reviews, continuing
+    /// the pass earlier calls started unless `restart`. `finish` publishes the
+    /// pass. Permissionless.
+    RecomputeStats {
+        restart: bool,
+        finish: bool,
+    },
+    /// Changes only a review's rating, leaving its title and description
+    /// untouched.
+    UpdateRating {
+        rating: u16,
+    },
+    /// Appends `text` to a review's description, growing the account when
+    /// it no longer fits. The reviewer pays the added rent.
+    AppendToDescription {
+        text: String,
+    },
+    /// Sets the sizes new reviews and comments are created at. Config
+    /// authority only.
+    SetContentLimits {
+        limits: ContentLimits,
+    },
+    /// Sets the range new ratings must fall in and how they are displayed.
+    /// Config authority only.
+    SetRatingScale {
+        scale: RatingScale,
+    },
+    /// Closes the signer's review and its comment counter, refunding their
+    /// rent to the reviewer. With `cascade`, first closes the comments passed
+    /// in, refunding each commenter; the review is only closed by the call
+    /// that leaves it without comments.
+    DeleteReview {
+        cascade: bool,
+    },
+    /// Moves a comment written at its little-endian address to the one
+    /// `comment_seed_bytes` derives. Permissionless.
+    RepairCommentAddress,
+    /// `AddMovieReview` that succeeds without effect if the signer's review of
+    /// `title` already exists with this rating and description, so a client
+    /// can resend it when unsure the first attempt landed.
+    AddMovieReviewIdempotent {
+        title: String,
+        rating: u16,
+        description: String,
+    },
+    /// Returns the `RequiredAccounts` of the instruction with tag
+    /// `instruction_tag` as Borsh return data. Takes no accounts.
+    GetRequiredAccounts {
+        instruction_tag: u8,
+    },
+    /// Sets the programs allowed to invoke state-changing instructions
+    /// through CPI, at most `ProgramConfig::MAX_CPI_CALLERS`. An empty list
+    /// allows any. Config authority only.
+    SetCpiCallers {
+        callers: Vec<Pubkey>,
+    },
+    /// Lets `session_key` sign for the signer in the instructions `scopes`
+    /// allows, a set of `Session` scope bits, until `expires_slot`.
+    CreateSession {
+        session_key: Pubkey,
+        expires_slot: u64,
+        scopes: u8,
+    },
+    /// Closes a session before it expires, refunding its rent to its
+    /// authority.
+    RevokeSession,
+    /// `AddMovieReview` submitted by a relayer that pays for it. The reviewer
+    /// signs `MovieAccountState::relayed_review_message` instead of the
+    /// transaction, verified by an ed25519 program instruction placed just
+    /// before this one. `nonce` must be the reviewer's next relay nonce.
+    AddRelayedReview {
+        title: String,
+        rating: u16,
+        description: String,
+        nonce: u64,
+        expires_slot: u64,
+    },
+    /// Switches subsystems on and off, replacing the config's `features`
+    /// with the `ProgramConfig::FEATURE_*` bits given. Config authority only.
+    SetFeatures {
+        features: u64,
+    },
+    /// Sets the slots authors have to appeal a proposed removal, zero for
+    /// the default. Config authority only.
+    SetRemovalWindow {
+        slots: u64,
+    },
+    /// Proposes removing a review, which stays up until the appeal window
+    /// ends. Config authority only.
+    ProposeRemoval,
+    /// Appeals the proposed removal of the signer's review, within the
+    /// appeal window.
+    AppealRemoval,
+    /// Rules on an appeal. Upholding it cancels the removal; rejecting it
+    /// lets the removal be finalized once the window ends. Config authority
+    /// only.
+    ResolveAppeal {
+        uphold: bool,
+    },
+    /// Closes a review whose appeal window ended without an upheld appeal,
+    /// refunding its author, like `DeleteReview` with its `cascade`. Config
+    /// authority only.
+    FinalizeRemoval {
+        cascade: bool,
+    },
+    /// Moves the signer's review body off-chain: the description becomes
+    /// `uri`, the body is rendered as `BodyFormat::OffChain`, and `hash`,
+    /// the SHA-256 of the body stored there, is committed to the review's
+    /// `BodyCommitment`. Called again to publish a new version.
+    UpdateBodyHash {
+        hash: [u8; 32],
+        uri: String,
+    },
+    /// Records or refreshes the `fingerprint::simhash` of a review's
+    /// description. Anyone can call it, paying the fingerprint's rent.
+    RecordFingerprint,
+    /// Flags the first review as a copy of the second, whose recorded and
+    /// current fingerprints must be within
+    /// `fingerprint::MAX_DUPLICATE_DISTANCE` bits. Anyone can call it,
+    /// paying for the challenge.
+    ChallengeDuplicate,
+    /// Sets how many slots old a wallet must be to post a review, zero to
+    /// switch the gate off, and the program whose attestations prove it.
+    /// Config authority only.
+    SetAgeGate {
+        min_wallet_age: u64,
+        age_attester: Pubkey,
+    },
+    /// Likes or reports a standalone comment, or switches the signer's vote
+    /// on it to `kind`, charging `FeeKind::Vote`. Flags the comment hidden
+    /// once its score falls below the config's hide threshold.
+    VoteOnComment {
+        kind: CommentVoteKind,
+    },
+    /// Sets the score below which comments are flagged hidden, zero to
+    /// switch hiding off. Config authority only.
+    SetHideThreshold {
+        threshold: i64,
+    },
+    /// Sets how many tokens of `mint` each edit of a review beyond the free
+    /// one burns, zero to make every edit free. Config authority only.
+    SetEditBurn {
+        mint: Pubkey,
+        amount: u64,
+    },
+    /// Lists up to `ReviewCoAuthors::MAX_CO_AUTHORS` co-authors on the
+    /// signer's review, each with their share of its tips and rewards in
+    /// basis points. An empty list clears them.
+    SetCoAuthors {
+        co_authors: Vec<(Pubkey, u16)>,
+    },
+    /// Accepts the signer's listing as a co-author of a review at
+    /// `share_bps`, which must match the listed share.
+    AcceptCoAuthorship {
+        share_bps: u16,
+    },
+    /// Lets `authority`, a PDA of `authority_program`, sign for the signer
+    /// wherever a session could, see `ReviewerProfile::authority`. Default
+    /// keys switch it off.
+    SetCriticAuthority {
+        authority: Pubkey,
+        authority_program: Pubkey,
+    },
+    /// Escrows `amount` lamports for a translation of the signer's review
+    /// into `language`, adding to any bounty already open for it.
+    FundTranslationBounty {
+        language: String,
+        amount: u64,
+    },
+    /// Submits the signer's translation for an open bounty, replacing their
+    /// earlier submission, and holds off cancelling the bounty for a review
+    /// period.
+    SubmitTranslation {
+        language: String,
+        description: String,
+    },
+    /// Publishes a submission as the review's `language` translation and
+    /// pays its translator the bounty. Reviewer only.
+    ApproveTranslation {
+        language: String,
+    },
+    /// Closes an open bounty, refunding it and its rent to the reviewer who
+    /// funded it, even once the review is deleted. Fails within a review
+    /// period of the latest submission.
+    CancelTranslationBounty {
+        language: String,
+    },
+    /// Closes the signer's submission for a bounty, refunding its rent.
+    WithdrawTranslationSubmission {
+        language: String,
+    },
+    /// Sets a registered movie's release date, overriding the attested one.
+    /// Zero clears it. Config authority only.
+    SetMovieReleaseDate {
+        release_date: UnixTimestamp,
+    },
+    /// Files a review keyed by a registered movie in the movie's rating
+    /// index, or refiles it under its current rating. Permissionless.
+    IndexMovieReview,
+    /// `AddMovieReview` with a `u16` rating, for scales above 255.
+    AddMovieReviewV2 {
+        title: String,
+        rating: u16,
+        description: String,
+    },
+    /// `UpdateMovieReview` with a `u16` rating, for scales above 255.
+    UpdateMovieReviewV2 {
+        title: String,
+        rating: u16,
+        description: String,
+    },
+    /// Adds `lamports` to the global lamport tip total. Only callable by the
+    /// configured tip program.
+    RecordSolTip {
+        lamports: u64,
     },
 }
 
@@ -25,18 +412,496 @@ struct MovieReviewPayload {
 }
 
 #[derive(BorshDeserialize)]
-struct CommentPayload {
+struct MovieReviewV2Payload {
+    title: String,
+    rating: u16,
+    description: String,
+}
+
+#[derive(BorshDeserialize)]
+struct RegisterMoviePayload {
+    title: String,
+}
+
+#[derive(BorshDeserialize)]
+struct MovieReviewForMoviePayload {
+    rating: u16,
+    description: String,
+}
+
+#[derive(BorshDeserialize)]
+struct AnonymousReviewPayload {
+    commitment: Pubkey,
+    title: String,
+    rating: u16,
+    description: String,
+}
+
+#[derive(BorshDeserialize)]
+struct RevealAuthorshipPayload {
+    salt: [u8; 32],
+}
+
+#[derive(BorshDeserialize)]
+struct RotateReviewCollectionPayload {
+    name: String,
+    symbol: String,
+    uri: String,
+}
+
+#[derive(BorshDeserialize)]
+struct MintReviewNftPayload {
+    uri: String,
+}
+
+#[derive(BorshDeserialize)]
+struct SetReleaseOraclePayload {
+    oracle: Pubkey,
+    feed_id: [u8; 32],
+}
+
+#[derive(BorshDeserialize)]
+struct SetVrfProgramPayload {
+    vrf_program: Pubkey,
+}
+
+#[derive(BorshDeserialize)]
+struct SetForeignEmitterPayload {
+    wormhole_program: Pubkey,
+    emitter_chain: u16,
+    emitter_address: [u8; 32],
+}
+
+#[derive(BorshDeserialize)]
+struct SetMovieExternalIdPayload {
+    external_id: String,
+}
+
+#[derive(BorshDeserialize)]
+struct TranslationPayload {
+    language: String,
+    description: String,
+}
+
+#[derive(BorshDeserialize)]
+struct DeleteTranslationPayload {
+    language: String,
+}
+
+#[derive(BorshDeserialize)]
+struct SetReviewBodyFormatPayload {
+    body_format: BodyFormat,
+}
+
+#[derive(BorshDeserialize)]
+struct FollowReviewerPayload {
+    reviewer: Pubkey,
+}
+
+#[derive(BorshDeserialize)]
+struct ReplyPayload {
     comment: String,
+    media_uri: Option<String>,
+}
+
+#[derive(BorshDeserialize)]
+struct PagedCommentPayload {
+    comment: String,
+}
+
+#[derive(BorshDeserialize)]
+struct WriteSnapshotPayload {
+    slot: u64,
+}
+
+#[derive(BorshDeserialize)]
+struct RecordTipPayload {
+    amount: u64,
+}
+
+#[derive(BorshDeserialize)]
+struct RecordSolTipPayload {
+    lamports: u64,
+}
+
+#[derive(BorshDeserialize)]
+struct SetTipProgramPayload {
+    tip_program: Pubkey,
+}
+
+#[derive(BorshDeserialize)]
+struct SetFeaturesPayload {
+    features: u64,
+}
+
+#[derive(BorshDeserialize)]
+struct SetRemovalWindowPayload {
+    slots: u64,
+}
+
+#[derive(BorshDeserialize)]
+struct ResolveAppealPayload {
+    uphold: bool,
+}
+
+#[derive(BorshDeserialize)]
+struct FinalizeRemovalPayload {
+    cascade: bool,
+}
+
+#[derive(BorshDeserialize)]
+struct SetAgeGatePayload {
+    min_wallet_age: u64,
+    age_attester: Pubkey,
+}
+
+#[derive(BorshDeserialize)]
+struct VoteOnCommentPayload {
+    kind: CommentVoteKind,
+}
+
+#[derive(BorshDeserialize)]
+struct SetHideThresholdPayload {
+    threshold: i64,
+}
+
+#[derive(BorshDeserialize)]
+struct SetEditBurnPayload {
+    mint: Pubkey,
+    amount: u64,
+}
+
+#[derive(BorshDeserialize)]
+struct SetCoAuthorsPayload {
+    co_authors: Vec<(Pubkey, u16)>,
+}
+
+#[derive(BorshDeserialize)]
+struct AcceptCoAuthorshipPayload {
+    share_bps: u16,
+}
+
+#[derive(BorshDeserialize)]
+struct SetCriticAuthorityPayload {
+    authority: Pubkey,
+    authority_program: Pubkey,
+}
+
+#[derive(BorshDeserialize)]
+struct SetMovieReleaseDatePayload {
+    release_date: UnixTimestamp,
+}
+
+#[derive(BorshDeserialize)]
+struct FundTranslationBountyPayload {
+    language: String,
+    amount: u64,
+}
+
+#[derive(BorshDeserialize)]
+struct UpdateBodyHashPayload {
+    hash: [u8; 32],
+    uri: String,
+}
+
+#[derive(BorshDeserialize)]
+struct UpdateRatingPayload {
+    rating: u16,
+}
+
+#[derive(BorshDeserialize)]
+struct AppendToDescriptionPayload {
+    text: String,
+}
+
+#[derive(BorshDeserialize)]
+struct SetContentLimitsPayload {
+    limits: ContentLimits,
+}
+
+#[derive(BorshDeserialize)]
+struct SetRatingScalePayload {
+    scale: RatingScale,
+}
+
+#[derive(BorshDeserialize)]
+struct DeleteReviewPayload {
+    cascade: bool,
+}
+
+#[derive(BorshDeserialize)]
+struct GetRequiredAccountsPayload {
+    instruction_tag: u8,
+}
+
+#[derive(BorshDeserialize)]
+struct SetCpiCallersPayload {
+    callers: Vec<Pubkey>,
+}
+
+#[derive(BorshDeserialize)]
+struct CreateSessionPayload {
+    session_key: Pubkey,
+    expires_slot: u64,
+    scopes: u8,
+}
+
+#[derive(BorshDeserialize)]
+struct RelayedReviewPayload {
+    title: String,
+    rating: u16,
+    description: String,
+    nonce: u64,
+    expires_slot: u64,
+}
+
+#[derive(BorshDeserialize)]
+struct RecomputeStatsPayload {
+    restart: bool,
+    finish: bool,
+}
+
+#[derive(BorshDeserialize)]
+struct MuteUserPayload {
+    user: Pubkey,
+}
+
+#[derive(BorshDeserialize)]
+struct SetCommentSettingsPayload {
+    comments_enabled: bool,
+    comment_policy: CommentPolicy,
+}
+
+#[derive(BorshDeserialize)]
+struct SetFeeSchedulePayload {
+    fees: FeeSchedule,
+    fee_exempt_mint: Pubkey,
+    fee_exempt_min_balance: u64,
+}
+
+#[derive(BorshDeserialize)]
+struct ConfigPayload {
+    allow_self_comments: bool,
+    require_reviewer_pass: bool,
+}
+
+/// Set on the tag byte to run an instruction as a dry run: every check runs
+/// but nothing is written, and what the payer would pay is returned as a
+/// `CostPreview`. Only `AddMovieReview`, `AddMovieReviewV2` and `AddComment`
+/// support it.
+pub const DRY_RUN_FLAG: u8 = 0x80;
+
+/// Prefix of the names hashed into `INSTRUCTION_DISCRIMINATORS`.
+pub const DISCRIMINATOR_NAMESPACE: &str = "movie_review";
+
+/// Name and 8-byte discriminator of each instruction, indexed by its tag.
+/// Following the SPL interface convention, the discriminator is the start of
+/// the SHA-256 hash of `"{DISCRIMINATOR_NAMESPACE}:{name}"`, and may lead the
+/// instruction data in place of the tag.
+pub const INSTRUCTION_DISCRIMINATORS: [(&str, [u8; 8]); 80] = [
+    ("add_movie_review", [69, 173, 167, 17, 106, 207, 146, 113]),
+    ("update_movie_review", [94, 210, 125, 185, 63, 146, 200, 27]),
+    ("add_comment", [49, 89, 36, 187, 168, 204, 187, 183]),
+    ("migrate_account", [108, 111, 145, 165, 78, 101, 107, 146]),
+    ("initialize_config", [184, 235, 49, 70, 105, 21, 149, 90]),
+    ("update_config", [103, 76, 25, 75, 156, 14, 179, 160]),
+    ("register_movie", [44, 239, 93, 58, 175, 119, 236, 199]),
+    (
+        "add_review_for_movie",
+        [222, 137, 255, 124, 77, 236, 27, 217],
+    ),
+    ("link_legacy_review", [28, 8, 236, 9, 158, 109, 85, 35]),
+    (
+        "add_anonymous_review",
+        [222, 31, 168, 197, 110, 89, 101, 173],
+    ),
+    ("reveal_authorship", [204, 46, 50, 64, 137, 23, 174, 134]),
+    ("initialize_pass_mint", [161, 119, 140, 208, 237, 16, 6, 32]),
+    ("issue_reviewer_pass", [96, 242, 82, 250, 82, 199, 24, 64]),
+    (
+        "rotate_review_collection",
+        [57, 149, 28, 108, 44, 83, 42, 130],
+    ),
+    ("mint_review_nft", [223, 59, 58, 73, 111, 156, 89, 213]),
+    (
+        "set_release_oracle",
+        [134, 176, 109, 176, 142, 140, 249, 46],
+    ),
+    (
+        "mark_verified_release",
+        [28, 77, 161, 36, 154, 54, 129, 242],
+    ),
+    (
+        "request_featured_draw",
+        [207, 109, 81, 88, 192, 211, 96, 21],
+    ),
+    (
+        "fulfill_featured_draw",
+        [137, 187, 40, 29, 22, 36, 137, 131],
+    ),
+    ("set_vrf_program", [90, 84, 234, 87, 41, 165, 109, 242]),
+    ("set_foreign_emitter", [86, 182, 59, 220, 25, 120, 36, 191]),
+    (
+        "import_foreign_review",
+        [196, 252, 54, 52, 238, 150, 18, 127],
+    ),
+    (
+        "get_upgrade_authority_status",
+        [251, 236, 171, 200, 27, 235, 40, 172],
+    ),
+    ("top_up_account", [25, 205, 27, 83, 49, 17, 9, 11]),
+    (
+        "withdraw_excess_lamports",
+        [149, 213, 148, 76, 54, 55, 32, 75],
+    ),
+    ("set_fee_schedule", [2, 15, 70, 36, 59, 13, 147, 160]),
+    (
+        "set_movie_external_id",
+        [104, 228, 199, 239, 235, 70, 121, 75],
+    ),
+    (
+        "add_review_translation",
+        [146, 211, 180, 186, 210, 19, 200, 155],
+    ),
+    (
+        "update_review_translation",
+        [73, 183, 87, 127, 206, 150, 96, 133],
+    ),
+    (
+        "delete_review_translation",
+        [13, 186, 238, 0, 173, 204, 24, 170],
+    ),
+    (
+        "set_review_body_format",
+        [105, 222, 147, 195, 144, 78, 86, 104],
+    ),
+    ("follow_reviewer", [51, 201, 37, 17, 235, 134, 124, 188]),
+    ("unfollow_reviewer", [132, 79, 38, 239, 140, 243, 1, 61]),
+    (
+        "set_comment_settings",
+        [160, 103, 118, 92, 90, 37, 211, 100],
+    ),
+    ("mute_user", [201, 191, 102, 147, 142, 140, 62, 11]),
+    ("unmute_user", [244, 207, 196, 130, 44, 187, 20, 198]),
+    ("reply_to_comment", [208, 102, 131, 95, 252, 74, 39, 4]),
+    ("add_paged_comment", [76, 249, 222, 231, 78, 42, 102, 173]),
+    ("write_snapshot", [134, 242, 34, 211, 9, 118, 212, 234]),
+    ("record_tip", [203, 79, 190, 157, 124, 48, 62, 204]),
+    ("set_tip_program", [228, 3, 254, 167, 44, 215, 97, 204]),
+    ("recompute_stats", [148, 207, 124, 57, 8, 76, 12, 169]),
+    ("update_rating", [82, 150, 218, 235, 228, 1, 2, 175]),
+    (
+        "append_to_description",
+        [112, 34, 133, 105, 100, 192, 7, 41],
+    ),
+    ("set_content_limits", [0, 244, 169, 96, 41, 143, 142, 175]),
+    ("set_rating_scale", [11, 243, 111, 94, 97, 211, 106, 44]),
+    ("delete_review", [225, 55, 133, 196, 46, 163, 73, 39]),
+    (
+        "repair_comment_address",
+        [45, 69, 200, 220, 131, 69, 146, 98],
+    ),
+    (
+        "add_movie_review_idempotent",
+        [54, 73, 193, 199, 60, 57, 204, 183],
+    ),
+    (
+        "get_required_accounts",
+        [146, 127, 99, 245, 207, 136, 54, 188],
+    ),
+    ("set_cpi_callers", [175, 193, 117, 44, 150, 35, 214, 4]),
+    ("create_session", [61, 3, 56, 36, 199, 64, 204, 103]),
+    ("revoke_session", [206, 22, 87, 173, 0, 122, 91, 155]),
+    ("add_relayed_review", [79, 251, 40, 229, 234, 73, 137, 111]),
+    ("set_features", [204, 133, 84, 241, 181, 63, 127, 29]),
+    ("set_removal_window", [95, 43, 56, 204, 255, 70, 178, 46]),
+    ("propose_removal", [167, 200, 253, 167, 27, 45, 160, 16]),
+    ("appeal_removal", [128, 186, 32, 192, 131, 111, 70, 83]),
+    ("resolve_appeal", [58, 5, 89, 41, 253, 1, 124, 135]),
+    ("finalize_removal", [9, 66, 217, 18, 221, 17, 123, 224]),
+    ("update_body_hash", [234, 104, 83, 27, 242, 181, 243, 132]),
+    ("record_fingerprint", [208, 98, 221, 161, 115, 219, 25, 97]),
+    ("challenge_duplicate", [77, 1, 50, 30, 137, 176, 185, 212]),
+    ("set_age_gate", [243, 161, 140, 244, 46, 232, 238, 207]),
+    ("vote_on_comment", [6, 83, 224, 92, 139, 196, 121, 115]),
+    ("set_hide_threshold", [79, 71, 151, 40, 194, 127, 74, 150]),
+    ("set_edit_burn", [229, 240, 75, 123, 18, 250, 79, 249]),
+    ("set_co_authors", [165, 250, 12, 144, 152, 59, 237, 137]),
+    ("accept_co_authorship", [109, 14, 161, 62, 18, 97, 24, 237]),
+    ("set_critic_authority", [16, 79, 223, 213, 14, 167, 139, 97]),
+    (
+        "fund_translation_bounty",
+        [31, 90, 99, 117, 15, 226, 164, 100],
+    ),
+    ("submit_translation", [44, 113, 89, 163, 59, 207, 206, 179]),
+    ("approve_translation", [192, 160, 233, 36, 49, 162, 81, 128]),
+    (
+        "cancel_translation_bounty",
+        [13, 25, 73, 143, 51, 187, 24, 91],
+    ),
+    (
+        "withdraw_translation_submission",
+        [110, 87, 167, 179, 104, 254, 108, 215],
+    ),
+    (
+        "set_movie_release_date",
+        [126, 242, 26, 117, 37, 231, 91, 178],
+    ),
+    ("index_movie_review", [239, 10, 107, 141, 156, 105, 82, 153]),
+    ("add_movie_review_v2", [161, 73, 253, 47, 201, 158, 45, 45]),
+    (
+        "update_movie_review_v2",
+        [98, 77, 243, 229, 11, 205, 59, 188],
+    ),
+    ("record_sol_tip", [36, 192, 153, 54, 95, 229, 225, 53]),
+];
+
+/// The tag of the instruction whose discriminator leads `input`, and the
+/// data after it.
+fn split_discriminator(input: &[u8]) -> Option<(u8, &[u8])> {
+    let discriminator = input.get(..8)?;
+    let tag = INSTRUCTION_DISCRIMINATORS
+        .iter()
+        .position(|(_, known)| known == discriminator)?;
+    Some((tag as u8, &input[8..]))
 }
 
 impl MovieInstruction {
+    /// Unpacks `input`, also returning whether its tag has `DRY_RUN_FLAG`.
+    pub fn unpack_with_dry_run(input: &[u8]) -> Result<(Self, bool), ProgramError> {
+        // Only legacy tags carry the flag
+        if split_discriminator(input).is_some() {
+            return Ok((Self::unpack(input)?, false));
+        }
+        let (&variant, rest) = input
+            .split_first()
+            .ok_or(ProgramError::InvalidInstructionData)?;
+        if variant & DRY_RUN_FLAG == 0 {
+            return Ok((Self::unpack(input)?, false));
+        }
+        let mut stripped = Vec::with_capacity(input.len());
+        stripped.push(variant & !DRY_RUN_FLAG);
+        stripped.extend_from_slice(rest);
+        Ok((Self::unpack(&stripped)?, true))
+    }
+
+    /// Unpacks `input`, led by either the instruction's tag or its
+    /// discriminator.
     pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
+        if let Some((tag, rest)) = split_discriminator(input) {
+            return Self::unpack_tagged(tag, rest);
+        }
         let (&variant, rest) = input
             .split_first()
             .ok_or(ProgramError::InvalidInstructionData)?;
+        Self::unpack_tagged(variant, rest)
+    }
+
+    fn unpack_tagged(variant: u8, rest: &[u8]) -> Result<Self, ProgramError> {
         Ok(match variant {
             0 => {
-                let payload = MovieReviewPayload::try_from_slice(rest).unwrap();
+                let payload = MovieReviewPayload::try_from_slice(rest)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
                 Self::AddMovieReview {
                     title: payload.title,
                     rating: payload.rating,
@@ -44,7 +909,8 @@ impl MovieInstruction {
                 }
             }
             1 => {
-                let payload = MovieReviewPayload::try_from_slice(rest).unwrap();
+                let payload = MovieReviewPayload::try_from_slice(rest)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
                 Self::UpdateMovieReview {
                     title: payload.title,
                     rating: payload.rating,
@@ -52,12 +918,812 @@ impl MovieInstruction {
                 }
             }
             2 => {
-                let payload = CommentPayload::try_from_slice(rest).unwrap();
-                Self::AddComment {
+                let mut payload = rest;
+                let comment = String::deserialize(&mut payload)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                // Clients predating attachments send the comment alone
+                let media_uri = if payload.is_empty() {
+                    None
+                } else {
+                    Option::<String>::try_from_slice(payload)
+                        .map_err(|_| ProgramError::InvalidInstructionData)?
+                };
+                Self::AddComment { comment, media_uri }
+            }
+            3 => Self::MigrateAccount,
+            4 => {
+                let payload = ConfigPayload::try_from_slice(rest)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                Self::InitializeConfig {
+                    allow_self_comments: payload.allow_self_comments,
+                    require_reviewer_pass: payload.require_reviewer_pass,
+                }
+            }
+            5 => {
+                let payload = ConfigPayload::try_from_slice(rest)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                Self::UpdateConfig {
+                    allow_self_comments: payload.allow_self_comments,
+                    require_reviewer_pass: payload.require_reviewer_pass,
+                }
+            }
+            6 => {
+                let payload = RegisterMoviePayload::try_from_slice(rest)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                Self::RegisterMovie {
+                    title: payload.title,
+                }
+            }
+            7 => {
+                let payload = MovieReviewForMoviePayload::try_from_slice(rest)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                Self::AddReviewForMovie {
+                    rating: payload.rating,
+                    description: payload.description,
+                }
+            }
+            8 => Self::LinkLegacyReview,
+            9 => {
+                let payload = AnonymousReviewPayload::try_from_slice(rest)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                Self::AddAnonymousReview {
+                    commitment: payload.commitment,
+                    title: payload.title,
+                    rating: payload.rating,
+                    description: payload.description,
+                }
+            }
+            10 => {
+                let payload = RevealAuthorshipPayload::try_from_slice(rest)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                Self::RevealAuthorship { salt: payload.salt }
+            }
+            11 => Self::InitializePassMint,
+            12 => Self::IssueReviewerPass,
+            13 => {
+                let payload = RotateReviewCollectionPayload::try_from_slice(rest)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                Self::RotateReviewCollection {
+                    name: payload.name,
+                    symbol: payload.symbol,
+                    uri: payload.uri,
+                }
+            }
+            14 => {
+                let payload = MintReviewNftPayload::try_from_slice(rest)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                Self::MintReviewNft { uri: payload.uri }
+            }
+            15 => {
+                let payload = SetReleaseOraclePayload::try_from_slice(rest)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                Self::SetReleaseOracle {
+                    oracle: payload.oracle,
+                    feed_id: payload.feed_id,
+                }
+            }
+            16 => Self::MarkVerifiedRelease,
+            17 => Self::RequestFeaturedDraw,
+            18 => Self::FulfillFeaturedDraw,
+            19 => {
+                let payload = SetVrfProgramPayload::try_from_slice(rest)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                Self::SetVrfProgram {
+                    vrf_program: payload.vrf_program,
+                }
+            }
+            20 => {
+                let payload = SetForeignEmitterPayload::try_from_slice(rest)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                Self::SetForeignEmitter {
+                    wormhole_program: payload.wormhole_program,
+                    emitter_chain: payload.emitter_chain,
+                    emitter_address: payload.emitter_address,
+                }
+            }
+            21 => Self::ImportForeignReview,
+            22 => Self::GetUpgradeAuthorityStatus,
+            23 => Self::TopUpAccount,
+            24 => Self::WithdrawExcessLamports,
+            25 => {
+                let payload = SetFeeSchedulePayload::try_from_slice(rest)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                Self::SetFeeSchedule {
+                    fees: payload.fees,
+                    fee_exempt_mint: payload.fee_exempt_mint,
+                    fee_exempt_min_balance: payload.fee_exempt_min_balance,
+                }
+            }
+            26 => {
+                let payload = SetMovieExternalIdPayload::try_from_slice(rest)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                Self::SetMovieExternalId {
+                    external_id: payload.external_id,
+                }
+            }
+            27 => {
+                let payload = TranslationPayload::try_from_slice(rest)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                Self::AddReviewTranslation {
+                    language: payload.language,
+                    description: payload.description,
+                }
+            }
+            28 => {
+                let payload = TranslationPayload::try_from_slice(rest)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                Self::UpdateReviewTranslation {
+                    language: payload.language,
+                    description: payload.description,
+                }
+            }
+            29 => {
+                let payload = DeleteTranslationPayload::try_from_slice(rest)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                Self::DeleteReviewTranslation {
+                    language: payload.language,
+                }
+            }
+            30 => {
+                let payload = SetReviewBodyFormatPayload::try_from_slice(rest)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                Self::SetReviewBodyFormat {
+                    body_format: payload.body_format,
+                }
+            }
+            31 => {
+                let payload = FollowReviewerPayload::try_from_slice(rest)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                Self::FollowReviewer {
+                    reviewer: payload.reviewer,
+                }
+            }
+            32 => Self::UnfollowReviewer,
+            33 => {
+                let payload = SetCommentSettingsPayload::try_from_slice(rest)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                Self::SetCommentSettings {
+                    comments_enabled: payload.comments_enabled,
+                    comment_policy: payload.comment_policy,
+                }
+            }
+            34 => {
+                let payload = MuteUserPayload::try_from_slice(rest)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                Self::MuteUser { user: payload.user }
+            }
+            35 => Self::UnmuteUser,
+            36 => {
+                let payload = ReplyPayload::try_from_slice(rest)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                Self::ReplyToComment {
+                    comment: payload.comment,
+                    media_uri: payload.media_uri,
+                }
+            }
+            37 => {
+                let payload = PagedCommentPayload::try_from_slice(rest)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                Self::AddPagedComment {
                     comment: payload.comment,
                 }
             }
+            38 => {
+                let payload = WriteSnapshotPayload::try_from_slice(rest)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                Self::WriteSnapshot { slot: payload.slot }
+            }
+            39 => {
+                let payload = RecordTipPayload::try_from_slice(rest)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                Self::RecordTip {
+                    amount: payload.amount,
+                }
+            }
+            40 => {
+                let payload = SetTipProgramPayload::try_from_slice(rest)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                Self::SetTipProgram {
+                    tip_program: payload.tip_program,
+                }
+            }
+            41 => {
+                let payload = RecomputeStatsPayload::try_from_slice(rest)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                Self::RecomputeStats {
+                    restart: payload.restart,
+                    finish: payload.finish,
+                }
+            }
+            42 => {
+                let payload = UpdateRatingPayload::try_from_slice(rest)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                Self::UpdateRating {
+                    rating: payload.rating,
+                }
+            }
+            43 => {
+                let payload = AppendToDescriptionPayload::try_from_slice(rest)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                Self::AppendToDescription { text: payload.text }
+            }
+            44 => {
+                let payload = SetContentLimitsPayload::try_from_slice(rest)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                Self::SetContentLimits {
+                    limits: payload.limits,
+                }
+            }
+            45 => {
+                let payload = SetRatingScalePayload::try_from_slice(rest)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                Self::SetRatingScale {
+                    scale: payload.scale,
+                }
+            }
+            46 => {
+                let payload = DeleteReviewPayload::try_from_slice(rest)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                Self::DeleteReview {
+                    cascade: payload.cascade,
+                }
+            }
+            47 => Self::RepairCommentAddress,
+            48 => {
+                let payload = MovieReviewV2Payload::try_from_slice(rest)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                Self::AddMovieReviewIdempotent {
+                    title: payload.title,
+                    rating: payload.rating,
+                    description: payload.description,
+                }
+            }
+            49 => {
+                let payload = GetRequiredAccountsPayload::try_from_slice(rest)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                Self::GetRequiredAccounts {
+                    instruction_tag: payload.instruction_tag,
+                }
+            }
+            50 => {
+                let payload = SetCpiCallersPayload::try_from_slice(rest)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                Self::SetCpiCallers {
+                    callers: payload.callers,
+                }
+            }
+            51 => {
+                let payload = CreateSessionPayload::try_from_slice(rest)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                Self::CreateSession {
+                    session_key: payload.session_key,
+                    expires_slot: payload.expires_slot,
+                    scopes: payload.scopes,
+                }
+            }
+            52 => Self::RevokeSession,
+            53 => {
+                let payload = RelayedReviewPayload::try_from_slice(rest)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                Self::AddRelayedReview {
+                    title: payload.title,
+                    rating: payload.rating,
+                    description: payload.description,
+                    nonce: payload.nonce,
+                    expires_slot: payload.expires_slot,
+                }
+            }
+            54 => {
+                let payload = SetFeaturesPayload::try_from_slice(rest)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                Self::SetFeatures {
+                    features: payload.features,
+                }
+            }
+            55 => {
+                let payload = SetRemovalWindowPayload::try_from_slice(rest)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                Self::SetRemovalWindow {
+                    slots: payload.slots,
+                }
+            }
+            56 => Self::ProposeRemoval,
+            57 => Self::AppealRemoval,
+            58 => {
+                let payload = ResolveAppealPayload::try_from_slice(rest)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                Self::ResolveAppeal {
+                    uphold: payload.uphold,
+                }
+            }
+            59 => {
+                let payload = FinalizeRemovalPayload::try_from_slice(rest)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                Self::FinalizeRemoval {
+                    cascade: payload.cascade,
+                }
+            }
+            60 => {
+                let payload = UpdateBodyHashPayload::try_from_slice(rest)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                Self::UpdateBodyHash {
+                    hash: payload.hash,
+                    uri: payload.uri,
+                }
+            }
+            61 => Self::RecordFingerprint,
+            62 => Self::ChallengeDuplicate,
+            63 => {
+                let payload = SetAgeGatePayload::try_from_slice(rest)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                Self::SetAgeGate {
+                    min_wallet_age: payload.min_wallet_age,
+                    age_attester: payload.age_attester,
+                }
+            }
+            64 => {
+                let payload = VoteOnCommentPayload::try_from_slice(rest)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                Self::VoteOnComment { kind: payload.kind }
+            }
+            65 => {
+                let payload = SetHideThresholdPayload::try_from_slice(rest)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                Self::SetHideThreshold {
+                    threshold: payload.threshold,
+                }
+            }
+            66 => {
+                let payload = SetEditBurnPayload::try_from_slice(rest)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                Self::SetEditBurn {
+                    mint: payload.mint,
+                    amount: payload.amount,
+                }
+            }
+            67 => {
+                let payload = SetCoAuthorsPayload::try_from_slice(rest)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                Self::SetCoAuthors {
+                    co_authors: payload.co_authors,
+                }
+            }
+            68 => {
+                let payload = AcceptCoAuthorshipPayload::try_from_slice(rest)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                Self::AcceptCoAuthorship {
+                    share_bps: payload.share_bps,
+                }
+            }
+            69 => {
+                let payload = SetCriticAuthorityPayload::try_from_slice(rest)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                Self::SetCriticAuthority {
+                    authority: payload.authority,
+                    authority_program: payload.authority_program,
+                }
+            }
+            70 => {
+                let payload = FundTranslationBountyPayload::try_from_slice(rest)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                Self::FundTranslationBounty {
+                    language: payload.language,
+                    amount: payload.amount,
+                }
+            }
+            71 => {
+                let payload = TranslationPayload::try_from_slice(rest)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                Self::SubmitTranslation {
+                    language: payload.language,
+                    description: payload.description,
+                }
+            }
+            72 => {
+                let payload = DeleteTranslationPayload::try_from_slice(rest)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                Self::ApproveTranslation {
+                    language: payload.language,
+                }
+            }
+            73 => {
+                let payload = DeleteTranslationPayload::try_from_slice(rest)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                Self::CancelTranslationBounty {
+                    language: payload.language,
+                }
+            }
+            74 => {
+                let payload = DeleteTranslationPayload::try_from_slice(rest)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                Self::WithdrawTranslationSubmission {
+                    language: payload.language,
+                }
+            }
+            75 => {
+                let payload = SetMovieReleaseDatePayload::try_from_slice(rest)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                Self::SetMovieReleaseDate {
+                    release_date: payload.release_date,
+                }
+            }
+            76 => Self::IndexMovieReview,
+            77 => {
+                let payload = MovieReviewV2Payload::try_from_slice(rest)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                Self::AddMovieReviewV2 {
+                    title: payload.title,
+                    rating: payload.rating,
+                    description: payload.description,
+                }
+            }
+            78 => {
+                let payload = MovieReviewV2Payload::try_from_slice(rest)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                Self::UpdateMovieReviewV2 {
+                    title: payload.title,
+                    rating: payload.rating,
+                    description: payload.description,
+                }
+            }
+            79 => {
+                let payload = RecordSolTipPayload::try_from_slice(rest)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                Self::RecordSolTip {
+                    lamports: payload.lamports,
+                }
+            }
             _ => return Err(ProgramError::InvalidInstructionData),
         })
     }
+    /// `pack`, led by the instruction's discriminator instead of its tag.
+    pub fn pack_with_discriminator(&self) -> Vec<u8> {
+        let tagged = self.pack();
+        let mut buf = INSTRUCTION_DISCRIMINATORS[tagged[0] as usize].1.to_vec();
+        buf.extend_from_slice(&tagged[1..]);
+        buf
+    }
+
+    pub fn pack(&self) -> Vec<u8> {
+        let mut buf = Vec::new();
+        match self {
+            Self::AddMovieReview {
+                title,
+                rating,
+                description,
+            } => {
+                buf.push(0);
+                borsh::to_writer(&mut buf, &(title, rating, description)).unwrap();
+            }
+            Self::UpdateMovieReview {
+                title,
+                rating,
+                description,
+            } => {
+                buf.push(1);
+                borsh::to_writer(&mut buf, &(title, rating, description)).unwrap();
+            }
+            Self::AddComment { comment, media_uri } => {
+                buf.push(2);
+                borsh::to_writer(&mut buf, &(comment, media_uri)).unwrap();
+            }
+            Self::MigrateAccount => buf.push(3),
+            Self::InitializeConfig {
+                allow_self_comments,
+                require_reviewer_pass,
+            } => {
+                buf.push(4);
+                borsh::to_writer(&mut buf, &(allow_self_comments, require_reviewer_pass)).unwrap();
+            }
+            Self::UpdateConfig {
+                allow_self_comments,
+                require_reviewer_pass,
+            } => {
+                buf.push(5);
+                borsh::to_writer(&mut buf, &(allow_self_comments, require_reviewer_pass)).unwrap();
+            }
+            Self::RegisterMovie { title } => {
+                buf.push(6);
+                borsh::to_writer(&mut buf, title).unwrap();
+            }
+            Self::AddReviewForMovie {
+                rating,
+                description,
+            } => {
+                buf.push(7);
+                borsh::to_writer(&mut buf, &(rating, description)).unwrap();
+            }
+            Self::LinkLegacyReview => buf.push(8),
+            Self::AddAnonymousReview {
+                commitment,
+                title,
+                rating,
+                description,
+            } => {
+                buf.push(9);
+                borsh::to_writer(&mut buf, &(commitment, title, rating, description)).unwrap();
+            }
+            Self::RevealAuthorship { salt } => {
+                buf.push(10);
+                borsh::to_writer(&mut buf, salt).unwrap();
+            }
+            Self::InitializePassMint => buf.push(11),
+            Self::IssueReviewerPass => buf.push(12),
+            Self::RotateReviewCollection { name, symbol, uri } => {
+                buf.push(13);
+                borsh::to_writer(&mut buf, &(name, symbol, uri)).unwrap();
+            }
+            Self::MintReviewNft { uri } => {
+                buf.push(14);
+                borsh::to_writer(&mut buf, uri).unwrap();
+            }
+            Self::SetReleaseOracle { oracle, feed_id } => {
+                buf.push(15);
+                borsh::to_writer(&mut buf, &(oracle, feed_id)).unwrap();
+            }
+            Self::MarkVerifiedRelease => buf.push(16),
+            Self::RequestFeaturedDraw => buf.push(17),
+            Self::FulfillFeaturedDraw => buf.push(18),
+            Self::SetVrfProgram { vrf_program } => {
+                buf.push(19);
+                borsh::to_writer(&mut buf, vrf_program).unwrap();
+            }
+            Self::SetForeignEmitter {
+                wormhole_program,
+                emitter_chain,
+                emitter_address,
+            } => {
+                buf.push(20);
+                borsh::to_writer(
+                    &mut buf,
+                    &(wormhole_program, emitter_chain, emitter_address),
+                )
+                .unwrap();
+            }
+            Self::ImportForeignReview => buf.push(21),
+            Self::GetUpgradeAuthorityStatus => buf.push(22),
+            Self::TopUpAccount => buf.push(23),
+            Self::WithdrawExcessLamports => buf.push(24),
+            Self::SetFeeSchedule {
+                fees,
+                fee_exempt_mint,
+                fee_exempt_min_balance,
+            } => {
+                buf.push(25);
+                borsh::to_writer(&mut buf, &(fees, fee_exempt_mint, fee_exempt_min_balance))
+                    .unwrap();
+            }
+            Self::SetMovieExternalId { external_id } => {
+                buf.push(26);
+                borsh::to_writer(&mut buf, external_id).unwrap();
+            }
+            Self::AddReviewTranslation {
+                language,
+                description,
+            } => {
+                buf.push(27);
+                borsh::to_writer(&mut buf, &(language, description)).unwrap();
+            }
+            Self::UpdateReviewTranslation {
+                language,
+                description,
+            } => {
+                buf.push(28);
+                borsh::to_writer(&mut buf, &(language, description)).unwrap();
+            }
+            Self::DeleteReviewTranslation { language } => {
+                buf.push(29);
+                borsh::to_writer(&mut buf, language).unwrap();
+            }
+            Self::SetReviewBodyFormat { body_format } => {
+                buf.push(30);
+                borsh::to_writer(&mut buf, body_format).unwrap();
+            }
+            Self::FollowReviewer { reviewer } => {
+                buf.push(31);
+                borsh::to_writer(&mut buf, reviewer).unwrap();
+            }
+            Self::UnfollowReviewer => buf.push(32),
+            Self::SetCommentSettings {
+                comments_enabled,
+                comment_policy,
+            } => {
+                buf.push(33);
+                borsh::to_writer(&mut buf, &(comments_enabled, comment_policy)).unwrap();
+            }
+            Self::MuteUser { user } => {
+                buf.push(34);
+                borsh::to_writer(&mut buf, user).unwrap();
+            }
+            Self::UnmuteUser => buf.push(35),
+            Self::ReplyToComment { comment, media_uri } => {
+                buf.push(36);
+                borsh::to_writer(&mut buf, &(comment, media_uri)).unwrap();
+            }
+            Self::AddPagedComment { comment } => {
+                buf.push(37);
+                borsh::to_writer(&mut buf, comment).unwrap();
+            }
+            Self::WriteSnapshot { slot } => {
+                buf.push(38);
+                borsh::to_writer(&mut buf, slot).unwrap();
+            }
+            Self::RecordTip { amount } => {
+                buf.push(39);
+                borsh::to_writer(&mut buf, amount).unwrap();
+            }
+            Self::SetTipProgram { tip_program } => {
+                buf.push(40);
+                borsh::to_writer(&mut buf, tip_program).unwrap();
+            }
+            Self::RecomputeStats { restart, finish } => {
+                buf.push(41);
+                borsh::to_writer(&mut buf, &(restart, finish)).unwrap();
+            }
+            Self::UpdateRating { rating } => {
+                buf.push(42);
+                borsh::to_writer(&mut buf, rating).unwrap();
+            }
+            Self::AppendToDescription { text } => {
+                buf.push(43);
+                borsh::to_writer(&mut buf, text).unwrap();
+            }
+            Self::SetContentLimits { limits } => {
+                buf.push(44);
+                borsh::to_writer(&mut buf, limits).unwrap();
+            }
+            Self::SetRatingScale { scale } => {
+                buf.push(45);
+                borsh::to_writer(&mut buf, scale).unwrap();
+            }
+            Self::DeleteReview { cascade } => {
+                buf.push(46);
+                borsh::to_writer(&mut buf, cascade).unwrap();
+            }
+            Self::RepairCommentAddress => buf.push(47),
+            Self::AddMovieReviewIdempotent {
+                title,
+                rating,
+                description,
+            } => {
+                buf.push(48);
+                borsh::to_writer(&mut buf, &(title, rating, description)).unwrap();
+            }
+            Self::GetRequiredAccounts { instruction_tag } => {
+                buf.push(49);
+                borsh::to_writer(&mut buf, instruction_tag).unwrap();
+            }
+            Self::SetCpiCallers { callers } => {
+                buf.push(50);
+                borsh::to_writer(&mut buf, callers).unwrap();
+            }
+            Self::CreateSession {
+                session_key,
+                expires_slot,
+                scopes,
+            } => {
+                buf.push(51);
+                borsh::to_writer(&mut buf, &(session_key, expires_slot, scopes)).unwrap();
+            }
+            Self::RevokeSession => buf.push(52),
+            Self::AddRelayedReview {
+                title,
+                rating,
+                description,
+                nonce,
+                expires_slot,
+            } => {
+                buf.push(53);
+                borsh::to_writer(&mut buf, &(title, rating, description, nonce, expires_slot))
+                    .unwrap();
+            }
+            Self::SetFeatures { features } => {
+                buf.push(54);
+                borsh::to_writer(&mut buf, features).unwrap();
+            }
+            Self::SetRemovalWindow { slots } => {
+                buf.push(55);
+                borsh::to_writer(&mut buf, slots).unwrap();
+            }
+            Self::ProposeRemoval => buf.push(56),
+            Self::AppealRemoval => buf.push(57),
+            Self::ResolveAppeal { uphold } => {
+                buf.push(58);
+                borsh::to_writer(&mut buf, uphold).unwrap();
+            }
+            Self::FinalizeRemoval { cascade } => {
+                buf.push(59);
+                borsh::to_writer(&mut buf, cascade).unwrap();
+            }
+            Self::UpdateBodyHash { hash, uri } => {
+                buf.push(60);
+                borsh::to_writer(&mut buf, &(hash, uri)).unwrap();
+            }
+            Self::RecordFingerprint => buf.push(61),
+            Self::ChallengeDuplicate => buf.push(62),
+            Self::SetAgeGate {
+                min_wallet_age,
+                age_attester,
+            } => {
+                buf.push(63);
+                borsh::to_writer(&mut buf, &(min_wallet_age, age_attester)).unwrap();
+            }
+            Self::VoteOnComment { kind } => {
+                buf.push(64);
+                borsh::to_writer(&mut buf, kind).unwrap();
+            }
+            Self::SetHideThreshold { threshold } => {
+                buf.push(65);
+                borsh::to_writer(&mut buf, threshold).unwrap();
+            }
+            Self::SetEditBurn { mint, amount } => {
+                buf.push(66);
+                borsh::to_writer(&mut buf, &(mint, amount)).unwrap();
+            }
+            Self::SetCoAuthors { co_authors } => {
+                buf.push(67);
+                borsh::to_writer(&mut buf, co_authors).unwrap();
+            }
+            Self::AcceptCoAuthorship { share_bps } => {
+                buf.push(68);
+                borsh::to_writer(&mut buf, share_bps).unwrap();
+            }
+            Self::SetCriticAuthority {
+                authority,
+                authority_program,
+            } => {
+                buf.push(69);
+                borsh::to_writer(&mut buf, &(authority, authority_program)).unwrap();
+            }
+            Self::FundTranslationBounty { language, amount } => {
+                buf.push(70);
+                borsh::to_writer(&mut buf, &(language, amount)).unwrap();
+            }
+            Self::SubmitTranslation {
+                language,
+                description,
+            } => {
+                buf.push(71);
+                borsh::to_writer(&mut buf, &(language, description)).unwrap();
+            }
+            Self::ApproveTranslation { language } => {
+                buf.push(72);
+                borsh::to_writer(&mut buf, language).unwrap();
+            }
+            Self::CancelTranslationBounty { language } => {
+                buf.push(73);
+                borsh::to_writer(&mut buf, language).unwrap();
+            }
+            Self::WithdrawTranslationSubmission { language } => {
+                buf.push(74);
+                borsh::to_writer(&mut buf, language).unwrap();
+            }
+            Self::SetMovieReleaseDate { release_date } => {
+                buf.push(75);
+                borsh::to_writer(&mut buf, release_date).unwrap();
+            }
+            Self::IndexMovieReview => buf.push(76),
+            Self::AddMovieReviewV2 {
+                title,
+                rating,
+                description,
+            } => {
+                buf.push(77);
+                borsh::to_writer(&mut buf, &(title, rating, description)).unwrap();
+            }
+            Self::UpdateMovieReviewV2 {
+                title,
+                rating,
+                description,
+            } => {
+                buf.push(78);
+                borsh::to_writer(&mut buf, &(title, rating, description)).unwrap();
+            }
+            Self::RecordSolTip { lamports } => {
+                buf.push(79);
+                borsh::to_writer(&mut buf, lamports).unwrap();
+            }
+        }
+        buf
+    }
 }
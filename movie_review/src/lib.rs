@@ -1,5 +1,19 @@
+pub mod abi;
+pub mod account_spec;
+pub mod client;
+pub mod decoder;
+#[cfg(all(feature = "program", not(feature = "no-entrypoint")))]
 pub mod entrypoint;
 pub mod error;
+pub mod event;
+pub mod fingerprint;
+pub mod heap;
 pub mod instruction;
+pub mod migration;
+#[cfg(feature = "program")]
 pub mod processor;
+pub mod programs;
+pub mod rich_text;
+#[cfg(feature = "serde")]
+pub mod serde_pubkey;
 pub mod state;
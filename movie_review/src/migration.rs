@@ -0,0 +1,389 @@
+//! Upgrades accounts written with earlier layouts to the current one.
+//!
+//! v1 stored discriminators as Borsh `String`s. A v1 account starts with the
+//! little-endian length of its discriminator (6 for `"review"`, 7 for
+//! `"counter"` and `"comment"`), which never collides with a current tag, so
+//! the first byte tells the layouts apart.
+//!
+//! v2 reviews already used one-byte tags but stored the rating as a `u8`.
+//! Reviews were retagged when the rating was widened to a `u16`, so the v2
+//! tags also identify their layout.
+//!
+//! Upgrading rewrites the account in place; its allocation is left unchanged.
+//! A v2 review gains one byte, which fits unless the review was grown to
+//! exactly its content with the largest `comment_settings`. Upgrading such a
+//! review fails until it is grown by `upgrade_shortfall` bytes.
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::AccountInfo, borsh1::try_from_slice_unchecked, program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+use crate::decoder::MovieReviewAccount;
+use crate::state::{
+    BodyFormat, CommentPath, CommentSettings, ForeignReviewOrigin, MovieAccountHeader,
+    MovieAccountState, MovieComment, MovieCommentCounter, ReviewSnapshot, SnapshotEntry,
+};
+
+pub const REVIEW_DISCRIMINATOR_V1: &str = "review";
+pub const COUNTER_DISCRIMINATOR_V1: &str = "counter";
+pub const COMMENT_DISCRIMINATOR_V1: &str = "comment";
+pub const REVIEW_DISCRIMINATOR_V2: u8 = 1;
+pub const ANONYMOUS_DISCRIMINATOR_V2: u8 = 9;
+pub const FOREIGN_DISCRIMINATOR_V2: u8 = 11;
+
+#[derive(BorshDeserialize)]
+struct MovieAccountStateV1 {
+    _discriminator: String,
+    is_initialized: bool,
+    reviewer: Pubkey,
+    rating: u8,
+    title: String,
+    description: String,
+}
+
+#[derive(BorshDeserialize)]
+struct MovieAccountHeaderV1 {
+    discriminator: String,
+    is_initialized: bool,
+    reviewer: Pubkey,
+    rating: u8,
+}
+
+#[derive(BorshDeserialize)]
+struct MovieAccountStateV2 {
+    _discriminator: u8,
+    is_initialized: bool,
+    reviewer: Pubkey,
+    rating: u8,
+    title: String,
+    description: String,
+    body_format: BodyFormat,
+    comment_settings: Option<CommentSettings>,
+}
+
+#[derive(BorshDeserialize)]
+struct MovieAccountHeaderV2 {
+    _discriminator: u8,
+    is_initialized: bool,
+    reviewer: Pubkey,
+    rating: u8,
+}
+
+#[derive(BorshDeserialize)]
+struct ReviewSnapshotV1 {
+    discriminator: u8,
+    is_initialized: bool,
+    version: u8,
+    slot: u64,
+    written_slot: u64,
+    review_count: u64,
+    comment_count: u64,
+    average_rating: u16,
+    average_comments: u64,
+    top_reviews: Vec<SnapshotEntryV1>,
+}
+
+#[derive(BorshDeserialize)]
+struct SnapshotEntryV1 {
+    review: Pubkey,
+    rating: u8,
+    comments: u64,
+}
+
+#[derive(BorshDeserialize)]
+struct MovieCommentCounterV1 {
+    _discriminator: String,
+    is_initialized: bool,
+    counter: u64,
+}
+
+#[derive(BorshDeserialize)]
+struct MovieCommentV1 {
+    _discriminator: String,
+    is_initialized: bool,
+    reviewer: Pubkey,
+    commenter: Pubkey,
+    comment: String,
+    count: u64,
+}
+
+impl From<MovieAccountStateV1> for MovieAccountState {
+    fn from(v1: MovieAccountStateV1) -> Self {
+        Self {
+            discriminator: MovieAccountState::DISCRIMINATOR,
+            is_initialized: v1.is_initialized,
+            reviewer: v1.reviewer,
+            rating: v1.rating.into(),
+            title: v1.title,
+            description: v1.description,
+            body_format: BodyFormat::Plain,
+            comment_settings: None,
+        }
+    }
+}
+
+impl From<MovieCommentCounterV1> for MovieCommentCounter {
+    fn from(v1: MovieCommentCounterV1) -> Self {
+        Self {
+            discriminator: MovieCommentCounter::DISCRIMINATOR,
+            is_initialized: v1.is_initialized,
+            total_comments_created: v1.counter,
+            page: 0,
+            active_comments: v1.counter,
+        }
+    }
+}
+
+impl From<MovieCommentV1> for MovieComment {
+    fn from(v1: MovieCommentV1) -> Self {
+        Self {
+            discriminator: MovieComment::DISCRIMINATOR,
+            is_initialized: v1.is_initialized,
+            reviewer: v1.reviewer,
+            commenter: v1.commenter,
+            comment: v1.comment,
+            count: v1.count,
+            media_uri: None,
+            path: CommentPath::default(),
+        }
+    }
+}
+
+/// Decodes `data` into the current types if it holds a v1 account, or returns
+/// `None` for any other layout.
+pub fn decode_v1(data: &[u8]) -> Result<Option<MovieReviewAccount>, ProgramError> {
+    let Ok(discriminator) = String::deserialize(&mut &data[..]) else {
+        return Ok(None);
+    };
+
+    Ok(Some(match discriminator.as_str() {
+        REVIEW_DISCRIMINATOR_V1 => MovieReviewAccount::Review(
+            try_from_slice_unchecked::<MovieAccountStateV1>(data)?.into(),
+        ),
+        COUNTER_DISCRIMINATOR_V1 => MovieReviewAccount::CommentCounter(
+            try_from_slice_unchecked::<MovieCommentCounterV1>(data)?.into(),
+        ),
+        COMMENT_DISCRIMINATOR_V1 => {
+            MovieReviewAccount::Comment(try_from_slice_unchecked::<MovieCommentV1>(data)?.into())
+        }
+        _ => return Ok(None),
+    }))
+}
+
+/// Decodes a counter with a one-byte tag. Counters created before comment
+/// pages or deletions are shorter: they read `page` as 0, and
+/// `active_comments` as `total_comments_created` since none of their comments
+/// could have been deleted yet.
+pub fn decode_counter(data: &[u8]) -> Result<MovieCommentCounter, ProgramError> {
+    let mut padded = data.to_vec();
+    padded.resize(data.len().max(MovieCommentCounter::get_account_size()), 0);
+    let mut counter = try_from_slice_unchecked::<MovieCommentCounter>(&padded)?;
+    if data.len() < MovieCommentCounter::get_account_size() {
+        counter.active_comments = counter.total_comments_created;
+    }
+    Ok(counter)
+}
+
+/// The current tag of a review stored with the v2 `tag`.
+fn review_tag_from_v2(tag: u8) -> Option<u8> {
+    match tag {
+        REVIEW_DISCRIMINATOR_V2 => Some(MovieAccountState::DISCRIMINATOR),
+        ANONYMOUS_DISCRIMINATOR_V2 => Some(MovieAccountState::ANONYMOUS_DISCRIMINATOR),
+        FOREIGN_DISCRIMINATOR_V2 => Some(MovieAccountState::FOREIGN_DISCRIMINATOR),
+        _ => None,
+    }
+}
+
+/// Decodes `data` into the current types if it holds a v2 review, or returns
+/// `None` for any other layout.
+pub fn decode_v2(data: &[u8]) -> Result<Option<MovieReviewAccount>, ProgramError> {
+    let Some(discriminator) = data.first().copied().and_then(review_tag_from_v2) else {
+        return Ok(None);
+    };
+
+    let v2 = try_from_slice_unchecked::<MovieAccountStateV2>(data)?;
+    // The v2 review was one byte shorter, and its origin follows it directly
+    let origin_offset = MovieAccountState::get_account_size(&v2.title, &v2.description) - 1;
+    let review = MovieAccountState {
+        discriminator,
+        is_initialized: v2.is_initialized,
+        reviewer: v2.reviewer,
+        rating: v2.rating.into(),
+        title: v2.title,
+        description: v2.description,
+        body_format: v2.body_format,
+        comment_settings: v2.comment_settings,
+    };
+
+    if discriminator != MovieAccountState::FOREIGN_DISCRIMINATOR {
+        return Ok(Some(MovieReviewAccount::Review(review)));
+    }
+    let mut origin_data = data
+        .get(origin_offset..)
+        .ok_or(ProgramError::InvalidAccountData)?;
+    let origin = ForeignReviewOrigin::deserialize(&mut origin_data)?;
+    Ok(Some(MovieReviewAccount::ForeignReview(review, origin)))
+}
+
+/// Bytes an account holding `data` must grow by before `upgrade_account` can
+/// rewrite it, zero if it already fits.
+pub fn upgrade_shortfall(data: &[u8]) -> Result<usize, ProgramError> {
+    let len = match decode_v2(data)? {
+        Some(MovieReviewAccount::Review(review)) => borsh::object_length(&review)?,
+        Some(MovieReviewAccount::ForeignReview(review, _)) => {
+            MovieAccountState::get_account_size(&review.title, &review.description)
+                + ForeignReviewOrigin::LEN
+        }
+        _ => return Ok(0),
+    };
+    Ok(len.saturating_sub(data.len()))
+}
+
+/// Decodes `data` if it holds an account in any earlier layout.
+pub fn decode_legacy(data: &[u8]) -> Result<Option<MovieReviewAccount>, ProgramError> {
+    match decode_v1(data)? {
+        Some(account) => Ok(Some(account)),
+        None => decode_v2(data),
+    }
+}
+
+/// Decodes a version 1 `ReviewSnapshot`. Snapshots are never written again,
+/// so they are only widened on read.
+pub fn decode_snapshot_v1(data: &[u8]) -> Result<ReviewSnapshot, ProgramError> {
+    let v1 = try_from_slice_unchecked::<ReviewSnapshotV1>(data)?;
+    Ok(ReviewSnapshot {
+        discriminator: v1.discriminator,
+        is_initialized: v1.is_initialized,
+        version: v1.version,
+        slot: v1.slot,
+        written_slot: v1.written_slot,
+        review_count: v1.review_count,
+        comment_count: v1.comment_count,
+        average_rating: v1.average_rating.into(),
+        average_comments: v1.average_comments,
+        top_reviews: v1
+            .top_reviews
+            .into_iter()
+            .map(|entry| SnapshotEntry {
+                review: entry.review,
+                rating: entry.rating.into(),
+                comments: entry.comments,
+            })
+            .collect(),
+    })
+}
+
+/// Reads the header of a review in any layout without decoding its title
+/// and description, for read-only accounts that cannot be upgraded in place.
+pub fn load_review_header(data: &[u8]) -> Result<MovieAccountHeader, ProgramError> {
+    if let Some(
+        &MovieAccountState::DISCRIMINATOR
+        | &MovieAccountState::ANONYMOUS_DISCRIMINATOR
+        | &MovieAccountState::FOREIGN_DISCRIMINATOR,
+    ) = data.first()
+    {
+        return Ok(MovieAccountHeader::deserialize(&mut &data[..])?);
+    }
+
+    if let Some(discriminator) = data.first().copied().and_then(review_tag_from_v2) {
+        let v2 = MovieAccountHeaderV2::deserialize(&mut &data[..])?;
+        return Ok(MovieAccountHeader {
+            discriminator,
+            is_initialized: v2.is_initialized,
+            reviewer: v2.reviewer,
+            rating: v2.rating.into(),
+        });
+    }
+
+    let v1 = MovieAccountHeaderV1::deserialize(&mut &data[..])
+        .map_err(|_| ProgramError::InvalidAccountData)?;
+    if v1.discriminator != REVIEW_DISCRIMINATOR_V1 {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    Ok(MovieAccountHeader {
+        discriminator: MovieAccountState::DISCRIMINATOR,
+        is_initialized: v1.is_initialized,
+        reviewer: v1.reviewer,
+        rating: v1.rating.into(),
+    })
+}
+
+/// Reads the comment settings of a review in any layout. v1 reviews predate
+/// the settings and use the defaults.
+pub fn load_comment_settings(data: &[u8]) -> Result<CommentSettings, ProgramError> {
+    let header = load_review_header(data)?;
+    let settings = match data.first() {
+        // Legacy headers are read with the current tag
+        Some(&tag) if tag == header.discriminator => MovieAccountState::comment_settings_of(data)?,
+        Some(&tag) if review_tag_from_v2(tag).is_some() => {
+            try_from_slice_unchecked::<MovieAccountStateV2>(data)?.comment_settings
+        }
+        _ => None,
+    };
+    Ok(settings.unwrap_or_default())
+}
+
+/// Rewrites `account` in the current layout if it holds an account in an
+/// earlier one. Returns whether anything changed.
+pub fn upgrade_account(account: &AccountInfo) -> Result<bool, ProgramError> {
+    let Some(upgraded) = decode_legacy(&account.data.borrow())? else {
+        return Ok(false);
+    };
+
+    let mut data = account.data.borrow_mut();
+    // A v1 account shrinks, so clear the stale tail of the old bytes. Every
+    // arm writes through a fresh slice so the account keeps its length
+    data.fill(0);
+    match upgraded {
+        MovieReviewAccount::Review(review) => review.serialize(&mut &mut data[..])?,
+        MovieReviewAccount::ForeignReview(review, origin) => {
+            let offset = MovieAccountState::get_account_size(&review.title, &review.description);
+            review.serialize(&mut &mut data[..])?;
+            let mut origin_data = data
+                .get_mut(offset..)
+                .ok_or(ProgramError::AccountDataTooSmall)?;
+            origin.serialize(&mut origin_data)?;
+        }
+        MovieReviewAccount::CommentCounter(counter) => {
+            // A v1 counter has no room for `active_comments`, which
+            // `decode_counter` derives for counters that short
+            let bytes = borsh::to_vec(&counter)?;
+            let len = bytes.len().min(data.len());
+            data[..len].copy_from_slice(&bytes[..len]);
+        }
+        MovieReviewAccount::Comment(comment) => comment.serialize(&mut &mut data[..])?,
+        // Only reviews, counters and comments ever changed layout
+        MovieReviewAccount::Config(_)
+        | MovieReviewAccount::Movie(_)
+        | MovieReviewAccount::ReviewLink(_)
+        | MovieReviewAccount::Featured(_)
+        | MovieReviewAccount::ExternalIdIndex(_)
+        | MovieReviewAccount::Translation(_)
+        | MovieReviewAccount::Follow(_)
+        | MovieReviewAccount::Mute(_)
+        | MovieReviewAccount::CommentPage(_)
+        | MovieReviewAccount::Snapshot(_)
+        | MovieReviewAccount::GlobalStats(_)
+        | MovieReviewAccount::ReviewerProfile(_)
+        | MovieReviewAccount::MovieStats(_)
+        | MovieReviewAccount::Session(_)
+        | MovieReviewAccount::AuditLog(_)
+        | MovieReviewAccount::PendingRemoval(_)
+        | MovieReviewAccount::BodyCommitment(_)
+        | MovieReviewAccount::Fingerprint(_)
+        | MovieReviewAccount::DuplicateChallenge(_)
+        | MovieReviewAccount::CommentVote(_)
+        | MovieReviewAccount::CommentKarma(_)
+        | MovieReviewAccount::HotFeed(_)
+        | MovieReviewAccount::ReviewEdits(_)
+        | MovieReviewAccount::CoAuthors(_)
+        | MovieReviewAccount::TranslationBounty(_)
+        | MovieReviewAccount::TranslationSubmission(_)
+        | MovieReviewAccount::RatingIndex(_)
+        | MovieReviewAccount::TitleFilter(_)
+        | MovieReviewAccount::RentPayer(_) => unreachable!(),
+    }
+    Ok(true)
+}
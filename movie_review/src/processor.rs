@@ -1,27 +1,96 @@
-use borsh::BorshSerialize;
+use borsh::{BorshDeserialize, BorshSerialize};
+use mpl_token_metadata::{
+    instructions::{
+        CreateMasterEditionV3CpiBuilder, CreateMetadataAccountV3CpiBuilder,
+        VerifySizedCollectionItemCpiBuilder,
+    },
+    types::{Collection, CollectionDetails, DataV2},
+};
 use solana_program::{
     account_info::{next_account_info, AccountInfo},
     borsh1::try_from_slice_unchecked,
+    bpf_loader_upgradeable::{self, UpgradeableLoaderState},
+    clock::{Clock, UnixTimestamp},
+    ed25519_program,
     entrypoint::ProgramResult,
+    epoch_schedule::EpochSchedule,
+    instruction::{get_stack_height, TRANSACTION_LEVEL_STACK_HEIGHT},
     msg,
-    program::invoke_signed,
+    program::{invoke, invoke_signed, set_return_data},
     program_error::ProgramError,
-    program_pack::IsInitialized,
+    program_pack::Pack,
     pubkey::Pubkey,
+    stake::{self, state::StakeStateV2},
     system_instruction,
-    sysvar::{rent::Rent, Sysvar},
+    sysvar::{self, instructions::get_instruction_relative, Sysvar},
+};
+use soldev_program_errors::CommonError;
+use soldev_program_utils::{
+    account::{
+        assert_not_closed, close_account, create_pda_account, rent_exempt_lamports, resize_account,
+        top_up_rent, withdraw_excess_lamports,
+    },
+    assert::{
+        assert_initialized, assert_max_len, assert_owned_by, assert_program_id, assert_rent_exempt,
+        assert_signer, assert_uninitialized,
+    },
+    pda::assert_pda,
+};
+use spl_associated_token_account::instruction::create_associated_token_account_idempotent;
+use spl_token_2022::{
+    extension::{ExtensionType, StateWithExtensions},
+    state::{Account as TokenAccount, Mint},
 };
 
+use crate::account_spec::required_accounts;
+use crate::decoder::{decode_account, MovieReviewAccount};
 use crate::error::ReviewError;
+use crate::event::{emit, ReviewEvent};
+use crate::fingerprint;
 use crate::instruction::MovieInstruction;
-use crate::state::{MovieAccountState, MovieComment, MovieCommentCounter};
+use crate::migration::{
+    decode_counter, decode_legacy, load_comment_settings, load_review_header, upgrade_account,
+    upgrade_shortfall,
+};
+use crate::state::{
+    comment_seed_bytes, AdminAction, AppealStatus, AuditEntry, AuditLog, BodyCommitment,
+    BodyFormat, CoAuthor, CommentKarma, CommentPage, CommentPath, CommentPolicy, CommentSettings,
+    CommentVote, CommentVoteKind, ContentLimits, CostPreview, DuplicateChallenge, ExternalIdIndex,
+    FeaturedReview, FeeKind, FeeSchedule, Follow, ForeignReviewMessage, ForeignReviewOrigin,
+    GlobalStats, HotFeed, Movie, MovieAccountHeader, MovieAccountState, MovieComment,
+    MovieCommentCounter, MovieStats, Mute, PagedComment, PendingRemoval, PostedVaa, ProgramConfig,
+    RatingIndex, RatingScale, ReleaseAttestation, RentPayer, ReviewCoAuthors, ReviewEdits,
+    ReviewFingerprint, ReviewLink, ReviewSnapshot, ReviewTranslation, ReviewerProfile, Session,
+    SnapshotEntry, TitleFilter, TranslationBounty, TranslationSubmission, UpgradeAuthorityStatus,
+    VrfRandomness, WalletAgeAttestation, MAX_COMMENT_DEPTH,
+};
 
 pub fn process_instruction(
     program_id: &Pubkey,
     accounts: &[AccountInfo],
     instruction_data: &[u8],
 ) -> ProgramResult {
-    let instruction = MovieInstruction::unpack(instruction_data)?;
+    let (instruction, dry_run) = MovieInstruction::unpack_with_dry_run(instruction_data)?;
+    if dry_run
+        && !matches!(
+            instruction,
+            MovieInstruction::AddMovieReview { .. }
+                | MovieInstruction::AddMovieReviewV2 { .. }
+                | MovieInstruction::AddComment { .. }
+        )
+    {
+        msg!("Instruction does not support dry runs");
+        return Err(ProgramError::InvalidInstructionData);
+    }
+    // The instructions sysvar, passed last for the CPI allowlist, is hidden
+    // from the instruction so it is not read as one of its trailing accounts
+    let (accounts, instructions_sysvar) = match accounts.split_last() {
+        Some((last, rest)) if sysvar::instructions::check_id(last.key) => (rest, Some(last)),
+        _ => (accounts, None),
+    };
+    if !dry_run && is_cpi_guarded(&instruction) {
+        assert_cpi_caller_allowed(program_id, accounts, instructions_sysvar)?;
+    }
 
     match instruction {
         MovieInstruction::AddMovieReview {
@@ -29,316 +98,7539 @@ pub fn process_instruction(
             rating,
             description,
         } => {
-            add_movie_review(program_id, accounts, title, rating, description)?;
+            add_movie_review(
+                program_id,
+                accounts,
+                title,
+                rating.into(),
+                description,
+                dry_run,
+            )?;
         }
         MovieInstruction::UpdateMovieReview {
             title,
             rating,
             description,
+        } => {
+            update_movie_review(program_id, accounts, title, rating.into(), description)?;
+        }
+        MovieInstruction::AddMovieReviewV2 {
+            title,
+            rating,
+            description,
+        } => {
+            add_movie_review(program_id, accounts, title, rating, description, dry_run)?;
+        }
+        MovieInstruction::UpdateMovieReviewV2 {
+            title,
+            rating,
+            description,
         } => {
             update_movie_review(program_id, accounts, title, rating, description)?;
         }
-        MovieInstruction::AddComment { comment } => {
-            add_comment(program_id, accounts, comment)?;
+        MovieInstruction::AddComment { comment, media_uri } => {
+            add_comment(program_id, accounts, comment, media_uri, dry_run)?;
+        }
+        MovieInstruction::ReplyToComment { comment, media_uri } => {
+            reply_to_comment(program_id, accounts, comment, media_uri)?;
+        }
+        MovieInstruction::AddPagedComment { comment } => {
+            add_paged_comment(program_id, accounts, comment)?;
+        }
+        MovieInstruction::WriteSnapshot { slot } => {
+            write_snapshot(program_id, accounts, slot)?;
+        }
+        MovieInstruction::RecordTip { amount } => {
+            record_tip(program_id, accounts, amount)?;
+        }
+        MovieInstruction::RecordSolTip { lamports } => {
+            record_sol_tip(program_id, accounts, lamports)?;
+        }
+        MovieInstruction::SetTipProgram { tip_program } => {
+            set_tip_program(program_id, accounts, tip_program)?;
+        }
+        MovieInstruction::SetFeatures { features } => {
+            set_features(program_id, accounts, features)?;
+        }
+        MovieInstruction::SetRemovalWindow { slots } => {
+            set_removal_window(program_id, accounts, slots)?;
+        }
+        MovieInstruction::SetAgeGate {
+            min_wallet_age,
+            age_attester,
+        } => {
+            set_age_gate(program_id, accounts, min_wallet_age, age_attester)?;
+        }
+        MovieInstruction::SetHideThreshold { threshold } => {
+            set_hide_threshold(program_id, accounts, threshold)?;
+        }
+        MovieInstruction::SetEditBurn { mint, amount } => {
+            set_edit_burn(program_id, accounts, mint, amount)?;
+        }
+        MovieInstruction::SetCoAuthors { co_authors } => {
+            set_co_authors(program_id, accounts, co_authors)?;
+        }
+        MovieInstruction::AcceptCoAuthorship { share_bps } => {
+            accept_co_authorship(program_id, accounts, share_bps)?;
+        }
+        MovieInstruction::SetCriticAuthority {
+            authority,
+            authority_program,
+        } => {
+            set_critic_authority(program_id, accounts, authority, authority_program)?;
+        }
+        MovieInstruction::FundTranslationBounty { language, amount } => {
+            fund_translation_bounty(program_id, accounts, language, amount)?;
+        }
+        MovieInstruction::SubmitTranslation {
+            language,
+            description,
+        } => {
+            submit_translation(program_id, accounts, language, description)?;
+        }
+        MovieInstruction::ApproveTranslation { language } => {
+            approve_translation(program_id, accounts, language)?;
+        }
+        MovieInstruction::CancelTranslationBounty { language } => {
+            cancel_translation_bounty(program_id, accounts, language)?;
+        }
+        MovieInstruction::WithdrawTranslationSubmission { language } => {
+            withdraw_translation_submission(program_id, accounts, language)?;
+        }
+        MovieInstruction::SetMovieReleaseDate { release_date } => {
+            set_movie_release_date(program_id, accounts, release_date)?;
+        }
+        MovieInstruction::IndexMovieReview => {
+            index_movie_review(program_id, accounts)?;
+        }
+        MovieInstruction::ProposeRemoval => {
+            propose_removal(program_id, accounts)?;
+        }
+        MovieInstruction::AppealRemoval => {
+            appeal_removal(program_id, accounts)?;
+        }
+        MovieInstruction::ResolveAppeal { uphold } => {
+            resolve_appeal(program_id, accounts, uphold)?;
+        }
+        MovieInstruction::FinalizeRemoval { cascade } => {
+            finalize_removal(program_id, accounts, cascade)?;
+        }
+        MovieInstruction::UpdateBodyHash { hash, uri } => {
+            update_body_hash(program_id, accounts, hash, uri)?;
+        }
+        MovieInstruction::RecordFingerprint => {
+            record_fingerprint(program_id, accounts)?;
+        }
+        MovieInstruction::ChallengeDuplicate => {
+            challenge_duplicate(program_id, accounts)?;
+        }
+        MovieInstruction::VoteOnComment { kind } => {
+            vote_on_comment(program_id, accounts, kind)?;
+        }
+        MovieInstruction::RecomputeStats { restart, finish } => {
+            recompute_stats(program_id, accounts, restart, finish)?;
+        }
+        MovieInstruction::UpdateRating { rating } => {
+            update_rating(program_id, accounts, rating)?;
+        }
+        MovieInstruction::AppendToDescription { text } => {
+            append_to_description(program_id, accounts, text)?;
+        }
+        MovieInstruction::SetContentLimits { limits } => {
+            set_content_limits(program_id, accounts, limits)?;
+        }
+        MovieInstruction::SetRatingScale { scale } => {
+            set_rating_scale(program_id, accounts, scale)?;
+        }
+        MovieInstruction::DeleteReview { cascade } => {
+            delete_review(program_id, accounts, cascade)?;
+        }
+        MovieInstruction::RepairCommentAddress => {
+            repair_comment_address(program_id, accounts)?;
+        }
+        MovieInstruction::AddMovieReviewIdempotent {
+            title,
+            rating,
+            description,
+        } => {
+            add_movie_review_idempotent(program_id, accounts, title, rating, description)?;
+        }
+        MovieInstruction::GetRequiredAccounts { instruction_tag } => {
+            get_required_accounts(instruction_tag)?;
         }
+        MovieInstruction::SetCpiCallers { callers } => {
+            set_cpi_callers(program_id, accounts, callers)?;
+        }
+        MovieInstruction::CreateSession {
+            session_key,
+            expires_slot,
+            scopes,
+        } => {
+            create_session(program_id, accounts, session_key, expires_slot, scopes)?;
+        }
+        MovieInstruction::RevokeSession => {
+            revoke_session(program_id, accounts)?;
+        }
+        MovieInstruction::AddRelayedReview {
+            title,
+            rating,
+            description,
+            nonce,
+            expires_slot,
+        } => {
+            add_relayed_review(
+                program_id,
+                accounts,
+                title,
+                rating,
+                description,
+                nonce,
+                expires_slot,
+            )?;
+        }
+        MovieInstruction::MigrateAccount => {
+            migrate_account(program_id, accounts)?;
+        }
+        MovieInstruction::InitializeConfig {
+            allow_self_comments,
+            require_reviewer_pass,
+        } => {
+            initialize_config(
+                program_id,
+                accounts,
+                allow_self_comments,
+                require_reviewer_pass,
+            )?;
+        }
+        MovieInstruction::UpdateConfig {
+            allow_self_comments,
+            require_reviewer_pass,
+        } => {
+            update_config(
+                program_id,
+                accounts,
+                allow_self_comments,
+                require_reviewer_pass,
+            )?;
+        }
+        MovieInstruction::RegisterMovie { title } => {
+            register_movie(program_id, accounts, title)?;
+        }
+        MovieInstruction::AddReviewForMovie {
+            rating,
+            description,
+        } => {
+            add_review_for_movie(program_id, accounts, rating, description)?;
+        }
+        MovieInstruction::LinkLegacyReview => {
+            link_legacy_review(program_id, accounts)?;
+        }
+        MovieInstruction::AddAnonymousReview {
+            commitment,
+            title,
+            rating,
+            description,
+        } => {
+            add_anonymous_review(program_id, accounts, commitment, title, rating, description)?;
+        }
+        MovieInstruction::RevealAuthorship { salt } => {
+            reveal_authorship(program_id, accounts, salt)?;
+        }
+        MovieInstruction::InitializePassMint => {
+            initialize_pass_mint(program_id, accounts)?;
+        }
+        MovieInstruction::IssueReviewerPass => {
+            issue_reviewer_pass(program_id, accounts)?;
+        }
+        MovieInstruction::RotateReviewCollection { name, symbol, uri } => {
+            rotate_review_collection(program_id, accounts, name, symbol, uri)?;
+        }
+        MovieInstruction::MintReviewNft { uri } => {
+            mint_review_nft(program_id, accounts, uri)?;
+        }
+        MovieInstruction::SetReleaseOracle { oracle, feed_id } => {
+            set_release_oracle(program_id, accounts, oracle, feed_id)?;
+        }
+        MovieInstruction::MarkVerifiedRelease => {
+            mark_verified_release(program_id, accounts)?;
+        }
+        MovieInstruction::RequestFeaturedDraw => {
+            request_featured_draw(program_id, accounts)?;
+        }
+        MovieInstruction::FulfillFeaturedDraw => {
+            fulfill_featured_draw(program_id, accounts)?;
+        }
+        MovieInstruction::SetVrfProgram { vrf_program } => {
+            set_vrf_program(program_id, accounts, vrf_program)?;
+        }
+        MovieInstruction::SetForeignEmitter {
+            wormhole_program,
+            emitter_chain,
+            emitter_address,
+        } => {
+            set_foreign_emitter(
+                program_id,
+                accounts,
+                wormhole_program,
+                emitter_chain,
+                emitter_address,
+            )?;
+        }
+        MovieInstruction::ImportForeignReview => {
+            import_foreign_review(program_id, accounts)?;
+        }
+        MovieInstruction::GetUpgradeAuthorityStatus => {
+            get_upgrade_authority_status(program_id, accounts)?;
+        }
+        MovieInstruction::SetFeeSchedule {
+            fees,
+            fee_exempt_mint,
+            fee_exempt_min_balance,
+        } => {
+            set_fee_schedule(
+                program_id,
+                accounts,
+                fees,
+                fee_exempt_mint,
+                fee_exempt_min_balance,
+            )?;
+        }
+        MovieInstruction::TopUpAccount => {
+            top_up_account(program_id, accounts)?;
+        }
+        MovieInstruction::WithdrawExcessLamports => {
+            withdraw_excess(program_id, accounts)?;
+        }
+        MovieInstruction::SetMovieExternalId { external_id } => {
+            set_movie_external_id(program_id, accounts, external_id)?;
+        }
+        MovieInstruction::AddReviewTranslation {
+            language,
+            description,
+        } => {
+            add_review_translation(program_id, accounts, language, description)?;
+        }
+        MovieInstruction::UpdateReviewTranslation {
+            language,
+            description,
+        } => {
+            update_review_translation(program_id, accounts, language, description)?;
+        }
+        MovieInstruction::DeleteReviewTranslation { language } => {
+            delete_review_translation(program_id, accounts, language)?;
+        }
+        MovieInstruction::SetReviewBodyFormat { body_format } => {
+            set_review_body_format(program_id, accounts, body_format)?;
+        }
+        MovieInstruction::FollowReviewer { reviewer } => {
+            follow_reviewer(program_id, accounts, reviewer)?;
+        }
+        MovieInstruction::UnfollowReviewer => {
+            unfollow_reviewer(program_id, accounts)?;
+        }
+        MovieInstruction::SetCommentSettings {
+            comments_enabled,
+            comment_policy,
+        } => {
+            set_comment_settings(program_id, accounts, comments_enabled, comment_policy)?;
+        }
+        MovieInstruction::MuteUser { user } => {
+            mute_user(program_id, accounts, user)?;
+        }
+        MovieInstruction::UnmuteUser => {
+            unmute_user(program_id, accounts)?;
+        }
+    }
+    Ok(())
+}
+
+/// Whether the CPI allowlist applies to `instruction`. Views write nothing,
+/// the permissionless upkeep instructions leave content untouched and tips
+/// are already limited to the tip program.
+fn is_cpi_guarded(instruction: &MovieInstruction) -> bool {
+    !matches!(
+        instruction,
+        MovieInstruction::GetUpgradeAuthorityStatus
+            | MovieInstruction::GetRequiredAccounts { .. }
+            | MovieInstruction::MigrateAccount
+            | MovieInstruction::TopUpAccount
+            | MovieInstruction::RepairCommentAddress
+            | MovieInstruction::RecomputeStats { .. }
+            | MovieInstruction::RecordTip { .. }
+            | MovieInstruction::RecordSolTip { .. }
+    )
+}
+
+/// Fails if this is a CPI and the config's allowlist does not hold the
+/// program of the transaction-level instruction that led to it. The config
+/// is found by key among `accounts`; under CPI, instructions that do not take
+/// it are passed it after their own accounts.
+fn assert_cpi_caller_allowed(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instructions_sysvar: Option<&AccountInfo>,
+) -> ProgramResult {
+    // Off-chain the stack height reads zero
+    if get_stack_height() <= TRANSACTION_LEVEL_STACK_HEIGHT {
+        return Ok(());
+    }
+
+    let (config_key, _) =
+        Pubkey::find_program_address(&[ProgramConfig::SEED.as_bytes()], program_id);
+    let Some(pda_config) = accounts.iter().find(|account| *account.key == config_key) else {
+        msg!("CPI calls must pass the config");
+        return Err(ReviewError::CpiCallerNotAllowed.into());
+    };
+    let config_data = load_config(program_id, pda_config)?;
+    if !config_data.has_cpi_allowlist() {
+        return Ok(());
+    }
+
+    let Some(instructions_sysvar) = instructions_sysvar else {
+        msg!("CPI calls must pass the instructions sysvar last");
+        return Err(ReviewError::CpiCallerNotAllowed.into());
+    };
+    let caller = get_instruction_relative(0, instructions_sysvar)?.program_id;
+    if !config_data.allows_cpi_caller(&caller) {
+        msg!("Program {} is not in the CPI allowlist", caller);
+        return Err(ReviewError::CpiCallerNotAllowed.into());
     }
+
     Ok(())
 }
+
+/// With `dry_run`, only validates the review and returns its `CostPreview`.
 pub fn add_movie_review(
     program_id: &Pubkey,
     accounts: &[AccountInfo],
     title: String,
-    rating: u8,
+    rating: u16,
     description: String,
+    dry_run: bool,
 ) -> ProgramResult {
     let account_info_iter = &mut accounts.iter();
     let reviewer = next_account_info(account_info_iter)?;
     let pda_review = next_account_info(account_info_iter)?;
     let pda_comment_counter = next_account_info(account_info_iter)?;
     let system_program = next_account_info(account_info_iter)?;
+    let pda_config = next_account_info(account_info_iter)?;
+    let pda_stats = next_account_info(account_info_iter)?;
+    let pda_profile = next_account_info(account_info_iter)?;
+    // The reviewer pass, then any token account proving a fee exemption and
+    // any attestation or stake account proving the wallet's age
+    let proofs = account_info_iter.as_slice();
 
     // Check if the instruction is signed
-    if !reviewer.is_signer {
-        msg!("Missing required signature");
-        return Err(ProgramError::MissingRequiredSignature);
-    }
-
-    // Derive review PDA
-    let (pda_review_key, bump_seed) =
-        Pubkey::find_program_address(&[reviewer.key.as_ref(), title.as_bytes()], program_id);
+    assert_signer(reviewer)?;
 
     // Check derived review PDA equals given review PDA
-    if pda_review_key != *pda_review.key {
-        msg!("Invalid seeds for PDA");
-        return Err(ReviewError::InvalidPDA.into());
-    }
+    let bump_seed = assert_pda(
+        pda_review,
+        &[reviewer.key.as_ref(), title.as_bytes()],
+        program_id,
+        ReviewError::InvalidPDA,
+    )?;
 
-    // Check rating is between 1 and 5
-    if !(1..=5).contains(&rating) {
-        msg!("Invalid rating");
-        return Err(ReviewError::InvalidRating.into());
+    let config = assert_may_post(program_id, reviewer, pda_config, proofs)?;
+
+    let review = MovieAccountState {
+        discriminator: MovieAccountState::DISCRIMINATOR,
+        is_initialized: true,
+        reviewer: *reviewer.key,
+        rating,
+        title,
+        description,
+        body_format: BodyFormat::Plain,
+        comment_settings: None,
     };
 
-    // Check the content of the review does not exceed the maximum length
-    let total_len = MovieAccountState::get_account_size(&title, &description);
-    if total_len > MovieAccountState::MAX_ACCOUNT_SIZE {
-        msg!("Input data exceeds max length");
-        return Err(ReviewError::InvalidDataLength.into());
+    if dry_run {
+        if pda_review.owner == program_id {
+            msg!("Reviewer has already reviewed this movie");
+            return Err(ReviewError::DuplicateReview.into());
+        }
+        let review_size = validate_review(&review, &config)?;
+        assert_pda(
+            pda_comment_counter,
+            &[pda_review.key.as_ref(), "comment".as_ref()],
+            program_id,
+            ReviewError::InvalidPDA,
+        )?;
+        assert_pda(
+            pda_profile,
+            &[ReviewerProfile::SEED.as_bytes(), reviewer.key.as_ref()],
+            program_id,
+            ReviewError::InvalidPDA,
+        )?;
+        assert_pda(
+            pda_stats,
+            &[GlobalStats::SEED.as_bytes()],
+            program_id,
+            ReviewError::InvalidPDA,
+        )?;
+        return return_costs(CostPreview {
+            fee: fee_due(program_id, &config, FeeKind::AddReview, reviewer, proofs)?,
+            rent: creation_rent(pda_review, review_size)?
+                + creation_rent(pda_comment_counter, MovieCommentCounter::get_account_size())?
+                + first_use_rent(program_id, pda_profile, ReviewerProfile::get_account_size())?
+                + first_use_rent(program_id, pda_stats, GlobalStats::get_account_size())?,
+        });
     }
 
-    msg!("creating review pda account");
-    let rent = Rent::get()?;
-    let rent_lamports = rent.minimum_balance(MovieAccountState::MAX_ACCOUNT_SIZE);
-
-    let create_account = system_instruction::create_account(
+    charge_fee(
+        program_id,
+        &config,
+        FeeKind::AddReview,
+        reviewer,
+        pda_config,
+        system_program,
+        proofs,
+    )?;
+    screen_title(
+        program_id,
         reviewer.key,
         pda_review.key,
-        rent_lamports,
-        MovieAccountState::MAX_ACCOUNT_SIZE.try_into().unwrap(),
+        &review.title,
+        reviewer,
+        system_program,
+        proofs,
+    )?;
+    create_review(
         program_id,
-    );
-    // Create the account CPI
-    invoke_signed(
-        &create_account,
-        &[reviewer.clone(), pda_review.clone(), system_program.clone()],
-        &[&[reviewer.key.as_ref(), title.as_bytes(), &[bump_seed]]],
+        reviewer,
+        pda_review,
+        pda_comment_counter,
+        system_program,
+        &[reviewer.key.as_ref(), review.title.as_bytes(), &[bump_seed]],
+        &review,
+        &config,
     )?;
-    msg!("review PDA created at: {}", pda_review_key);
-
-    let mut account_data =
-        try_from_slice_unchecked::<MovieAccountState>(&pda_review.data.borrow())?;
-
-    msg!("checking if account is initialized");
-    if account_data.is_initialized() {
-        msg!("Account already initialized");
-        return Err(ReviewError::UninitializedAccount.into());
-    }
 
-    account_data.discriminator = MovieAccountState::DISCRIMINATOR.to_string();
-    account_data.reviewer = *reviewer.key;
-    account_data.title = title;
-    account_data.rating = rating;
-    account_data.description = description;
-    account_data.is_initialized = true;
-
-    msg!("serializing account");
-    account_data.serialize(&mut *pda_review.data.borrow_mut())?;
-    msg!("state account serialized");
+    let new_reviewer = record_reviewer(
+        program_id,
+        reviewer,
+        reviewer.key,
+        pda_profile,
+        system_program,
+        rating,
+        &config.rating_scale,
+    )?;
+    update_global_stats(program_id, pda_stats, reviewer, system_program, |stats| {
+        stats.record_review(new_reviewer)
+    })
+}
 
-    let (pda_counter_key, counter_bump_seed) =
-        Pubkey::find_program_address(&[pda_review.key.as_ref(), "comment".as_ref()], program_id);
+/// Reviews keyed by a registered movie: the PDA is `[reviewer, movie]`, so a
+/// reviewer gets at most one per movie. A title-seeded review of the same movie
+/// counts too, so the legacy review PDA is passed in and must not exist. With
+/// `FEATURE_RELEASE_WINDOW`, the movie must be out.
+pub fn add_review_for_movie(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    rating: u16,
+    description: String,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let reviewer = next_account_info(account_info_iter)?;
+    let movie = next_account_info(account_info_iter)?;
+    let pda_review = next_account_info(account_info_iter)?;
+    let pda_legacy_review = next_account_info(account_info_iter)?;
+    let pda_comment_counter = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+    let pda_config = next_account_info(account_info_iter)?;
+    let pda_stats = next_account_info(account_info_iter)?;
+    let pda_profile = next_account_info(account_info_iter)?;
+    let pda_movie_stats = next_account_info(account_info_iter)?;
+    let pda_rating_index = next_account_info(account_info_iter)?;
+    // The reviewer pass, then any token account proving a fee exemption and
+    // any attestation or stake account proving the wallet's age
+    let proofs = account_info_iter.as_slice();
 
-    if pda_comment_counter.key != &pda_counter_key {
-        msg!("Invalid seeds for PDA");
-        return Err(ReviewError::InvalidPDA.into());
-    }
+    assert_signer(reviewer)?;
 
-    msg!("creating comment counter");
-    let rent = Rent::get()?;
-    let counter_rent_lamports = rent.minimum_balance(MovieCommentCounter::get_account_size());
+    let movie_data = load_movie(program_id, movie)?;
 
-    let create_pda_counter = system_instruction::create_account(
-        reviewer.key,
-        pda_comment_counter.key,
-        counter_rent_lamports,
-        MovieCommentCounter::get_account_size().try_into().unwrap(),
+    let bump_seed = assert_pda(
+        pda_review,
+        &[reviewer.key.as_ref(), movie.key.as_ref()],
         program_id,
-    );
-
-    invoke_signed(
-        &create_pda_counter,
-        &[
-            reviewer.clone(),
-            pda_comment_counter.clone(),
-            system_program.clone(),
-        ],
-        &[&[
-            pda_review.key.as_ref(),
-            "comment".as_ref(),
-            &[counter_bump_seed],
-        ]],
+        ReviewError::InvalidPDA,
+    )?;
+    assert_pda(
+        pda_legacy_review,
+        &[reviewer.key.as_ref(), movie_data.title.as_bytes()],
+        program_id,
+        ReviewError::InvalidPDA,
     )?;
-    msg!("comment counter PDA created at: {}", pda_counter_key);
 
-    let mut counter_data =
-        try_from_slice_unchecked::<MovieCommentCounter>(&pda_comment_counter.data.borrow())?;
+    if pda_review.owner == program_id || pda_legacy_review.owner == program_id {
+        msg!("Reviewer has already reviewed this movie");
+        return Err(ReviewError::DuplicateReview.into());
+    }
 
-    if counter_data.is_initialized() {
-        msg!("Counter account already initialized");
-        return Err(ReviewError::UninitializedAccount.into());
+    let config = assert_may_post(program_id, reviewer, pda_config, proofs)?;
+    // Movies without a date are released, so the clock is not needed
+    if config.is_enabled(ProgramConfig::FEATURE_RELEASE_WINDOW)
+        && movie_data.release_date != 0
+        && !movie_data.is_released(Clock::get()?.unix_timestamp)
+    {
+        msg!("{} is out on {}", movie_data.title, movie_data.release_date);
+        return Err(ReviewError::MovieNotReleased.into());
     }
+    charge_fee(
+        program_id,
+        &config,
+        FeeKind::AddReview,
+        reviewer,
+        pda_config,
+        system_program,
+        proofs,
+    )?;
+    screen_title(
+        program_id,
+        reviewer.key,
+        pda_review.key,
+        &movie_data.title,
+        reviewer,
+        system_program,
+        proofs,
+    )?;
 
-    msg!("initializing counter account");
-    counter_data.discriminator = MovieCommentCounter::DISCRIMINATOR.to_string();
-    counter_data.counter = 0;
-    counter_data.is_initialized = true;
+    let review = MovieAccountState {
+        discriminator: MovieAccountState::DISCRIMINATOR,
+        is_initialized: true,
+        reviewer: *reviewer.key,
+        rating,
+        title: movie_data.title,
+        description,
+        body_format: BodyFormat::Plain,
+        comment_settings: None,
+    };
+    create_review(
+        program_id,
+        reviewer,
+        pda_review,
+        pda_comment_counter,
+        system_program,
+        &[reviewer.key.as_ref(), movie.key.as_ref(), &[bump_seed]],
+        &review,
+        &config,
+    )?;
 
-    counter_data.serialize(&mut *pda_comment_counter.data.borrow_mut())?;
-    msg!("counter account initialized");
+    let mut movie_stats =
+        load_movie_stats(program_id, movie, pda_movie_stats, reviewer, system_program)?;
+    movie_stats
+        .record_review(rating)
+        .ok_or(CommonError::Overflow)?;
+    movie_stats.serialize(&mut &mut pda_movie_stats.data.borrow_mut()[..])?;
+    index_review(
+        program_id,
+        movie.key,
+        pda_review.key,
+        rating_bucket(&config, rating)?,
+        pda_rating_index,
+        reviewer,
+        system_program,
+    )?;
 
-    Ok(())
+    let new_reviewer = record_reviewer(
+        program_id,
+        reviewer,
+        reviewer.key,
+        pda_profile,
+        system_program,
+        rating,
+        &config.rating_scale,
+    )?;
+    update_global_stats(program_id, pda_stats, reviewer, system_program, |stats| {
+        stats.record_review(new_reviewer)
+    })
 }
 
-pub fn update_movie_review(
+/// The review PDA is `[commitment, title]`, mirroring `[reviewer, title]`, so
+/// the address does not reveal the author either.
+pub fn add_anonymous_review(
     program_id: &Pubkey,
     accounts: &[AccountInfo],
+    commitment: Pubkey,
     title: String,
-    rating: u8,
+    rating: u16,
     description: String,
 ) -> ProgramResult {
     let account_info_iter = &mut accounts.iter();
+    let payer = next_account_info(account_info_iter)?;
+    let pda_review = next_account_info(account_info_iter)?;
+    let pda_comment_counter = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+    let pda_config = next_account_info(account_info_iter)?;
+    let pda_stats = next_account_info(account_info_iter)?;
+    // The reviewer pass, then any token account proving a fee exemption and
+    // any attestation or stake account proving the wallet's age
+    let proofs = account_info_iter.as_slice();
 
-    let updater = next_account_info(account_info_iter)?;
-    let pda_account = next_account_info(account_info_iter)?;
+    assert_signer(payer)?;
 
-    // Check if the instruction is signed
-    if pda_account.owner != program_id {
-        msg!("Invalid account owner");
-        return Err(ProgramError::IllegalOwner);
-    }
+    let bump_seed = assert_pda(
+        pda_review,
+        &[commitment.as_ref(), title.as_bytes()],
+        program_id,
+        ReviewError::InvalidPDA,
+    )?;
 
-    if !updater.is_signer {
-        msg!("Missing required signature");
-        return Err(ProgramError::MissingRequiredSignature);
-    }
+    // The pass gate applies to the payer, which keeps the author hidden but
+    // still limits anonymous posting to pass holders
+    let config = assert_may_post(program_id, payer, pda_config, proofs)?;
+    charge_fee(
+        program_id,
+        &config,
+        FeeKind::AddReview,
+        payer,
+        pda_config,
+        system_program,
+        proofs,
+    )?;
 
-    let (pda, _bump_seed) =
-        Pubkey::find_program_address(&[updater.key.as_ref(), title.as_bytes()], program_id);
+    let review = MovieAccountState {
+        discriminator: MovieAccountState::ANONYMOUS_DISCRIMINATOR,
+        is_initialized: true,
+        reviewer: commitment,
+        rating,
+        title,
+        description,
+        body_format: BodyFormat::Plain,
+        comment_settings: None,
+    };
+    create_review(
+        program_id,
+        payer,
+        pda_review,
+        pda_comment_counter,
+        system_program,
+        &[commitment.as_ref(), review.title.as_bytes(), &[bump_seed]],
+        &review,
+        &config,
+    )?;
 
-    if pda != *pda_account.key {
-        msg!("Invalid seeds for PDA");
-        return Err(ReviewError::InvalidPDA.into());
-    }
+    // The author stays hidden, so they get no profile
+    update_global_stats(program_id, pda_stats, payer, system_program, |stats| {
+        stats.record_review(false)
+    })
+}
 
-    if !(1..=5).contains(&rating) {
-        msg!("Invalid rating");
-        return Err(ReviewError::InvalidRating.into());
-    };
+/// Proves the signer wrote an anonymous review and records them as its author.
+/// Only the header is rewritten; the review keeps its address.
+pub fn reveal_authorship(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    salt: [u8; 32],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let reviewer = next_account_info(account_info_iter)?;
+    let pda_review = next_account_info(account_info_iter)?;
 
-    let total_len = MovieAccountState::get_account_size(&title, &description);
-    if total_len > MovieAccountState::MAX_ACCOUNT_SIZE {
-        msg!("Input data exceeds max length");
-        return Err(ReviewError::InvalidDataLength.into());
-    }
+    assert_signer(reviewer)?;
+    assert_owned_by(pda_review, program_id)?;
 
-    msg!("unpacking state account");
-    let mut account_data =
-        try_from_slice_unchecked::<MovieAccountState>(&pda_account.data.borrow())?;
-    msg!("borrowed account data");
+    upgrade_account(pda_review)?;
+    let mut header = MovieAccountHeader::deserialize(&mut &pda_review.data.borrow()[..])?;
+    assert_initialized(&header, ReviewError::UninitializedAccount)?;
 
-    if !account_data.is_initialized() {
-        msg!("Account not initialized yet");
-        return Err(ReviewError::UninitializedAccount.into());
+    if header.discriminator != MovieAccountState::ANONYMOUS_DISCRIMINATOR {
+        msg!("Review is not anonymous");
+        return Err(ReviewError::InvalidDiscriminator.into());
     }
 
-    account_data.title = title;
-    account_data.rating = rating;
-    account_data.description = description;
+    if MovieAccountState::authorship_commitment(reviewer.key, &salt) != header.reviewer {
+        msg!("Salt and signer do not match the commitment");
+        return Err(ReviewError::InvalidAuthorshipProof.into());
+    }
+
+    header.discriminator = MovieAccountState::DISCRIMINATOR;
+    header.reviewer = *reviewer.key;
 
-    account_data.serialize(&mut *pda_account.data.borrow_mut())?;
+    assert_rent_exempt(pda_review, CommonError::NotRentExempt)?;
+    header.serialize(&mut &mut pda_review.data.borrow_mut()[..])?;
+    msg!(
+        "authorship of {} revealed as {}",
+        pda_review.key,
+        reviewer.key
+    );
 
     Ok(())
 }
 
-pub fn add_comment(
+/// Like `add_movie_review`, but a review already at the PDA with the same
+/// rating and description is left as it is: nothing is charged or counted
+/// and `AlreadyExists` is logged. A review with other content is still a
+/// `DuplicateReview`.
+///
+/// Accounts are those of `AddMovieReview`.
+pub fn add_movie_review_idempotent(
     program_id: &Pubkey,
     accounts: &[AccountInfo],
-    comment: String,
+    title: String,
+    rating: u16,
+    description: String,
 ) -> ProgramResult {
     let account_info_iter = &mut accounts.iter();
-
-    let commenter = next_account_info(account_info_iter)?;
+    let reviewer = next_account_info(account_info_iter)?;
     let pda_review = next_account_info(account_info_iter)?;
-    let pda_counter = next_account_info(account_info_iter)?;
+
+    if pda_review.owner != program_id {
+        return add_movie_review(program_id, accounts, title, rating, description, false);
+    }
+
+    assert_signer(reviewer)?;
+    assert_pda(
+        pda_review,
+        &[reviewer.key.as_ref(), title.as_bytes()],
+        program_id,
+        ReviewError::InvalidPDA,
+    )?;
+    assert_not_closed(pda_review, CommonError::AccountClosed)?;
+
+    let MovieReviewAccount::Review(existing) = decode_account(&pda_review.data.borrow())? else {
+        msg!("Account is not a review");
+        return Err(ReviewError::InvalidDiscriminator.into());
+    };
+    assert_initialized(&existing, ReviewError::UninitializedAccount)?;
+    if existing.reviewer != *reviewer.key
+        || existing.rating != rating
+        || existing.description != description
+    {
+        msg!("Reviewer has already reviewed this movie differently");
+        return Err(ReviewError::DuplicateReview.into());
+    }
+
+    emit(&ReviewEvent::AlreadyExists {
+        account: *pda_review.key,
+    });
+    msg!("review already exists at {}", pda_review.key);
+    Ok(())
+}
+
+/// Accounts are those of `AddMovieReview` with the relayer paying in the
+/// reviewer's place, and the reviewer, no longer a signer, and the
+/// instructions sysvar after it: `[relayer, reviewer, instructions_sysvar,
+/// review, ..., profile, rent_payer, ...]`. Fees are charged to the relayer,
+/// who is recorded at `rent_payer` as the review's payer.
+pub fn add_relayed_review(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    title: String,
+    rating: u16,
+    description: String,
+    nonce: u64,
+    expires_slot: u64,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let relayer = next_account_info(account_info_iter)?;
+    let reviewer = next_account_info(account_info_iter)?;
+    let instructions_sysvar = next_account_info(account_info_iter)?;
+    let pda_review = next_account_info(account_info_iter)?;
+    let pda_comment_counter = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+    let pda_config = next_account_info(account_info_iter)?;
+    let pda_stats = next_account_info(account_info_iter)?;
+    let pda_profile = next_account_info(account_info_iter)?;
+    let pda_rent_payer = next_account_info(account_info_iter)?;
+    // The reviewer's pass, then any token account proving the relayer's fee
+    // exemption
+    let proofs = account_info_iter.as_slice();
+
+    assert_signer(relayer)?;
+
+    let bump_seed = assert_pda(
+        pda_review,
+        &[reviewer.key.as_ref(), title.as_bytes()],
+        program_id,
+        ReviewError::InvalidPDA,
+    )?;
+
+    let message = MovieAccountState::relayed_review_message(
+        program_id,
+        &title,
+        rating,
+        &description,
+        nonce,
+        expires_slot,
+    );
+    assert_ed25519_signature(instructions_sysvar, reviewer.key, &message)?;
+    if Clock::get()?.slot > expires_slot {
+        msg!("Relayed review expired at slot {}", expires_slot);
+        return Err(ReviewError::RelayedSignatureExpired.into());
+    }
+
+    let config = assert_may_post(program_id, reviewer, pda_config, proofs)?;
+    assert_feature(&config, ProgramConfig::FEATURE_RELAYED_REVIEWS)?;
+    charge_fee(
+        program_id,
+        &config,
+        FeeKind::AddReview,
+        relayer,
+        pda_config,
+        system_program,
+        proofs,
+    )?;
+    screen_title(
+        program_id,
+        reviewer.key,
+        pda_review.key,
+        &title,
+        relayer,
+        system_program,
+        proofs,
+    )?;
+
+    let review = MovieAccountState {
+        discriminator: MovieAccountState::DISCRIMINATOR,
+        is_initialized: true,
+        reviewer: *reviewer.key,
+        rating,
+        title,
+        description,
+        body_format: BodyFormat::Plain,
+        comment_settings: None,
+    };
+    create_review(
+        program_id,
+        relayer,
+        pda_review,
+        pda_comment_counter,
+        system_program,
+        &[reviewer.key.as_ref(), review.title.as_bytes(), &[bump_seed]],
+        &review,
+        &config,
+    )?;
+    msg!("{} relayed review for {}", relayer.key, reviewer.key);
+    record_rent_payer(
+        program_id,
+        relayer,
+        pda_review,
+        pda_rent_payer,
+        system_program,
+    )?;
+
+    let new_reviewer = record_reviewer(
+        program_id,
+        relayer,
+        reviewer.key,
+        pda_profile,
+        system_program,
+        rating,
+        &config.rating_scale,
+    )?;
+    advance_relay_nonce(pda_profile, relayer, system_program, nonce)?;
+    update_global_stats(program_id, pda_stats, relayer, system_program, |stats| {
+        stats.record_review(new_reviewer)
+    })
+}
+
+/// Records `payer` as the payer of `account`'s rent at `pda_rent_payer`,
+/// creating the record at their expense, or taking over the record left by
+/// a deleted account at the same address.
+fn record_rent_payer<'a>(
+    program_id: &Pubkey,
+    payer: &AccountInfo<'a>,
+    account: &AccountInfo<'a>,
+    pda_rent_payer: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+) -> ProgramResult {
+    let bump_seed = assert_pda(
+        pda_rent_payer,
+        &[RentPayer::SEED.as_bytes(), account.key.as_ref()],
+        program_id,
+        ReviewError::InvalidPDA,
+    )?;
+    if pda_rent_payer.owner != program_id {
+        create_pda_account(
+            payer,
+            pda_rent_payer,
+            system_program,
+            RentPayer::get_account_size(),
+            program_id,
+            &[
+                RentPayer::SEED.as_bytes(),
+                account.key.as_ref(),
+                &[bump_seed],
+            ],
+        )?;
+    }
+
+    let record = RentPayer {
+        discriminator: RentPayer::DISCRIMINATOR,
+        is_initialized: true,
+        account: *account.key,
+        payer: *payer.key,
+    };
+    record.serialize(&mut &mut pda_rent_payer.data.borrow_mut()[..])?;
+    Ok(())
+}
+
+/// Loads who paid `account`'s rent from its `pda_rent_payer`, `None` if
+/// nobody was recorded.
+fn load_rent_payer(
+    program_id: &Pubkey,
+    account: &AccountInfo,
+    pda_rent_payer: &AccountInfo,
+) -> Result<Option<Pubkey>, ProgramError> {
+    assert_pda(
+        pda_rent_payer,
+        &[RentPayer::SEED.as_bytes(), account.key.as_ref()],
+        program_id,
+        ReviewError::InvalidPDA,
+    )?;
+    if pda_rent_payer.owner != program_id {
+        return Ok(None);
+    }
+
+    let record = try_from_slice_unchecked::<RentPayer>(&pda_rent_payer.data.borrow())?;
+    if record.discriminator != RentPayer::DISCRIMINATOR {
+        msg!("Account is not a rent payer record");
+        return Err(ReviewError::InvalidDiscriminator.into());
+    }
+    assert_initialized(&record, ReviewError::UninitializedAccount)?;
+    Ok(Some(record.payer))
+}
+
+/// Consumes `nonce`, which must be the profile's next relay nonce, growing
+/// profiles created before the nonce at `payer`'s expense.
+fn advance_relay_nonce<'a>(
+    pda_profile: &AccountInfo<'a>,
+    payer: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    nonce: u64,
+) -> ProgramResult {
+    let mut profile_data = load_profile(pda_profile)?;
+    if nonce != profile_data.relay_nonce {
+        msg!(
+            "Expected relay nonce {}, got {}",
+            profile_data.relay_nonce,
+            nonce
+        );
+        return Err(ReviewError::InvalidRelayNonce.into());
+    }
+    profile_data.relay_nonce = nonce.checked_add(1).ok_or(CommonError::Overflow)?;
+
+    if pda_profile.data_len() < ReviewerProfile::get_account_size() {
+        resize_account(
+            pda_profile,
+            payer,
+            system_program,
+            ReviewerProfile::get_account_size(),
+        )?;
+    }
+    assert_rent_exempt(pda_profile, CommonError::NotRentExempt)?;
+    profile_data.serialize(&mut &mut pda_profile.data.borrow_mut()[..])?;
+    Ok(())
+}
+
+/// Checks the instruction before the current one has the ed25519 program
+/// verify one signature by `signer` over `message`, both stored in that
+/// instruction's own data.
+fn assert_ed25519_signature(
+    instructions_sysvar: &AccountInfo,
+    signer: &Pubkey,
+    message: &[u8],
+) -> ProgramResult {
+    let verify = get_instruction_relative(-1, instructions_sysvar)?;
+    if verify.program_id != ed25519_program::ID {
+        msg!("Expected an ed25519 program instruction before this one");
+        return Err(ReviewError::InvalidRelayedSignature.into());
+    }
+
+    // A signature count and a padding byte, then seven u16 offsets:
+    // signature, its instruction, key, its instruction, message, its size and
+    // its instruction. `u16::MAX` stands for the ed25519 instruction itself
+    let data = &verify.data;
+    if data.len() < 16 || data[0] != 1 {
+        msg!("ed25519 instruction must verify exactly one signature");
+        return Err(ReviewError::InvalidRelayedSignature.into());
+    }
+    let offset = |i: usize| u16::from_le_bytes([data[2 + 2 * i], data[3 + 2 * i]]) as usize;
+    let (key_start, message_start, message_len) = (offset(2), offset(4), offset(5));
+    let in_own_data = [offset(1), offset(3), offset(6)]
+        .iter()
+        .all(|&index| index == u16::MAX as usize);
+    let key = data.get(key_start..key_start + 32);
+    let signed = data.get(message_start..message_start + message_len);
+    if !in_own_data || key != Some(signer.as_ref()) || signed != Some(message) {
+        msg!("ed25519 instruction does not verify {}'s review", signer);
+        return Err(ReviewError::InvalidRelayedSignature.into());
+    }
+
+    Ok(())
+}
+
+/// Checks `review` may be created under `config`, returning the size its
+/// account is allocated at.
+fn validate_review(
+    review: &MovieAccountState,
+    config: &ProgramConfig,
+) -> Result<usize, ProgramError> {
+    if !config.rating_scale.contains(review.rating) {
+        msg!("Invalid rating");
+        return Err(ReviewError::InvalidRating.into());
+    };
+
+    let review_size = config.limits.review_size();
+
+    // Check the content of the review does not exceed the maximum length
+    assert_max_len(
+        MovieAccountState::get_account_size(&review.title, &review.description),
+        review_size,
+        ReviewError::InvalidDataLength,
+    )?;
+    Ok(review_size)
+}
+
+/// Checks `title` against `reviewer`'s title filter if it is among the
+/// `proofs`, warning with `PossibleDuplicateTitle` if they probably reviewed
+/// it under another spelling, then adds it. The filter is created on first
+/// use at `payer`'s expense.
+fn screen_title<'a>(
+    program_id: &Pubkey,
+    reviewer: &Pubkey,
+    review: &Pubkey,
+    title: &str,
+    payer: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    proofs: &[AccountInfo<'a>],
+) -> ProgramResult {
+    let (filter, bump_seed) = Pubkey::find_program_address(
+        &[TitleFilter::SEED.as_bytes(), reviewer.as_ref()],
+        program_id,
+    );
+    let Some(pda_filter) = proofs.iter().find(|proof| *proof.key == filter) else {
+        return Ok(());
+    };
+
+    let mut filter_data = if pda_filter.owner == program_id {
+        let filter_data = try_from_slice_unchecked::<TitleFilter>(&pda_filter.data.borrow())?;
+        if filter_data.discriminator != TitleFilter::DISCRIMINATOR {
+            msg!("Account is not a title filter");
+            return Err(ReviewError::InvalidDiscriminator.into());
+        }
+        filter_data
+    } else {
+        msg!("creating title filter account");
+        create_pda_account(
+            payer,
+            pda_filter,
+            system_program,
+            TitleFilter::get_account_size(),
+            program_id,
+            &[
+                TitleFilter::SEED.as_bytes(),
+                reviewer.as_ref(),
+                &[bump_seed],
+            ],
+        )?;
+        TitleFilter {
+            discriminator: TitleFilter::DISCRIMINATOR,
+            is_initialized: true,
+            reviewer: *reviewer,
+            titles: 0,
+            bits: [0; TitleFilter::WORDS],
+        }
+    };
+
+    if filter_data.may_contain(title) {
+        msg!("{} has probably reviewed {} before", reviewer, title);
+        emit(&ReviewEvent::PossibleDuplicateTitle {
+            recipient: *reviewer,
+            review: *review,
+            title: title.to_string(),
+        });
+    }
+    filter_data.insert(title);
+    filter_data.serialize(&mut &mut pda_filter.data.borrow_mut()[..])?;
+    Ok(())
+}
+
+/// Validates `review`, then creates the review account at `pda_review`
+/// (derived from `review_seeds`), writes it and creates its comment counter.
+/// `payer` funds both accounts.
+#[allow(clippy::too_many_arguments)]
+fn create_review<'a>(
+    program_id: &Pubkey,
+    payer: &AccountInfo<'a>,
+    pda_review: &AccountInfo<'a>,
+    pda_comment_counter: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    review_seeds: &[&[u8]],
+    review: &MovieAccountState,
+    config: &ProgramConfig,
+) -> ProgramResult {
+    let review_size = validate_review(review, config)?;
+
+    msg!("creating review pda account");
+    create_pda_account(
+        payer,
+        pda_review,
+        system_program,
+        review_size,
+        program_id,
+        review_seeds,
+    )?;
+    msg!("review PDA created at: {}", pda_review.key);
+    assert_rent_exempt(pda_review, CommonError::NotRentExempt)?;
+
+    let account_data = MovieAccountHeader::deserialize(&mut &pda_review.data.borrow()[..])?;
+
+    msg!("checking if account is initialized");
+    assert_uninitialized(&account_data, ReviewError::UninitializedAccount)?;
+
+    msg!("serializing account");
+    review.serialize(&mut &mut pda_review.data.borrow_mut()[..])?;
+    msg!("state account serialized");
+
+    let counter_bump_seed = assert_pda(
+        pda_comment_counter,
+        &[pda_review.key.as_ref(), "comment".as_ref()],
+        program_id,
+        ReviewError::InvalidPDA,
+    )?;
+
+    msg!("creating comment counter");
+    create_pda_account(
+        payer,
+        pda_comment_counter,
+        system_program,
+        MovieCommentCounter::get_account_size(),
+        program_id,
+        &[
+            pda_review.key.as_ref(),
+            "comment".as_ref(),
+            &[counter_bump_seed],
+        ],
+    )?;
+    msg!(
+        "comment counter PDA created at: {}",
+        pda_comment_counter.key
+    );
+    assert_rent_exempt(pda_comment_counter, CommonError::NotRentExempt)?;
+
+    let mut counter_data =
+        try_from_slice_unchecked::<MovieCommentCounter>(&pda_comment_counter.data.borrow())?;
+
+    assert_uninitialized(&counter_data, ReviewError::UninitializedAccount)?;
+
+    msg!("initializing counter account");
+    counter_data.discriminator = MovieCommentCounter::DISCRIMINATOR;
+    counter_data.total_comments_created = 0;
+    counter_data.active_comments = 0;
+    counter_data.is_initialized = true;
+
+    counter_data.serialize(&mut &mut pda_comment_counter.data.borrow_mut()[..])?;
+    msg!("counter account initialized");
+
+    Ok(())
+}
+
+/// Reserves the `[reviewer, movie]` address for a review created before the
+/// movie was registered, pointing it at the title-seeded review. Once linked,
+/// the reviewer cannot add a second review of the movie, and clients can look
+/// up either kind of review from the movie.
+pub fn link_legacy_review(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let reviewer = next_account_info(account_info_iter)?;
+    let movie = next_account_info(account_info_iter)?;
+    let pda_legacy_review = next_account_info(account_info_iter)?;
+    let pda_link = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+
+    assert_signer(reviewer)?;
+    assert_owned_by(pda_legacy_review, program_id)?;
+
+    let movie_data = load_movie(program_id, movie)?;
+
+    assert_pda(
+        pda_legacy_review,
+        &[reviewer.key.as_ref(), movie_data.title.as_bytes()],
+        program_id,
+        ReviewError::InvalidPDA,
+    )?;
+    let review = load_review_header(&pda_legacy_review.data.borrow())?;
+    assert_initialized(&review, ReviewError::UninitializedAccount)?;
+
+    let bump_seed = assert_pda(
+        pda_link,
+        &[reviewer.key.as_ref(), movie.key.as_ref()],
+        program_id,
+        ReviewError::InvalidPDA,
+    )?;
+    if pda_link.owner == program_id {
+        msg!("Reviewer has already reviewed this movie");
+        return Err(ReviewError::DuplicateReview.into());
+    }
+
+    msg!("creating review link");
+    create_pda_account(
+        reviewer,
+        pda_link,
+        system_program,
+        ReviewLink::get_account_size(),
+        program_id,
+        &[reviewer.key.as_ref(), movie.key.as_ref(), &[bump_seed]],
+    )?;
+    assert_rent_exempt(pda_link, CommonError::NotRentExempt)?;
+
+    let link_data = ReviewLink {
+        discriminator: ReviewLink::DISCRIMINATOR,
+        is_initialized: true,
+        review: *pda_legacy_review.key,
+    };
+    link_data.serialize(&mut &mut pda_link.data.borrow_mut()[..])?;
+    msg!("linked {} to {}", pda_link.key, pda_legacy_review.key);
+
+    Ok(())
+}
+
+/// Adds a movie to the registry at `["movie", sha256(title)]`. Permissionless:
+/// the PDA makes each title unique.
+pub fn register_movie(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    title: String,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let payer = next_account_info(account_info_iter)?;
+    let pda_movie = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+
+    assert_signer(payer)?;
+
+    assert_max_len(
+        title.len(),
+        Movie::MAX_TITLE_LEN,
+        ReviewError::InvalidDataLength,
+    )?;
+
+    let title_hash = Movie::title_hash(&title);
+    let bump_seed = assert_pda(
+        pda_movie,
+        &[Movie::SEED.as_bytes(), title_hash.as_ref()],
+        program_id,
+        ReviewError::InvalidPDA,
+    )?;
+
+    msg!("creating movie account");
+    create_pda_account(
+        payer,
+        pda_movie,
+        system_program,
+        Movie::get_account_size(&title, ""),
+        program_id,
+        &[Movie::SEED.as_bytes(), title_hash.as_ref(), &[bump_seed]],
+    )?;
+    assert_rent_exempt(pda_movie, CommonError::NotRentExempt)?;
+
+    let movie_data = Movie {
+        discriminator: Movie::DISCRIMINATOR,
+        is_initialized: true,
+        title,
+        verified_release: false,
+        release_date: 0,
+        external_id: String::new(),
+    };
+    movie_data.serialize(&mut &mut pda_movie.data.borrow_mut()[..])?;
+    msg!("movie registered at: {}", pda_movie.key);
+
+    Ok(())
+}
+
+/// Rewrites a review. Its first edit is free; later ones burn the config's
+/// `edit_burn_amount` of the edit burn mint from the updater's token account.
+///
+/// Accounts are `[updater, review, profile, config, edits, system_program,
+/// (movie), (updater_token, burn_mint, token_program)]`, the movie only for
+/// reviews keyed by a registered movie and the burn accounts only for paid
+/// edits. The updater pays for the review's `ReviewEdits` on its first edit.
+pub fn update_movie_review(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    title: String,
+    rating: u16,
+    description: String,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let updater = next_account_info(account_info_iter)?;
+    let pda_account = next_account_info(account_info_iter)?;
+    let pda_profile = next_account_info(account_info_iter)?;
+    let pda_config = next_account_info(account_info_iter)?;
+    let pda_edits = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+    // The movie and its rating index are only passed for reviews keyed by a
+    // registered movie, so they are there when the remaining accounts are two
+    // more than the burn triple or none
+    let remaining_accounts = account_info_iter.as_slice();
+    let (movie, burn_accounts) = match remaining_accounts {
+        [movie, pda_rating_index, burn_accounts @ ..] if remaining_accounts.len() % 3 == 2 => {
+            (Some((movie, pda_rating_index)), burn_accounts)
+        }
+        _ => (None, remaining_accounts),
+    };
+
+    assert_owned_by(pda_account, program_id)?;
+
+    // Check if the instruction is signed
+    assert_signer(updater)?;
+
+    match movie {
+        Some((movie, _)) => {
+            let movie_data = load_movie(program_id, movie)?;
+            if movie_data.title != title {
+                msg!("Title does not match the registered movie");
+                return Err(ReviewError::InvalidPDA.into());
+            }
+            assert_pda(
+                pda_account,
+                &[updater.key.as_ref(), movie.key.as_ref()],
+                program_id,
+                ReviewError::InvalidPDA,
+            )?;
+        }
+        None => {
+            assert_pda(
+                pda_account,
+                &[updater.key.as_ref(), title.as_bytes()],
+                program_id,
+                ReviewError::InvalidPDA,
+            )?;
+        }
+    }
+    assert_profile_pda(program_id, updater.key, pda_profile)?;
+
+    let config = load_config(program_id, pda_config)?;
+    if !config.rating_scale.contains(rating) {
+        msg!("Invalid rating");
+        return Err(ReviewError::InvalidRating.into());
+    };
+
+    // The review was allocated at the content limit in force when it was
+    // posted, or grown since, and is rewritten in place
+    assert_max_len(
+        MovieAccountState::get_account_size(&title, &description),
+        pda_account.data_len(),
+        ReviewError::InvalidDataLength,
+    )?;
+
+    // Only the header is decoded: the old title and description are about to
+    // be replaced, so there is no need to copy them onto the heap
+    upgrade_account(pda_account)?;
+
+    msg!("unpacking state account header");
+    let header = MovieAccountHeader::deserialize(&mut &pda_account.data.borrow()[..])?;
+
+    assert_initialized(&header, ReviewError::UninitializedAccount)?;
+
+    if header.discriminator != MovieAccountState::DISCRIMINATOR {
+        msg!("Account is not a review");
+        return Err(ReviewError::InvalidDiscriminator.into());
+    }
+
+    let body_format = MovieAccountState::body_format_of(&pda_account.data.borrow())?;
+    if !body_format.is_valid(&description) {
+        msg!("Description is not valid {:?}", body_format);
+        return Err(ReviewError::InvalidRichText.into());
+    }
+    let comment_settings = MovieAccountState::comment_settings_of(&pda_account.data.borrow())?;
+
+    count_edit(
+        program_id,
+        &config,
+        updater,
+        pda_account,
+        pda_edits,
+        system_program,
+        burn_accounts,
+    )?;
+
+    let account_data = MovieAccountState {
+        discriminator: header.discriminator,
+        is_initialized: header.is_initialized,
+        reviewer: header.reviewer,
+        rating,
+        title,
+        description,
+        body_format,
+        comment_settings,
+    };
+
+    assert_rent_exempt(pda_account, CommonError::NotRentExempt)?;
+    account_data.serialize(&mut &mut pda_account.data.borrow_mut()[..])?;
+
+    // Files reviews posted before the index existed at the updater's expense
+    if let Some((movie, pda_rating_index)) = movie {
+        index_review(
+            program_id,
+            movie.key,
+            pda_account.key,
+            rating_bucket(&config, rating)?,
+            pda_rating_index,
+            updater,
+            system_program,
+        )?;
+    }
+
+    rerate_reviewer(
+        program_id,
+        pda_profile,
+        header.rating,
+        rating,
+        &config.rating_scale,
+    )
+}
+
+/// Counts an edit of `review` in its `ReviewEdits`, creating them on the
+/// first edit, and burns the config's edit burn out of `burn_accounts`,
+/// `[updater_token, burn_mint, token_program]`, once the free edits are used.
+fn count_edit<'a>(
+    program_id: &Pubkey,
+    config: &ProgramConfig,
+    updater: &AccountInfo<'a>,
+    review: &AccountInfo<'a>,
+    pda_edits: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    burn_accounts: &[AccountInfo<'a>],
+) -> ProgramResult {
+    let edits_bump = assert_pda(
+        pda_edits,
+        &[ReviewEdits::SEED.as_bytes(), review.key.as_ref()],
+        program_id,
+        ReviewError::InvalidPDA,
+    )?;
+
+    let mut edits_data = if pda_edits.owner == program_id {
+        let edits_data = try_from_slice_unchecked::<ReviewEdits>(&pda_edits.data.borrow())?;
+        assert_initialized(&edits_data, ReviewError::UninitializedAccount)?;
+        if edits_data.discriminator != ReviewEdits::DISCRIMINATOR {
+            msg!("Account is not a review's edits");
+            return Err(ReviewError::InvalidDiscriminator.into());
+        }
+        edits_data
+    } else {
+        msg!("creating review edits account");
+        create_pda_account(
+            updater,
+            pda_edits,
+            system_program,
+            ReviewEdits::get_account_size(),
+            program_id,
+            &[
+                ReviewEdits::SEED.as_bytes(),
+                review.key.as_ref(),
+                &[edits_bump],
+            ],
+        )?;
+        ReviewEdits {
+            discriminator: ReviewEdits::DISCRIMINATOR,
+            is_initialized: true,
+            review: *review.key,
+            edits: 0,
+        }
+    };
+
+    if edits_data.next_edit_burns(config) {
+        let [updater_token, burn_mint, token_program] = burn_accounts else {
+            msg!("Edits after the free ones must burn tokens");
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+        if *burn_mint.key != config.edit_burn_mint {
+            msg!("Mint is not the config's edit burn mint");
+            return Err(ReviewError::InvalidBurnMint.into());
+        }
+        if token_program.key != &spl_token::ID && token_program.key != &spl_token_2022::ID {
+            msg!("Invalid token program {}", token_program.key);
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        assert_owned_by(burn_mint, token_program.key)?;
+
+        msg!("burning {} for edit", config.edit_burn_amount);
+        invoke(
+            &spl_token_2022::instruction::burn(
+                token_program.key,
+                updater_token.key,
+                burn_mint.key,
+                updater.key,
+                &[],
+                config.edit_burn_amount,
+            )?,
+            &[
+                updater_token.clone(),
+                burn_mint.clone(),
+                updater.clone(),
+                token_program.clone(),
+            ],
+        )?;
+    }
+
+    edits_data.edits = edits_data
+        .edits
+        .checked_add(1)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    edits_data.serialize(&mut &mut pda_edits.data.borrow_mut()[..])?;
+    Ok(())
+}
+
+/// Writes the new rating straight to its bytes in the review. Only the
+/// fixed-size header and the title are decoded, so the description is never
+/// copied onto the heap or re-serialized.
+///
+/// Accounts are `[reviewer, review, profile, config, (rating_index),
+/// (session)]`, where a session with `Session::SCOPE_UPDATE` lets its key
+/// sign as the reviewer. A review keyed by a registered movie needs the
+/// movie's rating index, in which an indexed review moves to its new fifth
+/// of the scale in place.
+pub fn update_rating(program_id: &Pubkey, accounts: &[AccountInfo], rating: u16) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let signer = next_account_info(account_info_iter)?;
+    let pda_review = next_account_info(account_info_iter)?;
+    let pda_profile = next_account_info(account_info_iter)?;
+    let pda_config = next_account_info(account_info_iter)?;
+    let proofs = account_info_iter.as_slice();
+
+    assert_signer(signer)?;
+    let reviewer = signer_authority(program_id, signer, proofs, Session::SCOPE_UPDATE)?;
+    assert_owned_by(pda_review, program_id)?;
+    assert_not_closed(pda_review, CommonError::AccountClosed)?;
+
+    let config = load_config(program_id, pda_config)?;
+    if !config.rating_scale.contains(rating) {
+        msg!("Invalid rating");
+        return Err(ReviewError::InvalidRating.into());
+    }
+
+    upgrade_account(pda_review)?;
+    let header = MovieAccountHeader::deserialize(&mut &pda_review.data.borrow()[..])?;
+    assert_initialized(&header, ReviewError::UninitializedAccount)?;
+
+    if header.discriminator != MovieAccountState::DISCRIMINATOR {
+        msg!("Account is not a review");
+        return Err(ReviewError::InvalidDiscriminator.into());
+    }
+    if header.reviewer != reviewer {
+        msg!("Signer did not write this review");
+        return Err(CommonError::Unauthorized.into());
+    }
+    assert_profile_pda(program_id, &reviewer, pda_profile)?;
+
+    let offset = MovieAccountState::RATING_OFFSET;
+    pda_review.data.borrow_mut()[offset..offset + 2].copy_from_slice(&rating.to_le_bytes());
+
+    let title = MovieAccountState::title_of(&pda_review.data.borrow())?;
+    let movie = Pubkey::find_program_address(
+        &[Movie::SEED.as_bytes(), &Movie::title_hash(&title)],
+        program_id,
+    )
+    .0;
+    if Pubkey::find_program_address(&[reviewer.as_ref(), movie.as_ref()], program_id).0
+        == *pda_review.key
+    {
+        let (rating_index, _) = Pubkey::find_program_address(
+            &[RatingIndex::SEED.as_bytes(), movie.as_ref()],
+            program_id,
+        );
+        let pda_rating_index = proofs
+            .iter()
+            .find(|proof| *proof.key == rating_index)
+            .ok_or_else(|| {
+                msg!(
+                    "Missing the rating index {} of the review's movie",
+                    rating_index
+                );
+                ProgramError::NotEnoughAccountKeys
+            })?;
+        // Movies without reviews keyed by them since the index have none
+        if pda_rating_index.owner == program_id {
+            check_rating_index(pda_rating_index)?;
+            let mut index_data = pda_rating_index.data.borrow_mut();
+            if RatingIndex::remove(&mut index_data, pda_review.key)?.is_some() {
+                RatingIndex::insert(
+                    &mut index_data,
+                    rating_bucket(&config, rating)?,
+                    pda_review.key,
+                )?;
+            }
+        }
+    }
+
+    rerate_reviewer(
+        program_id,
+        pda_profile,
+        header.rating,
+        rating,
+        &config.rating_scale,
+    )
+}
+
+/// With `dry_run`, only validates the comment and returns its `CostPreview`.
+pub fn add_comment(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    comment: String,
+    media_uri: Option<String>,
+    dry_run: bool,
+) -> ProgramResult {
+    write_comment(program_id, accounts, comment, media_uri, false, dry_run)
+}
+
+/// Adds a comment in reply to `parent`, nested at most `MAX_COMMENT_DEPTH`
+/// deep.
+///
+/// Accounts are those of `AddComment` with the parent comment inserted
+/// after the mute: `[commenter, review, counter, comment, system_program,
+/// config, mute, parent, global_stats, hot_feed, proofs...]`.
+pub fn reply_to_comment(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    comment: String,
+    media_uri: Option<String>,
+) -> ProgramResult {
+    write_comment(program_id, accounts, comment, media_uri, true, false)
+}
+
+fn write_comment(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    comment: String,
+    media_uri: Option<String>,
+    is_reply: bool,
+    dry_run: bool,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+
+    let commenter = next_account_info(account_info_iter)?;
+    let pda_review = next_account_info(account_info_iter)?;
+    let pda_counter = next_account_info(account_info_iter)?;
+    let pda_comment = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+    let pda_config = next_account_info(account_info_iter)?;
+    // Where the reviewer's mute of the commenter would be
+    let pda_mute = next_account_info(account_info_iter)?;
+    let pda_parent = if is_reply {
+        Some(next_account_info(account_info_iter)?)
+    } else {
+        None
+    };
+    let pda_stats = next_account_info(account_info_iter)?;
+    let pda_feed = next_account_info(account_info_iter)?;
+    // Accounts proving a fee exemption, that the commenter meets the review's
+    // comment policy or that the signer holds the commenter's session
+    let proofs = account_info_iter.as_slice();
+
+    let mut counter_data = if dry_run {
+        // Legacy counters are read as they are, since a dry run writes nothing
+        read_counter(&pda_counter.data.borrow())?
+    } else {
+        load_counter(pda_counter)?
+    };
+
+    // Check if the instruction is signed
+    assert_signer(commenter)?;
+    let author = signer_authority(program_id, commenter, proofs, Session::SCOPE_COMMENT)?;
+
+    // Check if the account owner is the program
+    assert_owned_by(pda_review, program_id)?;
+
+    // Derived and signed with the same bytes, so the address checked is the
+    // one created
+    let comment_seed = comment_seed_bytes(counter_data.total_comments_created);
+    let bump_seed = assert_pda(
+        pda_comment,
+        &[pda_review.key.as_ref(), &comment_seed],
+        program_id,
+        ReviewError::InvalidPDA,
+    )?;
+
+    if let Some(uri) = &media_uri {
+        if !MovieComment::is_valid_media_uri(uri) {
+            msg!(
+                "Media URI must be an https, ipfs or ar URI of at most {} bytes",
+                MovieComment::MAX_MEDIA_URI_LEN
+            );
+            return Err(ReviewError::InvalidMediaUri.into());
+        }
+    }
+
+    let (config, review) = authorize_comment(
+        program_id, &author, pda_review, pda_config, pda_mute, proofs,
+    )?;
+
+    assert_max_len(
+        MovieComment::get_account_size(&comment, media_uri.as_deref()),
+        config.limits.comment_size(),
+        ReviewError::InvalidDataLength,
+    )?;
+
+    let parent = match pda_parent {
+        Some(pda_parent) => Some((
+            *pda_parent.key,
+            load_parent_comment(program_id, pda_review, pda_parent)?,
+        )),
+        None => None,
+    };
+    let path = match &parent {
+        Some((_, parent)) => parent.path.child(parent.count).ok_or_else(|| {
+            msg!("Parent is already {} replies deep", MAX_COMMENT_DEPTH);
+            ReviewError::ThreadTooDeep
+        })?,
+        None => CommentPath::default(),
+    };
+
+    if dry_run {
+        if pda_comment.owner == program_id {
+            msg!("Comment {} already exists", pda_comment.key);
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+        assert_pda(
+            pda_stats,
+            &[GlobalStats::SEED.as_bytes()],
+            program_id,
+            ReviewError::InvalidPDA,
+        )?;
+        assert_pda(
+            pda_feed,
+            &[HotFeed::SEED.as_bytes()],
+            program_id,
+            ReviewError::InvalidPDA,
+        )?;
+        // `save_counter` grows counters created before pages or deletions
+        let counter_rent = if pda_counter.data_len() < MovieCommentCounter::get_account_size() {
+            creation_rent(pda_counter, MovieCommentCounter::get_account_size())?
+        } else {
+            0
+        };
+        return return_costs(CostPreview {
+            fee: fee_due(program_id, &config, FeeKind::AddComment, commenter, proofs)?,
+            rent: creation_rent(pda_comment, config.limits.comment_size())?
+                + counter_rent
+                + first_use_rent(program_id, pda_stats, GlobalStats::get_account_size())?
+                + first_use_rent(program_id, pda_feed, HotFeed::get_account_size())?,
+        });
+    }
+
+    charge_fee(
+        program_id,
+        &config,
+        FeeKind::AddComment,
+        commenter,
+        pda_config,
+        system_program,
+        proofs,
+    )?;
+
+    create_pda_account(
+        commenter,
+        pda_comment,
+        system_program,
+        config.limits.comment_size(),
+        program_id,
+        &[pda_review.key.as_ref(), &comment_seed, &[bump_seed]],
+    )?;
+    msg!("comment PDA created: {}", pda_comment.key);
+    assert_rent_exempt(pda_comment, CommonError::NotRentExempt)?;
+
+    let mut comment_data = try_from_slice_unchecked::<MovieComment>(&pda_comment.data.borrow())?;
+
+    msg!("checking if comment account is initialized");
+    assert_uninitialized(&comment_data, ReviewError::UninitializedAccount)?;
+
+    comment_data.discriminator = MovieComment::DISCRIMINATOR;
+    comment_data.is_initialized = true;
+    comment_data.reviewer = *pda_review.key;
+    comment_data.commenter = author;
+    let mentions = MovieComment::mentions(&comment);
+    comment_data.comment = comment;
+    comment_data.media_uri = media_uri;
+    comment_data.count = counter_data.next_comment_index()?;
+    comment_data.path = path;
+
+    comment_data.serialize(&mut &mut pda_comment.data.borrow_mut()[..])?;
+
+    notify_comment(&review, pda_review.key, pda_comment.key, &author, mentions);
+    if let Some((parent_key, parent)) = parent.filter(|(_, p)| p.commenter != author) {
+        emit(&ReviewEvent::CommentReplied {
+            recipient: parent.commenter,
+            comment: parent_key,
+            reply: *pda_comment.key,
+            replier: author,
+        });
+    }
+
+    msg!("incrementing counter");
+    save_counter(
+        pda_review.key,
+        &counter_data,
+        pda_counter,
+        commenter,
+        system_program,
+    )?;
+
+    update_global_stats(
+        program_id,
+        pda_stats,
+        commenter,
+        system_program,
+        GlobalStats::record_comment,
+    )?;
+    record_activity(
+        program_id,
+        pda_feed,
+        commenter,
+        system_program,
+        pda_review.key,
+        HotFeed::COMMENT_WEIGHT,
+    )
+}
+
+/// Loads a review's comment counter, upgrading v1 counters first.
+fn load_counter(pda_counter: &AccountInfo) -> Result<MovieCommentCounter, ProgramError> {
+    upgrade_account(pda_counter)?;
+    read_counter(&pda_counter.data.borrow())
+}
+
+/// Reads a comment counter in any layout without upgrading it. Shorter
+/// counters are read as `migration::decode_counter` describes.
+fn read_counter(data: &[u8]) -> Result<MovieCommentCounter, ProgramError> {
+    let counter_data = match decode_legacy(data)? {
+        Some(MovieReviewAccount::CommentCounter(counter_data)) => counter_data,
+        _ => decode_counter(data)?,
+    };
+
+    // Check if counter_data is initialized
+    assert_initialized(&counter_data, ReviewError::UninitializedAccount)?;
+
+    if counter_data.discriminator != MovieCommentCounter::DISCRIMINATOR {
+        msg!("Account is not a comment counter");
+        return Err(ReviewError::InvalidDiscriminator.into());
+    }
+    Ok(counter_data)
+}
+
+/// Writes `review`'s counter back, growing counters created before comment
+/// pages or deletions at `payer`'s expense, and logs the new counts.
+fn save_counter<'a>(
+    review: &Pubkey,
+    counter_data: &MovieCommentCounter,
+    pda_counter: &AccountInfo<'a>,
+    payer: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+) -> ProgramResult {
+    if pda_counter.data_len() < MovieCommentCounter::get_account_size() {
+        resize_account(
+            pda_counter,
+            payer,
+            system_program,
+            MovieCommentCounter::get_account_size(),
+        )?;
+    }
+    assert_rent_exempt(pda_counter, CommonError::NotRentExempt)?;
+    counter_data.serialize(&mut &mut pda_counter.data.borrow_mut()[..])?;
+    emit(&ReviewEvent::CommentCountChanged {
+        review: *review,
+        active_comments: counter_data.active_comments,
+        total_comments_created: counter_data.total_comments_created,
+    });
+    Ok(())
+}
+
+/// Applies `update` to the global stats at `["global_stats"]`, creating them
+/// at `payer`'s expense on first use, or growing stats created before the
+/// newest fields were appended. `update` returns `None` on overflow.
+fn update_global_stats<'a>(
+    program_id: &Pubkey,
+    pda_stats: &AccountInfo<'a>,
+    payer: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    update: impl FnOnce(&mut GlobalStats) -> Option<()>,
+) -> ProgramResult {
+    let bump_seed = assert_pda(
+        pda_stats,
+        &[GlobalStats::SEED.as_bytes()],
+        program_id,
+        ReviewError::InvalidPDA,
+    )?;
+
+    let mut stats_data = if pda_stats.owner == program_id {
+        let mut data = pda_stats.data.borrow().to_vec();
+        data.resize(data.len().max(GlobalStats::get_account_size()), 0);
+        let stats_data = try_from_slice_unchecked::<GlobalStats>(&data)?;
+        if stats_data.discriminator != GlobalStats::DISCRIMINATOR {
+            msg!("Account is not the global stats");
+            return Err(ReviewError::InvalidDiscriminator.into());
+        }
+        if pda_stats.data_len() < GlobalStats::get_account_size() {
+            resize_account(
+                pda_stats,
+                payer,
+                system_program,
+                GlobalStats::get_account_size(),
+            )?;
+        }
+        stats_data
+    } else {
+        msg!("creating global stats account");
+        create_pda_account(
+            payer,
+            pda_stats,
+            system_program,
+            GlobalStats::get_account_size(),
+            program_id,
+            &[GlobalStats::SEED.as_bytes(), &[bump_seed]],
+        )?;
+        GlobalStats {
+            discriminator: GlobalStats::DISCRIMINATOR,
+            is_initialized: true,
+            ..GlobalStats::default()
+        }
+    };
+
+    update(&mut stats_data).ok_or(CommonError::Overflow)?;
+    stats_data.serialize(&mut &mut pda_stats.data.borrow_mut()[..])?;
+    Ok(())
+}
+
+/// Records `weight` of activity on `review` in the hot feed at
+/// `["hot_feed"]`, creating it at `payer`'s expense on first use.
+fn record_activity<'a>(
+    program_id: &Pubkey,
+    pda_feed: &AccountInfo<'a>,
+    payer: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    review: &Pubkey,
+    weight: u64,
+) -> ProgramResult {
+    let bump_seed = assert_pda(
+        pda_feed,
+        &[HotFeed::SEED.as_bytes()],
+        program_id,
+        ReviewError::InvalidPDA,
+    )?;
+
+    let mut feed_data = if pda_feed.owner == program_id {
+        let feed_data = try_from_slice_unchecked::<HotFeed>(&pda_feed.data.borrow())?;
+        if feed_data.discriminator != HotFeed::DISCRIMINATOR {
+            msg!("Account is not the hot feed");
+            return Err(ReviewError::InvalidDiscriminator.into());
+        }
+        feed_data
+    } else {
+        msg!("creating hot feed account");
+        create_pda_account(
+            payer,
+            pda_feed,
+            system_program,
+            HotFeed::get_account_size(),
+            program_id,
+            &[HotFeed::SEED.as_bytes(), &[bump_seed]],
+        )?;
+        HotFeed {
+            discriminator: HotFeed::DISCRIMINATOR,
+            is_initialized: true,
+            ..HotFeed::default()
+        }
+    };
+
+    feed_data.record(review, weight, Clock::get()?.slot);
+    feed_data.serialize(&mut &mut pda_feed.data.borrow_mut()[..])?;
+    Ok(())
+}
+
+/// Loads the stats of `movie` at `["movie_stats", movie]`, creating them at
+/// `payer`'s expense on first use.
+fn load_movie_stats<'a>(
+    program_id: &Pubkey,
+    movie: &AccountInfo,
+    pda_movie_stats: &AccountInfo<'a>,
+    payer: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+) -> Result<MovieStats, ProgramError> {
+    let bump_seed = assert_pda(
+        pda_movie_stats,
+        &[MovieStats::SEED.as_bytes(), movie.key.as_ref()],
+        program_id,
+        ReviewError::InvalidPDA,
+    )?;
+
+    if pda_movie_stats.owner == program_id {
+        let stats_data = try_from_slice_unchecked::<MovieStats>(&pda_movie_stats.data.borrow())?;
+        if stats_data.discriminator != MovieStats::DISCRIMINATOR {
+            msg!("Account is not a movie's stats");
+            return Err(ReviewError::InvalidDiscriminator.into());
+        }
+        return Ok(stats_data);
+    }
+
+    msg!("creating movie stats account");
+    create_pda_account(
+        payer,
+        pda_movie_stats,
+        system_program,
+        MovieStats::get_account_size(),
+        program_id,
+        &[
+            MovieStats::SEED.as_bytes(),
+            movie.key.as_ref(),
+            &[bump_seed],
+        ],
+    )?;
+    Ok(MovieStats {
+        discriminator: MovieStats::DISCRIMINATOR,
+        is_initialized: true,
+        movie: *movie.key,
+        ..MovieStats::default()
+    })
+}
+
+/// Which fifth of the config's rating scale `rating` is filed under in a
+/// `RatingIndex`.
+fn rating_bucket(config: &ProgramConfig, rating: u16) -> Result<usize, ProgramError> {
+    config
+        .rating_scale
+        .bucket(rating, RatingIndex::BUCKETS)
+        .ok_or_else(|| {
+            msg!("Rating {} is off the scale", rating);
+            ReviewError::InvalidRating.into()
+        })
+}
+
+fn check_rating_index(pda_rating_index: &AccountInfo) -> ProgramResult {
+    if pda_rating_index.data.borrow().first() != Some(&RatingIndex::DISCRIMINATOR) {
+        msg!("Account is not a movie's rating index");
+        return Err(ReviewError::InvalidDiscriminator.into());
+    }
+    Ok(())
+}
+
+/// Files `review` under `bucket` in `movie`'s rating index, creating the
+/// index with the movie's first review and growing it by a key, both at
+/// `payer`'s expense. A review already indexed is moved to `bucket` in place.
+fn index_review<'a>(
+    program_id: &Pubkey,
+    movie: &Pubkey,
+    review: &Pubkey,
+    bucket: usize,
+    pda_rating_index: &AccountInfo<'a>,
+    payer: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+) -> ProgramResult {
+    let bump_seed = assert_pda(
+        pda_rating_index,
+        &[RatingIndex::SEED.as_bytes(), movie.as_ref()],
+        program_id,
+        ReviewError::InvalidPDA,
+    )?;
+
+    if pda_rating_index.owner == program_id {
+        check_rating_index(pda_rating_index)?;
+        let mut index_data = pda_rating_index.data.borrow_mut();
+        if RatingIndex::remove(&mut index_data, review)?.is_some() {
+            return RatingIndex::insert(&mut index_data, bucket, review);
+        }
+    } else {
+        msg!("creating rating index account");
+        create_pda_account(
+            payer,
+            pda_rating_index,
+            system_program,
+            RatingIndex::get_account_size(0),
+            program_id,
+            &[RatingIndex::SEED.as_bytes(), movie.as_ref(), &[bump_seed]],
+        )?;
+        RatingIndex {
+            discriminator: RatingIndex::DISCRIMINATOR,
+            is_initialized: true,
+            movie: *movie,
+            counts: [0; RatingIndex::BUCKETS],
+            reviews: Vec::new(),
+        }
+        .serialize(&mut &mut pda_rating_index.data.borrow_mut()[..])?;
+    }
+
+    let account_size = pda_rating_index.data_len() + 32;
+    resize_account(pda_rating_index, payer, system_program, account_size)?;
+    RatingIndex::insert(&mut pda_rating_index.data.borrow_mut(), bucket, review)
+}
+
+/// Counts a review by `reviewer` on their profile and extends their posting
+/// streak, creating the profile with their first review at `payer`'s expense
+/// and growing profiles created before the streak. Returns whether it was
+/// created.
+fn record_reviewer<'a>(
+    program_id: &Pubkey,
+    payer: &AccountInfo<'a>,
+    reviewer: &Pubkey,
+    pda_profile: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    rating: u16,
+    scale: &RatingScale,
+) -> Result<bool, ProgramError> {
+    let bump_seed = assert_pda(
+        pda_profile,
+        &[ReviewerProfile::SEED.as_bytes(), reviewer.as_ref()],
+        program_id,
+        ReviewError::InvalidPDA,
+    )?;
+
+    let is_new = pda_profile.owner != program_id;
+    let mut profile_data = if is_new {
+        msg!("creating reviewer profile");
+        create_pda_account(
+            payer,
+            pda_profile,
+            system_program,
+            ReviewerProfile::get_account_size(),
+            program_id,
+            &[
+                ReviewerProfile::SEED.as_bytes(),
+                reviewer.as_ref(),
+                &[bump_seed],
+            ],
+        )?;
+        ReviewerProfile {
+            discriminator: ReviewerProfile::DISCRIMINATOR,
+            is_initialized: true,
+            reviewer: *reviewer,
+            review_count: 0,
+            ratings: [0; 5],
+            relay_nonce: 0,
+            last_active_epoch: 0,
+            streak: 0,
+            authority_program: Pubkey::default(),
+            authority: Pubkey::default(),
+        }
+    } else {
+        load_profile(pda_profile)?
+    };
+
+    profile_data.review_count = profile_data
+        .review_count
+        .checked_add(1)
+        .ok_or(CommonError::Overflow)?;
+    profile_data
+        .record_rating(rating, scale)
+        .ok_or(CommonError::Overflow)?;
+    profile_data.record_activity(Clock::get()?.epoch);
+
+    if pda_profile.data_len() < ReviewerProfile::get_account_size() {
+        resize_account(
+            pda_profile,
+            payer,
+            system_program,
+            ReviewerProfile::get_account_size(),
+        )?;
+    }
+    assert_rent_exempt(pda_profile, CommonError::NotRentExempt)?;
+    store_profile(&profile_data, pda_profile)?;
+    Ok(is_new)
+}
+
+fn load_profile(pda_profile: &AccountInfo) -> Result<ReviewerProfile, ProgramError> {
+    // Zero-extend profiles created before their newest fields were appended
+    let mut data = pda_profile.data.borrow().to_vec();
+    data.resize(data.len().max(ReviewerProfile::get_account_size()), 0);
+    let profile_data = try_from_slice_unchecked::<ReviewerProfile>(&data)?;
+    if profile_data.discriminator != ReviewerProfile::DISCRIMINATOR {
+        msg!("Account is not a reviewer profile");
+        return Err(ReviewError::InvalidDiscriminator.into());
+    }
+    Ok(profile_data)
+}
+
+/// Writes `profile_data` to `pda_profile`. A profile created before the relay
+/// nonce, streak or critic authority gets only the fields that fit, reading
+/// the rest as zero: the handlers changing them grow the account first.
+fn store_profile(profile_data: &ReviewerProfile, pda_profile: &AccountInfo) -> ProgramResult {
+    let bytes = borsh::to_vec(profile_data)?;
+    let mut data = pda_profile.data.borrow_mut();
+    let len = bytes.len().min(data.len());
+    data[..len].copy_from_slice(&bytes[..len]);
+    Ok(())
+}
+
+/// Fails unless `pda_profile` is `reviewer`'s profile address. Callers check
+/// it before changing anything, as the profile is only written last.
+fn assert_profile_pda(
+    program_id: &Pubkey,
+    reviewer: &Pubkey,
+    pda_profile: &AccountInfo,
+) -> ProgramResult {
+    assert_pda(
+        pda_profile,
+        &[ReviewerProfile::SEED.as_bytes(), reviewer.as_ref()],
+        program_id,
+        ReviewError::InvalidPDA,
+    )?;
+    Ok(())
+}
+
+/// Moves a review from `old_rating` to `new_rating` in its reviewer's
+/// histogram. Reviewers whose reviews all predate profiles have none yet and
+/// are skipped. `pda_profile` must already have passed `assert_profile_pda`.
+fn rerate_reviewer(
+    program_id: &Pubkey,
+    pda_profile: &AccountInfo,
+    old_rating: u16,
+    new_rating: u16,
+    scale: &RatingScale,
+) -> ProgramResult {
+    if pda_profile.owner != program_id || old_rating == new_rating {
+        return Ok(());
+    }
+
+    let mut profile_data = load_profile(pda_profile)?;
+    profile_data.change_rating(old_rating, new_rating, scale);
+    store_profile(&profile_data, pda_profile)?;
+    Ok(())
+}
+
+/// Fails unless the config has `feature` switched on.
+fn assert_feature(config: &ProgramConfig, feature: u64) -> ProgramResult {
+    if !config.is_enabled(feature) {
+        msg!("Feature {:#x} is switched off", feature);
+        return Err(ReviewError::FeatureDisabled.into());
+    }
+    Ok(())
+}
+
+/// The wallet `signer` acts for: the authority of a session among `proofs`
+/// that gives `signer` `scope`, the reviewer of a profile among them naming
+/// `signer` as its critic authority, otherwise `signer` itself. A session of
+/// the signer's that has expired or lacks the scope is an error rather than
+/// ignored, so the action is not silently attributed to the session key.
+fn signer_authority(
+    program_id: &Pubkey,
+    signer: &AccountInfo,
+    proofs: &[AccountInfo],
+    scope: u8,
+) -> Result<Pubkey, ProgramError> {
+    for proof in proofs {
+        if proof.owner != program_id {
+            continue;
+        }
+        // Only this program creates accounts with the profile discriminator,
+        // and only at the reviewer's profile PDA. A critic authority is a PDA,
+        // so only its program can have it sign, through `invoke_signed`
+        if proof.data.borrow().first() == Some(&ReviewerProfile::DISCRIMINATOR) {
+            let profile_data = load_profile(proof)?;
+            if profile_data.has_authority() && profile_data.authority == *signer.key {
+                return Ok(profile_data.reviewer);
+            }
+            continue;
+        }
+        // Only CreateSession can create a program account with this
+        // discriminator, and only at the session's PDA
+        if proof.data.borrow().first() != Some(&Session::DISCRIMINATOR) {
+            continue;
+        }
+        let session = try_from_slice_unchecked::<Session>(&proof.data.borrow())?;
+        if session.session_key != *signer.key {
+            continue;
+        }
+        if !session.allows(scope, Clock::get()?.slot) {
+            msg!("Session {} is expired or out of scope", proof.key);
+            return Err(ReviewError::SessionNotValid.into());
+        }
+        return Ok(session.authority);
+    }
+    Ok(*signer.key)
+}
+
+/// Checks `commenter` may comment on `pda_review`: the config's self-comment
+/// rule, the reviewer's mute and the review's comment settings. Returns the
+/// config and the review's header.
+fn authorize_comment(
+    program_id: &Pubkey,
+    commenter: &Pubkey,
+    pda_review: &AccountInfo,
+    pda_config: &AccountInfo,
+    pda_mute: &AccountInfo,
+    proofs: &[AccountInfo],
+) -> Result<(ProgramConfig, MovieAccountHeader), ProgramError> {
+    let config = load_config(program_id, pda_config)?;
+    let review = load_review_header(&pda_review.data.borrow())?;
+    if !config.allow_self_comments && review.reviewer == *commenter {
+        msg!("Reviewer cannot comment on their own review");
+        return Err(ReviewError::SelfComment.into());
+    }
+
+    assert_pda(
+        pda_mute,
+        &[
+            Mute::SEED.as_bytes(),
+            review.reviewer.as_ref(),
+            commenter.as_ref(),
+        ],
+        program_id,
+        ReviewError::InvalidPDA,
+    )?;
+    // Only MuteUser can create a program account at this address
+    if pda_mute.owner == program_id {
+        msg!("{} is muted by {}", commenter, review.reviewer);
+        return Err(ReviewError::Muted.into());
+    }
+
+    let settings = load_comment_settings(&pda_review.data.borrow())?;
+    if !settings.comments_enabled {
+        msg!("Comments are disabled on this review");
+        return Err(ReviewError::CommentsDisabled.into());
+    }
+    if !meets_comment_policy(
+        program_id,
+        &settings.comment_policy,
+        &review.reviewer,
+        commenter,
+        proofs,
+    ) {
+        msg!("Commenter does not meet {:?}", settings.comment_policy);
+        return Err(ReviewError::CommentPolicyNotMet.into());
+    }
+
+    Ok((config, review))
+}
+
+/// Notifies the review's author and everyone mentioned of a new comment
+/// stored at `comment`.
+fn notify_comment(
+    review: &MovieAccountHeader,
+    review_key: &Pubkey,
+    comment: &Pubkey,
+    commenter: &Pubkey,
+    mentions: Vec<Pubkey>,
+) {
+    // Anonymous and imported reviews have no author key to notify
+    if review.discriminator == MovieAccountState::DISCRIMINATOR && review.reviewer != *commenter {
+        emit(&ReviewEvent::ReviewCommented {
+            recipient: review.reviewer,
+            review: *review_key,
+            comment: *comment,
+            commenter: *commenter,
+        });
+    }
+    for recipient in mentions {
+        if recipient != *commenter {
+            emit(&ReviewEvent::Mentioned {
+                recipient,
+                comment: *comment,
+                author: *commenter,
+            });
+        }
+    }
+}
+
+/// Appends a comment to the review's current `CommentPage`, creating the
+/// page when it is the first comment on it. Moderation and fees are those
+/// of `AddComment`.
+///
+/// Accounts are `[commenter, review, counter, page, system_program, config,
+/// mute, global_stats, hot_feed, proofs...]`, where `page` is at the
+/// counter's `page`.
+pub fn add_paged_comment(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    comment: String,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let commenter = next_account_info(account_info_iter)?;
+    let pda_review = next_account_info(account_info_iter)?;
+    let pda_counter = next_account_info(account_info_iter)?;
+    let pda_page = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+    let pda_config = next_account_info(account_info_iter)?;
+    let pda_mute = next_account_info(account_info_iter)?;
+    let pda_stats = next_account_info(account_info_iter)?;
+    let pda_feed = next_account_info(account_info_iter)?;
+    let proofs = account_info_iter.as_slice();
+
+    assert_signer(commenter)?;
+    let author = signer_authority(program_id, commenter, proofs, Session::SCOPE_COMMENT)?;
+    assert_owned_by(pda_review, program_id)?;
+    assert_owned_by(pda_counter, program_id)?;
+    assert_pda(
+        pda_counter,
+        &[pda_review.key.as_ref(), "comment".as_ref()],
+        program_id,
+        ReviewError::InvalidPDA,
+    )?;
+    let mut counter_data = load_counter(pda_counter)?;
+
+    let page_bytes = counter_data.page.to_le_bytes();
+    let bump_seed = assert_pda(
+        pda_page,
+        &[
+            CommentPage::SEED.as_bytes(),
+            pda_review.key.as_ref(),
+            &page_bytes,
+        ],
+        program_id,
+        ReviewError::InvalidCommentPage,
+    )?;
+    assert_max_len(
+        comment.len(),
+        CommentPage::MAX_COMMENT_LEN,
+        ReviewError::InvalidDataLength,
+    )?;
+
+    let (config, review) = authorize_comment(
+        program_id, &author, pda_review, pda_config, pda_mute, proofs,
+    )?;
+    charge_fee(
+        program_id,
+        &config,
+        FeeKind::AddComment,
+        commenter,
+        pda_config,
+        system_program,
+        proofs,
+    )?;
+
+    let mut page_data = if pda_page.owner == program_id {
+        assert_not_closed(pda_page, ReviewError::InvalidCommentPage)?;
+        let page_data = try_from_slice_unchecked::<CommentPage>(&pda_page.data.borrow())?;
+        assert_initialized(&page_data, ReviewError::UninitializedAccount)?;
+        if page_data.review != *pda_review.key || page_data.page != counter_data.page {
+            msg!("Page {} is not this review's current page", page_data.page);
+            return Err(ReviewError::InvalidCommentPage.into());
+        }
+        page_data
+    } else {
+        msg!("creating comment page {}", counter_data.page);
+        create_pda_account(
+            commenter,
+            pda_page,
+            system_program,
+            CommentPage::ACCOUNT_SIZE,
+            program_id,
+            &[
+                CommentPage::SEED.as_bytes(),
+                pda_review.key.as_ref(),
+                &page_bytes,
+                &[bump_seed],
+            ],
+        )?;
+        CommentPage {
+            discriminator: CommentPage::DISCRIMINATOR,
+            is_initialized: true,
+            review: *pda_review.key,
+            page: counter_data.page,
+            comments: Vec::with_capacity(CommentPage::COMMENTS_PER_PAGE),
+        }
+    };
+    assert_rent_exempt(pda_page, CommonError::NotRentExempt)?;
+    if page_data.discriminator != CommentPage::DISCRIMINATOR || page_data.is_full() {
+        msg!("Page {} cannot take more comments", counter_data.page);
+        return Err(ReviewError::InvalidCommentPage.into());
+    }
+
+    let mentions = MovieComment::mentions(&comment);
+    page_data.comments.push(PagedComment {
+        commenter: author,
+        index: counter_data.next_comment_index()?,
+        comment,
+    });
+    if page_data.is_full() {
+        counter_data.page = counter_data
+            .page
+            .checked_add(1)
+            .ok_or(CommonError::Overflow)?;
+    }
+    page_data.serialize(&mut &mut pda_page.data.borrow_mut()[..])?;
+    msg!(
+        "comment {} added to page {}",
+        page_data.comments.len() - 1,
+        page_data.page
+    );
+
+    notify_comment(&review, pda_review.key, pda_page.key, &author, mentions);
+    save_counter(
+        pda_review.key,
+        &counter_data,
+        pda_counter,
+        commenter,
+        system_program,
+    )?;
+
+    update_global_stats(
+        program_id,
+        pda_stats,
+        commenter,
+        system_program,
+        GlobalStats::record_comment,
+    )?;
+    record_activity(
+        program_id,
+        pda_feed,
+        commenter,
+        system_program,
+        pda_review.key,
+        HotFeed::COMMENT_WEIGHT,
+    )
+}
+
+/// Loads the comment a reply is for, which must be on `pda_review`.
+fn load_parent_comment(
+    program_id: &Pubkey,
+    pda_review: &AccountInfo,
+    pda_parent: &AccountInfo,
+) -> Result<MovieComment, ProgramError> {
+    assert_owned_by(pda_parent, program_id)?;
+    assert_not_closed(pda_parent, CommonError::AccountClosed)?;
+    upgrade_account(pda_parent)?;
+
+    let parent = try_from_slice_unchecked::<MovieComment>(&pda_parent.data.borrow())?;
+    assert_initialized(&parent, ReviewError::UninitializedAccount)?;
+    if parent.discriminator != MovieComment::DISCRIMINATOR || parent.reviewer != *pda_review.key {
+        msg!("{} is not a comment on {}", pda_parent.key, pda_review.key);
+        return Err(ReviewError::InvalidParentComment.into());
+    }
+    Ok(parent)
+}
+
+/// Upgrades one program account from the v1 layout. Accounts already in the
+/// current layout are left untouched, so this is safe to call repeatedly.
+pub fn migrate_account(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let account = next_account_info(account_info_iter)?;
+
+    assert_owned_by(account, program_id)?;
+    assert_rent_exempt(account, CommonError::NotRentExempt)?;
+
+    if upgrade_account(account)? {
+        msg!("migrated {} to the current layout", account.key);
+    } else {
+        msg!("{} already uses the current layout", account.key);
+    }
+
+    Ok(())
+}
+
+/// Moves a comment created at the little-endian address
+/// `[review, count.to_le_bytes()]` to `[review, comment_seed_bytes(count)]`,
+/// where comment lookups and replies expect it. Permissionless: the payer
+/// funds the new account and receives the old one's rent.
+///
+/// Accounts are `[payer, review, legacy_comment, comment, system_program]`.
+pub fn repair_comment_address(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let payer = next_account_info(account_info_iter)?;
+    let pda_review = next_account_info(account_info_iter)?;
+    let legacy_comment = next_account_info(account_info_iter)?;
     let pda_comment = next_account_info(account_info_iter)?;
     let system_program = next_account_info(account_info_iter)?;
 
-    let mut counter_data =
-        try_from_slice_unchecked::<MovieCommentCounter>(&pda_counter.data.borrow()).unwrap();
+    assert_signer(payer)?;
+    assert_owned_by(legacy_comment, program_id)?;
+    assert_not_closed(legacy_comment, CommonError::AccountClosed)?;
+
+    let count = match decode_account(&legacy_comment.data.borrow())? {
+        MovieReviewAccount::Comment(comment_data) if comment_data.reviewer == *pda_review.key => {
+            comment_data.count
+        }
+        MovieReviewAccount::Comment(_) => {
+            msg!(
+                "{} is not a comment on {}",
+                legacy_comment.key,
+                pda_review.key
+            );
+            return Err(ReviewError::InvalidPDA.into());
+        }
+        _ => {
+            msg!("Account {} is not a comment", legacy_comment.key);
+            return Err(ReviewError::InvalidDiscriminator.into());
+        }
+    };
+
+    let comment_seed = comment_seed_bytes(count);
+    if comment_seed == count.to_le_bytes() {
+        msg!(
+            "Comment {} has the same address in either byte order",
+            count
+        );
+        return Err(ReviewError::CommentNotMisplaced.into());
+    }
+    assert_pda(
+        legacy_comment,
+        &[pda_review.key.as_ref(), &count.to_le_bytes()],
+        program_id,
+        ReviewError::InvalidPDA,
+    )?;
+    let bump_seed = assert_pda(
+        pda_comment,
+        &[pda_review.key.as_ref(), &comment_seed],
+        program_id,
+        ReviewError::InvalidPDA,
+    )?;
+    if pda_comment.owner == program_id {
+        msg!("Comment {} already exists at {}", count, pda_comment.key);
+        return Err(ProgramError::AccountAlreadyInitialized);
+    }
+
+    create_pda_account(
+        payer,
+        pda_comment,
+        system_program,
+        legacy_comment.data_len(),
+        program_id,
+        &[pda_review.key.as_ref(), &comment_seed, &[bump_seed]],
+    )?;
+    pda_comment
+        .data
+        .borrow_mut()
+        .copy_from_slice(&legacy_comment.data.borrow());
+    close_account(legacy_comment, payer)?;
+    msg!("moved comment {} to {}", count, pda_comment.key);
+
+    Ok(())
+}
+
+/// Accounts are `[authority, config, system_program, audit_log]`.
+pub fn initialize_config(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    allow_self_comments: bool,
+    require_reviewer_pass: bool,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let authority = next_account_info(account_info_iter)?;
+    let pda_config = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+    let pda_audit = next_account_info(account_info_iter)?;
+
+    assert_signer(authority)?;
+
+    let bump_seed = assert_pda(
+        pda_config,
+        &[ProgramConfig::SEED.as_bytes()],
+        program_id,
+        ReviewError::InvalidPDA,
+    )?;
+
+    msg!("creating config account");
+    create_pda_account(
+        authority,
+        pda_config,
+        system_program,
+        ProgramConfig::get_account_size(),
+        program_id,
+        &[ProgramConfig::SEED.as_bytes(), &[bump_seed]],
+    )?;
+    assert_rent_exempt(pda_config, CommonError::NotRentExempt)?;
+
+    let config_data = ProgramConfig {
+        is_initialized: true,
+        authority: *authority.key,
+        allow_self_comments,
+        require_reviewer_pass,
+        ..ProgramConfig::default()
+    };
+    config_data.serialize(&mut &mut pda_config.data.borrow_mut()[..])?;
+    msg!("config initialized at: {}", pda_config.key);
+    record_admin_action(
+        program_id,
+        pda_audit,
+        authority,
+        system_program,
+        AdminAction::InitializeConfig,
+        pda_config.key,
+    )?;
+
+    Ok(())
+}
+
+/// Accounts are `[authority, config, system_program, audit_log]`. The
+/// authority pays to grow a config created before its newest fields.
+pub fn update_config(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    allow_self_comments: bool,
+    require_reviewer_pass: bool,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let authority = next_account_info(account_info_iter)?;
+    let pda_config = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+    let pda_audit = next_account_info(account_info_iter)?;
+
+    assert_signer(authority)?;
+
+    let mut config_data = load_config(program_id, pda_config)?;
+    assert_initialized(&config_data, ReviewError::UninitializedAccount)?;
+
+    if config_data.authority != *authority.key {
+        msg!("Signer is not the config authority");
+        return Err(CommonError::Unauthorized.into());
+    }
+
+    config_data.allow_self_comments = allow_self_comments;
+    config_data.require_reviewer_pass = require_reviewer_pass;
+
+    if pda_config.data_len() < ProgramConfig::get_account_size() {
+        resize_account(
+            pda_config,
+            authority,
+            system_program,
+            ProgramConfig::get_account_size(),
+        )?;
+    }
+
+    assert_rent_exempt(pda_config, CommonError::NotRentExempt)?;
+    config_data.serialize(&mut &mut pda_config.data.borrow_mut()[..])?;
+    record_admin_action(
+        program_id,
+        pda_audit,
+        authority,
+        system_program,
+        AdminAction::UpdateConfig,
+        pda_config.key,
+    )?;
+
+    Ok(())
+}
+
+/// Loads the config PDA, or the defaults if it has not been created yet.
+fn load_config(
+    program_id: &Pubkey,
+    pda_config: &AccountInfo,
+) -> Result<ProgramConfig, ProgramError> {
+    assert_pda(
+        pda_config,
+        &[ProgramConfig::SEED.as_bytes()],
+        program_id,
+        ReviewError::InvalidPDA,
+    )?;
+
+    if pda_config.owner != program_id {
+        return Ok(ProgramConfig::default());
+    }
+
+    // Zero-extend configs written before the newest fields were appended
+    let mut data = pda_config.data.borrow().to_vec();
+    data.resize(data.len().max(ProgramConfig::get_account_size()), 0);
+    let config_data = try_from_slice_unchecked::<ProgramConfig>(&data)?;
+    if config_data.discriminator != ProgramConfig::DISCRIMINATOR {
+        msg!("Account is not the program config");
+        return Err(ReviewError::InvalidDiscriminator.into());
+    }
+
+    Ok(config_data)
+}
+
+fn load_movie(program_id: &Pubkey, movie: &AccountInfo) -> Result<Movie, ProgramError> {
+    assert_owned_by(movie, program_id)?;
+
+    // Zero-extend movies registered before the newest fields were appended
+    let mut data = movie.data.borrow().to_vec();
+    data.resize(data.len() + Movie::LEGACY_PADDING_LEN, 0);
+    let movie_data = try_from_slice_unchecked::<Movie>(&data)?;
+    if movie_data.discriminator != Movie::DISCRIMINATOR {
+        msg!("Account is not a registered movie");
+        return Err(ReviewError::InvalidDiscriminator.into());
+    }
+    assert_initialized(&movie_data, ReviewError::UninitializedAccount)?;
+
+    Ok(movie_data)
+}
+
+/// Creates the reviewer pass mint: a Token-2022 mint with the
+/// `NonTransferable` extension, so passes stay with the reviewer they were
+/// issued to. The config PDA is its mint and freeze authority.
+///
+/// Accounts are `[authority, config, pass_mint, token_program,
+/// system_program, audit_log]`.
+pub fn initialize_pass_mint(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let authority = next_account_info(account_info_iter)?;
+    let pda_config = next_account_info(account_info_iter)?;
+    let pass_mint = next_account_info(account_info_iter)?;
+    let token_program = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+    let pda_audit = next_account_info(account_info_iter)?;
+
+    assert_signer(authority)?;
+    assert_program_id(token_program, &spl_token_2022::ID)?;
+    assert_config_authority(program_id, authority, pda_config)?;
+
+    let mint_bump_seed = assert_pda(
+        pass_mint,
+        &[ProgramConfig::PASS_MINT_SEED.as_bytes()],
+        program_id,
+        ReviewError::InvalidPDA,
+    )?;
+
+    let space =
+        ExtensionType::try_calculate_account_len::<Mint>(&[ExtensionType::NonTransferable])?;
+    msg!("creating reviewer pass mint");
+    create_pda_account(
+        authority,
+        pass_mint,
+        system_program,
+        space,
+        token_program.key,
+        &[ProgramConfig::PASS_MINT_SEED.as_bytes(), &[mint_bump_seed]],
+    )?;
+
+    // Extensions must be initialized before the mint itself
+    invoke(
+        &spl_token_2022::instruction::initialize_non_transferable_mint(
+            token_program.key,
+            pass_mint.key,
+        )?,
+        std::slice::from_ref(pass_mint),
+    )?;
+    invoke(
+        &spl_token_2022::instruction::initialize_mint2(
+            token_program.key,
+            pass_mint.key,
+            pda_config.key,
+            Some(pda_config.key),
+            0,
+        )?,
+        std::slice::from_ref(pass_mint),
+    )?;
+    msg!("reviewer pass mint created at: {}", pass_mint.key);
+    record_admin_action(
+        program_id,
+        pda_audit,
+        authority,
+        system_program,
+        AdminAction::InitializePassMint,
+        pass_mint.key,
+    )?;
+
+    Ok(())
+}
+
+/// Mints one pass into the reviewer's Token-2022 associated token account,
+/// creating it if needed. Verifying the reviewer happens off-chain; the config
+/// authority signing is the attestation.
+///
+/// Accounts are `[authority, config, pass_mint, reviewer, reviewer_pass,
+/// token_program, associated_token_program, system_program, audit_log]`.
+pub fn issue_reviewer_pass(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let authority = next_account_info(account_info_iter)?;
+    let pda_config = next_account_info(account_info_iter)?;
+    let pass_mint = next_account_info(account_info_iter)?;
+    let reviewer = next_account_info(account_info_iter)?;
+    let reviewer_pass = next_account_info(account_info_iter)?;
+    let token_program = next_account_info(account_info_iter)?;
+    let associated_token_program = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+    let pda_audit = next_account_info(account_info_iter)?;
+
+    assert_signer(authority)?;
+    assert_program_id(token_program, &spl_token_2022::ID)?;
+    assert_program_id(associated_token_program, &spl_associated_token_account::ID)?;
+    let config_bump_seed = assert_config_authority(program_id, authority, pda_config)?;
+
+    assert_pda(
+        pass_mint,
+        &[ProgramConfig::PASS_MINT_SEED.as_bytes()],
+        program_id,
+        ReviewError::InvalidPDA,
+    )?;
+
+    msg!("creating reviewer pass account");
+    invoke(
+        &create_associated_token_account_idempotent(
+            authority.key,
+            reviewer.key,
+            pass_mint.key,
+            token_program.key,
+        ),
+        &[
+            authority.clone(),
+            reviewer_pass.clone(),
+            reviewer.clone(),
+            pass_mint.clone(),
+            system_program.clone(),
+            token_program.clone(),
+            associated_token_program.clone(),
+        ],
+    )?;
+
+    let pass_amount = StateWithExtensions::<TokenAccount>::unpack(&reviewer_pass.data.borrow())?
+        .base
+        .amount;
+    if pass_amount > 0 {
+        msg!("Reviewer already holds a pass");
+        return Err(ReviewError::PassAlreadyIssued.into());
+    }
+
+    msg!("minting reviewer pass to {}", reviewer.key);
+    invoke_signed(
+        &spl_token_2022::instruction::mint_to(
+            token_program.key,
+            pass_mint.key,
+            reviewer_pass.key,
+            pda_config.key,
+            &[],
+            1,
+        )?,
+        &[pass_mint.clone(), reviewer_pass.clone(), pda_config.clone()],
+        &[&[ProgramConfig::SEED.as_bytes(), &[config_bump_seed]]],
+    )?;
+    record_admin_action(
+        program_id,
+        pda_audit,
+        authority,
+        system_program,
+        AdminAction::IssueReviewerPass,
+        reviewer.key,
+    )?;
+
+    Ok(())
+}
+
+/// Checks `authority` signs for an initialized config and returns the config
+/// PDA's bump.
+fn assert_config_authority(
+    program_id: &Pubkey,
+    authority: &AccountInfo,
+    pda_config: &AccountInfo,
+) -> Result<u8, ProgramError> {
+    let config_data = load_config(program_id, pda_config)?;
+    assert_initialized(&config_data, ReviewError::UninitializedAccount)?;
+
+    if config_data.authority != *authority.key {
+        msg!("Signer is not the config authority");
+        return Err(CommonError::Unauthorized.into());
+    }
+
+    assert_pda(
+        pda_config,
+        &[ProgramConfig::SEED.as_bytes()],
+        program_id,
+        ReviewError::InvalidPDA,
+    )
+}
+
+/// Appends `action` on `target` by `authority` to the audit log at
+/// `["audit_log"]`, creating it at the authority's expense on first use, and
+/// emits it.
+fn record_admin_action<'a>(
+    program_id: &Pubkey,
+    pda_audit: &AccountInfo<'a>,
+    authority: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    action: AdminAction,
+    target: &Pubkey,
+) -> ProgramResult {
+    let bump_seed = assert_pda(
+        pda_audit,
+        &[AuditLog::SEED.as_bytes()],
+        program_id,
+        ReviewError::InvalidPDA,
+    )?;
+
+    let mut audit_data = if pda_audit.owner == program_id {
+        let audit_data = try_from_slice_unchecked::<AuditLog>(&pda_audit.data.borrow())?;
+        if audit_data.discriminator != AuditLog::DISCRIMINATOR {
+            msg!("Account is not the audit log");
+            return Err(ReviewError::InvalidDiscriminator.into());
+        }
+        audit_data
+    } else {
+        msg!("creating audit log account");
+        create_pda_account(
+            authority,
+            pda_audit,
+            system_program,
+            AuditLog::get_account_size(),
+            program_id,
+            &[AuditLog::SEED.as_bytes(), &[bump_seed]],
+        )?;
+        AuditLog {
+            is_initialized: true,
+            ..AuditLog::default()
+        }
+    };
+
+    let sequence = audit_data.push(AuditEntry {
+        sequence: 0,
+        slot: Clock::get()?.slot,
+        authority: *authority.key,
+        action,
+        target: *target,
+    });
+    audit_data.serialize(&mut &mut pda_audit.data.borrow_mut()[..])?;
+    emit(&ReviewEvent::AdminAction {
+        sequence,
+        authority: *authority.key,
+        action,
+        target: *target,
+    });
+    Ok(())
+}
+
+/// Enforces the config's posting rules for `poster` and returns the config.
+/// When passes are required, the first of `proofs` must be a Token-2022
+/// account of the pass mint owned by `poster` and holding a pass. When a
+/// minimum wallet age is set, one of `proofs` must date the wallet back
+/// that far, see `wallet_first_seen`.
+fn assert_may_post(
+    program_id: &Pubkey,
+    poster: &AccountInfo,
+    pda_config: &AccountInfo,
+    proofs: &[AccountInfo],
+) -> Result<ProgramConfig, ProgramError> {
+    let config = load_config(program_id, pda_config)?;
+    if config.require_reviewer_pass {
+        let Some(pass) = proofs.first() else {
+            msg!("Posting requires a reviewer pass");
+            return Err(ReviewError::MissingReviewerPass.into());
+        };
+        assert_owned_by(pass, &spl_token_2022::ID)?;
+
+        let (pass_mint, _) =
+            Pubkey::find_program_address(&[ProgramConfig::PASS_MINT_SEED.as_bytes()], program_id);
+        let pass_data = StateWithExtensions::<TokenAccount>::unpack(&pass.data.borrow())?.base;
+        if pass_data.mint != pass_mint || pass_data.owner != *poster.key || pass_data.amount == 0 {
+            msg!("Account is not a reviewer pass held by the signer");
+            return Err(ReviewError::MissingReviewerPass.into());
+        }
+    }
+    if config.min_wallet_age > 0 {
+        assert_wallet_age(&config, poster, proofs)?;
+    }
+
+    Ok(config)
+}
+
+/// Requires the earliest slot any of `proofs` dates `poster` back to to be
+/// at least `min_wallet_age` slots ago.
+fn assert_wallet_age(
+    config: &ProgramConfig,
+    poster: &AccountInfo,
+    proofs: &[AccountInfo],
+) -> ProgramResult {
+    let mut first_seen = None;
+    for proof in proofs {
+        if let Some(slot) = wallet_first_seen(config, poster, proof)? {
+            first_seen = Some(first_seen.map_or(slot, |earliest: u64| earliest.min(slot)));
+        }
+    }
+    let Some(first_seen) = first_seen else {
+        msg!("Posting requires proof of the wallet's age");
+        return Err(ReviewError::WalletTooNew.into());
+    };
+
+    let age = Clock::get()?.slot.saturating_sub(first_seen);
+    if age < config.min_wallet_age {
+        msg!(
+            "Wallet is {} slots old, {} required",
+            age,
+            config.min_wallet_age
+        );
+        return Err(ReviewError::WalletTooNew.into());
+    }
+    Ok(())
+}
+
+/// The slot `proof` dates `poster` back to, if it is a `WalletAgeAttestation`
+/// of the configured attester for `poster`, or a delegated stake account
+/// `poster` is the withdraw authority of, dated from its activation epoch.
+fn wallet_first_seen(
+    config: &ProgramConfig,
+    poster: &AccountInfo,
+    proof: &AccountInfo,
+) -> Result<Option<u64>, ProgramError> {
+    if config.age_attester != Pubkey::default() && *proof.owner == config.age_attester {
+        let Ok(attestation) =
+            try_from_slice_unchecked::<WalletAgeAttestation>(&proof.data.borrow())
+        else {
+            return Ok(None);
+        };
+        return Ok((attestation.wallet == *poster.key).then_some(attestation.first_seen_slot));
+    }
+
+    if *proof.owner == stake::program::ID {
+        let Ok(StakeStateV2::Stake(meta, delegated, _)) =
+            try_from_slice_unchecked::<StakeStateV2>(&proof.data.borrow())
+        else {
+            return Ok(None);
+        };
+        if meta.authorized.withdrawer != *poster.key {
+            return Ok(None);
+        }
+        // Bootstrap stakes activate at `u64::MAX`, which no slot maps to
+        let activation_epoch = delegated.delegation.activation_epoch;
+        if activation_epoch > Clock::get()?.epoch {
+            return Ok(None);
+        }
+        let epoch_schedule = EpochSchedule::get()?;
+        return Ok(Some(
+            epoch_schedule.get_first_slot_in_epoch(activation_epoch),
+        ));
+    }
+
+    Ok(None)
+}
+
+/// Whether `proof` is a token account of `payer` holding a reviewer pass or
+/// at least the configured balance of the fee exemption mint.
+/// Reads `proof` as a token account of either token program owned by
+/// `owner`.
+fn owned_token_account(owner: &Pubkey, proof: &AccountInfo) -> Option<TokenAccount> {
+    if proof.owner != &spl_token::ID && proof.owner != &spl_token_2022::ID {
+        return None;
+    }
+    let token_account = StateWithExtensions::<TokenAccount>::unpack(&proof.data.borrow())
+        .map(|state| state.base)
+        .ok()?;
+    (token_account.owner == *owner).then_some(token_account)
+}
+
+fn proves_fee_exemption(
+    program_id: &Pubkey,
+    config: &ProgramConfig,
+    payer: &AccountInfo,
+    proof: &AccountInfo,
+) -> Result<bool, ProgramError> {
+    let Some(token_account) = owned_token_account(payer.key, proof) else {
+        return Ok(false);
+    };
+
+    let (pass_mint, _) =
+        Pubkey::find_program_address(&[ProgramConfig::PASS_MINT_SEED.as_bytes()], program_id);
+    let holds_pass = proof.owner == &spl_token_2022::ID
+        && token_account.mint == pass_mint
+        && token_account.amount > 0;
+    let holds_tokens = config.fee_exempt_mint != Pubkey::default()
+        && token_account.mint == config.fee_exempt_mint
+        && token_account.amount >= config.fee_exempt_min_balance;
+    Ok(holds_pass || holds_tokens)
+}
+
+/// Whether any of `proofs` shows `commenter` may comment under `policy` on a
+/// review by `reviewer`: their `Follow` of the reviewer, or a token account
+/// of theirs holding the policy's mint.
+fn meets_comment_policy(
+    program_id: &Pubkey,
+    policy: &CommentPolicy,
+    reviewer: &Pubkey,
+    commenter: &Pubkey,
+    proofs: &[AccountInfo],
+) -> bool {
+    match policy {
+        CommentPolicy::Anyone => true,
+        CommentPolicy::FollowersOnly => {
+            let (follow, _) = Pubkey::find_program_address(
+                &[
+                    Follow::SEED.as_bytes(),
+                    commenter.as_ref(),
+                    reviewer.as_ref(),
+                ],
+                program_id,
+            );
+            proofs.iter().any(|proof| {
+                proof.key == &follow
+                    && proof.owner == program_id
+                    && try_from_slice_unchecked::<Follow>(&proof.data.borrow())
+                        .is_ok_and(|f| f.discriminator == Follow::DISCRIMINATOR && f.is_initialized)
+            })
+        }
+        CommentPolicy::TokenHoldersOnly { mint } => proofs.iter().any(|proof| {
+            owned_token_account(commenter, proof).is_some_and(|token_account| {
+                token_account.mint == *mint && token_account.amount > 0
+            })
+        }),
+    }
+}
+
+/// Charges `payer` the config's fee for `kind` into the config PDA, where the
+/// config authority can withdraw it. Waived if any of `proofs` proves a fee
+/// exemption.
+fn charge_fee<'a>(
+    program_id: &Pubkey,
+    config: &ProgramConfig,
+    kind: FeeKind,
+    payer: &AccountInfo<'a>,
+    pda_config: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    proofs: &[AccountInfo<'a>],
+) -> ProgramResult {
+    let amount = fee_due(program_id, config, kind, payer, proofs)?;
+    if amount == 0 {
+        return Ok(());
+    }
+
+    invoke(
+        &system_instruction::transfer(payer.key, pda_config.key, amount),
+        &[payer.clone(), pda_config.clone(), system_program.clone()],
+    )?;
+    emit(&ReviewEvent::FeeCharged {
+        payer: *payer.key,
+        kind,
+        amount,
+    });
+
+    Ok(())
+}
+
+/// The config's fee for `kind`, or zero if any of `proofs` proves `payer` is
+/// exempt.
+fn fee_due(
+    program_id: &Pubkey,
+    config: &ProgramConfig,
+    kind: FeeKind,
+    payer: &AccountInfo,
+    proofs: &[AccountInfo],
+) -> Result<u64, ProgramError> {
+    let amount = config.fees.fee(kind);
+    if amount == 0 {
+        return Ok(0);
+    }
+    for proof in proofs {
+        if proves_fee_exemption(program_id, config, payer, proof)? {
+            msg!("{:?} fee waived for {}", kind, payer.key);
+            return Ok(0);
+        }
+    }
+    Ok(amount)
+}
+
+/// Lamports `create_pda_account` moves into `account` to create it with
+/// `space` bytes, less whatever it already holds.
+fn creation_rent(account: &AccountInfo, space: usize) -> Result<u64, ProgramError> {
+    Ok(rent_exempt_lamports(space)?.saturating_sub(account.lamports()))
+}
+
+/// `creation_rent` of an account created on first use, zero once it exists.
+fn first_use_rent(
+    program_id: &Pubkey,
+    account: &AccountInfo,
+    space: usize,
+) -> Result<u64, ProgramError> {
+    if account.owner == program_id {
+        return Ok(0);
+    }
+    creation_rent(account, space)
+}
+
+/// Ends a dry run, returning `preview` to the caller.
+fn return_costs(preview: CostPreview) -> ProgramResult {
+    msg!("dry run: fee {}, rent {}", preview.fee, preview.rent);
+    set_return_data(&borsh::to_vec(&preview)?);
+    Ok(())
+}
+
+/// Longest name Token Metadata accepts.
+const MAX_NFT_NAME_LEN: usize = 32;
+
+/// Accounts of an NFT minted by the collection authority PDA.
+struct NftAccounts<'a, 'b> {
+    mint: &'b AccountInfo<'a>,
+    owner: &'b AccountInfo<'a>,
+    owner_token: &'b AccountInfo<'a>,
+    metadata: &'b AccountInfo<'a>,
+    master_edition: &'b AccountInfo<'a>,
+}
+
+struct NftPrograms<'a, 'b> {
+    token: &'b AccountInfo<'a>,
+    associated_token: &'b AccountInfo<'a>,
+    token_metadata: &'b AccountInfo<'a>,
+    system: &'b AccountInfo<'a>,
+}
+
+impl NftPrograms<'_, '_> {
+    fn assert_ids(&self) -> ProgramResult {
+        assert_program_id(self.token, &spl_token::ID)?;
+        assert_program_id(self.associated_token, &spl_associated_token_account::ID)?;
+        assert_program_id(self.token_metadata, &mpl_token_metadata::ID)
+    }
+}
+
+/// Creates a one-of-one SPL Token NFT at the `mint` PDA, mints it to the
+/// owner's associated token account and adds its metadata and master
+/// edition. The collection authority PDA is mint and update authority.
+#[allow(clippy::too_many_arguments)]
+fn mint_nft<'a>(
+    payer: &AccountInfo<'a>,
+    collection_authority: &AccountInfo<'a>,
+    nft: &NftAccounts<'a, '_>,
+    programs: &NftPrograms<'a, '_>,
+    mint_seeds: &[&[u8]],
+    authority_seeds: &[&[u8]],
+    data: DataV2,
+    collection_details: Option<CollectionDetails>,
+) -> ProgramResult {
+    create_pda_account(
+        payer,
+        nft.mint,
+        programs.system,
+        spl_token::state::Mint::LEN,
+        &spl_token::ID,
+        mint_seeds,
+    )?;
+    invoke(
+        &spl_token::instruction::initialize_mint2(
+            &spl_token::ID,
+            nft.mint.key,
+            collection_authority.key,
+            Some(collection_authority.key),
+            0,
+        )?,
+        std::slice::from_ref(nft.mint),
+    )?;
+
+    invoke(
+        &create_associated_token_account_idempotent(
+            payer.key,
+            nft.owner.key,
+            nft.mint.key,
+            &spl_token::ID,
+        ),
+        &[
+            payer.clone(),
+            nft.owner_token.clone(),
+            nft.owner.clone(),
+            nft.mint.clone(),
+            programs.system.clone(),
+            programs.token.clone(),
+            programs.associated_token.clone(),
+        ],
+    )?;
+    invoke_signed(
+        &spl_token::instruction::mint_to(
+            &spl_token::ID,
+            nft.mint.key,
+            nft.owner_token.key,
+            collection_authority.key,
+            &[],
+            1,
+        )?,
+        &[
+            nft.mint.clone(),
+            nft.owner_token.clone(),
+            collection_authority.clone(),
+        ],
+        &[authority_seeds],
+    )?;
+
+    let mut create_metadata = CreateMetadataAccountV3CpiBuilder::new(programs.token_metadata);
+    create_metadata
+        .metadata(nft.metadata)
+        .mint(nft.mint)
+        .mint_authority(collection_authority)
+        .payer(payer)
+        .update_authority(collection_authority, true)
+        .system_program(programs.system)
+        .data(data)
+        .is_mutable(true);
+    if let Some(collection_details) = collection_details {
+        create_metadata.collection_details(collection_details);
+    }
+    create_metadata.invoke_signed(&[authority_seeds])?;
+
+    // Supply 0 makes it a one-of-one: the master edition takes over the mint
+    // authority, so no second token can ever be minted
+    CreateMasterEditionV3CpiBuilder::new(programs.token_metadata)
+        .edition(nft.master_edition)
+        .mint(nft.mint)
+        .update_authority(collection_authority)
+        .mint_authority(collection_authority)
+        .payer(payer)
+        .metadata(nft.metadata)
+        .token_program(programs.token)
+        .system_program(programs.system)
+        .max_supply(0)
+        .invoke_signed(&[authority_seeds])
+}
+
+/// Creates the next sized collection, held by the collection authority PDA,
+/// and makes it the one review NFTs are verified into.
+///
+/// Accounts are `[authority, config, collection_authority, collection_mint,
+/// collection_token, collection_metadata, collection_master_edition,
+/// token_program, associated_token_program, token_metadata_program,
+/// system_program, audit_log]`.
+pub fn rotate_review_collection(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    name: String,
+    symbol: String,
+    uri: String,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let authority = next_account_info(account_info_iter)?;
+    let pda_config = next_account_info(account_info_iter)?;
+    let collection_authority = next_account_info(account_info_iter)?;
+    let collection_mint = next_account_info(account_info_iter)?;
+    let collection_token = next_account_info(account_info_iter)?;
+    let collection_metadata = next_account_info(account_info_iter)?;
+    let collection_master_edition = next_account_info(account_info_iter)?;
+    let programs = NftPrograms {
+        token: next_account_info(account_info_iter)?,
+        associated_token: next_account_info(account_info_iter)?,
+        token_metadata: next_account_info(account_info_iter)?,
+        system: next_account_info(account_info_iter)?,
+    };
+    let pda_audit = next_account_info(account_info_iter)?;
+
+    assert_signer(authority)?;
+    programs.assert_ids()?;
+
+    let mut config_data = load_config(program_id, pda_config)?;
+    assert_initialized(&config_data, ReviewError::UninitializedAccount)?;
+
+    if config_data.authority != *authority.key {
+        msg!("Signer is not the config authority");
+        return Err(CommonError::Unauthorized.into());
+    }
+
+    let authority_bump_seed = assert_pda(
+        collection_authority,
+        &[ProgramConfig::COLLECTION_AUTHORITY_SEED.as_bytes()],
+        program_id,
+        ReviewError::InvalidPDA,
+    )?;
+    let generation = config_data.collection_generation.to_le_bytes();
+    let mint_bump_seed = assert_pda(
+        collection_mint,
+        &[ProgramConfig::COLLECTION_SEED.as_bytes(), &generation],
+        program_id,
+        ReviewError::InvalidPDA,
+    )?;
+
+    msg!("creating review collection {}", collection_mint.key);
+    mint_nft(
+        authority,
+        collection_authority,
+        &NftAccounts {
+            mint: collection_mint,
+            owner: collection_authority,
+            owner_token: collection_token,
+            metadata: collection_metadata,
+            master_edition: collection_master_edition,
+        },
+        &programs,
+        &[
+            ProgramConfig::COLLECTION_SEED.as_bytes(),
+            &generation,
+            &[mint_bump_seed],
+        ],
+        &[
+            ProgramConfig::COLLECTION_AUTHORITY_SEED.as_bytes(),
+            &[authority_bump_seed],
+        ],
+        DataV2 {
+            name,
+            symbol,
+            uri,
+            seller_fee_basis_points: 0,
+            creators: None,
+            collection: None,
+            uses: None,
+        },
+        // A sized collection tracks its item count on-chain, and its items
+        // must be verified with the sized instructions
+        Some(CollectionDetails::V1 { size: 0 }),
+    )?;
+
+    config_data.review_collection = *collection_mint.key;
+    config_data.collection_generation = config_data
+        .collection_generation
+        .checked_add(1)
+        .ok_or(CommonError::Overflow)?;
+
+    if pda_config.data_len() < ProgramConfig::get_account_size() {
+        resize_account(
+            pda_config,
+            authority,
+            programs.system,
+            ProgramConfig::get_account_size(),
+        )?;
+    }
+
+    assert_rent_exempt(pda_config, CommonError::NotRentExempt)?;
+    config_data.serialize(&mut &mut pda_config.data.borrow_mut()[..])?;
+    record_admin_action(
+        program_id,
+        pda_audit,
+        authority,
+        programs.system,
+        AdminAction::RotateReviewCollection,
+        collection_mint.key,
+    )?;
+
+    Ok(())
+}
+
+/// Truncates `title` to a valid NFT name without splitting a character.
+fn nft_name(title: &str) -> String {
+    let mut end = title.len().min(MAX_NFT_NAME_LEN);
+    while !title.is_char_boundary(end) {
+        end -= 1;
+    }
+    title[..end].to_string()
+}
+
+/// Mints the author of `review` a one-of-one NFT of it and verifies it into
+/// the current review collection. Each review can be minted once.
+///
+/// Accounts are `[reviewer, review, config, collection_authority, nft_mint,
+/// reviewer_nft_token, metadata, master_edition, collection_mint,
+/// collection_metadata, collection_master_edition, token_program,
+/// associated_token_program, token_metadata_program, system_program]`.
+pub fn mint_review_nft(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    uri: String,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let reviewer = next_account_info(account_info_iter)?;
+    let pda_review = next_account_info(account_info_iter)?;
+    let pda_config = next_account_info(account_info_iter)?;
+    let collection_authority = next_account_info(account_info_iter)?;
+    let nft_mint = next_account_info(account_info_iter)?;
+    let reviewer_nft_token = next_account_info(account_info_iter)?;
+    let metadata = next_account_info(account_info_iter)?;
+    let master_edition = next_account_info(account_info_iter)?;
+    let collection_mint = next_account_info(account_info_iter)?;
+    let collection_metadata = next_account_info(account_info_iter)?;
+    let collection_master_edition = next_account_info(account_info_iter)?;
+    let programs = NftPrograms {
+        token: next_account_info(account_info_iter)?,
+        associated_token: next_account_info(account_info_iter)?,
+        token_metadata: next_account_info(account_info_iter)?,
+        system: next_account_info(account_info_iter)?,
+    };
+
+    assert_signer(reviewer)?;
+    programs.assert_ids()?;
+    assert_owned_by(pda_review, program_id)?;
+
+    // The review is read-only here, so earlier layouts are decoded rather
+    // than upgraded
+    let review_data = match decode_account(&pda_review.data.borrow())? {
+        MovieReviewAccount::Review(review_data)
+            if review_data.discriminator == MovieAccountState::DISCRIMINATOR =>
+        {
+            review_data
+        }
+        _ => {
+            msg!("Account is not an attributed review");
+            return Err(ReviewError::InvalidDiscriminator.into());
+        }
+    };
+    assert_initialized(&review_data, ReviewError::UninitializedAccount)?;
+
+    if review_data.reviewer != *reviewer.key {
+        msg!("Signer is not the author of the review");
+        return Err(CommonError::Unauthorized.into());
+    }
+
+    let config_data = load_config(program_id, pda_config)?;
+    if config_data.review_collection == Pubkey::default()
+        || config_data.review_collection != *collection_mint.key
+    {
+        msg!("Review collection missing or not the current one");
+        return Err(ReviewError::InvalidReviewCollection.into());
+    }
+
+    let authority_bump_seed = assert_pda(
+        collection_authority,
+        &[ProgramConfig::COLLECTION_AUTHORITY_SEED.as_bytes()],
+        program_id,
+        ReviewError::InvalidPDA,
+    )?;
+    let mint_bump_seed = assert_pda(
+        nft_mint,
+        &[
+            ProgramConfig::REVIEW_NFT_SEED.as_bytes(),
+            pda_review.key.as_ref(),
+        ],
+        program_id,
+        ReviewError::InvalidPDA,
+    )?;
+    let authority_seeds: &[&[u8]] = &[
+        ProgramConfig::COLLECTION_AUTHORITY_SEED.as_bytes(),
+        &[authority_bump_seed],
+    ];
+
+    msg!("minting review NFT {}", nft_mint.key);
+    mint_nft(
+        reviewer,
+        collection_authority,
+        &NftAccounts {
+            mint: nft_mint,
+            owner: reviewer,
+            owner_token: reviewer_nft_token,
+            metadata,
+            master_edition,
+        },
+        &programs,
+        &[
+            ProgramConfig::REVIEW_NFT_SEED.as_bytes(),
+            pda_review.key.as_ref(),
+            &[mint_bump_seed],
+        ],
+        authority_seeds,
+        DataV2 {
+            name: nft_name(&review_data.title),
+            symbol: ProgramConfig::REVIEW_NFT_SYMBOL.to_string(),
+            uri,
+            seller_fee_basis_points: 0,
+            creators: None,
+            collection: Some(Collection {
+                verified: false,
+                key: *collection_mint.key,
+            }),
+            uses: None,
+        },
+        None,
+    )?;
+
+    // The collection authority PDA is the collection's update authority, so
+    // it can verify without a delegate record
+    VerifySizedCollectionItemCpiBuilder::new(programs.token_metadata)
+        .metadata(metadata)
+        .collection_authority(collection_authority)
+        .payer(reviewer)
+        .collection_mint(collection_mint)
+        .collection(collection_metadata)
+        .collection_master_edition_account(collection_master_edition)
+        .invoke_signed(&[authority_seeds])
+}
+
+/// Accounts are `[authority, config, system_program, audit_log]`. The
+/// authority pays to grow a config created before the oracle fields.
+pub fn set_release_oracle(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    oracle: Pubkey,
+    feed_id: [u8; 32],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let authority = next_account_info(account_info_iter)?;
+    let pda_config = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+    let pda_audit = next_account_info(account_info_iter)?;
+
+    assert_signer(authority)?;
+
+    let mut config_data = load_config(program_id, pda_config)?;
+    assert_initialized(&config_data, ReviewError::UninitializedAccount)?;
+
+    if config_data.authority != *authority.key {
+        msg!("Signer is not the config authority");
+        return Err(CommonError::Unauthorized.into());
+    }
+
+    config_data.release_oracle = oracle;
+    config_data.release_feed_id = feed_id;
+
+    if pda_config.data_len() < ProgramConfig::get_account_size() {
+        resize_account(
+            pda_config,
+            authority,
+            system_program,
+            ProgramConfig::get_account_size(),
+        )?;
+    }
+
+    assert_rent_exempt(pda_config, CommonError::NotRentExempt)?;
+    config_data.serialize(&mut &mut pda_config.data.borrow_mut()[..])?;
+    record_admin_action(
+        program_id,
+        pda_audit,
+        authority,
+        system_program,
+        AdminAction::SetReleaseOracle,
+        pda_config.key,
+    )?;
+    msg!("release oracle set to {}", oracle);
+
+    Ok(())
+}
+
+/// Marks `movie` as a verified theatrical release. Anyone may call it: the
+/// attestation is trusted because of its owner and feed ID, not the signer,
+/// who only pays to grow movies registered before the release fields.
+///
+/// Accounts are `[payer, movie, config, attestation, system_program]`.
+pub fn mark_verified_release(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let payer = next_account_info(account_info_iter)?;
+    let pda_movie = next_account_info(account_info_iter)?;
+    let pda_config = next_account_info(account_info_iter)?;
+    let attestation = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+
+    assert_signer(payer)?;
+
+    let mut movie_data = load_movie(program_id, pda_movie)?;
+    let config_data = load_config(program_id, pda_config)?;
+
+    // Anyone can write an account in the attestation layout; only the
+    // configured oracle's feed is trusted
+    if config_data.release_oracle == Pubkey::default()
+        || attestation.owner != &config_data.release_oracle
+    {
+        msg!("Attestation is not owned by the release oracle");
+        return Err(ReviewError::InvalidReleaseAttestation.into());
+    }
+    let attestation_data =
+        try_from_slice_unchecked::<ReleaseAttestation>(&attestation.data.borrow())?;
+    if attestation_data.feed_id != config_data.release_feed_id {
+        msg!("Attestation is not from the configured feed");
+        return Err(ReviewError::InvalidReleaseAttestation.into());
+    }
+    if attestation_data.title_hash != Movie::title_hash(&movie_data.title) {
+        msg!("Attestation is for a different movie");
+        return Err(ReviewError::InvalidReleaseAttestation.into());
+    }
+    if attestation_data.release_date > Clock::get()?.unix_timestamp {
+        msg!("Attested release date is in the future");
+        return Err(ReviewError::InvalidReleaseAttestation.into());
+    }
+
+    movie_data.verified_release = true;
+    movie_data.release_date = attestation_data.release_date;
+
+    let account_size = Movie::get_account_size(&movie_data.title, &movie_data.external_id);
+    if pda_movie.data_len() < account_size {
+        resize_account(pda_movie, payer, system_program, account_size)?;
+    }
+
+    assert_rent_exempt(pda_movie, CommonError::NotRentExempt)?;
+    movie_data.serialize(&mut &mut pda_movie.data.borrow_mut()[..])?;
+    msg!("{} verified as released", pda_movie.key);
+
+    Ok(())
+}
+
+/// Loads a VRF randomness account, which must be owned by the configured VRF
+/// program.
+fn load_randomness(
+    config_data: &ProgramConfig,
+    randomness: &AccountInfo,
+) -> Result<VrfRandomness, ProgramError> {
+    if config_data.vrf_program == Pubkey::default() || randomness.owner != &config_data.vrf_program
+    {
+        msg!("Randomness account is not owned by the VRF program");
+        return Err(ReviewError::InvalidRandomness.into());
+    }
+    let data = randomness.data.borrow();
+    let fields = data
+        .get(VrfRandomness::ACCOUNT_DISCRIMINATOR_LEN..)
+        .ok_or(ProgramError::InvalidAccountData)?;
+    Ok(VrfRandomness::deserialize(&mut &fields[..])?)
+}
+
+/// Requests today's featured review draw among `candidates`, the current
+/// leaderboard page. The draw is committed to `randomness`, a VRF request
+/// the caller made beforehand that must not be fulfilled yet, so nobody
+/// knows the winner when the candidates are fixed.
+///
+/// Accounts are
+/// `[payer, featured, config, randomness, system_program, candidates...]`.
+pub fn request_featured_draw(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let payer = next_account_info(account_info_iter)?;
+    let pda_featured = next_account_info(account_info_iter)?;
+    let pda_config = next_account_info(account_info_iter)?;
+    let randomness = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+    let candidates = account_info_iter.as_slice();
+
+    assert_signer(payer)?;
+
+    if candidates.is_empty() {
+        msg!("No candidate reviews passed");
+        return Err(ReviewError::NoFeaturedCandidates.into());
+    }
+    for candidate in candidates {
+        assert_owned_by(candidate, program_id)?;
+        let header = load_review_header(&candidate.data.borrow())?;
+        assert_initialized(&header, ReviewError::UninitializedAccount)?;
+    }
+
+    let config_data = load_config(program_id, pda_config)?;
+    if load_randomness(&config_data, randomness)?.is_fulfilled() {
+        msg!("Randomness is already known");
+        return Err(ReviewError::InvalidRandomness.into());
+    }
+
+    let bump_seed = assert_pda(
+        pda_featured,
+        &[FeaturedReview::SEED.as_bytes()],
+        program_id,
+        ReviewError::InvalidPDA,
+    )?;
+
+    let day = Clock::get()?.unix_timestamp / FeaturedReview::SECONDS_PER_DAY;
+
+    if pda_featured.owner != program_id {
+        msg!("creating featured review account");
+        create_pda_account(
+            payer,
+            pda_featured,
+            system_program,
+            FeaturedReview::get_account_size(),
+            program_id,
+            &[FeaturedReview::SEED.as_bytes(), &[bump_seed]],
+        )?;
+    }
+
+    let mut featured_data =
+        try_from_slice_unchecked::<FeaturedReview>(&pda_featured.data.borrow())?;
+    if featured_data.is_initialized && featured_data.day >= day {
+        msg!("Featured review already drawn for day {}", day);
+        return Err(ReviewError::FeaturedAlreadyDrawn.into());
+    }
+
+    let candidate_keys: Vec<&Pubkey> = candidates.iter().map(|candidate| candidate.key).collect();
+
+    featured_data.discriminator = FeaturedReview::DISCRIMINATOR;
+    featured_data.is_initialized = true;
+    featured_data.day = day;
+    featured_data.pending = true;
+    featured_data.randomness = *randomness.key;
+    featured_data.candidates_hash = FeaturedReview::candidates_hash(&candidate_keys);
+
+    assert_rent_exempt(pda_featured, CommonError::NotRentExempt)?;
+    featured_data.serialize(&mut &mut pda_featured.data.borrow_mut()[..])?;
+    msg!("featured review draw requested for day {}", day);
+
+    // Escrow the bounty for whoever cranks the fulfillment
+    invoke(
+        &system_instruction::transfer(payer.key, pda_featured.key, FeaturedReview::CRANK_BOUNTY),
+        &[payer.clone(), pda_featured.clone(), system_program.clone()],
+    )?;
+
+    Ok(())
+}
+
+/// Settles the pending featured review draw with its fulfilled randomness.
+/// The candidates must be passed again, in the order they were requested.
+///
+/// A permissionless crank: it only succeeds once per request, and pays the
+/// signer the bounties escrowed in the featured PDA.
+///
+/// Accounts are `[cranker, featured, config, randomness, candidates...]`.
+pub fn fulfill_featured_draw(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let cranker = next_account_info(account_info_iter)?;
+    let pda_featured = next_account_info(account_info_iter)?;
+    let pda_config = next_account_info(account_info_iter)?;
+    let randomness = next_account_info(account_info_iter)?;
+    let candidates = account_info_iter.as_slice();
+
+    assert_signer(cranker)?;
+    assert_owned_by(pda_featured, program_id)?;
+    let mut featured_data =
+        try_from_slice_unchecked::<FeaturedReview>(&pda_featured.data.borrow())?;
+    if featured_data.discriminator != FeaturedReview::DISCRIMINATOR {
+        msg!("Account is not the featured review");
+        return Err(ReviewError::InvalidDiscriminator.into());
+    }
+    if !featured_data.pending {
+        msg!("No featured review draw is pending");
+        return Err(ReviewError::NoPendingDraw.into());
+    }
+
+    if *randomness.key != featured_data.randomness {
+        msg!("Randomness account is not the one requested");
+        return Err(ReviewError::InvalidRandomness.into());
+    }
+    let candidate_keys: Vec<&Pubkey> = candidates.iter().map(|candidate| candidate.key).collect();
+    if candidates.is_empty()
+        || FeaturedReview::candidates_hash(&candidate_keys) != featured_data.candidates_hash
+    {
+        msg!("Candidates differ from the requested ones");
+        return Err(ReviewError::FeaturedCandidatesMismatch.into());
+    }
+
+    let config_data = load_config(program_id, pda_config)?;
+    let randomness_data = load_randomness(&config_data, randomness)?;
+    if !randomness_data.is_fulfilled() {
+        msg!("Randomness not fulfilled yet");
+        return Err(ReviewError::RandomnessNotFulfilled.into());
+    }
+
+    let value = u64::from_le_bytes(randomness_data.randomness[..8].try_into().unwrap());
+    let winner = candidate_keys[(value % candidates.len() as u64) as usize];
+
+    featured_data.review = *winner;
+    featured_data.pending = false;
+
+    featured_data.serialize(&mut &mut pda_featured.data.borrow_mut()[..])?;
+    msg!("featured review for day {}: {}", featured_data.day, winner);
+
+    // Everything above rent exemption is escrowed bounty, including any left
+    // by requests that expired unfulfilled
+    let bounty = pda_featured
+        .lamports()
+        .saturating_sub(rent_exempt_lamports(pda_featured.data_len())?);
+    **pda_featured.try_borrow_mut_lamports()? -= bounty;
+    **cranker.try_borrow_mut_lamports()? = cranker
+        .lamports()
+        .checked_add(bounty)
+        .ok_or(CommonError::Overflow)?;
+    msg!("paid crank bounty of {} lamports", bounty);
+
+    Ok(())
+}
+
+/// Accounts are `[authority, config, system_program, audit_log]`. The
+/// authority pays to grow a config created before the VRF field.
+pub fn set_vrf_program(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    vrf_program: Pubkey,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let authority = next_account_info(account_info_iter)?;
+    let pda_config = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+    let pda_audit = next_account_info(account_info_iter)?;
+
+    assert_signer(authority)?;
+
+    let mut config_data = load_config(program_id, pda_config)?;
+    assert_initialized(&config_data, ReviewError::UninitializedAccount)?;
+
+    if config_data.authority != *authority.key {
+        msg!("Signer is not the config authority");
+        return Err(CommonError::Unauthorized.into());
+    }
+
+    config_data.vrf_program = vrf_program;
+
+    if pda_config.data_len() < ProgramConfig::get_account_size() {
+        resize_account(
+            pda_config,
+            authority,
+            system_program,
+            ProgramConfig::get_account_size(),
+        )?;
+    }
+
+    assert_rent_exempt(pda_config, CommonError::NotRentExempt)?;
+    config_data.serialize(&mut &mut pda_config.data.borrow_mut()[..])?;
+    record_admin_action(
+        program_id,
+        pda_audit,
+        authority,
+        system_program,
+        AdminAction::SetVrfProgram,
+        pda_config.key,
+    )?;
+    msg!("VRF program set to {}", vrf_program);
+
+    Ok(())
+}
+
+/// Accounts are `[authority, config, system_program, audit_log]`. The
+/// authority pays to grow a config created before the Wormhole fields.
+pub fn set_foreign_emitter(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    wormhole_program: Pubkey,
+    emitter_chain: u16,
+    emitter_address: [u8; 32],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let authority = next_account_info(account_info_iter)?;
+    let pda_config = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+    let pda_audit = next_account_info(account_info_iter)?;
+
+    assert_signer(authority)?;
+
+    let mut config_data = load_config(program_id, pda_config)?;
+    assert_initialized(&config_data, ReviewError::UninitializedAccount)?;
+
+    if config_data.authority != *authority.key {
+        msg!("Signer is not the config authority");
+        return Err(CommonError::Unauthorized.into());
+    }
+
+    config_data.wormhole_program = wormhole_program;
+    config_data.foreign_emitter_chain = emitter_chain;
+    config_data.foreign_emitter = emitter_address;
+
+    if pda_config.data_len() < ProgramConfig::get_account_size() {
+        resize_account(
+            pda_config,
+            authority,
+            system_program,
+            ProgramConfig::get_account_size(),
+        )?;
+    }
+
+    assert_rent_exempt(pda_config, CommonError::NotRentExempt)?;
+    config_data.serialize(&mut &mut pda_config.data.borrow_mut()[..])?;
+    record_admin_action(
+        program_id,
+        pda_audit,
+        authority,
+        system_program,
+        AdminAction::SetForeignEmitter,
+        pda_config.key,
+    )?;
+    msg!("foreign reviews trusted from chain {}", emitter_chain);
+
+    Ok(())
+}
+
+/// Imports a review posted on another chain. The VAA is trusted because the
+/// configured Wormhole bridge owns the posted account, which it only writes
+/// after verifying the guardian signatures, and because it names the
+/// configured emitter. The signer only pays.
+///
+/// Accounts are `[payer, posted_vaa, config, review, comment_counter,
+/// system_program, global_stats]`.
+pub fn import_foreign_review(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let payer = next_account_info(account_info_iter)?;
+    let posted_vaa = next_account_info(account_info_iter)?;
+    let pda_config = next_account_info(account_info_iter)?;
+    let pda_review = next_account_info(account_info_iter)?;
+    let pda_comment_counter = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+    let pda_stats = next_account_info(account_info_iter)?;
+
+    assert_signer(payer)?;
+
+    let config_data = load_config(program_id, pda_config)?;
+    if config_data.wormhole_program == Pubkey::default()
+        || posted_vaa.owner != &config_data.wormhole_program
+    {
+        msg!("VAA was not posted by the Wormhole bridge");
+        return Err(ReviewError::InvalidForeignMessage.into());
+    }
+    let vaa = try_from_slice_unchecked::<PostedVaa>(&posted_vaa.data.borrow())?;
+    if vaa.magic != PostedVaa::MAGIC
+        || vaa.emitter_chain != config_data.foreign_emitter_chain
+        || vaa.emitter_address != config_data.foreign_emitter
+    {
+        msg!("VAA is not from the trusted review emitter");
+        return Err(ReviewError::InvalidForeignMessage.into());
+    }
+    let message = ForeignReviewMessage::try_from_slice(&vaa.payload)
+        .map_err(|_| ReviewError::InvalidForeignMessage)?;
+
+    let chain_bytes = vaa.emitter_chain.to_le_bytes();
+    let sequence_bytes = vaa.sequence.to_le_bytes();
+    let bump_seed = assert_pda(
+        pda_review,
+        &[
+            MovieAccountState::FOREIGN_SEED.as_bytes(),
+            &chain_bytes,
+            &sequence_bytes,
+        ],
+        program_id,
+        ReviewError::InvalidPDA,
+    )?;
+    if pda_review.owner == program_id {
+        msg!("VAA {} has already been imported", vaa.sequence);
+        return Err(ReviewError::DuplicateReview.into());
+    }
+
+    // Leave room for the origin after the description
+    assert_max_len(
+        MovieAccountState::get_account_size(&message.title, &message.description)
+            + ForeignReviewOrigin::LEN,
+        config_data.limits.review_size(),
+        ReviewError::InvalidDataLength,
+    )?;
+
+    let review = MovieAccountState {
+        discriminator: MovieAccountState::FOREIGN_DISCRIMINATOR,
+        is_initialized: true,
+        reviewer: Pubkey::new_from_array(message.author),
+        rating: message.rating.into(),
+        title: message.title,
+        description: message.description,
+        body_format: BodyFormat::Plain,
+        comment_settings: None,
+    };
+    create_review(
+        program_id,
+        payer,
+        pda_review,
+        pda_comment_counter,
+        system_program,
+        &[
+            MovieAccountState::FOREIGN_SEED.as_bytes(),
+            &chain_bytes,
+            &sequence_bytes,
+            &[bump_seed],
+        ],
+        &review,
+        &config_data,
+    )?;
+
+    let origin = ForeignReviewOrigin {
+        origin_chain: vaa.emitter_chain,
+        emitter_address: vaa.emitter_address,
+        sequence: vaa.sequence,
+    };
+    let offset = MovieAccountState::get_account_size(&review.title, &review.description);
+    origin.serialize(&mut &mut pda_review.data.borrow_mut()[offset..])?;
+    msg!(
+        "imported review {} from chain {}",
+        vaa.sequence,
+        vaa.emitter_chain
+    );
+
+    // The author is an address on another chain, so they get no profile
+    update_global_stats(program_id, pda_stats, payer, system_program, |stats| {
+        stats.record_review(false)
+    })
+}
+
+/// Returns who can upgrade this program, so frontends can show whether it is
+/// immutable or controlled by the config authority. A config that does not
+/// exist yet has no authority, so only `Immutable` is trusted then.
+///
+/// Accounts are `[program_data, config]`.
+pub fn get_upgrade_authority_status(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let program_data = next_account_info(account_info_iter)?;
+    let pda_config = next_account_info(account_info_iter)?;
+
+    assert_pda(
+        program_data,
+        &[program_id.as_ref()],
+        &bpf_loader_upgradeable::ID,
+        ReviewError::InvalidPDA,
+    )?;
+    assert_owned_by(program_data, &bpf_loader_upgradeable::ID)?;
+
+    // Bincode layout of `UpgradeableLoaderState::ProgramData`: a u32 variant
+    // index, the deployment slot, then the optional authority
+    let upgrade_authority = {
+        let data = program_data.data.borrow();
+        let metadata = data
+            .get(..UpgradeableLoaderState::size_of_programdata_metadata())
+            .ok_or(ProgramError::InvalidAccountData)?;
+        if metadata[..4] != 3u32.to_le_bytes() {
+            msg!("Account is not program data");
+            return Err(ProgramError::InvalidAccountData);
+        }
+        match metadata[12] {
+            0 => None,
+            1 => Some(Pubkey::try_from(&metadata[13..45]).unwrap()),
+            _ => return Err(ProgramError::InvalidAccountData),
+        }
+    };
+
+    let config_data = load_config(program_id, pda_config)?;
+    let status = match upgrade_authority {
+        None => UpgradeAuthorityStatus::Immutable,
+        Some(authority) if config_data.is_initialized && authority == config_data.authority => {
+            UpgradeAuthorityStatus::ConfigAuthority
+        }
+        Some(authority) => UpgradeAuthorityStatus::Other(authority),
+    };
+    msg!("upgrade authority status: {:?}", status);
+
+    set_return_data(&borsh::to_vec(&status)?);
+
+    Ok(())
+}
+
+/// Returns the accounts the instruction with tag `instruction_tag` takes, so
+/// tools can build it from the program alone.
+pub fn get_required_accounts(instruction_tag: u8) -> ProgramResult {
+    let Some(required) = required_accounts(instruction_tag) else {
+        msg!("No instruction has tag {}", instruction_tag);
+        return Err(ProgramError::InvalidInstructionData);
+    };
+    set_return_data(&borsh::to_vec(&required)?);
+
+    Ok(())
+}
+
+/// Accounts are `[payer, account, system_program]`.
+pub fn top_up_account(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let payer = next_account_info(account_info_iter)?;
+    let account = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+
+    assert_signer(payer)?;
+    assert_owned_by(account, program_id)?;
+    assert_not_closed(account, CommonError::AccountClosed)?;
+
+    let added = top_up_rent(account, payer, system_program)?;
+    msg!("topped up {} by {} lamports", account.key, added);
+
+    Ok(())
+}
+
+/// Accounts are `[authority, account, rent_payer, system_program,
+/// audit_log]`. The authority receives the excess, and must be the payer
+/// recorded at `rent_payer`, or the account's controller if none was. The
+/// last two are only needed to record a withdrawal from the config in the
+/// audit log.
+pub fn withdraw_excess(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let authority = next_account_info(account_info_iter)?;
+    let account = next_account_info(account_info_iter)?;
+    let pda_rent_payer = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter).ok();
+    let pda_audit = next_account_info(account_info_iter).ok();
+
+    assert_signer(authority)?;
+    assert_owned_by(account, program_id)?;
+    assert_not_closed(account, CommonError::AccountClosed)?;
+    let recorded_payer = load_rent_payer(program_id, account, pda_rent_payer)?;
+
+    // Anonymous reviews hold a commitment and imported ones a foreign
+    // address, which no signer matches
+    let decoded = decode_account(&account.data.borrow())?;
+    let is_config = matches!(decoded, MovieReviewAccount::Config(_));
+    let controller = match decoded {
+        MovieReviewAccount::Review(review) => Some(review.reviewer),
+        MovieReviewAccount::Comment(comment) => Some(comment.commenter),
+        MovieReviewAccount::Config(config) => Some(config.authority),
+        _ => None,
+    };
+    if recorded_payer.or(controller) != Some(*authority.key) {
+        msg!("Signer did not pay for {}", account.key);
+        return Err(CommonError::Unauthorized.into());
+    }
+
+    let withdrawn = withdraw_excess_lamports(account, authority)?;
+    msg!(
+        "withdrew {} excess lamports from {}",
+        withdrawn,
+        account.key
+    );
+    if is_config {
+        record_admin_action(
+            program_id,
+            pda_audit.ok_or(ProgramError::NotEnoughAccountKeys)?,
+            authority,
+            system_program.ok_or(ProgramError::NotEnoughAccountKeys)?,
+            AdminAction::WithdrawFees,
+            account.key,
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Accounts are `[authority, config, system_program, audit_log]`. The
+/// authority pays to grow a config created before the fee fields.
+pub fn set_fee_schedule(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    fees: FeeSchedule,
+    fee_exempt_mint: Pubkey,
+    fee_exempt_min_balance: u64,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let authority = next_account_info(account_info_iter)?;
+    let pda_config = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+    let pda_audit = next_account_info(account_info_iter)?;
+
+    assert_signer(authority)?;
+
+    let mut config_data = load_config(program_id, pda_config)?;
+    assert_initialized(&config_data, ReviewError::UninitializedAccount)?;
+
+    if config_data.authority != *authority.key {
+        msg!("Signer is not the config authority");
+        return Err(CommonError::Unauthorized.into());
+    }
+
+    config_data.fees = fees;
+    config_data.fee_exempt_mint = fee_exempt_mint;
+    config_data.fee_exempt_min_balance = fee_exempt_min_balance;
+
+    if pda_config.data_len() < ProgramConfig::get_account_size() {
+        resize_account(
+            pda_config,
+            authority,
+            system_program,
+            ProgramConfig::get_account_size(),
+        )?;
+    }
+
+    assert_rent_exempt(pda_config, CommonError::NotRentExempt)?;
+    config_data.serialize(&mut &mut pda_config.data.borrow_mut()[..])?;
+    record_admin_action(
+        program_id,
+        pda_audit,
+        authority,
+        system_program,
+        AdminAction::SetFeeSchedule,
+        pda_config.key,
+    )?;
+    msg!("fee schedule set to {:?}", fees);
+
+    Ok(())
+}
+
+/// Accounts are `[authority, config, system_program, audit_log]`. The
+/// authority pays to grow a config created before the content limits.
+pub fn set_content_limits(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    limits: ContentLimits,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let authority = next_account_info(account_info_iter)?;
+    let pda_config = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+    let pda_audit = next_account_info(account_info_iter)?;
+
+    assert_signer(authority)?;
+
+    let mut config_data = load_config(program_id, pda_config)?;
+    assert_initialized(&config_data, ReviewError::UninitializedAccount)?;
+
+    if config_data.authority != *authority.key {
+        msg!("Signer is not the config authority");
+        return Err(CommonError::Unauthorized.into());
+    }
+
+    if !limits.is_valid() {
+        msg!("Invalid content limits {:?}", limits);
+        return Err(ReviewError::InvalidContentLimits.into());
+    }
+    config_data.limits = limits;
+
+    if pda_config.data_len() < ProgramConfig::get_account_size() {
+        resize_account(
+            pda_config,
+            authority,
+            system_program,
+            ProgramConfig::get_account_size(),
+        )?;
+    }
+
+    assert_rent_exempt(pda_config, CommonError::NotRentExempt)?;
+    config_data.serialize(&mut &mut pda_config.data.borrow_mut()[..])?;
+    record_admin_action(
+        program_id,
+        pda_audit,
+        authority,
+        system_program,
+        AdminAction::SetContentLimits,
+        pda_config.key,
+    )?;
+    msg!("content limits set to {:?}", limits);
+
+    Ok(())
+}
+
+/// Accounts are `[authority, config, system_program, audit_log]`. The
+/// authority pays to grow a config created before the rating scale.
+pub fn set_rating_scale(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    scale: RatingScale,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let authority = next_account_info(account_info_iter)?;
+    let pda_config = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+    let pda_audit = next_account_info(account_info_iter)?;
+
+    assert_signer(authority)?;
+
+    let mut config_data = load_config(program_id, pda_config)?;
+    assert_initialized(&config_data, ReviewError::UninitializedAccount)?;
+
+    if config_data.authority != *authority.key {
+        msg!("Signer is not the config authority");
+        return Err(CommonError::Unauthorized.into());
+    }
+
+    if !scale.is_valid() {
+        msg!("Invalid rating scale {:?}", scale);
+        return Err(ReviewError::InvalidRatingScale.into());
+    }
+    config_data.rating_scale = scale;
+
+    if pda_config.data_len() < ProgramConfig::get_account_size() {
+        resize_account(
+            pda_config,
+            authority,
+            system_program,
+            ProgramConfig::get_account_size(),
+        )?;
+    }
+
+    assert_rent_exempt(pda_config, CommonError::NotRentExempt)?;
+    config_data.serialize(&mut &mut pda_config.data.borrow_mut()[..])?;
+    record_admin_action(
+        program_id,
+        pda_audit,
+        authority,
+        system_program,
+        AdminAction::SetRatingScale,
+        pda_config.key,
+    )?;
+    msg!("rating scale set to {:?}", scale);
+
+    Ok(())
+}
+
+/// Accounts are `[authority, config, system_program, audit_log]`. The
+/// authority pays to grow a config created before the allowlist.
+pub fn set_cpi_callers(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    callers: Vec<Pubkey>,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let authority = next_account_info(account_info_iter)?;
+    let pda_config = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+    let pda_audit = next_account_info(account_info_iter)?;
+
+    assert_signer(authority)?;
+
+    let mut config_data = load_config(program_id, pda_config)?;
+    assert_initialized(&config_data, ReviewError::UninitializedAccount)?;
+
+    if config_data.authority != *authority.key {
+        msg!("Signer is not the config authority");
+        return Err(CommonError::Unauthorized.into());
+    }
+
+    if callers.len() > ProgramConfig::MAX_CPI_CALLERS {
+        msg!(
+            "At most {} CPI callers can be allowed",
+            ProgramConfig::MAX_CPI_CALLERS
+        );
+        return Err(ReviewError::TooManyCpiCallers.into());
+    }
+    config_data.cpi_callers = [Pubkey::default(); ProgramConfig::MAX_CPI_CALLERS];
+    config_data.cpi_callers[..callers.len()].copy_from_slice(&callers);
+
+    if pda_config.data_len() < ProgramConfig::get_account_size() {
+        resize_account(
+            pda_config,
+            authority,
+            system_program,
+            ProgramConfig::get_account_size(),
+        )?;
+    }
+
+    assert_rent_exempt(pda_config, CommonError::NotRentExempt)?;
+    config_data.serialize(&mut &mut pda_config.data.borrow_mut()[..])?;
+    record_admin_action(
+        program_id,
+        pda_audit,
+        authority,
+        system_program,
+        AdminAction::SetCpiCallers,
+        pda_config.key,
+    )?;
+    msg!("CPI callers set to {:?}", callers);
+
+    Ok(())
+}
+
+/// Assigns `movie` its external ID and creates the index PDA resolving the
+/// ID back to it. The index PDA keeps each ID on a single movie.
+///
+/// Accounts are `[authority, movie, config, index, system_program,
+/// audit_log]`. The authority pays for the index and the movie's growth.
+pub fn set_movie_external_id(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    external_id: String,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let authority = next_account_info(account_info_iter)?;
+    let pda_movie = next_account_info(account_info_iter)?;
+    let pda_config = next_account_info(account_info_iter)?;
+    let pda_index = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+    let pda_audit = next_account_info(account_info_iter)?;
+
+    assert_signer(authority)?;
+
+    let config_data = load_config(program_id, pda_config)?;
+    assert_initialized(&config_data, ReviewError::UninitializedAccount)?;
+
+    if config_data.authority != *authority.key {
+        msg!("Signer is not the config authority");
+        return Err(CommonError::Unauthorized.into());
+    }
+
+    if !Movie::is_valid_external_id(&external_id) {
+        msg!("Invalid external ID {}", external_id);
+        return Err(ReviewError::InvalidExternalId.into());
+    }
+
+    let mut movie_data = load_movie(program_id, pda_movie)?;
+    if !movie_data.external_id.is_empty() {
+        msg!("Movie already has external ID {}", movie_data.external_id);
+        return Err(ReviewError::ExternalIdTaken.into());
+    }
+
+    let external_id_hash = Movie::external_id_hash(&external_id);
+    let bump_seed = assert_pda(
+        pda_index,
+        &[
+            Movie::EXTERNAL_ID_SEED.as_bytes(),
+            external_id_hash.as_ref(),
+        ],
+        program_id,
+        ReviewError::InvalidPDA,
+    )?;
+    if pda_index.owner == program_id {
+        msg!("External ID {} already indexed", external_id);
+        return Err(ReviewError::ExternalIdTaken.into());
+    }
+
+    msg!("creating external ID index");
+    create_pda_account(
+        authority,
+        pda_index,
+        system_program,
+        ExternalIdIndex::get_account_size(),
+        program_id,
+        &[
+            Movie::EXTERNAL_ID_SEED.as_bytes(),
+            external_id_hash.as_ref(),
+            &[bump_seed],
+        ],
+    )?;
+    let index_data = ExternalIdIndex {
+        discriminator: ExternalIdIndex::DISCRIMINATOR,
+        is_initialized: true,
+        movie: *pda_movie.key,
+    };
+    index_data.serialize(&mut &mut pda_index.data.borrow_mut()[..])?;
+
+    movie_data.external_id = external_id;
+    let account_size = Movie::get_account_size(&movie_data.title, &movie_data.external_id);
+    if pda_movie.data_len() < account_size {
+        resize_account(pda_movie, authority, system_program, account_size)?;
+    }
+
+    assert_rent_exempt(pda_movie, CommonError::NotRentExempt)?;
+    movie_data.serialize(&mut &mut pda_movie.data.borrow_mut()[..])?;
+    msg!("{} indexed as {}", pda_movie.key, movie_data.external_id);
+    record_admin_action(
+        program_id,
+        pda_audit,
+        authority,
+        system_program,
+        AdminAction::SetMovieExternalId,
+        pda_movie.key,
+    )?;
+
+    Ok(())
+}
+
+/// Sets `movie`'s release date, e.g. a scheduled release before the oracle
+/// attests it, or an earlier date to open reviews of a premiere. A later
+/// `MarkVerifiedRelease` replaces it with the attested date.
+///
+/// Accounts are `[authority, movie, config, system_program, audit_log]`. The
+/// authority pays to grow movies registered before the release fields.
+pub fn set_movie_release_date(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    release_date: UnixTimestamp,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let authority = next_account_info(account_info_iter)?;
+    let pda_movie = next_account_info(account_info_iter)?;
+    let pda_config = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+    let pda_audit = next_account_info(account_info_iter)?;
+
+    assert_signer(authority)?;
+
+    let config_data = load_config(program_id, pda_config)?;
+    assert_initialized(&config_data, ReviewError::UninitializedAccount)?;
+
+    if config_data.authority != *authority.key {
+        msg!("Signer is not the config authority");
+        return Err(CommonError::Unauthorized.into());
+    }
+
+    if release_date < 0 {
+        msg!("Invalid release date {}", release_date);
+        return Err(ReviewError::InvalidReleaseDate.into());
+    }
+
+    let mut movie_data = load_movie(program_id, pda_movie)?;
+    movie_data.release_date = release_date;
+
+    let account_size = Movie::get_account_size(&movie_data.title, &movie_data.external_id);
+    if pda_movie.data_len() < account_size {
+        resize_account(pda_movie, authority, system_program, account_size)?;
+    }
+
+    assert_rent_exempt(pda_movie, CommonError::NotRentExempt)?;
+    movie_data.serialize(&mut &mut pda_movie.data.borrow_mut()[..])?;
+    msg!("{} releases on {}", pda_movie.key, release_date);
+    record_admin_action(
+        program_id,
+        pda_audit,
+        authority,
+        system_program,
+        AdminAction::SetMovieReleaseDate,
+        pda_movie.key,
+    )?;
+
+    Ok(())
+}
+
+/// Checks `reviewer` signed and wrote `review`, and that `pda_translation` is
+/// the address of its `language` translation. Returns the translation's bump.
+fn assert_translation_access(
+    program_id: &Pubkey,
+    reviewer: &AccountInfo,
+    review: &AccountInfo,
+    pda_translation: &AccountInfo,
+    language: &str,
+) -> Result<u8, ProgramError> {
+    assert_signer(reviewer)?;
+    assert_owned_by(review, program_id)?;
+    assert_not_closed(review, CommonError::AccountClosed)?;
+
+    let header = load_review_header(&review.data.borrow())?;
+    assert_initialized(&header, ReviewError::UninitializedAccount)?;
+    if header.reviewer != *reviewer.key {
+        msg!("Signer did not write this review");
+        return Err(CommonError::Unauthorized.into());
+    }
+
+    if !ReviewTranslation::is_valid_language(language) {
+        msg!("Invalid language tag {}", language);
+        return Err(ReviewError::InvalidLanguageTag.into());
+    }
+    assert_pda(
+        pda_translation,
+        &[
+            ReviewTranslation::SEED.as_bytes(),
+            review.key.as_ref(),
+            language.as_bytes(),
+        ],
+        program_id,
+        ReviewError::InvalidPDA,
+    )
+}
+
+/// Accounts are `[reviewer, review, translation, system_program]`.
+pub fn add_review_translation(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    language: String,
+    description: String,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let reviewer = next_account_info(account_info_iter)?;
+    let review = next_account_info(account_info_iter)?;
+    let pda_translation = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+
+    let bump_seed =
+        assert_translation_access(program_id, reviewer, review, pda_translation, &language)?;
+    assert_max_len(
+        ReviewTranslation::get_account_size(&language, &description),
+        ReviewTranslation::MAX_ACCOUNT_SIZE,
+        ReviewError::InvalidDataLength,
+    )?;
+    assert_no_translation(program_id, pda_translation, &language)?;
+
+    create_translation(
+        program_id,
+        reviewer,
+        review,
+        pda_translation,
+        system_program,
+        bump_seed,
+        language,
+        description,
+    )
+}
+
+fn assert_no_translation(
+    program_id: &Pubkey,
+    pda_translation: &AccountInfo,
+    language: &str,
+) -> ProgramResult {
+    if pda_translation.owner == program_id {
+        msg!("Review already has a {} translation", language);
+        return Err(ReviewError::DuplicateTranslation.into());
+    }
+    Ok(())
+}
+
+/// Creates the translation at `pda_translation`, already checked to be the
+/// free address of `review`'s `language` translation, paid by `reviewer`.
+#[allow(clippy::too_many_arguments)]
+fn create_translation<'a>(
+    program_id: &Pubkey,
+    reviewer: &AccountInfo<'a>,
+    review: &AccountInfo<'a>,
+    pda_translation: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    bump_seed: u8,
+    language: String,
+    description: String,
+) -> ProgramResult {
+    msg!("creating {} translation", language);
+    create_pda_account(
+        reviewer,
+        pda_translation,
+        system_program,
+        ReviewTranslation::MAX_ACCOUNT_SIZE,
+        program_id,
+        &[
+            ReviewTranslation::SEED.as_bytes(),
+            review.key.as_ref(),
+            language.as_bytes(),
+            &[bump_seed],
+        ],
+    )?;
+    assert_rent_exempt(pda_translation, CommonError::NotRentExempt)?;
+
+    let translation_data = ReviewTranslation {
+        discriminator: ReviewTranslation::DISCRIMINATOR,
+        is_initialized: true,
+        review: *review.key,
+        language,
+        description,
+    };
+    translation_data.serialize(&mut &mut pda_translation.data.borrow_mut()[..])?;
+
+    Ok(())
+}
+
+/// Loads the translation at `pda_translation`, already checked to be the
+/// address of a translation of `review`.
+fn load_translation(
+    program_id: &Pubkey,
+    pda_translation: &AccountInfo,
+) -> Result<ReviewTranslation, ProgramError> {
+    assert_owned_by(pda_translation, program_id)?;
+    assert_not_closed(pda_translation, CommonError::AccountClosed)?;
+
+    let translation_data =
+        try_from_slice_unchecked::<ReviewTranslation>(&pda_translation.data.borrow())?;
+    if translation_data.discriminator != ReviewTranslation::DISCRIMINATOR {
+        msg!("Account is not a review translation");
+        return Err(ReviewError::InvalidDiscriminator.into());
+    }
+    assert_initialized(&translation_data, ReviewError::UninitializedAccount)?;
+
+    Ok(translation_data)
+}
+
+/// Accounts are `[reviewer, review, translation]`.
+pub fn update_review_translation(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    language: String,
+    description: String,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let reviewer = next_account_info(account_info_iter)?;
+    let review = next_account_info(account_info_iter)?;
+    let pda_translation = next_account_info(account_info_iter)?;
+
+    assert_translation_access(program_id, reviewer, review, pda_translation, &language)?;
+    assert_max_len(
+        ReviewTranslation::get_account_size(&language, &description),
+        ReviewTranslation::MAX_ACCOUNT_SIZE,
+        ReviewError::InvalidDataLength,
+    )?;
+
+    let mut translation_data = load_translation(program_id, pda_translation)?;
+    translation_data.description = description;
+
+    // A shorter description would leave the old one's tail behind
+    let mut data = pda_translation.data.borrow_mut();
+    data.fill(0);
+    translation_data.serialize(&mut &mut data[..])?;
+    msg!("updated {} translation", language);
+
+    Ok(())
+}
+
+/// Accounts are `[reviewer, review, translation]`. The reviewer receives the
+/// translation's rent.
+pub fn delete_review_translation(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    language: String,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let reviewer = next_account_info(account_info_iter)?;
+    let review = next_account_info(account_info_iter)?;
+    let pda_translation = next_account_info(account_info_iter)?;
+
+    assert_translation_access(program_id, reviewer, review, pda_translation, &language)?;
+    load_translation(program_id, pda_translation)?;
+
+    close_account(pda_translation, reviewer)?;
+    msg!("deleted {} translation", language);
+
+    Ok(())
+}
+
+/// Checks `pda_bounty` is the address of `review`'s `language` translation
+/// bounty and returns its bump.
+fn assert_bounty_address(
+    program_id: &Pubkey,
+    review: &AccountInfo,
+    pda_bounty: &AccountInfo,
+    language: &str,
+) -> Result<u8, ProgramError> {
+    assert_pda(
+        pda_bounty,
+        &[
+            TranslationBounty::SEED.as_bytes(),
+            review.key.as_ref(),
+            language.as_bytes(),
+        ],
+        program_id,
+        ReviewError::InvalidPDA,
+    )
+}
+
+/// Loads the open bounty at `pda_bounty`, already checked to be its address.
+fn load_bounty(
+    program_id: &Pubkey,
+    pda_bounty: &AccountInfo,
+) -> Result<TranslationBounty, ProgramError> {
+    assert_owned_by(pda_bounty, program_id)?;
+    assert_not_closed(pda_bounty, CommonError::AccountClosed)?;
+
+    let bounty_data = try_from_slice_unchecked::<TranslationBounty>(&pda_bounty.data.borrow())?;
+    if bounty_data.discriminator != TranslationBounty::DISCRIMINATOR {
+        msg!("Account is not a translation bounty");
+        return Err(ReviewError::InvalidDiscriminator.into());
+    }
+    assert_initialized(&bounty_data, ReviewError::UninitializedAccount)?;
+
+    Ok(bounty_data)
+}
+
+/// Checks `pda_submission` is `translator`'s submission for the bounty at
+/// `pda_bounty` and returns its bump.
+fn assert_submission_address(
+    program_id: &Pubkey,
+    pda_bounty: &AccountInfo,
+    translator: &AccountInfo,
+    pda_submission: &AccountInfo,
+) -> Result<u8, ProgramError> {
+    assert_pda(
+        pda_submission,
+        &[
+            TranslationSubmission::SEED.as_bytes(),
+            pda_bounty.key.as_ref(),
+            translator.key.as_ref(),
+        ],
+        program_id,
+        ReviewError::InvalidPDA,
+    )
+}
+
+/// Loads the submission at `pda_submission`, already checked to be its
+/// address.
+fn load_submission(
+    program_id: &Pubkey,
+    pda_submission: &AccountInfo,
+) -> Result<TranslationSubmission, ProgramError> {
+    assert_owned_by(pda_submission, program_id)?;
+    assert_not_closed(pda_submission, CommonError::AccountClosed)?;
+
+    let submission_data =
+        try_from_slice_unchecked::<TranslationSubmission>(&pda_submission.data.borrow())?;
+    if submission_data.discriminator != TranslationSubmission::DISCRIMINATOR {
+        msg!("Account is not a translation submission");
+        return Err(ReviewError::InvalidDiscriminator.into());
+    }
+    assert_initialized(&submission_data, ReviewError::UninitializedAccount)?;
+
+    Ok(submission_data)
+}
+
+/// Escrows `amount` lamports in the bounty for a translation of the signer's
+/// review into `language`, opening it if needed. The review must not have a
+/// translation in that language yet.
+///
+/// Accounts are `[reviewer, review, translation, bounty, system_program]`.
+pub fn fund_translation_bounty(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    language: String,
+    amount: u64,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let reviewer = next_account_info(account_info_iter)?;
+    let review = next_account_info(account_info_iter)?;
+    let pda_translation = next_account_info(account_info_iter)?;
+    let pda_bounty = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+
+    assert_translation_access(program_id, reviewer, review, pda_translation, &language)?;
+    assert_no_translation(program_id, pda_translation, &language)?;
+    let bump_seed = assert_bounty_address(program_id, review, pda_bounty, &language)?;
+    if amount == 0 {
+        msg!("Bounty must be positive");
+        return Err(ReviewError::InvalidBountyAmount.into());
+    }
+
+    let mut bounty_data = if pda_bounty.owner == program_id {
+        load_bounty(program_id, pda_bounty)?
+    } else {
+        msg!("opening {} translation bounty", language);
+        create_pda_account(
+            reviewer,
+            pda_bounty,
+            system_program,
+            TranslationBounty::get_account_size(),
+            program_id,
+            &[
+                TranslationBounty::SEED.as_bytes(),
+                review.key.as_ref(),
+                language.as_bytes(),
+                &[bump_seed],
+            ],
+        )?;
+        assert_rent_exempt(pda_bounty, CommonError::NotRentExempt)?;
+        TranslationBounty {
+            discriminator: TranslationBounty::DISCRIMINATOR,
+            is_initialized: true,
+            review: *review.key,
+            reviewer: *reviewer.key,
+            amount: 0,
+            language,
+            cancellable_at: 0,
+        }
+    };
+
+    invoke(
+        &system_instruction::transfer(reviewer.key, pda_bounty.key, amount),
+        &[reviewer.clone(), pda_bounty.clone(), system_program.clone()],
+    )?;
+    bounty_data.amount = bounty_data
+        .amount
+        .checked_add(amount)
+        .ok_or(CommonError::Overflow)?;
+    msg!(
+        "{} translation bounty is {} lamports",
+        bounty_data.language,
+        bounty_data.amount
+    );
+    bounty_data.serialize(&mut &mut pda_bounty.data.borrow_mut()[..])?;
+
+    Ok(())
+}
+
+/// Submits the signer's translation for the review's open `language`
+/// bounty, replacing their earlier submission. The translator pays for the
+/// submission, which is resized to the new description. The bounty cannot be
+/// cancelled until a review period after it.
+///
+/// Accounts are `[translator, review, bounty, submission, system_program]`.
+pub fn submit_translation(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    language: String,
+    description: String,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let translator = next_account_info(account_info_iter)?;
+    let review = next_account_info(account_info_iter)?;
+    let pda_bounty = next_account_info(account_info_iter)?;
+    let pda_submission = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+
+    assert_signer(translator)?;
+    assert_bounty_address(program_id, review, pda_bounty, &language)?;
+    let mut bounty_data = load_bounty(program_id, pda_bounty)?;
+    let bump_seed = assert_submission_address(program_id, pda_bounty, translator, pda_submission)?;
+    // The submission has to fit the translation it would become
+    assert_max_len(
+        ReviewTranslation::get_account_size(&language, &description),
+        ReviewTranslation::MAX_ACCOUNT_SIZE,
+        ReviewError::InvalidDataLength,
+    )?;
+
+    let account_size = TranslationSubmission::get_account_size(&description);
+    if pda_submission.owner == program_id {
+        load_submission(program_id, pda_submission)?;
+        resize_account(pda_submission, translator, system_program, account_size)?;
+    } else {
+        create_pda_account(
+            translator,
+            pda_submission,
+            system_program,
+            account_size,
+            program_id,
+            &[
+                TranslationSubmission::SEED.as_bytes(),
+                pda_bounty.key.as_ref(),
+                translator.key.as_ref(),
+                &[bump_seed],
+            ],
+        )?;
+        assert_rent_exempt(pda_submission, CommonError::NotRentExempt)?;
+    }
+
+    let submission_data = TranslationSubmission {
+        discriminator: TranslationSubmission::DISCRIMINATOR,
+        is_initialized: true,
+        bounty: *pda_bounty.key,
+        translator: *translator.key,
+        description,
+    };
+    submission_data.serialize(&mut &mut pda_submission.data.borrow_mut()[..])?;
+    msg!("{} submitted a {} translation", translator.key, language);
+
+    let cancellable_at = Clock::get()?
+        .unix_timestamp
+        .checked_add(TranslationBounty::REVIEW_PERIOD)
+        .ok_or(CommonError::Overflow)?;
+    bounty_data.cancellable_at = bounty_data.cancellable_at.max(cancellable_at);
+    bounty_data.serialize(&mut &mut pda_bounty.data.borrow_mut()[..])?;
+
+    Ok(())
+}
+
+/// Publishes `translator`'s submission as the review's `language`
+/// translation and pays them the bounty. The reviewer pays for the
+/// translation and gets the bounty's rent back; the translator gets the
+/// submission's.
+///
+/// Accounts are `[reviewer, review, translation, bounty, submission,
+/// translator, system_program]`.
+pub fn approve_translation(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    language: String,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let reviewer = next_account_info(account_info_iter)?;
+    let review = next_account_info(account_info_iter)?;
+    let pda_translation = next_account_info(account_info_iter)?;
+    let pda_bounty = next_account_info(account_info_iter)?;
+    let pda_submission = next_account_info(account_info_iter)?;
+    let translator = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+
+    let bump_seed =
+        assert_translation_access(program_id, reviewer, review, pda_translation, &language)?;
+    assert_no_translation(program_id, pda_translation, &language)?;
+    assert_bounty_address(program_id, review, pda_bounty, &language)?;
+    let bounty_data = load_bounty(program_id, pda_bounty)?;
+    assert_submission_address(program_id, pda_bounty, translator, pda_submission)?;
+    let submission_data = load_submission(program_id, pda_submission)?;
+
+    create_translation(
+        program_id,
+        reviewer,
+        review,
+        pda_translation,
+        system_program,
+        bump_seed,
+        language,
+        submission_data.description,
+    )?;
+
+    **pda_bounty.try_borrow_mut_lamports()? = pda_bounty
+        .lamports()
+        .checked_sub(bounty_data.amount)
+        .ok_or(CommonError::Overflow)?;
+    **translator.try_borrow_mut_lamports()? = translator
+        .lamports()
+        .checked_add(bounty_data.amount)
+        .ok_or(CommonError::Overflow)?;
+    msg!(
+        "paid {} lamports bounty to {}",
+        bounty_data.amount,
+        translator.key
+    );
+
+    close_account(pda_submission, translator)?;
+    close_account(pda_bounty, reviewer)?;
+
+    Ok(())
+}
+
+/// Closes the review's open `language` bounty, refunding the bounty and its
+/// rent to the reviewer who funded it. The review may already be deleted, so
+/// its bounties are not stranded. Fails within the review period of the
+/// latest submission. Submissions stay until their translators withdraw them.
+///
+/// Accounts are `[reviewer, review, bounty]`.
+pub fn cancel_translation_bounty(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    language: String,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let reviewer = next_account_info(account_info_iter)?;
+    let review = next_account_info(account_info_iter)?;
+    let pda_bounty = next_account_info(account_info_iter)?;
+
+    assert_signer(reviewer)?;
+    assert_bounty_address(program_id, review, pda_bounty, &language)?;
+    let bounty_data = load_bounty(program_id, pda_bounty)?;
+    if bounty_data.reviewer != *reviewer.key {
+        msg!("Signer did not fund this bounty");
+        return Err(CommonError::Unauthorized.into());
+    }
+    if Clock::get()?.unix_timestamp < bounty_data.cancellable_at {
+        msg!("Bounty has a submission under review");
+        return Err(ReviewError::BountyUnderReview.into());
+    }
+
+    close_account(pda_bounty, reviewer)?;
+    msg!("cancelled {} translation bounty", language);
+
+    Ok(())
+}
+
+/// Closes the signer's submission for the review's `language` bounty,
+/// refunding its rent to them. The bounty may already be closed.
+///
+/// Accounts are `[translator, review, bounty, submission]`.
+pub fn withdraw_translation_submission(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    language: String,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let translator = next_account_info(account_info_iter)?;
+    let review = next_account_info(account_info_iter)?;
+    let pda_bounty = next_account_info(account_info_iter)?;
+    let pda_submission = next_account_info(account_info_iter)?;
+
+    assert_signer(translator)?;
+    assert_bounty_address(program_id, review, pda_bounty, &language)?;
+    assert_submission_address(program_id, pda_bounty, translator, pda_submission)?;
+    load_submission(program_id, pda_submission)?;
+
+    close_account(pda_submission, translator)?;
+    msg!("withdrew {} translation submission", language);
+
+    Ok(())
+}
+
+/// Loads the signed-in reviewer's own review, upgrading it first. Anonymous
+/// and imported reviews have no signing author, so they are rejected.
+fn load_own_review(
+    program_id: &Pubkey,
+    reviewer: &AccountInfo,
+    pda_review: &AccountInfo,
+) -> Result<MovieAccountState, ProgramError> {
+    assert_signer(reviewer)?;
+    let review_data = load_review(program_id, pda_review)?;
+    if review_data.reviewer != *reviewer.key {
+        msg!("Signer did not write this review");
+        return Err(CommonError::Unauthorized.into());
+    }
+    Ok(review_data)
+}
+
+/// Loads a review `signer` may change in `scope`: their own, or that of the
+/// authority of a session among `proofs` giving them the scope.
+fn load_delegated_review(
+    program_id: &Pubkey,
+    signer: &AccountInfo,
+    pda_review: &AccountInfo,
+    proofs: &[AccountInfo],
+    scope: u8,
+) -> Result<MovieAccountState, ProgramError> {
+    assert_signer(signer)?;
+    let reviewer = signer_authority(program_id, signer, proofs, scope)?;
+    let review_data = load_review(program_id, pda_review)?;
+    if review_data.reviewer != reviewer {
+        msg!("Signer did not write this review");
+        return Err(CommonError::Unauthorized.into());
+    }
+    Ok(review_data)
+}
+
+/// Loads a signed review, upgrading it to the current layout first.
+fn load_review(
+    program_id: &Pubkey,
+    pda_review: &AccountInfo,
+) -> Result<MovieAccountState, ProgramError> {
+    assert_owned_by(pda_review, program_id)?;
+    assert_not_closed(pda_review, CommonError::AccountClosed)?;
+
+    upgrade_account(pda_review)?;
+    let review_data = try_from_slice_unchecked::<MovieAccountState>(&pda_review.data.borrow())?;
+    assert_initialized(&review_data, ReviewError::UninitializedAccount)?;
+
+    if review_data.discriminator != MovieAccountState::DISCRIMINATOR {
+        msg!("Account is not a review");
+        return Err(ReviewError::InvalidDiscriminator.into());
+    }
+    Ok(review_data)
+}
+
+/// Lists the co-authors of the signer's review. Co-authors listed again at
+/// the same share stay accepted; the others must accept their new listing.
+/// The reviewer pays for the co-authors account on the first listing.
+///
+/// Accounts are `[reviewer, review, co_authors, system_program]`.
+pub fn set_co_authors(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    co_authors: Vec<(Pubkey, u16)>,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let reviewer = next_account_info(account_info_iter)?;
+    let pda_review = next_account_info(account_info_iter)?;
+    let pda_co_authors = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+
+    load_own_review(program_id, reviewer, pda_review)?;
+    if !ReviewCoAuthors::is_valid_listing(reviewer.key, &co_authors) {
+        msg!("Invalid co-author listing");
+        return Err(ReviewError::InvalidCoAuthors.into());
+    }
+
+    let bump_seed = assert_pda(
+        pda_co_authors,
+        &[ReviewCoAuthors::SEED.as_bytes(), pda_review.key.as_ref()],
+        program_id,
+        ReviewError::InvalidPDA,
+    )?;
+    let listed = if pda_co_authors.owner == program_id {
+        load_co_authors(pda_co_authors)?.co_authors
+    } else {
+        msg!("creating co-authors account");
+        create_pda_account(
+            reviewer,
+            pda_co_authors,
+            system_program,
+            ReviewCoAuthors::get_account_size(),
+            program_id,
+            &[
+                ReviewCoAuthors::SEED.as_bytes(),
+                pda_review.key.as_ref(),
+                &[bump_seed],
+            ],
+        )?;
+        Vec::new()
+    };
+
+    let co_authors_data = ReviewCoAuthors {
+        discriminator: ReviewCoAuthors::DISCRIMINATOR,
+        is_initialized: true,
+        review: *pda_review.key,
+        co_authors: co_authors
+            .into_iter()
+            .map(|(co_author, share_bps)| CoAuthor {
+                co_author,
+                share_bps,
+                accepted: listed.iter().any(|previous| {
+                    previous.accepted
+                        && previous.co_author == co_author
+                        && previous.share_bps == share_bps
+                }),
+            })
+            .collect(),
+    };
+    co_authors_data.serialize(&mut &mut pda_co_authors.data.borrow_mut()[..])?;
+    msg!(
+        "review {} lists {} co-authors",
+        pda_review.key,
+        co_authors_data.co_authors.len()
+    );
+
+    Ok(())
+}
+
+/// Accepts the signer's listing as a co-author at `share_bps`, so a share
+/// lowered before the acceptance lands is not accepted.
+///
+/// Accounts are `[co_author, co_authors]`.
+pub fn accept_co_authorship(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    share_bps: u16,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let co_author = next_account_info(account_info_iter)?;
+    let pda_co_authors = next_account_info(account_info_iter)?;
+
+    assert_signer(co_author)?;
+    assert_owned_by(pda_co_authors, program_id)?;
+
+    let mut co_authors_data = load_co_authors(pda_co_authors)?;
+    let Some(listing) = co_authors_data
+        .co_authors
+        .iter_mut()
+        .find(|listing| listing.co_author == *co_author.key && listing.share_bps == share_bps)
+    else {
+        msg!("Signer is not listed at {} bps", share_bps);
+        return Err(ReviewError::NotCoAuthor.into());
+    };
+    listing.accepted = true;
+
+    co_authors_data.serialize(&mut &mut pda_co_authors.data.borrow_mut()[..])?;
+    msg!(
+        "{} accepted co-authorship of review {}",
+        co_author.key,
+        co_authors_data.review
+    );
+
+    Ok(())
+}
+
+/// Sets the critic authority that signs for the signer, a PDA of
+/// `authority_program`, or clears it with default keys. The signer pays for
+/// their profile if they have none yet, or for growing one created before
+/// the authority was appended.
+///
+/// Accounts are `[reviewer, profile, system_program]`.
+pub fn set_critic_authority(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    authority: Pubkey,
+    authority_program: Pubkey,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let reviewer = next_account_info(account_info_iter)?;
+    let pda_profile = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+
+    assert_signer(reviewer)?;
+    let cleared = authority == Pubkey::default() && authority_program == Pubkey::default();
+    // A key on the curve could sign for itself, outside its program
+    if !cleared
+        && (authority == Pubkey::default()
+            || authority_program == Pubkey::default()
+            || authority.is_on_curve())
+    {
+        msg!("Critic authority must be off the curve and name its program");
+        return Err(ReviewError::InvalidCriticAuthority.into());
+    }
+    let bump_seed = assert_pda(
+        pda_profile,
+        &[ReviewerProfile::SEED.as_bytes(), reviewer.key.as_ref()],
+        program_id,
+        ReviewError::InvalidPDA,
+    )?;
+
+    let mut profile_data = if pda_profile.owner == program_id {
+        load_profile(pda_profile)?
+    } else {
+        msg!("creating reviewer profile");
+        create_pda_account(
+            reviewer,
+            pda_profile,
+            system_program,
+            ReviewerProfile::get_account_size(),
+            program_id,
+            &[
+                ReviewerProfile::SEED.as_bytes(),
+                reviewer.key.as_ref(),
+                &[bump_seed],
+            ],
+        )?;
+        ReviewerProfile {
+            discriminator: ReviewerProfile::DISCRIMINATOR,
+            is_initialized: true,
+            reviewer: *reviewer.key,
+            review_count: 0,
+            ratings: [0; 5],
+            relay_nonce: 0,
+            last_active_epoch: 0,
+            streak: 0,
+            authority_program: Pubkey::default(),
+            authority: Pubkey::default(),
+        }
+    };
+    profile_data.authority = authority;
+    profile_data.authority_program = authority_program;
+
+    if pda_profile.data_len() < ReviewerProfile::get_account_size() {
+        resize_account(
+            pda_profile,
+            reviewer,
+            system_program,
+            ReviewerProfile::get_account_size(),
+        )?;
+    }
+    assert_rent_exempt(pda_profile, CommonError::NotRentExempt)?;
+    store_profile(&profile_data, pda_profile)?;
+    msg!(
+        "critic authority of {} set to {} of {}",
+        reviewer.key,
+        authority,
+        authority_program
+    );
+
+    Ok(())
+}
+
+fn load_co_authors(pda_co_authors: &AccountInfo) -> Result<ReviewCoAuthors, ProgramError> {
+    let co_authors_data =
+        try_from_slice_unchecked::<ReviewCoAuthors>(&pda_co_authors.data.borrow())?;
+    assert_initialized(&co_authors_data, ReviewError::UninitializedAccount)?;
+    if co_authors_data.discriminator != ReviewCoAuthors::DISCRIMINATOR {
+        msg!("Account is not a review's co-authors");
+        return Err(ReviewError::InvalidDiscriminator.into());
+    }
+    Ok(co_authors_data)
+}
+
+/// Switches how the review's description is rendered. The current
+/// description must already be valid in the new format.
+///
+/// Accounts are `[reviewer, review, (session)]`, where a session with
+/// `Session::SCOPE_UPDATE` lets its key sign as the reviewer.
+pub fn set_review_body_format(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    body_format: BodyFormat,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let signer = next_account_info(account_info_iter)?;
+    let pda_review = next_account_info(account_info_iter)?;
+    let proofs = account_info_iter.as_slice();
+
+    let mut review_data = load_delegated_review(
+        program_id,
+        signer,
+        pda_review,
+        proofs,
+        Session::SCOPE_UPDATE,
+    )?;
+    if body_format == BodyFormat::OffChain {
+        msg!("Off-chain bodies are committed with UpdateBodyHash");
+        return Err(ReviewError::BodyHashRequired.into());
+    }
+    if !body_format.is_valid(&review_data.description) {
+        msg!("Description is not valid {:?}", body_format);
+        return Err(ReviewError::InvalidRichText.into());
+    }
+
+    review_data.body_format = body_format;
+    review_data.serialize(&mut &mut pda_review.data.borrow_mut()[..])?;
+    msg!("review body format set to {:?}", body_format);
+
+    Ok(())
+}
+
+/// Appends `text` to the description. Reviews are created at the configured
+/// `ContentLimits::review_size`; once the description no longer fits,
+/// the account is reallocated, up to `MAX_GROWN_ACCOUNT_SIZE`, with the
+/// reviewer topping up its rent.
+///
+/// Accounts are `[reviewer, review, system_program]`.
+pub fn append_to_description(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    text: String,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let reviewer = next_account_info(account_info_iter)?;
+    let pda_review = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+
+    // Only a review grown to exactly its content can be too short to upgrade,
+    // and growing it is what this instruction pays for anyway
+    assert_signer(reviewer)?;
+    assert_owned_by(pda_review, program_id)?;
+    let shortfall = upgrade_shortfall(&pda_review.data.borrow())?;
+    if shortfall > 0 && load_review_header(&pda_review.data.borrow())?.reviewer == *reviewer.key {
+        let new_len = pda_review.data_len() + shortfall;
+        resize_account(pda_review, reviewer, system_program, new_len)?;
+    }
+
+    let mut review_data = load_own_review(program_id, reviewer, pda_review)?;
+    if text.is_empty() {
+        msg!("Nothing to append");
+        return Err(ReviewError::InvalidDataLength.into());
+    }
+
+    review_data.description.push_str(&text);
+    let new_size =
+        MovieAccountState::get_account_size(&review_data.title, &review_data.description);
+    assert_max_len(
+        new_size,
+        MovieAccountState::MAX_GROWN_ACCOUNT_SIZE,
+        ReviewError::InvalidDataLength,
+    )?;
+    if !review_data.body_format.is_valid(&review_data.description) {
+        msg!("Description is not valid {:?}", review_data.body_format);
+        return Err(ReviewError::InvalidRichText.into());
+    }
+
+    if pda_review.data_len() < new_size {
+        resize_account(pda_review, reviewer, system_program, new_size)?;
+    }
+    review_data.serialize(&mut &mut pda_review.data.borrow_mut()[..])?;
+    msg!("appended {} bytes to the description", text.len());
+
+    Ok(())
+}
+
+/// Records that the signer follows `reviewer`, letting them comment on the
+/// reviewer's followers-only reviews.
+///
+/// Accounts are `[follower, follow, system_program]`.
+pub fn follow_reviewer(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    reviewer: Pubkey,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let follower = next_account_info(account_info_iter)?;
+    let pda_follow = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+
+    assert_signer(follower)?;
+    let bump_seed = assert_pda(
+        pda_follow,
+        &[
+            Follow::SEED.as_bytes(),
+            follower.key.as_ref(),
+            reviewer.as_ref(),
+        ],
+        program_id,
+        ReviewError::InvalidPDA,
+    )?;
+    if pda_follow.owner == program_id {
+        msg!("{} already follows {}", follower.key, reviewer);
+        return Err(ProgramError::AccountAlreadyInitialized);
+    }
+
+    create_pda_account(
+        follower,
+        pda_follow,
+        system_program,
+        Follow::get_account_size(),
+        program_id,
+        &[
+            Follow::SEED.as_bytes(),
+            follower.key.as_ref(),
+            reviewer.as_ref(),
+            &[bump_seed],
+        ],
+    )?;
+    assert_rent_exempt(pda_follow, CommonError::NotRentExempt)?;
+
+    let follow_data = Follow {
+        discriminator: Follow::DISCRIMINATOR,
+        is_initialized: true,
+        follower: *follower.key,
+        reviewer,
+    };
+    follow_data.serialize(&mut &mut pda_follow.data.borrow_mut()[..])?;
+    msg!("{} now follows {}", follower.key, reviewer);
+
+    Ok(())
+}
+
+/// Closes the signer's follow, refunding its rent to them.
+///
+/// Accounts are `[follower, follow]`.
+pub fn unfollow_reviewer(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let follower = next_account_info(account_info_iter)?;
+    let pda_follow = next_account_info(account_info_iter)?;
+
+    assert_signer(follower)?;
+    assert_owned_by(pda_follow, program_id)?;
+    assert_not_closed(pda_follow, CommonError::AccountClosed)?;
+
+    let follow_data = try_from_slice_unchecked::<Follow>(&pda_follow.data.borrow())?;
+    assert_initialized(&follow_data, ReviewError::UninitializedAccount)?;
+    if follow_data.discriminator != Follow::DISCRIMINATOR {
+        msg!("Account is not a follow");
+        return Err(ReviewError::InvalidDiscriminator.into());
+    }
+    if follow_data.follower != *follower.key {
+        msg!("Signer is not the follower");
+        return Err(CommonError::Unauthorized.into());
+    }
+
+    close_account(pda_follow, follower)?;
+    msg!("{} unfollowed {}", follower.key, follow_data.reviewer);
+
+    Ok(())
+}
+
+/// Sets whether the review takes comments and who may write them.
+///
+/// Accounts are `[reviewer, review, (session)]`, where a session with
+/// `Session::SCOPE_UPDATE` lets its key sign as the reviewer.
+pub fn set_comment_settings(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    comments_enabled: bool,
+    comment_policy: CommentPolicy,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let signer = next_account_info(account_info_iter)?;
+    let pda_review = next_account_info(account_info_iter)?;
+    let proofs = account_info_iter.as_slice();
+
+    let mut review_data = load_delegated_review(
+        program_id,
+        signer,
+        pda_review,
+        proofs,
+        Session::SCOPE_UPDATE,
+    )?;
+    // Reviews written before the settings existed may not have room for them
+    assert_max_len(
+        MovieAccountState::get_account_size(&review_data.title, &review_data.description),
+        pda_review.data_len(),
+        ReviewError::InvalidDataLength,
+    )?;
+
+    review_data.comment_settings = Some(CommentSettings {
+        comments_enabled,
+        comment_policy,
+    });
+    review_data.serialize(&mut &mut pda_review.data.borrow_mut()[..])?;
+    msg!(
+        "comments {} under {:?}",
+        if comments_enabled {
+            "enabled"
+        } else {
+            "disabled"
+        },
+        comment_policy
+    );
+
+    Ok(())
+}
+
+/// Blocks `user` from commenting on any of the signer's reviews. Comments
+/// they already wrote are kept.
+///
+/// Accounts are `[reviewer, mute, system_program]`.
+pub fn mute_user(program_id: &Pubkey, accounts: &[AccountInfo], user: Pubkey) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let reviewer = next_account_info(account_info_iter)?;
+    let pda_mute = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+
+    assert_signer(reviewer)?;
+    let bump_seed = assert_pda(
+        pda_mute,
+        &[Mute::SEED.as_bytes(), reviewer.key.as_ref(), user.as_ref()],
+        program_id,
+        ReviewError::InvalidPDA,
+    )?;
+    if pda_mute.owner == program_id {
+        msg!("{} already muted {}", reviewer.key, user);
+        return Err(ProgramError::AccountAlreadyInitialized);
+    }
+
+    create_pda_account(
+        reviewer,
+        pda_mute,
+        system_program,
+        Mute::get_account_size(),
+        program_id,
+        &[
+            Mute::SEED.as_bytes(),
+            reviewer.key.as_ref(),
+            user.as_ref(),
+            &[bump_seed],
+        ],
+    )?;
+    assert_rent_exempt(pda_mute, CommonError::NotRentExempt)?;
+
+    let mute_data = Mute {
+        discriminator: Mute::DISCRIMINATOR,
+        is_initialized: true,
+        reviewer: *reviewer.key,
+        muted: user,
+    };
+    mute_data.serialize(&mut &mut pda_mute.data.borrow_mut()[..])?;
+    msg!("{} muted {}", reviewer.key, user);
+
+    Ok(())
+}
+
+/// Closes the signer's mute, refunding its rent to them.
+///
+/// Accounts are `[reviewer, mute]`.
+pub fn unmute_user(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let reviewer = next_account_info(account_info_iter)?;
+    let pda_mute = next_account_info(account_info_iter)?;
+
+    assert_signer(reviewer)?;
+    assert_owned_by(pda_mute, program_id)?;
+    assert_not_closed(pda_mute, CommonError::AccountClosed)?;
+
+    let mute_data = try_from_slice_unchecked::<Mute>(&pda_mute.data.borrow())?;
+    assert_initialized(&mute_data, ReviewError::UninitializedAccount)?;
+    if mute_data.discriminator != Mute::DISCRIMINATOR {
+        msg!("Account is not a mute");
+        return Err(ReviewError::InvalidDiscriminator.into());
+    }
+    if mute_data.reviewer != *reviewer.key {
+        msg!("Signer did not create this mute");
+        return Err(CommonError::Unauthorized.into());
+    }
+
+    close_account(pda_mute, reviewer)?;
+    msg!("{} unmuted {}", reviewer.key, mute_data.muted);
+
+    Ok(())
+}
+
+/// Accounts are `[authority, session, system_program, config]`. Instructions
+/// in the session's scopes accept `session_key` as signer when the session
+/// is passed among their proofs. Sessions already open keep working if the
+/// feature is switched off.
+pub fn create_session(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    session_key: Pubkey,
+    expires_slot: u64,
+    scopes: u8,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let authority = next_account_info(account_info_iter)?;
+    let pda_session = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+    let pda_config = next_account_info(account_info_iter)?;
+
+    assert_signer(authority)?;
+    if scopes == 0 || scopes & !Session::ALL_SCOPES != 0 {
+        msg!("Unknown session scopes {:#04x}", scopes);
+        return Err(ReviewError::InvalidSessionScope.into());
+    }
+    let bump_seed = assert_pda(
+        pda_session,
+        &[
+            Session::SEED.as_bytes(),
+            authority.key.as_ref(),
+            session_key.as_ref(),
+        ],
+        program_id,
+        ReviewError::InvalidPDA,
+    )?;
+    if pda_session.owner == program_id {
+        msg!(
+            "{} already has a session for {}",
+            authority.key,
+            session_key
+        );
+        return Err(ProgramError::AccountAlreadyInitialized);
+    }
+
+    let slot = Clock::get()?.slot;
+    if expires_slot <= slot || expires_slot - slot > Session::MAX_DURATION_SLOTS {
+        msg!(
+            "Session must expire within {} slots of {}",
+            Session::MAX_DURATION_SLOTS,
+            slot
+        );
+        return Err(ReviewError::InvalidSessionExpiry.into());
+    }
+    assert_feature(
+        &load_config(program_id, pda_config)?,
+        ProgramConfig::FEATURE_SESSIONS,
+    )?;
+
+    create_pda_account(
+        authority,
+        pda_session,
+        system_program,
+        Session::get_account_size(),
+        program_id,
+        &[
+            Session::SEED.as_bytes(),
+            authority.key.as_ref(),
+            session_key.as_ref(),
+            &[bump_seed],
+        ],
+    )?;
+    assert_rent_exempt(pda_session, CommonError::NotRentExempt)?;
+
+    let session_data = Session {
+        discriminator: Session::DISCRIMINATOR,
+        is_initialized: true,
+        authority: *authority.key,
+        session_key,
+        expires_slot,
+        scopes,
+    };
+    session_data.serialize(&mut &mut pda_session.data.borrow_mut()[..])?;
+    msg!(
+        "{} created session {} until slot {}",
+        authority.key,
+        session_key,
+        expires_slot
+    );
+
+    Ok(())
+}
+
+/// Closes the signer's session, refunding its rent to them.
+///
+/// Accounts are `[authority, session]`.
+pub fn revoke_session(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let authority = next_account_info(account_info_iter)?;
+    let pda_session = next_account_info(account_info_iter)?;
+
+    assert_signer(authority)?;
+    assert_owned_by(pda_session, program_id)?;
+    assert_not_closed(pda_session, CommonError::AccountClosed)?;
+
+    let session_data = try_from_slice_unchecked::<Session>(&pda_session.data.borrow())?;
+    assert_initialized(&session_data, ReviewError::UninitializedAccount)?;
+    if session_data.discriminator != Session::DISCRIMINATOR {
+        msg!("Account is not a session");
+        return Err(ReviewError::InvalidDiscriminator.into());
+    }
+    if session_data.authority != *authority.key {
+        msg!("Signer did not create this session");
+        return Err(CommonError::Unauthorized.into());
+    }
+
+    close_account(pda_session, authority)?;
+    msg!(
+        "{} revoked session {}",
+        authority.key,
+        session_data.session_key
+    );
+
+    Ok(())
+}
+
+/// Writes a `ReviewSnapshot` of `reviews` at `["snapshot", slot]`. Each
+/// review is followed by its comment counter, and reviews must be passed in
+/// ascending key order so none is counted twice.
+///
+/// Accounts are
+/// `[authority, config, snapshot, system_program, (review, counter)...]`.
+pub fn write_snapshot(program_id: &Pubkey, accounts: &[AccountInfo], slot: u64) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let authority = next_account_info(account_info_iter)?;
+    let pda_config = next_account_info(account_info_iter)?;
+    let pda_snapshot = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+    let reviews = account_info_iter.as_slice();
+
+    assert_signer(authority)?;
+    assert_config_authority(program_id, authority, pda_config)?;
+
+    let slot_bytes = slot.to_le_bytes();
+    let bump_seed = assert_pda(
+        pda_snapshot,
+        &[ReviewSnapshot::SEED.as_bytes(), &slot_bytes],
+        program_id,
+        ReviewError::InvalidPDA,
+    )?;
+    if pda_snapshot.owner == program_id {
+        msg!("Snapshot for slot {} already written", slot);
+        return Err(ReviewError::SnapshotExists.into());
+    }
+
+    if !reviews.len().is_multiple_of(2) {
+        msg!("Every review must be followed by its comment counter");
+        return Err(ProgramError::NotEnoughAccountKeys);
+    }
+    let mut entries = Vec::with_capacity(reviews.len() / 2);
+    let mut previous: Option<&Pubkey> = None;
+    for pair in reviews.chunks_exact(2) {
+        let (review, counter) = (&pair[0], &pair[1]);
+        if previous.is_some_and(|previous| review.key <= previous) {
+            return Err(ReviewError::UnorderedSnapshotReviews.into());
+        }
+        previous = Some(review.key);
+
+        assert_owned_by(review, program_id)?;
+        assert_not_closed(review, CommonError::AccountClosed)?;
+        let rating = match decode_account(&review.data.borrow())? {
+            MovieReviewAccount::Review(state) | MovieReviewAccount::ForeignReview(state, _) => {
+                assert_initialized(&state, ReviewError::UninitializedAccount)?;
+                state.rating
+            }
+            _ => {
+                msg!("Account {} is not a review", review.key);
+                return Err(ReviewError::InvalidDiscriminator.into());
+            }
+        };
+
+        assert_owned_by(counter, program_id)?;
+        assert_pda(
+            counter,
+            &[review.key.as_ref(), "comment".as_ref()],
+            program_id,
+            ReviewError::InvalidPDA,
+        )?;
+        let comments = match decode_account(&counter.data.borrow())? {
+            MovieReviewAccount::CommentCounter(counter_data) => counter_data.active_comments,
+            _ => return Err(ReviewError::InvalidDiscriminator.into()),
+        };
+
+        entries.push(SnapshotEntry {
+            review: *review.key,
+            rating,
+            comments,
+        });
+    }
+
+    let written_slot = Clock::get()?.slot;
+    if slot > written_slot {
+        msg!("Slot {} is after the current slot {}", slot, written_slot);
+        return Err(ReviewError::InvalidSnapshotSlot.into());
+    }
+
+    msg!("creating snapshot account");
+    create_pda_account(
+        authority,
+        pda_snapshot,
+        system_program,
+        ReviewSnapshot::get_account_size(),
+        program_id,
+        &[ReviewSnapshot::SEED.as_bytes(), &slot_bytes, &[bump_seed]],
+    )?;
+
+    let snapshot_data = ReviewSnapshot::summarize(slot, written_slot, entries);
+    snapshot_data.serialize(&mut &mut pda_snapshot.data.borrow_mut()[..])?;
+    msg!(
+        "snapshot of {} reviews written for slot {}",
+        snapshot_data.review_count,
+        slot
+    );
+
+    Ok(())
+}
+
+/// Adds a reward token tip to the global stats. Only the configured tip
+/// program can call it, through a CPI signed by its
+/// `GlobalStats::TIP_AUTHORITY_SEED` PDA.
+///
+/// Accounts are `[tip_authority, config, stats, payer, system_program,
+/// review, hot_feed]`, where `payer` funds the stats and the feed if this is
+/// their first update and `review` is the tipped review.
+pub fn record_tip(program_id: &Pubkey, accounts: &[AccountInfo], amount: u64) -> ProgramResult {
+    record_tip_with(program_id, accounts, |stats| stats.record_tip(amount))?;
+    msg!("recorded tip of {}", amount);
+
+    Ok(())
+}
+
+/// Adds a tip of `lamports` to the global stats, like `record_tip`.
+///
+/// Accounts are those of `record_tip`.
+pub fn record_sol_tip(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    lamports: u64,
+) -> ProgramResult {
+    record_tip_with(program_id, accounts, |stats| stats.record_sol_tip(lamports))?;
+    msg!("recorded tip of {} lamports", lamports);
+
+    Ok(())
+}
+
+/// Checks the tip program signed for the tip, applies `update` to the global
+/// stats and counts the tip as activity on the review.
+fn record_tip_with(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    update: impl FnOnce(&mut GlobalStats) -> Option<()>,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let tip_authority = next_account_info(account_info_iter)?;
+    let pda_config = next_account_info(account_info_iter)?;
+    let pda_stats = next_account_info(account_info_iter)?;
+    let payer = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+    let pda_review = next_account_info(account_info_iter)?;
+    let pda_feed = next_account_info(account_info_iter)?;
+
+    assert_signer(tip_authority)?;
+
+    let config_data = load_config(program_id, pda_config)?;
+    if config_data.tip_program == Pubkey::default()
+        || *tip_authority.key
+            != Pubkey::find_program_address(
+                &[GlobalStats::TIP_AUTHORITY_SEED.as_bytes()],
+                &config_data.tip_program,
+            )
+            .0
+    {
+        msg!("Signer is not the tip program's authority");
+        return Err(ReviewError::InvalidTipAuthority.into());
+    }
+    assert_feature(&config_data, ProgramConfig::FEATURE_TIPS)?;
+    assert_owned_by(pda_review, program_id)?;
+    load_review_header(&pda_review.data.borrow())?;
+
+    update_global_stats(program_id, pda_stats, payer, system_program, update)?;
+    record_activity(
+        program_id,
+        pda_feed,
+        payer,
+        system_program,
+        pda_review.key,
+        HotFeed::TIP_WEIGHT,
+    )
+}
+
+/// Accounts are `[authority, config, system_program, audit_log]`. The
+/// authority pays to grow a config created before the tip program field.
+pub fn set_tip_program(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    tip_program: Pubkey,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let authority = next_account_info(account_info_iter)?;
+    let pda_config = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+    let pda_audit = next_account_info(account_info_iter)?;
+
+    assert_signer(authority)?;
+
+    let mut config_data = load_config(program_id, pda_config)?;
+    assert_initialized(&config_data, ReviewError::UninitializedAccount)?;
+
+    if config_data.authority != *authority.key {
+        msg!("Signer is not the config authority");
+        return Err(CommonError::Unauthorized.into());
+    }
+
+    config_data.tip_program = tip_program;
+
+    if pda_config.data_len() < ProgramConfig::get_account_size() {
+        resize_account(
+            pda_config,
+            authority,
+            system_program,
+            ProgramConfig::get_account_size(),
+        )?;
+    }
+
+    assert_rent_exempt(pda_config, CommonError::NotRentExempt)?;
+    config_data.serialize(&mut &mut pda_config.data.borrow_mut()[..])?;
+    record_admin_action(
+        program_id,
+        pda_audit,
+        authority,
+        system_program,
+        AdminAction::SetTipProgram,
+        pda_config.key,
+    )?;
+    msg!("tip program set to {}", tip_program);
+
+    Ok(())
+}
+
+/// Accounts are `[authority, config, system_program, audit_log]`. The
+/// authority pays to grow a config created before the feature flags.
+pub fn set_features(program_id: &Pubkey, accounts: &[AccountInfo], features: u64) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let authority = next_account_info(account_info_iter)?;
+    let pda_config = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+    let pda_audit = next_account_info(account_info_iter)?;
+
+    assert_signer(authority)?;
+
+    let mut config_data = load_config(program_id, pda_config)?;
+    assert_initialized(&config_data, ReviewError::UninitializedAccount)?;
+
+    if config_data.authority != *authority.key {
+        msg!("Signer is not the config authority");
+        return Err(CommonError::Unauthorized.into());
+    }
+
+    if features & !ProgramConfig::ALL_FEATURES != 0 {
+        msg!("Unknown feature bits {:#x}", features);
+        return Err(ReviewError::UnknownFeature.into());
+    }
+    config_data.features = features | ProgramConfig::FEATURES_SET;
+
+    if pda_config.data_len() < ProgramConfig::get_account_size() {
+        resize_account(
+            pda_config,
+            authority,
+            system_program,
+            ProgramConfig::get_account_size(),
+        )?;
+    }
+
+    assert_rent_exempt(pda_config, CommonError::NotRentExempt)?;
+    config_data.serialize(&mut &mut pda_config.data.borrow_mut()[..])?;
+    record_admin_action(
+        program_id,
+        pda_audit,
+        authority,
+        system_program,
+        AdminAction::SetFeatures,
+        pda_config.key,
+    )?;
+    msg!("features set to {:#x}", features);
+
+    Ok(())
+}
+
+/// Accounts are `[authority, config, system_program, audit_log]`. The
+/// authority pays to grow a config created before the removal window.
+pub fn set_removal_window(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    slots: u64,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let authority = next_account_info(account_info_iter)?;
+    let pda_config = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+    let pda_audit = next_account_info(account_info_iter)?;
+
+    assert_signer(authority)?;
+
+    let mut config_data = load_config(program_id, pda_config)?;
+    assert_initialized(&config_data, ReviewError::UninitializedAccount)?;
+
+    if config_data.authority != *authority.key {
+        msg!("Signer is not the config authority");
+        return Err(CommonError::Unauthorized.into());
+    }
+
+    config_data.removal_window = slots;
+
+    if pda_config.data_len() < ProgramConfig::get_account_size() {
+        resize_account(
+            pda_config,
+            authority,
+            system_program,
+            ProgramConfig::get_account_size(),
+        )?;
+    }
+
+    assert_rent_exempt(pda_config, CommonError::NotRentExempt)?;
+    config_data.serialize(&mut &mut pda_config.data.borrow_mut()[..])?;
+    record_admin_action(
+        program_id,
+        pda_audit,
+        authority,
+        system_program,
+        AdminAction::SetRemovalWindow,
+        pda_config.key,
+    )?;
+    msg!(
+        "removal window set to {} slots",
+        config_data.removal_window()
+    );
+
+    Ok(())
+}
+
+/// Accounts are `[authority, config, system_program, audit_log]`. The
+/// authority pays to grow a config created before the age gate.
+pub fn set_age_gate(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    min_wallet_age: u64,
+    age_attester: Pubkey,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let authority = next_account_info(account_info_iter)?;
+    let pda_config = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+    let pda_audit = next_account_info(account_info_iter)?;
+
+    assert_signer(authority)?;
+
+    let mut config_data = load_config(program_id, pda_config)?;
+    assert_initialized(&config_data, ReviewError::UninitializedAccount)?;
+
+    if config_data.authority != *authority.key {
+        msg!("Signer is not the config authority");
+        return Err(CommonError::Unauthorized.into());
+    }
+
+    config_data.min_wallet_age = min_wallet_age;
+    config_data.age_attester = age_attester;
+
+    if pda_config.data_len() < ProgramConfig::get_account_size() {
+        resize_account(
+            pda_config,
+            authority,
+            system_program,
+            ProgramConfig::get_account_size(),
+        )?;
+    }
+
+    assert_rent_exempt(pda_config, CommonError::NotRentExempt)?;
+    config_data.serialize(&mut &mut pda_config.data.borrow_mut()[..])?;
+    record_admin_action(
+        program_id,
+        pda_audit,
+        authority,
+        system_program,
+        AdminAction::SetAgeGate,
+        pda_config.key,
+    )?;
+    msg!(
+        "wallets must be {} slots old, attested by {}",
+        min_wallet_age,
+        age_attester
+    );
+
+    Ok(())
+}
+
+/// Accounts are `[authority, config, system_program, audit_log]`. The
+/// authority pays to grow a config created before the hide threshold.
+/// Comments already voted on keep their flag until their next vote.
+pub fn set_hide_threshold(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    threshold: i64,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let authority = next_account_info(account_info_iter)?;
+    let pda_config = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+    let pda_audit = next_account_info(account_info_iter)?;
+
+    assert_signer(authority)?;
+
+    let mut config_data = load_config(program_id, pda_config)?;
+    assert_initialized(&config_data, ReviewError::UninitializedAccount)?;
+
+    if config_data.authority != *authority.key {
+        msg!("Signer is not the config authority");
+        return Err(CommonError::Unauthorized.into());
+    }
+
+    config_data.hide_threshold = threshold;
+
+    if pda_config.data_len() < ProgramConfig::get_account_size() {
+        resize_account(
+            pda_config,
+            authority,
+            system_program,
+            ProgramConfig::get_account_size(),
+        )?;
+    }
+
+    assert_rent_exempt(pda_config, CommonError::NotRentExempt)?;
+    config_data.serialize(&mut &mut pda_config.data.borrow_mut()[..])?;
+    record_admin_action(
+        program_id,
+        pda_audit,
+        authority,
+        system_program,
+        AdminAction::SetHideThreshold,
+        pda_config.key,
+    )?;
+    msg!("comments scoring below {} are hidden", threshold);
+
+    Ok(())
+}
+
+/// Accounts are `[authority, config, system_program, audit_log]`. The
+/// authority pays to grow a config created before the edit burn.
+pub fn set_edit_burn(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    mint: Pubkey,
+    amount: u64,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let authority = next_account_info(account_info_iter)?;
+    let pda_config = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+    let pda_audit = next_account_info(account_info_iter)?;
+
+    assert_signer(authority)?;
+
+    let mut config_data = load_config(program_id, pda_config)?;
+    assert_initialized(&config_data, ReviewError::UninitializedAccount)?;
+
+    if config_data.authority != *authority.key {
+        msg!("Signer is not the config authority");
+        return Err(CommonError::Unauthorized.into());
+    }
+
+    config_data.edit_burn_mint = mint;
+    config_data.edit_burn_amount = amount;
+
+    if pda_config.data_len() < ProgramConfig::get_account_size() {
+        resize_account(
+            pda_config,
+            authority,
+            system_program,
+            ProgramConfig::get_account_size(),
+        )?;
+    }
+
+    assert_rent_exempt(pda_config, CommonError::NotRentExempt)?;
+    config_data.serialize(&mut &mut pda_config.data.borrow_mut()[..])?;
+    record_admin_action(
+        program_id,
+        pda_audit,
+        authority,
+        system_program,
+        AdminAction::SetEditBurn,
+        pda_config.key,
+    )?;
+    msg!("paid edits burn {} of {}", amount, mint);
+
+    Ok(())
+}
+
+/// Loads the pending removal of `review`.
+fn load_pending_removal(
+    program_id: &Pubkey,
+    pda_removal: &AccountInfo,
+    review: &Pubkey,
+) -> Result<PendingRemoval, ProgramError> {
+    assert_owned_by(pda_removal, program_id)?;
+    assert_not_closed(pda_removal, CommonError::AccountClosed)?;
+    assert_pda(
+        pda_removal,
+        &[PendingRemoval::SEED.as_bytes(), review.as_ref()],
+        program_id,
+        ReviewError::InvalidPDA,
+    )?;
+
+    let removal_data = try_from_slice_unchecked::<PendingRemoval>(&pda_removal.data.borrow())?;
+    if removal_data.discriminator != PendingRemoval::DISCRIMINATOR {
+        msg!("Account is not a pending removal");
+        return Err(ReviewError::InvalidDiscriminator.into());
+    }
+    Ok(removal_data)
+}
+
+/// Proposes removing a review. It stays up while its author may appeal,
+/// for the config's removal window.
+///
+/// Accounts are `[authority, config, review, pending_removal, system_program,
+/// audit_log]`. The authority pays for the pending removal.
+pub fn propose_removal(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let authority = next_account_info(account_info_iter)?;
+    let pda_config = next_account_info(account_info_iter)?;
+    let pda_review = next_account_info(account_info_iter)?;
+    let pda_removal = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+    let pda_audit = next_account_info(account_info_iter)?;
+
+    assert_signer(authority)?;
+
+    let config_data = load_config(program_id, pda_config)?;
+    assert_initialized(&config_data, ReviewError::UninitializedAccount)?;
+
+    if config_data.authority != *authority.key {
+        msg!("Signer is not the config authority");
+        return Err(CommonError::Unauthorized.into());
+    }
+
+    let review_data = load_review(program_id, pda_review)?;
+
+    let bump_seed = assert_pda(
+        pda_removal,
+        &[PendingRemoval::SEED.as_bytes(), pda_review.key.as_ref()],
+        program_id,
+        ReviewError::InvalidPDA,
+    )?;
+    if pda_removal.owner == program_id {
+        msg!("Removal of {} already proposed", pda_review.key);
+        return Err(ReviewError::RemovalPending.into());
+    }
+
+    let slot = Clock::get()?.slot;
+    let appeal_deadline = slot
+        .checked_add(config_data.removal_window())
+        .ok_or(CommonError::Overflow)?;
+
+    msg!("creating pending removal account");
+    create_pda_account(
+        authority,
+        pda_removal,
+        system_program,
+        PendingRemoval::get_account_size(),
+        program_id,
+        &[
+            PendingRemoval::SEED.as_bytes(),
+            pda_review.key.as_ref(),
+            &[bump_seed],
+        ],
+    )?;
+    let removal_data = PendingRemoval {
+        discriminator: PendingRemoval::DISCRIMINATOR,
+        is_initialized: true,
+        review: *pda_review.key,
+        author: review_data.reviewer,
+        proposed_slot: slot,
+        appeal_deadline,
+        appeal: AppealStatus::None,
+    };
+    removal_data.serialize(&mut &mut pda_removal.data.borrow_mut()[..])?;
+
+    emit(&ReviewEvent::RemovalProposed {
+        recipient: review_data.reviewer,
+        review: *pda_review.key,
+        appeal_deadline,
+    });
+    record_admin_action(
+        program_id,
+        pda_audit,
+        authority,
+        system_program,
+        AdminAction::ProposeRemoval,
+        pda_review.key,
+    )?;
+    msg!(
+        "removal of {} appealable until slot {}",
+        pda_review.key,
+        appeal_deadline
+    );
+
+    Ok(())
+}
+
+/// Accounts are `[author, review, pending_removal]`.
+pub fn appeal_removal(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let author = next_account_info(account_info_iter)?;
+    let pda_review = next_account_info(account_info_iter)?;
+    let pda_removal = next_account_info(account_info_iter)?;
+
+    assert_signer(author)?;
+
+    let mut removal_data = load_pending_removal(program_id, pda_removal, pda_review.key)?;
+    if removal_data.author != *author.key {
+        msg!("Signer did not write this review");
+        return Err(CommonError::Unauthorized.into());
+    }
+    if removal_data.appeal != AppealStatus::None {
+        msg!("Removal was already appealed");
+        return Err(ReviewError::AlreadyAppealed.into());
+    }
+    if Clock::get()?.slot > removal_data.appeal_deadline {
+        msg!("Appeals closed at slot {}", removal_data.appeal_deadline);
+        return Err(ReviewError::AppealWindowClosed.into());
+    }
+
+    removal_data.appeal = AppealStatus::Filed;
+    removal_data.serialize(&mut &mut pda_removal.data.borrow_mut()[..])?;
+    emit(&ReviewEvent::RemovalAppealed {
+        review: *pda_review.key,
+    });
+
+    Ok(())
+}
+
+/// Upholding the appeal cancels the removal, refunding the pending removal's
+/// rent to the authority.
+///
+/// Accounts are `[authority, config, review, pending_removal, system_program,
+/// audit_log]`.
+pub fn resolve_appeal(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    uphold: bool,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let authority = next_account_info(account_info_iter)?;
+    let pda_config = next_account_info(account_info_iter)?;
+    let pda_review = next_account_info(account_info_iter)?;
+    let pda_removal = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+    let pda_audit = next_account_info(account_info_iter)?;
+
+    assert_signer(authority)?;
+    assert_config_authority(program_id, authority, pda_config)?;
 
-    // Check if counter_data is initialized
-    if !counter_data.is_initialized() {
-        msg!("Counter account not initialized yet");
-        return Err(ReviewError::UninitializedAccount.into());
+    let mut removal_data = load_pending_removal(program_id, pda_removal, pda_review.key)?;
+    if removal_data.appeal != AppealStatus::Filed {
+        msg!("Removal of {} was not appealed", pda_review.key);
+        return Err(ReviewError::NoAppealFiled.into());
     }
 
-    // Check if the instruction is signed
-    if !commenter.is_signer {
-        msg!("Missing required signature");
-        return Err(ProgramError::MissingRequiredSignature);
+    if uphold {
+        close_account(pda_removal, authority)?;
+        msg!("appeal upheld, {} stays up", pda_review.key);
+    } else {
+        removal_data.appeal = AppealStatus::Rejected;
+        removal_data.serialize(&mut &mut pda_removal.data.borrow_mut()[..])?;
+        msg!("appeal rejected");
     }
 
-    // Check if the account owner is the program
-    if pda_review.owner != program_id {
-        msg!("Invalid account owner");
-        return Err(ProgramError::IllegalOwner);
+    emit(&ReviewEvent::AppealResolved {
+        recipient: removal_data.author,
+        review: *pda_review.key,
+        upheld: uphold,
+    });
+    record_admin_action(
+        program_id,
+        pda_audit,
+        authority,
+        system_program,
+        AdminAction::ResolveAppeal,
+        pda_review.key,
+    )
+}
+
+/// Removes a review once its appeal window ended without an upheld appeal.
+/// As with `DeleteReview`, a review with comments is emptied by `cascade`
+/// calls first; the call that closes it refunds its author and the pending
+/// removal's rent to the authority, who pays for everything else.
+///
+/// Accounts are `[authority, pending_removal, audit_log]` followed by those
+/// of `DeleteReview`, with the author in place of its signer.
+pub fn finalize_removal(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    cascade: bool,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let authority = next_account_info(account_info_iter)?;
+    let pda_removal = next_account_info(account_info_iter)?;
+    let pda_audit = next_account_info(account_info_iter)?;
+    let review_accounts = account_info_iter.as_slice();
+    let author = next_account_info(account_info_iter)?;
+    let pda_review = next_account_info(account_info_iter)?;
+    // Skipping the counter and profile, then the global and movie stats and
+    // the rating index
+    let pda_config = account_info_iter
+        .nth(2)
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+    let system_program = account_info_iter
+        .nth(3)
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+    assert_signer(authority)?;
+    assert_config_authority(program_id, authority, pda_config)?;
+
+    let removal_data = load_pending_removal(program_id, pda_removal, pda_review.key)?;
+    if removal_data.appeal == AppealStatus::Filed {
+        msg!("Removal of {} has an unresolved appeal", pda_review.key);
+        return Err(ReviewError::AppealPending.into());
+    }
+    if *author.key != removal_data.author {
+        msg!(
+            "Rent of {} goes back to {}",
+            pda_review.key,
+            removal_data.author
+        );
+        return Err(ReviewError::WrongRefundRecipient.into());
+    }
+    if Clock::get()?.slot <= removal_data.appeal_deadline {
+        msg!(
+            "Appeals are open until slot {}",
+            removal_data.appeal_deadline
+        );
+        return Err(ReviewError::AppealWindowOpen.into());
     }
 
-    let (pda, bump_seed) = Pubkey::find_program_address(
-        &[
-            pda_review.key.as_ref(),
-            counter_data.counter.to_be_bytes().as_ref(),
-        ],
+    // The author may have deleted the review themselves in the meantime
+    if pda_review.owner == program_id {
+        let review_data = load_review(program_id, pda_review)?;
+        if !close_review(
+            program_id,
+            &review_data,
+            authority,
+            review_accounts,
+            cascade,
+        )? {
+            return Ok(());
+        }
+    }
+
+    close_account(pda_removal, authority)?;
+    record_admin_action(
         program_id,
-    );
+        pda_audit,
+        authority,
+        system_program,
+        AdminAction::FinalizeRemoval,
+        pda_review.key,
+    )?;
+    msg!("removed {}", pda_review.key);
+
+    Ok(())
+}
+
+/// Commits the review to the off-chain body hashing to `hash` at `uri`,
+/// creating the body commitment on first use with the reviewer paying its
+/// rent. The description is replaced by `uri`, growing the review if it
+/// does not fit.
+///
+/// Accounts are `[reviewer, review, body_commitment, system_program]`.
+pub fn update_body_hash(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    hash: [u8; 32],
+    uri: String,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let reviewer = next_account_info(account_info_iter)?;
+    let pda_review = next_account_info(account_info_iter)?;
+    let pda_commitment = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
 
-    if pda != *pda_comment.key {
-        msg!("Invalid seeds for PDA");
-        return Err(ReviewError::InvalidPDA.into());
+    assert_signer(reviewer)?;
+    assert_owned_by(pda_review, program_id)?;
+    let shortfall = upgrade_shortfall(&pda_review.data.borrow())?;
+    if shortfall > 0 && load_review_header(&pda_review.data.borrow())?.reviewer == *reviewer.key {
+        let new_len = pda_review.data_len() + shortfall;
+        resize_account(pda_review, reviewer, system_program, new_len)?;
     }
 
-    let total_len = MovieComment::get_account_size(&comment);
-    if total_len > MovieComment::MAX_ACCOUNT_SIZE {
-        msg!("Input data exceeds max length");
-        return Err(ReviewError::InvalidDataLength.into());
+    let mut review_data = load_own_review(program_id, reviewer, pda_review)?;
+    if !MovieComment::is_valid_media_uri(&uri) {
+        msg!(
+            "Body URI must be an https, ipfs or ar URI of at most {} bytes",
+            MovieComment::MAX_MEDIA_URI_LEN
+        );
+        return Err(ReviewError::InvalidBodyUri.into());
+    }
+
+    let bump_seed = assert_pda(
+        pda_commitment,
+        &[BodyCommitment::SEED.as_bytes(), pda_review.key.as_ref()],
+        program_id,
+        ReviewError::InvalidPDA,
+    )?;
+    let mut commitment_data = if pda_commitment.owner == program_id {
+        let commitment_data =
+            try_from_slice_unchecked::<BodyCommitment>(&pda_commitment.data.borrow())?;
+        if commitment_data.discriminator != BodyCommitment::DISCRIMINATOR {
+            msg!("Account is not a body commitment");
+            return Err(ReviewError::InvalidDiscriminator.into());
+        }
+        commitment_data
+    } else {
+        msg!("creating body commitment account");
+        create_pda_account(
+            reviewer,
+            pda_commitment,
+            system_program,
+            BodyCommitment::get_account_size(),
+            program_id,
+            &[
+                BodyCommitment::SEED.as_bytes(),
+                pda_review.key.as_ref(),
+                &[bump_seed],
+            ],
+        )?;
+        BodyCommitment {
+            discriminator: BodyCommitment::DISCRIMINATOR,
+            is_initialized: true,
+            review: *pda_review.key,
+            hash: [0; 32],
+        }
+    };
+    commitment_data.hash = hash;
+    commitment_data.serialize(&mut &mut pda_commitment.data.borrow_mut()[..])?;
+
+    review_data.description = uri;
+    review_data.body_format = BodyFormat::OffChain;
+    let new_size =
+        MovieAccountState::get_account_size(&review_data.title, &review_data.description);
+    if pda_review.data_len() < new_size {
+        resize_account(pda_review, reviewer, system_program, new_size)?;
     }
+    review_data.serialize(&mut &mut pda_review.data.borrow_mut()[..])?;
+    msg!("review body committed to {}", review_data.description);
+
+    Ok(())
+}
+
+/// Fingerprints the review's current description, creating the fingerprint
+/// account on first use with the payer covering its rent.
+///
+/// Accounts are `[payer, review, fingerprint, system_program]`.
+pub fn record_fingerprint(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let payer = next_account_info(account_info_iter)?;
+    let pda_review = next_account_info(account_info_iter)?;
+    let pda_fingerprint = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
 
-    let rent = Rent::get()?;
-    let rent_lamports = rent.minimum_balance(MovieComment::MAX_ACCOUNT_SIZE);
+    assert_signer(payer)?;
+    let review_data = load_review(program_id, pda_review)?;
 
-    let create_pda_comment = system_instruction::create_account(
-        commenter.key,
-        pda_comment.key,
-        rent_lamports,
-        MovieComment::MAX_ACCOUNT_SIZE.try_into().unwrap(),
+    let bump_seed = assert_pda(
+        pda_fingerprint,
+        &[ReviewFingerprint::SEED.as_bytes(), pda_review.key.as_ref()],
         program_id,
-    );
+        ReviewError::InvalidPDA,
+    )?;
+    let mut fingerprint_data = if pda_fingerprint.owner == program_id {
+        load_fingerprint(program_id, pda_fingerprint, pda_review.key)?
+    } else {
+        msg!("creating fingerprint account");
+        create_pda_account(
+            payer,
+            pda_fingerprint,
+            system_program,
+            ReviewFingerprint::get_account_size(),
+            program_id,
+            &[
+                ReviewFingerprint::SEED.as_bytes(),
+                pda_review.key.as_ref(),
+                &[bump_seed],
+            ],
+        )?;
+        ReviewFingerprint {
+            discriminator: ReviewFingerprint::DISCRIMINATOR,
+            is_initialized: true,
+            review: *pda_review.key,
+            fingerprint: 0,
+            slot: 0,
+        }
+    };
 
-    invoke_signed(
-        &create_pda_comment,
+    fingerprint_data.fingerprint = fingerprint::simhash(&review_data.description);
+    fingerprint_data.slot = Clock::get()?.slot;
+    fingerprint_data.serialize(&mut &mut pda_fingerprint.data.borrow_mut()[..])?;
+    msg!("fingerprint {:016x}", fingerprint_data.fingerprint);
+
+    Ok(())
+}
+
+/// Loads the fingerprint recorded for `review`.
+fn load_fingerprint(
+    program_id: &Pubkey,
+    pda_fingerprint: &AccountInfo,
+    review: &Pubkey,
+) -> Result<ReviewFingerprint, ProgramError> {
+    assert_pda(
+        pda_fingerprint,
+        &[ReviewFingerprint::SEED.as_bytes(), review.as_ref()],
+        program_id,
+        ReviewError::InvalidPDA,
+    )?;
+    assert_owned_by(pda_fingerprint, program_id)?;
+    let fingerprint_data =
+        try_from_slice_unchecked::<ReviewFingerprint>(&pda_fingerprint.data.borrow())?;
+    assert_initialized(&fingerprint_data, ReviewError::UninitializedAccount)?;
+    if fingerprint_data.discriminator != ReviewFingerprint::DISCRIMINATOR {
+        msg!("Account is not a review fingerprint");
+        return Err(ReviewError::InvalidDiscriminator.into());
+    }
+    Ok(fingerprint_data)
+}
+
+/// Flags `review` as a copy of `original` for the config authority. Both
+/// fingerprints must be current, so a review edited since it was
+/// fingerprinted has to be recorded again first.
+///
+/// Accounts are `[challenger, review, review_fingerprint, original,
+/// original_fingerprint, challenge, system_program]`.
+pub fn challenge_duplicate(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let challenger = next_account_info(account_info_iter)?;
+    let pda_review = next_account_info(account_info_iter)?;
+    let pda_review_fingerprint = next_account_info(account_info_iter)?;
+    let pda_original = next_account_info(account_info_iter)?;
+    let pda_original_fingerprint = next_account_info(account_info_iter)?;
+    let pda_challenge = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+
+    assert_signer(challenger)?;
+    if pda_review.key == pda_original.key {
+        msg!("A review cannot duplicate itself");
+        return Err(ReviewError::NotDuplicate.into());
+    }
+
+    let mut fingerprints = [0; 2];
+    for (fingerprint, (pda_target, pda_fingerprint)) in fingerprints.iter_mut().zip([
+        (pda_review, pda_review_fingerprint),
+        (pda_original, pda_original_fingerprint),
+    ]) {
+        let target_data = load_review(program_id, pda_target)?;
+        let fingerprint_data = load_fingerprint(program_id, pda_fingerprint, pda_target.key)?;
+        if fingerprint_data.fingerprint != fingerprint::simhash(&target_data.description) {
+            msg!("Fingerprint of {} is stale", pda_target.key);
+            return Err(ReviewError::StaleFingerprint.into());
+        }
+        *fingerprint = fingerprint_data.fingerprint;
+    }
+    let distance = fingerprint::distance(fingerprints[0], fingerprints[1]);
+    if distance > fingerprint::MAX_DUPLICATE_DISTANCE {
+        msg!(
+            "Fingerprints are {} bits apart, at most {} allowed",
+            distance,
+            fingerprint::MAX_DUPLICATE_DISTANCE
+        );
+        return Err(ReviewError::NotDuplicate.into());
+    }
+
+    let bump_seed = assert_pda(
+        pda_challenge,
         &[
-            commenter.clone(),
-            pda_comment.clone(),
-            system_program.clone(),
+            DuplicateChallenge::SEED.as_bytes(),
+            pda_review.key.as_ref(),
+            pda_original.key.as_ref(),
         ],
-        &[&[
+        program_id,
+        ReviewError::InvalidPDA,
+    )?;
+    if pda_challenge.owner == program_id {
+        msg!(
+            "{} was already flagged as a copy of {}",
+            pda_review.key,
+            pda_original.key
+        );
+        return Err(ProgramError::AccountAlreadyInitialized);
+    }
+
+    let slot = Clock::get()?.slot;
+    msg!("creating duplicate challenge account");
+    create_pda_account(
+        challenger,
+        pda_challenge,
+        system_program,
+        DuplicateChallenge::get_account_size(),
+        program_id,
+        &[
+            DuplicateChallenge::SEED.as_bytes(),
             pda_review.key.as_ref(),
-            counter_data.counter.to_le_bytes().as_ref(),
+            pda_original.key.as_ref(),
             &[bump_seed],
-        ]],
+        ],
     )?;
-    msg!("comment PDA created: {}", pda);
+    let challenge_data = DuplicateChallenge {
+        discriminator: DuplicateChallenge::DISCRIMINATOR,
+        is_initialized: true,
+        challenger: *challenger.key,
+        review: *pda_review.key,
+        original: *pda_original.key,
+        distance: distance as u8,
+        slot,
+    };
+    challenge_data.serialize(&mut &mut pda_challenge.data.borrow_mut()[..])?;
+    emit(&ReviewEvent::DuplicateFlagged {
+        challenger: *challenger.key,
+        review: *pda_review.key,
+        original: *pda_original.key,
+        distance: challenge_data.distance,
+    });
 
-    let mut comment_data = try_from_slice_unchecked::<MovieComment>(&pda_comment.data.borrow())?;
+    Ok(())
+}
 
-    msg!("checking if comment account is initialized");
-    if comment_data.is_initialized() {
-        msg!("Account already initialized");
-        return Err(ReviewError::UninitializedAccount.into());
+/// Likes or reports a standalone comment, or switches the voter's earlier
+/// vote on it. A new vote pays the `FeeKind::Vote` fee and the rent of the
+/// vote, and of the comment's karma on its first vote.
+///
+/// Likes count toward the review's place in the hot feed, reports do not.
+///
+/// Accounts are `[voter, comment, vote, karma, config, system_program,
+/// hot_feed, proofs...]`, where the proofs can exempt the voter from the
+/// fee.
+pub fn vote_on_comment(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    kind: CommentVoteKind,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let voter = next_account_info(account_info_iter)?;
+    let pda_comment = next_account_info(account_info_iter)?;
+    let pda_vote = next_account_info(account_info_iter)?;
+    let pda_karma = next_account_info(account_info_iter)?;
+    let pda_config = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+    let pda_feed = next_account_info(account_info_iter)?;
+    let proofs = account_info_iter.as_slice();
+
+    assert_signer(voter)?;
+    assert_owned_by(pda_comment, program_id)?;
+    assert_not_closed(pda_comment, CommonError::AccountClosed)?;
+    let MovieReviewAccount::Comment(comment_data) = decode_account(&pda_comment.data.borrow())?
+    else {
+        msg!("Account {} is not a comment", pda_comment.key);
+        return Err(ReviewError::InvalidDiscriminator.into());
+    };
+    if comment_data.commenter == *voter.key {
+        msg!("Commenter cannot vote on their own comment");
+        return Err(ReviewError::SelfVote.into());
     }
+    let config = load_config(program_id, pda_config)?;
 
-    comment_data.discriminator = MovieComment::DISCRIMINATOR.to_string();
-    comment_data.is_initialized = true;
-    comment_data.reviewer = *pda_review.key;
-    comment_data.commenter = *commenter.key;
-    comment_data.comment = comment;
-    comment_data.count = counter_data.counter;
+    let vote_seeds = [
+        CommentVote::SEED.as_bytes(),
+        pda_comment.key.as_ref(),
+        voter.key.as_ref(),
+    ];
+    let vote_bump = assert_pda(pda_vote, &vote_seeds, program_id, ReviewError::InvalidPDA)?;
+    let karma_bump = assert_pda(
+        pda_karma,
+        &[CommentKarma::SEED.as_bytes(), pda_comment.key.as_ref()],
+        program_id,
+        ReviewError::InvalidPDA,
+    )?;
+
+    let mut karma_data = if pda_karma.owner == program_id {
+        let karma_data = try_from_slice_unchecked::<CommentKarma>(&pda_karma.data.borrow())?;
+        assert_initialized(&karma_data, ReviewError::UninitializedAccount)?;
+        if karma_data.discriminator != CommentKarma::DISCRIMINATOR {
+            msg!("Account is not a comment's karma");
+            return Err(ReviewError::InvalidDiscriminator.into());
+        }
+        karma_data
+    } else {
+        CommentKarma {
+            discriminator: CommentKarma::DISCRIMINATOR,
+            is_initialized: true,
+            comment: *pda_comment.key,
+            likes: 0,
+            reports: 0,
+            hidden: false,
+        }
+    };
 
-    comment_data.serialize(&mut *pda_comment.data.borrow_mut())?;
+    let mut vote_data = if pda_vote.owner == program_id {
+        let vote_data = try_from_slice_unchecked::<CommentVote>(&pda_vote.data.borrow())?;
+        assert_initialized(&vote_data, ReviewError::UninitializedAccount)?;
+        if vote_data.discriminator != CommentVote::DISCRIMINATOR {
+            msg!("Account is not a comment vote");
+            return Err(ReviewError::InvalidDiscriminator.into());
+        }
+        if vote_data.kind == kind {
+            msg!(
+                "{} already voted {:?} on {}",
+                voter.key,
+                kind,
+                pda_comment.key
+            );
+            return Err(ReviewError::AlreadyVoted.into());
+        }
+        karma_data.count(vote_data.kind, false)?;
+        vote_data
+    } else {
+        charge_fee(
+            program_id,
+            &config,
+            FeeKind::Vote,
+            voter,
+            pda_config,
+            system_program,
+            proofs,
+        )?;
+        msg!("creating comment vote account");
+        create_pda_account(
+            voter,
+            pda_vote,
+            system_program,
+            CommentVote::get_account_size(),
+            program_id,
+            &[vote_seeds[0], vote_seeds[1], vote_seeds[2], &[vote_bump]],
+        )?;
+        CommentVote {
+            discriminator: CommentVote::DISCRIMINATOR,
+            is_initialized: true,
+            comment: *pda_comment.key,
+            voter: *voter.key,
+            kind,
+        }
+    };
+    vote_data.kind = kind;
+    vote_data.serialize(&mut &mut pda_vote.data.borrow_mut()[..])?;
 
-    msg!("incrementing counter");
-    counter_data.counter += 1;
-    counter_data.serialize(&mut *pda_counter.data.borrow_mut())?;
+    if pda_karma.owner != program_id {
+        msg!("creating comment karma account");
+        create_pda_account(
+            voter,
+            pda_karma,
+            system_program,
+            CommentKarma::get_account_size(),
+            program_id,
+            &[
+                CommentKarma::SEED.as_bytes(),
+                pda_comment.key.as_ref(),
+                &[karma_bump],
+            ],
+        )?;
+    }
+    karma_data.count(kind, true)?;
+    let score = karma_data.score();
+    let hidden = config.hides(score);
+    if hidden != karma_data.hidden {
+        karma_data.hidden = hidden;
+        emit(&ReviewEvent::CommentVisibilityChanged {
+            recipient: comment_data.commenter,
+            comment: *pda_comment.key,
+            hidden,
+            score,
+        });
+    }
+    karma_data.serialize(&mut &mut pda_karma.data.borrow_mut()[..])?;
+    msg!("comment score {}", score);
+
+    if kind == CommentVoteKind::Like {
+        record_activity(
+            program_id,
+            pda_feed,
+            voter,
+            system_program,
+            &comment_data.reviewer,
+            HotFeed::LIKE_WEIGHT,
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Counts up to `MovieStats::MAX_RECOMPUTE_REVIEWS` reviews keyed by `movie`
+/// into the stats' recompute pass. Reviews must come in ascending key order,
+/// across calls too, so a pass counts each review once however it is split.
+///
+/// Only the reviews passed in are seen, so a pass cannot prove it is
+/// complete. Deleted reviews leave the live count and the pass together, so
+/// `finish` at least rejects a pass that counted fewer; reviews added behind
+/// the cursor during a pass show up that way too, and need a `restart`.
+///
+/// Accounts are `[payer, movie, movie_stats, system_program, review...]`,
+/// where `payer` funds the stats if they do not exist yet.
+pub fn recompute_stats(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    restart: bool,
+    finish: bool,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let payer = next_account_info(account_info_iter)?;
+    let movie = next_account_info(account_info_iter)?;
+    let pda_movie_stats = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+    let reviews = account_info_iter.as_slice();
+
+    assert_signer(payer)?;
+    load_movie(program_id, movie)?;
+
+    if reviews.len() > MovieStats::MAX_RECOMPUTE_REVIEWS {
+        msg!(
+            "At most {} reviews per call",
+            MovieStats::MAX_RECOMPUTE_REVIEWS
+        );
+        return Err(ReviewError::RecomputePageTooLarge.into());
+    }
+
+    let mut stats_data =
+        load_movie_stats(program_id, movie, pda_movie_stats, payer, system_program)?;
+    if restart {
+        stats_data.reset_recompute();
+    }
+
+    for review in reviews {
+        if *review.key <= stats_data.recompute_cursor {
+            return Err(ReviewError::UnorderedRecomputeReviews.into());
+        }
+
+        assert_owned_by(review, program_id)?;
+        assert_not_closed(review, CommonError::AccountClosed)?;
+        let header = load_review_header(&review.data.borrow())?;
+        if header.discriminator != MovieAccountState::DISCRIMINATOR {
+            msg!("Account {} is not a review", review.key);
+            return Err(ReviewError::InvalidDiscriminator.into());
+        }
+        assert_initialized(&header, ReviewError::UninitializedAccount)?;
+        assert_pda(
+            review,
+            &[header.reviewer.as_ref(), movie.key.as_ref()],
+            program_id,
+            ReviewError::InvalidPDA,
+        )?;
+
+        stats_data
+            .recompute_review(*review.key, header.rating)
+            .ok_or(CommonError::Overflow)?;
+    }
+
+    if finish {
+        if stats_data.recompute_count < stats_data.review_count {
+            msg!(
+                "Pass counted {} of at least {} reviews",
+                stats_data.recompute_count,
+                stats_data.review_count
+            );
+            return Err(ReviewError::IncompleteRecompute.into());
+        }
+        stats_data.review_count = stats_data.recompute_count;
+        stats_data.rating_sum = stats_data.recompute_sum;
+        stats_data.reset_recompute();
+        msg!(
+            "movie stats recomputed: {} reviews, average {}",
+            stats_data.review_count,
+            stats_data.average_rating()
+        );
+    }
+
+    stats_data.serialize(&mut &mut pda_movie_stats.data.borrow_mut()[..])?;
+
+    Ok(())
+}
+
+/// Files a review keyed by a registered movie in the movie's rating index,
+/// for reviews posted before the index existed, or refiles it under its
+/// rating's fifth of the current scale. Anyone may call it, paying for the
+/// index to grow if the review was not indexed.
+///
+/// Accounts are `[payer, movie, review, config, rating_index,
+/// system_program]`.
+pub fn index_movie_review(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let payer = next_account_info(account_info_iter)?;
+    let movie = next_account_info(account_info_iter)?;
+    let pda_review = next_account_info(account_info_iter)?;
+    let pda_config = next_account_info(account_info_iter)?;
+    let pda_rating_index = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+
+    assert_signer(payer)?;
+    load_movie(program_id, movie)?;
+    assert_owned_by(pda_review, program_id)?;
+
+    let header = load_review_header(&pda_review.data.borrow())?;
+    assert_initialized(&header, ReviewError::UninitializedAccount)?;
+    if header.discriminator != MovieAccountState::DISCRIMINATOR {
+        msg!("Account is not a review");
+        return Err(ReviewError::InvalidDiscriminator.into());
+    }
+    assert_pda(
+        pda_review,
+        &[header.reviewer.as_ref(), movie.key.as_ref()],
+        program_id,
+        ReviewError::InvalidPDA,
+    )?;
+
+    let config = load_config(program_id, pda_config)?;
+    let bucket = rating_bucket(&config, header.rating)?;
+    index_review(
+        program_id,
+        movie.key,
+        pda_review.key,
+        bucket,
+        pda_rating_index,
+        payer,
+        system_program,
+    )?;
+    msg!("filed {} under fifth {}", pda_review.key, bucket + 1);
+
+    Ok(())
+}
+
+/// Deletes the signer's review. It must have no active comments, so a
+/// review with comments is emptied first by `cascade` calls, each closing the
+/// comments and comment pages passed in and refunding their rent to the
+/// commenters who paid it. Cascade calls also close the review's
+/// translations, co-authors, fingerprint and edits passed in, refunding the
+/// reviewer, and its rent payer record, refunding the payer. The call that empties the review closes it and its counter,
+/// refunding the reviewer, and uncounts it from the stats.
+///
+/// Accounts are `[reviewer, review, counter, profile, config, global_stats,
+/// movie_stats, rating_index, system_program, (child, recipient)...]`.
+/// `movie_stats` and `rating_index` are the title's, only updated if the
+/// review is keyed by the registered movie. The review's key in the index is
+/// refunded to the reviewer.
+pub fn delete_review(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    cascade: bool,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let reviewer = next_account_info(account_info_iter)?;
+    let pda_review = next_account_info(account_info_iter)?;
+
+    let review_data = load_own_review(program_id, reviewer, pda_review)?;
+    close_review(program_id, &review_data, reviewer, accounts, cascade)?;
+
+    Ok(())
+}
+
+/// The work of `DeleteReview` on `review_data`, loaded from the review among
+/// its `accounts`, whose reviewer need not sign. `payer` funds growing the
+/// counter and creating the global stats. Returns whether the review was
+/// closed.
+fn close_review<'a>(
+    program_id: &Pubkey,
+    review_data: &MovieAccountState,
+    payer: &AccountInfo<'a>,
+    accounts: &[AccountInfo<'a>],
+    cascade: bool,
+) -> Result<bool, ProgramError> {
+    let account_info_iter = &mut accounts.iter();
+    let reviewer = next_account_info(account_info_iter)?;
+    let pda_review = next_account_info(account_info_iter)?;
+    let pda_counter = next_account_info(account_info_iter)?;
+    let pda_profile = next_account_info(account_info_iter)?;
+    let pda_config = next_account_info(account_info_iter)?;
+    let pda_stats = next_account_info(account_info_iter)?;
+    let pda_movie_stats = next_account_info(account_info_iter)?;
+    let pda_rating_index = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+    let comments = account_info_iter.as_slice();
+
+    assert_owned_by(pda_counter, program_id)?;
+    assert_pda(
+        pda_counter,
+        &[pda_review.key.as_ref(), "comment".as_ref()],
+        program_id,
+        ReviewError::InvalidPDA,
+    )?;
+    let mut counter_data = load_counter(pda_counter)?;
+
+    let mut closed = 0u64;
+    if cascade {
+        if !comments.len().is_multiple_of(2) {
+            msg!("Each account must be followed by its refund recipient");
+            return Err(ProgramError::NotEnoughAccountKeys);
+        }
+        for pair in comments.chunks_exact(2) {
+            closed += close_review_child(program_id, review_data, pda_review, &pair[0], &pair[1])?;
+        }
+        counter_data.remove_comments(closed);
+    }
+
+    if counter_data.active_comments > 0 {
+        if !cascade {
+            msg!("Review has {} comments", counter_data.active_comments);
+            return Err(ReviewError::ReviewHasComments.into());
+        }
+        save_counter(
+            pda_review.key,
+            &counter_data,
+            pda_counter,
+            payer,
+            system_program,
+        )?;
+        update_global_stats(program_id, pda_stats, payer, system_program, |stats| {
+            stats.remove_review(false, closed);
+            Some(())
+        })?;
+        msg!(
+            "closed {} comments, {} left",
+            closed,
+            counter_data.active_comments
+        );
+        return Ok(false);
+    }
+
+    let movie = Pubkey::find_program_address(
+        &[
+            Movie::SEED.as_bytes(),
+            &Movie::title_hash(&review_data.title),
+        ],
+        program_id,
+    )
+    .0;
+    assert_pda(
+        pda_movie_stats,
+        &[MovieStats::SEED.as_bytes(), movie.as_ref()],
+        program_id,
+        ReviewError::InvalidPDA,
+    )?;
+    assert_pda(
+        pda_rating_index,
+        &[RatingIndex::SEED.as_bytes(), movie.as_ref()],
+        program_id,
+        ReviewError::InvalidPDA,
+    )?;
+    let keyed_by_movie =
+        Pubkey::find_program_address(&[reviewer.key.as_ref(), movie.as_ref()], program_id).0
+            == *pda_review.key;
+    if keyed_by_movie && pda_movie_stats.owner == program_id {
+        let mut stats_data =
+            try_from_slice_unchecked::<MovieStats>(&pda_movie_stats.data.borrow())?;
+        if stats_data.discriminator != MovieStats::DISCRIMINATOR {
+            msg!("Account is not a movie's stats");
+            return Err(ReviewError::InvalidDiscriminator.into());
+        }
+        stats_data.remove_review(*pda_review.key, review_data.rating);
+        stats_data.serialize(&mut &mut pda_movie_stats.data.borrow_mut()[..])?;
+    }
+    if keyed_by_movie && pda_rating_index.owner == program_id {
+        check_rating_index(pda_rating_index)?;
+        let indexed = RatingIndex::remove(&mut pda_rating_index.data.borrow_mut(), pda_review.key)?;
+        if indexed.is_some() {
+            let account_size = pda_rating_index.data_len() - 32;
+            resize_account(pda_rating_index, reviewer, system_program, account_size)?;
+        }
+    }
+
+    let config = load_config(program_id, pda_config)?;
+    unrecord_reviewer(
+        program_id,
+        reviewer,
+        pda_profile,
+        review_data.rating,
+        &config.rating_scale,
+    )?;
+    update_global_stats(program_id, pda_stats, payer, system_program, |stats| {
+        stats.remove_review(true, closed);
+        Some(())
+    })?;
+
+    emit(&ReviewEvent::CommentCountChanged {
+        review: *pda_review.key,
+        active_comments: 0,
+        total_comments_created: counter_data.total_comments_created,
+    });
+    close_account(pda_counter, reviewer)?;
+    close_account(pda_review, reviewer)?;
+    msg!("deleted review of {}", review_data.title);
+
+    Ok(true)
+}
+
+/// Closes one of `pda_review`'s comments, comment pages, translations,
+/// co-authors, fingerprint, edits or rent payer record, refunding
+/// `commenter`: the comment's author, a page's first commenter, the record's
+/// payer, or else the reviewer. Returns the
+/// comments closed.
+fn close_review_child(
+    program_id: &Pubkey,
+    review_data: &MovieAccountState,
+    pda_review: &AccountInfo,
+    comment: &AccountInfo,
+    commenter: &AccountInfo,
+) -> Result<u64, ProgramError> {
+    assert_owned_by(comment, program_id)?;
+    assert_not_closed(comment, CommonError::AccountClosed)?;
+
+    let (payer, count) = match decode_account(&comment.data.borrow())? {
+        MovieReviewAccount::Comment(comment_data) => {
+            if comment_data.reviewer != *pda_review.key {
+                msg!("Comment {} is not on this review", comment.key);
+                return Err(ReviewError::InvalidPDA.into());
+            }
+            assert_pda(
+                comment,
+                &[
+                    pda_review.key.as_ref(),
+                    &comment_seed_bytes(comment_data.count),
+                ],
+                program_id,
+                ReviewError::InvalidPDA,
+            )?;
+            (comment_data.commenter, 1)
+        }
+        MovieReviewAccount::CommentPage(page_data) => {
+            if page_data.review != *pda_review.key {
+                msg!("Comment page {} is not on this review", comment.key);
+                return Err(ReviewError::InvalidPDA.into());
+            }
+            assert_pda(
+                comment,
+                &[
+                    CommentPage::SEED.as_bytes(),
+                    pda_review.key.as_ref(),
+                    &page_data.page.to_le_bytes(),
+                ],
+                program_id,
+                ReviewError::InvalidPDA,
+            )?;
+            let first = page_data
+                .comments
+                .first()
+                .ok_or(ReviewError::InvalidCommentPage)?;
+            (first.commenter, page_data.comments.len() as u64)
+        }
+        MovieReviewAccount::Translation(translation_data) => {
+            assert_pda(
+                comment,
+                &[
+                    ReviewTranslation::SEED.as_bytes(),
+                    pda_review.key.as_ref(),
+                    translation_data.language.as_bytes(),
+                ],
+                program_id,
+                ReviewError::InvalidPDA,
+            )?;
+            (review_data.reviewer, 0)
+        }
+        MovieReviewAccount::CoAuthors(_) => {
+            assert_pda(
+                comment,
+                &[ReviewCoAuthors::SEED.as_bytes(), pda_review.key.as_ref()],
+                program_id,
+                ReviewError::InvalidPDA,
+            )?;
+            (review_data.reviewer, 0)
+        }
+        MovieReviewAccount::Fingerprint(_) => {
+            assert_pda(
+                comment,
+                &[ReviewFingerprint::SEED.as_bytes(), pda_review.key.as_ref()],
+                program_id,
+                ReviewError::InvalidPDA,
+            )?;
+            (review_data.reviewer, 0)
+        }
+        MovieReviewAccount::ReviewEdits(_) => {
+            assert_pda(
+                comment,
+                &[ReviewEdits::SEED.as_bytes(), pda_review.key.as_ref()],
+                program_id,
+                ReviewError::InvalidPDA,
+            )?;
+            (review_data.reviewer, 0)
+        }
+        MovieReviewAccount::RentPayer(record) => {
+            assert_pda(
+                comment,
+                &[RentPayer::SEED.as_bytes(), pda_review.key.as_ref()],
+                program_id,
+                ReviewError::InvalidPDA,
+            )?;
+            (record.payer, 0)
+        }
+        _ => {
+            msg!("Account {} is not part of a review", comment.key);
+            return Err(ReviewError::InvalidDiscriminator.into());
+        }
+    };
+
+    if *commenter.key != payer {
+        msg!("Rent of {} goes back to {}", comment.key, payer);
+        return Err(ReviewError::WrongRefundRecipient.into());
+    }
+    close_account(comment, commenter)?;
+    Ok(count)
+}
+
+/// Uncounts a deleted review from its reviewer's profile. Reviewers whose
+/// reviews all predate profiles have none and are skipped.
+fn unrecord_reviewer(
+    program_id: &Pubkey,
+    reviewer: &AccountInfo,
+    pda_profile: &AccountInfo,
+    rating: u16,
+    scale: &RatingScale,
+) -> ProgramResult {
+    assert_pda(
+        pda_profile,
+        &[ReviewerProfile::SEED.as_bytes(), reviewer.key.as_ref()],
+        program_id,
+        ReviewError::InvalidPDA,
+    )?;
+    if pda_profile.owner != program_id {
+        return Ok(());
+    }
 
+    let mut profile_data = load_profile(pda_profile)?;
+    profile_data.remove_rating(rating, scale);
+    store_profile(&profile_data, pda_profile)?;
     Ok(())
 }
@@ -0,0 +1,47 @@
+//! Ids and derived addresses of the token and metadata programs reviews CPI
+//! into. Declared here rather than taken from their crates, which only the
+//! `program` feature pulls in, so the client builds for wasm without them.
+
+use solana_program::pubkey::Pubkey;
+
+pub mod token_2022 {
+    solana_program::declare_id!("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb");
+}
+
+pub mod associated_token {
+    solana_program::declare_id!("ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL");
+}
+
+pub mod token_metadata {
+    solana_program::declare_id!("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s");
+}
+
+/// `wallet`'s associated token account for `mint` under `token_program`.
+pub fn associated_token_address(wallet: &Pubkey, mint: &Pubkey, token_program: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[wallet.as_ref(), token_program.as_ref(), mint.as_ref()],
+        &associated_token::ID,
+    )
+    .0
+}
+
+pub fn metadata_address(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"metadata", token_metadata::ID.as_ref(), mint.as_ref()],
+        &token_metadata::ID,
+    )
+    .0
+}
+
+pub fn master_edition_address(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            b"metadata",
+            token_metadata::ID.as_ref(),
+            mint.as_ref(),
+            b"edition",
+        ],
+        &token_metadata::ID,
+    )
+    .0
+}
@@ -0,0 +1,77 @@
+//! Structural validation of markdown-lite review bodies.
+//!
+//! The subset is `**strong**`, `*emphasis*` or `_emphasis_`, `` `code` `` and
+//! `[label](https://...)` links, with `\` escaping the next character. Every
+//! marker must be closed, links must use HTTPS and are capped at `MAX_LINKS`,
+//! and `<` and `>` are rejected outside code so no HTML reaches a renderer.
+
+pub const MAX_LINKS: usize = 5;
+const LINK_SCHEME: &[u8] = b"https://";
+
+/// Length of the link starting at the `[` that opens `text`, or `None` if it
+/// is not a well-formed link.
+fn link_len(text: &[u8]) -> Option<usize> {
+    let label_end = text.iter().position(|&b| b == b']')?;
+    let label = &text[1..label_end];
+    if label.is_empty()
+        || label
+            .iter()
+            .any(|b| matches!(b, b'[' | b'<' | b'>' | b'\n'))
+    {
+        return None;
+    }
+
+    let url_start = label_end + 2;
+    if text.get(label_end + 1) != Some(&b'(') {
+        return None;
+    }
+    let url_len = text[url_start..].iter().position(|&b| b == b')')?;
+    let url = &text[url_start..url_start + url_len];
+    if !url.starts_with(LINK_SCHEME)
+        || url.len() == LINK_SCHEME.len()
+        || url
+            .iter()
+            .any(|b| b.is_ascii_whitespace() || matches!(b, b'(' | b'<' | b'>' | b'"'))
+    {
+        return None;
+    }
+
+    Some(url_start + url_len + 1)
+}
+
+pub fn is_valid_markdown_lite(text: &str) -> bool {
+    let bytes = text.as_bytes();
+    let (mut strong, mut star, mut underscore, mut code) = (false, false, false, false);
+    let mut links = 0;
+
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'`' => code = !code,
+            _ if code => {}
+            b'\\' => i += 1,
+            b'<' | b'>' | b']' => return false,
+            b'*' if bytes.get(i + 1) == Some(&b'*') => {
+                strong = !strong;
+                i += 1;
+            }
+            b'*' => star = !star,
+            b'_' => underscore = !underscore,
+            b'[' => {
+                let Some(len) = link_len(&bytes[i..]) else {
+                    return false;
+                };
+                links += 1;
+                if links > MAX_LINKS {
+                    return false;
+                }
+                i += len;
+                continue;
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+
+    !(strong || star || underscore || code)
+}
@@ -0,0 +1,65 @@
+//! Serializes a `Pubkey` as its base58 string, for use with
+//! `#[serde(with = "crate::serde_pubkey")]`, and arrays and vectors of them
+//! with `crate::serde_pubkey::array` and `crate::serde_pubkey::vec`.
+
+use std::str::FromStr;
+
+use serde::{de::Error, Deserialize, Deserializer, Serializer};
+use solana_program::pubkey::Pubkey;
+
+pub fn serialize<S: Serializer>(pubkey: &Pubkey, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(pubkey)
+}
+
+pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Pubkey, D::Error> {
+    let encoded = String::deserialize(deserializer)?;
+    Pubkey::from_str(&encoded).map_err(D::Error::custom)
+}
+
+pub mod array {
+    use std::str::FromStr;
+
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+    use solana_program::pubkey::Pubkey;
+
+    pub fn serialize<S: Serializer, const N: usize>(
+        pubkeys: &[Pubkey; N],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(pubkeys.iter().map(Pubkey::to_string))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>, const N: usize>(
+        deserializer: D,
+    ) -> Result<[Pubkey; N], D::Error> {
+        let encoded = Vec::<String>::deserialize(deserializer)?;
+        let pubkeys = encoded
+            .iter()
+            .map(|key| Pubkey::from_str(key).map_err(D::Error::custom))
+            .collect::<Result<Vec<_>, _>>()?;
+        pubkeys.try_into().map_err(|pubkeys: Vec<Pubkey>| {
+            D::Error::invalid_length(pubkeys.len(), &"a full array of keys")
+        })
+    }
+}
+
+pub mod vec {
+    use std::str::FromStr;
+
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+    use solana_program::pubkey::Pubkey;
+
+    pub fn serialize<S: Serializer>(pubkeys: &[Pubkey], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(pubkeys.iter().map(Pubkey::to_string))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<Pubkey>, D::Error> {
+        let encoded = Vec::<String>::deserialize(deserializer)?;
+        encoded
+            .iter()
+            .map(|key| Pubkey::from_str(key).map_err(D::Error::custom))
+            .collect()
+    }
+}
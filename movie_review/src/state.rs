@@ -1,15 +1,93 @@
+use std::{cmp::Reverse, ops::Range};
+
 use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::clock::{Epoch, UnixTimestamp};
+use solana_program::hash::{hash, hashv};
+use solana_program::program_error::ProgramError;
 use solana_program::program_pack::{IsInitialized, Sealed};
 use solana_program::pubkey::Pubkey;
 
 #[derive(BorshSerialize, BorshDeserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MovieAccountState {
-    pub discriminator: String,
+    pub discriminator: u8,
     pub is_initialized: bool,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_pubkey"))]
     pub reviewer: Pubkey,
-    pub rating: u8,
+    /// On the config's `RatingScale` at the time it was written.
+    pub rating: u16,
     pub title: String,
     pub description: String,
+    /// How frontends should render `description`. Reviews written before
+    /// the field existed read the zero padding after it as `Plain`.
+    pub body_format: BodyFormat,
+    /// Who may comment, set by the reviewer. `None` until first set, which
+    /// is also how reviews written before the field existed read their zero
+    /// padding, and means `CommentSettings::default()`.
+    pub comment_settings: Option<CommentSettings>,
+}
+
+/// Markup of a review body.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BodyFormat {
+    #[default]
+    Plain,
+    /// The markdown-lite subset checked by `rich_text`.
+    MarkdownLite,
+    /// The body is stored off-chain: the description is its URI, and the
+    /// review's `BodyCommitment` holds the hash it must match. Only set by
+    /// `UpdateBodyHash`.
+    OffChain,
+}
+
+impl BodyFormat {
+    pub fn is_valid(&self, body: &str) -> bool {
+        match self {
+            BodyFormat::Plain => true,
+            BodyFormat::MarkdownLite => crate::rich_text::is_valid_markdown_lite(body),
+            // Held to the same rules as comment media, for the same frontends
+            BodyFormat::OffChain => MovieComment::is_valid_media_uri(body),
+        }
+    }
+}
+
+/// Whether and by whom a review can be commented on.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CommentSettings {
+    pub comments_enabled: bool,
+    pub comment_policy: CommentPolicy,
+}
+
+impl Default for CommentSettings {
+    fn default() -> Self {
+        Self {
+            comments_enabled: true,
+            comment_policy: CommentPolicy::Anyone,
+        }
+    }
+}
+
+impl CommentSettings {
+    /// Serialized size of the largest policy.
+    pub const MAX_LEN: usize = 1 + 1 + 32;
+}
+
+/// Who may comment on a review. Commenters prove eligibility with the
+/// accounts they append to `AddComment`.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CommentPolicy {
+    #[default]
+    Anyone,
+    /// Commenters with a `Follow` of the reviewer.
+    FollowersOnly,
+    /// Commenters holding a nonzero balance of `mint`.
+    TokenHoldersOnly {
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_pubkey"))]
+        mint: Pubkey,
+    },
 }
 
 impl Sealed for MovieAccountState {}
@@ -20,26 +98,143 @@ impl IsInitialized for MovieAccountState {
     }
 }
 
+/// The fields of `MovieAccountState` that precede the title, for checks that
+/// should not decode the stored title and description onto the heap.
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct MovieAccountHeader {
+    pub discriminator: u8,
+    pub is_initialized: bool,
+    pub reviewer: Pubkey,
+    pub rating: u16,
+}
+
+impl IsInitialized for MovieAccountHeader {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
 impl MovieAccountState {
-    pub const DISCRIMINATOR: &'static str = "review";
+    // Every account starts with a one-byte tag naming its type. Tags are never
+    // 0, so a zeroed account never looks initialized, and never 6 or 7, the
+    // first byte of the v1 layout's length-prefixed string discriminators.
+    // Reviews took tags 1, 9 and 11 while ratings were a u8; those are now
+    // read as the v2 layout
+    pub const DISCRIMINATOR: u8 = 21;
+    /// Tag of an anonymous review, whose `reviewer` field holds an
+    /// `authorship_commitment` rather than the author's key until revealed.
+    pub const ANONYMOUS_DISCRIMINATOR: u8 = 22;
+    /// Tag of a review imported from another chain through Wormhole. Its
+    /// `reviewer` holds the author's address on that chain, and a
+    /// `ForeignReviewOrigin` follows the description.
+    pub const FOREIGN_DISCRIMINATOR: u8 = 23;
+    /// Seed of an imported review, followed by the emitter chain and the VAA
+    /// sequence, so each message is imported once.
+    pub const FOREIGN_SEED: &'static str = "foreign";
+    /// Leads `relayed_review_message`, so the signature cannot pass for any
+    /// other message the wallet signs.
+    pub const RELAYED_MESSAGE_PREFIX: &'static str = "movie_review:add_relayed_review";
     // pub const MAX_TITLE_LEN: usize = 100;
     // pub const MAX_DESCRIPTION_LEN: usize = 1000;
+    /// Size reviews are created at unless `ContentLimits` overrides it.
     pub const MAX_ACCOUNT_SIZE: usize = 1000;
+    /// Size a review can grow to through `AppendToDescription`, past the
+    /// size it was created at.
+    pub const MAX_GROWN_ACCOUNT_SIZE: usize = 10_000;
+    /// Byte offset of `rating`, right after the fixed-size header fields.
+    pub const RATING_OFFSET: usize = 1 + 1 + 32;
 
+    /// Room is always left for the largest `comment_settings`, so setting
+    /// them never needs a resize and never moves what follows the review.
     pub fn get_account_size(title: &str, description: &str) -> usize {
-        (4 + MovieAccountState::DISCRIMINATOR.len())
-            + 1
-            + 1
+        1 + 1
+            + 32
+            + 2
             + (4 + title.len())
             + (4 + description.len())
+            + 1
+            + 1
+            + CommentSettings::MAX_LEN
+    }
+
+    /// Offset of the first field after the description.
+    fn description_end(data: &[u8]) -> Result<usize, ProgramError> {
+        let mut offset = MovieAccountState::RATING_OFFSET + 2;
+        for _ in 0..2 {
+            let len = data
+                .get(offset..offset + 4)
+                .ok_or(ProgramError::InvalidAccountData)?;
+            offset += 4 + u32::from_le_bytes(len.try_into().unwrap()) as usize;
+        }
+        Ok(offset)
+    }
+
+    /// Reads `title` without decoding the description.
+    pub fn title_of(data: &[u8]) -> Result<String, ProgramError> {
+        let mut title_data = data
+            .get(MovieAccountState::RATING_OFFSET + 2..)
+            .ok_or(ProgramError::InvalidAccountData)?;
+        Ok(String::deserialize(&mut title_data)?)
+    }
+
+    /// Reads `body_format` by skipping over the title and description rather
+    /// than decoding them.
+    pub fn body_format_of(data: &[u8]) -> Result<BodyFormat, ProgramError> {
+        let offset = MovieAccountState::description_end(data)?;
+        let mut format_data = data.get(offset..).ok_or(ProgramError::InvalidAccountData)?;
+        Ok(BodyFormat::deserialize(&mut format_data)?)
+    }
+
+    /// Reads `comment_settings` the same way.
+    pub fn comment_settings_of(data: &[u8]) -> Result<Option<CommentSettings>, ProgramError> {
+        let offset = MovieAccountState::description_end(data)? + 1;
+        let mut settings_data = data.get(offset..).ok_or(ProgramError::InvalidAccountData)?;
+        Ok(Option::<CommentSettings>::deserialize(&mut settings_data)?)
+    }
+
+    /// Commitment stored in place of the author of an anonymous review. The
+    /// salt keeps the author from being found by hashing candidate keys.
+    pub fn authorship_commitment(reviewer: &Pubkey, salt: &[u8; 32]) -> Pubkey {
+        Pubkey::new_from_array(hashv(&[reviewer.as_ref(), salt]).to_bytes())
+    }
+
+    /// Message a reviewer signs with their wallet key to have a relayer post
+    /// this review for them through `program_id` until `expires_slot`.
+    /// `nonce` is their profile's `relay_nonce`.
+    pub fn relayed_review_message(
+        program_id: &Pubkey,
+        title: &str,
+        rating: u16,
+        description: &str,
+        nonce: u64,
+        expires_slot: u64,
+    ) -> Vec<u8> {
+        let mut message = Self::RELAYED_MESSAGE_PREFIX.as_bytes().to_vec();
+        message.extend_from_slice(program_id.as_ref());
+        borsh::to_writer(
+            &mut message,
+            &(title, rating, description, nonce, expires_slot),
+        )
+        .unwrap();
+        message
     }
 }
 
 #[derive(BorshSerialize, BorshDeserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MovieCommentCounter {
-    pub discriminator: String,
+    pub discriminator: u8,
     pub is_initialized: bool,
-    pub counter: u64,
+    /// Comments ever added, standalone and paged. Comment addresses are
+    /// derived from it, so deletions never lower it and no index is reused.
+    pub total_comments_created: u64,
+    /// The `CommentPage` paged comments are appended to, the first one with
+    /// room. Counters created before pages are shorter and read it as 0.
+    pub page: u64,
+    /// Comments not yet deleted. Counters created before deletions are
+    /// shorter and read it as `total_comments_created`, see
+    /// `migration::decode_counter`.
+    pub active_comments: u64,
 }
 
 impl IsInitialized for MovieCommentCounter {
@@ -49,21 +244,141 @@ impl IsInitialized for MovieCommentCounter {
 }
 
 impl MovieCommentCounter {
-    pub const DISCRIMINATOR: &'static str = "counter";
+    pub const DISCRIMINATOR: u8 = 2;
 
     pub fn get_account_size() -> usize {
-        (4 + MovieCommentCounter::DISCRIMINATOR.len()) + 1 + 8
+        1 + 1 + 8 + 8 + 8
+    }
+
+    /// Returns the index the next comment is stored at and advances the
+    /// counter past it.
+    pub fn next_comment_index(&mut self) -> Result<u64, ProgramError> {
+        let index = self.total_comments_created;
+        self.total_comments_created = index
+            .checked_add(1)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        self.active_comments = self
+            .active_comments
+            .checked_add(1)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        Ok(index)
+    }
+
+    /// Uncounts `deleted` comments. Only `active_comments` drops, so later
+    /// comments still get fresh indexes.
+    pub fn remove_comments(&mut self, deleted: u64) {
+        self.active_comments = self.active_comments.saturating_sub(deleted);
     }
 }
 
 #[derive(BorshSerialize, BorshDeserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MovieComment {
-    pub discriminator: String,
+    pub discriminator: u8,
     pub is_initialized: bool,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_pubkey"))]
     pub reviewer: Pubkey,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_pubkey"))]
     pub commenter: Pubkey,
     pub comment: String,
     pub count: u64,
+    /// Image or other media attached to the comment. Comments written before
+    /// attachments read the zero padding after `count` as `None`.
+    pub media_uri: Option<String>,
+    /// Where the comment sits in the review's thread. Comments written
+    /// before replies read the zero padding as top-level.
+    pub path: CommentPath,
+}
+
+/// Up to `COMMENTS_PER_PAGE` comments on a review packed into one account,
+/// at `["comment_page", review, page]`. Long threads cost one account to
+/// rent and fetch per page rather than per comment.
+#[derive(BorshSerialize, BorshDeserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CommentPage {
+    pub discriminator: u8,
+    pub is_initialized: bool,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_pubkey"))]
+    pub review: Pubkey,
+    pub page: u64,
+    pub comments: Vec<PagedComment>,
+}
+
+/// One comment in a `CommentPage`. `index` comes from the review's comment
+/// counter, so it is unique across paged and standalone comments.
+#[derive(BorshSerialize, BorshDeserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PagedComment {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_pubkey"))]
+    pub commenter: Pubkey,
+    pub index: u64,
+    pub comment: String,
+}
+
+impl IsInitialized for CommentPage {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+impl CommentPage {
+    pub const DISCRIMINATOR: u8 = 16;
+    pub const SEED: &'static str = "comment_page";
+    pub const COMMENTS_PER_PAGE: usize = 16;
+    pub const MAX_COMMENT_LEN: usize = 280;
+    /// Pages are allocated full size, with room for every comment at
+    /// `MAX_COMMENT_LEN`.
+    pub const ACCOUNT_SIZE: usize = 1
+        + 1
+        + 32
+        + 8
+        + 4
+        + CommentPage::COMMENTS_PER_PAGE * (32 + 8 + 4 + CommentPage::MAX_COMMENT_LEN);
+
+    pub fn is_full(&self) -> bool {
+        self.comments.len() >= CommentPage::COMMENTS_PER_PAGE
+    }
+}
+
+/// Deepest a reply can be nested: a reply to a reply to a reply to a reply
+/// to a top-level comment.
+pub const MAX_COMMENT_DEPTH: usize = 4;
+
+/// The indices of a comment's ancestors in its review, from the top-level
+/// comment down to its parent, so clients can rebuild a thread from the
+/// comments alone in one pass.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CommentPath {
+    /// Number of ancestors, 0 for a top-level comment.
+    pub depth: u8,
+    /// Only the first `depth` entries are set.
+    pub ancestors: [u64; MAX_COMMENT_DEPTH],
+}
+
+impl CommentPath {
+    pub const LEN: usize = 1 + 8 * MAX_COMMENT_DEPTH;
+
+    pub fn ancestors(&self) -> &[u64] {
+        &self.ancestors[..(self.depth as usize).min(MAX_COMMENT_DEPTH)]
+    }
+
+    pub fn parent(&self) -> Option<u64> {
+        self.ancestors().last().copied()
+    }
+
+    /// Path of a reply to the comment at `index` that has this path, or
+    /// `None` if the reply would be nested deeper than `MAX_COMMENT_DEPTH`.
+    pub fn child(&self, index: u64) -> Option<CommentPath> {
+        let depth = self.depth as usize;
+        if depth >= MAX_COMMENT_DEPTH {
+            return None;
+        }
+        let mut child = *self;
+        child.ancestors[depth] = index;
+        child.depth += 1;
+        Some(child)
+    }
 }
 
 impl IsInitialized for MovieComment {
@@ -72,12 +387,2051 @@ impl IsInitialized for MovieComment {
     }
 }
 
+/// Seed bytes of the comment at index `counter`, which sits at
+/// `[review, comment_seed_bytes(counter)]`. Every derivation and signature of
+/// a comment address must use these; see `RepairCommentAddress` for comments
+/// written at the little-endian address instead.
+pub fn comment_seed_bytes(counter: u64) -> [u8; 8] {
+    counter.to_be_bytes()
+}
+
 impl MovieComment {
-    pub const DISCRIMINATOR: &'static str = "comment";
+    pub const DISCRIMINATOR: u8 = 3;
     // pub const MAX_COMMENT_LEN: usize = 1000;
     pub const MAX_ACCOUNT_SIZE: usize = 1000;
+    pub const MAX_MEDIA_URI_LEN: usize = 200;
+    pub const MEDIA_URI_SCHEMES: [&str; 3] = ["https://", "ipfs://", "ar://"];
+    pub const MAX_MENTIONS: usize = 5;
+
+    pub fn get_account_size(comment: &str, media_uri: Option<&str>) -> usize {
+        1 + 1
+            + 32
+            + 32
+            + (4 + comment.len())
+            + 8
+            + 1
+            + media_uri.map_or(0, |uri| 4 + uri.len())
+            + CommentPath::LEN
+    }
+
+    /// Keys mentioned in `comment` as `@` followed by the base58 key, in
+    /// order and without repeats, up to `MAX_MENTIONS`.
+    pub fn mentions(comment: &str) -> Vec<Pubkey> {
+        let mut mentions = Vec::new();
+        for rest in comment.split('@').skip(1) {
+            let word = rest
+                .split(|c: char| !c.is_ascii_alphanumeric())
+                .next()
+                .unwrap_or_default();
+            let Ok(key) = word.parse::<Pubkey>() else {
+                continue;
+            };
+            if !mentions.contains(&key) {
+                mentions.push(key);
+            }
+            if mentions.len() == MovieComment::MAX_MENTIONS {
+                break;
+            }
+        }
+        mentions
+    }
+
+    /// Accepts HTTPS, IPFS and Arweave URIs of printable ASCII without
+    /// whitespace or quotes, so frontends can drop them into an `src`
+    /// attribute as is.
+    pub fn is_valid_media_uri(media_uri: &str) -> bool {
+        let Some(path) = MovieComment::MEDIA_URI_SCHEMES
+            .iter()
+            .find_map(|scheme| media_uri.strip_prefix(scheme))
+        else {
+            return false;
+        };
+        media_uri.len() <= MovieComment::MAX_MEDIA_URI_LEN
+            && !path.is_empty()
+            && path
+                .bytes()
+                .all(|b| b.is_ascii_graphic() && !matches!(b, b'"' | b'\'' | b'<' | b'>' | b'`'))
+    }
+}
+
+/// Deployment-wide settings, stored at the `["config"]` PDA. Until it is
+/// initialized every rule falls back to `ProgramConfig::default()`.
+#[derive(BorshSerialize, BorshDeserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ProgramConfig {
+    pub discriminator: u8,
+    pub is_initialized: bool,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_pubkey"))]
+    pub authority: Pubkey,
+    /// Whether a reviewer may comment on their own review.
+    pub allow_self_comments: bool,
+    /// Whether posting a review requires holding a reviewer pass.
+    pub require_reviewer_pass: bool,
+    /// Mint of the Metaplex collection review NFTs are verified into. Zero
+    /// until the first `RotateReviewCollection`.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_pubkey"))]
+    pub review_collection: Pubkey,
+    /// Number of collections created so far, which seeds the next one.
+    pub collection_generation: u32,
+    /// Program whose `ReleaseAttestation` accounts can verify a release.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_pubkey"))]
+    pub release_oracle: Pubkey,
+    /// Feed an attestation must come from, among those of `release_oracle`.
+    pub release_feed_id: [u8; 32],
+    /// VRF program whose randomness accounts pick the featured review.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_pubkey"))]
+    pub vrf_program: Pubkey,
+    /// Wormhole core bridge whose posted VAAs can import foreign reviews.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_pubkey"))]
+    pub wormhole_program: Pubkey,
+    /// Wormhole chain ID of the trusted review emitter.
+    pub foreign_emitter_chain: u16,
+    /// Emitter address, on its own chain, whose review messages are trusted.
+    pub foreign_emitter: [u8; 32],
+    /// Lamports charged per instruction, paid into the config PDA.
+    pub fees: FeeSchedule,
+    /// Mint whose holders skip fees, e.g. the reward mint. Pass holders
+    /// always skip them.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_pubkey"))]
+    pub fee_exempt_mint: Pubkey,
+    /// Balance of `fee_exempt_mint`, in base units, that waives the fee.
+    pub fee_exempt_min_balance: u64,
+    /// Program whose `GlobalStats::TIP_AUTHORITY_SEED` PDA may record tips.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_pubkey"))]
+    pub tip_program: Pubkey,
+    /// Size caps on new reviews and comments.
+    pub limits: ContentLimits,
+    /// Ratings new reviews and rating edits must fall in.
+    pub rating_scale: RatingScale,
+    /// Programs allowed to invoke state-changing instructions through CPI.
+    /// Unused slots are the default key; with none set, any program may.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_pubkey::array"))]
+    pub cpi_callers: [Pubkey; ProgramConfig::MAX_CPI_CALLERS],
+    /// `FEATURE_*` bits of the subsystems switched on. Zero means
+    /// `DEFAULT_FEATURES`, so configs created before the flags keep the
+    /// subsystems they had.
+    pub features: u64,
+    /// Slots a review's author has to appeal its removal. Zero means
+    /// `DEFAULT_REMOVAL_WINDOW`.
+    pub removal_window: u64,
+    /// Slots a wallet must have existed for before it can post a review,
+    /// proven by a `WalletAgeAttestation` or a stake account it withdraws
+    /// from. Zero switches the gate off.
+    pub min_wallet_age: u64,
+    /// Program whose `WalletAgeAttestation` accounts can prove a wallet's
+    /// age. With the default key, only stake accounts can.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_pubkey"))]
+    pub age_attester: Pubkey,
+    /// Score a comment's `CommentKarma` has to fall below for it to be
+    /// flagged hidden. Zero switches hiding off.
+    pub hide_threshold: i64,
+    /// Mint whose tokens are burned for every edit of a review after the
+    /// first `ReviewEdits::FREE_EDITS`, e.g. the reward mint.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_pubkey"))]
+    pub edit_burn_mint: Pubkey,
+    /// Base units of `edit_burn_mint` each paid edit burns. Zero makes every
+    /// edit free.
+    pub edit_burn_amount: u64,
+}
+
+impl IsInitialized for ProgramConfig {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+impl Default for ProgramConfig {
+    fn default() -> Self {
+        Self {
+            discriminator: ProgramConfig::DISCRIMINATOR,
+            is_initialized: false,
+            authority: Pubkey::default(),
+            allow_self_comments: true,
+            require_reviewer_pass: false,
+            review_collection: Pubkey::default(),
+            collection_generation: 0,
+            release_oracle: Pubkey::default(),
+            release_feed_id: [0; 32],
+            vrf_program: Pubkey::default(),
+            wormhole_program: Pubkey::default(),
+            foreign_emitter_chain: 0,
+            foreign_emitter: [0; 32],
+            fees: FeeSchedule::default(),
+            fee_exempt_mint: Pubkey::default(),
+            fee_exempt_min_balance: 0,
+            tip_program: Pubkey::default(),
+            limits: ContentLimits::default(),
+            rating_scale: RatingScale::default(),
+            cpi_callers: [Pubkey::default(); ProgramConfig::MAX_CPI_CALLERS],
+            features: 0,
+            removal_window: 0,
+            min_wallet_age: 0,
+            age_attester: Pubkey::default(),
+            hide_threshold: 0,
+            edit_burn_mint: Pubkey::default(),
+            edit_burn_amount: 0,
+        }
+    }
+}
+
+impl ProgramConfig {
+    pub const DISCRIMINATOR: u8 = 4;
+    pub const SEED: &'static str = "config";
+    /// Seed of the Token-2022 non-transferable mint whose tokens are reviewer
+    /// passes. The config PDA is its mint and freeze authority.
+    pub const PASS_MINT_SEED: &'static str = "reviewer_pass";
+    /// Seed of the PDA that is mint and update authority of the review
+    /// collections and review NFTs.
+    pub const COLLECTION_AUTHORITY_SEED: &'static str = "collection_authority";
+    /// Seed of a review collection mint, followed by its generation.
+    pub const COLLECTION_SEED: &'static str = "collection";
+    /// Seed of a review NFT mint, followed by the review address.
+    pub const REVIEW_NFT_SEED: &'static str = "review_nft";
+    pub const REVIEW_NFT_SYMBOL: &'static str = "REVIEW";
+    pub const MAX_CPI_CALLERS: usize = 4;
+    /// `RecordTip` and `RecordSolTip` count tips into the global stats.
+    pub const FEATURE_TIPS: u64 = 1 << 0;
+    /// `CreateSession` opens new sessions.
+    pub const FEATURE_SESSIONS: u64 = 1 << 1;
+    /// `AddRelayedReview` accepts reviews submitted by relayers.
+    pub const FEATURE_RELAYED_REVIEWS: u64 = 1 << 2;
+    /// `AddReviewForMovie` rejects reviews of movies before their
+    /// `release_date`.
+    pub const FEATURE_RELEASE_WINDOW: u64 = 1 << 3;
+    pub const ALL_FEATURES: u64 = ProgramConfig::FEATURE_TIPS
+        | ProgramConfig::FEATURE_SESSIONS
+        | ProgramConfig::FEATURE_RELAYED_REVIEWS
+        | ProgramConfig::FEATURE_RELEASE_WINDOW;
+    /// The subsystems that predate the flags. Newer ones ship switched off
+    /// until the authority enables them.
+    pub const DEFAULT_FEATURES: u64 = ProgramConfig::FEATURE_TIPS;
+    /// Set in every `features` written by `SetFeatures`, so switching every
+    /// subsystem off is not read as the default.
+    pub const FEATURES_SET: u64 = 1 << 63;
+    /// About two days of slots.
+    pub const DEFAULT_REMOVAL_WINDOW: u64 = 432_000;
+
+    // Fields are only ever appended. Configs created before a field existed
+    // are shorter and read it as zero until the next UpdateConfig grows them
+    pub fn get_account_size() -> usize {
+        1 + 1
+            + 32
+            + 1
+            + 1
+            + 32
+            + 4
+            + 32
+            + 32
+            + 32
+            + 32
+            + 2
+            + 32
+            + FeeSchedule::LEN
+            + 32
+            + 8
+            + 32
+            + ContentLimits::LEN
+            + RatingScale::LEN
+            + 32 * ProgramConfig::MAX_CPI_CALLERS
+            + 8
+            + 8
+            + 8
+            + 32
+            + 8
+            + 32
+            + 8
+    }
+
+    pub fn has_cpi_allowlist(&self) -> bool {
+        self.cpi_callers
+            .iter()
+            .any(|caller| *caller != Pubkey::default())
+    }
+
+    /// Whether `program` may invoke state-changing instructions through CPI.
+    pub fn allows_cpi_caller(&self, program: &Pubkey) -> bool {
+        !self.has_cpi_allowlist() || self.cpi_callers.contains(program)
+    }
+
+    /// Whether every bit of `feature` is switched on.
+    pub fn is_enabled(&self, feature: u64) -> bool {
+        let features = match self.features {
+            0 => ProgramConfig::DEFAULT_FEATURES,
+            features => features,
+        };
+        features & feature == feature
+    }
+
+    /// Slots an author has to appeal a proposed removal.
+    pub fn removal_window(&self) -> u64 {
+        match self.removal_window {
+            0 => ProgramConfig::DEFAULT_REMOVAL_WINDOW,
+            slots => slots,
+        }
+    }
+
+    /// Whether a comment scoring `score` is flagged hidden.
+    pub fn hides(&self, score: i64) -> bool {
+        self.hide_threshold != 0 && score < self.hide_threshold
+    }
+}
+
+/// Sizes new review and comment accounts are created at, which cap their
+/// content: a review's title and description share one budget. Zero means
+/// the built-in default, so configs created before the limits existed keep
+/// the original caps. Accounts already created keep their size.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ContentLimits {
+    pub max_review_size: u32,
+    pub max_comment_size: u32,
+}
+
+impl ContentLimits {
+    pub const LEN: usize = 4 + 4;
+
+    /// `MovieAccountState::MAX_ACCOUNT_SIZE` unless overridden.
+    pub fn review_size(&self) -> usize {
+        match self.max_review_size {
+            0 => MovieAccountState::MAX_ACCOUNT_SIZE,
+            size => size as usize,
+        }
+    }
+
+    /// `MovieComment::MAX_ACCOUNT_SIZE` unless overridden.
+    pub fn comment_size(&self) -> usize {
+        match self.max_comment_size {
+            0 => MovieComment::MAX_ACCOUNT_SIZE,
+            size => size as usize,
+        }
+    }
+
+    /// Whether both sizes fit an empty account of their kind and stay within
+    /// what a review can grow to.
+    pub fn is_valid(&self) -> bool {
+        let max = MovieAccountState::MAX_GROWN_ACCOUNT_SIZE;
+        (MovieAccountState::get_account_size("", "") + ForeignReviewOrigin::LEN..=max)
+            .contains(&self.review_size())
+            && (MovieComment::get_account_size("", None)..=max).contains(&self.comment_size())
+    }
+}
+
+/// Range ratings are given in, e.g. 1 to 10 stars or 0 to 1000 for a score
+/// with one decimal. Ratings are stored as the raw integer and `decimals`
+/// tells frontends where to put the point. A zero `max` means 1 to 5 stars,
+/// so configs created before the scale existed keep the original range.
+/// Ratings already stored are not rescaled when the scale changes.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RatingScale {
+    pub min: u16,
+    pub max: u16,
+    pub decimals: u8,
+}
+
+impl RatingScale {
+    pub const LEN: usize = 2 + 2 + 1;
+    pub const MAX_DECIMALS: u8 = 4;
+    /// What a zero scale stands for.
+    pub const STARS: RatingScale = RatingScale {
+        min: 1,
+        max: 5,
+        decimals: 0,
+    };
+
+    fn effective(&self) -> RatingScale {
+        match self.max {
+            0 => RatingScale::STARS,
+            _ => *self,
+        }
+    }
+
+    pub fn contains(&self, rating: u16) -> bool {
+        let scale = self.effective();
+        (scale.min..=scale.max).contains(&rating)
+    }
+
+    /// Which of `buckets` equal slices of the scale `rating` falls in, lowest
+    /// first, or `None` if it is off the scale. A scale with fewer values
+    /// than buckets leaves some of them unused.
+    pub fn bucket(&self, rating: u16, buckets: usize) -> Option<usize> {
+        if !self.contains(rating) {
+            return None;
+        }
+        let scale = self.effective();
+        let span = usize::from(scale.max - scale.min) + 1;
+        Some(usize::from(rating - scale.min) * buckets / span)
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.min < self.max && self.decimals <= RatingScale::MAX_DECIMALS
+    }
+}
+
+/// Instructions that can carry a fee.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FeeKind {
+    AddReview,
+    AddComment,
+    Vote,
+    Boost,
+}
+
+/// Fee in lamports of each `FeeKind`. Zero means free.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FeeSchedule {
+    pub add_review: u64,
+    pub add_comment: u64,
+    pub vote: u64,
+    pub boost: u64,
+}
+
+impl FeeSchedule {
+    pub const LEN: usize = 4 * 8;
+
+    pub fn fee(&self, kind: FeeKind) -> u64 {
+        match kind {
+            FeeKind::AddReview => self.add_review,
+            FeeKind::AddComment => self.add_comment,
+            FeeKind::Vote => self.vote,
+            FeeKind::Boost => self.boost,
+        }
+    }
+}
+
+/// A registered movie, at `["movie", sha256(title)]`. Hashing the title keeps
+/// the seed within the 32-byte limit for long titles.
+#[derive(BorshSerialize, BorshDeserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Movie {
+    pub discriminator: u8,
+    pub is_initialized: bool,
+    pub title: String,
+    /// Set once the configured release feed confirms a theatrical release.
+    pub verified_release: bool,
+    /// Release date from that confirmation, or as scheduled by the config
+    /// authority with `SetMovieReleaseDate`. Zero when unknown.
+    pub release_date: UnixTimestamp,
+    /// Catalog identifier such as `imdb:tt0133093`, empty until assigned.
+    pub external_id: String,
+}
+
+impl IsInitialized for Movie {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+impl Movie {
+    pub const DISCRIMINATOR: u8 = 5;
+    pub const SEED: &'static str = "movie";
+    pub const MAX_TITLE_LEN: usize = 100;
+    /// Length of the release fields appended after the title. Movies
+    /// registered before they existed are that much shorter and read them as
+    /// zero until verified.
+    pub const RELEASE_FIELDS_LEN: usize = 1 + 8;
+    /// Zero bytes that make the shortest legacy movie read every appended
+    /// field as zero or empty: the release fields and an empty external ID.
+    pub const LEGACY_PADDING_LEN: usize = Movie::RELEASE_FIELDS_LEN + 4;
+    pub const EXTERNAL_ID_SEED: &'static str = "external_id";
+    pub const MAX_EXTERNAL_ID_LEN: usize = 32;
+
+    pub fn title_hash(title: &str) -> [u8; 32] {
+        hash(title.as_bytes()).to_bytes()
+    }
+
+    pub fn external_id_hash(external_id: &str) -> [u8; 32] {
+        hash(external_id.as_bytes()).to_bytes()
+    }
+
+    pub fn get_account_size(title: &str, external_id: &str) -> usize {
+        1 + 1 + (4 + title.len()) + Movie::RELEASE_FIELDS_LEN + (4 + external_id.len())
+    }
+
+    /// Whether the movie is out at `now`. Movies without a release date
+    /// count as released.
+    pub fn is_released(&self, now: UnixTimestamp) -> bool {
+        self.release_date <= now
+    }
+
+    /// An external ID is `namespace:value`, e.g. `imdb:tt0133093` or
+    /// `tmdb:603`, in lowercase ASCII letters and digits, so every catalog
+    /// entry has exactly one spelling.
+    pub fn is_valid_external_id(external_id: &str) -> bool {
+        let Some((namespace, value)) = external_id.split_once(':') else {
+            return false;
+        };
+        let is_token = |part: &str| {
+            !part.is_empty()
+                && part
+                    .bytes()
+                    .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit())
+        };
+        external_id.len() <= Movie::MAX_EXTERNAL_ID_LEN && is_token(namespace) && is_token(value)
+    }
+}
+
+/// A description of a review in another language, at
+/// `["translation", review, language]`. `language` is a lowercase BCP-47
+/// tag, so each language of a review has a single address.
+#[derive(BorshSerialize, BorshDeserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ReviewTranslation {
+    pub discriminator: u8,
+    pub is_initialized: bool,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_pubkey"))]
+    pub review: Pubkey,
+    pub language: String,
+    pub description: String,
+}
+
+impl IsInitialized for ReviewTranslation {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+impl ReviewTranslation {
+    pub const DISCRIMINATOR: u8 = 13;
+    pub const SEED: &'static str = "translation";
+    pub const MAX_LANGUAGE_LEN: usize = 32;
+    pub const MAX_ACCOUNT_SIZE: usize = 1000;
+
+    pub fn get_account_size(language: &str, description: &str) -> usize {
+        1 + 1 + 32 + (4 + language.len()) + (4 + description.len())
+    }
+
+    /// Accepts well-formed lowercase BCP-47 tags such as `en`, `pt-br` or
+    /// `zh-hant-tw`: a 2-3 or 5-8 letter primary language followed by
+    /// subtags of 1-8 letters or digits. The tag is a seed, so it is capped
+    /// at the 32-byte seed limit.
+    pub fn is_valid_language(language: &str) -> bool {
+        if language.is_empty() || language.len() > ReviewTranslation::MAX_LANGUAGE_LEN {
+            return false;
+        }
+        let mut subtags = language.split('-');
+        let primary = subtags.next().unwrap_or_default();
+        (matches!(primary.len(), 2..=3 | 5..=8) && primary.bytes().all(|b| b.is_ascii_lowercase()))
+            && subtags.all(|subtag| {
+                (1..=8).contains(&subtag.len())
+                    && subtag
+                        .bytes()
+                        .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit())
+            })
+    }
+}
+
+/// Lamports a reviewer escrows for a translation of their review into
+/// `language`, at `["translation_bounty", review, language]`. The bounty is
+/// held above the account's rent until the reviewer approves a submission,
+/// which pays it to the translator, or cancels it. Each submission holds off
+/// cancelling for a review period, so a reviewer cannot read a submission and
+/// take the bounty back before deciding on it.
+#[derive(BorshSerialize, BorshDeserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TranslationBounty {
+    pub discriminator: u8,
+    pub is_initialized: bool,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_pubkey"))]
+    pub review: Pubkey,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_pubkey"))]
+    pub reviewer: Pubkey,
+    pub amount: u64,
+    pub language: String,
+    /// When the reviewer may cancel, a review period after the latest
+    /// submission.
+    pub cancellable_at: UnixTimestamp,
+}
+
+impl IsInitialized for TranslationBounty {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+impl TranslationBounty {
+    pub const DISCRIMINATOR: u8 = 35;
+    pub const SEED: &'static str = "translation_bounty";
+    pub const REVIEW_PERIOD: i64 = 7 * 24 * 60 * 60;
+
+    pub fn get_account_size() -> usize {
+        1 + 1 + 32 + 32 + 8 + (4 + ReviewTranslation::MAX_LANGUAGE_LEN) + 8
+    }
+}
+
+/// A translator's description for a translation bounty, at
+/// `["translation_submission", bounty, translator]`. Sized to the
+/// description, and resized when the translator resubmits.
+#[derive(BorshSerialize, BorshDeserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TranslationSubmission {
+    pub discriminator: u8,
+    pub is_initialized: bool,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_pubkey"))]
+    pub bounty: Pubkey,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_pubkey"))]
+    pub translator: Pubkey,
+    pub description: String,
+}
+
+impl IsInitialized for TranslationSubmission {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+impl TranslationSubmission {
+    pub const DISCRIMINATOR: u8 = 36;
+    pub const SEED: &'static str = "translation_submission";
+
+    pub fn get_account_size(description: &str) -> usize {
+        1 + 1 + 32 + 32 + (4 + description.len())
+    }
+}
+
+/// A Bloom filter of the titles a reviewer has reviewed, at
+/// `["title_filter", reviewer]`. Opt-in: passed after a new review's proofs,
+/// the title is checked against it before the review is allocated, with a
+/// likely match only warned about, then added. Titles are compared as
+/// `TitleFilter::normalize`d, so the same movie reviewed under another
+/// spelling, and thus at another PDA, is still caught, at the cost of false
+/// positives that grow with the titles added.
+#[derive(BorshSerialize, BorshDeserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TitleFilter {
+    pub discriminator: u8,
+    pub is_initialized: bool,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_pubkey"))]
+    pub reviewer: Pubkey,
+    /// Titles added, which bounds the false positive rate.
+    pub titles: u32,
+    pub bits: [u64; TitleFilter::WORDS],
+}
+
+impl IsInitialized for TitleFilter {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+impl TitleFilter {
+    pub const DISCRIMINATOR: u8 = 38;
+    pub const SEED: &'static str = "title_filter";
+    /// 1024 bits, about a 1% false positive rate at 100 titles.
+    pub const WORDS: usize = 16;
+    pub const HASHES: usize = 4;
+    /// Words dropped from titles before comparing them.
+    const ARTICLES: [&'static str; 3] = ["the", "a", "an"];
+
+    pub fn get_account_size() -> usize {
+        1 + 1 + 32 + 4 + 8 * TitleFilter::WORDS
+    }
+
+    /// `title` lowercased, without its articles, punctuation or spacing, so
+    /// "The Matrix", "Matrix, The" and "matrix" are one title, and so are
+    /// "Spider-Man" and "Spiderman".
+    pub fn normalize(title: &str) -> String {
+        title
+            .split(|c: char| !c.is_alphanumeric())
+            .map(str::to_lowercase)
+            .filter(|word| !TitleFilter::ARTICLES.contains(&word.as_str()))
+            .collect()
+    }
+
+    fn bit_indexes(title: &str) -> [usize; TitleFilter::HASHES] {
+        let digest = hash(TitleFilter::normalize(title).as_bytes()).to_bytes();
+        let mut indexes = [0; TitleFilter::HASHES];
+        for (i, index) in indexes.iter_mut().enumerate() {
+            let bits = u16::from_le_bytes([digest[2 * i], digest[2 * i + 1]]);
+            *index = usize::from(bits) % (64 * TitleFilter::WORDS);
+        }
+        indexes
+    }
+
+    /// Whether a title normalizing like `title` was probably added. Never
+    /// false for one that was.
+    pub fn may_contain(&self, title: &str) -> bool {
+        TitleFilter::bit_indexes(title)
+            .iter()
+            .all(|&index| self.bits[index / 64] & (1 << (index % 64)) != 0)
+    }
+
+    pub fn insert(&mut self, title: &str) {
+        for index in TitleFilter::bit_indexes(title) {
+            self.bits[index / 64] |= 1 << (index % 64);
+        }
+        self.titles = self.titles.saturating_add(1);
+    }
+}
+
+/// Who paid the rent of `account`, at `["rent_payer", account]`, kept for
+/// the accounts paid for by someone other than their controller: reviews
+/// added with `AddRelayedReview` record their relayer. `WithdrawExcessLamports`
+/// pays the excess back to them, and a cascading `DeleteReview` closes it.
+#[derive(BorshSerialize, BorshDeserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RentPayer {
+    pub discriminator: u8,
+    pub is_initialized: bool,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_pubkey"))]
+    pub account: Pubkey,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_pubkey"))]
+    pub payer: Pubkey,
+}
+
+impl IsInitialized for RentPayer {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+impl RentPayer {
+    pub const DISCRIMINATOR: u8 = 39;
+    pub const SEED: &'static str = "rent_payer";
+
+    pub fn get_account_size() -> usize {
+        1 + 1 + 32 + 32
+    }
+}
+
+/// Resolves an external ID to its movie, at
+/// `["external_id", sha256(external_id)]`.
+#[derive(BorshSerialize, BorshDeserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ExternalIdIndex {
+    pub discriminator: u8,
+    pub is_initialized: bool,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_pubkey"))]
+    pub movie: Pubkey,
+}
+
+impl IsInitialized for ExternalIdIndex {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+impl ExternalIdIndex {
+    pub const DISCRIMINATOR: u8 = 12;
+
+    pub fn get_account_size() -> usize {
+        1 + 1 + 32
+    }
+}
+
+/// Records that `follower` follows `reviewer`, at
+/// `["follow", follower, reviewer]`. Reviews can limit comments to followers.
+#[derive(BorshSerialize, BorshDeserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Follow {
+    pub discriminator: u8,
+    pub is_initialized: bool,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_pubkey"))]
+    pub follower: Pubkey,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_pubkey"))]
+    pub reviewer: Pubkey,
+}
+
+impl IsInitialized for Follow {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+impl Follow {
+    pub const DISCRIMINATOR: u8 = 14;
+    pub const SEED: &'static str = "follow";
+
+    pub fn get_account_size() -> usize {
+        1 + 1 + 32 + 32
+    }
+}
+
+/// Blocks `muted` from commenting on any review by `reviewer`, at
+/// `["mute", reviewer, muted]`.
+#[derive(BorshSerialize, BorshDeserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Mute {
+    pub discriminator: u8,
+    pub is_initialized: bool,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_pubkey"))]
+    pub reviewer: Pubkey,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_pubkey"))]
+    pub muted: Pubkey,
+}
+
+impl IsInitialized for Mute {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+impl Mute {
+    pub const DISCRIMINATOR: u8 = 15;
+    pub const SEED: &'static str = "mute";
+
+    pub fn get_account_size() -> usize {
+        1 + 1 + 32 + 32
+    }
+}
+
+/// Lets `session_key` sign for `authority` in the instructions `scopes`
+/// allows until `expires_slot`, at `["session", authority, session_key]`.
+/// An app can keep the key itself so commenting needs no wallet prompt.
+#[derive(BorshSerialize, BorshDeserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Session {
+    pub discriminator: u8,
+    pub is_initialized: bool,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_pubkey"))]
+    pub authority: Pubkey,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_pubkey"))]
+    pub session_key: Pubkey,
+    /// Last slot the session can be used in.
+    pub expires_slot: u64,
+    pub scopes: u8,
+}
+
+impl IsInitialized for Session {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+impl Session {
+    pub const DISCRIMINATOR: u8 = 24;
+    pub const SEED: &'static str = "session";
+    /// Scope letting the key add comments and replies.
+    pub const SCOPE_COMMENT: u8 = 1 << 0;
+    /// Scope letting the key change the authority's reviews with
+    /// `UpdateRating`, `SetReviewBodyFormat` and `SetCommentSettings`.
+    /// Rewriting a review still takes the authority's signature, as the edit
+    /// can burn its tokens.
+    pub const SCOPE_UPDATE: u8 = 1 << 1;
+    /// Scope letting the key claim the authority's rewards distributor
+    /// earnings, which are still paid to the authority.
+    pub const SCOPE_CLAIM: u8 = 1 << 2;
+    pub const ALL_SCOPES: u8 =
+        Session::SCOPE_COMMENT | Session::SCOPE_UPDATE | Session::SCOPE_CLAIM;
+    /// About a week of 400 ms slots.
+    pub const MAX_DURATION_SLOTS: u64 = 1_512_000;
+
+    pub fn get_account_size() -> usize {
+        1 + 1 + 32 + 32 + 8 + 1
+    }
+
+    /// Whether the session lets its key act in `scope` at `slot`.
+    pub fn allows(&self, scope: u8, slot: u64) -> bool {
+        self.scopes & scope == scope && slot <= self.expires_slot
+    }
+}
+
+/// Config authority actions recorded in the `AuditLog`.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AdminAction {
+    #[default]
+    InitializeConfig,
+    UpdateConfig,
+    SetReleaseOracle,
+    SetVrfProgram,
+    SetForeignEmitter,
+    SetFeeSchedule,
+    SetContentLimits,
+    SetRatingScale,
+    SetCpiCallers,
+    SetTipProgram,
+    SetFeatures,
+    InitializePassMint,
+    IssueReviewerPass,
+    RotateReviewCollection,
+    SetMovieExternalId,
+    /// Excess lamports, e.g. collected fees, withdrawn from the config.
+    WithdrawFees,
+    SetRemovalWindow,
+    ProposeRemoval,
+    ResolveAppeal,
+    FinalizeRemoval,
+    SetAgeGate,
+    SetHideThreshold,
+    SetEditBurn,
+    SetMovieReleaseDate,
+}
+
+/// One `AdminAction` on `target`, e.g. the config, or the reviewer issued a
+/// pass.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AuditEntry {
+    /// Position among every action ever recorded.
+    pub sequence: u64,
+    pub slot: u64,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_pubkey"))]
+    pub authority: Pubkey,
+    pub action: AdminAction,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_pubkey"))]
+    pub target: Pubkey,
+}
+
+/// The last `CAPACITY` config authority actions, at `["audit_log"]`, created
+/// by the first one. Entry `sequence` is stored at `sequence % CAPACITY`, so
+/// the oldest are overwritten; every action is also emitted as
+/// `ReviewEvent::AdminAction` for indexers keeping the full history.
+#[derive(BorshSerialize, BorshDeserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AuditLog {
+    pub discriminator: u8,
+    pub is_initialized: bool,
+    /// Sequence of the next action, i.e. how many were ever recorded.
+    pub next_sequence: u64,
+    pub entries: [AuditEntry; AuditLog::CAPACITY],
+}
+
+impl IsInitialized for AuditLog {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+impl Default for AuditLog {
+    fn default() -> Self {
+        Self {
+            discriminator: AuditLog::DISCRIMINATOR,
+            is_initialized: false,
+            next_sequence: 0,
+            entries: [AuditEntry::default(); AuditLog::CAPACITY],
+        }
+    }
+}
+
+impl AuditLog {
+    pub const DISCRIMINATOR: u8 = 25;
+    pub const SEED: &'static str = "audit_log";
+    pub const CAPACITY: usize = 32;
+    pub const ENTRY_LEN: usize = 8 + 8 + 32 + 1 + 32;
+
+    pub fn get_account_size() -> usize {
+        1 + 1 + 8 + AuditLog::ENTRY_LEN * AuditLog::CAPACITY
+    }
+
+    /// Records `entry` under the next sequence, which it returns.
+    pub fn push(&mut self, mut entry: AuditEntry) -> u64 {
+        let sequence = self.next_sequence;
+        entry.sequence = sequence;
+        self.entries[(sequence % AuditLog::CAPACITY as u64) as usize] = entry;
+        self.next_sequence += 1;
+        sequence
+    }
+
+    /// The recorded entries still held, oldest first.
+    pub fn recent(&self) -> impl Iterator<Item = &AuditEntry> {
+        let held = self.next_sequence.min(AuditLog::CAPACITY as u64);
+        (self.next_sequence - held..self.next_sequence)
+            .map(|sequence| &self.entries[(sequence % AuditLog::CAPACITY as u64) as usize])
+    }
+}
+
+/// Where the author's appeal against a pending removal stands.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AppealStatus {
+    #[default]
+    None,
+    /// Filed and waiting for the config authority, which blocks finalizing.
+    Filed,
+    /// Turned down, so the removal can be finalized once the window ends.
+    Rejected,
+}
+
+/// A removal of `review` proposed by the config authority, at
+/// `["pending_removal", review]`. The review stays up until `FinalizeRemoval`
+/// after `appeal_deadline`; an upheld appeal closes this account instead.
+#[derive(BorshSerialize, BorshDeserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PendingRemoval {
+    pub discriminator: u8,
+    pub is_initialized: bool,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_pubkey"))]
+    pub review: Pubkey,
+    /// The review's author, who may appeal and gets its rent back.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_pubkey"))]
+    pub author: Pubkey,
+    pub proposed_slot: u64,
+    /// Last slot an appeal can be filed in.
+    pub appeal_deadline: u64,
+    pub appeal: AppealStatus,
+}
+
+impl IsInitialized for PendingRemoval {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+impl PendingRemoval {
+    pub const DISCRIMINATOR: u8 = 26;
+    pub const SEED: &'static str = "pending_removal";
+
+    pub fn get_account_size() -> usize {
+        1 + 1 + 32 + 32 + 8 + 8 + 1
+    }
+}
+
+/// The hash of an off-chain review body, at `["body", review]`, written by
+/// `UpdateBodyHash`. The review's description is the URI the body is stored
+/// at, so bodies are not bounded by the review account's size.
+#[derive(BorshSerialize, BorshDeserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BodyCommitment {
+    pub discriminator: u8,
+    pub is_initialized: bool,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_pubkey"))]
+    pub review: Pubkey,
+    /// SHA-256 of the body's bytes, see `client::body`.
+    pub hash: [u8; 32],
+}
+
+impl IsInitialized for BodyCommitment {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+impl BodyCommitment {
+    pub const DISCRIMINATOR: u8 = 27;
+    pub const SEED: &'static str = "body";
+
+    pub fn get_account_size() -> usize {
+        1 + 1 + 32 + 32
+    }
+}
+
+/// `fingerprint::simhash` of a review's description, at
+/// `["fingerprint", review]`. Anyone can record or refresh it with
+/// `RecordFingerprint`, so indexers can match reviews without reading them.
+#[derive(BorshSerialize, BorshDeserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ReviewFingerprint {
+    pub discriminator: u8,
+    pub is_initialized: bool,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_pubkey"))]
+    pub review: Pubkey,
+    pub fingerprint: u64,
+    /// Slot the fingerprint was last recorded in.
+    pub slot: u64,
+}
+
+impl IsInitialized for ReviewFingerprint {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+impl ReviewFingerprint {
+    pub const DISCRIMINATOR: u8 = 28;
+    pub const SEED: &'static str = "fingerprint";
+
+    pub fn get_account_size() -> usize {
+        1 + 1 + 32 + 8 + 8
+    }
+}
+
+/// A flag that `review` copies `original`, at
+/// `["duplicate", review, original]`, left by `ChallengeDuplicate` for the
+/// config authority to act on with `ProposeRemoval`. One per pair, so the
+/// same copy cannot be flagged twice.
+#[derive(BorshSerialize, BorshDeserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DuplicateChallenge {
+    pub discriminator: u8,
+    pub is_initialized: bool,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_pubkey"))]
+    pub challenger: Pubkey,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_pubkey"))]
+    pub review: Pubkey,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_pubkey"))]
+    pub original: Pubkey,
+    /// Bits the two fingerprints differed in when challenged.
+    pub distance: u8,
+    pub slot: u64,
+}
+
+impl IsInitialized for DuplicateChallenge {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+impl DuplicateChallenge {
+    pub const DISCRIMINATOR: u8 = 29;
+    pub const SEED: &'static str = "duplicate";
+
+    pub fn get_account_size() -> usize {
+        1 + 1 + 32 + 32 + 32 + 1 + 8
+    }
+}
+
+/// How a voter rated a comment.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CommentVoteKind {
+    Like,
+    Report,
+}
+
+/// A voter's vote on a standalone comment, at
+/// `["comment_vote", comment, voter]`, so each voter counts once. Voting
+/// again with the other kind switches it.
+#[derive(BorshSerialize, BorshDeserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CommentVote {
+    pub discriminator: u8,
+    pub is_initialized: bool,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_pubkey"))]
+    pub comment: Pubkey,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_pubkey"))]
+    pub voter: Pubkey,
+    pub kind: CommentVoteKind,
+}
+
+impl IsInitialized for CommentVote {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+impl CommentVote {
+    pub const DISCRIMINATOR: u8 = 30;
+    pub const SEED: &'static str = "comment_vote";
+
+    pub fn get_account_size() -> usize {
+        1 + 1 + 32 + 32 + 1
+    }
+}
+
+/// Likes and reports of a standalone comment, at `["karma", comment]`,
+/// created by its first vote. `hidden` is re-evaluated against the config's
+/// `hide_threshold` on every vote; a hidden comment stays readable, clients
+/// are expected to collapse it.
+#[derive(BorshSerialize, BorshDeserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CommentKarma {
+    pub discriminator: u8,
+    pub is_initialized: bool,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_pubkey"))]
+    pub comment: Pubkey,
+    pub likes: u64,
+    pub reports: u64,
+    pub hidden: bool,
+}
+
+impl IsInitialized for CommentKarma {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+impl CommentKarma {
+    pub const DISCRIMINATOR: u8 = 31;
+    pub const SEED: &'static str = "karma";
+
+    pub fn get_account_size() -> usize {
+        1 + 1 + 32 + 8 + 8 + 1
+    }
+
+    /// Likes minus reports.
+    pub fn score(&self) -> i64 {
+        (self.likes as i64).saturating_sub(self.reports as i64)
+    }
+
+    /// Counts one `kind` vote, or takes one back with `add` false.
+    pub fn count(&mut self, kind: CommentVoteKind, add: bool) -> Result<(), ProgramError> {
+        let tally = match kind {
+            CommentVoteKind::Like => &mut self.likes,
+            CommentVoteKind::Report => &mut self.reports,
+        };
+        *tally = if add {
+            tally.checked_add(1)
+        } else {
+            tally.checked_sub(1)
+        }
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+        Ok(())
+    }
+}
+
+/// Edits made to one review with `UpdateMovieReview`, at `["edits", review]`,
+/// created by its first edit. Edits beyond `FREE_EDITS` burn the config's
+/// `edit_burn_amount`.
+#[derive(BorshSerialize, BorshDeserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ReviewEdits {
+    pub discriminator: u8,
+    pub is_initialized: bool,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_pubkey"))]
+    pub review: Pubkey,
+    pub edits: u64,
+}
+
+impl IsInitialized for ReviewEdits {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+impl ReviewEdits {
+    pub const DISCRIMINATOR: u8 = 33;
+    pub const SEED: &'static str = "edits";
+    pub const FREE_EDITS: u64 = 1;
+
+    pub fn get_account_size() -> usize {
+        1 + 1 + 32 + 8
+    }
+
+    /// Whether the next edit burns tokens under `config`.
+    pub fn next_edit_burns(&self, config: &ProgramConfig) -> bool {
+        config.edit_burn_amount > 0 && self.edits >= ReviewEdits::FREE_EDITS
+    }
+}
+
+/// Co-authors a reviewer lists on their review, at `["co_authors", review]`,
+/// created by the first `SetCoAuthors`. Each gets their share of the review's
+/// tips and rewards once they accept with `AcceptCoAuthorship`; the reviewer
+/// keeps the rest. A cascading `DeleteReview` closes the list along with the
+/// review.
+#[derive(BorshSerialize, BorshDeserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ReviewCoAuthors {
+    pub discriminator: u8,
+    pub is_initialized: bool,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_pubkey"))]
+    pub review: Pubkey,
+    pub co_authors: Vec<CoAuthor>,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CoAuthor {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_pubkey"))]
+    pub co_author: Pubkey,
+    /// Basis points of the review's tips and rewards.
+    pub share_bps: u16,
+    pub accepted: bool,
+}
+
+impl IsInitialized for ReviewCoAuthors {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+impl ReviewCoAuthors {
+    pub const DISCRIMINATOR: u8 = 34;
+    pub const SEED: &'static str = "co_authors";
+    pub const MAX_CO_AUTHORS: usize = 3;
+    pub const MAX_BPS: u16 = 10_000;
+
+    /// Allocated with room for `MAX_CO_AUTHORS`.
+    pub fn get_account_size() -> usize {
+        1 + 1 + 32 + 4 + ReviewCoAuthors::MAX_CO_AUTHORS * (32 + 2 + 1)
+    }
+
+    /// Whether `co_authors` can be listed on a review by `reviewer`: at most
+    /// `MAX_CO_AUTHORS` distinct keys other than the reviewer's, with
+    /// positive shares adding up to at most `MAX_BPS`.
+    pub fn is_valid_listing(reviewer: &Pubkey, co_authors: &[(Pubkey, u16)]) -> bool {
+        co_authors.len() <= ReviewCoAuthors::MAX_CO_AUTHORS
+            && co_authors
+                .iter()
+                .enumerate()
+                .all(|(i, (co_author, share))| {
+                    *share > 0
+                        && co_author != reviewer
+                        && co_authors[..i].iter().all(|(other, _)| other != co_author)
+                })
+            && co_authors
+                .iter()
+                .map(|(_, share)| *share as u32)
+                .sum::<u32>()
+                <= ReviewCoAuthors::MAX_BPS as u32
+    }
+
+    /// Splits `amount` between the reviewer and the co-authors who accepted.
+    /// Each part is rounded down, so the reviewer keeps the dust along with
+    /// the unaccepted shares. `None` on overflow.
+    pub fn split(&self, amount: u64) -> Option<(u64, Vec<(Pubkey, u64)>)> {
+        let mut rest = amount;
+        let parts = self
+            .co_authors
+            .iter()
+            .filter(|co_author| co_author.accepted)
+            .map(|co_author| {
+                let part = u64::try_from(
+                    amount as u128 * co_author.share_bps as u128 / ReviewCoAuthors::MAX_BPS as u128,
+                )
+                .ok()?;
+                rest = rest.checked_sub(part)?;
+                Some((co_author.co_author, part))
+            })
+            .collect::<Option<Vec<_>>>()?;
+        Some((rest, parts))
+    }
+}
+
+/// Running totals across the deployment, at `["global_stats"]`, created by
+/// whichever handler first updates it. `DeleteReview` takes the reviews and
+/// comments it closes back out; tips and unique reviewers only grow.
+#[derive(BorshSerialize, BorshDeserialize, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GlobalStats {
+    pub discriminator: u8,
+    pub is_initialized: bool,
+    pub total_reviews: u64,
+    pub total_comments: u64,
+    /// Reward token base units tipped through the configured tip program.
+    pub total_tips: u64,
+    /// Reviewers with a `ReviewerProfile`, i.e. who posted a review under
+    /// their own key. Anonymous and imported reviews are not attributed.
+    pub unique_reviewers: u64,
+    /// Lamports tipped through the configured tip program. Zero in stats
+    /// created before it was appended.
+    pub total_tip_lamports: u64,
+}
+
+impl IsInitialized for GlobalStats {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+impl GlobalStats {
+    pub const DISCRIMINATOR: u8 = 18;
+    pub const SEED: &'static str = "global_stats";
+    /// Seed of the tip program's PDA that signs `RecordTip`, the rewards
+    /// distributor's state account.
+    pub const TIP_AUTHORITY_SEED: &'static str = "distributor";
+
+    pub fn get_account_size() -> usize {
+        1 + 1 + 8 + 8 + 8 + 8 + 8
+    }
+
+    /// Counts a review, and its author if it is their first. `None` on
+    /// overflow.
+    pub fn record_review(&mut self, new_reviewer: bool) -> Option<()> {
+        self.total_reviews = self.total_reviews.checked_add(1)?;
+        if new_reviewer {
+            self.unique_reviewers = self.unique_reviewers.checked_add(1)?;
+        }
+        Some(())
+    }
+
+    pub fn record_comment(&mut self) -> Option<()> {
+        self.total_comments = self.total_comments.checked_add(1)?;
+        Some(())
+    }
+
+    pub fn record_tip(&mut self, amount: u64) -> Option<()> {
+        self.total_tips = self.total_tips.checked_add(amount)?;
+        Some(())
+    }
+
+    pub fn record_sol_tip(&mut self, lamports: u64) -> Option<()> {
+        self.total_tip_lamports = self.total_tip_lamports.checked_add(lamports)?;
+        Some(())
+    }
+
+    /// Uncounts `comments` deleted comments, and their review if `review`.
+    /// Authors stay counted as unique reviewers.
+    pub fn remove_review(&mut self, review: bool, comments: u64) {
+        self.total_comments = self.total_comments.saturating_sub(comments);
+        if review {
+            self.total_reviews = self.total_reviews.saturating_sub(1);
+        }
+    }
+}
+
+/// The reviews with the most recent comments, likes and tips, at
+/// `["hot_feed"]`, so a front page needs one account fetch. Created by
+/// whichever handler first records activity. A review not yet in the feed
+/// replaces the least recently active entry; unused entries have the default
+/// key.
+#[derive(BorshSerialize, BorshDeserialize, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HotFeed {
+    pub discriminator: u8,
+    pub is_initialized: bool,
+    pub entries: [HotEntry; HotFeed::ENTRIES],
+}
+
+impl IsInitialized for HotFeed {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+impl HotFeed {
+    pub const DISCRIMINATOR: u8 = 32;
+    pub const SEED: &'static str = "hot_feed";
+    pub const ENTRIES: usize = 32;
+    /// Slots over which an entry's score halves, about an hour.
+    pub const HALF_LIFE_SLOTS: u64 = 9_000;
+    pub const COMMENT_WEIGHT: u64 = 4;
+    pub const LIKE_WEIGHT: u64 = 1;
+    pub const TIP_WEIGHT: u64 = 8;
+
+    pub fn get_account_size() -> usize {
+        1 + 1 + HotEntry::LEN * HotFeed::ENTRIES
+    }
+
+    /// Adds `weight` to `review`'s score decayed to `slot`, evicting the least
+    /// recently active review if it is not in the feed yet.
+    pub fn record(&mut self, review: &Pubkey, weight: u64, slot: u64) {
+        let entry = match self.entries.iter().position(|e| e.review == *review) {
+            Some(index) => &mut self.entries[index],
+            None => {
+                let entry = self
+                    .entries
+                    .iter_mut()
+                    .min_by_key(|e| (e.review != Pubkey::default(), e.slot))
+                    .expect("the feed has entries");
+                *entry = HotEntry {
+                    review: *review,
+                    score: 0,
+                    slot,
+                };
+                entry
+            }
+        };
+        entry.score = entry.score_at(slot).saturating_add(weight);
+        entry.slot = slot;
+    }
+
+    /// Reviews in the feed with their scores decayed to `slot`, hottest
+    /// first.
+    pub fn ranked(&self, slot: u64) -> Vec<(Pubkey, u64)> {
+        let mut ranked: Vec<_> = self
+            .entries
+            .iter()
+            .filter(|e| e.review != Pubkey::default())
+            .map(|e| (e.review, e.score_at(slot)))
+            .collect();
+        ranked.sort_by_key(|&(_, score)| Reverse(score));
+        ranked
+    }
+}
+
+/// One review in the `HotFeed`.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HotEntry {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_pubkey"))]
+    pub review: Pubkey,
+    /// Activity weight as of `slot`.
+    pub score: u64,
+    /// Slot of the review's latest activity.
+    pub slot: u64,
+}
+
+impl HotEntry {
+    pub const LEN: usize = 32 + 8 + 8;
+
+    /// `score` halved for every `HotFeed::HALF_LIFE_SLOTS` since `slot`.
+    pub fn score_at(&self, slot: u64) -> u64 {
+        let halvings = slot.saturating_sub(self.slot) / HotFeed::HALF_LIFE_SLOTS;
+        self.score
+            .checked_shr(u32::try_from(halvings).unwrap_or(u32::MAX))
+            .unwrap_or(0)
+    }
+}
+
+/// A reviewer's record, at `["reviewer_profile", reviewer]`, created with
+/// their first review.
+#[derive(BorshSerialize, BorshDeserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ReviewerProfile {
+    pub discriminator: u8,
+    pub is_initialized: bool,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_pubkey"))]
+    pub reviewer: Pubkey,
+    pub review_count: u64,
+    /// Reviews per fifth of the `RatingScale`, `ratings[0]` counting the
+    /// lowest. Reviews posted before the reviewer had a profile are not
+    /// counted.
+    pub ratings: [u32; 5],
+    /// Nonce the reviewer's next relayed review must sign, so each signed
+    /// review is posted once. Profiles created before it was appended are
+    /// shorter and read it as zero until their first relayed review.
+    pub relay_nonce: u64,
+    /// Epoch of the reviewer's latest review. Profiles created before it was
+    /// appended read it and `streak` as zero until their next review.
+    pub last_active_epoch: Epoch,
+    /// Consecutive epochs the reviewer posted in, ending at
+    /// `last_active_epoch`.
+    pub streak: u32,
+    /// Program `authority` is derived from.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_pubkey"))]
+    pub authority_program: Pubkey,
+    /// PDA of `authority_program`, such as a multisig running a shared critic
+    /// identity, that may sign for the reviewer wherever a session could.
+    /// Only its program can sign for it, through `invoke_signed`. The default
+    /// key, also how older profiles read it, when there is none.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_pubkey"))]
+    pub authority: Pubkey,
+}
+
+impl IsInitialized for ReviewerProfile {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+impl ReviewerProfile {
+    pub const DISCRIMINATOR: u8 = 19;
+    pub const SEED: &'static str = "reviewer_profile";
+
+    pub fn get_account_size() -> usize {
+        1 + 1 + 32 + 8 + 4 * 5 + 8 + 8 + 4 + 32 + 32
+    }
+
+    pub fn has_authority(&self) -> bool {
+        self.authority != Pubkey::default()
+    }
+
+    /// Counts a review posted in `epoch` towards the streak, which grows
+    /// once per consecutive epoch and restarts at one after a missed epoch.
+    pub fn record_activity(&mut self, epoch: Epoch) {
+        if self.streak > 0 && self.last_active_epoch == epoch {
+            return;
+        }
+        self.streak = if self.streak > 0 && self.last_active_epoch.checked_add(1) == Some(epoch) {
+            self.streak.saturating_add(1)
+        } else {
+            1
+        };
+        self.last_active_epoch = epoch;
+    }
+
+    /// The streak as of `epoch`: zero once an epoch went by without a
+    /// review, since the next review restarts it.
+    pub fn current_streak(&self, epoch: Epoch) -> u32 {
+        if epoch > self.last_active_epoch.saturating_add(1) {
+            0
+        } else {
+            self.streak
+        }
+    }
+
+    /// Counts a review with `rating`, which must be on `scale`. `None` on
+    /// overflow.
+    pub fn record_rating(&mut self, rating: u16, scale: &RatingScale) -> Option<()> {
+        let bucket = self.ratings.get_mut(scale.bucket(rating, 5)?)?;
+        *bucket = bucket.checked_add(1)?;
+        Some(())
+    }
+
+    /// Moves a review from `old_rating` to `new_rating`. A no-op when no review
+    /// is counted at `old_rating`, i.e. the review predates the profile, so the
+    /// histogram never counts more reviews than `review_count`.
+    /// A rating left off the scale by a scale change counts as a no-op too.
+    pub fn change_rating(&mut self, old_rating: u16, new_rating: u16, scale: &RatingScale) {
+        let (Some(old), Some(new)) = (scale.bucket(old_rating, 5), scale.bucket(new_rating, 5))
+        else {
+            return;
+        };
+        if self.ratings[old] > 0 {
+            self.ratings[old] -= 1;
+            self.ratings[new] = self.ratings[new].saturating_add(1);
+        }
+    }
+
+    /// Uncounts a deleted review with `rating`. Like `change_rating`, a no-op
+    /// for a rating with no review counted or off the scale.
+    pub fn remove_rating(&mut self, rating: u16, scale: &RatingScale) {
+        self.review_count = self.review_count.saturating_sub(1);
+        if let Some(bucket) = scale.bucket(rating, 5) {
+            self.ratings[bucket] = self.ratings[bucket].saturating_sub(1);
+        }
+    }
+}
+
+/// Totals over a registered movie's reviews, at `["movie_stats", movie]`.
+/// Counted as reviews keyed by the movie are added and deleted; rating edits
+/// and reviews posted before the stats existed are only picked up by a
+/// `RecomputeStats` pass.
+#[derive(BorshSerialize, BorshDeserialize, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MovieStats {
+    pub discriminator: u8,
+    pub is_initialized: bool,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_pubkey"))]
+    pub movie: Pubkey,
+    pub review_count: u64,
+    pub rating_sum: u64,
+    /// Last review counted by the recompute pass in progress, the default key
+    /// if none is.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_pubkey"))]
+    pub recompute_cursor: Pubkey,
+    pub recompute_count: u64,
+    pub recompute_sum: u64,
+}
+
+impl IsInitialized for MovieStats {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+impl MovieStats {
+    pub const DISCRIMINATOR: u8 = 20;
+    pub const SEED: &'static str = "movie_stats";
+    /// Reviews a single `RecomputeStats` call may count.
+    pub const MAX_RECOMPUTE_REVIEWS: usize = 10;
+
+    pub fn get_account_size() -> usize {
+        1 + 1 + 32 + 8 + 8 + 32 + 8 + 8
+    }
+
+    /// `None` on overflow.
+    pub fn record_review(&mut self, rating: u16) -> Option<()> {
+        self.review_count = self.review_count.checked_add(1)?;
+        self.rating_sum = self.rating_sum.checked_add(u64::from(rating))?;
+        Some(())
+    }
+
+    /// Counts `review` in the recompute pass. `None` on overflow.
+    pub fn recompute_review(&mut self, review: Pubkey, rating: u16) -> Option<()> {
+        self.recompute_count = self.recompute_count.checked_add(1)?;
+        self.recompute_sum = self.recompute_sum.checked_add(u64::from(rating))?;
+        self.recompute_cursor = review;
+        Some(())
+    }
+
+    /// Uncounts a deleted `review`, from the recompute pass too if the pass
+    /// already counted it.
+    pub fn remove_review(&mut self, review: Pubkey, rating: u16) {
+        self.review_count = self.review_count.saturating_sub(1);
+        self.rating_sum = self.rating_sum.saturating_sub(u64::from(rating));
+        if self.recompute_count > 0 && review <= self.recompute_cursor {
+            self.recompute_count -= 1;
+            self.recompute_sum = self.recompute_sum.saturating_sub(u64::from(rating));
+        }
+    }
+
+    pub fn reset_recompute(&mut self) {
+        self.recompute_cursor = Pubkey::default();
+        self.recompute_count = 0;
+        self.recompute_sum = 0;
+    }
+
+    /// Mean rating in hundredths, zero without reviews.
+    pub fn average_rating(&self) -> u32 {
+        match self.review_count {
+            0 => 0,
+            n => u32::try_from(self.rating_sum * 100 / n).unwrap_or(u32::MAX),
+        }
+    }
+}
+
+/// A registered movie's reviews grouped by rating, at
+/// `["rating_index", movie]`. `reviews` lists the `counts[0]` reviews in the
+/// lowest fifth of the rating scale, then the `counts[1]` in the next, up to
+/// the top fifth, in no order within a fifth, so clients fetch a movie's
+/// top-rated reviews a page at a time by slicing the account data at
+/// `RatingIndex::bucket_range` instead of scanning every review.
+///
+/// Reviews keyed by the movie are filed as they are added, moved as they are
+/// rerated and dropped as they are deleted; reviews posted before the index
+/// existed are filed by `IndexMovieReview`. A review stays in the fifth it
+/// was filed under when the rating scale changes, until it is rerated or
+/// refiled. The processor edits the keys in place through the associated
+/// functions rather than decoding them onto the heap.
+#[derive(BorshSerialize, BorshDeserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RatingIndex {
+    pub discriminator: u8,
+    pub is_initialized: bool,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_pubkey"))]
+    pub movie: Pubkey,
+    pub counts: [u32; RatingIndex::BUCKETS],
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_pubkey::vec"))]
+    pub reviews: Vec<Pubkey>,
+}
+
+impl IsInitialized for RatingIndex {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+impl RatingIndex {
+    pub const DISCRIMINATOR: u8 = 37;
+    pub const SEED: &'static str = "rating_index";
+    pub const BUCKETS: usize = 5;
+    const COUNTS_OFFSET: usize = 1 + 1 + 32;
+    const LEN_OFFSET: usize = RatingIndex::COUNTS_OFFSET + 4 * RatingIndex::BUCKETS;
+    /// Byte offset of the first review key, after the length of `reviews`.
+    pub const REVIEWS_OFFSET: usize = RatingIndex::LEN_OFFSET + 4;
+
+    pub fn get_account_size(reviews: usize) -> usize {
+        RatingIndex::REVIEWS_OFFSET + 32 * reviews
+    }
+
+    fn read_u32(data: &[u8], offset: usize) -> Result<u32, ProgramError> {
+        let bytes = data
+            .get(offset..offset + 4)
+            .ok_or(ProgramError::InvalidAccountData)?;
+        Ok(u32::from_le_bytes(bytes.try_into().unwrap()))
+    }
+
+    fn counts_of(data: &[u8]) -> Result<[u32; RatingIndex::BUCKETS], ProgramError> {
+        let mut counts = [0; RatingIndex::BUCKETS];
+        for (bucket, count) in counts.iter_mut().enumerate() {
+            *count = RatingIndex::read_u32(data, RatingIndex::COUNTS_OFFSET + 4 * bucket)?;
+        }
+        Ok(counts)
+    }
+
+    fn len_of(data: &[u8]) -> Result<usize, ProgramError> {
+        Ok(RatingIndex::read_u32(data, RatingIndex::LEN_OFFSET)? as usize)
+    }
+
+    /// Adds `delta` to the count of `bucket` and the length of `reviews`.
+    fn adjust(data: &mut [u8], bucket: usize, delta: i64) -> Result<(), ProgramError> {
+        for offset in [
+            RatingIndex::COUNTS_OFFSET + 4 * bucket,
+            RatingIndex::LEN_OFFSET,
+        ] {
+            let value = u32::try_from(i64::from(RatingIndex::read_u32(data, offset)?) + delta)
+                .map_err(|_| ProgramError::InvalidAccountData)?;
+            data[offset..offset + 4].copy_from_slice(&value.to_le_bytes());
+        }
+        Ok(())
+    }
+
+    /// Byte range of `bucket`'s review keys in the account data. Only the
+    /// counts are read, so `data` may be just the first `REVIEWS_OFFSET`
+    /// bytes of the account.
+    pub fn bucket_range(data: &[u8], bucket: usize) -> Result<Range<usize>, ProgramError> {
+        let counts = RatingIndex::counts_of(data)?;
+        let count = *counts.get(bucket).ok_or(ProgramError::InvalidArgument)? as usize;
+        let before: usize = counts[..bucket].iter().map(|&count| count as usize).sum();
+        let start = RatingIndex::REVIEWS_OFFSET + 32 * before;
+        Ok(start..start + 32 * count)
+    }
+
+    /// Files `review` under `bucket`, making room by shifting the higher
+    /// fifths up a key. `data` must already have room for one more key.
+    pub fn insert(data: &mut [u8], bucket: usize, review: &Pubkey) -> Result<(), ProgramError> {
+        let end = RatingIndex::bucket_range(data, bucket)?.end;
+        let len = RatingIndex::len_of(data)?;
+        let keys_end = RatingIndex::get_account_size(len);
+        if data.len() < keys_end + 32 || end > keys_end {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        data.copy_within(end..keys_end, end + 32);
+        data[end..end + 32].copy_from_slice(review.as_ref());
+        RatingIndex::adjust(data, bucket, 1)
+    }
+
+    /// Takes `review` out of the index, shifting the keys after it down and
+    /// zeroing the key left free at the end. Returns the fifth it was filed
+    /// under, `None` if it was not indexed.
+    pub fn remove(data: &mut [u8], review: &Pubkey) -> Result<Option<usize>, ProgramError> {
+        let counts = RatingIndex::counts_of(data)?;
+        let keys_end = RatingIndex::get_account_size(RatingIndex::len_of(data)?);
+        let keys = data
+            .get(RatingIndex::REVIEWS_OFFSET..keys_end)
+            .ok_or(ProgramError::InvalidAccountData)?;
+        let Some(position) = keys.chunks_exact(32).position(|key| key == review.as_ref()) else {
+            return Ok(None);
+        };
+
+        let mut filed = 0;
+        let bucket = counts
+            .iter()
+            .position(|&count| {
+                filed += count as usize;
+                position < filed
+            })
+            .ok_or(ProgramError::InvalidAccountData)?;
+
+        let start = RatingIndex::REVIEWS_OFFSET + 32 * position;
+        data.copy_within(start + 32..keys_end, start);
+        data[keys_end - 32..keys_end].fill(0);
+        RatingIndex::adjust(data, bucket, -1)?;
+        Ok(Some(bucket))
+    }
+}
+
+/// Aggregates over a set of reviews, written once by the config authority at
+/// `["snapshot", slot]` so analytics consumers can diff snapshots instead of
+/// replaying events. Only the reviews passed to `WriteSnapshot` are counted.
+#[derive(BorshSerialize, BorshDeserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ReviewSnapshot {
+    pub discriminator: u8,
+    pub is_initialized: bool,
+    /// Layout version, `ReviewSnapshot::VERSION` when written.
+    pub version: u8,
+    /// Slot the snapshot is keyed by.
+    pub slot: u64,
+    /// Slot the accounts were actually read at, never before `slot`.
+    pub written_slot: u64,
+    pub review_count: u64,
+    pub comment_count: u64,
+    /// Mean rating in hundredths, zero without reviews.
+    pub average_rating: u32,
+    /// Mean comments per review in hundredths, zero without reviews.
+    pub average_comments: u64,
+    /// Highest rated reviews, most commented first among equal ratings and
+    /// then by key, so the same accounts always give the same list.
+    pub top_reviews: Vec<SnapshotEntry>,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SnapshotEntry {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_pubkey"))]
+    pub review: Pubkey,
+    pub rating: u16,
+    pub comments: u64,
+}
+
+impl IsInitialized for ReviewSnapshot {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+impl SnapshotEntry {
+    pub const LEN: usize = 32 + 2 + 8;
+}
+
+impl ReviewSnapshot {
+    pub const DISCRIMINATOR: u8 = 17;
+    pub const SEED: &'static str = "snapshot";
+    /// Version 1 held `u8` ratings and a `u16` average, see
+    /// `migration::decode_snapshot_v1`.
+    pub const VERSION: u8 = 2;
+    pub const MAX_TOP_REVIEWS: usize = 10;
+
+    pub fn get_account_size() -> usize {
+        1 + 1 + 1 + 8 + 8 + 8 + 8 + 4 + 8 + 4 + ReviewSnapshot::MAX_TOP_REVIEWS * SnapshotEntry::LEN
+    }
+
+    /// Summarizes `entries`, one per review, into a snapshot at `slot`.
+    pub fn summarize(slot: u64, written_slot: u64, mut entries: Vec<SnapshotEntry>) -> Self {
+        let review_count = entries.len() as u64;
+        let rating_sum: u64 = entries.iter().map(|entry| u64::from(entry.rating)).sum();
+        let comment_count = entries
+            .iter()
+            .fold(0u64, |sum, entry| sum.saturating_add(entry.comments));
+        let (average_rating, average_comments) = match review_count {
+            0 => (0, 0),
+            // Ratings fit a u16, so their mean in hundredths fits a u32
+            n => (
+                (rating_sum * 100 / n) as u32,
+                u64::try_from(u128::from(comment_count) * 100 / u128::from(n)).unwrap_or(u64::MAX),
+            ),
+        };
+
+        entries.sort_by(|a, b| {
+            b.rating
+                .cmp(&a.rating)
+                .then(b.comments.cmp(&a.comments))
+                .then(a.review.cmp(&b.review))
+        });
+        entries.truncate(ReviewSnapshot::MAX_TOP_REVIEWS);
+
+        Self {
+            discriminator: ReviewSnapshot::DISCRIMINATOR,
+            is_initialized: true,
+            version: ReviewSnapshot::VERSION,
+            slot,
+            written_slot,
+            review_count,
+            comment_count,
+            average_rating,
+            average_comments,
+            top_reviews: entries,
+        }
+    }
+}
+
+/// Account layout a release oracle writes for every theatrical release it
+/// confirms. The program trusts one only if it is owned by the configured
+/// `release_oracle` and carries the configured `release_feed_id`.
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct ReleaseAttestation {
+    pub feed_id: [u8; 32],
+    /// `Movie::title_hash` of the released movie.
+    pub title_hash: [u8; 32],
+    pub release_date: UnixTimestamp,
+}
+
+/// Account layout an age attester writes for every wallet it vouches for.
+/// The program trusts one only if it is owned by the configured
+/// `age_attester`.
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct WalletAgeAttestation {
+    pub wallet: Pubkey,
+    /// Earliest slot the attester saw the wallet active in.
+    pub first_seen_slot: u64,
+}
+
+/// What a dry run found the instruction would cost its payer, in lamports,
+/// as returned with `instruction::DRY_RUN_FLAG`.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct CostPreview {
+    /// Fee paid into the config PDA, zero if free or waived.
+    pub fee: u64,
+    /// Rent deposited into the accounts created or grown.
+    pub rent: u64,
+}
+
+/// Who can upgrade the program, as returned by `GetUpgradeAuthorityStatus`.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Copy, Debug, Eq, PartialEq)]
+pub enum UpgradeAuthorityStatus {
+    /// The upgrade authority is burned, so the program can never change.
+    Immutable,
+    /// Only the config authority can upgrade the program.
+    ConfigAuthority,
+    /// Someone other than the config authority can upgrade the program.
+    Other(Pubkey),
+}
+
+/// Where an imported review came from, stored right after the room
+/// `MovieAccountState::get_account_size` gives the review.
+#[derive(BorshSerialize, BorshDeserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ForeignReviewOrigin {
+    /// Wormhole chain ID the review was posted on.
+    pub origin_chain: u16,
+    pub emitter_address: [u8; 32],
+    pub sequence: u64,
+}
+
+impl ForeignReviewOrigin {
+    pub const LEN: usize = 2 + 32 + 8;
+}
+
+/// Account layout the Wormhole core bridge writes for every VAA whose
+/// guardian signatures it has verified (`PostedVAA`).
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct PostedVaa {
+    pub magic: [u8; 3],
+    pub vaa_version: u8,
+    pub consistency_level: u8,
+    pub vaa_time: u32,
+    pub vaa_signature_account: Pubkey,
+    pub submission_time: u32,
+    pub nonce: u32,
+    pub sequence: u64,
+    pub emitter_chain: u16,
+    pub emitter_address: [u8; 32],
+    pub payload: Vec<u8>,
+}
+
+impl PostedVaa {
+    pub const MAGIC: [u8; 3] = *b"vaa";
+}
+
+/// Payload of a VAA announcing a review posted on another chain.
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct ForeignReviewMessage {
+    /// The author's address on the origin chain.
+    pub author: [u8; 32],
+    pub title: String,
+    /// Part of the wire format, so it stays a `u8`; imported reviews must
+    /// still fall on the configured `RatingScale`.
+    pub rating: u8,
+    pub description: String,
+}
+
+/// Occupies the `[reviewer, movie]` review address for a title-seeded review
+/// written before the movie was registered, and points at it.
+#[derive(BorshSerialize, BorshDeserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ReviewLink {
+    pub discriminator: u8,
+    pub is_initialized: bool,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_pubkey"))]
+    pub review: Pubkey,
+}
+
+impl IsInitialized for ReviewLink {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+impl ReviewLink {
+    pub const DISCRIMINATOR: u8 = 8;
+
+    pub fn get_account_size() -> usize {
+        1 + 1 + 32
+    }
+}
+
+/// The review drawn as featured, at `["featured"]`. A draw is requested at
+/// most once per UTC day and settled once its VRF randomness is fulfilled.
+#[derive(BorshSerialize, BorshDeserialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FeaturedReview {
+    pub discriminator: u8,
+    pub is_initialized: bool,
+    /// Days since the Unix epoch of the latest request.
+    pub day: i64,
+    /// Winner of the latest settled draw.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_pubkey"))]
+    pub review: Pubkey,
+    /// Whether the latest request still awaits its randomness.
+    pub pending: bool,
+    /// VRF randomness account committed to by the latest request.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_pubkey"))]
+    pub randomness: Pubkey,
+    /// `FeaturedReview::candidates_hash` of the candidates requested.
+    pub candidates_hash: [u8; 32],
+}
+
+impl IsInitialized for FeaturedReview {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+impl FeaturedReview {
+    pub const DISCRIMINATOR: u8 = 10;
+    pub const SEED: &'static str = "featured";
+    pub const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+    /// Lamports a draw request escrows for whoever cranks its fulfillment.
+    pub const CRANK_BOUNTY: u64 = 10_000;
+
+    pub fn candidates_hash(candidates: &[&Pubkey]) -> [u8; 32] {
+        let keys: Vec<&[u8]> = candidates.iter().map(|key| key.as_ref()).collect();
+        hashv(&keys).to_bytes()
+    }
+
+    pub fn get_account_size() -> usize {
+        1 + 1 + 8 + 32 + 1 + 32 + 32
+    }
+}
+
+/// Leading fields of a VRF randomness account, after its 8-byte account
+/// discriminator. This is the layout of ORAO's `Randomness` account; the
+/// randomness stays zero until the request is fulfilled.
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct VrfRandomness {
+    pub seed: [u8; 32],
+    pub randomness: [u8; 64],
+}
+
+impl VrfRandomness {
+    pub const ACCOUNT_DISCRIMINATOR_LEN: usize = 8;
 
-    pub fn get_account_size(comment: &str) -> usize {
-        (4 + MovieComment::DISCRIMINATOR.len()) + 1 + 32 + 32 + (4 + comment.len()) + 8
+    pub fn is_fulfilled(&self) -> bool {
+        self.randomness != [0; 64]
     }
 }
@@ -0,0 +1,364 @@
+//! Layout stability suite: `abi` offsets are read by consumers that never
+//! deserialize, so their values are pinned here and checked against the
+//! Borsh encoding of the structs they describe.
+
+use borsh::BorshSerialize;
+use movie_review::{
+    abi::{self, Field},
+    programs,
+    state::{
+        AdminAction, AuditEntry, AuditLog, BodyCommitment, CommentKarma, CommentPath, CommentVote,
+        DuplicateChallenge, FeaturedReview, FeeSchedule, HotEntry, HotFeed, MovieAccountState,
+        MovieComment, PendingRemoval, ProgramConfig, RatingScale, ReviewEdits, ReviewFingerprint,
+        ReviewSnapshot, ReviewerProfile, Session,
+    },
+};
+use solana_program::pubkey::Pubkey;
+
+fn assert_field<T: BorshSerialize>(data: &[u8], field: Field, value: &T) {
+    assert_eq!(
+        field.read(data),
+        Some(borsh::to_vec(value).unwrap().as_slice()),
+        "{field:?}"
+    );
+}
+
+#[test]
+fn layout_lengths_are_pinned() {
+    let lengths = [
+        ("content_limits", abi::content_limits::LEN, 8),
+        ("rating_scale", abi::rating_scale::LEN, 5),
+        ("fee_schedule", abi::fee_schedule::LEN, 32),
+        ("review", abi::review::LEN, 36),
+        ("comment_counter", abi::comment_counter::LEN, 26),
+        ("comment", abi::comment::LEN, 66),
+        ("comment_page", abi::comment_page::LEN, 42),
+        ("config", abi::config::LEN, 583),
+        ("movie", abi::movie::LEN, 2),
+        ("translation", abi::translation::LEN, 34),
+        ("external_id_index", abi::external_id_index::LEN, 34),
+        ("follow", abi::follow::LEN, 66),
+        ("mute", abi::mute::LEN, 66),
+        ("session", abi::session::LEN, 75),
+        ("global_stats", abi::global_stats::LEN, 42),
+        ("reviewer_profile", abi::reviewer_profile::LEN, 146),
+        ("movie_stats", abi::movie_stats::LEN, 98),
+        ("snapshot", abi::snapshot::LEN, 47),
+        ("review_link", abi::review_link::LEN, 34),
+        ("featured_review", abi::featured_review::LEN, 107),
+        ("audit_entry", abi::audit_entry::LEN, 81),
+        ("audit_log", abi::audit_log::LEN, 2602),
+        ("pending_removal", abi::pending_removal::LEN, 83),
+        ("body_commitment", abi::body_commitment::LEN, 66),
+        ("review_fingerprint", abi::review_fingerprint::LEN, 50),
+        ("duplicate_challenge", abi::duplicate_challenge::LEN, 107),
+        ("comment_vote", abi::comment_vote::LEN, 67),
+        ("comment_karma", abi::comment_karma::LEN, 51),
+        ("hot_entry", abi::hot_entry::LEN, 48),
+        ("hot_feed", abi::hot_feed::LEN, 1538),
+        ("review_edits", abi::review_edits::LEN, 42),
+        ("co_authors", abi::co_authors::LEN, 34),
+        ("translation_bounty", abi::translation_bounty::LEN, 74),
+        (
+            "translation_submission",
+            abi::translation_submission::LEN,
+            66,
+        ),
+        ("rating_index", abi::rating_index::LEN, 54),
+        ("title_filter", abi::title_filter::LEN, 166),
+        ("rent_payer", abi::rent_payer::LEN, 66),
+    ];
+    for (name, len, pinned) in lengths {
+        assert_eq!(len, pinned, "{name}");
+    }
+}
+
+#[test]
+fn field_offsets_are_pinned() {
+    assert_eq!(abi::review::REVIEWER.offset, 2);
+    assert_eq!(abi::review::RATING.offset, 34);
+    assert_eq!(abi::comment::COMMENTER.offset, 34);
+    assert_eq!(abi::config::FEES.offset, 234);
+    assert_eq!(abi::config::RATING_SCALE.offset, 346);
+    assert_eq!(abi::config::CPI_CALLERS.offset, 351);
+    assert_eq!(abi::config::FEATURES.offset, 479);
+    assert_eq!(abi::config::REMOVAL_WINDOW.offset, 487);
+    assert_eq!(abi::config::AGE_ATTESTER.offset, 503);
+    assert_eq!(abi::reviewer_profile::RELAY_NONCE.offset, 62);
+    assert_eq!(abi::reviewer_profile::STREAK.offset, 78);
+    assert_eq!(abi::reviewer_profile::AUTHORITY_PROGRAM.offset, 82);
+    assert_eq!(abi::reviewer_profile::AUTHORITY.offset, 114);
+    assert_eq!(abi::audit_log::ENTRIES.offset, 10);
+    assert_eq!(abi::audit_entry::TARGET.offset, 49);
+}
+
+#[test]
+fn fixed_layouts_span_their_accounts() {
+    assert_eq!(abi::config::LEN, ProgramConfig::get_account_size());
+    assert_eq!(abi::session::LEN, Session::get_account_size());
+    assert_eq!(
+        abi::reviewer_profile::LEN,
+        ReviewerProfile::get_account_size()
+    );
+    assert_eq!(
+        abi::featured_review::LEN,
+        FeaturedReview::get_account_size()
+    );
+    assert_eq!(abi::audit_log::LEN, AuditLog::get_account_size());
+    assert_eq!(
+        abi::pending_removal::LEN,
+        PendingRemoval::get_account_size()
+    );
+    assert_eq!(
+        abi::body_commitment::LEN,
+        BodyCommitment::get_account_size()
+    );
+    assert_eq!(
+        abi::review_fingerprint::LEN,
+        ReviewFingerprint::get_account_size()
+    );
+    assert_eq!(
+        abi::duplicate_challenge::LEN,
+        DuplicateChallenge::get_account_size()
+    );
+    assert_eq!(abi::comment_vote::LEN, CommentVote::get_account_size());
+    assert_eq!(abi::comment_karma::LEN, CommentKarma::get_account_size());
+    assert_eq!(abi::hot_entry::LEN, HotEntry::LEN);
+    assert_eq!(abi::hot_feed::LEN, HotFeed::get_account_size());
+    assert_eq!(abi::review_edits::LEN, ReviewEdits::get_account_size());
+}
+
+#[test]
+fn config_fields_match_borsh() {
+    let config = ProgramConfig {
+        is_initialized: true,
+        authority: Pubkey::new_unique(),
+        collection_generation: 3,
+        foreign_emitter_chain: 2,
+        fees: FeeSchedule {
+            add_review: 1,
+            add_comment: 2,
+            vote: 3,
+            boost: 4,
+        },
+        fee_exempt_min_balance: 7,
+        tip_program: Pubkey::new_unique(),
+        rating_scale: RatingScale {
+            min: 1,
+            max: 10,
+            decimals: 1,
+        },
+        cpi_callers: [Pubkey::new_unique(); ProgramConfig::MAX_CPI_CALLERS],
+        features: ProgramConfig::ALL_FEATURES | ProgramConfig::FEATURES_SET,
+        removal_window: 9_000,
+        min_wallet_age: 216_000,
+        age_attester: Pubkey::new_unique(),
+        hide_threshold: -3,
+        edit_burn_mint: Pubkey::new_unique(),
+        edit_burn_amount: 5,
+        ..ProgramConfig::default()
+    };
+    let data = borsh::to_vec(&config).unwrap();
+    assert_eq!(data.len(), abi::config::LEN);
+    assert_field(&data, abi::config::AUTHORITY, &config.authority);
+    assert_field(
+        &data,
+        abi::config::COLLECTION_GENERATION,
+        &config.collection_generation,
+    );
+    assert_field(
+        &data,
+        abi::config::FOREIGN_EMITTER_CHAIN,
+        &config.foreign_emitter_chain,
+    );
+    assert_field(&data, abi::config::FEES, &config.fees);
+    assert_field(
+        &data,
+        abi::config::FEE_EXEMPT_MIN_BALANCE,
+        &config.fee_exempt_min_balance,
+    );
+    assert_field(&data, abi::config::TIP_PROGRAM, &config.tip_program);
+    assert_field(&data, abi::config::RATING_SCALE, &config.rating_scale);
+    assert_field(&data, abi::config::CPI_CALLERS, &config.cpi_callers);
+    assert_field(&data, abi::config::FEATURES, &config.features);
+    assert_field(&data, abi::config::REMOVAL_WINDOW, &config.removal_window);
+    assert_field(&data, abi::config::MIN_WALLET_AGE, &config.min_wallet_age);
+    assert_field(&data, abi::config::AGE_ATTESTER, &config.age_attester);
+    assert_field(&data, abi::config::HIDE_THRESHOLD, &config.hide_threshold);
+    assert_field(&data, abi::config::EDIT_BURN_MINT, &config.edit_burn_mint);
+    assert_field(
+        &data,
+        abi::config::EDIT_BURN_AMOUNT,
+        &config.edit_burn_amount,
+    );
+
+    let fees = abi::config::FEES.read(&data).unwrap();
+    assert_field(fees, abi::fee_schedule::BOOST, &config.fees.boost);
+}
+
+#[test]
+fn variable_accounts_match_borsh_up_to_their_first_variable_field() {
+    let review = MovieAccountState {
+        discriminator: MovieAccountState::DISCRIMINATOR,
+        is_initialized: true,
+        reviewer: Pubkey::new_unique(),
+        rating: 4,
+        title: "Dune".to_string(),
+        description: "Sand".to_string(),
+        body_format: Default::default(),
+        comment_settings: None,
+    };
+    let data = borsh::to_vec(&review).unwrap();
+    assert_field(&data, abi::review::REVIEWER, &review.reviewer);
+    assert_field(&data, abi::review::RATING, &review.rating);
+    assert_field(
+        &data[abi::review::LEN..],
+        Field { offset: 0, len: 8 },
+        &review.title,
+    );
+
+    let comment = MovieComment {
+        discriminator: MovieComment::DISCRIMINATOR,
+        is_initialized: true,
+        reviewer: Pubkey::new_unique(),
+        commenter: Pubkey::new_unique(),
+        comment: "Hi".to_string(),
+        count: 9,
+        media_uri: None,
+        path: CommentPath::default(),
+    };
+    let data = borsh::to_vec(&comment).unwrap();
+    assert_field(&data, abi::comment::COMMENTER, &comment.commenter);
+
+    let snapshot = ReviewSnapshot {
+        discriminator: ReviewSnapshot::DISCRIMINATOR,
+        is_initialized: true,
+        version: ReviewSnapshot::VERSION,
+        slot: 1,
+        written_slot: 2,
+        review_count: 3,
+        comment_count: 4,
+        average_rating: 5,
+        average_comments: 6,
+        top_reviews: Vec::new(),
+    };
+    let data = borsh::to_vec(&snapshot).unwrap();
+    assert_field(
+        &data,
+        abi::snapshot::AVERAGE_RATING,
+        &snapshot.average_rating,
+    );
+    assert_field(
+        &data,
+        abi::snapshot::AVERAGE_COMMENTS,
+        &snapshot.average_comments,
+    );
+}
+
+#[test]
+fn audit_log_wraps_and_matches_borsh() {
+    let mut log = AuditLog {
+        is_initialized: true,
+        ..AuditLog::default()
+    };
+    let authority = Pubkey::new_unique();
+    let count = AuditLog::CAPACITY as u64 + 3;
+    for slot in 0..count {
+        log.push(AuditEntry {
+            slot,
+            authority,
+            action: AdminAction::SetFeatures,
+            ..AuditEntry::default()
+        });
+    }
+    assert_eq!(log.next_sequence, count);
+    let recent: Vec<u64> = log.recent().map(|entry| entry.sequence).collect();
+    assert_eq!(recent, (3..count).collect::<Vec<_>>());
+    assert!(log.recent().all(|entry| entry.slot == entry.sequence));
+
+    let data = borsh::to_vec(&log).unwrap();
+    assert_eq!(data.len(), abi::audit_log::LEN);
+    assert_field(&data, abi::audit_log::NEXT_SEQUENCE, &log.next_sequence);
+    let first = Field {
+        offset: abi::audit_log::ENTRIES.offset,
+        len: abi::audit_entry::LEN,
+    };
+    let entry = first.read(&data).unwrap();
+    assert_field(entry, abi::audit_entry::SEQUENCE, &log.entries[0].sequence);
+    assert_field(entry, abi::audit_entry::AUTHORITY, &authority);
+    assert_field(entry, abi::audit_entry::ACTION, &AdminAction::SetFeatures);
+}
+
+#[test]
+fn profile_fields_match_borsh() {
+    let profile = ReviewerProfile {
+        discriminator: ReviewerProfile::DISCRIMINATOR,
+        is_initialized: true,
+        reviewer: Pubkey::new_unique(),
+        review_count: 5,
+        ratings: [1, 0, 2, 0, 2],
+        relay_nonce: 11,
+        last_active_epoch: 420,
+        streak: 3,
+        authority_program: Pubkey::new_unique(),
+        authority: Pubkey::new_unique(),
+    };
+    let data = borsh::to_vec(&profile).unwrap();
+    assert_field(&data, abi::reviewer_profile::REVIEWER, &profile.reviewer);
+    assert_field(
+        &data,
+        abi::reviewer_profile::REVIEW_COUNT,
+        &profile.review_count,
+    );
+    assert_field(&data, abi::reviewer_profile::RATINGS, &profile.ratings);
+    assert_field(
+        &data,
+        abi::reviewer_profile::RELAY_NONCE,
+        &profile.relay_nonce,
+    );
+    assert_field(
+        &data,
+        abi::reviewer_profile::LAST_ACTIVE_EPOCH,
+        &profile.last_active_epoch,
+    );
+    assert_field(&data, abi::reviewer_profile::STREAK, &profile.streak);
+    assert_field(
+        &data,
+        abi::reviewer_profile::AUTHORITY_PROGRAM,
+        &profile.authority_program,
+    );
+    assert_field(&data, abi::reviewer_profile::AUTHORITY, &profile.authority);
+}
+
+/// The client derives token and metadata addresses without those programs'
+/// crates, so they must agree with them.
+#[test]
+fn program_ids_match_their_crates() {
+    assert_eq!(programs::token_2022::ID, spl_token_2022::ID);
+    assert_eq!(
+        programs::associated_token::ID,
+        spl_associated_token_account::ID
+    );
+    assert_eq!(programs::token_metadata::ID, mpl_token_metadata::ID);
+
+    let wallet = Pubkey::new_unique();
+    let mint = Pubkey::new_unique();
+    for token_program in [spl_token::ID, spl_token_2022::ID] {
+        assert_eq!(
+            programs::associated_token_address(&wallet, &mint, &token_program),
+            spl_associated_token_account::get_associated_token_address_with_program_id(
+                &wallet,
+                &mint,
+                &token_program,
+            )
+        );
+    }
+    assert_eq!(
+        programs::metadata_address(&mint),
+        mpl_token_metadata::accounts::Metadata::find_pda(&mint).0
+    );
+    assert_eq!(
+        programs::master_edition_address(&mint),
+        mpl_token_metadata::accounts::MasterEdition::find_pda(&mint).0
+    );
+}
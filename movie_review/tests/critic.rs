@@ -0,0 +1,236 @@
+//! A critic run by another program, e.g. a multisig or DAO, acts through a
+//! PDA of that program it signs for with `invoke_signed`. Only the PDA the
+//! critic's profile names may sign for them.
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use movie_review::{
+    client::{
+        instructions::{add_comment, set_critic_authority, with_critic_authority},
+        pda::{find_comment_address, find_comment_counter_address, find_review_address},
+    },
+    error::ReviewError,
+    state::{BodyFormat, MovieAccountState, MovieCommentCounter},
+};
+use solana_program::{
+    account_info::AccountInfo,
+    entrypoint::ProgramResult,
+    instruction::{AccountMeta, Instruction, InstructionError},
+    program::invoke_signed,
+};
+use solana_program_test::{processor, BanksClient, ProgramTest};
+use solana_sdk::{
+    account::Account,
+    hash::Hash,
+    native_token::LAMPORTS_PER_SOL,
+    pubkey::Pubkey,
+    rent::Rent,
+    signature::Keypair,
+    signer::Signer,
+    system_program,
+    transaction::{Transaction, TransactionError},
+};
+
+const TITLE: &str = "Twelve Angry Men";
+
+/// What the multisig forwards: the seed of the PDA it signs with and the
+/// movie review instruction data.
+#[derive(BorshSerialize, BorshDeserialize)]
+struct Forward {
+    seed: Vec<u8>,
+    data: Vec<u8>,
+}
+
+/// Stands in for a multisig: forwards to the movie review program, passed
+/// last, with the first account, its PDA for `seed`, signing.
+fn multisig(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let forward = Forward::try_from_slice(data)?;
+    let (movie_review, accounts) = accounts.split_last().unwrap();
+    let (_, bump) = Pubkey::find_program_address(&[&forward.seed], program_id);
+    let metas = accounts
+        .iter()
+        .enumerate()
+        .map(|(i, account)| AccountMeta {
+            pubkey: *account.key,
+            is_signer: i == 0,
+            is_writable: account.is_writable,
+        })
+        .collect();
+    invoke_signed(
+        &Instruction::new_with_bytes(*movie_review.key, &forward.data, metas),
+        accounts,
+        &[&[&forward.seed, &[bump]]],
+    )
+}
+
+/// Has `multisig_id` sign `instruction` with its PDA for `seed`.
+fn through_multisig(multisig_id: &Pubkey, seed: &[u8], instruction: Instruction) -> Instruction {
+    let mut accounts: Vec<_> = instruction
+        .accounts
+        .into_iter()
+        .map(|meta| AccountMeta {
+            is_signer: false,
+            ..meta
+        })
+        .collect();
+    accounts.push(AccountMeta::new_readonly(instruction.program_id, false));
+    Instruction::new_with_borsh(
+        *multisig_id,
+        &Forward {
+            seed: seed.to_vec(),
+            data: instruction.data,
+        },
+        accounts,
+    )
+}
+
+async fn send(
+    banks_client: &mut BanksClient,
+    payer: &Keypair,
+    signers: &[&Keypair],
+    instruction: Instruction,
+    recent_blockhash: Hash,
+) -> Result<(), TransactionError> {
+    let mut all_signers = vec![payer];
+    all_signers.extend_from_slice(signers);
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction],
+        Some(&payer.pubkey()),
+        &all_signers,
+        recent_blockhash,
+    );
+    banks_client
+        .process_transaction(transaction)
+        .await
+        .map_err(|err| err.unwrap())
+}
+
+fn program_account(program_id: &Pubkey, mut data: Vec<u8>, len: usize) -> Account {
+    data.resize(len, 0);
+    Account {
+        lamports: Rent::default().minimum_balance(len),
+        data,
+        owner: *program_id,
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
+#[tokio::test]
+async fn critic_authority_signs_through_its_program() {
+    let program_id = Pubkey::new_unique();
+    let multisig_id = Pubkey::new_unique();
+    let critic = Keypair::new();
+    let author = Pubkey::new_unique();
+    let (review, _) = find_review_address(&program_id, &author, TITLE);
+    let (authority, _) = Pubkey::find_program_address(&[b"critic"], &multisig_id);
+    let (other, _) = Pubkey::find_program_address(&[b"other"], &multisig_id);
+
+    let mut program_test = ProgramTest::new(
+        "movie_review",
+        program_id,
+        processor!(movie_review::processor::process_instruction),
+    );
+    program_test.add_program("multisig", multisig_id, processor!(multisig));
+    // The PDAs pay for the comments they sign
+    for wallet in [critic.pubkey(), authority, other] {
+        program_test.add_account(
+            wallet,
+            Account::new(LAMPORTS_PER_SOL, 0, &system_program::ID),
+        );
+    }
+    let review_data = MovieAccountState {
+        discriminator: MovieAccountState::DISCRIMINATOR,
+        is_initialized: true,
+        reviewer: author,
+        rating: 4,
+        title: TITLE.to_string(),
+        description: "Tense".to_string(),
+        body_format: BodyFormat::Plain,
+        comment_settings: None,
+    };
+    program_test.add_account(
+        review,
+        program_account(
+            &program_id,
+            borsh::to_vec(&review_data).unwrap(),
+            MovieAccountState::MAX_ACCOUNT_SIZE,
+        ),
+    );
+    let counter_data = MovieCommentCounter {
+        discriminator: MovieCommentCounter::DISCRIMINATOR,
+        is_initialized: true,
+        total_comments_created: 0,
+        page: 0,
+        active_comments: 0,
+    };
+    program_test.add_account(
+        find_comment_counter_address(&program_id, &review).0,
+        program_account(
+            &program_id,
+            borsh::to_vec(&counter_data).unwrap(),
+            MovieCommentCounter::get_account_size(),
+        ),
+    );
+
+    let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+    send(
+        &mut banks_client,
+        &payer,
+        &[&critic],
+        set_critic_authority(&program_id, &critic.pubkey(), &authority, &multisig_id),
+        recent_blockhash,
+    )
+    .await
+    .unwrap();
+
+    let comment = |authority: &Pubkey| {
+        with_critic_authority(
+            add_comment(
+                &program_id,
+                &critic.pubkey(),
+                &review,
+                &author,
+                0,
+                "Agreed",
+                None,
+            ),
+            &critic.pubkey(),
+            authority,
+        )
+    };
+
+    // Another PDA of the same program comments as itself, so the critic's
+    // mute address passed for it does not match
+    let err = send(
+        &mut banks_client,
+        &payer,
+        &[],
+        through_multisig(&multisig_id, b"other", comment(&other)),
+        recent_blockhash,
+    )
+    .await
+    .unwrap_err();
+    assert_eq!(
+        err,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(ReviewError::InvalidPDA as u32)
+        )
+    );
+
+    // The mute address is derived for the critic, so the comment only goes
+    // through with the authority acting as them
+    send(
+        &mut banks_client,
+        &payer,
+        &[],
+        through_multisig(&multisig_id, b"critic", comment(&authority)),
+        recent_blockhash,
+    )
+    .await
+    .unwrap();
+    let (comment, _) = find_comment_address(&program_id, &review, 0);
+    let account = banks_client.get_account(comment).await.unwrap().unwrap();
+    assert_eq!(account.owner, program_id);
+    assert!(banks_client.get_balance(authority).await.unwrap() < LAMPORTS_PER_SOL);
+}
@@ -0,0 +1,3434 @@
+//! Negative-test matrix. Each instruction gets a valid baseline case and the
+//! `error_matrix!` macro generates one test per violated constraint, applying
+//! a mutation to the baseline and asserting the exact error returned.
+//!
+//! Tests call the processor directly with in-memory accounts: every check runs
+//! before the first CPI, and oversized payloads would not fit in a real
+//! transaction anyway.
+
+use movie_review::{
+    abi,
+    error::ReviewError,
+    fingerprint,
+    instruction::{MovieInstruction, DRY_RUN_FLAG},
+    processor::process_instruction,
+    state::{
+        comment_seed_bytes, AppealStatus, AuditLog, BodyCommitment, BodyFormat, CoAuthor,
+        CommentKarma, CommentPage, CommentPath, CommentPolicy, CommentSettings, CommentVote,
+        CommentVoteKind, ContentLimits, DuplicateChallenge, FeaturedReview, Follow,
+        ForeignReviewMessage, GlobalStats, HotFeed, Movie, MovieAccountState, MovieComment,
+        MovieCommentCounter, MovieStats, Mute, PendingRemoval, PostedVaa, ProgramConfig,
+        RatingIndex, RatingScale, ReleaseAttestation, RentPayer, ReviewCoAuthors, ReviewEdits,
+        ReviewFingerprint, ReviewSnapshot, ReviewTranslation, ReviewerProfile, Session,
+        TitleFilter, TranslationBounty, TranslationSubmission, VrfRandomness, MAX_COMMENT_DEPTH,
+    },
+};
+use solana_program::{
+    account_info::AccountInfo, borsh1::try_from_slice_unchecked, bpf_loader_upgradeable,
+    entrypoint::ProgramResult, program_error::ProgramError, pubkey::Pubkey, system_program, sysvar,
+};
+use solana_sdk::{signature::Keypair, signer::Signer};
+use soldev_program_errors::CommonError;
+
+struct TestAccount {
+    key: Pubkey,
+    owner: Pubkey,
+    lamports: u64,
+    data: Vec<u8>,
+    is_signer: bool,
+    is_writable: bool,
+}
+
+impl TestAccount {
+    fn new(key: Pubkey, owner: Pubkey, data: Vec<u8>) -> Self {
+        Self {
+            key,
+            owner,
+            lamports: 1_000_000_000,
+            data,
+            is_signer: false,
+            is_writable: true,
+        }
+    }
+
+    fn signer(key: Pubkey) -> Self {
+        Self {
+            is_signer: true,
+            ..Self::new(key, system_program::ID, vec![])
+        }
+    }
+}
+
+struct Case {
+    program_id: Pubkey,
+    accounts: Vec<TestAccount>,
+    data: Vec<u8>,
+}
+
+impl Case {
+    fn run(mut self) -> ProgramResult {
+        let infos: Vec<AccountInfo> = self
+            .accounts
+            .iter_mut()
+            .map(|a| {
+                AccountInfo::new(
+                    &a.key,
+                    a.is_signer,
+                    a.is_writable,
+                    &mut a.lamports,
+                    &mut a.data,
+                    &a.owner,
+                    false,
+                    0,
+                )
+            })
+            .collect();
+        process_instruction(&self.program_id, &infos, &self.data)
+    }
+}
+
+const TITLE: &str = "The Matrix";
+
+/// The config PDA, not yet created, so every rule takes its default.
+fn config_account(program_id: &Pubkey) -> TestAccount {
+    let (config, _) = Pubkey::find_program_address(&[ProgramConfig::SEED.as_bytes()], program_id);
+    TestAccount::new(config, system_program::ID, vec![])
+}
+
+fn audit_log_account(program_id: &Pubkey) -> TestAccount {
+    let (audit_log, _) = Pubkey::find_program_address(&[AuditLog::SEED.as_bytes()], program_id);
+    TestAccount::new(audit_log, system_program::ID, vec![])
+}
+
+fn global_stats_account(program_id: &Pubkey) -> TestAccount {
+    let (stats, _) = Pubkey::find_program_address(&[GlobalStats::SEED.as_bytes()], program_id);
+    TestAccount::new(stats, system_program::ID, vec![])
+}
+
+fn hot_feed_account(program_id: &Pubkey) -> TestAccount {
+    let (feed, _) = Pubkey::find_program_address(&[HotFeed::SEED.as_bytes()], program_id);
+    TestAccount::new(feed, system_program::ID, vec![])
+}
+
+fn reviewer_profile_account(program_id: &Pubkey, reviewer: &Pubkey) -> TestAccount {
+    let (profile, _) = Pubkey::find_program_address(
+        &[ReviewerProfile::SEED.as_bytes(), reviewer.as_ref()],
+        program_id,
+    );
+    TestAccount::new(profile, system_program::ID, vec![])
+}
+
+fn set_content_limits(c: &mut Case, config_index: usize, limits: ContentLimits) {
+    c.accounts[config_index].owner = c.program_id;
+    c.accounts[config_index].data = borsh::to_vec(&ProgramConfig {
+        is_initialized: true,
+        authority: Pubkey::new_unique(),
+        limits,
+        ..ProgramConfig::default()
+    })
+    .unwrap();
+}
+
+fn set_rating_scale(c: &mut Case, config_index: usize, min: u16, max: u16) {
+    c.accounts[config_index].owner = c.program_id;
+    c.accounts[config_index].data = borsh::to_vec(&ProgramConfig {
+        is_initialized: true,
+        authority: Pubkey::new_unique(),
+        rating_scale: RatingScale {
+            min,
+            max,
+            decimals: 0,
+        },
+        ..ProgramConfig::default()
+    })
+    .unwrap();
+}
+
+fn require_reviewer_pass(c: &mut Case, config_index: usize) {
+    c.accounts[config_index].owner = c.program_id;
+    c.accounts[config_index].data = borsh::to_vec(&ProgramConfig {
+        is_initialized: true,
+        authority: Pubkey::new_unique(),
+        require_reviewer_pass: true,
+        ..ProgramConfig::default()
+    })
+    .unwrap();
+}
+
+fn require_wallet_age(c: &mut Case, config_index: usize) {
+    c.accounts[config_index].owner = c.program_id;
+    c.accounts[config_index].data = borsh::to_vec(&ProgramConfig {
+        is_initialized: true,
+        authority: Pubkey::new_unique(),
+        min_wallet_age: 1_000,
+        age_attester: Pubkey::new_unique(),
+        ..ProgramConfig::default()
+    })
+    .unwrap();
+}
+
+/// `ReviewEdits` of `review` that has been edited `edits` times.
+fn review_edits_account(program_id: &Pubkey, review: &Pubkey, edits: u64) -> TestAccount {
+    let (pda_edits, _) =
+        Pubkey::find_program_address(&[ReviewEdits::SEED.as_bytes(), review.as_ref()], program_id);
+    let data = borsh::to_vec(&ReviewEdits {
+        discriminator: ReviewEdits::DISCRIMINATOR,
+        is_initialized: true,
+        review: *review,
+        edits,
+    })
+    .unwrap();
+    TestAccount::new(pda_edits, *program_id, data)
+}
+
+/// Makes the update its review's second edit under a config charging a burn.
+fn require_edit_burn(c: &mut Case) {
+    c.accounts[3].owner = c.program_id;
+    c.accounts[3].data = borsh::to_vec(&ProgramConfig {
+        is_initialized: true,
+        authority: Pubkey::new_unique(),
+        edit_burn_mint: Pubkey::new_unique(),
+        edit_burn_amount: 10,
+        ..ProgramConfig::default()
+    })
+    .unwrap();
+    c.accounts[4] = review_edits_account(&c.program_id, &c.accounts[1].key, 1);
+}
+
+fn oversize_text() -> String {
+    "x".repeat(MovieAccountState::MAX_ACCOUNT_SIZE)
+}
+
+fn review_data(reviewer: &Pubkey) -> Vec<u8> {
+    formatted_review_data(reviewer, "Still holds up", BodyFormat::Plain)
+}
+
+fn formatted_review_data(reviewer: &Pubkey, description: &str, body_format: BodyFormat) -> Vec<u8> {
+    let mut data = borsh::to_vec(&MovieAccountState {
+        discriminator: MovieAccountState::DISCRIMINATOR,
+        is_initialized: true,
+        reviewer: *reviewer,
+        rating: 4,
+        title: TITLE.to_string(),
+        description: description.to_string(),
+        body_format,
+        comment_settings: None,
+    })
+    .unwrap();
+    data.resize(MovieAccountState::MAX_ACCOUNT_SIZE, 0);
+    data
+}
+
+fn valid_add_movie_review() -> Case {
+    let program_id = Pubkey::new_unique();
+    let reviewer = Pubkey::new_unique();
+    let (review, _) =
+        Pubkey::find_program_address(&[reviewer.as_ref(), TITLE.as_bytes()], &program_id);
+    let (counter, _) =
+        Pubkey::find_program_address(&[review.as_ref(), "comment".as_ref()], &program_id);
+    Case {
+        program_id,
+        accounts: vec![
+            TestAccount::signer(reviewer),
+            TestAccount::new(review, system_program::ID, vec![]),
+            TestAccount::new(counter, system_program::ID, vec![]),
+            TestAccount::new(system_program::ID, Pubkey::default(), vec![]),
+            config_account(&program_id),
+            global_stats_account(&program_id),
+            reviewer_profile_account(&program_id, &reviewer),
+        ],
+        data: MovieInstruction::AddMovieReview {
+            title: TITLE.to_string(),
+            rating: 4,
+            description: "Still holds up".to_string(),
+        }
+        .pack(),
+    }
+}
+
+fn valid_add_relayed_review() -> Case {
+    let program_id = Pubkey::new_unique();
+    let reviewer = Pubkey::new_unique();
+    let (review, _) =
+        Pubkey::find_program_address(&[reviewer.as_ref(), TITLE.as_bytes()], &program_id);
+    let (counter, _) =
+        Pubkey::find_program_address(&[review.as_ref(), "comment".as_ref()], &program_id);
+    Case {
+        program_id,
+        accounts: vec![
+            TestAccount::signer(Pubkey::new_unique()),
+            TestAccount::new(reviewer, system_program::ID, vec![]),
+            TestAccount::new(sysvar::instructions::ID, sysvar::ID, vec![]),
+            TestAccount::new(review, system_program::ID, vec![]),
+            TestAccount::new(counter, system_program::ID, vec![]),
+            TestAccount::new(system_program::ID, Pubkey::default(), vec![]),
+            config_account(&program_id),
+            global_stats_account(&program_id),
+            reviewer_profile_account(&program_id, &reviewer),
+            TestAccount::new(
+                Pubkey::find_program_address(
+                    &[RentPayer::SEED.as_bytes(), review.as_ref()],
+                    &program_id,
+                )
+                .0,
+                system_program::ID,
+                vec![],
+            ),
+        ],
+        data: MovieInstruction::AddRelayedReview {
+            title: TITLE.to_string(),
+            rating: 4,
+            description: "Still holds up".to_string(),
+            nonce: 0,
+            expires_slot: 1_000,
+        }
+        .pack(),
+    }
+}
+
+fn dry_run(c: &mut Case) {
+    c.data[0] |= DRY_RUN_FLAG;
+}
+
+fn valid_add_movie_review_dry_run() -> Case {
+    let mut c = valid_add_movie_review();
+    dry_run(&mut c);
+    c
+}
+
+fn valid_add_comment_dry_run() -> Case {
+    let mut c = valid_add_comment();
+    dry_run(&mut c);
+    c
+}
+
+/// The same review has already landed, so the resend is a no-op.
+fn valid_add_movie_review_idempotent() -> Case {
+    let mut c = valid_add_movie_review();
+    let reviewer = c.accounts[0].key;
+    c.accounts[1].owner = c.program_id;
+    c.accounts[1].data = review_data(&reviewer);
+    c.data = idempotent_review_data(4, "Still holds up");
+    c
+}
+
+fn idempotent_review_data(rating: u16, description: &str) -> Vec<u8> {
+    MovieInstruction::AddMovieReviewIdempotent {
+        title: TITLE.to_string(),
+        rating,
+        description: description.to_string(),
+    }
+    .pack()
+}
+
+fn valid_update_movie_review() -> Case {
+    let program_id = Pubkey::new_unique();
+    let reviewer = Pubkey::new_unique();
+    let (review, _) =
+        Pubkey::find_program_address(&[reviewer.as_ref(), TITLE.as_bytes()], &program_id);
+    Case {
+        program_id,
+        accounts: vec![
+            TestAccount::signer(reviewer),
+            TestAccount::new(review, program_id, review_data(&reviewer)),
+            reviewer_profile_account(&program_id, &reviewer),
+            config_account(&program_id),
+            review_edits_account(&program_id, &review, 0),
+            TestAccount::new(system_program::ID, Pubkey::default(), vec![]),
+        ],
+        data: MovieInstruction::UpdateMovieReview {
+            title: TITLE.to_string(),
+            rating: 5,
+            description: "Even better the second time".to_string(),
+        }
+        .pack(),
+    }
+}
+
+fn valid_update_rating() -> Case {
+    let program_id = Pubkey::new_unique();
+    let reviewer = Pubkey::new_unique();
+    let (review, _) =
+        Pubkey::find_program_address(&[reviewer.as_ref(), TITLE.as_bytes()], &program_id);
+    Case {
+        program_id,
+        accounts: vec![
+            TestAccount::signer(reviewer),
+            TestAccount::new(review, program_id, review_data(&reviewer)),
+            reviewer_profile_account(&program_id, &reviewer),
+            config_account(&program_id),
+        ],
+        data: MovieInstruction::UpdateRating { rating: 5 }.pack(),
+    }
+}
+
+fn valid_append_to_description() -> Case {
+    let program_id = Pubkey::new_unique();
+    let reviewer = Pubkey::new_unique();
+    let (review, _) =
+        Pubkey::find_program_address(&[reviewer.as_ref(), TITLE.as_bytes()], &program_id);
+    Case {
+        program_id,
+        accounts: vec![
+            TestAccount::signer(reviewer),
+            TestAccount::new(review, program_id, review_data(&reviewer)),
+            TestAccount::new(system_program::ID, Pubkey::default(), vec![]),
+        ],
+        data: MovieInstruction::AppendToDescription {
+            text: " and better with every watch".to_string(),
+        }
+        .pack(),
+    }
+}
+
+fn valid_add_comment() -> Case {
+    let program_id = Pubkey::new_unique();
+    let reviewer = Pubkey::new_unique();
+    let commenter = Pubkey::new_unique();
+    let (review, _) =
+        Pubkey::find_program_address(&[reviewer.as_ref(), TITLE.as_bytes()], &program_id);
+    let (counter, _) =
+        Pubkey::find_program_address(&[review.as_ref(), "comment".as_ref()], &program_id);
+    let (comment, _) = Pubkey::find_program_address(
+        &[review.as_ref(), comment_seed_bytes(0).as_ref()],
+        &program_id,
+    );
+    let (mute, _) = Pubkey::find_program_address(
+        &[Mute::SEED.as_bytes(), reviewer.as_ref(), commenter.as_ref()],
+        &program_id,
+    );
+    let counter_data = borsh::to_vec(&MovieCommentCounter {
+        discriminator: MovieCommentCounter::DISCRIMINATOR,
+        is_initialized: true,
+        total_comments_created: 0,
+        page: 0,
+        active_comments: 0,
+    })
+    .unwrap();
+    Case {
+        program_id,
+        accounts: vec![
+            TestAccount::signer(commenter),
+            TestAccount::new(review, program_id, review_data(&reviewer)),
+            TestAccount::new(counter, program_id, counter_data),
+            TestAccount::new(comment, system_program::ID, vec![]),
+            TestAccount::new(system_program::ID, Pubkey::default(), vec![]),
+            config_account(&program_id),
+            TestAccount::new(mute, system_program::ID, vec![]),
+            global_stats_account(&program_id),
+            hot_feed_account(&program_id),
+        ],
+        data: MovieInstruction::AddComment {
+            comment: "Agreed!".to_string(),
+            media_uri: Some(
+                "ipfs://bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi".to_string(),
+            ),
+        }
+        .pack(),
+    }
+}
+
+fn valid_reply_to_comment() -> Case {
+    let mut c = valid_add_comment();
+    let review = c.accounts[1].key;
+    // The parent is comment 0, so the reply is comment 1
+    c.accounts[2].data = borsh::to_vec(&MovieCommentCounter {
+        discriminator: MovieCommentCounter::DISCRIMINATOR,
+        is_initialized: true,
+        total_comments_created: 1,
+        page: 0,
+        active_comments: 1,
+    })
+    .unwrap();
+    c.accounts[3].key = Pubkey::find_program_address(
+        &[review.as_ref(), comment_seed_bytes(1).as_ref()],
+        &c.program_id,
+    )
+    .0;
+    let (parent, _) = Pubkey::find_program_address(
+        &[review.as_ref(), comment_seed_bytes(0).as_ref()],
+        &c.program_id,
+    );
+    c.accounts.insert(
+        7,
+        TestAccount::new(
+            parent,
+            c.program_id,
+            parent_comment_data(&review, CommentPath::default()),
+        ),
+    );
+    c.data = MovieInstruction::ReplyToComment {
+        comment: "Agreed!".to_string(),
+        media_uri: None,
+    }
+    .pack();
+    c
+}
+
+fn valid_add_paged_comment() -> Case {
+    let mut c = valid_add_comment();
+    let review = c.accounts[1].key;
+    c.accounts[3].key = Pubkey::find_program_address(
+        &[
+            CommentPage::SEED.as_bytes(),
+            review.as_ref(),
+            &0u64.to_le_bytes(),
+        ],
+        &c.program_id,
+    )
+    .0;
+    c.data = MovieInstruction::AddPagedComment {
+        comment: "Agreed!".to_string(),
+    }
+    .pack();
+    c
+}
+
+fn valid_record_tip() -> Case {
+    let program_id = Pubkey::new_unique();
+    let tip_program = Pubkey::new_unique();
+    let reviewer = Pubkey::new_unique();
+    let (review, _) =
+        Pubkey::find_program_address(&[reviewer.as_ref(), TITLE.as_bytes()], &program_id);
+    let (tip_authority, _) =
+        Pubkey::find_program_address(&[GlobalStats::TIP_AUTHORITY_SEED.as_bytes()], &tip_program);
+    let mut config = config_account(&program_id);
+    config.owner = program_id;
+    config.data = borsh::to_vec(&ProgramConfig {
+        is_initialized: true,
+        authority: Pubkey::new_unique(),
+        tip_program,
+        ..ProgramConfig::default()
+    })
+    .unwrap();
+    let mut stats = global_stats_account(&program_id);
+    stats.owner = program_id;
+    stats.data = borsh::to_vec(&GlobalStats {
+        discriminator: GlobalStats::DISCRIMINATOR,
+        is_initialized: true,
+        ..GlobalStats::default()
+    })
+    .unwrap();
+    Case {
+        program_id,
+        accounts: vec![
+            TestAccount::signer(tip_authority),
+            config,
+            stats,
+            TestAccount::signer(Pubkey::new_unique()),
+            TestAccount::new(system_program::ID, Pubkey::default(), vec![]),
+            TestAccount::new(review, program_id, review_data(&reviewer)),
+            hot_feed_account(&program_id),
+        ],
+        data: MovieInstruction::RecordTip { amount: 1_000 }.pack(),
+    }
+}
+
+fn valid_record_sol_tip() -> Case {
+    let mut c = valid_record_tip();
+    c.data = MovieInstruction::RecordSolTip { lamports: 1_000 }.pack();
+    c
+}
+
+fn valid_set_content_limits() -> Case {
+    let program_id = Pubkey::new_unique();
+    let authority = Pubkey::new_unique();
+    let mut config = config_account(&program_id);
+    config.owner = program_id;
+    config.data = borsh::to_vec(&ProgramConfig {
+        is_initialized: true,
+        authority,
+        ..ProgramConfig::default()
+    })
+    .unwrap();
+    Case {
+        program_id,
+        accounts: vec![
+            TestAccount::signer(authority),
+            config,
+            TestAccount::new(system_program::ID, Pubkey::default(), vec![]),
+            audit_log_account(&program_id),
+        ],
+        data: MovieInstruction::SetContentLimits {
+            limits: ContentLimits {
+                max_review_size: 2_000,
+                max_comment_size: 500,
+            },
+        }
+        .pack(),
+    }
+}
+
+fn valid_set_rating_scale() -> Case {
+    let mut case = valid_set_content_limits();
+    case.data = set_scale_data(0, 100, 1);
+    case
+}
+
+fn valid_set_cpi_callers() -> Case {
+    let mut case = valid_set_content_limits();
+    case.data = set_cpi_callers_data(ProgramConfig::MAX_CPI_CALLERS);
+    case
+}
+
+fn valid_set_features() -> Case {
+    let mut case = valid_set_content_limits();
+    case.data = MovieInstruction::SetFeatures {
+        features: ProgramConfig::ALL_FEATURES,
+    }
+    .pack();
+    case
+}
+
+fn valid_set_age_gate() -> Case {
+    let mut case = valid_set_content_limits();
+    case.data = MovieInstruction::SetAgeGate {
+        min_wallet_age: 1_000,
+        age_attester: Pubkey::new_unique(),
+    }
+    .pack();
+    case
+}
+
+fn valid_set_hide_threshold() -> Case {
+    let mut case = valid_set_content_limits();
+    case.data = MovieInstruction::SetHideThreshold { threshold: -5 }.pack();
+    case
+}
+
+fn valid_set_edit_burn() -> Case {
+    let mut case = valid_set_content_limits();
+    case.data = MovieInstruction::SetEditBurn {
+        mint: Pubkey::new_unique(),
+        amount: 10,
+    }
+    .pack();
+    case
+}
+
+fn set_cpi_callers_data(count: usize) -> Vec<u8> {
+    MovieInstruction::SetCpiCallers {
+        callers: (0..count).map(|_| Pubkey::new_unique()).collect(),
+    }
+    .pack()
+}
+
+fn set_scale_data(min: u16, max: u16, decimals: u8) -> Vec<u8> {
+    MovieInstruction::SetRatingScale {
+        scale: RatingScale { min, max, decimals },
+    }
+    .pack()
+}
+
+fn set_limits_data(max_review_size: u32, max_comment_size: u32) -> Vec<u8> {
+    MovieInstruction::SetContentLimits {
+        limits: ContentLimits {
+            max_review_size,
+            max_comment_size,
+        },
+    }
+    .pack()
+}
+
+fn valid_write_snapshot() -> Case {
+    let program_id = Pubkey::new_unique();
+    let authority = Pubkey::new_unique();
+    let mut config = config_account(&program_id);
+    config.owner = program_id;
+    config.data = borsh::to_vec(&ProgramConfig {
+        is_initialized: true,
+        authority,
+        ..ProgramConfig::default()
+    })
+    .unwrap();
+    let (snapshot, _) = Pubkey::find_program_address(
+        &[ReviewSnapshot::SEED.as_bytes(), &7u64.to_le_bytes()],
+        &program_id,
+    );
+    let mut accounts = vec![
+        TestAccount::signer(authority),
+        config,
+        TestAccount::new(snapshot, system_program::ID, vec![]),
+        TestAccount::new(system_program::ID, Pubkey::default(), vec![]),
+    ];
+    let mut reviews: Vec<Pubkey> = (0..2).map(|_| Pubkey::new_unique()).collect();
+    reviews.sort();
+    for review in reviews {
+        let (counter, _) =
+            Pubkey::find_program_address(&[review.as_ref(), "comment".as_ref()], &program_id);
+        accounts.push(TestAccount::new(
+            review,
+            program_id,
+            review_data(&Pubkey::new_unique()),
+        ));
+        accounts.push(TestAccount::new(
+            counter,
+            program_id,
+            borsh::to_vec(&MovieCommentCounter {
+                discriminator: MovieCommentCounter::DISCRIMINATOR,
+                is_initialized: true,
+                total_comments_created: 3,
+                page: 0,
+                active_comments: 3,
+            })
+            .unwrap(),
+        ));
+    }
+    Case {
+        program_id,
+        accounts,
+        data: MovieInstruction::WriteSnapshot { slot: 7 }.pack(),
+    }
+}
+
+fn parent_comment_data(review: &Pubkey, path: CommentPath) -> Vec<u8> {
+    let mut data = borsh::to_vec(&MovieComment {
+        discriminator: MovieComment::DISCRIMINATOR,
+        is_initialized: true,
+        reviewer: *review,
+        commenter: Pubkey::new_unique(),
+        comment: "First!".to_string(),
+        count: 0,
+        media_uri: None,
+        path,
+    })
+    .unwrap();
+    data.resize(MovieComment::MAX_ACCOUNT_SIZE, 0);
+    data
+}
+
+fn set_media_uri(c: &mut Case, media_uri: &str) {
+    c.data = MovieInstruction::AddComment {
+        comment: "Agreed!".to_string(),
+        media_uri: Some(media_uri.to_string()),
+    }
+    .pack();
+}
+
+fn valid_add_review_for_movie() -> Case {
+    let program_id = Pubkey::new_unique();
+    let reviewer = Pubkey::new_unique();
+    let (movie, _) = Pubkey::find_program_address(
+        &[Movie::SEED.as_bytes(), Movie::title_hash(TITLE).as_ref()],
+        &program_id,
+    );
+    let (review, _) =
+        Pubkey::find_program_address(&[reviewer.as_ref(), movie.as_ref()], &program_id);
+    let (legacy_review, _) =
+        Pubkey::find_program_address(&[reviewer.as_ref(), TITLE.as_bytes()], &program_id);
+    let (counter, _) =
+        Pubkey::find_program_address(&[review.as_ref(), "comment".as_ref()], &program_id);
+    let (movie_stats, _) =
+        Pubkey::find_program_address(&[MovieStats::SEED.as_bytes(), movie.as_ref()], &program_id);
+    Case {
+        program_id,
+        accounts: vec![
+            TestAccount::signer(reviewer),
+            TestAccount::new(movie, program_id, registered_movie_data()),
+            TestAccount::new(review, system_program::ID, vec![]),
+            TestAccount::new(legacy_review, system_program::ID, vec![]),
+            TestAccount::new(counter, system_program::ID, vec![]),
+            TestAccount::new(system_program::ID, Pubkey::default(), vec![]),
+            config_account(&program_id),
+            global_stats_account(&program_id),
+            reviewer_profile_account(&program_id, &reviewer),
+            TestAccount::new(movie_stats, system_program::ID, vec![]),
+            TestAccount::new(
+                rating_index_address(&program_id, &movie),
+                system_program::ID,
+                vec![],
+            ),
+        ],
+        data: MovieInstruction::AddReviewForMovie {
+            rating: 4,
+            description: "Still holds up".to_string(),
+        }
+        .pack(),
+    }
+}
+
+fn registered_movie_data() -> Vec<u8> {
+    borsh::to_vec(&Movie {
+        discriminator: Movie::DISCRIMINATOR,
+        is_initialized: true,
+        title: TITLE.to_string(),
+        verified_release: false,
+        release_date: 0,
+        external_id: String::new(),
+    })
+    .unwrap()
+}
+
+fn rating_index_address(program_id: &Pubkey, movie: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[RatingIndex::SEED.as_bytes(), movie.as_ref()], program_id).0
+}
+
+/// Refiles a review the movie's rating index already holds, so the index is
+/// not resized.
+fn valid_index_movie_review() -> Case {
+    let program_id = Pubkey::new_unique();
+    let reviewer = Pubkey::new_unique();
+    let (movie, _) = Pubkey::find_program_address(
+        &[Movie::SEED.as_bytes(), Movie::title_hash(TITLE).as_ref()],
+        &program_id,
+    );
+    let (review, _) =
+        Pubkey::find_program_address(&[reviewer.as_ref(), movie.as_ref()], &program_id);
+    let index_data = borsh::to_vec(&RatingIndex {
+        discriminator: RatingIndex::DISCRIMINATOR,
+        is_initialized: true,
+        movie,
+        counts: [0, 0, 1, 0, 0],
+        reviews: vec![review],
+    })
+    .unwrap();
+    Case {
+        program_id,
+        accounts: vec![
+            TestAccount::signer(Pubkey::new_unique()),
+            TestAccount::new(movie, program_id, registered_movie_data()),
+            TestAccount::new(review, program_id, review_data(&reviewer)),
+            config_account(&program_id),
+            TestAccount::new(
+                rating_index_address(&program_id, &movie),
+                program_id,
+                index_data,
+            ),
+            TestAccount::new(system_program::ID, Pubkey::default(), vec![]),
+        ],
+        data: MovieInstruction::IndexMovieReview.pack(),
+    }
+}
+
+fn valid_recompute_stats() -> Case {
+    let program_id = Pubkey::new_unique();
+    let (movie, _) = Pubkey::find_program_address(
+        &[Movie::SEED.as_bytes(), Movie::title_hash(TITLE).as_ref()],
+        &program_id,
+    );
+    let (movie_stats, _) =
+        Pubkey::find_program_address(&[MovieStats::SEED.as_bytes(), movie.as_ref()], &program_id);
+    let stats_data = borsh::to_vec(&MovieStats {
+        discriminator: MovieStats::DISCRIMINATOR,
+        is_initialized: true,
+        movie,
+        review_count: 1,
+        rating_sum: 4,
+        ..MovieStats::default()
+    })
+    .unwrap();
+    let mut accounts = vec![
+        TestAccount::signer(Pubkey::new_unique()),
+        TestAccount::new(movie, program_id, registered_movie_data()),
+        TestAccount::new(movie_stats, program_id, stats_data),
+        TestAccount::new(system_program::ID, Pubkey::default(), vec![]),
+    ];
+    let mut reviews: Vec<(Pubkey, Pubkey)> = (0..2)
+        .map(|_| {
+            let reviewer = Pubkey::new_unique();
+            let (review, _) =
+                Pubkey::find_program_address(&[reviewer.as_ref(), movie.as_ref()], &program_id);
+            (review, reviewer)
+        })
+        .collect();
+    reviews.sort();
+    for (review, reviewer) in reviews {
+        accounts.push(TestAccount::new(review, program_id, review_data(&reviewer)));
+    }
+    Case {
+        program_id,
+        accounts,
+        data: MovieInstruction::RecomputeStats {
+            restart: true,
+            finish: true,
+        }
+        .pack(),
+    }
+}
+
+/// Applies `update` to the movie stats at `accounts[2]`.
+fn update_movie_stats(c: &mut Case, update: impl FnOnce(&mut MovieStats)) {
+    let mut stats: MovieStats = try_from_slice_unchecked(&c.accounts[2].data).unwrap();
+    update(&mut stats);
+    c.accounts[2].data = borsh::to_vec(&stats).unwrap();
+}
+
+const SALT: [u8; 32] = [7; 32];
+
+fn valid_reveal_authorship() -> Case {
+    let program_id = Pubkey::new_unique();
+    let reviewer = Pubkey::new_unique();
+    let commitment = MovieAccountState::authorship_commitment(&reviewer, &SALT);
+    let (review, _) =
+        Pubkey::find_program_address(&[commitment.as_ref(), TITLE.as_bytes()], &program_id);
+    let mut data = review_data(&commitment);
+    data[0] = MovieAccountState::ANONYMOUS_DISCRIMINATOR;
+    Case {
+        program_id,
+        accounts: vec![
+            TestAccount::signer(reviewer),
+            TestAccount::new(review, program_id, data),
+        ],
+        data: MovieInstruction::RevealAuthorship { salt: SALT }.pack(),
+    }
+}
+
+fn valid_mint_review_nft() -> Case {
+    let program_id = Pubkey::new_unique();
+    let reviewer = Pubkey::new_unique();
+    let collection_mint = Pubkey::new_unique();
+    let (review, _) =
+        Pubkey::find_program_address(&[reviewer.as_ref(), TITLE.as_bytes()], &program_id);
+    let (collection_authority, _) = Pubkey::find_program_address(
+        &[ProgramConfig::COLLECTION_AUTHORITY_SEED.as_bytes()],
+        &program_id,
+    );
+    let (nft_mint, _) = Pubkey::find_program_address(
+        &[ProgramConfig::REVIEW_NFT_SEED.as_bytes(), review.as_ref()],
+        &program_id,
+    );
+    let mut config = config_account(&program_id);
+    config.owner = program_id;
+    config.data = borsh::to_vec(&ProgramConfig {
+        is_initialized: true,
+        authority: Pubkey::new_unique(),
+        review_collection: collection_mint,
+        collection_generation: 1,
+        ..ProgramConfig::default()
+    })
+    .unwrap();
+    let placeholder = || TestAccount::new(Pubkey::new_unique(), system_program::ID, vec![]);
+    Case {
+        program_id,
+        accounts: vec![
+            TestAccount::signer(reviewer),
+            TestAccount::new(review, program_id, review_data(&reviewer)),
+            config,
+            TestAccount::new(collection_authority, system_program::ID, vec![]),
+            TestAccount::new(nft_mint, system_program::ID, vec![]),
+            placeholder(),
+            placeholder(),
+            placeholder(),
+            TestAccount::new(collection_mint, spl_token::ID, vec![]),
+            placeholder(),
+            placeholder(),
+            TestAccount::new(spl_token::ID, Pubkey::default(), vec![]),
+            TestAccount::new(spl_associated_token_account::ID, Pubkey::default(), vec![]),
+            TestAccount::new(mpl_token_metadata::ID, Pubkey::default(), vec![]),
+            TestAccount::new(system_program::ID, Pubkey::default(), vec![]),
+        ],
+        data: MovieInstruction::MintReviewNft {
+            uri: "https://example.com/review.json".to_string(),
+        }
+        .pack(),
+    }
+}
+
+const FEED_ID: [u8; 32] = [3; 32];
+
+fn valid_mark_verified_release() -> Case {
+    let program_id = Pubkey::new_unique();
+    let oracle = Pubkey::new_unique();
+    let (movie, _) = Pubkey::find_program_address(
+        &[Movie::SEED.as_bytes(), Movie::title_hash(TITLE).as_ref()],
+        &program_id,
+    );
+    let movie_data = borsh::to_vec(&Movie {
+        discriminator: Movie::DISCRIMINATOR,
+        is_initialized: true,
+        title: TITLE.to_string(),
+        verified_release: false,
+        release_date: 0,
+        external_id: String::new(),
+    })
+    .unwrap();
+    let mut config = config_account(&program_id);
+    config.owner = program_id;
+    config.data = borsh::to_vec(&ProgramConfig {
+        is_initialized: true,
+        authority: Pubkey::new_unique(),
+        release_oracle: oracle,
+        release_feed_id: FEED_ID,
+        ..ProgramConfig::default()
+    })
+    .unwrap();
+    let attestation_data = borsh::to_vec(&ReleaseAttestation {
+        feed_id: FEED_ID,
+        title_hash: Movie::title_hash(TITLE),
+        release_date: 1,
+    })
+    .unwrap();
+    Case {
+        program_id,
+        accounts: vec![
+            TestAccount::signer(Pubkey::new_unique()),
+            TestAccount::new(movie, program_id, movie_data),
+            config,
+            TestAccount::new(Pubkey::new_unique(), oracle, attestation_data),
+            TestAccount::new(system_program::ID, Pubkey::default(), vec![]),
+        ],
+        data: MovieInstruction::MarkVerifiedRelease.pack(),
+    }
+}
+
+fn vrf_config(program_id: &Pubkey, vrf_program: Pubkey) -> TestAccount {
+    let mut config = config_account(program_id);
+    config.owner = *program_id;
+    config.data = borsh::to_vec(&ProgramConfig {
+        is_initialized: true,
+        authority: Pubkey::new_unique(),
+        vrf_program,
+        ..ProgramConfig::default()
+    })
+    .unwrap();
+    config
+}
+
+fn randomness_account(vrf_program: Pubkey, randomness: [u8; 64]) -> TestAccount {
+    let mut data = vec![0; VrfRandomness::ACCOUNT_DISCRIMINATOR_LEN];
+    data.extend(
+        borsh::to_vec(&VrfRandomness {
+            seed: [5; 32],
+            randomness,
+        })
+        .unwrap(),
+    );
+    TestAccount::new(Pubkey::new_unique(), vrf_program, data)
+}
+
+fn valid_request_featured_draw() -> Case {
+    let program_id = Pubkey::new_unique();
+    let vrf_program = Pubkey::new_unique();
+    let (featured, _) =
+        Pubkey::find_program_address(&[FeaturedReview::SEED.as_bytes()], &program_id);
+    Case {
+        program_id,
+        accounts: vec![
+            TestAccount::signer(Pubkey::new_unique()),
+            TestAccount::new(featured, system_program::ID, vec![]),
+            vrf_config(&program_id, vrf_program),
+            randomness_account(vrf_program, [0; 64]),
+            TestAccount::new(system_program::ID, Pubkey::default(), vec![]),
+            TestAccount::new(
+                Pubkey::new_unique(),
+                program_id,
+                review_data(&Pubkey::new_unique()),
+            ),
+        ],
+        data: MovieInstruction::RequestFeaturedDraw.pack(),
+    }
+}
+
+fn valid_fulfill_featured_draw() -> Case {
+    let program_id = Pubkey::new_unique();
+    let vrf_program = Pubkey::new_unique();
+    let (featured, _) =
+        Pubkey::find_program_address(&[FeaturedReview::SEED.as_bytes()], &program_id);
+    let randomness = randomness_account(vrf_program, [9; 64]);
+    let candidate = Pubkey::new_unique();
+    let featured_data = borsh::to_vec(&FeaturedReview {
+        discriminator: FeaturedReview::DISCRIMINATOR,
+        is_initialized: true,
+        day: 1,
+        review: Pubkey::default(),
+        pending: true,
+        randomness: randomness.key,
+        candidates_hash: FeaturedReview::candidates_hash(&[&candidate]),
+    })
+    .unwrap();
+    Case {
+        program_id,
+        accounts: vec![
+            TestAccount::signer(Pubkey::new_unique()),
+            TestAccount::new(featured, program_id, featured_data),
+            vrf_config(&program_id, vrf_program),
+            randomness,
+            TestAccount::new(candidate, program_id, review_data(&Pubkey::new_unique())),
+        ],
+        data: MovieInstruction::FulfillFeaturedDraw.pack(),
+    }
+}
+
+const EMITTER_CHAIN: u16 = 2;
+const EMITTER: [u8; 32] = [4; 32];
+const SEQUENCE: u64 = 7;
+
+fn posted_vaa_data(emitter_address: [u8; 32], rating: u8, description: &str) -> Vec<u8> {
+    borsh::to_vec(&PostedVaa {
+        magic: PostedVaa::MAGIC,
+        vaa_version: 1,
+        consistency_level: 1,
+        vaa_time: 0,
+        vaa_signature_account: Pubkey::new_unique(),
+        submission_time: 0,
+        nonce: 0,
+        sequence: SEQUENCE,
+        emitter_chain: EMITTER_CHAIN,
+        emitter_address,
+        payload: borsh::to_vec(&ForeignReviewMessage {
+            author: [6; 32],
+            title: TITLE.to_string(),
+            rating,
+            description: description.to_string(),
+        })
+        .unwrap(),
+    })
+    .unwrap()
+}
+
+fn valid_import_foreign_review() -> Case {
+    let program_id = Pubkey::new_unique();
+    let wormhole_program = Pubkey::new_unique();
+    let mut config = config_account(&program_id);
+    config.owner = program_id;
+    config.data = borsh::to_vec(&ProgramConfig {
+        is_initialized: true,
+        authority: Pubkey::new_unique(),
+        wormhole_program,
+        foreign_emitter_chain: EMITTER_CHAIN,
+        foreign_emitter: EMITTER,
+        ..ProgramConfig::default()
+    })
+    .unwrap();
+    let (review, _) = Pubkey::find_program_address(
+        &[
+            MovieAccountState::FOREIGN_SEED.as_bytes(),
+            &EMITTER_CHAIN.to_le_bytes(),
+            &SEQUENCE.to_le_bytes(),
+        ],
+        &program_id,
+    );
+    Case {
+        program_id,
+        accounts: vec![
+            TestAccount::signer(Pubkey::new_unique()),
+            TestAccount::new(
+                Pubkey::new_unique(),
+                wormhole_program,
+                posted_vaa_data(EMITTER, 4, "Still holds up"),
+            ),
+            config,
+            TestAccount::new(review, system_program::ID, vec![]),
+            TestAccount::new(Pubkey::new_unique(), system_program::ID, vec![]),
+            TestAccount::new(system_program::ID, Pubkey::default(), vec![]),
+            global_stats_account(&program_id),
+        ],
+        data: MovieInstruction::ImportForeignReview.pack(),
+    }
+}
+
+fn valid_get_upgrade_authority_status() -> Case {
+    let program_id = Pubkey::new_unique();
+    let (program_data, _) =
+        Pubkey::find_program_address(&[program_id.as_ref()], &bpf_loader_upgradeable::ID);
+    let mut data = 3u32.to_le_bytes().to_vec();
+    data.extend(1u64.to_le_bytes());
+    data.push(1);
+    data.extend(Pubkey::new_unique().to_bytes());
+    Case {
+        program_id,
+        accounts: vec![
+            TestAccount::new(program_data, bpf_loader_upgradeable::ID, data),
+            config_account(&program_id),
+        ],
+        data: MovieInstruction::GetUpgradeAuthorityStatus.pack(),
+    }
+}
+
+fn valid_top_up_account() -> Case {
+    let program_id = Pubkey::new_unique();
+    Case {
+        program_id,
+        accounts: vec![
+            TestAccount::signer(Pubkey::new_unique()),
+            TestAccount::new(
+                Pubkey::new_unique(),
+                program_id,
+                review_data(&Pubkey::new_unique()),
+            ),
+            TestAccount::new(system_program::ID, Pubkey::default(), vec![]),
+        ],
+        data: MovieInstruction::TopUpAccount.pack(),
+    }
+}
+
+fn valid_withdraw_excess_lamports() -> Case {
+    let program_id = Pubkey::new_unique();
+    let reviewer = Pubkey::new_unique();
+    let review = Pubkey::new_unique();
+    let (rent_payer, _) =
+        Pubkey::find_program_address(&[RentPayer::SEED.as_bytes(), review.as_ref()], &program_id);
+    Case {
+        program_id,
+        accounts: vec![
+            TestAccount::signer(reviewer),
+            TestAccount::new(review, program_id, review_data(&reviewer)),
+            TestAccount::new(rent_payer, system_program::ID, vec![]),
+        ],
+        data: MovieInstruction::WithdrawExcessLamports.pack(),
+    }
+}
+
+/// Initializes the config with self-comments disabled and has the reviewer
+/// sign as the commenter.
+const EXTERNAL_ID: &str = "imdb:tt0133093";
+
+fn valid_set_movie_external_id() -> Case {
+    let program_id = Pubkey::new_unique();
+    let authority = Pubkey::new_unique();
+    let (movie, _) = Pubkey::find_program_address(
+        &[Movie::SEED.as_bytes(), Movie::title_hash(TITLE).as_ref()],
+        &program_id,
+    );
+    let movie_data = borsh::to_vec(&Movie {
+        discriminator: Movie::DISCRIMINATOR,
+        is_initialized: true,
+        title: TITLE.to_string(),
+        verified_release: false,
+        release_date: 0,
+        external_id: String::new(),
+    })
+    .unwrap();
+    let mut config = config_account(&program_id);
+    config.owner = program_id;
+    config.data = borsh::to_vec(&ProgramConfig {
+        is_initialized: true,
+        authority,
+        ..ProgramConfig::default()
+    })
+    .unwrap();
+    let (index, _) = Pubkey::find_program_address(
+        &[
+            Movie::EXTERNAL_ID_SEED.as_bytes(),
+            Movie::external_id_hash(EXTERNAL_ID).as_ref(),
+        ],
+        &program_id,
+    );
+    Case {
+        program_id,
+        accounts: vec![
+            TestAccount::signer(authority),
+            TestAccount::new(movie, program_id, movie_data),
+            config,
+            TestAccount::new(index, system_program::ID, vec![]),
+            TestAccount::new(system_program::ID, Pubkey::default(), vec![]),
+            audit_log_account(&program_id),
+        ],
+        data: MovieInstruction::SetMovieExternalId {
+            external_id: EXTERNAL_ID.to_string(),
+        }
+        .pack(),
+    }
+}
+
+/// Shares the external ID baseline, less the index.
+fn valid_set_movie_release_date() -> Case {
+    let mut c = valid_set_movie_external_id();
+    c.accounts.remove(3);
+    c.data = MovieInstruction::SetMovieReleaseDate {
+        release_date: 1_700_000_000,
+    }
+    .pack();
+    c
+}
+
+fn set_external_id(c: &mut Case, external_id: &str) {
+    c.data = MovieInstruction::SetMovieExternalId {
+        external_id: external_id.to_string(),
+    }
+    .pack();
+}
+
+fn valid_add_review_translation() -> Case {
+    let program_id = Pubkey::new_unique();
+    let reviewer = Pubkey::new_unique();
+    let (review, _) =
+        Pubkey::find_program_address(&[reviewer.as_ref(), TITLE.as_bytes()], &program_id);
+    let (translation, _) = Pubkey::find_program_address(
+        &[
+            ReviewTranslation::SEED.as_bytes(),
+            review.as_ref(),
+            "pt-br".as_bytes(),
+        ],
+        &program_id,
+    );
+    Case {
+        program_id,
+        accounts: vec![
+            TestAccount::signer(reviewer),
+            TestAccount::new(review, program_id, review_data(&reviewer)),
+            TestAccount::new(translation, system_program::ID, vec![]),
+            TestAccount::new(system_program::ID, Pubkey::default(), vec![]),
+        ],
+        data: MovieInstruction::AddReviewTranslation {
+            language: "pt-br".to_string(),
+            description: "Ainda se sustenta".to_string(),
+        }
+        .pack(),
+    }
+}
+
+fn set_language(c: &mut Case, language: &str) {
+    c.data = MovieInstruction::AddReviewTranslation {
+        language: language.to_string(),
+        description: String::new(),
+    }
+    .pack();
+}
+
+/// The review, `pt-br` translation and bounty addresses of a review by
+/// `reviewer`.
+fn translation_bounty_addresses(program_id: &Pubkey, reviewer: &Pubkey) -> [Pubkey; 3] {
+    let (review, _) =
+        Pubkey::find_program_address(&[reviewer.as_ref(), TITLE.as_bytes()], program_id);
+    let (translation, _) = Pubkey::find_program_address(
+        &[
+            ReviewTranslation::SEED.as_bytes(),
+            review.as_ref(),
+            "pt-br".as_bytes(),
+        ],
+        program_id,
+    );
+    let (bounty, _) = Pubkey::find_program_address(
+        &[
+            TranslationBounty::SEED.as_bytes(),
+            review.as_ref(),
+            "pt-br".as_bytes(),
+        ],
+        program_id,
+    );
+    [review, translation, bounty]
+}
+
+fn translation_bounty_data(review: &Pubkey, reviewer: &Pubkey) -> Vec<u8> {
+    let mut data = borsh::to_vec(&TranslationBounty {
+        discriminator: TranslationBounty::DISCRIMINATOR,
+        is_initialized: true,
+        review: *review,
+        reviewer: *reviewer,
+        amount: 1_000_000,
+        language: "pt-br".to_string(),
+        cancellable_at: 0,
+    })
+    .unwrap();
+    data.resize(TranslationBounty::get_account_size(), 0);
+    data
+}
+
+/// `translator`'s submission for `bounty` and its address.
+fn translation_submission_account(
+    program_id: &Pubkey,
+    bounty: &Pubkey,
+    translator: &Pubkey,
+) -> TestAccount {
+    let (submission, _) = Pubkey::find_program_address(
+        &[
+            TranslationSubmission::SEED.as_bytes(),
+            bounty.as_ref(),
+            translator.as_ref(),
+        ],
+        program_id,
+    );
+    TestAccount::new(
+        submission,
+        *program_id,
+        borsh::to_vec(&TranslationSubmission {
+            discriminator: TranslationSubmission::DISCRIMINATOR,
+            is_initialized: true,
+            bounty: *bounty,
+            translator: *translator,
+            description: "Ainda se sustenta".to_string(),
+        })
+        .unwrap(),
+    )
+}
+
+fn valid_fund_translation_bounty() -> Case {
+    let program_id = Pubkey::new_unique();
+    let reviewer = Pubkey::new_unique();
+    let [review, translation, bounty] = translation_bounty_addresses(&program_id, &reviewer);
+    Case {
+        program_id,
+        accounts: vec![
+            TestAccount::signer(reviewer),
+            TestAccount::new(review, program_id, review_data(&reviewer)),
+            TestAccount::new(translation, system_program::ID, vec![]),
+            TestAccount::new(bounty, system_program::ID, vec![]),
+            TestAccount::new(system_program::ID, Pubkey::default(), vec![]),
+        ],
+        data: MovieInstruction::FundTranslationBounty {
+            language: "pt-br".to_string(),
+            amount: 1_000_000,
+        }
+        .pack(),
+    }
+}
+
+fn valid_submit_translation() -> Case {
+    let program_id = Pubkey::new_unique();
+    let reviewer = Pubkey::new_unique();
+    let translator = Pubkey::new_unique();
+    let [review, _, bounty] = translation_bounty_addresses(&program_id, &reviewer);
+    let mut submission = translation_submission_account(&program_id, &bounty, &translator);
+    submission.owner = system_program::ID;
+    submission.data.clear();
+    Case {
+        program_id,
+        accounts: vec![
+            TestAccount::signer(translator),
+            TestAccount::new(review, program_id, review_data(&reviewer)),
+            TestAccount::new(
+                bounty,
+                program_id,
+                translation_bounty_data(&review, &reviewer),
+            ),
+            submission,
+            TestAccount::new(system_program::ID, Pubkey::default(), vec![]),
+        ],
+        data: MovieInstruction::SubmitTranslation {
+            language: "pt-br".to_string(),
+            description: "Ainda se sustenta".to_string(),
+        }
+        .pack(),
+    }
+}
+
+fn valid_approve_translation() -> Case {
+    let program_id = Pubkey::new_unique();
+    let reviewer = Pubkey::new_unique();
+    let translator = Pubkey::new_unique();
+    let [review, translation, bounty] = translation_bounty_addresses(&program_id, &reviewer);
+    Case {
+        program_id,
+        accounts: vec![
+            TestAccount::signer(reviewer),
+            TestAccount::new(review, program_id, review_data(&reviewer)),
+            TestAccount::new(translation, system_program::ID, vec![]),
+            TestAccount::new(
+                bounty,
+                program_id,
+                translation_bounty_data(&review, &reviewer),
+            ),
+            translation_submission_account(&program_id, &bounty, &translator),
+            TestAccount::new(translator, system_program::ID, vec![]),
+            TestAccount::new(system_program::ID, Pubkey::default(), vec![]),
+        ],
+        data: MovieInstruction::ApproveTranslation {
+            language: "pt-br".to_string(),
+        }
+        .pack(),
+    }
+}
+
+fn valid_cancel_translation_bounty() -> Case {
+    let program_id = Pubkey::new_unique();
+    let reviewer = Pubkey::new_unique();
+    let [review, _, bounty] = translation_bounty_addresses(&program_id, &reviewer);
+    Case {
+        program_id,
+        accounts: vec![
+            TestAccount::signer(reviewer),
+            // Deleted: the bounty's reviewer may still cancel it
+            TestAccount::new(review, system_program::ID, vec![]),
+            TestAccount::new(
+                bounty,
+                program_id,
+                translation_bounty_data(&review, &reviewer),
+            ),
+        ],
+        data: MovieInstruction::CancelTranslationBounty {
+            language: "pt-br".to_string(),
+        }
+        .pack(),
+    }
+}
+
+fn valid_withdraw_translation_submission() -> Case {
+    let program_id = Pubkey::new_unique();
+    let reviewer = Pubkey::new_unique();
+    let translator = Pubkey::new_unique();
+    let [review, _, bounty] = translation_bounty_addresses(&program_id, &reviewer);
+    Case {
+        program_id,
+        accounts: vec![
+            TestAccount::signer(translator),
+            TestAccount::new(review, program_id, review_data(&reviewer)),
+            TestAccount::new(bounty, system_program::ID, vec![]),
+            translation_submission_account(&program_id, &bounty, &translator),
+        ],
+        data: MovieInstruction::WithdrawTranslationSubmission {
+            language: "pt-br".to_string(),
+        }
+        .pack(),
+    }
+}
+
+fn valid_set_co_authors() -> Case {
+    let program_id = Pubkey::new_unique();
+    let reviewer = Pubkey::new_unique();
+    let (review, _) =
+        Pubkey::find_program_address(&[reviewer.as_ref(), TITLE.as_bytes()], &program_id);
+    let (co_authors, _) = Pubkey::find_program_address(
+        &[ReviewCoAuthors::SEED.as_bytes(), review.as_ref()],
+        &program_id,
+    );
+    Case {
+        program_id,
+        accounts: vec![
+            TestAccount::signer(reviewer),
+            TestAccount::new(review, program_id, review_data(&reviewer)),
+            TestAccount::new(co_authors, system_program::ID, vec![]),
+            TestAccount::new(system_program::ID, system_program::ID, vec![]),
+        ],
+        data: MovieInstruction::SetCoAuthors {
+            co_authors: vec![(Pubkey::new_unique(), 2_500), (Pubkey::new_unique(), 2_500)],
+        }
+        .pack(),
+    }
+}
+
+fn set_co_author_listing(c: &mut Case, co_authors: Vec<(Pubkey, u16)>) {
+    c.data = MovieInstruction::SetCoAuthors { co_authors }.pack();
+}
+
+fn valid_set_critic_authority() -> Case {
+    let program_id = Pubkey::new_unique();
+    let reviewer = Pubkey::new_unique();
+    let authority_program = Pubkey::new_unique();
+    let (authority, _) = Pubkey::find_program_address(&[b"critic"], &authority_program);
+    Case {
+        program_id,
+        accounts: vec![
+            TestAccount::signer(reviewer),
+            reviewer_profile_account(&program_id, &reviewer),
+            TestAccount::new(system_program::ID, system_program::ID, vec![]),
+        ],
+        data: MovieInstruction::SetCriticAuthority {
+            authority,
+            authority_program,
+        }
+        .pack(),
+    }
+}
+
+fn valid_accept_co_authorship() -> Case {
+    let program_id = Pubkey::new_unique();
+    let co_author = Pubkey::new_unique();
+    let review = Pubkey::new_unique();
+    let (co_authors, _) = Pubkey::find_program_address(
+        &[ReviewCoAuthors::SEED.as_bytes(), review.as_ref()],
+        &program_id,
+    );
+    let data = borsh::to_vec(&ReviewCoAuthors {
+        discriminator: ReviewCoAuthors::DISCRIMINATOR,
+        is_initialized: true,
+        review,
+        co_authors: vec![CoAuthor {
+            co_author,
+            share_bps: 3_000,
+            accepted: false,
+        }],
+    })
+    .unwrap();
+    Case {
+        program_id,
+        accounts: vec![
+            TestAccount::signer(co_author),
+            TestAccount::new(co_authors, program_id, data),
+        ],
+        data: MovieInstruction::AcceptCoAuthorship { share_bps: 3_000 }.pack(),
+    }
+}
+
+fn valid_set_review_body_format() -> Case {
+    let program_id = Pubkey::new_unique();
+    let reviewer = Pubkey::new_unique();
+    let (review, _) =
+        Pubkey::find_program_address(&[reviewer.as_ref(), TITLE.as_bytes()], &program_id);
+    Case {
+        program_id,
+        accounts: vec![
+            TestAccount::signer(reviewer),
+            TestAccount::new(
+                review,
+                program_id,
+                formatted_review_data(
+                    &reviewer,
+                    "**Still** holds up, see [the trailer](https://example.com/trailer)",
+                    BodyFormat::Plain,
+                ),
+            ),
+        ],
+        data: MovieInstruction::SetReviewBodyFormat {
+            body_format: BodyFormat::MarkdownLite,
+        }
+        .pack(),
+    }
+}
+
+/// Replaces the stored description of the review at `accounts[1]`.
+fn set_stored_description(c: &mut Case, description: &str, body_format: BodyFormat) {
+    c.accounts[1].data = formatted_review_data(&c.accounts[0].key, description, body_format);
+}
+
+/// Stores `settings` on the review at `accounts[1]`.
+fn set_review_comment_settings(c: &mut Case, settings: CommentSettings) {
+    let mut review: MovieAccountState = try_from_slice_unchecked(&c.accounts[1].data).unwrap();
+    review.comment_settings = Some(settings);
+    borsh::to_writer(&mut c.accounts[1].data[..], &review).unwrap();
+}
+
+fn restrict_comments(c: &mut Case, comment_policy: CommentPolicy) {
+    set_review_comment_settings(
+        c,
+        CommentSettings {
+            comments_enabled: true,
+            comment_policy,
+        },
+    );
+}
+
+fn valid_set_comment_settings() -> Case {
+    let program_id = Pubkey::new_unique();
+    let reviewer = Pubkey::new_unique();
+    let (review, _) =
+        Pubkey::find_program_address(&[reviewer.as_ref(), TITLE.as_bytes()], &program_id);
+    Case {
+        program_id,
+        accounts: vec![
+            TestAccount::signer(reviewer),
+            TestAccount::new(review, program_id, review_data(&reviewer)),
+        ],
+        data: MovieInstruction::SetCommentSettings {
+            comments_enabled: true,
+            comment_policy: CommentPolicy::FollowersOnly,
+        }
+        .pack(),
+    }
+}
+
+fn valid_follow_reviewer() -> Case {
+    let program_id = Pubkey::new_unique();
+    let follower = Pubkey::new_unique();
+    let reviewer = Pubkey::new_unique();
+    let (follow, _) = Pubkey::find_program_address(
+        &[
+            Follow::SEED.as_bytes(),
+            follower.as_ref(),
+            reviewer.as_ref(),
+        ],
+        &program_id,
+    );
+    Case {
+        program_id,
+        accounts: vec![
+            TestAccount::signer(follower),
+            TestAccount::new(follow, system_program::ID, vec![]),
+            TestAccount::new(system_program::ID, Pubkey::default(), vec![]),
+        ],
+        data: MovieInstruction::FollowReviewer { reviewer }.pack(),
+    }
+}
+
+fn valid_unfollow_reviewer() -> Case {
+    let program_id = Pubkey::new_unique();
+    let follower = Pubkey::new_unique();
+    let reviewer = Pubkey::new_unique();
+    let (follow, _) = Pubkey::find_program_address(
+        &[
+            Follow::SEED.as_bytes(),
+            follower.as_ref(),
+            reviewer.as_ref(),
+        ],
+        &program_id,
+    );
+    Case {
+        program_id,
+        accounts: vec![
+            TestAccount::signer(follower),
+            TestAccount::new(
+                follow,
+                program_id,
+                borsh::to_vec(&Follow {
+                    discriminator: Follow::DISCRIMINATOR,
+                    is_initialized: true,
+                    follower,
+                    reviewer,
+                })
+                .unwrap(),
+            ),
+        ],
+        data: MovieInstruction::UnfollowReviewer.pack(),
+    }
+}
+
+fn valid_mute_user() -> Case {
+    let program_id = Pubkey::new_unique();
+    let reviewer = Pubkey::new_unique();
+    let user = Pubkey::new_unique();
+    let (mute, _) = Pubkey::find_program_address(
+        &[Mute::SEED.as_bytes(), reviewer.as_ref(), user.as_ref()],
+        &program_id,
+    );
+    Case {
+        program_id,
+        accounts: vec![
+            TestAccount::signer(reviewer),
+            TestAccount::new(mute, system_program::ID, vec![]),
+            TestAccount::new(system_program::ID, Pubkey::default(), vec![]),
+        ],
+        data: MovieInstruction::MuteUser { user }.pack(),
+    }
+}
+
+fn valid_unmute_user() -> Case {
+    let program_id = Pubkey::new_unique();
+    let reviewer = Pubkey::new_unique();
+    let user = Pubkey::new_unique();
+    let (mute, _) = Pubkey::find_program_address(
+        &[Mute::SEED.as_bytes(), reviewer.as_ref(), user.as_ref()],
+        &program_id,
+    );
+    Case {
+        program_id,
+        accounts: vec![
+            TestAccount::signer(reviewer),
+            TestAccount::new(
+                mute,
+                program_id,
+                borsh::to_vec(&Mute {
+                    discriminator: Mute::DISCRIMINATOR,
+                    is_initialized: true,
+                    reviewer,
+                    muted: user,
+                })
+                .unwrap(),
+            ),
+        ],
+        data: MovieInstruction::UnmuteUser.pack(),
+    }
+}
+
+fn valid_create_session() -> Case {
+    let program_id = Pubkey::new_unique();
+    let authority = Pubkey::new_unique();
+    let session_key = Pubkey::new_unique();
+    let (session, _) = Pubkey::find_program_address(
+        &[
+            Session::SEED.as_bytes(),
+            authority.as_ref(),
+            session_key.as_ref(),
+        ],
+        &program_id,
+    );
+    Case {
+        program_id,
+        accounts: vec![
+            TestAccount::signer(authority),
+            TestAccount::new(session, system_program::ID, vec![]),
+            TestAccount::new(system_program::ID, Pubkey::default(), vec![]),
+            config_account(&program_id),
+        ],
+        data: MovieInstruction::CreateSession {
+            session_key,
+            expires_slot: 1_000,
+            scopes: Session::SCOPE_COMMENT,
+        }
+        .pack(),
+    }
+}
+
+fn valid_revoke_session() -> Case {
+    let program_id = Pubkey::new_unique();
+    let authority = Pubkey::new_unique();
+    let session_key = Pubkey::new_unique();
+    let (session, _) = Pubkey::find_program_address(
+        &[
+            Session::SEED.as_bytes(),
+            authority.as_ref(),
+            session_key.as_ref(),
+        ],
+        &program_id,
+    );
+    Case {
+        program_id,
+        accounts: vec![
+            TestAccount::signer(authority),
+            TestAccount::new(
+                session,
+                program_id,
+                borsh::to_vec(&Session {
+                    discriminator: Session::DISCRIMINATOR,
+                    is_initialized: true,
+                    authority,
+                    session_key,
+                    expires_slot: 1_000,
+                    scopes: Session::SCOPE_COMMENT,
+                })
+                .unwrap(),
+            ),
+        ],
+        data: MovieInstruction::RevokeSession.pack(),
+    }
+}
+
+/// A `Session::ALL_SCOPES` session of `authority`'s for `session_key`.
+fn session_account(program_id: &Pubkey, authority: &Pubkey, session_key: &Pubkey) -> TestAccount {
+    let (session, _) = Pubkey::find_program_address(
+        &[
+            Session::SEED.as_bytes(),
+            authority.as_ref(),
+            session_key.as_ref(),
+        ],
+        program_id,
+    );
+    TestAccount::new(
+        session,
+        *program_id,
+        borsh::to_vec(&Session {
+            discriminator: Session::DISCRIMINATOR,
+            is_initialized: true,
+            authority: *authority,
+            session_key: *session_key,
+            expires_slot: 1_000,
+            scopes: Session::ALL_SCOPES,
+        })
+        .unwrap(),
+    )
+}
+
+fn valid_delete_review() -> Case {
+    let program_id = Pubkey::new_unique();
+    let reviewer = Pubkey::new_unique();
+    let commenter = Pubkey::new_unique();
+    let (review, _) =
+        Pubkey::find_program_address(&[reviewer.as_ref(), TITLE.as_bytes()], &program_id);
+    let (counter, _) =
+        Pubkey::find_program_address(&[review.as_ref(), "comment".as_ref()], &program_id);
+    let (comment, _) = Pubkey::find_program_address(
+        &[review.as_ref(), comment_seed_bytes(0).as_ref()],
+        &program_id,
+    );
+    let (movie, _) = Pubkey::find_program_address(
+        &[Movie::SEED.as_bytes(), Movie::title_hash(TITLE).as_ref()],
+        &program_id,
+    );
+    let (movie_stats, _) =
+        Pubkey::find_program_address(&[MovieStats::SEED.as_bytes(), movie.as_ref()], &program_id);
+    let counter_data = borsh::to_vec(&MovieCommentCounter {
+        discriminator: MovieCommentCounter::DISCRIMINATOR,
+        is_initialized: true,
+        total_comments_created: 2,
+        page: 0,
+        active_comments: 2,
+    })
+    .unwrap();
+    let mut comment_data = borsh::to_vec(&MovieComment {
+        discriminator: MovieComment::DISCRIMINATOR,
+        is_initialized: true,
+        reviewer: review,
+        commenter,
+        comment: "First!".to_string(),
+        count: 0,
+        media_uri: None,
+        path: CommentPath::default(),
+    })
+    .unwrap();
+    comment_data.resize(MovieComment::MAX_ACCOUNT_SIZE, 0);
+    Case {
+        program_id,
+        accounts: vec![
+            TestAccount::signer(reviewer),
+            TestAccount::new(review, program_id, review_data(&reviewer)),
+            TestAccount::new(counter, program_id, counter_data),
+            reviewer_profile_account(&program_id, &reviewer),
+            config_account(&program_id),
+            global_stats_account(&program_id),
+            TestAccount::new(movie_stats, system_program::ID, vec![]),
+            TestAccount::new(
+                rating_index_address(&program_id, &movie),
+                system_program::ID,
+                vec![],
+            ),
+            TestAccount::new(system_program::ID, Pubkey::default(), vec![]),
+            TestAccount::new(comment, program_id, comment_data),
+            TestAccount::new(commenter, system_program::ID, vec![]),
+        ],
+        data: MovieInstruction::DeleteReview { cascade: true }.pack(),
+    }
+}
+
+/// An initialized config whose authority is `authority`.
+fn authority_config(program_id: &Pubkey, authority: &Pubkey) -> TestAccount {
+    let mut config = config_account(program_id);
+    config.owner = *program_id;
+    config.data = borsh::to_vec(&ProgramConfig {
+        is_initialized: true,
+        authority: *authority,
+        ..ProgramConfig::default()
+    })
+    .unwrap();
+    config
+}
+
+fn pending_removal_account(
+    program_id: &Pubkey,
+    review: &Pubkey,
+    author: &Pubkey,
+    appeal: AppealStatus,
+) -> TestAccount {
+    let (removal, _) = Pubkey::find_program_address(
+        &[PendingRemoval::SEED.as_bytes(), review.as_ref()],
+        program_id,
+    );
+    let data = borsh::to_vec(&PendingRemoval {
+        discriminator: PendingRemoval::DISCRIMINATOR,
+        is_initialized: true,
+        review: *review,
+        author: *author,
+        proposed_slot: 0,
+        appeal_deadline: u64::MAX,
+        appeal,
+    })
+    .unwrap();
+    TestAccount::new(removal, *program_id, data)
+}
+
+fn set_appeal(c: &mut Case, removal_index: usize, appeal: AppealStatus) {
+    let mut removal: PendingRemoval =
+        try_from_slice_unchecked(&c.accounts[removal_index].data).unwrap();
+    removal.appeal = appeal;
+    c.accounts[removal_index].data = borsh::to_vec(&removal).unwrap();
+}
+
+fn valid_propose_removal() -> Case {
+    let program_id = Pubkey::new_unique();
+    let authority = Pubkey::new_unique();
+    let reviewer = Pubkey::new_unique();
+    let (review, _) =
+        Pubkey::find_program_address(&[reviewer.as_ref(), TITLE.as_bytes()], &program_id);
+    let mut removal = pending_removal_account(&program_id, &review, &reviewer, AppealStatus::None);
+    removal.owner = system_program::ID;
+    removal.data = Vec::new();
+    Case {
+        program_id,
+        accounts: vec![
+            TestAccount::signer(authority),
+            authority_config(&program_id, &authority),
+            TestAccount::new(review, program_id, review_data(&reviewer)),
+            removal,
+            TestAccount::new(system_program::ID, Pubkey::default(), vec![]),
+            audit_log_account(&program_id),
+        ],
+        data: MovieInstruction::ProposeRemoval.pack(),
+    }
+}
+
+fn valid_appeal_removal() -> Case {
+    let program_id = Pubkey::new_unique();
+    let reviewer = Pubkey::new_unique();
+    let (review, _) =
+        Pubkey::find_program_address(&[reviewer.as_ref(), TITLE.as_bytes()], &program_id);
+    Case {
+        program_id,
+        accounts: vec![
+            TestAccount::signer(reviewer),
+            TestAccount::new(review, program_id, review_data(&reviewer)),
+            pending_removal_account(&program_id, &review, &reviewer, AppealStatus::None),
+        ],
+        data: MovieInstruction::AppealRemoval.pack(),
+    }
+}
+
+fn valid_resolve_appeal() -> Case {
+    let mut case = valid_propose_removal();
+    let review = case.accounts[2].key;
+    let reviewer = Pubkey::new_unique();
+    case.accounts[3] =
+        pending_removal_account(&case.program_id, &review, &reviewer, AppealStatus::Filed);
+    case.data = MovieInstruction::ResolveAppeal { uphold: false }.pack();
+    case
+}
+
+/// `DeleteReview`'s accounts, after the authority, pending removal and audit
+/// log, with the reviewer no longer signing.
+fn valid_finalize_removal() -> Case {
+    let mut case = valid_delete_review();
+    let program_id = case.program_id;
+    let authority = Pubkey::new_unique();
+    let reviewer = case.accounts[0].key;
+    let review = case.accounts[1].key;
+    case.accounts[0].is_signer = false;
+    case.accounts[4] = authority_config(&program_id, &authority);
+    case.accounts.splice(
+        0..0,
+        [
+            TestAccount::signer(authority),
+            pending_removal_account(&program_id, &review, &reviewer, AppealStatus::Rejected),
+            audit_log_account(&program_id),
+        ],
+    );
+    case.data = MovieInstruction::FinalizeRemoval { cascade: true }.pack();
+    case
+}
+
+fn valid_update_body_hash() -> Case {
+    let program_id = Pubkey::new_unique();
+    let reviewer = Pubkey::new_unique();
+    let (review, _) =
+        Pubkey::find_program_address(&[reviewer.as_ref(), TITLE.as_bytes()], &program_id);
+    let (commitment, _) = Pubkey::find_program_address(
+        &[BodyCommitment::SEED.as_bytes(), review.as_ref()],
+        &program_id,
+    );
+    let data = borsh::to_vec(&BodyCommitment {
+        discriminator: BodyCommitment::DISCRIMINATOR,
+        is_initialized: true,
+        review,
+        hash: [1; 32],
+    })
+    .unwrap();
+    Case {
+        program_id,
+        accounts: vec![
+            TestAccount::signer(reviewer),
+            TestAccount::new(review, program_id, review_data(&reviewer)),
+            TestAccount::new(commitment, program_id, data),
+            TestAccount::new(system_program::ID, Pubkey::default(), vec![]),
+        ],
+        data: MovieInstruction::UpdateBodyHash {
+            hash: [2; 32],
+            uri: "ipfs://bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi".to_string(),
+        }
+        .pack(),
+    }
+}
+
+fn set_body_uri(c: &mut Case, uri: &str) {
+    c.data = MovieInstruction::UpdateBodyHash {
+        hash: [2; 32],
+        uri: uri.to_string(),
+    }
+    .pack();
+}
+
+/// The fingerprint of `review`, recorded while its description was
+/// `description`.
+fn fingerprint_account(program_id: &Pubkey, review: &Pubkey, description: &str) -> TestAccount {
+    let (address, _) = Pubkey::find_program_address(
+        &[ReviewFingerprint::SEED.as_bytes(), review.as_ref()],
+        program_id,
+    );
+    let data = borsh::to_vec(&ReviewFingerprint {
+        discriminator: ReviewFingerprint::DISCRIMINATOR,
+        is_initialized: true,
+        review: *review,
+        fingerprint: fingerprint::simhash(description),
+        slot: 0,
+    })
+    .unwrap();
+    TestAccount::new(address, *program_id, data)
+}
+
+fn valid_record_fingerprint() -> Case {
+    let program_id = Pubkey::new_unique();
+    let reviewer = Pubkey::new_unique();
+    let (review, _) =
+        Pubkey::find_program_address(&[reviewer.as_ref(), TITLE.as_bytes()], &program_id);
+    Case {
+        program_id,
+        accounts: vec![
+            TestAccount::signer(Pubkey::new_unique()),
+            TestAccount::new(review, program_id, review_data(&reviewer)),
+            fingerprint_account(&program_id, &review, ""),
+            TestAccount::new(system_program::ID, Pubkey::default(), vec![]),
+        ],
+        data: MovieInstruction::RecordFingerprint.pack(),
+    }
+}
+
+/// Two reviewers posting the same description, both fingerprinted.
+fn valid_challenge_duplicate() -> Case {
+    let program_id = Pubkey::new_unique();
+    let copier = Pubkey::new_unique();
+    let author = Pubkey::new_unique();
+    let (review, _) =
+        Pubkey::find_program_address(&[copier.as_ref(), TITLE.as_bytes()], &program_id);
+    let (original, _) =
+        Pubkey::find_program_address(&[author.as_ref(), TITLE.as_bytes()], &program_id);
+    let (challenge, _) = Pubkey::find_program_address(
+        &[
+            DuplicateChallenge::SEED.as_bytes(),
+            review.as_ref(),
+            original.as_ref(),
+        ],
+        &program_id,
+    );
+    let description = "Still holds up";
+    Case {
+        program_id,
+        accounts: vec![
+            TestAccount::signer(Pubkey::new_unique()),
+            TestAccount::new(review, program_id, review_data(&copier)),
+            fingerprint_account(&program_id, &review, description),
+            TestAccount::new(original, program_id, review_data(&author)),
+            fingerprint_account(&program_id, &original, description),
+            TestAccount::new(challenge, system_program::ID, vec![]),
+            TestAccount::new(system_program::ID, Pubkey::default(), vec![]),
+        ],
+        data: MovieInstruction::ChallengeDuplicate.pack(),
+    }
+}
+
+fn valid_vote_on_comment() -> Case {
+    let program_id = Pubkey::new_unique();
+    let voter = Pubkey::new_unique();
+    let (review, _) = Pubkey::find_program_address(
+        &[Pubkey::new_unique().as_ref(), TITLE.as_bytes()],
+        &program_id,
+    );
+    let (comment, _) = Pubkey::find_program_address(
+        &[review.as_ref(), comment_seed_bytes(0).as_ref()],
+        &program_id,
+    );
+    let (vote, _) = Pubkey::find_program_address(
+        &[
+            CommentVote::SEED.as_bytes(),
+            comment.as_ref(),
+            voter.as_ref(),
+        ],
+        &program_id,
+    );
+    let (karma, _) = Pubkey::find_program_address(
+        &[CommentKarma::SEED.as_bytes(), comment.as_ref()],
+        &program_id,
+    );
+    Case {
+        program_id,
+        accounts: vec![
+            TestAccount::signer(voter),
+            TestAccount::new(
+                comment,
+                program_id,
+                parent_comment_data(&review, CommentPath::default()),
+            ),
+            TestAccount::new(vote, system_program::ID, vec![]),
+            TestAccount::new(karma, system_program::ID, vec![]),
+            config_account(&program_id),
+            TestAccount::new(system_program::ID, Pubkey::default(), vec![]),
+            hot_feed_account(&program_id),
+        ],
+        data: MovieInstruction::VoteOnComment {
+            kind: CommentVoteKind::Like,
+        }
+        .pack(),
+    }
+}
+
+/// Records the voter's earlier `kind` vote on the comment.
+fn set_comment_vote(c: &mut Case, kind: CommentVoteKind) {
+    c.accounts[2].owner = c.program_id;
+    c.accounts[2].data = borsh::to_vec(&CommentVote {
+        discriminator: CommentVote::DISCRIMINATOR,
+        is_initialized: true,
+        comment: c.accounts[1].key,
+        voter: c.accounts[0].key,
+        kind,
+    })
+    .unwrap();
+}
+
+fn valid_repair_comment_address() -> Case {
+    let program_id = Pubkey::new_unique();
+    let reviewer = Pubkey::new_unique();
+    let (review, _) =
+        Pubkey::find_program_address(&[reviewer.as_ref(), TITLE.as_bytes()], &program_id);
+    let (legacy_comment, _) =
+        Pubkey::find_program_address(&[review.as_ref(), &1u64.to_le_bytes()], &program_id);
+    let (comment, _) =
+        Pubkey::find_program_address(&[review.as_ref(), &comment_seed_bytes(1)], &program_id);
+    let mut c = Case {
+        program_id,
+        accounts: vec![
+            TestAccount::signer(Pubkey::new_unique()),
+            TestAccount::new(review, program_id, review_data(&reviewer)),
+            TestAccount::new(legacy_comment, program_id, vec![]),
+            TestAccount::new(comment, system_program::ID, vec![]),
+            TestAccount::new(system_program::ID, Pubkey::default(), vec![]),
+        ],
+        data: MovieInstruction::RepairCommentAddress.pack(),
+    };
+    set_comment_count(&mut c, 1);
+    c
+}
+
+fn set_comment_count(c: &mut Case, count: u64) {
+    let mut comment: MovieComment = try_from_slice_unchecked(&parent_comment_data(
+        &c.accounts[1].key,
+        CommentPath::default(),
+    ))
+    .unwrap();
+    comment.count = count;
+    let mut data = borsh::to_vec(&comment).unwrap();
+    data.resize(MovieComment::MAX_ACCOUNT_SIZE, 0);
+    c.accounts[2].data = data;
+}
+
+fn comment_page_data(review: Pubkey) -> Vec<u8> {
+    borsh::to_vec(&CommentPage {
+        discriminator: CommentPage::DISCRIMINATOR,
+        is_initialized: true,
+        review,
+        page: 0,
+        comments: vec![],
+    })
+    .unwrap()
+}
+
+fn comment_as_reviewer_with_self_comments_disabled(c: &mut Case) {
+    c.accounts[5].owner = c.program_id;
+    c.accounts[5].data = borsh::to_vec(&ProgramConfig {
+        is_initialized: true,
+        authority: Pubkey::new_unique(),
+        allow_self_comments: false,
+        ..ProgramConfig::default()
+    })
+    .unwrap();
+    let review: MovieAccountState = try_from_slice_unchecked(&c.accounts[1].data).unwrap();
+    c.accounts[0].key = review.reviewer;
+}
+
+/// `error_matrix! { module => baseline { case: |c| mutation => error, ... } }`
+/// generates `module::case` tests that build `baseline()`, apply the mutation
+/// and assert the instruction fails with `error`.
+macro_rules! error_matrix {
+    ($($module:ident => $baseline:ident {
+        $($case:ident: |$c:ident| $mutation:expr => $expected:expr,)*
+    })*) => {
+        $(
+            mod $module {
+                use super::*;
+
+                $(
+                    #[test]
+                    fn $case() {
+                        let mut $c = $baseline();
+                        $mutation;
+                        let expected: ProgramError = $expected.into();
+                        assert_eq!($c.run(), Err(expected));
+                    }
+                )*
+            }
+        )*
+    };
+}
+
+error_matrix! {
+    add_movie_review => valid_add_movie_review {
+        non_signer: |c| c.accounts[0].is_signer = false
+            => ProgramError::MissingRequiredSignature,
+        over_configured_size: |c| set_content_limits(&mut c, 4, ContentLimits {
+            max_review_size: MovieAccountState::get_account_size(TITLE, "") as u32,
+            max_comment_size: 0,
+        }) => ReviewError::InvalidDataLength,
+        wrong_pda: |c| c.accounts[1].key = Pubkey::new_unique()
+            => ReviewError::InvalidPDA,
+        below_configured_scale: |c| set_rating_scale(&mut c, 4, 5, 10)
+            => ReviewError::InvalidRating,
+        rating_too_low: |c| c.data = MovieInstruction::AddMovieReview {
+            title: TITLE.to_string(),
+            rating: 0,
+            description: String::new(),
+        }.pack() => ReviewError::InvalidRating,
+        rating_too_high: |c| c.data = MovieInstruction::AddMovieReview {
+            title: TITLE.to_string(),
+            rating: 6,
+            description: String::new(),
+        }.pack() => ReviewError::InvalidRating,
+        oversize_payload: |c| c.data = MovieInstruction::AddMovieReview {
+            title: TITLE.to_string(),
+            rating: 4,
+            description: oversize_text(),
+        }.pack() => ReviewError::InvalidDataLength,
+        wrong_config_pda: |c| c.accounts[4].key = Pubkey::new_unique()
+            => ReviewError::InvalidPDA,
+        missing_pass: |c| require_reviewer_pass(&mut c, 4)
+            => ReviewError::MissingReviewerPass,
+        pass_not_token_2022: |c| {
+            require_reviewer_pass(&mut c, 4);
+            c.accounts.push(TestAccount::new(Pubkey::new_unique(), Pubkey::new_unique(), vec![]));
+        } => ProgramError::IllegalOwner,
+        missing_age_proof: |c| require_wallet_age(&mut c, 4)
+            => ReviewError::WalletTooNew,
+        unattested_age_proof: |c| {
+            require_wallet_age(&mut c, 4);
+            c.accounts.push(TestAccount::new(Pubkey::new_unique(), Pubkey::new_unique(), vec![]));
+        } => ReviewError::WalletTooNew,
+        not_a_title_filter: |c| {
+            let (filter, _) = Pubkey::find_program_address(
+                &[TitleFilter::SEED.as_bytes(), c.accounts[0].key.as_ref()],
+                &c.program_id,
+            );
+            let mut data = vec![0; TitleFilter::get_account_size()];
+            data[0] = MovieStats::DISCRIMINATOR;
+            c.accounts.push(TestAccount::new(filter, c.program_id, data));
+        } => ReviewError::InvalidDiscriminator,
+    }
+
+    add_relayed_review => valid_add_relayed_review {
+        non_signer: |c| c.accounts[0].is_signer = false
+            => ProgramError::MissingRequiredSignature,
+        wrong_pda: |c| c.accounts[3].key = Pubkey::new_unique()
+            => ReviewError::InvalidPDA,
+        signed_by_relayer: |c| c.accounts[1].key = c.accounts[0].key
+            => ReviewError::InvalidPDA,
+        wrong_sysvar: |c| c.accounts[2].key = Pubkey::new_unique()
+            => ProgramError::UnsupportedSysvar,
+    }
+
+    add_movie_review_dry_run => valid_add_movie_review_dry_run {
+        non_signer: |c| c.accounts[0].is_signer = false
+            => ProgramError::MissingRequiredSignature,
+        already_reviewed: |c| c.accounts[1].owner = c.program_id
+            => ReviewError::DuplicateReview,
+        over_configured_size: |c| set_content_limits(&mut c, 4, ContentLimits {
+            max_review_size: MovieAccountState::get_account_size(TITLE, "") as u32,
+            max_comment_size: 0,
+        }) => ReviewError::InvalidDataLength,
+        wrong_counter: |c| c.accounts[2].key = Pubkey::new_unique()
+            => ReviewError::InvalidPDA,
+        wrong_stats: |c| c.accounts[5].key = Pubkey::new_unique()
+            => ReviewError::InvalidPDA,
+        wrong_profile: |c| c.accounts[6].key = Pubkey::new_unique()
+            => ReviewError::InvalidPDA,
+        unsupported_instruction: |c| {
+            c.data = MovieInstruction::UpdateRating { rating: 5 }.pack();
+            dry_run(&mut c);
+        } => ProgramError::InvalidInstructionData,
+    }
+
+    add_comment_dry_run => valid_add_comment_dry_run {
+        non_signer: |c| c.accounts[0].is_signer = false
+            => ProgramError::MissingRequiredSignature,
+        wrong_comment_pda: |c| c.accounts[3].key = Pubkey::new_unique()
+            => ReviewError::InvalidPDA,
+        comment_exists: |c| c.accounts[3].owner = c.program_id
+            => ProgramError::AccountAlreadyInitialized,
+        wrong_stats: |c| c.accounts[7].key = Pubkey::new_unique()
+            => ReviewError::InvalidPDA,
+        wrong_hot_feed: |c| c.accounts[8].key = Pubkey::new_unique()
+            => ReviewError::InvalidPDA,
+        self_comment: |c| comment_as_reviewer_with_self_comments_disabled(&mut c)
+            => ReviewError::SelfComment,
+    }
+
+    add_movie_review_idempotent => valid_add_movie_review_idempotent {
+        non_signer: |c| c.accounts[0].is_signer = false
+            => ProgramError::MissingRequiredSignature,
+        wrong_pda: |c| c.accounts[1].key = Pubkey::new_unique()
+            => ReviewError::InvalidPDA,
+        closed: |c| c.accounts[1].data[..8].fill(0xff)
+            => CommonError::AccountClosed,
+        not_a_review: |c| c.accounts[1].data = borsh::to_vec(&MovieCommentCounter {
+            discriminator: MovieCommentCounter::DISCRIMINATOR,
+            is_initialized: true,
+            total_comments_created: 0,
+            page: 0,
+            active_comments: 0,
+        }).unwrap() => ReviewError::InvalidDiscriminator,
+        different_rating: |c| c.data = idempotent_review_data(5, "Still holds up")
+            => ReviewError::DuplicateReview,
+        different_description: |c| c.data = idempotent_review_data(4, "Overrated")
+            => ReviewError::DuplicateReview,
+        new_review_validated: |c| {
+            c.accounts[1] = TestAccount::new(c.accounts[1].key, system_program::ID, vec![]);
+            c.data = idempotent_review_data(0, "Still holds up");
+        } => ReviewError::InvalidRating,
+    }
+
+    update_movie_review => valid_update_movie_review {
+        non_signer: |c| c.accounts[0].is_signer = false
+            => ProgramError::MissingRequiredSignature,
+        wrong_owner: |c| c.accounts[1].owner = Pubkey::new_unique()
+            => ProgramError::IllegalOwner,
+        wrong_pda: |c| c.accounts[0].key = Pubkey::new_unique()
+            => ReviewError::InvalidPDA,
+        rating_too_high: |c| c.data = MovieInstruction::UpdateMovieReview {
+            title: TITLE.to_string(),
+            rating: 6,
+            description: String::new(),
+        }.pack() => ReviewError::InvalidRating,
+        above_configured_scale: |c| set_rating_scale(&mut c, 3, 0, 4)
+            => ReviewError::InvalidRating,
+        oversize_payload: |c| c.data = MovieInstruction::UpdateMovieReview {
+            title: TITLE.to_string(),
+            rating: 4,
+            description: oversize_text(),
+        }.pack() => ReviewError::InvalidDataLength,
+        uninitialized: |c| c.accounts[1].data = vec![0; MovieAccountState::MAX_ACCOUNT_SIZE]
+            => ReviewError::UninitializedAccount,
+        invalid_markdown: |c| {
+            set_stored_description(&mut c, "Fine", BodyFormat::MarkdownLite);
+            c.data = MovieInstruction::UpdateMovieReview {
+                title: TITLE.to_string(),
+                rating: 5,
+                description: "**Even better".to_string(),
+            }.pack();
+        } => ReviewError::InvalidRichText,
+        wrong_profile: |c| c.accounts[2].key = Pubkey::new_unique()
+            => ReviewError::InvalidPDA,
+        wrong_edits: |c| c.accounts[4].key = Pubkey::new_unique()
+            => ReviewError::InvalidPDA,
+        paid_edit_without_burn: |c| require_edit_burn(&mut c)
+            => ProgramError::NotEnoughAccountKeys,
+        wrong_burn_mint: |c| {
+            require_edit_burn(&mut c);
+            for _ in 0..3 {
+                c.accounts.push(TestAccount::new(Pubkey::new_unique(), spl_token::ID, vec![]));
+            }
+        } => ReviewError::InvalidBurnMint,
+    }
+
+    update_rating => valid_update_rating {
+        non_signer: |c| c.accounts[0].is_signer = false
+            => ProgramError::MissingRequiredSignature,
+        wrong_owner: |c| c.accounts[1].owner = Pubkey::new_unique()
+            => ProgramError::IllegalOwner,
+        closed: |c| c.accounts[1].data[..8].fill(0xff)
+            => CommonError::AccountClosed,
+        rating_too_low: |c| c.data = MovieInstruction::UpdateRating { rating: 0 }.pack()
+            => ReviewError::InvalidRating,
+        rating_too_high: |c| c.data = MovieInstruction::UpdateRating { rating: 6 }.pack()
+            => ReviewError::InvalidRating,
+        above_configured_scale: |c| {
+            set_rating_scale(&mut c, 3, 0, 100);
+            c.data = MovieInstruction::UpdateRating { rating: 101 }.pack();
+        } => ReviewError::InvalidRating,
+        uninitialized: |c| c.accounts[1].data[1] = 0
+            => ReviewError::UninitializedAccount,
+        anonymous: |c| c.accounts[1].data[0] = MovieAccountState::ANONYMOUS_DISCRIMINATOR
+            => ReviewError::InvalidDiscriminator,
+        not_reviewer: |c| c.accounts[0].key = Pubkey::new_unique()
+            => CommonError::Unauthorized,
+        wrong_profile: |c| c.accounts[2].key = Pubkey::new_unique()
+            => ReviewError::InvalidPDA,
+        movie_keyed_without_rating_index: |c| {
+            let (movie, _) = Pubkey::find_program_address(
+                &[Movie::SEED.as_bytes(), Movie::title_hash(TITLE).as_ref()],
+                &c.program_id,
+            );
+            c.accounts[1].key = Pubkey::find_program_address(
+                &[c.accounts[0].key.as_ref(), movie.as_ref()],
+                &c.program_id,
+            )
+            .0;
+        } => ProgramError::NotEnoughAccountKeys,
+    }
+
+    append_to_description => valid_append_to_description {
+        non_signer: |c| c.accounts[0].is_signer = false
+            => ProgramError::MissingRequiredSignature,
+        wrong_owner: |c| c.accounts[1].owner = Pubkey::new_unique()
+            => ProgramError::IllegalOwner,
+        not_reviewer: |c| c.accounts[0].key = Pubkey::new_unique()
+            => CommonError::Unauthorized,
+        anonymous: |c| c.accounts[1].data[0] = MovieAccountState::ANONYMOUS_DISCRIMINATOR
+            => ReviewError::InvalidDiscriminator,
+        empty_text: |c| c.data = MovieInstruction::AppendToDescription {
+            text: String::new(),
+        }.pack() => ReviewError::InvalidDataLength,
+        past_max_size: |c| c.data = MovieInstruction::AppendToDescription {
+            text: "x".repeat(MovieAccountState::MAX_GROWN_ACCOUNT_SIZE),
+        }.pack() => ReviewError::InvalidDataLength,
+        invalid_markdown: |c| {
+            set_stored_description(&mut c, "Fine", BodyFormat::MarkdownLite);
+            c.data = MovieInstruction::AppendToDescription {
+                text: " **really".to_string(),
+            }.pack();
+        } => ReviewError::InvalidRichText,
+    }
+
+    set_review_body_format => valid_set_review_body_format {
+        non_signer: |c| c.accounts[0].is_signer = false
+            => ProgramError::MissingRequiredSignature,
+        wrong_owner: |c| c.accounts[1].owner = Pubkey::new_unique()
+            => ProgramError::IllegalOwner,
+        not_reviewer: |c| c.accounts[0].key = Pubkey::new_unique()
+            => CommonError::Unauthorized,
+        anonymous_review: |c| c.accounts[1].data[0] = MovieAccountState::ANONYMOUS_DISCRIMINATOR
+            => ReviewError::InvalidDiscriminator,
+        unclosed_strong: |c| set_stored_description(&mut c, "**Still holds up", BodyFormat::Plain)
+            => ReviewError::InvalidRichText,
+        unclosed_code: |c| set_stored_description(&mut c, "`Still holds up", BodyFormat::Plain)
+            => ReviewError::InvalidRichText,
+        html: |c| set_stored_description(&mut c, "<b>Still</b> holds up", BodyFormat::Plain)
+            => ReviewError::InvalidRichText,
+        stray_bracket: |c| set_stored_description(&mut c, "Still] holds up", BodyFormat::Plain)
+            => ReviewError::InvalidRichText,
+        insecure_link: |c| set_stored_description(&mut c, "[trailer](http://example.com)", BodyFormat::Plain)
+            => ReviewError::InvalidRichText,
+        unclosed_link: |c| set_stored_description(&mut c, "[trailer](https://example.com", BodyFormat::Plain)
+            => ReviewError::InvalidRichText,
+        too_many_links: |c| set_stored_description(
+            &mut c,
+            &"[a](https://example.com) ".repeat(6),
+            BodyFormat::Plain,
+        ) => ReviewError::InvalidRichText,
+        off_chain: |c| c.data = MovieInstruction::SetReviewBodyFormat {
+            body_format: BodyFormat::OffChain,
+        }.pack() => ReviewError::BodyHashRequired,
+    }
+
+    add_review_for_movie => valid_add_review_for_movie {
+        non_signer: |c| c.accounts[0].is_signer = false
+            => ProgramError::MissingRequiredSignature,
+        unregistered_movie: |c| c.accounts[1].owner = Pubkey::new_unique()
+            => ProgramError::IllegalOwner,
+        wrong_pda: |c| c.accounts[2].key = Pubkey::new_unique()
+            => ReviewError::InvalidPDA,
+        wrong_legacy_pda: |c| c.accounts[3].key = Pubkey::new_unique()
+            => ReviewError::InvalidPDA,
+        duplicate_review: |c| c.accounts[2].owner = c.program_id
+            => ReviewError::DuplicateReview,
+        duplicate_legacy_review: |c| c.accounts[3].owner = c.program_id
+            => ReviewError::DuplicateReview,
+        missing_pass: |c| require_reviewer_pass(&mut c, 6)
+            => ReviewError::MissingReviewerPass,
+    }
+
+    reveal_authorship => valid_reveal_authorship {
+        non_signer: |c| c.accounts[0].is_signer = false
+            => ProgramError::MissingRequiredSignature,
+        wrong_owner: |c| c.accounts[1].owner = Pubkey::new_unique()
+            => ProgramError::IllegalOwner,
+        wrong_salt: |c| c.data = MovieInstruction::RevealAuthorship { salt: [8; 32] }.pack()
+            => ReviewError::InvalidAuthorshipProof,
+        wrong_signer: |c| c.accounts[0].key = Pubkey::new_unique()
+            => ReviewError::InvalidAuthorshipProof,
+        not_anonymous: |c| c.accounts[1].data[0] = MovieAccountState::DISCRIMINATOR
+            => ReviewError::InvalidDiscriminator,
+    }
+
+    mint_review_nft => valid_mint_review_nft {
+        non_signer: |c| c.accounts[0].is_signer = false
+            => ProgramError::MissingRequiredSignature,
+        wrong_token_program: |c| c.accounts[11].key = Pubkey::new_unique()
+            => ProgramError::IncorrectProgramId,
+        wrong_owner: |c| c.accounts[1].owner = Pubkey::new_unique()
+            => ProgramError::IllegalOwner,
+        anonymous_review: |c| c.accounts[1].data[0] = MovieAccountState::ANONYMOUS_DISCRIMINATOR
+            => ReviewError::InvalidDiscriminator,
+        not_author: |c| c.accounts[0].key = Pubkey::new_unique()
+            => CommonError::Unauthorized,
+        no_collection: |c| c.accounts[2] = config_account(&c.program_id)
+            => ReviewError::InvalidReviewCollection,
+        stale_collection: |c| c.accounts[8].key = Pubkey::new_unique()
+            => ReviewError::InvalidReviewCollection,
+        wrong_nft_mint: |c| c.accounts[4].key = Pubkey::new_unique()
+            => ReviewError::InvalidPDA,
+    }
+
+    mark_verified_release => valid_mark_verified_release {
+        non_signer: |c| c.accounts[0].is_signer = false
+            => ProgramError::MissingRequiredSignature,
+        unregistered_movie: |c| c.accounts[1].owner = Pubkey::new_unique()
+            => ProgramError::IllegalOwner,
+        no_oracle: |c| c.accounts[2] = config_account(&c.program_id)
+            => ReviewError::InvalidReleaseAttestation,
+        untrusted_attestation: |c| c.accounts[3].owner = Pubkey::new_unique()
+            => ReviewError::InvalidReleaseAttestation,
+        wrong_feed: |c| c.accounts[3].data[0] ^= 1
+            => ReviewError::InvalidReleaseAttestation,
+        other_movie: |c| c.accounts[3].data[32] ^= 1
+            => ReviewError::InvalidReleaseAttestation,
+    }
+
+    set_movie_external_id => valid_set_movie_external_id {
+        non_signer: |c| c.accounts[0].is_signer = false
+            => ProgramError::MissingRequiredSignature,
+        not_authority: |c| c.accounts[0].key = Pubkey::new_unique()
+            => CommonError::Unauthorized,
+        no_config: |c| c.accounts[2] = config_account(&c.program_id)
+            => ReviewError::UninitializedAccount,
+        no_namespace: |c| set_external_id(&mut c, "tt0133093")
+            => ReviewError::InvalidExternalId,
+        empty_value: |c| set_external_id(&mut c, "imdb:")
+            => ReviewError::InvalidExternalId,
+        uppercase: |c| set_external_id(&mut c, "IMDB:tt0133093")
+            => ReviewError::InvalidExternalId,
+        punctuation: |c| set_external_id(&mut c, "imdb:tt/0133093")
+            => ReviewError::InvalidExternalId,
+        too_long: |c| set_external_id(&mut c, &format!("imdb:{}", "1".repeat(Movie::MAX_EXTERNAL_ID_LEN)))
+            => ReviewError::InvalidExternalId,
+        unregistered_movie: |c| c.accounts[1].owner = Pubkey::new_unique()
+            => ProgramError::IllegalOwner,
+        wrong_pda: |c| c.accounts[3].key = Pubkey::new_unique()
+            => ReviewError::InvalidPDA,
+        already_indexed: |c| c.accounts[3].owner = c.program_id
+            => ReviewError::ExternalIdTaken,
+    }
+    set_movie_release_date => valid_set_movie_release_date {
+        non_signer: |c| c.accounts[0].is_signer = false
+            => ProgramError::MissingRequiredSignature,
+        not_authority: |c| c.accounts[0].key = Pubkey::new_unique()
+            => CommonError::Unauthorized,
+        no_config: |c| c.accounts[2] = config_account(&c.program_id)
+            => ReviewError::UninitializedAccount,
+        negative_date: |c| c.data = MovieInstruction::SetMovieReleaseDate {
+            release_date: -1,
+        }.pack() => ReviewError::InvalidReleaseDate,
+        unregistered_movie: |c| c.accounts[1].owner = Pubkey::new_unique()
+            => ProgramError::IllegalOwner,
+    }
+
+    add_review_translation => valid_add_review_translation {
+        non_signer: |c| c.accounts[0].is_signer = false
+            => ProgramError::MissingRequiredSignature,
+        foreign_review: |c| c.accounts[1].owner = Pubkey::new_unique()
+            => ProgramError::IllegalOwner,
+        not_reviewer: |c| c.accounts[0].key = Pubkey::new_unique()
+            => CommonError::Unauthorized,
+        empty_language: |c| set_language(&mut c, "")
+            => ReviewError::InvalidLanguageTag,
+        uppercase_language: |c| set_language(&mut c, "pt-BR")
+            => ReviewError::InvalidLanguageTag,
+        short_primary: |c| set_language(&mut c, "p-br")
+            => ReviewError::InvalidLanguageTag,
+        empty_subtag: |c| set_language(&mut c, "pt--br")
+            => ReviewError::InvalidLanguageTag,
+        wrong_pda: |c| c.accounts[2].key = Pubkey::new_unique()
+            => ReviewError::InvalidPDA,
+        oversize_payload: |c| c.data = MovieInstruction::AddReviewTranslation {
+            language: "pt-br".to_string(),
+            description: oversize_text(),
+        }.pack() => ReviewError::InvalidDataLength,
+        duplicate: |c| c.accounts[2].owner = c.program_id
+            => ReviewError::DuplicateTranslation,
+    }
+    fund_translation_bounty => valid_fund_translation_bounty {
+        non_signer: |c| c.accounts[0].is_signer = false
+            => ProgramError::MissingRequiredSignature,
+        not_reviewer: |c| c.accounts[0].key = Pubkey::new_unique()
+            => CommonError::Unauthorized,
+        translated: |c| c.accounts[2].owner = c.program_id
+            => ReviewError::DuplicateTranslation,
+        wrong_pda: |c| c.accounts[3].key = Pubkey::new_unique()
+            => ReviewError::InvalidPDA,
+        zero_amount: |c| c.data = MovieInstruction::FundTranslationBounty {
+            language: "pt-br".to_string(),
+            amount: 0,
+        }.pack() => ReviewError::InvalidBountyAmount,
+        closed_bounty: |c| {
+            c.accounts[3].owner = c.program_id;
+            c.accounts[3].data = vec![0xff; TranslationBounty::get_account_size()];
+        } => CommonError::AccountClosed,
+    }
+    submit_translation => valid_submit_translation {
+        non_signer: |c| c.accounts[0].is_signer = false
+            => ProgramError::MissingRequiredSignature,
+        no_bounty: |c| c.accounts[2].owner = system_program::ID
+            => ProgramError::IllegalOwner,
+        wrong_bounty: |c| c.accounts[2].key = Pubkey::new_unique()
+            => ReviewError::InvalidPDA,
+        not_bounty: |c| c.accounts[2].data[0] = ReviewTranslation::DISCRIMINATOR
+            => ReviewError::InvalidDiscriminator,
+        wrong_submission: |c| c.accounts[3].key = Pubkey::new_unique()
+            => ReviewError::InvalidPDA,
+        oversize_payload: |c| c.data = MovieInstruction::SubmitTranslation {
+            language: "pt-br".to_string(),
+            description: oversize_text(),
+        }.pack() => ReviewError::InvalidDataLength,
+    }
+    approve_translation => valid_approve_translation {
+        non_signer: |c| c.accounts[0].is_signer = false
+            => ProgramError::MissingRequiredSignature,
+        not_reviewer: |c| c.accounts[0].key = Pubkey::new_unique()
+            => CommonError::Unauthorized,
+        translated: |c| c.accounts[2].owner = c.program_id
+            => ReviewError::DuplicateTranslation,
+        no_bounty: |c| c.accounts[3].owner = system_program::ID
+            => ProgramError::IllegalOwner,
+        other_translator: |c| c.accounts[5].key = Pubkey::new_unique()
+            => ReviewError::InvalidPDA,
+        withdrawn_submission: |c| c.accounts[4].data[..8].fill(0xff)
+            => CommonError::AccountClosed,
+    }
+    cancel_translation_bounty => valid_cancel_translation_bounty {
+        non_signer: |c| c.accounts[0].is_signer = false
+            => ProgramError::MissingRequiredSignature,
+        not_funder: |c| c.accounts[0].key = Pubkey::new_unique()
+            => CommonError::Unauthorized,
+        wrong_pda: |c| c.accounts[2].key = Pubkey::new_unique()
+            => ReviewError::InvalidPDA,
+        no_bounty: |c| c.accounts[2].owner = system_program::ID
+            => ProgramError::IllegalOwner,
+        not_bounty: |c| c.accounts[2].data[0] = ReviewTranslation::DISCRIMINATOR
+            => ReviewError::InvalidDiscriminator,
+    }
+    withdraw_translation_submission => valid_withdraw_translation_submission {
+        non_signer: |c| c.accounts[0].is_signer = false
+            => ProgramError::MissingRequiredSignature,
+        other_translator: |c| c.accounts[0].key = Pubkey::new_unique()
+            => ReviewError::InvalidPDA,
+        not_submission: |c| c.accounts[3].data[0] = ReviewTranslation::DISCRIMINATOR
+            => ReviewError::InvalidDiscriminator,
+    }
+
+    request_featured_draw => valid_request_featured_draw {
+        non_signer: |c| c.accounts[0].is_signer = false
+            => ProgramError::MissingRequiredSignature,
+        no_candidates: |c| c.accounts.truncate(5)
+            => ReviewError::NoFeaturedCandidates,
+        foreign_candidate: |c| c.accounts[5].owner = Pubkey::new_unique()
+            => ProgramError::IllegalOwner,
+        uninitialized_candidate: |c| c.accounts[5].data[1] = 0
+            => ReviewError::UninitializedAccount,
+        no_vrf_program: |c| c.accounts[2] = config_account(&c.program_id)
+            => ReviewError::InvalidRandomness,
+        untrusted_randomness: |c| c.accounts[3].owner = Pubkey::new_unique()
+            => ReviewError::InvalidRandomness,
+        fulfilled_randomness: |c| *c.accounts[3].data.last_mut().unwrap() = 1
+            => ReviewError::InvalidRandomness,
+        wrong_pda: |c| c.accounts[1].key = Pubkey::new_unique()
+            => ReviewError::InvalidPDA,
+    }
+
+    fulfill_featured_draw => valid_fulfill_featured_draw {
+        non_signer: |c| c.accounts[0].is_signer = false
+            => ProgramError::MissingRequiredSignature,
+        wrong_owner: |c| c.accounts[1].owner = Pubkey::new_unique()
+            => ProgramError::IllegalOwner,
+        not_pending: |c| c.accounts[1].data[1 + 1 + 8 + 32] = 0
+            => ReviewError::NoPendingDraw,
+        other_randomness: |c| c.accounts[3].key = Pubkey::new_unique()
+            => ReviewError::InvalidRandomness,
+        other_candidates: |c| c.accounts[4].key = Pubkey::new_unique()
+            => ReviewError::FeaturedCandidatesMismatch,
+        no_candidates: |c| c.accounts.truncate(4)
+            => ReviewError::FeaturedCandidatesMismatch,
+        untrusted_randomness: |c| c.accounts[3].owner = Pubkey::new_unique()
+            => ReviewError::InvalidRandomness,
+        unfulfilled: |c| {
+            let len = c.accounts[3].data.len();
+            c.accounts[3].data[len - 64..].fill(0);
+        } => ReviewError::RandomnessNotFulfilled,
+    }
+
+    import_foreign_review => valid_import_foreign_review {
+        non_signer: |c| c.accounts[0].is_signer = false
+            => ProgramError::MissingRequiredSignature,
+        no_emitter: |c| c.accounts[2] = config_account(&c.program_id)
+            => ReviewError::InvalidForeignMessage,
+        unverified_vaa: |c| c.accounts[1].owner = Pubkey::new_unique()
+            => ReviewError::InvalidForeignMessage,
+        untrusted_emitter: |c| c.accounts[1].data = posted_vaa_data([5; 32], 4, "")
+            => ReviewError::InvalidForeignMessage,
+        wrong_pda: |c| c.accounts[3].key = Pubkey::new_unique()
+            => ReviewError::InvalidPDA,
+        already_imported: |c| c.accounts[3].owner = c.program_id
+            => ReviewError::DuplicateReview,
+        oversize_payload: |c| c.accounts[1].data = posted_vaa_data(EMITTER, 4, &oversize_text())
+            => ReviewError::InvalidDataLength,
+        rating_too_high: |c| c.accounts[1].data = posted_vaa_data(EMITTER, 6, "")
+            => ReviewError::InvalidRating,
+    }
+
+    get_upgrade_authority_status => valid_get_upgrade_authority_status {
+        wrong_pda: |c| c.accounts[0].key = Pubkey::new_unique()
+            => ReviewError::InvalidPDA,
+        wrong_owner: |c| c.accounts[0].owner = Pubkey::new_unique()
+            => ProgramError::IllegalOwner,
+        not_program_data: |c| c.accounts[0].data[0] = 2
+            => ProgramError::InvalidAccountData,
+        truncated: |c| c.accounts[0].data.truncate(12)
+            => ProgramError::InvalidAccountData,
+        wrong_config_pda: |c| c.accounts[1].key = Pubkey::new_unique()
+            => ReviewError::InvalidPDA,
+    }
+
+    top_up_account => valid_top_up_account {
+        non_signer: |c| c.accounts[0].is_signer = false
+            => ProgramError::MissingRequiredSignature,
+        wrong_owner: |c| c.accounts[1].owner = Pubkey::new_unique()
+            => ProgramError::IllegalOwner,
+        closed: |c| c.accounts[1].data[..8].fill(0xff)
+            => CommonError::AccountClosed,
+    }
+
+    mute_user => valid_mute_user {
+        non_signer: |c| c.accounts[0].is_signer = false
+            => ProgramError::MissingRequiredSignature,
+        wrong_pda: |c| c.accounts[1].key = Pubkey::new_unique()
+            => ReviewError::InvalidPDA,
+        already_muted: |c| c.accounts[1].owner = c.program_id
+            => ProgramError::AccountAlreadyInitialized,
+    }
+
+    unmute_user => valid_unmute_user {
+        non_signer: |c| c.accounts[0].is_signer = false
+            => ProgramError::MissingRequiredSignature,
+        wrong_owner: |c| c.accounts[1].owner = Pubkey::new_unique()
+            => ProgramError::IllegalOwner,
+        not_reviewer: |c| c.accounts[0].key = Pubkey::new_unique()
+            => CommonError::Unauthorized,
+        wrong_discriminator: |c| c.accounts[1].data[0] = Follow::DISCRIMINATOR
+            => ReviewError::InvalidDiscriminator,
+    }
+
+    create_session => valid_create_session {
+        non_signer: |c| c.accounts[0].is_signer = false
+            => ProgramError::MissingRequiredSignature,
+        no_scopes: |c| *c.data.last_mut().unwrap() = 0
+            => ReviewError::InvalidSessionScope,
+        unknown_scope: |c| *c.data.last_mut().unwrap() = 0x80
+            => ReviewError::InvalidSessionScope,
+        wrong_pda: |c| c.accounts[1].key = Pubkey::new_unique()
+            => ReviewError::InvalidPDA,
+        already_exists: |c| c.accounts[1].owner = c.program_id
+            => ProgramError::AccountAlreadyInitialized,
+    }
+
+    revoke_session => valid_revoke_session {
+        non_signer: |c| c.accounts[0].is_signer = false
+            => ProgramError::MissingRequiredSignature,
+        wrong_owner: |c| c.accounts[1].owner = Pubkey::new_unique()
+            => ProgramError::IllegalOwner,
+        not_authority: |c| c.accounts[0].key = Pubkey::new_unique()
+            => CommonError::Unauthorized,
+        wrong_discriminator: |c| c.accounts[1].data[0] = Follow::DISCRIMINATOR
+            => ReviewError::InvalidDiscriminator,
+    }
+
+    withdraw_excess_lamports => valid_withdraw_excess_lamports {
+        non_signer: |c| c.accounts[0].is_signer = false
+            => ProgramError::MissingRequiredSignature,
+        wrong_owner: |c| c.accounts[1].owner = Pubkey::new_unique()
+            => ProgramError::IllegalOwner,
+        closed: |c| c.accounts[1].data[..8].fill(0xff)
+            => CommonError::AccountClosed,
+        not_reviewer: |c| c.accounts[0].key = Pubkey::new_unique()
+            => CommonError::Unauthorized,
+        uncontrolled_account: |c| c.accounts[1].data =
+            borsh::to_vec(&MovieCommentCounter {
+                discriminator: MovieCommentCounter::DISCRIMINATOR,
+                is_initialized: true,
+                total_comments_created: 0,
+                page: 0,
+                active_comments: 0,
+            }).unwrap()
+            => CommonError::Unauthorized,
+        relayed_by_another: |c| {
+            c.accounts[2].owner = c.program_id;
+            c.accounts[2].data = borsh::to_vec(&RentPayer {
+                discriminator: RentPayer::DISCRIMINATOR,
+                is_initialized: true,
+                account: c.accounts[1].key,
+                payer: Pubkey::new_unique(),
+            }).unwrap();
+        } => CommonError::Unauthorized,
+        wrong_rent_payer: |c| c.accounts[2].key = Pubkey::new_unique()
+            => ReviewError::InvalidPDA,
+    }
+
+    add_comment => valid_add_comment {
+        non_signer: |c| c.accounts[0].is_signer = false
+            => ProgramError::MissingRequiredSignature,
+        wrong_owner: |c| c.accounts[1].owner = Pubkey::new_unique()
+            => ProgramError::IllegalOwner,
+        wrong_pda: |c| c.accounts[3].key = Pubkey::new_unique()
+            => ReviewError::InvalidPDA,
+        oversize_payload: |c| c.data = MovieInstruction::AddComment {
+            comment: "x".repeat(MovieComment::MAX_ACCOUNT_SIZE),
+            media_uri: None,
+        }.pack() => ReviewError::InvalidDataLength,
+        media_uri_bad_scheme: |c| set_media_uri(&mut c, "javascript:alert(1)")
+            => ReviewError::InvalidMediaUri,
+        media_uri_plain_http: |c| set_media_uri(&mut c, "http://example.com/cat.png")
+            => ReviewError::InvalidMediaUri,
+        media_uri_empty_path: |c| set_media_uri(&mut c, "https://")
+            => ReviewError::InvalidMediaUri,
+        media_uri_whitespace: |c| set_media_uri(&mut c, "https://example.com/a cat.png")
+            => ReviewError::InvalidMediaUri,
+        media_uri_too_long: |c| set_media_uri(
+            &mut c,
+            &format!("https://{}", "a".repeat(MovieComment::MAX_MEDIA_URI_LEN)),
+        ) => ReviewError::InvalidMediaUri,
+        uninitialized_counter: |c| c.accounts[2].data =
+            vec![0; MovieCommentCounter::get_account_size()]
+            => ReviewError::UninitializedAccount,
+        wrong_config_pda: |c| c.accounts[5].key = Pubkey::new_unique()
+            => ReviewError::InvalidPDA,
+        self_comment: |c| comment_as_reviewer_with_self_comments_disabled(&mut c)
+            => ReviewError::SelfComment,
+        comments_disabled: |c| set_review_comment_settings(&mut c, CommentSettings {
+            comments_enabled: false,
+            comment_policy: CommentPolicy::Anyone,
+        }) => ReviewError::CommentsDisabled,
+        not_following: |c| restrict_comments(&mut c, CommentPolicy::FollowersOnly)
+            => ReviewError::CommentPolicyNotMet,
+        follow_of_someone_else: |c| {
+            restrict_comments(&mut c, CommentPolicy::FollowersOnly);
+            let program_id = c.program_id;
+            c.accounts.push(TestAccount::new(Pubkey::new_unique(), program_id, borsh::to_vec(&Follow {
+                discriminator: Follow::DISCRIMINATOR,
+                is_initialized: true,
+                follower: c.accounts[0].key,
+                reviewer: Pubkey::new_unique(),
+            }).unwrap()));
+        } => ReviewError::CommentPolicyNotMet,
+        wrong_mute_pda: |c| c.accounts[6].key = Pubkey::new_unique()
+            => ReviewError::InvalidPDA,
+        muted: |c| c.accounts[6].owner = c.program_id
+            => ReviewError::Muted,
+        not_token_holder: |c| restrict_comments(&mut c, CommentPolicy::TokenHoldersOnly {
+            mint: Pubkey::new_unique(),
+        }) => ReviewError::CommentPolicyNotMet,
+    }
+
+    add_paged_comment => valid_add_paged_comment {
+        non_signer: |c| c.accounts[0].is_signer = false
+            => ProgramError::MissingRequiredSignature,
+        wrong_page_pda: |c| c.accounts[3].key = Pubkey::new_unique()
+            => ReviewError::InvalidCommentPage,
+        oversize_comment: |c| c.data = MovieInstruction::AddPagedComment {
+            comment: "x".repeat(CommentPage::MAX_COMMENT_LEN + 1),
+        }.pack() => ReviewError::InvalidDataLength,
+        muted: |c| c.accounts[6].owner = c.program_id
+            => ReviewError::Muted,
+        page_of_other_review: |c| {
+            c.accounts[3].owner = c.program_id;
+            c.accounts[3].data = borsh::to_vec(&CommentPage {
+                discriminator: CommentPage::DISCRIMINATOR,
+                is_initialized: true,
+                review: Pubkey::new_unique(),
+                page: 0,
+                comments: vec![],
+            }).unwrap();
+        } => ReviewError::InvalidCommentPage,
+    }
+
+    record_tip => valid_record_tip {
+        non_signer: |c| c.accounts[0].is_signer = false
+            => ProgramError::MissingRequiredSignature,
+        no_tip_program: |c| c.accounts[1] = config_account(&c.program_id)
+            => ReviewError::InvalidTipAuthority,
+        not_tip_authority: |c| c.accounts[0].key = Pubkey::new_unique()
+            => ReviewError::InvalidTipAuthority,
+        tips_disabled: |c| {
+            let features = abi::config::FEATURES;
+            c.accounts[1].data[features.offset..features.end()]
+                .copy_from_slice(&ProgramConfig::FEATURES_SET.to_le_bytes());
+        } => ReviewError::FeatureDisabled,
+        wrong_stats_pda: |c| c.accounts[2].key = Pubkey::new_unique()
+            => ReviewError::InvalidPDA,
+        not_global_stats: |c| c.accounts[2].data[0] = ReviewerProfile::DISCRIMINATOR
+            => ReviewError::InvalidDiscriminator,
+        overflow: |c| c.accounts[2].data = borsh::to_vec(&GlobalStats {
+            discriminator: GlobalStats::DISCRIMINATOR,
+            is_initialized: true,
+            total_tips: u64::MAX,
+            ..GlobalStats::default()
+        }).unwrap() => CommonError::Overflow,
+        unowned_review: |c| c.accounts[5].owner = system_program::ID
+            => ProgramError::IllegalOwner,
+    }
+
+    record_sol_tip => valid_record_sol_tip {
+        non_signer: |c| c.accounts[0].is_signer = false
+            => ProgramError::MissingRequiredSignature,
+        not_tip_authority: |c| c.accounts[0].key = Pubkey::new_unique()
+            => ReviewError::InvalidTipAuthority,
+        overflow: |c| c.accounts[2].data = borsh::to_vec(&GlobalStats {
+            discriminator: GlobalStats::DISCRIMINATOR,
+            is_initialized: true,
+            total_tip_lamports: u64::MAX,
+            ..GlobalStats::default()
+        }).unwrap() => CommonError::Overflow,
+    }
+
+    set_content_limits => valid_set_content_limits {
+        non_signer: |c| c.accounts[0].is_signer = false
+            => ProgramError::MissingRequiredSignature,
+        not_authority: |c| c.accounts[0].key = Pubkey::new_unique()
+            => CommonError::Unauthorized,
+        uninitialized_config: |c| c.accounts[1] = config_account(&c.program_id)
+            => ReviewError::UninitializedAccount,
+        review_too_small: |c| c.data = set_limits_data(1, 0)
+            => ReviewError::InvalidContentLimits,
+        review_too_large: |c| c.data = set_limits_data(
+            MovieAccountState::MAX_GROWN_ACCOUNT_SIZE as u32 + 1,
+            0,
+        ) => ReviewError::InvalidContentLimits,
+        comment_too_small: |c| c.data = set_limits_data(0, 1)
+            => ReviewError::InvalidContentLimits,
+    }
+
+    set_rating_scale => valid_set_rating_scale {
+        non_signer: |c| c.accounts[0].is_signer = false
+            => ProgramError::MissingRequiredSignature,
+        not_authority: |c| c.accounts[0].key = Pubkey::new_unique()
+            => CommonError::Unauthorized,
+        uninitialized_config: |c| c.accounts[1] = config_account(&c.program_id)
+            => ReviewError::UninitializedAccount,
+        single_value: |c| c.data = set_scale_data(3, 3, 0)
+            => ReviewError::InvalidRatingScale,
+        inverted: |c| c.data = set_scale_data(10, 1, 0)
+            => ReviewError::InvalidRatingScale,
+        too_many_decimals: |c| c.data = set_scale_data(0, 100, RatingScale::MAX_DECIMALS + 1)
+            => ReviewError::InvalidRatingScale,
+    }
+
+    set_cpi_callers => valid_set_cpi_callers {
+        non_signer: |c| c.accounts[0].is_signer = false
+            => ProgramError::MissingRequiredSignature,
+        not_authority: |c| c.accounts[0].key = Pubkey::new_unique()
+            => CommonError::Unauthorized,
+        uninitialized_config: |c| c.accounts[1] = config_account(&c.program_id)
+            => ReviewError::UninitializedAccount,
+        too_many: |c| c.data = set_cpi_callers_data(ProgramConfig::MAX_CPI_CALLERS + 1)
+            => ReviewError::TooManyCpiCallers,
+    }
+
+    set_features => valid_set_features {
+        non_signer: |c| c.accounts[0].is_signer = false
+            => ProgramError::MissingRequiredSignature,
+        not_authority: |c| c.accounts[0].key = Pubkey::new_unique()
+            => CommonError::Unauthorized,
+        uninitialized_config: |c| c.accounts[1] = config_account(&c.program_id)
+            => ReviewError::UninitializedAccount,
+        unknown_feature: |c| c.data = MovieInstruction::SetFeatures {
+            features: ProgramConfig::FEATURES_SET,
+        }.pack() => ReviewError::UnknownFeature,
+    }
+
+    set_age_gate => valid_set_age_gate {
+        non_signer: |c| c.accounts[0].is_signer = false
+            => ProgramError::MissingRequiredSignature,
+        not_authority: |c| c.accounts[0].key = Pubkey::new_unique()
+            => CommonError::Unauthorized,
+        uninitialized_config: |c| c.accounts[1] = config_account(&c.program_id)
+            => ReviewError::UninitializedAccount,
+    }
+
+    set_hide_threshold => valid_set_hide_threshold {
+        non_signer: |c| c.accounts[0].is_signer = false
+            => ProgramError::MissingRequiredSignature,
+        not_authority: |c| c.accounts[0].key = Pubkey::new_unique()
+            => CommonError::Unauthorized,
+        uninitialized_config: |c| c.accounts[1] = config_account(&c.program_id)
+            => ReviewError::UninitializedAccount,
+    }
+
+    set_edit_burn => valid_set_edit_burn {
+        non_signer: |c| c.accounts[0].is_signer = false
+            => ProgramError::MissingRequiredSignature,
+        not_authority: |c| c.accounts[0].key = Pubkey::new_unique()
+            => CommonError::Unauthorized,
+        uninitialized_config: |c| c.accounts[1] = config_account(&c.program_id)
+            => ReviewError::UninitializedAccount,
+    }
+
+    set_co_authors => valid_set_co_authors {
+        non_signer: |c| c.accounts[0].is_signer = false
+            => ProgramError::MissingRequiredSignature,
+        not_reviewer: |c| c.accounts[0].key = Pubkey::new_unique()
+            => CommonError::Unauthorized,
+        too_many: |c| set_co_author_listing(&mut c, (0..4).map(|_| (Pubkey::new_unique(), 100)).collect())
+            => ReviewError::InvalidCoAuthors,
+        reviewer_listed: |c| {
+            let reviewer = c.accounts[0].key;
+            set_co_author_listing(&mut c, vec![(reviewer, 100)]);
+        } => ReviewError::InvalidCoAuthors,
+        listed_twice: |c| {
+            let co_author = Pubkey::new_unique();
+            set_co_author_listing(&mut c, vec![(co_author, 100), (co_author, 200)]);
+        } => ReviewError::InvalidCoAuthors,
+        zero_share: |c| set_co_author_listing(&mut c, vec![(Pubkey::new_unique(), 0)])
+            => ReviewError::InvalidCoAuthors,
+        shares_over_whole: |c| set_co_author_listing(
+            &mut c,
+            vec![(Pubkey::new_unique(), 6_000), (Pubkey::new_unique(), 4_001)],
+        ) => ReviewError::InvalidCoAuthors,
+        wrong_pda: |c| c.accounts[2].key = Pubkey::new_unique()
+            => ReviewError::InvalidPDA,
+    }
+
+    set_critic_authority => valid_set_critic_authority {
+        non_signer: |c| c.accounts[0].is_signer = false
+            => ProgramError::MissingRequiredSignature,
+        on_curve_authority: |c| c.data = MovieInstruction::SetCriticAuthority {
+            authority: Keypair::new().pubkey(),
+            authority_program: Pubkey::new_unique(),
+        }
+        .pack() => ReviewError::InvalidCriticAuthority,
+        no_authority_program: |c| c.data = MovieInstruction::SetCriticAuthority {
+            authority: Pubkey::find_program_address(&[b"critic"], &c.program_id).0,
+            authority_program: Pubkey::default(),
+        }
+        .pack() => ReviewError::InvalidCriticAuthority,
+        wrong_pda: |c| c.accounts[1].key = Pubkey::new_unique()
+            => ReviewError::InvalidPDA,
+        not_profile: |c| {
+            c.accounts[1].owner = c.program_id;
+            c.accounts[1].data = vec![0; ReviewerProfile::get_account_size()];
+            c.accounts[1].data[0] = ReviewEdits::DISCRIMINATOR;
+        } => ReviewError::InvalidDiscriminator,
+    }
+
+    accept_co_authorship => valid_accept_co_authorship {
+        non_signer: |c| c.accounts[0].is_signer = false
+            => ProgramError::MissingRequiredSignature,
+        wrong_owner: |c| c.accounts[1].owner = Pubkey::new_unique()
+            => ProgramError::IllegalOwner,
+        not_listed: |c| c.accounts[0].key = Pubkey::new_unique()
+            => ReviewError::NotCoAuthor,
+        lowered_share: |c| c.data = MovieInstruction::AcceptCoAuthorship { share_bps: 3_001 }.pack()
+            => ReviewError::NotCoAuthor,
+        not_co_authors: |c| c.accounts[1].data[0] = ReviewEdits::DISCRIMINATOR
+            => ReviewError::InvalidDiscriminator,
+    }
+
+    write_snapshot => valid_write_snapshot {
+        non_signer: |c| c.accounts[0].is_signer = false
+            => ProgramError::MissingRequiredSignature,
+        not_authority: |c| c.accounts[0].key = Pubkey::new_unique()
+            => CommonError::Unauthorized,
+        wrong_pda: |c| c.accounts[2].key = Pubkey::new_unique()
+            => ReviewError::InvalidPDA,
+        already_written: |c| c.accounts[2].owner = c.program_id
+            => ReviewError::SnapshotExists,
+        missing_counter: |c| { c.accounts.pop(); } => ProgramError::NotEnoughAccountKeys,
+        unordered: |c| {
+            let second = c.accounts.split_off(6);
+            c.accounts.splice(4..4, second);
+        } => ReviewError::UnorderedSnapshotReviews,
+        duplicate: |c| {
+            let program_id = c.program_id;
+            for i in 4..6 {
+                c.accounts[i + 2] =
+                    TestAccount::new(c.accounts[i].key, program_id, c.accounts[i].data.clone());
+            }
+        } => ReviewError::UnorderedSnapshotReviews,
+        not_a_review: |c| c.accounts[4].data = c.accounts[5].data.clone()
+            => ReviewError::InvalidDiscriminator,
+        counter_of_other_review: |c| c.accounts.swap(5, 7)
+            => ReviewError::InvalidPDA,
+    }
+
+    recompute_stats => valid_recompute_stats {
+        non_signer: |c| c.accounts[0].is_signer = false
+            => ProgramError::MissingRequiredSignature,
+        unregistered_movie: |c| c.accounts[1].owner = Pubkey::new_unique()
+            => ProgramError::IllegalOwner,
+        wrong_stats_pda: |c| c.accounts[2].key = Pubkey::new_unique()
+            => ReviewError::InvalidPDA,
+        not_movie_stats: |c| c.accounts[2].data[0] = GlobalStats::DISCRIMINATOR
+            => ReviewError::InvalidDiscriminator,
+        page_too_large: |c| {
+            let program_id = c.program_id;
+            c.accounts.extend((0..MovieStats::MAX_RECOMPUTE_REVIEWS).map(|_| {
+                TestAccount::new(Pubkey::new_unique(), program_id, vec![])
+            }));
+        } => ReviewError::RecomputePageTooLarge,
+        unordered: |c| c.accounts.swap(4, 5)
+            => ReviewError::UnorderedRecomputeReviews,
+        duplicate: |c| {
+            c.accounts[5] =
+                TestAccount::new(c.accounts[4].key, c.program_id, c.accounts[4].data.clone());
+        } => ReviewError::UnorderedRecomputeReviews,
+        behind_cursor: |c| {
+            let cursor = c.accounts[5].key;
+            c.data = MovieInstruction::RecomputeStats { restart: false, finish: true }.pack();
+            update_movie_stats(&mut c, |stats| stats.recompute_cursor = cursor);
+        } => ReviewError::UnorderedRecomputeReviews,
+        wrong_owner: |c| c.accounts[4].owner = Pubkey::new_unique()
+            => ProgramError::IllegalOwner,
+        not_a_review: |c| c.accounts[4].data[0] = MovieAccountState::ANONYMOUS_DISCRIMINATOR
+            => ReviewError::InvalidDiscriminator,
+        review_of_other_movie: |c| c.accounts[4].data = review_data(&Pubkey::new_unique())
+            => ReviewError::InvalidPDA,
+        incomplete: |c| update_movie_stats(&mut c, |stats| stats.review_count = 3)
+            => ReviewError::IncompleteRecompute,
+    }
+
+    index_movie_review => valid_index_movie_review {
+        non_signer: |c| c.accounts[0].is_signer = false
+            => ProgramError::MissingRequiredSignature,
+        unregistered_movie: |c| c.accounts[1].owner = Pubkey::new_unique()
+            => ProgramError::IllegalOwner,
+        wrong_owner: |c| c.accounts[2].owner = Pubkey::new_unique()
+            => ProgramError::IllegalOwner,
+        not_a_review: |c| c.accounts[2].data[0] = MovieAccountState::ANONYMOUS_DISCRIMINATOR
+            => ReviewError::InvalidDiscriminator,
+        review_of_other_movie: |c| c.accounts[2].data = review_data(&Pubkey::new_unique())
+            => ReviewError::InvalidPDA,
+        off_the_scale: |c| set_rating_scale(&mut c, 3, 0, 3)
+            => ReviewError::InvalidRating,
+        wrong_index_pda: |c| c.accounts[4].key = Pubkey::new_unique()
+            => ReviewError::InvalidPDA,
+        not_a_rating_index: |c| c.accounts[4].data[0] = MovieStats::DISCRIMINATOR
+            => ReviewError::InvalidDiscriminator,
+    }
+
+    reply_to_comment => valid_reply_to_comment {
+        non_signer: |c| c.accounts[0].is_signer = false
+            => ProgramError::MissingRequiredSignature,
+        muted: |c| c.accounts[6].owner = c.program_id
+            => ReviewError::Muted,
+        parent_wrong_owner: |c| c.accounts[7].owner = Pubkey::new_unique()
+            => ProgramError::IllegalOwner,
+        parent_closed: |c| c.accounts[7].data[..8].fill(0xff)
+            => CommonError::AccountClosed,
+        parent_on_other_review: |c| c.accounts[7].data =
+            parent_comment_data(&Pubkey::new_unique(), CommentPath::default())
+            => ReviewError::InvalidParentComment,
+        too_deep: |c| {
+            let review = c.accounts[1].key;
+            c.accounts[7].data = parent_comment_data(&review, CommentPath {
+                depth: MAX_COMMENT_DEPTH as u8,
+                ancestors: [0; MAX_COMMENT_DEPTH],
+            });
+        } => ReviewError::ThreadTooDeep,
+    }
+
+    set_comment_settings => valid_set_comment_settings {
+        non_signer: |c| c.accounts[0].is_signer = false
+            => ProgramError::MissingRequiredSignature,
+        wrong_owner: |c| c.accounts[1].owner = Pubkey::new_unique()
+            => ProgramError::IllegalOwner,
+        not_reviewer: |c| c.accounts[0].key = Pubkey::new_unique()
+            => CommonError::Unauthorized,
+        session_of_other_key: |c| {
+            let reviewer = c.accounts[0].key;
+            c.accounts[0].key = Pubkey::new_unique();
+            c.accounts.push(session_account(&c.program_id, &reviewer, &Pubkey::new_unique()));
+        } => CommonError::Unauthorized,
+        anonymous_review: |c| c.accounts[1].data[0] = MovieAccountState::ANONYMOUS_DISCRIMINATOR
+            => ReviewError::InvalidDiscriminator,
+        no_room_for_settings: |c| set_stored_description(
+            &mut c,
+            &"x".repeat(
+                MovieAccountState::MAX_ACCOUNT_SIZE
+                    - MovieAccountState::get_account_size(TITLE, "")
+                    + 1,
+            ),
+            BodyFormat::Plain,
+        ) => ReviewError::InvalidDataLength,
+    }
+
+    follow_reviewer => valid_follow_reviewer {
+        non_signer: |c| c.accounts[0].is_signer = false
+            => ProgramError::MissingRequiredSignature,
+        wrong_pda: |c| c.accounts[1].key = Pubkey::new_unique()
+            => ReviewError::InvalidPDA,
+        already_following: |c| c.accounts[1].owner = c.program_id
+            => ProgramError::AccountAlreadyInitialized,
+    }
+
+    unfollow_reviewer => valid_unfollow_reviewer {
+        non_signer: |c| c.accounts[0].is_signer = false
+            => ProgramError::MissingRequiredSignature,
+        wrong_owner: |c| c.accounts[1].owner = Pubkey::new_unique()
+            => ProgramError::IllegalOwner,
+        not_follower: |c| c.accounts[0].key = Pubkey::new_unique()
+            => CommonError::Unauthorized,
+        closed: |c| c.accounts[1].data[..8].fill(0xff)
+            => CommonError::AccountClosed,
+    }
+
+    delete_review => valid_delete_review {
+        non_signer: |c| c.accounts[0].is_signer = false
+            => ProgramError::MissingRequiredSignature,
+        not_author: |c| c.accounts[0].key = Pubkey::new_unique()
+            => CommonError::Unauthorized,
+        wrong_counter: |c| c.accounts[2].key = Pubkey::new_unique()
+            => ReviewError::InvalidPDA,
+        has_comments_without_cascade: |c| c.data = MovieInstruction::DeleteReview {
+            cascade: false,
+        }.pack() => ReviewError::ReviewHasComments,
+        missing_commenter: |c| { c.accounts.pop(); } => ProgramError::NotEnoughAccountKeys,
+        wrong_refund: |c| c.accounts[10].key = Pubkey::new_unique()
+            => ReviewError::WrongRefundRecipient,
+        comment_wrong_owner: |c| c.accounts[9].owner = Pubkey::new_unique()
+            => ProgramError::IllegalOwner,
+        closed_comment: |c| c.accounts[9].data[..8].fill(0xff)
+            => CommonError::AccountClosed,
+        comment_of_other_review: |c| c.accounts[9].data =
+            parent_comment_data(&Pubkey::new_unique(), CommentPath::default())
+            => ReviewError::InvalidPDA,
+        not_a_comment: |c| c.accounts[9].data = c.accounts[2].data.clone()
+            => ReviewError::InvalidDiscriminator,
+        page_of_other_review: |c| c.accounts[9].data = comment_page_data(Pubkey::new_unique())
+            => ReviewError::InvalidPDA,
+        empty_page: |c| {
+            let review = c.accounts[1].key;
+            c.accounts[9].key = Pubkey::find_program_address(
+                &[CommentPage::SEED.as_bytes(), review.as_ref(), &0u64.to_le_bytes()],
+                &c.program_id,
+            )
+            .0;
+            c.accounts[9].data = comment_page_data(review);
+        } => ReviewError::InvalidCommentPage,
+    }
+
+    repair_comment_address => valid_repair_comment_address {
+        non_signer: |c| c.accounts[0].is_signer = false
+            => ProgramError::MissingRequiredSignature,
+        wrong_owner: |c| c.accounts[2].owner = Pubkey::new_unique()
+            => ProgramError::IllegalOwner,
+        closed: |c| c.accounts[2].data[..8].fill(0xff)
+            => CommonError::AccountClosed,
+        not_a_comment: |c| c.accounts[2].data = review_data(&Pubkey::new_unique())
+            => ReviewError::InvalidDiscriminator,
+        comment_of_other_review: |c| c.accounts[2].data =
+            parent_comment_data(&Pubkey::new_unique(), CommentPath::default())
+            => ReviewError::InvalidPDA,
+        same_in_either_order: |c| set_comment_count(&mut c, 0)
+            => ReviewError::CommentNotMisplaced,
+        not_at_legacy_address: |c| c.accounts[2].key = Pubkey::new_unique()
+            => ReviewError::InvalidPDA,
+        wrong_target: |c| c.accounts[3].key = Pubkey::new_unique()
+            => ReviewError::InvalidPDA,
+        already_repaired: |c| c.accounts[3].owner = c.program_id
+            => ProgramError::AccountAlreadyInitialized,
+    }
+
+    propose_removal => valid_propose_removal {
+        non_signer: |c| c.accounts[0].is_signer = false
+            => ProgramError::MissingRequiredSignature,
+        not_authority: |c| c.accounts[0].key = Pubkey::new_unique()
+            => CommonError::Unauthorized,
+        uninitialized_config: |c| c.accounts[1] = config_account(&c.program_id)
+            => ReviewError::UninitializedAccount,
+        review_wrong_owner: |c| c.accounts[2].owner = Pubkey::new_unique()
+            => ProgramError::IllegalOwner,
+        anonymous_review: |c| c.accounts[2].data[0] = MovieAccountState::ANONYMOUS_DISCRIMINATOR
+            => ReviewError::InvalidDiscriminator,
+        wrong_pda: |c| c.accounts[3].key = Pubkey::new_unique()
+            => ReviewError::InvalidPDA,
+        already_proposed: |c| c.accounts[3].owner = c.program_id
+            => ReviewError::RemovalPending,
+    }
+
+    appeal_removal => valid_appeal_removal {
+        non_signer: |c| c.accounts[0].is_signer = false
+            => ProgramError::MissingRequiredSignature,
+        not_author: |c| c.accounts[0].key = Pubkey::new_unique()
+            => CommonError::Unauthorized,
+        wrong_owner: |c| c.accounts[2].owner = Pubkey::new_unique()
+            => ProgramError::IllegalOwner,
+        wrong_pda: |c| c.accounts[1].key = Pubkey::new_unique()
+            => ReviewError::InvalidPDA,
+        not_a_removal: |c| c.accounts[2].data[0] = Follow::DISCRIMINATOR
+            => ReviewError::InvalidDiscriminator,
+        already_appealed: |c| set_appeal(&mut c, 2, AppealStatus::Filed)
+            => ReviewError::AlreadyAppealed,
+        appeal_rejected: |c| set_appeal(&mut c, 2, AppealStatus::Rejected)
+            => ReviewError::AlreadyAppealed,
+    }
+
+    resolve_appeal => valid_resolve_appeal {
+        non_signer: |c| c.accounts[0].is_signer = false
+            => ProgramError::MissingRequiredSignature,
+        not_authority: |c| c.accounts[0].key = Pubkey::new_unique()
+            => CommonError::Unauthorized,
+        wrong_pda: |c| c.accounts[2].key = Pubkey::new_unique()
+            => ReviewError::InvalidPDA,
+        not_appealed: |c| set_appeal(&mut c, 3, AppealStatus::None)
+            => ReviewError::NoAppealFiled,
+        already_resolved: |c| set_appeal(&mut c, 3, AppealStatus::Rejected)
+            => ReviewError::NoAppealFiled,
+    }
+
+    finalize_removal => valid_finalize_removal {
+        non_signer: |c| c.accounts[0].is_signer = false
+            => ProgramError::MissingRequiredSignature,
+        not_authority: |c| c.accounts[0].key = Pubkey::new_unique()
+            => CommonError::Unauthorized,
+        missing_accounts: |c| c.accounts.truncate(9)
+            => ProgramError::NotEnoughAccountKeys,
+        wrong_pda: |c| c.accounts[1].key = Pubkey::new_unique()
+            => ReviewError::InvalidPDA,
+        appeal_pending: |c| set_appeal(&mut c, 1, AppealStatus::Filed)
+            => ReviewError::AppealPending,
+        wrong_refund: |c| c.accounts[3].key = Pubkey::new_unique()
+            => ReviewError::WrongRefundRecipient,
+    }
+
+    update_body_hash => valid_update_body_hash {
+        non_signer: |c| c.accounts[0].is_signer = false
+            => ProgramError::MissingRequiredSignature,
+        not_reviewer: |c| c.accounts[0].key = Pubkey::new_unique()
+            => CommonError::Unauthorized,
+        insecure_uri: |c| set_body_uri(&mut c, "http://example.com/review.md")
+            => ReviewError::InvalidBodyUri,
+        bare_scheme: |c| set_body_uri(&mut c, "ar://")
+            => ReviewError::InvalidBodyUri,
+        whitespace_uri: |c| set_body_uri(&mut c, "https://example.com/my review.md")
+            => ReviewError::InvalidBodyUri,
+        long_uri: |c| set_body_uri(&mut c, &format!("https://{}", "a".repeat(MovieComment::MAX_MEDIA_URI_LEN)))
+            => ReviewError::InvalidBodyUri,
+        wrong_pda: |c| c.accounts[2].key = Pubkey::new_unique()
+            => ReviewError::InvalidPDA,
+        not_a_commitment: |c| c.accounts[2].data[0] = PendingRemoval::DISCRIMINATOR
+            => ReviewError::InvalidDiscriminator,
+    }
+
+    record_fingerprint => valid_record_fingerprint {
+        non_signer: |c| c.accounts[0].is_signer = false
+            => ProgramError::MissingRequiredSignature,
+        wrong_owner: |c| c.accounts[1].owner = Pubkey::new_unique()
+            => ProgramError::IllegalOwner,
+        anonymous_review: |c| c.accounts[1].data[0] = MovieAccountState::ANONYMOUS_DISCRIMINATOR
+            => ReviewError::InvalidDiscriminator,
+        wrong_pda: |c| c.accounts[2].key = Pubkey::new_unique()
+            => ReviewError::InvalidPDA,
+        not_a_fingerprint: |c| c.accounts[2].data[0] = BodyCommitment::DISCRIMINATOR
+            => ReviewError::InvalidDiscriminator,
+    }
+
+    challenge_duplicate => valid_challenge_duplicate {
+        non_signer: |c| c.accounts[0].is_signer = false
+            => ProgramError::MissingRequiredSignature,
+        same_review: |c| c.accounts[3].key = c.accounts[1].key
+            => ReviewError::NotDuplicate,
+        unrecorded: |c| c.accounts[4].owner = system_program::ID
+            => ProgramError::IllegalOwner,
+        wrong_fingerprint: |c| c.accounts[2].key = c.accounts[4].key
+            => ReviewError::InvalidPDA,
+        stale: |c| c.accounts[1].data = formatted_review_data(
+            &Pubkey::new_unique(),
+            "Still holds up, mostly",
+            BodyFormat::Plain,
+        ) => ReviewError::StaleFingerprint,
+        too_far_apart: |c| {
+            let description = "A slow, muddled remake nobody asked for";
+            let (original, author) = (c.accounts[3].key, Pubkey::new_unique());
+            c.accounts[3].data = formatted_review_data(&author, description, BodyFormat::Plain);
+            c.accounts[4] = fingerprint_account(&c.program_id, &original, description);
+        } => ReviewError::NotDuplicate,
+        wrong_challenge: |c| c.accounts[5].key = Pubkey::new_unique()
+            => ReviewError::InvalidPDA,
+        already_flagged: |c| c.accounts[5].owner = c.program_id
+            => ProgramError::AccountAlreadyInitialized,
+    }
+
+    vote_on_comment => valid_vote_on_comment {
+        non_signer: |c| c.accounts[0].is_signer = false
+            => ProgramError::MissingRequiredSignature,
+        not_a_comment: |c| c.accounts[1].data = review_data(&Pubkey::new_unique())
+            => ReviewError::InvalidDiscriminator,
+        closed: |c| c.accounts[1].data[..8].fill(0xff)
+            => CommonError::AccountClosed,
+        own_comment: |c| {
+            let comment: MovieComment = try_from_slice_unchecked(&c.accounts[1].data).unwrap();
+            c.accounts[0].key = comment.commenter;
+        } => ReviewError::SelfVote,
+        wrong_vote: |c| c.accounts[2].key = Pubkey::new_unique()
+            => ReviewError::InvalidPDA,
+        wrong_karma: |c| c.accounts[3].key = Pubkey::new_unique()
+            => ReviewError::InvalidPDA,
+        already_liked: |c| set_comment_vote(&mut c, CommentVoteKind::Like)
+            => ReviewError::AlreadyVoted,
+    }
+}
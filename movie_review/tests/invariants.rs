@@ -0,0 +1,708 @@
+//! Property-based model checks for the account size math, the comment
+//! counter, the rating index, the title filter, the event encoding, the
+//! account specs, the instruction discriminators and the review fingerprints.
+
+use std::{
+    cmp::Reverse,
+    collections::{HashSet, VecDeque},
+};
+
+use movie_review::{
+    account_spec::required_accounts,
+    client::{instructions, pda::find_comment_address},
+    event::{decode_event, ReviewEvent, RECIPIENT_OFFSET},
+    fingerprint,
+    instruction::{MovieInstruction, DISCRIMINATOR_NAMESPACE, INSTRUCTION_DISCRIMINATORS},
+    state::{
+        BodyFormat, CoAuthor, CommentKarma, CommentPage, CommentPath, CommentSettings,
+        CommentVoteKind, ContentLimits, GlobalStats, HotFeed, MovieAccountState, MovieComment,
+        MovieCommentCounter, PagedComment, ProgramConfig, RatingIndex, RatingScale,
+        ReviewCoAuthors, ReviewSnapshot, ReviewerProfile, SnapshotEntry, TitleFilter,
+        MAX_COMMENT_DEPTH,
+    },
+};
+use proptest::prelude::*;
+use solana_program::{
+    hash::hash, instruction::Instruction, program::MAX_RETURN_DATA, pubkey::Pubkey,
+};
+
+fn review(title: String, description: String) -> MovieAccountState {
+    MovieAccountState {
+        discriminator: MovieAccountState::DISCRIMINATOR,
+        is_initialized: true,
+        reviewer: Pubkey::new_unique(),
+        rating: 3,
+        title,
+        description,
+        body_format: BodyFormat::Plain,
+        comment_settings: None,
+    }
+}
+
+fn comment(text: String, count: u64, media_uri: Option<String>) -> MovieComment {
+    MovieComment {
+        discriminator: MovieComment::DISCRIMINATOR,
+        is_initialized: true,
+        reviewer: Pubkey::new_unique(),
+        commenter: Pubkey::new_unique(),
+        comment: text,
+        count,
+        media_uri,
+        path: CommentPath::default(),
+    }
+}
+
+proptest! {
+    #[test]
+    fn review_size_matches_serialized_len(title in ".{0,64}", description in ".{0,1200}") {
+        let expected = MovieAccountState::get_account_size(&title, &description);
+        let serialized = borsh::to_vec(&review(title, description)).unwrap();
+        // Without comment settings only their reserved room is left over
+        prop_assert_eq!(serialized.len() + CommentSettings::MAX_LEN, expected);
+    }
+
+    /// Anything that passes the length check fits the fixed allocation.
+    #[test]
+    fn accepted_review_fits_allocation(title in ".{0,64}", description in ".{0,1200}") {
+        prop_assume!(
+            MovieAccountState::get_account_size(&title, &description)
+                <= MovieAccountState::MAX_ACCOUNT_SIZE
+        );
+        let serialized = borsh::to_vec(&review(title, description)).unwrap();
+        prop_assert!(serialized.len() <= MovieAccountState::MAX_ACCOUNT_SIZE);
+    }
+
+    #[test]
+    fn comment_size_matches_serialized_len(
+        text in ".{0,1200}",
+        count: u64,
+        media_uri in proptest::option::of(".{0,200}"),
+    ) {
+        let expected = MovieComment::get_account_size(&text, media_uri.as_deref());
+        let serialized = borsh::to_vec(&comment(text, count, media_uri)).unwrap();
+        prop_assert_eq!(serialized.len(), expected);
+        if expected <= MovieComment::MAX_ACCOUNT_SIZE {
+            prop_assert!(serialized.len() <= MovieComment::MAX_ACCOUNT_SIZE);
+        }
+    }
+
+    #[test]
+    fn reply_paths_end_with_parent_and_stop_at_max_depth(indices in proptest::collection::vec(any::<u64>(), 0..8)) {
+        let mut path = CommentPath::default();
+        for (depth, &index) in indices.iter().enumerate() {
+            match path.child(index) {
+                Some(child) => {
+                    prop_assert!(depth < MAX_COMMENT_DEPTH);
+                    prop_assert_eq!(child.ancestors(), &indices[..=depth]);
+                    prop_assert_eq!(child.parent(), Some(index));
+                    path = child;
+                }
+                None => prop_assert_eq!(depth, MAX_COMMENT_DEPTH),
+            }
+            if depth == MAX_COMMENT_DEPTH {
+                break;
+            }
+        }
+        prop_assert_eq!(borsh::to_vec(&path).unwrap().len(), CommentPath::LEN);
+    }
+
+    #[test]
+    fn counter_size_matches_serialized_len(counter: u64) {
+        let serialized = borsh::to_vec(&MovieCommentCounter {
+            discriminator: MovieCommentCounter::DISCRIMINATOR,
+            is_initialized: true,
+            total_comments_created: counter,
+            page: 0,
+            active_comments: counter,
+        })
+        .unwrap();
+        prop_assert_eq!(serialized.len(), MovieCommentCounter::get_account_size());
+    }
+
+    #[test]
+    fn snapshot_fits_account_and_ranks_top_reviews(
+        entries in prop::collection::vec((1u16..=5, any::<u64>()), 0..40),
+        slot: u64,
+    ) {
+        let entries: Vec<SnapshotEntry> = entries
+            .into_iter()
+            .map(|(rating, comments)| SnapshotEntry {
+                review: Pubkey::new_unique(),
+                rating,
+                comments,
+            })
+            .collect();
+        let snapshot = ReviewSnapshot::summarize(slot, slot, entries.clone());
+
+        prop_assert_eq!(snapshot.review_count, entries.len() as u64);
+        prop_assert!(snapshot.average_rating <= 500);
+        prop_assert!(snapshot.top_reviews.len() <= ReviewSnapshot::MAX_TOP_REVIEWS);
+        let ranked = snapshot.top_reviews.windows(2).all(|pair| {
+            (pair[0].rating, pair[0].comments) >= (pair[1].rating, pair[1].comments)
+        });
+        prop_assert!(ranked, "top reviews are not ranked");
+        // No review left out ranks above the last one kept
+        if let Some(last) = snapshot.top_reviews.last() {
+            prop_assert!(entries
+                .iter()
+                .filter(|entry| !snapshot.top_reviews.contains(entry))
+                .all(|entry| (entry.rating, entry.comments) <= (last.rating, last.comments)));
+        }
+        prop_assert!(borsh::to_vec(&snapshot).unwrap().len() <= ReviewSnapshot::get_account_size());
+    }
+
+    #[test]
+    fn global_stats_never_wrap(tips in prop::collection::vec(any::<u64>(), 0..8)) {
+        let mut stats = GlobalStats::default();
+        let mut expected = Some(0u64);
+        for &amount in &tips {
+            expected = expected.and_then(|total| total.checked_add(amount));
+            let recorded = stats.record_tip(amount);
+            prop_assert_eq!(recorded.is_some(), expected.is_some());
+            if expected.is_none() {
+                break;
+            }
+        }
+        if let Some(total) = expected {
+            prop_assert_eq!(stats.total_tips, total);
+        }
+        prop_assert_eq!(
+            borsh::to_vec(&stats).unwrap().len(),
+            GlobalStats::get_account_size()
+        );
+    }
+
+    #[test]
+    fn program_config_fits_account(max_review_size: u32, max_comment_size: u32) {
+        let config = ProgramConfig {
+            limits: ContentLimits {
+                max_review_size,
+                max_comment_size,
+            },
+            ..ProgramConfig::default()
+        };
+        prop_assert_eq!(
+            borsh::to_vec(&config).unwrap().len(),
+            ProgramConfig::get_account_size()
+        );
+    }
+
+    #[test]
+    fn ratings_histogram_tracks_reviews(
+        ops in prop::collection::vec((any::<bool>(), any::<usize>(), 1u16..=5), 0..32),
+    ) {
+        let mut profile = ReviewerProfile {
+            discriminator: ReviewerProfile::DISCRIMINATOR,
+            is_initialized: true,
+            reviewer: Pubkey::new_unique(),
+            review_count: 0,
+            ratings: [0; 5],
+            relay_nonce: 0,
+            last_active_epoch: 0,
+            streak: 0,
+            authority_program: Pubkey::default(),
+            authority: Pubkey::default(),
+        };
+        let scale = RatingScale::default();
+        let mut reviews: Vec<u16> = Vec::new();
+        for (add, index, rating) in ops {
+            if add || reviews.is_empty() {
+                prop_assert!(profile.record_rating(rating, &scale).is_some());
+                reviews.push(rating);
+            } else {
+                let i = index % reviews.len();
+                let review = &mut reviews[i];
+                profile.change_rating(*review, rating, &scale);
+                *review = rating;
+            }
+        }
+        for (i, &count) in profile.ratings.iter().enumerate() {
+            let expected = reviews.iter().filter(|&&r| usize::from(r) == i + 1).count();
+            prop_assert_eq!(count as usize, expected);
+        }
+        prop_assert_eq!(
+            borsh::to_vec(&profile).unwrap().len(),
+            ReviewerProfile::get_account_size()
+        );
+    }
+
+    #[test]
+    fn streak_counts_trailing_consecutive_epochs(
+        gaps in prop::collection::vec(0u64..3, 1..32),
+    ) {
+        let mut profile = ReviewerProfile {
+            discriminator: ReviewerProfile::DISCRIMINATOR,
+            is_initialized: true,
+            reviewer: Pubkey::new_unique(),
+            review_count: 0,
+            ratings: [0; 5],
+            relay_nonce: 0,
+            last_active_epoch: 0,
+            streak: 0,
+            authority_program: Pubkey::default(),
+            authority: Pubkey::default(),
+        };
+        let mut epoch = 0;
+        let mut run = 0;
+        for gap in gaps {
+            epoch += gap;
+            run = match gap {
+                0 if run > 0 => run,
+                1 => run + 1,
+                _ => 1,
+            };
+            profile.record_activity(epoch);
+            prop_assert_eq!(profile.streak, run);
+        }
+        prop_assert_eq!(profile.current_streak(epoch + 1), run);
+        prop_assert_eq!(profile.current_streak(epoch + 2), 0);
+    }
+
+    #[test]
+    fn co_author_split_conserves_amount(
+        shares in prop::collection::vec((1u16..=3_333, any::<bool>()), 0..=3),
+        amount: u64,
+    ) {
+        let co_authors = ReviewCoAuthors {
+            discriminator: ReviewCoAuthors::DISCRIMINATOR,
+            is_initialized: true,
+            review: Pubkey::new_unique(),
+            co_authors: shares
+                .iter()
+                .map(|&(share_bps, accepted)| CoAuthor {
+                    co_author: Pubkey::new_unique(),
+                    share_bps,
+                    accepted,
+                })
+                .collect(),
+        };
+        let listing: Vec<_> = co_authors
+            .co_authors
+            .iter()
+            .map(|co_author| (co_author.co_author, co_author.share_bps))
+            .collect();
+        prop_assert!(ReviewCoAuthors::is_valid_listing(&Pubkey::new_unique(), &listing));
+
+        let (rest, parts) = co_authors.split(amount).unwrap();
+        prop_assert_eq!(parts.len(), shares.iter().filter(|(_, accepted)| *accepted).count());
+        let paid: u128 = parts.iter().map(|(_, part)| *part as u128).sum();
+        prop_assert_eq!(rest as u128 + paid, amount as u128);
+        prop_assert!(borsh::to_vec(&co_authors).unwrap().len() <= ReviewCoAuthors::get_account_size());
+    }
+
+    #[test]
+    fn rating_scale_buckets_cover_the_range(
+        min: u16,
+        span in 1u16..,
+        offset: u16,
+    ) {
+        let max = min.saturating_add(span);
+        prop_assume!(min < max);
+        let scale = RatingScale { min, max, decimals: 0 };
+        let rating = min + offset % (max - min);
+
+        prop_assert_eq!(scale.bucket(min, 5), Some(0));
+        // Scales with fewer than five values leave some buckets unused
+        let top = scale.bucket(max, 5).unwrap();
+        prop_assert!(top == 4 || (max - min < 4 && top < 4));
+        prop_assert!(scale.bucket(rating, 5) <= scale.bucket(rating + 1, 5));
+        if max < u16::MAX {
+            prop_assert_eq!(scale.bucket(max + 1, 5), None);
+        }
+    }
+
+    #[test]
+    fn rating_index_files_reviews_by_bucket(
+        ops in prop::collection::vec(
+            (0..RatingIndex::BUCKETS, any::<bool>(), any::<prop::sample::Index>()),
+            0..32,
+        ),
+    ) {
+        let mut data = borsh::to_vec(&RatingIndex {
+            discriminator: RatingIndex::DISCRIMINATOR,
+            is_initialized: true,
+            movie: Pubkey::new_unique(),
+            counts: [0; RatingIndex::BUCKETS],
+            reviews: Vec::new(),
+        })
+        .unwrap();
+        let mut model = vec![Vec::new(); RatingIndex::BUCKETS];
+        for (bucket, insert, pick) in ops {
+            let filed: Vec<Pubkey> = model.concat();
+            if insert || filed.is_empty() {
+                // The processor grows the account by a key before filing
+                let review = Pubkey::new_unique();
+                data.resize(data.len() + 32, 0);
+                RatingIndex::insert(&mut data, bucket, &review).unwrap();
+                model[bucket].push(review);
+            } else {
+                let review = filed[pick.index(filed.len())];
+                let from = model.iter().position(|reviews| reviews.contains(&review));
+                prop_assert_eq!(RatingIndex::remove(&mut data, &review).unwrap(), from);
+                data.truncate(data.len() - 32);
+                model[from.unwrap()].retain(|filed| *filed != review);
+            }
+        }
+
+        prop_assert_eq!(RatingIndex::remove(&mut data.clone(), &Pubkey::new_unique()).unwrap(), None);
+        let index: RatingIndex = borsh::from_slice(&data).unwrap();
+        prop_assert_eq!(index.reviews.len(), model.concat().len());
+        for (bucket, expected) in model.iter_mut().enumerate() {
+            let header = &data[..RatingIndex::REVIEWS_OFFSET];
+            let range = RatingIndex::bucket_range(header, bucket).unwrap();
+            let mut filed: Vec<Pubkey> = data[range]
+                .chunks_exact(32)
+                .map(|key| Pubkey::try_from(key).unwrap())
+                .collect();
+            filed.sort();
+            expected.sort();
+            prop_assert_eq!(&filed, expected);
+        }
+    }
+
+    #[test]
+    fn title_filter_never_misses_a_respelled_title(
+        titles in prop::collection::vec("[A-Za-z0-9 ]{1,24}", 1..16),
+        separator in "[ ,.:!-]{1,3}",
+    ) {
+        let mut filter = TitleFilter {
+            discriminator: TitleFilter::DISCRIMINATOR,
+            is_initialized: true,
+            reviewer: Pubkey::new_unique(),
+            titles: 0,
+            bits: [0; TitleFilter::WORDS],
+        };
+        for title in &titles {
+            filter.insert(title);
+        }
+
+        prop_assert_eq!(filter.titles as usize, titles.len());
+        prop_assert_eq!(borsh::to_vec(&filter).unwrap().len(), TitleFilter::get_account_size());
+        for title in &titles {
+            let words: Vec<&str> = title.split(' ').collect();
+            let respelled = format!("The{separator}{}", words.join(&separator).to_uppercase());
+            prop_assert!(filter.may_contain(title));
+            prop_assert!(filter.may_contain(&respelled), "{}", respelled);
+        }
+    }
+
+    #[test]
+    fn full_comment_page_fits_account(
+        comments in prop::collection::vec(
+            ".{0,280}",
+            CommentPage::COMMENTS_PER_PAGE,
+        ),
+        page: u64,
+    ) {
+        let page = CommentPage {
+            discriminator: CommentPage::DISCRIMINATOR,
+            is_initialized: true,
+            review: Pubkey::new_unique(),
+            page,
+            comments: comments
+                .into_iter()
+                .enumerate()
+                .map(|(index, comment)| {
+                    // Multi-byte characters can push the regex's output over
+                    // the byte limit the processor enforces
+                    let mut end = comment.len().min(CommentPage::MAX_COMMENT_LEN);
+                    while !comment.is_char_boundary(end) {
+                        end -= 1;
+                    }
+                    PagedComment {
+                        commenter: Pubkey::new_unique(),
+                        index: index as u64,
+                        comment: comment[..end].to_string(),
+                    }
+                })
+                .collect(),
+        };
+        prop_assert!(page.is_full());
+        prop_assert!(borsh::to_vec(&page).unwrap().len() <= CommentPage::ACCOUNT_SIZE);
+    }
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(32))]
+
+    /// Model of AddComment and a cascading DeleteReview: each successful add
+    /// initializes the comment PDA at the current index, and each delete
+    /// closes the oldest open comment. `0` is a successful add, `1` one that
+    /// fails validation before the counter is touched and `2` a delete.
+    /// `total_comments_created` must always equal the number of distinct
+    /// comment PDAs ever initialized, so deletions never lead to a reused
+    /// PDA, and `active_comments` the number still open.
+    #[test]
+    fn counter_equals_initialized_comments(ops in prop::collection::vec(0u8..3, 0..24)) {
+        let program_id = Pubkey::new_unique();
+        let review = Pubkey::new_unique();
+        let mut counter = MovieCommentCounter {
+            discriminator: MovieCommentCounter::DISCRIMINATOR,
+            is_initialized: true,
+            total_comments_created: 0,
+            page: 0,
+            active_comments: 0,
+        };
+        let mut initialized = HashSet::new();
+        let mut open = VecDeque::new();
+
+        for op in ops {
+            match op {
+                0 => {
+                    let index = counter.next_comment_index().unwrap();
+                    let (address, _) = find_comment_address(&program_id, &review, index);
+                    prop_assert!(initialized.insert(address), "comment PDA reused at index {}", index);
+                    open.push_back(address);
+                }
+                2 if open.pop_front().is_some() => counter.remove_comments(1),
+                _ => continue,
+            }
+            prop_assert_eq!(counter.total_comments_created as usize, initialized.len());
+            prop_assert_eq!(counter.active_comments as usize, open.len());
+        }
+        prop_assert_eq!(counter.total_comments_created as usize, initialized.len());
+        prop_assert_eq!(counter.active_comments as usize, open.len());
+    }
+
+    #[test]
+    fn karma_tracks_votes(
+        ops in prop::collection::vec((any::<usize>(), any::<bool>()), 0..32),
+        threshold in -4i64..4,
+    ) {
+        let mut karma = CommentKarma {
+            discriminator: CommentKarma::DISCRIMINATOR,
+            is_initialized: true,
+            comment: Pubkey::new_unique(),
+            likes: 0,
+            reports: 0,
+            hidden: false,
+        };
+        let config = ProgramConfig { hide_threshold: threshold, ..ProgramConfig::default() };
+        // Each voter's current vote, as `VoteOnComment` keeps it
+        let mut votes: Vec<Option<CommentVoteKind>> = vec![None; 8];
+        for (voter, report) in ops {
+            let kind = if report { CommentVoteKind::Report } else { CommentVoteKind::Like };
+            let vote = &mut votes[voter % 8];
+            match *vote {
+                Some(previous) if previous == kind => continue,
+                Some(previous) => karma.count(previous, false).unwrap(),
+                None => {}
+            }
+            karma.count(kind, true).unwrap();
+            *vote = Some(kind);
+        }
+        let likes = votes.iter().filter(|v| **v == Some(CommentVoteKind::Like)).count() as i64;
+        let reports = votes.iter().filter(|v| **v == Some(CommentVoteKind::Report)).count() as i64;
+        prop_assert_eq!(karma.score(), likes - reports);
+        prop_assert_eq!(config.hides(karma.score()), threshold != 0 && likes - reports < threshold);
+    }
+
+    #[test]
+    fn hot_feed_keeps_the_most_recently_active_reviews(
+        activity in prop::collection::vec((0usize..48, 1u64..10), 1..128),
+    ) {
+        let reviews: Vec<Pubkey> = (0..48).map(|_| Pubkey::new_unique()).collect();
+        let mut feed = HotFeed::default();
+        let mut last_active = vec![None; reviews.len()];
+        for (slot, &(review, weight)) in activity.iter().enumerate() {
+            feed.record(&reviews[review], weight, slot as u64 + 1);
+            last_active[review] = Some(slot);
+        }
+        let mut recent: Vec<_> = last_active
+            .iter()
+            .enumerate()
+            .filter_map(|(review, slot)| slot.map(|slot| (slot, reviews[review])))
+            .collect();
+        recent.sort_by_key(|&(slot, _)| Reverse(slot));
+        recent.truncate(HotFeed::ENTRIES);
+
+        let ranked = feed.ranked(activity.len() as u64);
+        prop_assert_eq!(ranked.len(), recent.len());
+        for (_, review) in recent {
+            prop_assert!(ranked.iter().any(|(key, _)| *key == review));
+        }
+        prop_assert!(ranked.windows(2).all(|pair| pair[0].1 >= pair[1].1));
+    }
+
+    #[test]
+    fn counter_never_wraps(start in (u64::MAX - 4)..=u64::MAX) {
+        let mut counter = MovieCommentCounter {
+            discriminator: MovieCommentCounter::DISCRIMINATOR,
+            is_initialized: true,
+            total_comments_created: start,
+            page: 0,
+            active_comments: 0,
+        };
+        while counter.total_comments_created < u64::MAX {
+            let before = counter.total_comments_created;
+            prop_assert_eq!(counter.next_comment_index().unwrap(), before);
+        }
+        prop_assert!(counter.next_comment_index().is_err());
+        prop_assert_eq!(counter.total_comments_created, u64::MAX);
+    }
+
+    #[test]
+    fn notification_recipient_sits_at_fixed_offset(
+        recipient: [u8; 32],
+        other: [u8; 32],
+        amount: u64,
+        variant in 0..6u8,
+    ) {
+        let (recipient, other) = (Pubkey::new_from_array(recipient), Pubkey::new_from_array(other));
+        let event = match variant {
+            0 => ReviewEvent::ReviewCommented { recipient, review: other, comment: other, commenter: other },
+            1 => ReviewEvent::CommentReplied { recipient, comment: other, reply: other, replier: other },
+            2 => ReviewEvent::TipReceived { recipient, review: other, tipper: other, amount },
+            3 => ReviewEvent::CommentVisibilityChanged { recipient, comment: other, hidden: true, score: amount as i64 },
+            4 => ReviewEvent::PossibleDuplicateTitle { recipient, review: other, title: amount.to_string() },
+            _ => ReviewEvent::Mentioned { recipient, comment: other, author: other },
+        };
+        let data = borsh::to_vec(&event).unwrap();
+        prop_assert_eq!(&data[RECIPIENT_OFFSET..RECIPIENT_OFFSET + 32], recipient.as_ref());
+        prop_assert_eq!(event.recipient(), Some(&recipient));
+        prop_assert_eq!(decode_event(&data), Some(event));
+    }
+
+    #[test]
+    fn mentions_are_found_in_order_without_repeats(
+        keys in proptest::collection::vec(any::<[u8; 32]>(), 0..8),
+        filler in "[ ,.!?]{0,8}",
+    ) {
+        let keys: Vec<Pubkey> = keys.into_iter().map(Pubkey::new_from_array).collect();
+        let comment: String = keys
+            .iter()
+            .chain(keys.first())
+            .map(|key| format!("{filler}@{key}{filler}"))
+            .collect();
+        let mut expected = Vec::new();
+        for key in &keys {
+            if !expected.contains(key) && expected.len() < MovieComment::MAX_MENTIONS {
+                expected.push(*key);
+            }
+        }
+        prop_assert_eq!(MovieComment::mentions(&comment), expected);
+    }
+
+    #[test]
+    fn required_accounts_fit_return_data(tag: u8) {
+        match required_accounts(tag) {
+            Some(required) => {
+                prop_assert!((tag as usize) < INSTRUCTION_DISCRIMINATORS.len());
+                prop_assert!(borsh::to_vec(&required).unwrap().len() <= MAX_RETURN_DATA);
+            }
+            None => prop_assert!(tag as usize >= INSTRUCTION_DISCRIMINATORS.len()),
+        }
+    }
+
+    #[test]
+    fn required_accounts_match_client(
+        program_id: [u8; 32],
+        user: [u8; 32],
+        other: [u8; 32],
+        which in 0usize..21,
+    ) {
+        let (program_id, user, other) = (
+            Pubkey::new_from_array(program_id),
+            Pubkey::new_from_array(user),
+            Pubkey::new_from_array(other),
+        );
+        let instruction: Instruction = match which {
+            0 => instructions::add_movie_review(&program_id, &user, "Dune", 5, "Sand"),
+            1 => instructions::add_comment(&program_id, &user, &other, &other, 3, "Hi", None),
+            2 => instructions::reply_to_comment(&program_id, &user, &other, &other, 3, 1, "Hi", None),
+            3 => instructions::add_review_for_movie(&program_id, &user, "Dune", 5, "Sand"),
+            4 => instructions::mint_review_nft(&program_id, &user, &other, &other, "https://a"),
+            5 => instructions::record_tip(&program_id, &other, &user, &other, 1),
+            6 => instructions::delete_review(&program_id, &user, &other, "Dune", false, &[]),
+            7 => instructions::add_relayed_review(
+                &program_id,
+                &other,
+                &user,
+                &instructions::RelayedReviewArgs {
+                    title: "Dune",
+                    rating: 5,
+                    description: "Sand",
+                    nonce: 0,
+                    expires_slot: 1,
+                },
+            ),
+            8 => instructions::create_session(&program_id, &user, &other, 100, 1),
+            9 => instructions::challenge_duplicate(&program_id, &user, &other, &program_id),
+            10 => instructions::vote_on_comment(&program_id, &user, &other, CommentVoteKind::Report),
+            11 => instructions::update_review_for_movie(&program_id, &user, "Dune", 5, "Sand"),
+            12 => instructions::set_co_authors(&program_id, &user, &other, vec![(program_id, 100)]),
+            13 => instructions::accept_co_authorship(&program_id, &user, &other, 100),
+            14 => instructions::set_critic_authority(&program_id, &user, &other, &program_id),
+            15 => instructions::submit_translation(&program_id, &user, &other, "fr", "Salut"),
+            16 => instructions::approve_translation(&program_id, &user, &other, "fr", &program_id),
+            17 => instructions::set_movie_release_date(&program_id, &user, "Dune", 1),
+            18 => instructions::index_movie_review(&program_id, &user, &other, "Dune"),
+            19 => instructions::record_sol_tip(&program_id, &other, &user, &other, 1),
+            _ => instructions::get_required_accounts(&program_id, 0),
+        };
+        let required = required_accounts(instruction.data[0]).unwrap();
+        // Proofs such as the reviewer pass are passed after the listed accounts
+        prop_assert!(instruction.accounts.len() >= required.accounts.len());
+        for (meta, spec) in instruction.accounts.iter().zip(&required.accounts) {
+            prop_assert_eq!(meta.is_signer, spec.is_signer, "{}", spec.name);
+            prop_assert_eq!(meta.is_writable, spec.is_writable, "{}", spec.name);
+            if let Some(address) = spec.address {
+                prop_assert_eq!(meta.pubkey, address, "{}", spec.name);
+            }
+        }
+    }
+
+    #[test]
+    fn discriminators_unpack_like_tags(
+        tag in 0usize..INSTRUCTION_DISCRIMINATORS.len(),
+        title in ".{0,32}",
+        rating: u16,
+    ) {
+        let (name, discriminator) = INSTRUCTION_DISCRIMINATORS[tag];
+        let preimage = format!("{DISCRIMINATOR_NAMESPACE}:{name}");
+        prop_assert_eq!(&hash(preimage.as_bytes()).to_bytes()[..8], discriminator.as_ref());
+        prop_assert_eq!(
+            INSTRUCTION_DISCRIMINATORS.iter().filter(|(_, known)| *known == discriminator).count(),
+            1
+        );
+
+        let instruction = MovieInstruction::AddMovieReviewV2 {
+            title,
+            rating,
+            description: String::new(),
+        };
+        let (unpacked, dry_run) =
+            MovieInstruction::unpack_with_dry_run(&instruction.pack_with_discriminator()).unwrap();
+        prop_assert!(!dry_run);
+        prop_assert_eq!(unpacked.pack(), instruction.pack());
+    }
+
+    #[test]
+    fn legacy_review_tags_keep_u8_ratings(
+        tag in 0u8..=1,
+        title in ".{0,32}",
+        rating: u8,
+        cut in 0usize..4,
+    ) {
+        let mut data = vec![tag];
+        borsh::to_writer(&mut data, &(&title, rating, "")).unwrap();
+        let unpacked = MovieInstruction::unpack(&data).unwrap();
+        prop_assert_eq!(unpacked.pack(), data.clone());
+
+        data.truncate(data.len() - 1 - cut);
+        prop_assert!(MovieInstruction::unpack(&data).is_err());
+    }
+
+    #[test]
+    fn fingerprint_ignores_case_and_punctuation(
+        words in proptest::collection::vec("[a-z0-9]{1,12}", 0..40),
+        separator in "[ ,.!?\n]{1,3}",
+    ) {
+        let plain = words.join(" ");
+        let shouted = words.join(&separator).to_uppercase();
+        prop_assert_eq!(fingerprint::simhash(&plain), fingerprint::simhash(&shouted));
+        if words.is_empty() {
+            prop_assert_eq!(fingerprint::simhash(&plain), 0);
+        }
+    }
+}
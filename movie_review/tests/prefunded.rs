@@ -0,0 +1,75 @@
+//! A review PDA that already holds lamports, e.g. from someone dusting the
+//! address ahead of time, must still be creatable.
+
+use movie_review::{
+    client::{
+        instructions::add_movie_review,
+        pda::{find_comment_counter_address, find_review_address},
+    },
+    decoder::{decode_account, MovieReviewAccount},
+    state::MovieAccountState,
+};
+use solana_program_test::{processor, ProgramTest};
+use solana_sdk::{
+    account::Account, native_token::LAMPORTS_PER_SOL, pubkey::Pubkey, signature::Keypair,
+    signer::Signer, system_program, transaction::Transaction,
+};
+
+const TITLE: &str = "The Matrix";
+
+#[tokio::test]
+async fn add_review_succeeds_on_prefunded_pdas() {
+    let program_id = Pubkey::new_unique();
+    let reviewer = Keypair::new();
+    let (review, _) = find_review_address(&program_id, &reviewer.pubkey(), TITLE);
+    let (counter, _) = find_comment_counter_address(&program_id, &review);
+
+    let mut program_test = ProgramTest::new(
+        "movie_review",
+        program_id,
+        processor!(movie_review::processor::process_instruction),
+    );
+    program_test.add_account(
+        reviewer.pubkey(),
+        Account::new(LAMPORTS_PER_SOL, 0, &system_program::ID),
+    );
+    // Dust below rent exemption on the review, more than enough on the counter
+    program_test.add_account(review, Account::new(1_000, 0, &system_program::ID));
+    program_test.add_account(
+        counter,
+        Account::new(LAMPORTS_PER_SOL, 0, &system_program::ID),
+    );
+
+    let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+    let transaction = Transaction::new_signed_with_payer(
+        &[add_movie_review(
+            &program_id,
+            &reviewer.pubkey(),
+            TITLE,
+            4,
+            "Still holds up",
+        )],
+        Some(&payer.pubkey()),
+        &[&payer, &reviewer],
+        recent_blockhash,
+    );
+    banks_client.process_transaction(transaction).await.unwrap();
+
+    let account = banks_client.get_account(review).await.unwrap().unwrap();
+    assert_eq!(account.owner, program_id);
+    assert_eq!(account.data.len(), MovieAccountState::MAX_ACCOUNT_SIZE);
+    match decode_account(&account.data).unwrap() {
+        MovieReviewAccount::Review(review) => {
+            assert_eq!(review.reviewer, reviewer.pubkey());
+            assert_eq!(review.title, TITLE);
+        }
+        _ => panic!("review PDA decoded to the wrong account type"),
+    }
+
+    let account = banks_client.get_account(counter).await.unwrap().unwrap();
+    assert_eq!(account.owner, program_id);
+    assert!(matches!(
+        decode_account(&account.data).unwrap(),
+        MovieReviewAccount::CommentCounter(counter) if counter.total_comments_created == 0
+    ));
+}
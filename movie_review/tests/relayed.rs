@@ -0,0 +1,170 @@
+//! The relayer of a relayed review paid its rent, so lamports above the rent
+//! exemption go back to them rather than to the reviewer.
+
+use movie_review::{
+    client::{
+        instructions::{
+            add_relayed_review, initialize_config, set_features, verify_reviewer_signature,
+            withdraw_excess_lamports, RelayedReviewArgs,
+        },
+        pda::{find_rent_payer_address, find_review_address},
+    },
+    decoder::{decode_account, MovieReviewAccount},
+    state::{MovieAccountState, ProgramConfig},
+};
+use solana_program_test::{processor, BanksClient, ProgramTest};
+use solana_sdk::{
+    account::Account,
+    hash::Hash,
+    instruction::{Instruction, InstructionError},
+    native_token::LAMPORTS_PER_SOL,
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    system_instruction, system_program,
+    transaction::{Transaction, TransactionError},
+};
+use soldev_program_errors::CommonError;
+
+const TITLE: &str = "Paprika";
+const EXCESS: u64 = 1_000_000;
+
+async fn try_send(
+    banks_client: &mut BanksClient,
+    payer: &Keypair,
+    signer: &Keypair,
+    instructions: &[Instruction],
+    recent_blockhash: Hash,
+) -> Result<(), TransactionError> {
+    let transaction = Transaction::new_signed_with_payer(
+        instructions,
+        Some(&payer.pubkey()),
+        &[payer, signer],
+        recent_blockhash,
+    );
+    banks_client
+        .process_transaction(transaction)
+        .await
+        .map_err(|err| err.unwrap())
+}
+
+#[tokio::test]
+async fn excess_on_relayed_review_goes_to_relayer() {
+    let program_id = Pubkey::new_unique();
+    let reviewer = Keypair::new();
+    let relayer = Keypair::new();
+    let mut program_test = ProgramTest::new(
+        "movie_review",
+        program_id,
+        processor!(movie_review::processor::process_instruction),
+    );
+    for wallet in [&reviewer, &relayer] {
+        program_test.add_account(
+            wallet.pubkey(),
+            Account::new(LAMPORTS_PER_SOL, 0, &system_program::ID),
+        );
+    }
+    let (review, _) = find_review_address(&program_id, &reviewer.pubkey(), TITLE);
+
+    let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+    try_send(
+        &mut banks_client,
+        &payer,
+        &payer,
+        &[
+            initialize_config(&program_id, &payer.pubkey(), true, false),
+            set_features(
+                &program_id,
+                &payer.pubkey(),
+                ProgramConfig::DEFAULT_FEATURES | ProgramConfig::FEATURE_RELAYED_REVIEWS,
+            ),
+        ],
+        recent_blockhash,
+    )
+    .await
+    .unwrap();
+
+    let args = RelayedReviewArgs {
+        title: TITLE,
+        rating: 4,
+        description: "Dreamlike",
+        nonce: 0,
+        expires_slot: u64::MAX,
+    };
+    let message = MovieAccountState::relayed_review_message(
+        &program_id,
+        args.title,
+        args.rating,
+        args.description,
+        args.nonce,
+        args.expires_slot,
+    );
+    let signature = reviewer.sign_message(&message);
+    try_send(
+        &mut banks_client,
+        &payer,
+        &relayer,
+        &[
+            verify_reviewer_signature(
+                &reviewer.pubkey(),
+                signature.as_ref().try_into().unwrap(),
+                &message,
+            ),
+            add_relayed_review(&program_id, &relayer.pubkey(), &reviewer.pubkey(), &args),
+            system_instruction::transfer(&relayer.pubkey(), &review, EXCESS),
+        ],
+        recent_blockhash,
+    )
+    .await
+    .unwrap();
+
+    let (rent_payer, _) = find_rent_payer_address(&program_id, &review);
+    let account = banks_client.get_account(rent_payer).await.unwrap().unwrap();
+    match decode_account(&account.data).unwrap() {
+        MovieReviewAccount::RentPayer(record) => {
+            assert_eq!(record.account, review);
+            assert_eq!(record.payer, relayer.pubkey());
+        }
+        _ => panic!("rent payer PDA decoded to the wrong account type"),
+    }
+
+    let err = try_send(
+        &mut banks_client,
+        &payer,
+        &reviewer,
+        &[withdraw_excess_lamports(
+            &program_id,
+            &reviewer.pubkey(),
+            &review,
+        )],
+        recent_blockhash,
+    )
+    .await
+    .unwrap_err();
+    assert_eq!(
+        err,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(CommonError::Unauthorized as u32)
+        )
+    );
+
+    let before = banks_client.get_balance(relayer.pubkey()).await.unwrap();
+    try_send(
+        &mut banks_client,
+        &payer,
+        &relayer,
+        &[withdraw_excess_lamports(
+            &program_id,
+            &relayer.pubkey(),
+            &review,
+        )],
+        recent_blockhash,
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        banks_client.get_balance(relayer.pubkey()).await.unwrap(),
+        before + EXCESS
+    );
+}
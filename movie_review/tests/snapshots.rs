@@ -0,0 +1,199 @@
+//! Backward-compatibility suite: loads account dumps captured from earlier
+//! builds of the program into `solana-program-test` and checks the current
+//! code still reads them.
+//!
+//! Each directory under `tests/fixtures` holds one layout generation, in the
+//! JSON format `solana-test-validator --account` and the `fixtures` binary use.
+
+use std::{fs, path::Path, str::FromStr};
+
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use movie_review::{
+    client::instructions::migrate_account,
+    decoder::{decode_account, MovieReviewAccount},
+    state::{CommentPolicy, MovieAccountState, MovieComment, MovieCommentCounter},
+};
+use solana_program_test::{processor, ProgramTest};
+use solana_sdk::{account::Account, pubkey::Pubkey, signer::Signer, transaction::Transaction};
+
+/// Owner of every fixture account.
+const FIXTURE_PROGRAM_ID: &str = "CmP6f3cgz9bBfkSSuT7wmsyCi6mb7mFVoqQ9Rkbjy4tL";
+
+/// Adds every `*.json` account dump in `dir` to `program_test`, returning the
+/// loaded addresses keyed by file stem.
+fn add_account_fixtures(program_test: &mut ProgramTest, dir: &Path) -> Vec<(String, Pubkey)> {
+    let mut loaded = Vec::new();
+    for entry in fs::read_dir(dir).unwrap() {
+        let path = entry.unwrap().path();
+        if path.extension().and_then(|ext| ext.to_str()) != Some("json") {
+            continue;
+        }
+        let dump: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        let address = Pubkey::from_str(dump["pubkey"].as_str().unwrap()).unwrap();
+        let account = &dump["account"];
+        assert_eq!(account["data"][1], "base64", "{}", path.display());
+
+        program_test.add_account(
+            address,
+            Account {
+                lamports: account["lamports"].as_u64().unwrap(),
+                data: BASE64.decode(account["data"][0].as_str().unwrap()).unwrap(),
+                owner: Pubkey::from_str(account["owner"].as_str().unwrap()).unwrap(),
+                executable: account["executable"].as_bool().unwrap(),
+                rent_epoch: account["rentEpoch"].as_u64().unwrap(),
+            },
+        );
+        let name = path.file_stem().unwrap().to_string_lossy().into_owned();
+        loaded.push((name, address));
+    }
+    loaded.sort();
+    loaded
+}
+
+#[tokio::test]
+async fn v1_accounts_still_decode() {
+    let program_id = Pubkey::from_str(FIXTURE_PROGRAM_ID).unwrap();
+    let mut program_test = ProgramTest::new(
+        "movie_review",
+        program_id,
+        processor!(movie_review::processor::process_instruction),
+    );
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/v1");
+    let loaded = add_account_fixtures(&mut program_test, &dir);
+    assert_eq!(loaded.len(), 3);
+
+    let (mut banks_client, _, _) = program_test.start().await;
+
+    for (name, address) in loaded {
+        let account = banks_client.get_account(address).await.unwrap().unwrap();
+        assert_eq!(account.owner, program_id);
+
+        match (name.as_str(), decode_account(&account.data).unwrap()) {
+            ("review", MovieReviewAccount::Review(review)) => {
+                assert!(review.is_initialized);
+                assert_eq!(review.title, "The Matrix");
+                assert_eq!(review.rating, 4);
+                assert_eq!(review.description, "Still holds up");
+            }
+            ("comment_counter", MovieReviewAccount::CommentCounter(counter)) => {
+                assert!(counter.is_initialized);
+                assert_eq!(counter.total_comments_created, 1);
+                assert_eq!(counter.active_comments, 1);
+            }
+            ("comment", MovieReviewAccount::Comment(comment)) => {
+                assert!(comment.is_initialized);
+                assert_eq!(comment.comment, "Agreed!");
+                assert_eq!(comment.count, 0);
+            }
+            (name, _) => panic!("fixture {} decoded to the wrong account type", name),
+        }
+    }
+}
+
+#[tokio::test]
+async fn v1_accounts_migrate_in_place() {
+    let program_id = Pubkey::from_str(FIXTURE_PROGRAM_ID).unwrap();
+    let mut program_test = ProgramTest::new(
+        "movie_review",
+        program_id,
+        processor!(movie_review::processor::process_instruction),
+    );
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/v1");
+    let loaded = add_account_fixtures(&mut program_test, &dir);
+
+    let (mut banks_client, payer, _) = program_test.start().await;
+
+    for (name, address) in loaded {
+        let before = banks_client.get_account(address).await.unwrap().unwrap();
+
+        // Migrating twice checks the second run is a no-op
+        let blockhash = banks_client.get_latest_blockhash().await.unwrap();
+        let transaction = Transaction::new_signed_with_payer(
+            &[
+                migrate_account(&program_id, &address),
+                migrate_account(&program_id, &address),
+            ],
+            Some(&payer.pubkey()),
+            &[&payer],
+            blockhash,
+        );
+        banks_client.process_transaction(transaction).await.unwrap();
+
+        let after = banks_client.get_account(address).await.unwrap().unwrap();
+
+        let expected_tag = match name.as_str() {
+            "review" => MovieAccountState::DISCRIMINATOR,
+            "comment_counter" => MovieCommentCounter::DISCRIMINATOR,
+            "comment" => MovieComment::DISCRIMINATOR,
+            name => panic!("unexpected fixture {}", name),
+        };
+        assert_eq!(after.data[0], expected_tag, "{}", name);
+
+        match (
+            decode_account(&before.data).unwrap(),
+            decode_account(&after.data).unwrap(),
+        ) {
+            (MovieReviewAccount::Review(v1), MovieReviewAccount::Review(v2)) => {
+                assert_eq!(v2.reviewer, v1.reviewer);
+                assert_eq!(v2.title, v1.title);
+                assert_eq!(v2.rating, v1.rating);
+                assert_eq!(v2.description, v1.description);
+            }
+            (MovieReviewAccount::CommentCounter(v1), MovieReviewAccount::CommentCounter(v2)) => {
+                assert_eq!(v2.total_comments_created, v1.total_comments_created);
+                assert_eq!(v2.active_comments, v1.active_comments);
+            }
+            (MovieReviewAccount::Comment(v1), MovieReviewAccount::Comment(v2)) => {
+                assert_eq!(v2.commenter, v1.commenter);
+                assert_eq!(v2.comment, v1.comment);
+                assert_eq!(v2.count, v1.count);
+            }
+            _ => panic!("fixture {} changed type during migration", name),
+        }
+    }
+}
+
+#[tokio::test]
+async fn v2_review_widens_rating_on_migration() {
+    let program_id = Pubkey::from_str(FIXTURE_PROGRAM_ID).unwrap();
+    let mut program_test = ProgramTest::new(
+        "movie_review",
+        program_id,
+        processor!(movie_review::processor::process_instruction),
+    );
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/v2");
+    let loaded = add_account_fixtures(&mut program_test, &dir);
+    assert_eq!(loaded.len(), 1);
+    let address = loaded[0].1;
+
+    let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+    let before = banks_client.get_account(address).await.unwrap().unwrap();
+
+    let transaction = Transaction::new_signed_with_payer(
+        &[migrate_account(&program_id, &address)],
+        Some(&payer.pubkey()),
+        &[&payer],
+        recent_blockhash,
+    );
+    banks_client.process_transaction(transaction).await.unwrap();
+
+    let after = banks_client.get_account(address).await.unwrap().unwrap();
+    assert_eq!(after.data[0], MovieAccountState::DISCRIMINATOR);
+
+    match (
+        decode_account(&before.data).unwrap(),
+        decode_account(&after.data).unwrap(),
+    ) {
+        (MovieReviewAccount::Review(v2), MovieReviewAccount::Review(current)) => {
+            assert_eq!(v2.discriminator, MovieAccountState::DISCRIMINATOR);
+            assert_eq!(current.reviewer, v2.reviewer);
+            assert_eq!(current.rating, 4);
+            assert_eq!(current.title, "The Matrix");
+            assert_eq!(current.description, "Still holds up");
+            let settings = current.comment_settings.unwrap();
+            assert_eq!(settings.comment_policy, CommentPolicy::FollowersOnly);
+        }
+        _ => panic!("v2 review changed type during migration"),
+    }
+}
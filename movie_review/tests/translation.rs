@@ -0,0 +1,310 @@
+//! Translation bounties pay out to the approved translator, and stay
+//! cancellable by the reviewer who funded them once the review is deleted,
+//! but not while a submission is under review. Translations and the other
+//! accounts of a review close with it in a cascading delete.
+
+use movie_review::{
+    client::{
+        instructions::{
+            add_movie_review, add_review_translation, approve_translation,
+            cancel_translation_bounty, delete_review, fund_translation_bounty, set_co_authors,
+            submit_translation,
+        },
+        pda::{
+            find_co_authors_address, find_review_address, find_translation_address,
+            find_translation_bounty_address,
+        },
+    },
+    decoder::{decode_account, MovieReviewAccount},
+    error::ReviewError,
+};
+use solana_program_test::{processor, BanksClient, ProgramTest};
+use solana_sdk::{
+    account::Account,
+    hash::Hash,
+    instruction::{Instruction, InstructionError},
+    native_token::LAMPORTS_PER_SOL,
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    system_program,
+    transaction::{Transaction, TransactionError},
+};
+
+const TITLE: &str = "Amelie";
+const LANGUAGE: &str = "fr";
+const BOUNTY: u64 = LAMPORTS_PER_SOL / 10;
+
+async fn try_send(
+    banks_client: &mut BanksClient,
+    payer: &Keypair,
+    signer: &Keypair,
+    instruction: Instruction,
+    recent_blockhash: Hash,
+) -> Result<(), TransactionError> {
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction],
+        Some(&payer.pubkey()),
+        &[payer, signer],
+        recent_blockhash,
+    );
+    banks_client
+        .process_transaction(transaction)
+        .await
+        .map_err(|err| err.unwrap())
+}
+
+async fn send(
+    banks_client: &mut BanksClient,
+    payer: &Keypair,
+    signer: &Keypair,
+    instruction: Instruction,
+    recent_blockhash: Hash,
+) {
+    try_send(banks_client, payer, signer, instruction, recent_blockhash)
+        .await
+        .unwrap();
+}
+
+/// Starts the program with funded `wallets`, the reviewer's review added and
+/// its bounty funded.
+async fn start_with_bounty(
+    program_id: Pubkey,
+    reviewer: &Keypair,
+    wallets: &[&Keypair],
+) -> (BanksClient, Keypair, Hash, Pubkey) {
+    let mut program_test = ProgramTest::new(
+        "movie_review",
+        program_id,
+        processor!(movie_review::processor::process_instruction),
+    );
+    for wallet in wallets {
+        program_test.add_account(
+            wallet.pubkey(),
+            Account::new(LAMPORTS_PER_SOL, 0, &system_program::ID),
+        );
+    }
+    let (review, _) = find_review_address(&program_id, &reviewer.pubkey(), TITLE);
+
+    let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+    for instruction in [
+        add_movie_review(&program_id, &reviewer.pubkey(), TITLE, 5, "Charmant"),
+        fund_translation_bounty(&program_id, &reviewer.pubkey(), &review, LANGUAGE, BOUNTY),
+    ] {
+        send(
+            &mut banks_client,
+            &payer,
+            reviewer,
+            instruction,
+            recent_blockhash,
+        )
+        .await;
+    }
+
+    (banks_client, payer, recent_blockhash, review)
+}
+
+async fn lamports(banks_client: &mut BanksClient, address: Pubkey) -> u64 {
+    banks_client.get_balance(address).await.unwrap()
+}
+
+#[tokio::test]
+async fn approved_translator_is_paid_the_bounty() {
+    let program_id = Pubkey::new_unique();
+    let reviewer = Keypair::new();
+    let translator = Keypair::new();
+    let (mut banks_client, payer, recent_blockhash, review) =
+        start_with_bounty(program_id, &reviewer, &[&reviewer, &translator]).await;
+
+    send(
+        &mut banks_client,
+        &payer,
+        &translator,
+        submit_translation(
+            &program_id,
+            &translator.pubkey(),
+            &review,
+            LANGUAGE,
+            "Charmant",
+        ),
+        recent_blockhash,
+    )
+    .await;
+    send(
+        &mut banks_client,
+        &payer,
+        &reviewer,
+        approve_translation(
+            &program_id,
+            &reviewer.pubkey(),
+            &review,
+            LANGUAGE,
+            &translator.pubkey(),
+        ),
+        recent_blockhash,
+    )
+    .await;
+
+    // The submission's rent comes back with the bounty
+    assert_eq!(
+        lamports(&mut banks_client, translator.pubkey()).await,
+        LAMPORTS_PER_SOL + BOUNTY
+    );
+    let (bounty, _) = find_translation_bounty_address(&program_id, &review, LANGUAGE);
+    assert_eq!(lamports(&mut banks_client, bounty).await, 0);
+
+    let (translation, _) = find_translation_address(&program_id, &review, LANGUAGE);
+    let account = banks_client
+        .get_account(translation)
+        .await
+        .unwrap()
+        .unwrap();
+    match decode_account(&account.data).unwrap() {
+        MovieReviewAccount::Translation(translation) => {
+            assert_eq!(translation.description, "Charmant");
+        }
+        _ => panic!("translation PDA decoded to the wrong account type"),
+    }
+}
+
+#[tokio::test]
+async fn bounty_is_refunded_after_review_is_deleted() {
+    let program_id = Pubkey::new_unique();
+    let reviewer = Keypair::new();
+    let (mut banks_client, payer, recent_blockhash, review) =
+        start_with_bounty(program_id, &reviewer, &[&reviewer]).await;
+    let (bounty, _) = find_translation_bounty_address(&program_id, &review, LANGUAGE);
+
+    send(
+        &mut banks_client,
+        &payer,
+        &reviewer,
+        delete_review(&program_id, &reviewer.pubkey(), &review, TITLE, false, &[]),
+        recent_blockhash,
+    )
+    .await;
+    let escrowed = lamports(&mut banks_client, bounty).await;
+    let before = lamports(&mut banks_client, reviewer.pubkey()).await;
+
+    send(
+        &mut banks_client,
+        &payer,
+        &reviewer,
+        cancel_translation_bounty(&program_id, &reviewer.pubkey(), &review, LANGUAGE),
+        recent_blockhash,
+    )
+    .await;
+
+    assert!(escrowed > BOUNTY);
+    assert_eq!(
+        lamports(&mut banks_client, reviewer.pubkey()).await,
+        before + escrowed
+    );
+    assert_eq!(lamports(&mut banks_client, bounty).await, 0);
+}
+
+#[tokio::test]
+async fn bounty_cannot_be_cancelled_under_review() {
+    let program_id = Pubkey::new_unique();
+    let reviewer = Keypair::new();
+    let translator = Keypair::new();
+    let (mut banks_client, payer, recent_blockhash, review) =
+        start_with_bounty(program_id, &reviewer, &[&reviewer, &translator]).await;
+
+    send(
+        &mut banks_client,
+        &payer,
+        &translator,
+        submit_translation(
+            &program_id,
+            &translator.pubkey(),
+            &review,
+            LANGUAGE,
+            "Charmant",
+        ),
+        recent_blockhash,
+    )
+    .await;
+    let err = try_send(
+        &mut banks_client,
+        &payer,
+        &reviewer,
+        cancel_translation_bounty(&program_id, &reviewer.pubkey(), &review, LANGUAGE),
+        recent_blockhash,
+    )
+    .await
+    .unwrap_err();
+
+    assert_eq!(
+        err,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(ReviewError::BountyUnderReview as u32)
+        )
+    );
+    let (bounty, _) = find_translation_bounty_address(&program_id, &review, LANGUAGE);
+    assert!(lamports(&mut banks_client, bounty).await > BOUNTY);
+}
+
+#[tokio::test]
+async fn cascade_closes_translations_and_co_authors() {
+    let program_id = Pubkey::new_unique();
+    let reviewer = Keypair::new();
+    let (mut banks_client, payer, recent_blockhash, review) =
+        start_with_bounty(program_id, &reviewer, &[&reviewer]).await;
+    let (translation, _) = find_translation_address(&program_id, &review, LANGUAGE);
+    let (co_authors, _) = find_co_authors_address(&program_id, &review);
+
+    for instruction in [
+        add_review_translation(
+            &program_id,
+            &reviewer.pubkey(),
+            &review,
+            LANGUAGE,
+            "Charmant",
+        ),
+        set_co_authors(
+            &program_id,
+            &reviewer.pubkey(),
+            &review,
+            vec![(Pubkey::new_unique(), 2_000)],
+        ),
+    ] {
+        send(
+            &mut banks_client,
+            &payer,
+            &reviewer,
+            instruction,
+            recent_blockhash,
+        )
+        .await;
+    }
+    let rent = lamports(&mut banks_client, translation).await
+        + lamports(&mut banks_client, co_authors).await
+        + lamports(&mut banks_client, review).await;
+    let before = lamports(&mut banks_client, reviewer.pubkey()).await;
+
+    send(
+        &mut banks_client,
+        &payer,
+        &reviewer,
+        delete_review(
+            &program_id,
+            &reviewer.pubkey(),
+            &review,
+            TITLE,
+            true,
+            &[
+                (translation, reviewer.pubkey()),
+                (co_authors, reviewer.pubkey()),
+            ],
+        ),
+        recent_blockhash,
+    )
+    .await;
+
+    assert_eq!(lamports(&mut banks_client, translation).await, 0);
+    assert_eq!(lamports(&mut banks_client, co_authors).await, 0);
+    assert_eq!(lamports(&mut banks_client, review).await, 0);
+    assert!(lamports(&mut banks_client, reviewer.pubkey()).await > before + rent);
+}
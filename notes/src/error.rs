@@ -0,0 +1,41 @@
+use num_derive::FromPrimitive;
+use num_traits::FromPrimitive;
+use solana_program::{
+    decode_error::DecodeError,
+    msg,
+    program_error::{PrintProgramError, ProgramError},
+};
+use thiserror::Error;
+
+#[derive(Clone, Copy, Debug, Eq, Error, FromPrimitive, PartialEq)]
+pub enum NoteError {
+    #[error("Account not initialized yet")]
+    UninitializedAccount,
+    #[error("PDA derived does not equal PDA passed in")]
+    InvalidPDA,
+    #[error("Input data exceeds max length")]
+    InvalidDataLength,
+    #[error("Signer is not the note author")]
+    InvalidAuthor,
+}
+
+impl From<NoteError> for ProgramError {
+    fn from(e: NoteError) -> Self {
+        ProgramError::Custom(e as u32)
+    }
+}
+
+impl<T> DecodeError<T> for NoteError {
+    fn type_of() -> &'static str {
+        "NoteError"
+    }
+}
+
+impl PrintProgramError for NoteError {
+    fn print<E>(&self)
+    where
+        E: 'static + std::error::Error + DecodeError<E> + PrintProgramError + FromPrimitive,
+    {
+        msg!(&self.to_string());
+    }
+}
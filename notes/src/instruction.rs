@@ -0,0 +1,53 @@
+use borsh::BorshDeserialize;
+use solana_program::program_error::ProgramError;
+
+pub enum NoteInstruction {
+    CreateNote { title: String, body: String },
+    UpdateNote { title: String, body: String },
+    DeleteNote { title: String },
+}
+
+#[derive(BorshDeserialize)]
+struct NotePayload {
+    title: String,
+    body: String,
+}
+
+#[derive(BorshDeserialize)]
+struct DeleteNotePayload {
+    title: String,
+}
+
+impl NoteInstruction {
+    pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
+        let (&variant, rest) = input
+            .split_first()
+            .ok_or(ProgramError::InvalidInstructionData)?;
+        Ok(match variant {
+            0 => {
+                let payload = NotePayload::try_from_slice(rest)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                Self::CreateNote {
+                    title: payload.title,
+                    body: payload.body,
+                }
+            }
+            1 => {
+                let payload = NotePayload::try_from_slice(rest)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                Self::UpdateNote {
+                    title: payload.title,
+                    body: payload.body,
+                }
+            }
+            2 => {
+                let payload = DeleteNotePayload::try_from_slice(rest)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                Self::DeleteNote {
+                    title: payload.title,
+                }
+            }
+            _ => return Err(ProgramError::InvalidInstructionData),
+        })
+    }
+}
@@ -0,0 +1,172 @@
+use borsh::BorshSerialize;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    borsh1::try_from_slice_unchecked,
+    entrypoint::ProgramResult,
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+use soldev_program_errors::CommonError;
+use soldev_program_utils::{
+    account::{assert_not_closed, close_account, create_pda_account, resize_account},
+    assert::{
+        assert_initialized, assert_max_len, assert_owned_by, assert_signer, assert_uninitialized,
+    },
+    pda::assert_pda,
+};
+
+use crate::error::NoteError;
+use crate::instruction::NoteInstruction;
+use crate::state::NoteState;
+
+pub fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    let instruction = NoteInstruction::unpack(instruction_data)?;
+
+    match instruction {
+        NoteInstruction::CreateNote { title, body } => {
+            create_note(program_id, accounts, title, body)?;
+        }
+        NoteInstruction::UpdateNote { title, body } => {
+            update_note(program_id, accounts, title, body)?;
+        }
+        NoteInstruction::DeleteNote { title } => {
+            delete_note(program_id, accounts, title)?;
+        }
+    }
+    Ok(())
+}
+
+pub fn create_note(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    title: String,
+    body: String,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let author = next_account_info(account_info_iter)?;
+    let pda_note = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+
+    // Check if the instruction is signed
+    assert_signer(author)?;
+
+    // The title is a PDA seed, so it is bounded by the seed length limit
+    assert_max_len(
+        title.len(),
+        NoteState::MAX_TITLE_LEN,
+        NoteError::InvalidDataLength,
+    )?;
+
+    let bump_seed = assert_pda(
+        pda_note,
+        &[author.key.as_ref(), title.as_bytes()],
+        program_id,
+        NoteError::InvalidPDA,
+    )?;
+
+    // Notes are sized to their content rather than a fixed maximum
+    let account_len = NoteState::get_account_size(&title, &body);
+    assert_max_len(
+        account_len,
+        NoteState::MAX_ACCOUNT_SIZE,
+        NoteError::InvalidDataLength,
+    )?;
+
+    msg!("creating note pda account");
+    create_pda_account(
+        author,
+        pda_note,
+        system_program,
+        account_len,
+        program_id,
+        &[author.key.as_ref(), title.as_bytes(), &[bump_seed]],
+    )?;
+    msg!("note PDA created at: {}", pda_note.key);
+
+    let mut note_data = try_from_slice_unchecked::<NoteState>(&pda_note.data.borrow())?;
+
+    assert_uninitialized(&note_data, ProgramError::AccountAlreadyInitialized)?;
+
+    note_data.discriminator = NoteState::DISCRIMINATOR.to_string();
+    note_data.is_initialized = true;
+    note_data.author = *author.key;
+    note_data.title = title;
+    note_data.body = body;
+
+    note_data.serialize(&mut *pda_note.data.borrow_mut())?;
+    msg!("note account initialized");
+
+    Ok(())
+}
+
+pub fn update_note(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    title: String,
+    body: String,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let author = next_account_info(account_info_iter)?;
+    let pda_note = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+
+    let mut note_data = load_note_for_author(program_id, author, pda_note, &title)?;
+
+    let new_len = NoteState::get_account_size(&title, &body);
+    assert_max_len(
+        new_len,
+        NoteState::MAX_ACCOUNT_SIZE,
+        NoteError::InvalidDataLength,
+    )?;
+
+    resize_account(pda_note, author, system_program, new_len)?;
+
+    note_data.body = body;
+    note_data.serialize(&mut *pda_note.data.borrow_mut())?;
+
+    Ok(())
+}
+
+pub fn delete_note(program_id: &Pubkey, accounts: &[AccountInfo], title: String) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let author = next_account_info(account_info_iter)?;
+    let pda_note = next_account_info(account_info_iter)?;
+
+    load_note_for_author(program_id, author, pda_note, &title)?;
+
+    msg!("closing note account");
+    close_account(pda_note, author)
+}
+
+fn load_note_for_author(
+    program_id: &Pubkey,
+    author: &AccountInfo,
+    pda_note: &AccountInfo,
+    title: &str,
+) -> Result<NoteState, ProgramError> {
+    assert_signer(author)?;
+    assert_owned_by(pda_note, program_id)?;
+    assert_not_closed(pda_note, CommonError::AccountClosed)?;
+    assert_pda(
+        pda_note,
+        &[author.key.as_ref(), title.as_bytes()],
+        program_id,
+        NoteError::InvalidPDA,
+    )?;
+
+    let note_data = try_from_slice_unchecked::<NoteState>(&pda_note.data.borrow())?;
+
+    assert_initialized(&note_data, NoteError::UninitializedAccount)?;
+
+    if note_data.author != *author.key {
+        msg!("Signer is not the note author");
+        return Err(NoteError::InvalidAuthor.into());
+    }
+
+    Ok(note_data)
+}
@@ -0,0 +1,30 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::program_pack::{IsInitialized, Sealed};
+use solana_program::pubkey::Pubkey;
+
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct NoteState {
+    pub discriminator: String,
+    pub is_initialized: bool,
+    pub author: Pubkey,
+    pub title: String,
+    pub body: String,
+}
+
+impl Sealed for NoteState {}
+
+impl IsInitialized for NoteState {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+impl NoteState {
+    pub const DISCRIMINATOR: &'static str = "note";
+    pub const MAX_TITLE_LEN: usize = 32;
+    pub const MAX_ACCOUNT_SIZE: usize = 10_240;
+
+    pub fn get_account_size(title: &str, body: &str) -> usize {
+        (4 + NoteState::DISCRIMINATOR.len()) + 1 + 32 + (4 + title.len()) + (4 + body.len())
+    }
+}
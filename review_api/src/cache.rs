@@ -0,0 +1,49 @@
+//! Responses kept for a fixed time, so a page that polls does not turn every
+//! request into a `getProgramAccounts` call.
+
+use std::{
+    collections::HashMap,
+    future::Future,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use serde_json::Value;
+
+pub struct Cache {
+    ttl: Duration,
+    entries: Mutex<HashMap<String, (Instant, Value)>>,
+}
+
+impl Cache {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// The value cached under `key`, or the result of `fetch` if it expired.
+    /// Errors are not cached. Concurrent misses on one key each fetch, which
+    /// is cheaper than holding a lock across the RPC call.
+    pub async fn get_or_fetch<F, E>(&self, key: &str, fetch: F) -> Result<Value, E>
+    where
+        F: Future<Output = Result<Value, E>>,
+    {
+        let cached = self
+            .entries
+            .lock()
+            .unwrap()
+            .get(key)
+            .filter(|(fetched_at, _)| fetched_at.elapsed() < self.ttl)
+            .map(|(_, value)| value.clone());
+        if let Some(value) = cached {
+            return Ok(value);
+        }
+        let value = fetch.await?;
+        let mut entries = self.entries.lock().unwrap();
+        entries.retain(|_, (fetched_at, _)| fetched_at.elapsed() < self.ttl);
+        entries.insert(key.to_string(), (Instant::now(), value.clone()));
+        Ok(value)
+    }
+}
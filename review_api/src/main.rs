@@ -0,0 +1,193 @@
+//! A REST backend over the movie review program, so frontend lessons can
+//! fetch reviews, comments and reviewer profiles without talking RPC.
+//!
+//! Every route reads the chain through `movie_review::client` and serves the
+//! program's own types as JSON. Responses are cached for `--ttl` seconds.
+//!
+//! - `GET /reviews`, optionally `?reviewer=<pubkey>`
+//! - `GET /reviews/:pda/comments`
+//! - `GET /reviewers/:pubkey`
+//!
+//! Usage: review_api --program-id KEY [--rpc-url URL] [--bind ADDR] [--ttl SECS]
+
+use std::{
+    collections::HashMap, net::SocketAddr, process, str::FromStr, sync::Arc, time::Duration,
+};
+
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    routing::get,
+    Json, Router,
+};
+use movie_review::client::{
+    accounts::{fetch_comments, fetch_reviews},
+    stats::fetch_reviewer_profile,
+};
+use serde_json::{json, Value};
+use solana_client::{client_error::ClientError, nonblocking::rpc_client::RpcClient};
+use solana_program::pubkey::Pubkey;
+
+mod cache;
+
+use cache::Cache;
+
+struct Options {
+    program_id: Pubkey,
+    rpc_url: String,
+    bind: SocketAddr,
+    ttl: Duration,
+}
+
+fn parse_options() -> Result<Options, String> {
+    let mut program_id = None;
+    let mut rpc_url = "http://127.0.0.1:8899".to_string();
+    let mut bind = SocketAddr::from(([127, 0, 0, 1], 3000));
+    let mut ttl = Duration::from_secs(5);
+    let mut args = std::env::args().skip(1);
+    while let Some(flag) = args.next() {
+        let value = args
+            .next()
+            .ok_or_else(|| format!("missing value for {}", flag))?;
+        let parse_err = |e: &dyn std::fmt::Display| format!("invalid {}: {}", flag, e);
+        match flag.as_str() {
+            "--program-id" => {
+                program_id = Some(Pubkey::from_str(&value).map_err(|e| parse_err(&e))?)
+            }
+            "--rpc-url" => rpc_url = value,
+            "--bind" => bind = value.parse().map_err(|e| parse_err(&e))?,
+            "--ttl" => ttl = Duration::from_secs(value.parse().map_err(|e| parse_err(&e))?),
+            _ => return Err(format!("unknown flag {}", flag)),
+        }
+    }
+    Ok(Options {
+        program_id: program_id.ok_or("--program-id is required")?,
+        rpc_url,
+        bind,
+        ttl,
+    })
+}
+
+struct AppState {
+    rpc: RpcClient,
+    program_id: Pubkey,
+    cache: Cache,
+}
+
+enum ApiError {
+    BadRequest(String),
+    Rpc(Box<ClientError>),
+}
+
+impl From<ClientError> for ApiError {
+    fn from(error: ClientError) -> Self {
+        ApiError::Rpc(Box::new(error))
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let (status, message) = match self {
+            ApiError::BadRequest(message) => (StatusCode::BAD_REQUEST, message),
+            ApiError::Rpc(error) => (StatusCode::BAD_GATEWAY, error.to_string()),
+        };
+        (status, Json(json!({ "error": message }))).into_response()
+    }
+}
+
+fn parse_pubkey(value: &str) -> Result<Pubkey, ApiError> {
+    Pubkey::from_str(value).map_err(|_| ApiError::BadRequest(format!("invalid pubkey {}", value)))
+}
+
+async fn reviews_json(state: &AppState, reviewer: Option<&Pubkey>) -> Result<Value, ApiError> {
+    let reviews = fetch_reviews(&state.rpc, &state.program_id, reviewer).await?;
+    Ok(reviews
+        .into_iter()
+        .map(|(address, review)| json!({ "address": address.to_string(), "review": review }))
+        .collect())
+}
+
+async fn reviews(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<HashMap<String, String>>,
+) -> Result<Json<Value>, ApiError> {
+    let reviewer = query.get("reviewer").map(|r| parse_pubkey(r)).transpose()?;
+    let key = match &reviewer {
+        Some(reviewer) => format!("reviews?reviewer={}", reviewer),
+        None => "reviews".to_string(),
+    };
+    let value = state
+        .cache
+        .get_or_fetch(&key, reviews_json(&state, reviewer.as_ref()))
+        .await?;
+    Ok(Json(value))
+}
+
+async fn comments(
+    State(state): State<Arc<AppState>>,
+    Path(pda): Path<String>,
+) -> Result<Json<Value>, ApiError> {
+    let review = parse_pubkey(&pda)?;
+    let value = state
+        .cache
+        .get_or_fetch(&format!("comments/{}", review), async {
+            let comments = fetch_comments(&state.rpc, &state.program_id, &review).await?;
+            Ok::<_, ApiError>(
+                comments
+                    .into_iter()
+                    .map(|(address, comment)| {
+                        json!({ "address": address.to_string(), "comment": comment })
+                    })
+                    .collect(),
+            )
+        })
+        .await?;
+    Ok(Json(value))
+}
+
+async fn reviewer(
+    State(state): State<Arc<AppState>>,
+    Path(pubkey): Path<String>,
+) -> Result<Json<Value>, ApiError> {
+    let reviewer = parse_pubkey(&pubkey)?;
+    let value = state
+        .cache
+        .get_or_fetch(&format!("reviewers/{}", reviewer), async {
+            let profile = fetch_reviewer_profile(&state.rpc, &state.program_id, &reviewer).await?;
+            let reviews = reviews_json(&state, Some(&reviewer)).await?;
+            Ok::<_, ApiError>(json!({ "profile": profile, "reviews": reviews }))
+        })
+        .await?;
+    Ok(Json(value))
+}
+
+#[tokio::main]
+async fn main() {
+    let options = parse_options().unwrap_or_else(|e| {
+        eprintln!("error: {}", e);
+        process::exit(2);
+    });
+    let state = Arc::new(AppState {
+        rpc: RpcClient::new(options.rpc_url),
+        program_id: options.program_id,
+        cache: Cache::new(options.ttl),
+    });
+    let app = Router::new()
+        .route("/reviews", get(reviews))
+        .route("/reviews/:pda/comments", get(comments))
+        .route("/reviewers/:pubkey", get(reviewer))
+        .with_state(state);
+
+    let listener = tokio::net::TcpListener::bind(options.bind)
+        .await
+        .unwrap_or_else(|e| {
+            eprintln!("error: {}", e);
+            process::exit(1);
+        });
+    println!("serving on {}", options.bind);
+    if let Err(e) = axum::serve(listener, app).await {
+        eprintln!("error: {}", e);
+        process::exit(1);
+    }
+}
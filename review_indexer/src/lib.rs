@@ -0,0 +1,196 @@
+//! A Geyser plugin that indexes the movie review program's accounts into
+//! SQLite as the validator writes them, the Rust counterpart of the track's
+//! indexing lesson.
+//!
+//! Each update of an account owned by the configured program is decoded with
+//! `movie_review::decoder` and stored as JSON in the `accounts` table, see
+//! `store`. Closed accounts are removed. Load it with
+//! `solana-test-validator --geyser-plugin-config review_indexer/config.json`.
+//!
+//! Reviews and comments first seen after startup are also sent to the
+//! config's `webhooks`, see `webhook`.
+
+use std::{fmt, fs, path::PathBuf, str::FromStr, sync::Mutex};
+
+use log::{info, warn};
+use movie_review::decoder::decode_account;
+use serde::Deserialize;
+use serde_json::Value;
+use solana_geyser_plugin_interface::geyser_plugin_interface::{
+    GeyserPlugin, GeyserPluginError, ReplicaAccountInfoVersions, Result,
+};
+use solana_program::pubkey::Pubkey;
+
+pub mod store;
+pub mod webhook;
+
+use store::Store;
+use webhook::{Event, WebhookConfig, Webhooks};
+
+/// The plugin config file, alongside the `libpath` the validator reads.
+#[derive(Deserialize)]
+struct Config {
+    program_id: String,
+    db_path: PathBuf,
+    #[serde(default)]
+    webhooks: Vec<WebhookConfig>,
+}
+
+#[derive(Default)]
+pub struct ReviewIndexer {
+    program_id: Pubkey,
+    // The validator calls the plugin from several threads, and a SQLite
+    // connection can only be used by one at a time
+    store: Option<Mutex<Store>>,
+    webhooks: Option<Webhooks>,
+}
+
+impl fmt::Debug for ReviewIndexer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ReviewIndexer")
+            .field("program_id", &self.program_id)
+            .finish()
+    }
+}
+
+fn custom_error(error: impl std::error::Error + Send + Sync + 'static) -> GeyserPluginError {
+    GeyserPluginError::Custom(Box::new(error))
+}
+
+impl ReviewIndexer {
+    fn notify(&self, kind: &str, address: String, state: Value, slot: u64) {
+        let Some(webhooks) = &self.webhooks else {
+            return;
+        };
+        let event = match kind {
+            "review" => Event::ReviewAdded {
+                address,
+                review: state,
+            },
+            "comment" => Event::CommentAdded {
+                address,
+                comment: state,
+            },
+            _ => return,
+        };
+        webhooks.send(event, slot);
+    }
+}
+
+impl GeyserPlugin for ReviewIndexer {
+    fn name(&self) -> &'static str {
+        "review_indexer"
+    }
+
+    fn on_load(&mut self, config_file: &str, _is_reload: bool) -> Result<()> {
+        let config: Config = fs::read_to_string(config_file)
+            .map_err(custom_error)
+            .and_then(|contents| serde_json::from_str(&contents).map_err(custom_error))?;
+        self.program_id = Pubkey::from_str(&config.program_id).map_err(|error| {
+            GeyserPluginError::ConfigFileReadError {
+                msg: format!("invalid program_id: {error}"),
+            }
+        })?;
+        let store = Store::open(&config.db_path).map_err(custom_error)?;
+        self.store = Some(Mutex::new(store));
+        if !config.webhooks.is_empty() {
+            self.webhooks = Some(Webhooks::start(config.webhooks));
+        }
+        info!(
+            "indexing {} into {}",
+            self.program_id,
+            config.db_path.display()
+        );
+        Ok(())
+    }
+
+    fn update_account(
+        &self,
+        account: ReplicaAccountInfoVersions,
+        slot: u64,
+        is_startup: bool,
+    ) -> Result<()> {
+        let (address, owner, lamports, data, write_version) = match account {
+            ReplicaAccountInfoVersions::V0_0_1(info) => (
+                info.pubkey,
+                info.owner,
+                info.lamports,
+                info.data,
+                info.write_version,
+            ),
+            ReplicaAccountInfoVersions::V0_0_2(info) => (
+                info.pubkey,
+                info.owner,
+                info.lamports,
+                info.data,
+                info.write_version,
+            ),
+            ReplicaAccountInfoVersions::V0_0_3(info) => (
+                info.pubkey,
+                info.owner,
+                info.lamports,
+                info.data,
+                info.write_version,
+            ),
+        };
+        if owner != self.program_id.as_ref() {
+            return Ok(());
+        }
+        let Some(store) = &self.store else {
+            return Ok(());
+        };
+        let address = Pubkey::try_from(address)
+            .map_err(|_| GeyserPluginError::AccountsUpdateError {
+                msg: "account key is not 32 bytes".to_string(),
+            })?
+            .to_string();
+        let store = store.lock().unwrap();
+
+        // Closing drains the lamports and overwrites the data with a marker
+        // no decoder accepts
+        if lamports == 0 {
+            return store.remove(&address, slot).map_err(custom_error);
+        }
+        match decode_account(data) {
+            Ok(decoded) => {
+                let inserted = store
+                    .upsert(&address, decoded, slot, write_version)
+                    .map_err(custom_error)?;
+                // Startup replays accounts that already existed
+                if let Some((kind, state)) = inserted.filter(|_| !is_startup) {
+                    self.notify(kind, address, state, slot);
+                }
+                Ok(())
+            }
+            Err(error) => {
+                warn!("skipping {address}: {error}");
+                Ok(())
+            }
+        }
+    }
+
+    fn on_unload(&mut self) {
+        self.webhooks = None;
+    }
+
+    fn account_data_notifications_enabled(&self) -> bool {
+        true
+    }
+
+    fn transaction_notifications_enabled(&self) -> bool {
+        false
+    }
+}
+
+/// Entry point the validator loads the plugin through.
+///
+/// # Safety
+///
+/// The validator takes ownership of the returned plugin and must be built
+/// with the same compiler and `solana-geyser-plugin-interface` version.
+#[no_mangle]
+#[allow(improper_ctypes_definitions)]
+pub unsafe extern "C" fn _create_plugin() -> *mut dyn GeyserPlugin {
+    let plugin: Box<dyn GeyserPlugin> = Box::<ReviewIndexer>::default();
+    Box::into_raw(plugin)
+}
@@ -0,0 +1,149 @@
+//! The SQLite table accounts are indexed into: one row per live account of
+//! the program, holding its latest decoded state as JSON.
+
+use std::path::Path;
+
+use movie_review::decoder::MovieReviewAccount;
+use rusqlite::{params, Connection, OptionalExtension};
+use serde_json::{json, Value};
+
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS accounts (
+        address TEXT PRIMARY KEY,
+        kind TEXT NOT NULL,
+        slot INTEGER NOT NULL,
+        write_version INTEGER NOT NULL,
+        state TEXT NOT NULL
+    );
+    CREATE INDEX IF NOT EXISTS accounts_by_kind ON accounts (kind);
+";
+
+pub struct Store {
+    connection: Connection,
+}
+
+impl Store {
+    pub fn open(path: &Path) -> rusqlite::Result<Self> {
+        let connection = Connection::open(path)?;
+        connection.execute_batch(SCHEMA)?;
+        Ok(Self { connection })
+    }
+
+    /// Writes `account` at `address` unless a later write is already stored:
+    /// updates arrive out of order while the validator replays its snapshot.
+    /// Returns the kind and state written if `address` had no row before.
+    pub fn upsert(
+        &self,
+        address: &str,
+        account: MovieReviewAccount,
+        slot: u64,
+        write_version: u64,
+    ) -> rusqlite::Result<Option<(&'static str, Value)>> {
+        let (kind, state) = to_row(account);
+        let existed = self
+            .connection
+            .query_row(
+                "SELECT 1 FROM accounts WHERE address = ?1",
+                params![address],
+                |_| Ok(()),
+            )
+            .optional()?
+            .is_some();
+        self.connection.execute(
+            "INSERT INTO accounts (address, kind, slot, write_version, state)
+             VALUES (?1, ?2, ?3, ?4, ?5)
+             ON CONFLICT (address) DO UPDATE SET
+                 kind = excluded.kind,
+                 slot = excluded.slot,
+                 write_version = excluded.write_version,
+                 state = excluded.state
+             WHERE (excluded.slot, excluded.write_version)
+                 > (accounts.slot, accounts.write_version)",
+            params![
+                address,
+                kind,
+                slot as i64,
+                write_version as i64,
+                state.to_string()
+            ],
+        )?;
+        Ok((!existed).then_some((kind, state)))
+    }
+
+    /// Drops a closed account, unless it was written again after `slot`.
+    pub fn remove(&self, address: &str, slot: u64) -> rusqlite::Result<()> {
+        self.connection.execute(
+            "DELETE FROM accounts WHERE address = ?1 AND slot <= ?2",
+            params![address, slot as i64],
+        )?;
+        Ok(())
+    }
+}
+
+/// The kind column and JSON state of `account`.
+fn to_row(account: MovieReviewAccount) -> (&'static str, Value) {
+    let row = match account {
+        MovieReviewAccount::Review(review) => ("review", serde_json::to_value(review)),
+        MovieReviewAccount::ForeignReview(review, origin) => {
+            return (
+                "foreign_review",
+                json!({ "review": review, "origin": origin }),
+            )
+        }
+        MovieReviewAccount::CommentCounter(counter) => {
+            ("comment_counter", serde_json::to_value(counter))
+        }
+        MovieReviewAccount::Comment(comment) => ("comment", serde_json::to_value(comment)),
+        MovieReviewAccount::Config(config) => ("config", serde_json::to_value(config)),
+        MovieReviewAccount::Movie(movie) => ("movie", serde_json::to_value(movie)),
+        MovieReviewAccount::ReviewLink(link) => ("review_link", serde_json::to_value(link)),
+        MovieReviewAccount::Featured(featured) => ("featured", serde_json::to_value(featured)),
+        MovieReviewAccount::ExternalIdIndex(index) => {
+            ("external_id_index", serde_json::to_value(index))
+        }
+        MovieReviewAccount::Translation(translation) => {
+            ("translation", serde_json::to_value(translation))
+        }
+        MovieReviewAccount::Follow(follow) => ("follow", serde_json::to_value(follow)),
+        MovieReviewAccount::Mute(mute) => ("mute", serde_json::to_value(mute)),
+        MovieReviewAccount::CommentPage(page) => ("comment_page", serde_json::to_value(page)),
+        MovieReviewAccount::Snapshot(snapshot) => ("snapshot", serde_json::to_value(snapshot)),
+        MovieReviewAccount::GlobalStats(stats) => ("global_stats", serde_json::to_value(stats)),
+        MovieReviewAccount::ReviewerProfile(profile) => {
+            ("reviewer_profile", serde_json::to_value(profile))
+        }
+        MovieReviewAccount::MovieStats(stats) => ("movie_stats", serde_json::to_value(stats)),
+        MovieReviewAccount::Session(session) => ("session", serde_json::to_value(session)),
+        MovieReviewAccount::AuditLog(log) => ("audit_log", serde_json::to_value(log)),
+        MovieReviewAccount::PendingRemoval(removal) => {
+            ("pending_removal", serde_json::to_value(removal))
+        }
+        MovieReviewAccount::BodyCommitment(commitment) => {
+            ("body_commitment", serde_json::to_value(commitment))
+        }
+        MovieReviewAccount::Fingerprint(fingerprint) => {
+            ("fingerprint", serde_json::to_value(fingerprint))
+        }
+        MovieReviewAccount::DuplicateChallenge(challenge) => {
+            ("duplicate_challenge", serde_json::to_value(challenge))
+        }
+        MovieReviewAccount::CommentVote(vote) => ("comment_vote", serde_json::to_value(vote)),
+        MovieReviewAccount::CommentKarma(karma) => ("comment_karma", serde_json::to_value(karma)),
+        MovieReviewAccount::HotFeed(feed) => ("hot_feed", serde_json::to_value(feed)),
+        MovieReviewAccount::ReviewEdits(edits) => ("review_edits", serde_json::to_value(edits)),
+        MovieReviewAccount::CoAuthors(co_authors) => {
+            ("co_authors", serde_json::to_value(co_authors))
+        }
+        MovieReviewAccount::TranslationBounty(bounty) => {
+            ("translation_bounty", serde_json::to_value(bounty))
+        }
+        MovieReviewAccount::TranslationSubmission(submission) => {
+            ("translation_submission", serde_json::to_value(submission))
+        }
+        MovieReviewAccount::RatingIndex(index) => ("rating_index", serde_json::to_value(index)),
+        MovieReviewAccount::TitleFilter(filter) => ("title_filter", serde_json::to_value(filter)),
+        MovieReviewAccount::RentPayer(record) => ("rent_payer", serde_json::to_value(record)),
+    };
+    // Program state is plain data, so serializing it cannot fail
+    (row.0, row.1.unwrap_or(Value::Null))
+}
@@ -0,0 +1,102 @@
+//! Webhooks fired when the indexer first sees a review or comment, so
+//! notification bots can react to new content without their own
+//! subscription.
+//!
+//! Each event is POSTed as JSON to every configured URL from a background
+//! thread, so a slow endpoint never stalls the validator. The body is signed
+//! with HMAC-SHA256 under the webhook's secret and the hex digest sent as
+//! `X-Review-Signature: sha256=<digest>`; receivers recompute it over the raw
+//! body to check the request came from this indexer.
+
+use std::{
+    sync::mpsc::{self, Sender},
+    thread,
+    time::Duration,
+};
+
+use hmac::{Hmac, Mac};
+use log::warn;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use sha2::Sha256;
+
+pub const SIGNATURE_HEADER: &str = "X-Review-Signature";
+
+#[derive(Clone, Deserialize)]
+pub struct WebhookConfig {
+    pub url: String,
+    pub secret: String,
+}
+
+pub enum Event {
+    ReviewAdded { address: String, review: Value },
+    CommentAdded { address: String, comment: Value },
+}
+
+impl Event {
+    fn to_json(&self, slot: u64) -> Value {
+        match self {
+            Event::ReviewAdded { address, review } => json!({
+                "event": "ReviewAdded",
+                "slot": slot,
+                "address": address,
+                "review": review,
+            }),
+            Event::CommentAdded { address, comment } => json!({
+                "event": "CommentAdded",
+                "slot": slot,
+                "address": address,
+                "comment": comment,
+            }),
+        }
+    }
+}
+
+/// The hex HMAC-SHA256 of `body` under `secret`.
+pub fn sign(secret: &str, body: &[u8]) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(body);
+    mac.finalize()
+        .into_bytes()
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+/// Queues events for delivery. Dropping it stops the delivery thread once
+/// the queue drains.
+pub struct Webhooks {
+    sender: Sender<(Event, u64)>,
+}
+
+impl Webhooks {
+    pub fn start(webhooks: Vec<WebhookConfig>) -> Self {
+        let (sender, receiver) = mpsc::channel::<(Event, u64)>();
+        thread::spawn(move || {
+            let agent = ureq::AgentBuilder::new()
+                .timeout(Duration::from_secs(10))
+                .build();
+            for (event, slot) in receiver {
+                let body = event.to_json(slot).to_string();
+                for webhook in &webhooks {
+                    let signature = format!("sha256={}", sign(&webhook.secret, body.as_bytes()));
+                    if let Err(error) = agent
+                        .post(&webhook.url)
+                        .set("Content-Type", "application/json")
+                        .set(SIGNATURE_HEADER, &signature)
+                        .send_string(&body)
+                    {
+                        warn!("webhook {} failed: {error}", webhook.url);
+                    }
+                }
+            }
+        });
+        Self { sender }
+    }
+
+    pub fn send(&self, event: Event, slot: u64) {
+        // The thread only exits once this sender is dropped
+        let _ = self.sender.send((event, slot));
+    }
+}
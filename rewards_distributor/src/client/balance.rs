@@ -0,0 +1,51 @@
+use solana_program::{clock::UnixTimestamp, program_error::ProgramError};
+use spl_token_2022::{
+    extension::{
+        interest_bearing_mint::InterestBearingConfig, BaseStateWithExtensions, StateWithExtensions,
+    },
+    state::{Account, Mint},
+};
+
+/// A reward token balance the way wallets display it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RewardBalance {
+    /// Raw amount held by the token account.
+    pub amount: u64,
+    /// Whole tokens, including any interest accrued so far.
+    pub ui_amount: f64,
+    /// Part of `ui_amount` that is accrued interest. Zero unless the mint
+    /// is interest bearing.
+    pub accrued_ui_amount: f64,
+}
+
+/// Decodes the balance of a reward token account as of `unix_timestamp`,
+/// usually the cluster's clock. Works for SPL Token and Token-2022 mints.
+///
+/// Interest never changes the raw `amount`; Token-2022 only accrues it in
+/// the UI amount, computed from the mint's rate history.
+pub fn reward_balance(
+    mint_data: &[u8],
+    token_account_data: &[u8],
+    unix_timestamp: UnixTimestamp,
+) -> Result<RewardBalance, ProgramError> {
+    let mint = StateWithExtensions::<Mint>::unpack(mint_data)?;
+    let amount = StateWithExtensions::<Account>::unpack(token_account_data)?
+        .base
+        .amount;
+    let decimals = mint.base.decimals;
+    let principal = amount as f64 / 10f64.powi(decimals as i32);
+
+    let ui_amount = match mint.get_extension::<InterestBearingConfig>() {
+        Ok(config) => config
+            .amount_to_ui_amount(amount, decimals, unix_timestamp)
+            .and_then(|ui_amount| ui_amount.parse().ok())
+            .ok_or(ProgramError::InvalidAccountData)?,
+        Err(_) => principal,
+    };
+
+    Ok(RewardBalance {
+        amount,
+        ui_amount,
+        accrued_ui_amount: ui_amount - principal,
+    })
+}
@@ -0,0 +1,156 @@
+//! Proof generation for `ConfidentialTip`.
+//!
+//! A confidential transfer needs an equality, a ciphertext validity and a
+//! range proof. Together they exceed the transaction size limit, so each is
+//! verified by the ZK Token Proof program into its own context state
+//! account, in separate transactions, before the tip references them:
+//!
+//! 1. `confidential_tip_proofs` generates the proofs from account data.
+//! 2. `verify_proof_instructions` for each proof, one transaction each.
+//! 3. `instructions::confidential_tip`.
+//! 4. `close_proof_instruction` for each context account, to reclaim rent.
+
+use std::mem::size_of;
+
+use bytemuck::Pod;
+use solana_program::{
+    instruction::Instruction, program_error::ProgramError, pubkey::Pubkey, system_instruction,
+};
+use spl_token_2022::{
+    extension::{
+        confidential_transfer::{
+            account_info::TransferAccountInfo, ConfidentialTransferAccount,
+            ConfidentialTransferMint, DecryptableBalance,
+        },
+        BaseStateWithExtensions, StateWithExtensions,
+    },
+    solana_zk_token_sdk::{
+        encryption::{
+            auth_encryption::AeKey,
+            elgamal::{ElGamalKeypair, ElGamalPubkey},
+        },
+        instruction::{
+            BatchedGroupedCiphertext2HandlesValidityProofData, BatchedRangeProofU128Data,
+            CiphertextCommitmentEqualityProofData, ZkProofData,
+        },
+        zk_token_proof_instruction::{close_context_state, ContextStateInfo, ProofInstruction},
+        zk_token_proof_program,
+        zk_token_elgamal::pod::ElGamalPubkey as PodElGamalPubkey,
+        zk_token_proof_state::ProofContextState,
+    },
+    state::{Account, Mint},
+};
+
+/// Everything a `ConfidentialTip` needs besides its accounts.
+pub struct ConfidentialTipProofs {
+    pub equality_proof: CiphertextCommitmentEqualityProofData,
+    pub ciphertext_validity_proof: BatchedGroupedCiphertext2HandlesValidityProofData,
+    pub range_proof: BatchedRangeProofU128Data,
+    pub new_decryptable_available_balance: [u8; 36],
+    pub source_decrypt_handles: [u8; 64],
+}
+
+/// Generates the proofs for tipping `amount` from the tipper's confidential
+/// balance to the reviewer. The ElGamal keypair and AES key are the ones the
+/// tipper configured their token account with.
+pub fn confidential_tip_proofs(
+    mint_data: &[u8],
+    tipper_token_data: &[u8],
+    reviewer_token_data: &[u8],
+    tipper_elgamal_keypair: &ElGamalKeypair,
+    tipper_aes_key: &AeKey,
+    amount: u64,
+) -> Result<ConfidentialTipProofs, ProgramError> {
+    let mint = StateWithExtensions::<Mint>::unpack(mint_data)?;
+    let auditor_elgamal_pubkey: Option<PodElGamalPubkey> = mint
+        .get_extension::<ConfidentialTransferMint>()?
+        .auditor_elgamal_pubkey
+        .into();
+    let auditor_elgamal_pubkey = auditor_elgamal_pubkey
+        .map(ElGamalPubkey::try_from)
+        .transpose()
+        .map_err(|_| ProgramError::InvalidAccountData)?;
+
+    let tipper_token = StateWithExtensions::<Account>::unpack(tipper_token_data)?;
+    let tipper_account_info =
+        TransferAccountInfo::new(tipper_token.get_extension::<ConfidentialTransferAccount>()?);
+
+    let reviewer_token = StateWithExtensions::<Account>::unpack(reviewer_token_data)?;
+    let reviewer_elgamal_pubkey = ElGamalPubkey::try_from(
+        reviewer_token
+            .get_extension::<ConfidentialTransferAccount>()?
+            .elgamal_pubkey,
+    )
+    .map_err(|_| ProgramError::InvalidAccountData)?;
+
+    let (equality_proof, ciphertext_validity_proof, range_proof, source_decrypt_handles) =
+        tipper_account_info.generate_split_transfer_proof_data(
+            amount,
+            tipper_elgamal_keypair,
+            tipper_aes_key,
+            &reviewer_elgamal_pubkey,
+            auditor_elgamal_pubkey.as_ref(),
+        )?;
+    let new_decryptable_available_balance =
+        tipper_account_info.new_decryptable_available_balance(amount, tipper_aes_key)?;
+
+    Ok(ConfidentialTipProofs {
+        equality_proof,
+        ciphertext_validity_proof,
+        range_proof,
+        new_decryptable_available_balance: DecryptableBalance::from(
+            new_decryptable_available_balance,
+        )
+        .0,
+        source_decrypt_handles: bytemuck::cast(source_decrypt_handles),
+    })
+}
+
+/// Creates `context_account` and verifies `proof_data` into it, with
+/// `authority` allowed to close it later. `lamports` must cover rent for
+/// `ProofContextState<U>`.
+pub fn verify_proof_instructions<T, U>(
+    payer: &Pubkey,
+    context_account: &Pubkey,
+    authority: &Pubkey,
+    proof_instruction: ProofInstruction,
+    proof_data: &T,
+    lamports: u64,
+) -> [Instruction; 2]
+where
+    T: Pod + ZkProofData<U>,
+    U: Pod,
+{
+    [
+        system_instruction::create_account(
+            payer,
+            context_account,
+            lamports,
+            size_of::<ProofContextState<U>>() as u64,
+            &zk_token_proof_program::id(),
+        ),
+        proof_instruction.encode_verify_proof(
+            Some(ContextStateInfo {
+                context_state_account: context_account,
+                context_state_authority: authority,
+            }),
+            proof_data,
+        ),
+    ]
+}
+
+/// Closes a proof context account once the tip has landed, refunding its
+/// rent to `destination`.
+pub fn close_proof_instruction(
+    context_account: &Pubkey,
+    authority: &Pubkey,
+    destination: &Pubkey,
+) -> Instruction {
+    close_context_state(
+        ContextStateInfo {
+            context_state_account: context_account,
+            context_state_authority: authority,
+        },
+        destination,
+    )
+}
@@ -0,0 +1,461 @@
+use movie_review::client::pda::{
+    find_co_authors_address, find_config_address, find_global_stats_address, find_hot_feed_address,
+    find_reviewer_profile_address, find_session_address,
+};
+use mpl_token_metadata::accounts::Metadata;
+use solana_program::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    system_program,
+};
+use spl_associated_token_account::get_associated_token_address_with_program_id;
+
+use super::pda::{
+    find_distributor_address, find_donation_receipt_address, find_earnings_address,
+    find_earnings_vault_address, find_reward_mint_address, find_treasury_address,
+    find_vesting_address, find_vesting_vault_address,
+};
+use crate::instruction::{DistributorInstruction, RewardMintConfig};
+
+/// Initializes the distributor. With `reward_mint_config`, `reward_mint` is
+/// ignored and a Token-2022 mint is created at the reward mint PDA.
+pub fn initialize(
+    program_id: &Pubkey,
+    authority: &Pubkey,
+    review_program: &Pubkey,
+    weekly_budget: u64,
+    reward_mint: &Pubkey,
+    token_program: &Pubkey,
+    reward_mint_config: Option<RewardMintConfig>,
+) -> Instruction {
+    let reward_mint = match reward_mint_config {
+        Some(_) => find_reward_mint_address(program_id).0,
+        None => *reward_mint,
+    };
+    Instruction::new_with_bytes(
+        *program_id,
+        &DistributorInstruction::Initialize {
+            review_program: *review_program,
+            weekly_budget,
+            reward_mint_config,
+        }
+        .pack(),
+        vec![
+            AccountMeta::new(*authority, true),
+            AccountMeta::new(find_distributor_address(program_id).0, false),
+            AccountMeta::new(find_treasury_address(program_id).0, false),
+            AccountMeta::new(reward_mint, false),
+            AccountMeta::new_readonly(*token_program, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+    )
+}
+
+/// Credits the weekly budget to `recipients`, given as `(review, reviewer,
+/// co_authors)`, `co_authors` being the review's accepted co-authors in
+/// listing order. Each reviewer's share accrues in their and their
+/// co-authors' earnings, to be claimed with `claim_earnings`; their profile
+/// in `review_program` sets their streak bonus. `hook_accounts` are the reward mint's transfer hook extra accounts,
+/// empty if it has none.
+pub fn distribute(
+    program_id: &Pubkey,
+    review_program: &Pubkey,
+    authority: &Pubkey,
+    reward_mint: &Pubkey,
+    token_program: &Pubkey,
+    recipients: &[(Pubkey, Pubkey, Vec<Pubkey>)],
+    hook_accounts: &[AccountMeta],
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(*authority, true),
+        AccountMeta::new(find_distributor_address(program_id).0, false),
+        AccountMeta::new(find_treasury_address(program_id).0, false),
+        AccountMeta::new(find_earnings_vault_address(program_id).0, false),
+        AccountMeta::new(*reward_mint, false),
+        AccountMeta::new_readonly(*token_program, false),
+        AccountMeta::new_readonly(system_program::ID, false),
+    ];
+    for (review, reviewer, co_authors) in recipients {
+        accounts.push(AccountMeta::new_readonly(*review, false));
+        accounts.push(AccountMeta::new(
+            find_earnings_address(program_id, reviewer).0,
+            false,
+        ));
+        accounts.push(AccountMeta::new_readonly(
+            find_reviewer_profile_address(review_program, reviewer).0,
+            false,
+        ));
+        accounts.push(AccountMeta::new_readonly(
+            find_co_authors_address(review_program, review).0,
+            false,
+        ));
+        accounts.extend(co_author_earnings(program_id, co_authors));
+    }
+    accounts.extend_from_slice(hook_accounts);
+    Instruction::new_with_bytes(
+        *program_id,
+        &DistributorInstruction::Distribute {
+            recipients: recipients.len() as u8,
+        }
+        .pack(),
+        accounts,
+    )
+}
+
+/// Tips `reviewer`, the author of `review`, and its accepted `co_authors`,
+/// in listing order, into their earnings, to be claimed with
+/// `claim_earnings`. The distributor's tip share of `amount` goes to the
+/// treasury. `review_program` is the distributor's, whose global stats and
+/// hot feed count the tip and which holds the review's co-authors.
+#[allow(clippy::too_many_arguments)]
+pub fn tip(
+    program_id: &Pubkey,
+    review_program: &Pubkey,
+    tipper: &Pubkey,
+    review: &Pubkey,
+    reviewer: &Pubkey,
+    co_authors: &[Pubkey],
+    tipper_token: &Pubkey,
+    reward_mint: &Pubkey,
+    token_program: &Pubkey,
+    amount: u64,
+    hook_accounts: &[AccountMeta],
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(*tipper, true),
+        AccountMeta::new_readonly(find_distributor_address(program_id).0, false),
+        AccountMeta::new_readonly(*review, false),
+        AccountMeta::new(*tipper_token, false),
+        AccountMeta::new(find_earnings_address(program_id, reviewer).0, false),
+        AccountMeta::new(find_earnings_vault_address(program_id).0, false),
+        AccountMeta::new_readonly(*reward_mint, false),
+        AccountMeta::new_readonly(*token_program, false),
+        AccountMeta::new_readonly(system_program::ID, false),
+        AccountMeta::new(find_treasury_address(program_id).0, false),
+        AccountMeta::new(find_vesting_address(program_id, reviewer).0, false),
+        AccountMeta::new(find_vesting_vault_address(program_id, reviewer).0, false),
+        AccountMeta::new_readonly(*review_program, false),
+        AccountMeta::new_readonly(find_config_address(review_program).0, false),
+        AccountMeta::new(find_global_stats_address(review_program).0, false),
+        AccountMeta::new(find_hot_feed_address(review_program).0, false),
+        AccountMeta::new_readonly(find_co_authors_address(review_program, review).0, false),
+    ];
+    accounts.extend(co_author_earnings(program_id, co_authors));
+    accounts.extend_from_slice(hook_accounts);
+    Instruction::new_with_bytes(
+        *program_id,
+        &DistributorInstruction::Tip { amount }.pack(),
+        accounts,
+    )
+}
+
+pub fn update_interest_rate(program_id: &Pubkey, authority: &Pubkey, rate: i16) -> Instruction {
+    Instruction::new_with_bytes(
+        *program_id,
+        &DistributorInstruction::UpdateInterestRate { rate }.pack(),
+        vec![
+            AccountMeta::new_readonly(*authority, true),
+            AccountMeta::new_readonly(find_distributor_address(program_id).0, false),
+            AccountMeta::new(find_reward_mint_address(program_id).0, false),
+            AccountMeta::new_readonly(spl_token_2022::ID, false),
+        ],
+    )
+}
+
+/// Sends a confidential tip. `proof_accounts` are the equality, ciphertext
+/// validity and range proof context accounts, in that order; the remaining
+/// arguments come from `client::confidential::confidential_tip_proofs`.
+#[allow(clippy::too_many_arguments)]
+pub fn confidential_tip(
+    program_id: &Pubkey,
+    tipper: &Pubkey,
+    review: &Pubkey,
+    tipper_token: &Pubkey,
+    reviewer_token: &Pubkey,
+    proof_accounts: &[Pubkey; 3],
+    new_decryptable_available_balance: [u8; 36],
+    source_decrypt_handles: [u8; 64],
+) -> Instruction {
+    Instruction::new_with_bytes(
+        *program_id,
+        &DistributorInstruction::ConfidentialTip {
+            new_decryptable_available_balance,
+            source_decrypt_handles,
+        }
+        .pack(),
+        vec![
+            AccountMeta::new_readonly(*tipper, true),
+            AccountMeta::new_readonly(find_distributor_address(program_id).0, false),
+            AccountMeta::new_readonly(*review, false),
+            AccountMeta::new(*tipper_token, false),
+            AccountMeta::new(*reviewer_token, false),
+            AccountMeta::new_readonly(find_reward_mint_address(program_id).0, false),
+            AccountMeta::new_readonly(spl_token_2022::ID, false),
+            AccountMeta::new_readonly(proof_accounts[0], false),
+            AccountMeta::new_readonly(proof_accounts[1], false),
+            AccountMeta::new_readonly(proof_accounts[2], false),
+        ],
+    )
+}
+
+/// Attaches Token Metadata to the distributor-created reward mint.
+pub fn create_reward_metadata(
+    program_id: &Pubkey,
+    authority: &Pubkey,
+    symbol: &str,
+    uri: &str,
+) -> Instruction {
+    let (reward_mint, _) = find_reward_mint_address(program_id);
+    Instruction::new_with_bytes(
+        *program_id,
+        &DistributorInstruction::CreateRewardMetadata {
+            symbol: symbol.to_string(),
+            uri: uri.to_string(),
+        }
+        .pack(),
+        vec![
+            AccountMeta::new(*authority, true),
+            AccountMeta::new_readonly(find_distributor_address(program_id).0, false),
+            AccountMeta::new_readonly(reward_mint, false),
+            AccountMeta::new(Metadata::find_pda(&reward_mint).0, false),
+            AccountMeta::new_readonly(mpl_token_metadata::ID, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+    )
+}
+
+pub fn donate_sol(program_id: &Pubkey, donor: &Pubkey, amount: u64) -> Instruction {
+    Instruction::new_with_bytes(
+        *program_id,
+        &DistributorInstruction::DonateSol { amount }.pack(),
+        vec![
+            AccountMeta::new(*donor, true),
+            AccountMeta::new(find_distributor_address(program_id).0, false),
+            AccountMeta::new(find_donation_receipt_address(program_id, donor).0, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+    )
+}
+
+pub fn donate_tokens(
+    program_id: &Pubkey,
+    donor: &Pubkey,
+    donor_token: &Pubkey,
+    reward_mint: &Pubkey,
+    token_program: &Pubkey,
+    amount: u64,
+    hook_accounts: &[AccountMeta],
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(*donor, true),
+        AccountMeta::new_readonly(find_distributor_address(program_id).0, false),
+        AccountMeta::new(find_donation_receipt_address(program_id, donor).0, false),
+        AccountMeta::new(*donor_token, false),
+        AccountMeta::new(find_treasury_address(program_id).0, false),
+        AccountMeta::new_readonly(*reward_mint, false),
+        AccountMeta::new_readonly(*token_program, false),
+        AccountMeta::new_readonly(system_program::ID, false),
+    ];
+    accounts.extend_from_slice(hook_accounts);
+    Instruction::new_with_bytes(
+        *program_id,
+        &DistributorInstruction::DonateTokens { amount }.pack(),
+        accounts,
+    )
+}
+
+pub fn set_tip_share(program_id: &Pubkey, authority: &Pubkey, treasury_bps: u16) -> Instruction {
+    Instruction::new_with_bytes(
+        *program_id,
+        &DistributorInstruction::SetTipShare { treasury_bps }.pack(),
+        vec![
+            AccountMeta::new(*authority, true),
+            AccountMeta::new(find_distributor_address(program_id).0, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+    )
+}
+
+pub fn set_tip_vesting(
+    program_id: &Pubkey,
+    authority: &Pubkey,
+    threshold: u64,
+    duration: i64,
+) -> Instruction {
+    Instruction::new_with_bytes(
+        *program_id,
+        &DistributorInstruction::SetTipVesting {
+            threshold,
+            duration,
+        }
+        .pack(),
+        vec![
+            AccountMeta::new(*authority, true),
+            AccountMeta::new(find_distributor_address(program_id).0, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+    )
+}
+
+/// Claims `reviewer`'s unlocked tips into their associated token account,
+/// which must already exist.
+pub fn claim_vested(
+    program_id: &Pubkey,
+    reviewer: &Pubkey,
+    reward_mint: &Pubkey,
+    token_program: &Pubkey,
+    hook_accounts: &[AccountMeta],
+) -> Instruction {
+    let reviewer_token =
+        get_associated_token_address_with_program_id(reviewer, reward_mint, token_program);
+    let mut accounts = vec![
+        AccountMeta::new_readonly(*reviewer, true),
+        AccountMeta::new_readonly(find_distributor_address(program_id).0, false),
+        AccountMeta::new(find_vesting_address(program_id, reviewer).0, false),
+        AccountMeta::new(find_vesting_vault_address(program_id, reviewer).0, false),
+        AccountMeta::new(reviewer_token, false),
+        AccountMeta::new_readonly(*reward_mint, false),
+        AccountMeta::new_readonly(*token_program, false),
+    ];
+    accounts.extend_from_slice(hook_accounts);
+    Instruction::new_with_bytes(
+        *program_id,
+        &DistributorInstruction::ClaimVested.pack(),
+        accounts,
+    )
+}
+
+/// Tips `amount` lamports to `reviewer`, the author of `review`, and its
+/// accepted `co_authors`, in listing order, into their earnings.
+/// `review_program` is the distributor's, whose global stats and hot feed
+/// count the tip and which holds the review's co-authors.
+pub fn tip_sol(
+    program_id: &Pubkey,
+    review_program: &Pubkey,
+    tipper: &Pubkey,
+    review: &Pubkey,
+    reviewer: &Pubkey,
+    co_authors: &[Pubkey],
+    amount: u64,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(*tipper, true),
+        AccountMeta::new(find_distributor_address(program_id).0, false),
+        AccountMeta::new_readonly(*review, false),
+        AccountMeta::new(find_earnings_address(program_id, reviewer).0, false),
+        AccountMeta::new_readonly(system_program::ID, false),
+        AccountMeta::new_readonly(*review_program, false),
+        AccountMeta::new_readonly(find_config_address(review_program).0, false),
+        AccountMeta::new(find_global_stats_address(review_program).0, false),
+        AccountMeta::new(find_hot_feed_address(review_program).0, false),
+        AccountMeta::new_readonly(find_co_authors_address(review_program, review).0, false),
+    ];
+    accounts.extend(co_author_earnings(program_id, co_authors));
+    Instruction::new_with_bytes(
+        *program_id,
+        &DistributorInstruction::TipSol { amount }.pack(),
+        accounts,
+    )
+}
+
+/// Writable earnings accounts of `co_authors`, for the instructions that
+/// split a review's earnings with them.
+fn co_author_earnings<'a>(
+    program_id: &'a Pubkey,
+    co_authors: &'a [Pubkey],
+) -> impl Iterator<Item = AccountMeta> + 'a {
+    co_authors
+        .iter()
+        .map(|co_author| AccountMeta::new(find_earnings_address(program_id, co_author).0, false))
+}
+
+/// Claims all of `reviewer`'s accrued tokens and lamports. The tokens go to
+/// their associated token account, which is created if needed.
+pub fn claim_earnings(
+    program_id: &Pubkey,
+    reviewer: &Pubkey,
+    reward_mint: &Pubkey,
+    token_program: &Pubkey,
+    hook_accounts: &[AccountMeta],
+) -> Instruction {
+    let reviewer_token =
+        get_associated_token_address_with_program_id(reviewer, reward_mint, token_program);
+    let mut accounts = vec![
+        AccountMeta::new(*reviewer, true),
+        AccountMeta::new_readonly(find_distributor_address(program_id).0, false),
+        AccountMeta::new(find_earnings_address(program_id, reviewer).0, false),
+        AccountMeta::new(find_earnings_vault_address(program_id).0, false),
+        AccountMeta::new(reviewer_token, false),
+        AccountMeta::new_readonly(*reward_mint, false),
+        AccountMeta::new_readonly(*token_program, false),
+        AccountMeta::new_readonly(spl_associated_token_account::ID, false),
+        AccountMeta::new_readonly(system_program::ID, false),
+    ];
+    accounts.extend_from_slice(hook_accounts);
+    Instruction::new_with_bytes(
+        *program_id,
+        &DistributorInstruction::ClaimEarnings.pack(),
+        accounts,
+    )
+}
+
+pub fn set_emission_schedule(
+    program_id: &Pubkey,
+    authority: &Pubkey,
+    amount_per_action: u64,
+    halving_epochs: u64,
+    cap: u64,
+) -> Instruction {
+    Instruction::new_with_bytes(
+        *program_id,
+        &DistributorInstruction::SetEmissionSchedule {
+            amount_per_action,
+            halving_epochs,
+            cap,
+        }
+        .pack(),
+        vec![
+            AccountMeta::new(*authority, true),
+            AccountMeta::new(find_distributor_address(program_id).0, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+    )
+}
+
+/// Claims `reviewer`'s earnings with a session `session_key` holds in
+/// `review_program`. The session key pays for the reviewer's associated
+/// token account if it is missing.
+pub fn claim_earnings_with_session(
+    program_id: &Pubkey,
+    review_program: &Pubkey,
+    session_key: &Pubkey,
+    reviewer: &Pubkey,
+    reward_mint: &Pubkey,
+    token_program: &Pubkey,
+    hook_accounts: &[AccountMeta],
+) -> Instruction {
+    let reviewer_token =
+        get_associated_token_address_with_program_id(reviewer, reward_mint, token_program);
+    let mut accounts = vec![
+        AccountMeta::new(*session_key, true),
+        AccountMeta::new_readonly(
+            find_session_address(review_program, reviewer, session_key).0,
+            false,
+        ),
+        AccountMeta::new(*reviewer, false),
+        AccountMeta::new_readonly(find_distributor_address(program_id).0, false),
+        AccountMeta::new(find_earnings_address(program_id, reviewer).0, false),
+        AccountMeta::new(find_earnings_vault_address(program_id).0, false),
+        AccountMeta::new(reviewer_token, false),
+        AccountMeta::new_readonly(*reward_mint, false),
+        AccountMeta::new_readonly(*token_program, false),
+        AccountMeta::new_readonly(spl_associated_token_account::ID, false),
+        AccountMeta::new_readonly(system_program::ID, false),
+    ];
+    accounts.extend_from_slice(hook_accounts);
+    Instruction::new_with_bytes(
+        *program_id,
+        &DistributorInstruction::ClaimEarningsWithSession.pack(),
+        accounts,
+    )
+}
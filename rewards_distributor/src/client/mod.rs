@@ -0,0 +1,6 @@
+//! Instruction, PDA, balance and proof helpers for off-chain callers.
+
+pub mod balance;
+pub mod confidential;
+pub mod instructions;
+pub mod pda;
@@ -0,0 +1,53 @@
+use solana_program::pubkey::Pubkey;
+
+use crate::state::{DistributorState, DonationReceipt, ReviewerEarnings, VestingSchedule};
+
+pub fn find_distributor_address(program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[DistributorState::SEED.as_bytes()], program_id)
+}
+
+pub fn find_treasury_address(program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[DistributorState::TREASURY_SEED.as_bytes()], program_id)
+}
+
+pub fn find_reward_mint_address(program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[DistributorState::REWARD_MINT_SEED.as_bytes()], program_id)
+}
+
+pub fn find_donation_receipt_address(program_id: &Pubkey, donor: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[DonationReceipt::SEED.as_bytes(), donor.as_ref()],
+        program_id,
+    )
+}
+
+pub fn find_vesting_address(program_id: &Pubkey, reviewer: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[VestingSchedule::SEED.as_bytes(), reviewer.as_ref()],
+        program_id,
+    )
+}
+
+pub fn find_vesting_vault_address(program_id: &Pubkey, reviewer: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            DistributorState::VESTING_VAULT_SEED.as_bytes(),
+            reviewer.as_ref(),
+        ],
+        program_id,
+    )
+}
+
+pub fn find_earnings_address(program_id: &Pubkey, reviewer: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[ReviewerEarnings::SEED.as_bytes(), reviewer.as_ref()],
+        program_id,
+    )
+}
+
+pub fn find_earnings_vault_address(program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[DistributorState::EARNINGS_VAULT_SEED.as_bytes()],
+        program_id,
+    )
+}
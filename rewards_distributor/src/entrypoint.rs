@@ -0,0 +1,27 @@
+use solana_program::{
+    account_info::AccountInfo, entrypoint, entrypoint::ProgramResult, msg, pubkey::Pubkey,
+};
+
+use crate::error::DistributorError;
+use crate::processor;
+
+entrypoint!(process_instruction);
+
+fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    msg!(
+        "process_instruction: {}: {} accounts, data={:?}",
+        program_id,
+        accounts.len(),
+        instruction_data
+    );
+
+    if let Err(error) = processor::process_instruction(program_id, accounts, instruction_data) {
+        soldev_program_errors::print_error::<DistributorError>(&error);
+        return Err(error);
+    }
+    Ok(())
+}
@@ -0,0 +1,69 @@
+use num_derive::FromPrimitive;
+use num_traits::FromPrimitive;
+use solana_program::{
+    decode_error::DecodeError,
+    msg,
+    program_error::{PrintProgramError, ProgramError},
+};
+use thiserror::Error;
+
+#[derive(Clone, Copy, Debug, Eq, Error, FromPrimitive, PartialEq)]
+pub enum DistributorError {
+    #[error("Account not initialized yet")]
+    UninitializedAccount,
+    #[error("PDA derived does not equal PDA passed in")]
+    InvalidPDA,
+    #[error("Signer is not the distributor authority")]
+    InvalidAuthority,
+    #[error("Weekly distribution already made")]
+    DistributionTooEarly,
+    #[error("Review account is not owned by the movie review program")]
+    InvalidReviewAccount,
+    #[error("Token account does not belong to the reviewer")]
+    InvalidRecipient,
+    #[error("No reviewers supplied")]
+    NoRecipients,
+    #[error("Mint is not the distributor's reward mint")]
+    InvalidRewardMint,
+    #[error("Tip amount must be greater than zero")]
+    InvalidTipAmount,
+    #[error("Donation amount must be greater than zero")]
+    InvalidDonationAmount,
+    #[error("Tip share exceeds 10000 basis points")]
+    InvalidTipShare,
+    #[error("Vesting duration must be positive")]
+    InvalidVestingDuration,
+    #[error("Nothing has vested yet")]
+    NothingToClaim,
+    #[error("No earnings to claim")]
+    NoEarnings,
+    #[error("Emission cap must be positive, not raised and not below what was emitted")]
+    InvalidEmissionSchedule,
+    #[error("Emission cap reached")]
+    EmissionExhausted,
+    #[error("Session cannot claim for the reviewer")]
+    InvalidSession,
+    #[error("Review passed more than once")]
+    DuplicateReview,
+}
+
+impl From<DistributorError> for ProgramError {
+    fn from(e: DistributorError) -> Self {
+        ProgramError::Custom(e as u32)
+    }
+}
+
+impl<T> DecodeError<T> for DistributorError {
+    fn type_of() -> &'static str {
+        "DistributorError"
+    }
+}
+
+impl PrintProgramError for DistributorError {
+    fn print<E>(&self)
+    where
+        E: 'static + std::error::Error + DecodeError<E> + PrintProgramError + FromPrimitive,
+    {
+        msg!(&self.to_string());
+    }
+}
@@ -0,0 +1,325 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{program_error::ProgramError, pubkey::Pubkey};
+
+/// Asks `Initialize` to create the reward mint itself, as a Token-2022 mint at
+/// the `reward_mint` PDA with the distributor PDA as mint authority.
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct RewardMintConfig {
+    /// Program Token-2022 calls on every transfer of the mint, e.g. the
+    /// workspace's `transfer_hook`.
+    pub transfer_hook_program: Option<Pubkey>,
+    /// Yearly interest in basis points, shown as accrued balance by wallets.
+    /// The distributor authority can change it with `UpdateInterestRate`.
+    pub interest_rate: Option<i16>,
+    /// Adds the confidential transfer extension so tips can be sent with
+    /// `ConfidentialTip`. New token accounts are approved automatically.
+    pub confidential_transfers: bool,
+}
+
+pub enum DistributorInstruction {
+    Initialize {
+        review_program: Pubkey,
+        weekly_budget: u64,
+        reward_mint_config: Option<RewardMintConfig>,
+    },
+    Distribute {
+        recipients: u8,
+    },
+    Tip {
+        amount: u64,
+    },
+    UpdateInterestRate {
+        rate: i16,
+    },
+    /// Tip whose amount is encrypted. The proofs are verified beforehand into
+    /// context state accounts, see `client::confidential`.
+    ConfidentialTip {
+        /// `DecryptableBalance` of the tipper after the tip, encrypted under
+        /// the tipper's AES key.
+        new_decryptable_available_balance: [u8; 36],
+        /// `SourceDecryptHandles` of the transfer amount for the tipper.
+        source_decrypt_handles: [u8; 64],
+    },
+    CreateRewardMetadata {
+        symbol: String,
+        uri: String,
+    },
+    /// Donates lamports to the distributor PDA, recorded on the donor's
+    /// receipt.
+    DonateSol {
+        amount: u64,
+    },
+    /// Donates reward tokens to the treasury, recorded on the donor's receipt.
+    DonateTokens {
+        amount: u64,
+    },
+    /// Sets the share of each tip paid into the treasury, in basis points.
+    SetTipShare {
+        treasury_bps: u16,
+    },
+    /// Sets how tips of at least `threshold` reward tokens to the reviewer
+    /// vest over `duration` seconds. A zero threshold disables vesting.
+    SetTipVesting {
+        threshold: u64,
+        duration: i64,
+    },
+    /// Pays the reviewer whatever their vesting schedule has unlocked.
+    ClaimVested,
+    /// Tips `amount` lamports to the author of a review, less the tip share,
+    /// credited to their earnings.
+    TipSol {
+        amount: u64,
+    },
+    /// Pays the reviewer all their accrued tokens and lamports.
+    ClaimEarnings,
+    /// Sets how many reward tokens `Distribute` mints for each reviewer it
+    /// credits, halving every `halving_epochs` epochs, up to `cap` in total.
+    SetEmissionSchedule {
+        amount_per_action: u64,
+        halving_epochs: u64,
+        cap: u64,
+    },
+    /// Pays the reviewer all their accrued tokens and lamports, signed for
+    /// by a review program session key with the claim scope.
+    ClaimEarningsWithSession,
+}
+
+#[derive(BorshDeserialize)]
+struct InitializePayload {
+    review_program: Pubkey,
+    weekly_budget: u64,
+    reward_mint_config: Option<RewardMintConfig>,
+}
+
+#[derive(BorshDeserialize)]
+struct DistributePayload {
+    recipients: u8,
+}
+
+#[derive(BorshDeserialize)]
+struct TipPayload {
+    amount: u64,
+}
+
+#[derive(BorshDeserialize)]
+struct DonatePayload {
+    amount: u64,
+}
+
+#[derive(BorshDeserialize)]
+struct SetTipSharePayload {
+    treasury_bps: u16,
+}
+
+#[derive(BorshDeserialize)]
+struct SetTipVestingPayload {
+    threshold: u64,
+    duration: i64,
+}
+
+#[derive(BorshDeserialize)]
+struct SetEmissionSchedulePayload {
+    amount_per_action: u64,
+    halving_epochs: u64,
+    cap: u64,
+}
+
+#[derive(BorshDeserialize)]
+struct UpdateInterestRatePayload {
+    rate: i16,
+}
+
+#[derive(BorshDeserialize)]
+struct CreateRewardMetadataPayload {
+    symbol: String,
+    uri: String,
+}
+
+#[derive(BorshDeserialize)]
+struct ConfidentialTipPayload {
+    new_decryptable_available_balance: [u8; 36],
+    source_decrypt_handles: [u8; 64],
+}
+
+impl DistributorInstruction {
+    pub fn unpack(input: &[u8]) -> Result<Self, ProgramError> {
+        let (&variant, rest) = input
+            .split_first()
+            .ok_or(ProgramError::InvalidInstructionData)?;
+        Ok(match variant {
+            0 => {
+                let payload = InitializePayload::try_from_slice(rest)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                Self::Initialize {
+                    review_program: payload.review_program,
+                    weekly_budget: payload.weekly_budget,
+                    reward_mint_config: payload.reward_mint_config,
+                }
+            }
+            1 => {
+                let payload = DistributePayload::try_from_slice(rest)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                Self::Distribute {
+                    recipients: payload.recipients,
+                }
+            }
+            2 => {
+                let payload = TipPayload::try_from_slice(rest)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                Self::Tip {
+                    amount: payload.amount,
+                }
+            }
+            3 => {
+                let payload = UpdateInterestRatePayload::try_from_slice(rest)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                Self::UpdateInterestRate { rate: payload.rate }
+            }
+            4 => {
+                let payload = ConfidentialTipPayload::try_from_slice(rest)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                Self::ConfidentialTip {
+                    new_decryptable_available_balance: payload.new_decryptable_available_balance,
+                    source_decrypt_handles: payload.source_decrypt_handles,
+                }
+            }
+            5 => {
+                let payload = CreateRewardMetadataPayload::try_from_slice(rest)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                Self::CreateRewardMetadata {
+                    symbol: payload.symbol,
+                    uri: payload.uri,
+                }
+            }
+            6 => {
+                let payload = DonatePayload::try_from_slice(rest)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                Self::DonateSol {
+                    amount: payload.amount,
+                }
+            }
+            7 => {
+                let payload = DonatePayload::try_from_slice(rest)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                Self::DonateTokens {
+                    amount: payload.amount,
+                }
+            }
+            8 => {
+                let payload = SetTipSharePayload::try_from_slice(rest)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                Self::SetTipShare {
+                    treasury_bps: payload.treasury_bps,
+                }
+            }
+            9 => {
+                let payload = SetTipVestingPayload::try_from_slice(rest)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                Self::SetTipVesting {
+                    threshold: payload.threshold,
+                    duration: payload.duration,
+                }
+            }
+            10 => Self::ClaimVested,
+            11 => {
+                let payload = TipPayload::try_from_slice(rest)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                Self::TipSol {
+                    amount: payload.amount,
+                }
+            }
+            12 => Self::ClaimEarnings,
+            13 => {
+                let payload = SetEmissionSchedulePayload::try_from_slice(rest)
+                    .map_err(|_| ProgramError::InvalidInstructionData)?;
+                Self::SetEmissionSchedule {
+                    amount_per_action: payload.amount_per_action,
+                    halving_epochs: payload.halving_epochs,
+                    cap: payload.cap,
+                }
+            }
+            14 => Self::ClaimEarningsWithSession,
+            _ => return Err(ProgramError::InvalidInstructionData),
+        })
+    }
+
+    pub fn pack(&self) -> Vec<u8> {
+        let mut buf = Vec::new();
+        match self {
+            Self::Initialize {
+                review_program,
+                weekly_budget,
+                reward_mint_config,
+            } => {
+                buf.push(0);
+                borsh::to_writer(
+                    &mut buf,
+                    &(review_program, weekly_budget, reward_mint_config),
+                )
+                .unwrap();
+            }
+            Self::Distribute { recipients } => {
+                buf.push(1);
+                borsh::to_writer(&mut buf, recipients).unwrap();
+            }
+            Self::Tip { amount } => {
+                buf.push(2);
+                borsh::to_writer(&mut buf, amount).unwrap();
+            }
+            Self::UpdateInterestRate { rate } => {
+                buf.push(3);
+                borsh::to_writer(&mut buf, rate).unwrap();
+            }
+            Self::ConfidentialTip {
+                new_decryptable_available_balance,
+                source_decrypt_handles,
+            } => {
+                buf.push(4);
+                borsh::to_writer(
+                    &mut buf,
+                    &(new_decryptable_available_balance, source_decrypt_handles),
+                )
+                .unwrap();
+            }
+            Self::CreateRewardMetadata { symbol, uri } => {
+                buf.push(5);
+                borsh::to_writer(&mut buf, &(symbol, uri)).unwrap();
+            }
+            Self::DonateSol { amount } => {
+                buf.push(6);
+                borsh::to_writer(&mut buf, amount).unwrap();
+            }
+            Self::DonateTokens { amount } => {
+                buf.push(7);
+                borsh::to_writer(&mut buf, amount).unwrap();
+            }
+            Self::SetTipShare { treasury_bps } => {
+                buf.push(8);
+                borsh::to_writer(&mut buf, treasury_bps).unwrap();
+            }
+            Self::SetTipVesting {
+                threshold,
+                duration,
+            } => {
+                buf.push(9);
+                borsh::to_writer(&mut buf, &(threshold, duration)).unwrap();
+            }
+            Self::ClaimVested => buf.push(10),
+            Self::TipSol { amount } => {
+                buf.push(11);
+                borsh::to_writer(&mut buf, amount).unwrap();
+            }
+            Self::ClaimEarnings => buf.push(12),
+            Self::SetEmissionSchedule {
+                amount_per_action,
+                halving_epochs,
+                cap,
+            } => {
+                buf.push(13);
+                borsh::to_writer(&mut buf, &(amount_per_action, halving_epochs, cap)).unwrap();
+            }
+            Self::ClaimEarningsWithSession => buf.push(14),
+        }
+        buf
+    }
+}
@@ -0,0 +1,1901 @@
+use borsh::BorshSerialize;
+use movie_review::{
+    client::instructions::{record_sol_tip, record_tip},
+    event::{emit, ReviewEvent},
+    migration::load_review_header,
+    state::{MovieAccountState, ReviewCoAuthors, ReviewerProfile, Session},
+};
+use mpl_token_metadata::{
+    accounts::Metadata, instructions::CreateMetadataAccountV3CpiBuilder, types::DataV2,
+};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    borsh1::try_from_slice_unchecked,
+    clock::{Clock, Epoch},
+    entrypoint::ProgramResult,
+    msg,
+    program::{invoke, invoke_signed},
+    program_error::ProgramError,
+    program_pack::IsInitialized,
+    pubkey::Pubkey,
+    system_instruction,
+    sysvar::Sysvar,
+};
+use soldev_program_errors::CommonError;
+use soldev_program_utils::{
+    account::{create_pda_account, resize_account, withdraw_excess_lamports},
+    assert::{
+        assert_initialized, assert_owned_by, assert_program_id, assert_rent_exempt, assert_signer,
+        assert_uninitialized,
+    },
+    pda::{assert_pda, assert_pda_with_bump},
+};
+use spl_associated_token_account::instruction::create_associated_token_account_idempotent;
+use spl_token_2022::{
+    extension::{
+        confidential_transfer::{
+            self, ciphertext_extraction::SourceDecryptHandles,
+            instruction::TransferSplitContextStateAccounts, DecryptableBalance,
+        },
+        interest_bearing_mint, transfer_hook, BaseStateWithExtensions, ExtensionType,
+        StateWithExtensions,
+    },
+    onchain::invoke_transfer_checked,
+    solana_zk_token_sdk::zk_token_proof_program,
+    state::{Account as TokenAccount, Mint},
+};
+
+use crate::error::DistributorError;
+use crate::instruction::{DistributorInstruction, RewardMintConfig};
+use crate::state::{DistributorState, DonationReceipt, ReviewerEarnings, VestingSchedule};
+
+pub fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    let instruction = DistributorInstruction::unpack(instruction_data)?;
+
+    match instruction {
+        DistributorInstruction::Initialize {
+            review_program,
+            weekly_budget,
+            reward_mint_config,
+        } => {
+            initialize(
+                program_id,
+                accounts,
+                review_program,
+                weekly_budget,
+                reward_mint_config,
+            )?;
+        }
+        DistributorInstruction::Distribute { recipients } => {
+            distribute(program_id, accounts, recipients)?;
+        }
+        DistributorInstruction::Tip { amount } => {
+            tip(program_id, accounts, amount)?;
+        }
+        DistributorInstruction::UpdateInterestRate { rate } => {
+            update_interest_rate(program_id, accounts, rate)?;
+        }
+        DistributorInstruction::ConfidentialTip {
+            new_decryptable_available_balance,
+            source_decrypt_handles,
+        } => {
+            confidential_tip(
+                program_id,
+                accounts,
+                &new_decryptable_available_balance,
+                &source_decrypt_handles,
+            )?;
+        }
+        DistributorInstruction::CreateRewardMetadata { symbol, uri } => {
+            create_reward_metadata(program_id, accounts, symbol, uri)?;
+        }
+        DistributorInstruction::DonateSol { amount } => {
+            donate_sol(program_id, accounts, amount)?;
+        }
+        DistributorInstruction::DonateTokens { amount } => {
+            donate_tokens(program_id, accounts, amount)?;
+        }
+        DistributorInstruction::SetTipShare { treasury_bps } => {
+            set_tip_share(program_id, accounts, treasury_bps)?;
+        }
+        DistributorInstruction::SetTipVesting {
+            threshold,
+            duration,
+        } => {
+            set_tip_vesting(program_id, accounts, threshold, duration)?;
+        }
+        DistributorInstruction::ClaimVested => {
+            claim_vested(program_id, accounts)?;
+        }
+        DistributorInstruction::TipSol { amount } => {
+            tip_sol(program_id, accounts, amount)?;
+        }
+        DistributorInstruction::ClaimEarnings => {
+            claim_earnings(program_id, accounts)?;
+        }
+        DistributorInstruction::SetEmissionSchedule {
+            amount_per_action,
+            halving_epochs,
+            cap,
+        } => {
+            set_emission_schedule(program_id, accounts, amount_per_action, halving_epochs, cap)?;
+        }
+        DistributorInstruction::ClaimEarningsWithSession => {
+            claim_earnings_with_session(program_id, accounts)?;
+        }
+    }
+    Ok(())
+}
+
+/// The reward mint may live under either token program, as long as the one
+/// passed in owns it.
+fn assert_token_program(token_program: &AccountInfo, reward_mint: &AccountInfo) -> ProgramResult {
+    if token_program.key != &spl_token::ID && token_program.key != &spl_token_2022::ID {
+        msg!("Invalid token program {}", token_program.key);
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    assert_owned_by(reward_mint, token_program.key)
+}
+
+fn mint_decimals(reward_mint: &AccountInfo) -> Result<u8, ProgramError> {
+    let mint_data = reward_mint.data.borrow();
+    Ok(StateWithExtensions::<Mint>::unpack(&mint_data)?
+        .base
+        .decimals)
+}
+
+/// Size of a token account for `reward_mint`, including whatever account
+/// extensions the mint's own extensions require, e.g. the transferring flag
+/// of a transfer hook.
+fn token_account_len(reward_mint: &AccountInfo) -> Result<usize, ProgramError> {
+    let mint_data = reward_mint.data.borrow();
+    let mint = StateWithExtensions::<Mint>::unpack(&mint_data)?;
+    let account_extensions =
+        ExtensionType::get_required_init_account_extensions(&mint.get_extension_types()?);
+    ExtensionType::try_calculate_account_len::<TokenAccount>(&account_extensions)
+}
+
+/// Creates the Token-2022 reward mint at its PDA, with the distributor PDA as
+/// mint authority and the initializing authority in charge of the extensions.
+fn create_reward_mint<'a>(
+    program_id: &Pubkey,
+    authority: &AccountInfo<'a>,
+    pda_distributor: &AccountInfo<'a>,
+    reward_mint: &AccountInfo<'a>,
+    token_program: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    config: &RewardMintConfig,
+) -> ProgramResult {
+    assert_program_id(token_program, &spl_token_2022::ID)?;
+    let mint_bump_seed = assert_pda(
+        reward_mint,
+        &[DistributorState::REWARD_MINT_SEED.as_bytes()],
+        program_id,
+        DistributorError::InvalidPDA,
+    )?;
+
+    let mut extensions = Vec::new();
+    if config.transfer_hook_program.is_some() {
+        extensions.push(ExtensionType::TransferHook);
+    }
+    if config.interest_rate.is_some() {
+        extensions.push(ExtensionType::InterestBearingConfig);
+    }
+    if config.confidential_transfers {
+        extensions.push(ExtensionType::ConfidentialTransferMint);
+    }
+
+    msg!("creating reward mint");
+    create_pda_account(
+        authority,
+        reward_mint,
+        system_program,
+        ExtensionType::try_calculate_account_len::<Mint>(&extensions)?,
+        token_program.key,
+        &[
+            DistributorState::REWARD_MINT_SEED.as_bytes(),
+            &[mint_bump_seed],
+        ],
+    )?;
+
+    // Extensions must be initialized before the mint itself
+    if let Some(transfer_hook_program) = config.transfer_hook_program {
+        msg!("setting transfer hook to {}", transfer_hook_program);
+        invoke(
+            &transfer_hook::instruction::initialize(
+                token_program.key,
+                reward_mint.key,
+                Some(*authority.key),
+                Some(transfer_hook_program),
+            )?,
+            std::slice::from_ref(reward_mint),
+        )?;
+    }
+    // The distributor PDA holds the rate authority so the rate can only move
+    // through `UpdateInterestRate`
+    if let Some(interest_rate) = config.interest_rate {
+        msg!("setting interest rate to {} bps", interest_rate);
+        invoke(
+            &interest_bearing_mint::instruction::initialize(
+                token_program.key,
+                reward_mint.key,
+                Some(*pda_distributor.key),
+                interest_rate,
+            )?,
+            std::slice::from_ref(reward_mint),
+        )?;
+    }
+    if config.confidential_transfers {
+        msg!("enabling confidential transfers");
+        invoke(
+            &confidential_transfer::instruction::initialize_mint(
+                token_program.key,
+                reward_mint.key,
+                Some(*authority.key),
+                true,
+                None,
+            )?,
+            std::slice::from_ref(reward_mint),
+        )?;
+    }
+
+    invoke(
+        &spl_token_2022::instruction::initialize_mint2(
+            token_program.key,
+            reward_mint.key,
+            pda_distributor.key,
+            None,
+            DistributorState::REWARD_MINT_DECIMALS,
+        )?,
+        std::slice::from_ref(reward_mint),
+    )
+}
+
+/// Zero-extends distributors created before the newest fields were appended.
+fn load_distributor(pda_distributor: &AccountInfo) -> Result<DistributorState, ProgramError> {
+    let mut data = pda_distributor.data.borrow().to_vec();
+    data.resize(data.len().max(DistributorState::get_account_size()), 0);
+    Ok(try_from_slice_unchecked::<DistributorState>(&data)?)
+}
+
+/// Checks `review` is an initialized review of the configured review program
+/// and returns its author. Only the header is decoded, so the title and
+/// description never land on the heap. Reviews still in an earlier layout are
+/// read as is, since the review program owns them.
+fn load_reviewer(
+    review: &AccountInfo,
+    distributor_data: &DistributorState,
+) -> Result<Pubkey, ProgramError> {
+    if review.owner != &distributor_data.review_program {
+        msg!("Review account is not owned by the movie review program");
+        return Err(DistributorError::InvalidReviewAccount.into());
+    }
+    let review_data = load_review_header(&review.data.borrow())?;
+    if !review_data.is_initialized()
+        || review_data.discriminator != MovieAccountState::DISCRIMINATOR
+    {
+        msg!("Review account not initialized");
+        return Err(DistributorError::InvalidReviewAccount.into());
+    }
+    Ok(review_data.reviewer)
+}
+
+/// The posting streak of `reviewer` as of `epoch`, read from their profile in
+/// the review program. Reviewers without a profile have none.
+fn load_streak(
+    pda_profile: &AccountInfo,
+    reviewer: &Pubkey,
+    distributor_data: &DistributorState,
+    epoch: Epoch,
+) -> Result<u32, ProgramError> {
+    assert_pda(
+        pda_profile,
+        &[ReviewerProfile::SEED.as_bytes(), reviewer.as_ref()],
+        &distributor_data.review_program,
+        DistributorError::InvalidPDA,
+    )?;
+    if pda_profile.owner != &distributor_data.review_program {
+        return Ok(0);
+    }
+    // Zero-extend profiles created before the streak was appended
+    let mut data = pda_profile.data.borrow().to_vec();
+    data.resize(data.len().max(ReviewerProfile::get_account_size()), 0);
+    let profile_data = try_from_slice_unchecked::<ReviewerProfile>(&data)?;
+    if profile_data.discriminator != ReviewerProfile::DISCRIMINATOR {
+        msg!("Account is not a reviewer profile");
+        return Err(DistributorError::InvalidPDA.into());
+    }
+    Ok(profile_data.current_streak(epoch))
+}
+
+/// Creates the reviewer's associated token account for the reward mint when
+/// there is no account yet, so first-time reviewers can be paid without a
+/// separate setup transaction. `payer` covers the rent.
+fn create_reviewer_token_account<'a>(
+    payer: &AccountInfo<'a>,
+    reviewer: &AccountInfo<'a>,
+    reviewer_token: &AccountInfo<'a>,
+    reward_mint: &AccountInfo<'a>,
+    token_program: &AccountInfo<'a>,
+    associated_token_program: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+) -> ProgramResult {
+    if !reviewer_token.data_is_empty() {
+        return Ok(());
+    }
+    msg!("creating reviewer token account");
+    invoke(
+        &create_associated_token_account_idempotent(
+            payer.key,
+            reviewer.key,
+            reward_mint.key,
+            token_program.key,
+        ),
+        &[
+            payer.clone(),
+            reviewer_token.clone(),
+            reviewer.clone(),
+            reward_mint.clone(),
+            system_program.clone(),
+            token_program.clone(),
+            associated_token_program.clone(),
+        ],
+    )
+}
+
+fn assert_reviewer_token_account(
+    reviewer_token: &AccountInfo,
+    reviewer: &Pubkey,
+    reward_mint: &Pubkey,
+) -> ProgramResult {
+    let token_data = reviewer_token.data.borrow();
+    let token_account = StateWithExtensions::<TokenAccount>::unpack(&token_data)?;
+    if token_account.base.owner != *reviewer || token_account.base.mint != *reward_mint {
+        msg!("Token account does not belong to the reviewer");
+        return Err(DistributorError::InvalidRecipient.into());
+    }
+    Ok(())
+}
+
+pub fn initialize(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    review_program: Pubkey,
+    weekly_budget: u64,
+    reward_mint_config: Option<RewardMintConfig>,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let authority = next_account_info(account_info_iter)?;
+    let pda_distributor = next_account_info(account_info_iter)?;
+    let pda_treasury = next_account_info(account_info_iter)?;
+    let reward_mint = next_account_info(account_info_iter)?;
+    let token_program = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+
+    assert_signer(authority)?;
+
+    let bump_seed = assert_pda(
+        pda_distributor,
+        &[DistributorState::SEED.as_bytes()],
+        program_id,
+        DistributorError::InvalidPDA,
+    )?;
+    let treasury_bump_seed = assert_pda(
+        pda_treasury,
+        &[DistributorState::TREASURY_SEED.as_bytes()],
+        program_id,
+        DistributorError::InvalidPDA,
+    )?;
+
+    msg!("creating distributor account");
+    create_pda_account(
+        authority,
+        pda_distributor,
+        system_program,
+        DistributorState::get_account_size(),
+        program_id,
+        &[DistributorState::SEED.as_bytes(), &[bump_seed]],
+    )?;
+
+    if let Some(config) = &reward_mint_config {
+        create_reward_mint(
+            program_id,
+            authority,
+            pda_distributor,
+            reward_mint,
+            token_program,
+            system_program,
+            config,
+        )?;
+    }
+    assert_token_program(token_program, reward_mint)?;
+
+    // The treasury's token authority is the distributor PDA, which signs the
+    // weekly payouts
+    msg!("creating treasury token account");
+    create_pda_account(
+        authority,
+        pda_treasury,
+        system_program,
+        token_account_len(reward_mint)?,
+        token_program.key,
+        &[
+            DistributorState::TREASURY_SEED.as_bytes(),
+            &[treasury_bump_seed],
+        ],
+    )?;
+    invoke(
+        &spl_token_2022::instruction::initialize_account3(
+            token_program.key,
+            pda_treasury.key,
+            reward_mint.key,
+            pda_distributor.key,
+        )?,
+        &[pda_treasury.clone(), reward_mint.clone()],
+    )?;
+
+    let mut distributor_data =
+        try_from_slice_unchecked::<DistributorState>(&pda_distributor.data.borrow())?;
+
+    assert_uninitialized(&distributor_data, ProgramError::AccountAlreadyInitialized)?;
+
+    distributor_data.discriminator = DistributorState::DISCRIMINATOR.to_string();
+    distributor_data.is_initialized = true;
+    distributor_data.authority = *authority.key;
+    distributor_data.review_program = review_program;
+    distributor_data.reward_mint = *reward_mint.key;
+    distributor_data.weekly_budget = weekly_budget;
+    distributor_data.last_distribution = 0;
+    distributor_data.bump = bump_seed;
+    distributor_data.tip_treasury_bps = 0;
+    distributor_data.tip_vesting_threshold = 0;
+    distributor_data.tip_vesting_duration = 0;
+    distributor_data.emission_per_action = 0;
+    distributor_data.emission_halving_epochs = 0;
+    distributor_data.emission_start_epoch = 0;
+    distributor_data.emission_cap = 0;
+    distributor_data.emitted = 0;
+
+    distributor_data.serialize(&mut &mut pda_distributor.data.borrow_mut()[..])?;
+    msg!("distributor initialized at: {}", pda_distributor.key);
+
+    Ok(())
+}
+
+/// Splits the weekly budget evenly across the reviews passed in. Ranking the
+/// top reviewers happens off-chain; this instruction verifies each review
+/// belongs to the movie review program and credits its author's earnings.
+/// Each review may only be listed once.
+/// The whole budget moves from the treasury to the earnings vault in a single
+/// transfer; reviewers collect their share with `ClaimEarnings`. With an
+/// emission schedule, each reviewer is also credited the current emission,
+/// raised by their posting streak and minted into the earnings vault, which
+/// fails with `EmissionExhausted` once the cap would be passed. What a review
+/// earns is split with its accepted co-authors.
+///
+/// Accounts are `[authority, distributor, treasury, earnings_vault,
+/// reward_mint, token_program, system_program]`, followed for each of the
+/// `recipients` by `[review, earnings, profile, co_authors]`, the last two
+/// being the reviewer's profile and the review's co-authors in the review
+/// program, and the earnings of each accepted co-author in listing order.
+/// Then come the extra accounts of the reward mint's transfer hook, if it
+/// has one. The authority pays for the earnings vault and any earnings
+/// accounts that do not exist yet.
+pub fn distribute(program_id: &Pubkey, accounts: &[AccountInfo], recipients: u8) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let authority = next_account_info(account_info_iter)?;
+    let pda_distributor = next_account_info(account_info_iter)?;
+    let pda_treasury = next_account_info(account_info_iter)?;
+    let pda_earnings_vault = next_account_info(account_info_iter)?;
+    let reward_mint = next_account_info(account_info_iter)?;
+    let token_program = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+
+    assert_signer(authority)?;
+    assert_owned_by(pda_distributor, program_id)?;
+    assert_token_program(token_program, reward_mint)?;
+
+    let mut distributor_data = load_distributor(pda_distributor)?;
+
+    assert_initialized(&distributor_data, DistributorError::UninitializedAccount)?;
+
+    if distributor_data.authority != *authority.key {
+        msg!("Signer is not the distributor authority");
+        return Err(DistributorError::InvalidAuthority.into());
+    }
+    if distributor_data.reward_mint != *reward_mint.key {
+        msg!("Mint is not the distributor's reward mint");
+        return Err(DistributorError::InvalidRewardMint.into());
+    }
+
+    assert_pda(
+        pda_treasury,
+        &[DistributorState::TREASURY_SEED.as_bytes()],
+        program_id,
+        DistributorError::InvalidPDA,
+    )?;
+
+    let clock = Clock::get()?;
+    if clock.unix_timestamp
+        < distributor_data
+            .last_distribution
+            .saturating_add(DistributorState::DISTRIBUTION_INTERVAL)
+    {
+        msg!("Weekly distribution already made");
+        return Err(DistributorError::DistributionTooEarly.into());
+    }
+
+    if recipients == 0 {
+        msg!("Expected at least one recipient");
+        return Err(DistributorError::NoRecipients.into());
+    }
+    let share = distributor_data.weekly_budget / recipients as u64;
+
+    assert_pda_with_bump(
+        pda_distributor,
+        &[DistributorState::SEED.as_bytes()],
+        distributor_data.bump,
+        program_id,
+        DistributorError::InvalidPDA,
+    )?;
+
+    create_vault(
+        program_id,
+        authority,
+        &[DistributorState::EARNINGS_VAULT_SEED.as_bytes()],
+        pda_distributor,
+        pda_earnings_vault,
+        reward_mint,
+        token_program,
+        system_program,
+    )?;
+
+    let mut emitted: u64 = 0;
+    let mut credited = Vec::with_capacity(recipients as usize);
+    for _ in 0..recipients {
+        let review = next_account_info(account_info_iter)?;
+        let pda_earnings = next_account_info(account_info_iter)?;
+        let pda_profile = next_account_info(account_info_iter)?;
+        let pda_co_authors = next_account_info(account_info_iter)?;
+
+        let reviewer = load_reviewer(review, &distributor_data)?;
+        if credited.contains(review.key) {
+            msg!("Review {} is listed more than once", review.key);
+            return Err(DistributorError::DuplicateReview.into());
+        }
+        credited.push(*review.key);
+        let streak = load_streak(pda_profile, &reviewer, &distributor_data, clock.epoch)?;
+        let emission = distributor_data
+            .streak_emission(clock.epoch, streak)
+            .ok_or(CommonError::Overflow)?;
+        emitted = emitted.checked_add(emission).ok_or(CommonError::Overflow)?;
+        let (reviewer_part, co_author_parts) =
+            load_co_authors(pda_co_authors, review, &distributor_data)?
+                .split(share.checked_add(emission).ok_or(CommonError::Overflow)?)
+                .ok_or(CommonError::Overflow)?;
+        credit_co_authors(
+            program_id,
+            authority,
+            &co_author_parts,
+            account_info_iter,
+            system_program,
+            Balance::Tokens,
+        )?;
+
+        let mut earnings_data = load_earnings(
+            program_id,
+            authority,
+            &reviewer,
+            pda_earnings,
+            system_program,
+        )?;
+        earnings_data.tokens = earnings_data
+            .tokens
+            .checked_add(reviewer_part)
+            .ok_or(CommonError::Overflow)?;
+        earnings_data.serialize(&mut &mut pda_earnings.data.borrow_mut()[..])?;
+        msg!(
+            "crediting {} of {} + {} to reviewer {} on a {} epoch streak",
+            reviewer_part,
+            share,
+            emission,
+            reviewer,
+            streak
+        );
+    }
+    let hook_accounts = account_info_iter.as_slice();
+
+    distributor_data
+        .emit(emitted)
+        .ok_or(DistributorError::EmissionExhausted)?;
+    if emitted > 0 {
+        msg!("minting {} emitted reward tokens", emitted);
+        invoke_signed(
+            &spl_token_2022::instruction::mint_to(
+                token_program.key,
+                reward_mint.key,
+                pda_earnings_vault.key,
+                pda_distributor.key,
+                &[],
+                emitted,
+            )?,
+            &[
+                reward_mint.clone(),
+                pda_earnings_vault.clone(),
+                pda_distributor.clone(),
+            ],
+            &[&[DistributorState::SEED.as_bytes(), &[distributor_data.bump]]],
+        )?;
+    }
+
+    let total = share
+        .checked_mul(recipients as u64)
+        .ok_or(CommonError::Overflow)?;
+    if total > 0 {
+        // Resolves the transfer hook's extra accounts out of `hook_accounts`,
+        // which a plain `transfer_checked` CPI would leave out
+        invoke_transfer_checked(
+            token_program.key,
+            pda_treasury.clone(),
+            reward_mint.clone(),
+            pda_earnings_vault.clone(),
+            pda_distributor.clone(),
+            hook_accounts,
+            total,
+            mint_decimals(reward_mint)?,
+            &[&[DistributorState::SEED.as_bytes(), &[distributor_data.bump]]],
+        )?;
+    }
+
+    distributor_data.last_distribution = clock.unix_timestamp;
+    if pda_distributor.data_len() < DistributorState::get_account_size() {
+        resize_account(
+            pda_distributor,
+            authority,
+            system_program,
+            DistributorState::get_account_size(),
+        )?;
+    }
+    distributor_data.serialize(&mut &mut pda_distributor.data.borrow_mut()[..])?;
+
+    Ok(())
+}
+
+/// Creates the vault at the PDA of `seeds` unless it exists already: a token
+/// account for the reward mint whose authority is the distributor PDA. Used
+/// for a reviewer's vesting vault on their first vested tip and for the
+/// earnings vault on the first accrual.
+#[allow(clippy::too_many_arguments)]
+fn create_vault<'a>(
+    program_id: &Pubkey,
+    payer: &AccountInfo<'a>,
+    seeds: &[&[u8]],
+    pda_distributor: &AccountInfo<'a>,
+    pda_vault: &AccountInfo<'a>,
+    reward_mint: &AccountInfo<'a>,
+    token_program: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+) -> ProgramResult {
+    let vault_bump_seed = [assert_pda(
+        pda_vault,
+        seeds,
+        program_id,
+        DistributorError::InvalidPDA,
+    )?];
+    if pda_vault.owner == token_program.key {
+        return Ok(());
+    }
+
+    msg!("creating vault {}", pda_vault.key);
+    let mut signer_seeds = seeds.to_vec();
+    signer_seeds.push(&vault_bump_seed);
+    create_pda_account(
+        payer,
+        pda_vault,
+        system_program,
+        token_account_len(reward_mint)?,
+        token_program.key,
+        &signer_seeds,
+    )?;
+    invoke(
+        &spl_token_2022::instruction::initialize_account3(
+            token_program.key,
+            pda_vault.key,
+            reward_mint.key,
+            pda_distributor.key,
+        )?,
+        &[pda_vault.clone(), reward_mint.clone()],
+    )
+}
+
+/// Loads `reviewer`'s vesting schedule, creating it on their first vested
+/// tip at `payer`'s expense.
+fn load_vesting<'a>(
+    program_id: &Pubkey,
+    payer: &AccountInfo<'a>,
+    reviewer: &Pubkey,
+    pda_vesting: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+) -> Result<VestingSchedule, ProgramError> {
+    let bump_seed = assert_pda(
+        pda_vesting,
+        &[VestingSchedule::SEED.as_bytes(), reviewer.as_ref()],
+        program_id,
+        DistributorError::InvalidPDA,
+    )?;
+
+    if pda_vesting.owner != program_id {
+        msg!("creating vesting schedule");
+        create_pda_account(
+            payer,
+            pda_vesting,
+            system_program,
+            VestingSchedule::get_account_size(),
+            program_id,
+            &[
+                VestingSchedule::SEED.as_bytes(),
+                reviewer.as_ref(),
+                &[bump_seed],
+            ],
+        )?;
+    }
+
+    let mut vesting_data = try_from_slice_unchecked::<VestingSchedule>(&pda_vesting.data.borrow())?;
+    if !vesting_data.is_initialized() {
+        vesting_data.discriminator = VestingSchedule::DISCRIMINATOR.to_string();
+        vesting_data.is_initialized = true;
+        vesting_data.recipient = *reviewer;
+    }
+    Ok(vesting_data)
+}
+
+/// Loads `reviewer`'s earnings, creating them on their first accrual at
+/// `payer`'s expense.
+fn load_earnings<'a>(
+    program_id: &Pubkey,
+    payer: &AccountInfo<'a>,
+    reviewer: &Pubkey,
+    pda_earnings: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+) -> Result<ReviewerEarnings, ProgramError> {
+    let bump_seed = assert_pda(
+        pda_earnings,
+        &[ReviewerEarnings::SEED.as_bytes(), reviewer.as_ref()],
+        program_id,
+        DistributorError::InvalidPDA,
+    )?;
+
+    if pda_earnings.owner != program_id {
+        msg!("creating reviewer earnings");
+        create_pda_account(
+            payer,
+            pda_earnings,
+            system_program,
+            ReviewerEarnings::get_account_size(),
+            program_id,
+            &[
+                ReviewerEarnings::SEED.as_bytes(),
+                reviewer.as_ref(),
+                &[bump_seed],
+            ],
+        )?;
+    }
+
+    let mut earnings_data =
+        try_from_slice_unchecked::<ReviewerEarnings>(&pda_earnings.data.borrow())?;
+    if !earnings_data.is_initialized() {
+        earnings_data.discriminator = ReviewerEarnings::DISCRIMINATOR.to_string();
+        earnings_data.is_initialized = true;
+        earnings_data.reviewer = *reviewer;
+    }
+    Ok(earnings_data)
+}
+
+/// The co-authors of `review`, from its co-authors account in the review
+/// program. Reviews without one have none.
+fn load_co_authors(
+    pda_co_authors: &AccountInfo,
+    review: &AccountInfo,
+    distributor_data: &DistributorState,
+) -> Result<ReviewCoAuthors, ProgramError> {
+    assert_pda(
+        pda_co_authors,
+        &[ReviewCoAuthors::SEED.as_bytes(), review.key.as_ref()],
+        &distributor_data.review_program,
+        DistributorError::InvalidPDA,
+    )?;
+    if pda_co_authors.owner != &distributor_data.review_program {
+        return Ok(ReviewCoAuthors {
+            discriminator: ReviewCoAuthors::DISCRIMINATOR,
+            is_initialized: false,
+            review: *review.key,
+            co_authors: Vec::new(),
+        });
+    }
+    let co_authors_data =
+        try_from_slice_unchecked::<ReviewCoAuthors>(&pda_co_authors.data.borrow())?;
+    if co_authors_data.discriminator != ReviewCoAuthors::DISCRIMINATOR {
+        msg!("Account is not a review's co-authors");
+        return Err(DistributorError::InvalidPDA.into());
+    }
+    Ok(co_authors_data)
+}
+
+/// Which balance of an earnings account a credit goes to.
+#[derive(Clone, Copy)]
+enum Balance {
+    Tokens,
+    Lamports,
+}
+
+/// Credits each co-author their part to their earnings, taken from
+/// `account_info_iter` in the order of `parts`, creating them at `payer`'s
+/// expense. Returns each earnings account with its part.
+fn credit_co_authors<'a, 'b>(
+    program_id: &Pubkey,
+    payer: &AccountInfo<'a>,
+    parts: &[(Pubkey, u64)],
+    account_info_iter: &mut std::slice::Iter<'b, AccountInfo<'a>>,
+    system_program: &AccountInfo<'a>,
+    balance: Balance,
+) -> Result<Vec<(&'b AccountInfo<'a>, u64)>, ProgramError> {
+    parts
+        .iter()
+        .map(|&(co_author, part)| {
+            let pda_earnings = next_account_info(account_info_iter)?;
+            let mut earnings_data =
+                load_earnings(program_id, payer, &co_author, pda_earnings, system_program)?;
+            let credited = match balance {
+                Balance::Tokens => &mut earnings_data.tokens,
+                Balance::Lamports => &mut earnings_data.lamports,
+            };
+            *credited = credited.checked_add(part).ok_or(CommonError::Overflow)?;
+            earnings_data.serialize(&mut &mut pda_earnings.data.borrow_mut()[..])?;
+            msg!("crediting {} to co-author {}", part, co_author);
+            Ok((pda_earnings, part))
+        })
+        .collect()
+}
+
+/// Moves `amount` reward tokens from the tipper into the earnings vault,
+/// credited to the author of `review` and its accepted co-authors, less the
+/// distributor's tip share, which goes to the treasury. An author's part at
+/// or above the vesting threshold goes to the reviewer's vesting vault
+/// instead, to be claimed with `ClaimVested` as it unlocks.
+///
+/// Accounts are `[tipper, distributor, review, tipper_token, earnings,
+/// earnings_vault, reward_mint, token_program, system_program, treasury,
+/// vesting, vesting_vault, review_program, review_config, global_stats,
+/// hot_feed, co_authors]`, followed by the earnings of each accepted
+/// co-author in listing order and the extra accounts of the reward mint's
+/// transfer hook, if it has one. The tipper pays for the earnings, vesting
+/// schedule and the vaults if they do not exist yet. The tip is counted in
+/// the review program's global stats and hot feed, which must have this
+/// program set as its tip program.
+pub fn tip(program_id: &Pubkey, accounts: &[AccountInfo], amount: u64) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let tipper = next_account_info(account_info_iter)?;
+    let pda_distributor = next_account_info(account_info_iter)?;
+    let review = next_account_info(account_info_iter)?;
+    let tipper_token = next_account_info(account_info_iter)?;
+    let pda_earnings = next_account_info(account_info_iter)?;
+    let pda_earnings_vault = next_account_info(account_info_iter)?;
+    let reward_mint = next_account_info(account_info_iter)?;
+    let token_program = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+    let pda_treasury = next_account_info(account_info_iter)?;
+    let pda_vesting = next_account_info(account_info_iter)?;
+    let pda_vault = next_account_info(account_info_iter)?;
+    let review_program = next_account_info(account_info_iter)?;
+    let review_config = next_account_info(account_info_iter)?;
+    let global_stats = next_account_info(account_info_iter)?;
+    let hot_feed = next_account_info(account_info_iter)?;
+    let pda_co_authors = next_account_info(account_info_iter)?;
+
+    assert_signer(tipper)?;
+    assert_owned_by(pda_distributor, program_id)?;
+    assert_token_program(token_program, reward_mint)?;
+
+    if amount == 0 {
+        msg!("Tip amount must be greater than zero");
+        return Err(DistributorError::InvalidTipAmount.into());
+    }
+
+    let distributor_data = load_distributor(pda_distributor)?;
+
+    assert_initialized(&distributor_data, DistributorError::UninitializedAccount)?;
+
+    if distributor_data.reward_mint != *reward_mint.key {
+        msg!("Mint is not the distributor's reward mint");
+        return Err(DistributorError::InvalidRewardMint.into());
+    }
+
+    let reviewer = load_reviewer(review, &distributor_data)?;
+    assert_program_id(review_program, &distributor_data.review_program)?;
+    assert_pda(
+        pda_treasury,
+        &[DistributorState::TREASURY_SEED.as_bytes()],
+        program_id,
+        DistributorError::InvalidPDA,
+    )?;
+
+    let (treasury_share, reviewer_share) = distributor_data
+        .split_tip(amount)
+        .ok_or(CommonError::Overflow)?;
+    let (author_share, co_author_parts) =
+        load_co_authors(pda_co_authors, review, &distributor_data)?
+            .split(reviewer_share)
+            .ok_or(CommonError::Overflow)?;
+    let co_author_share = reviewer_share - author_share;
+    let decimals = mint_decimals(reward_mint)?;
+
+    let vests = distributor_data.vests(author_share);
+    if !vests || co_author_share > 0 {
+        create_vault(
+            program_id,
+            tipper,
+            &[DistributorState::EARNINGS_VAULT_SEED.as_bytes()],
+            pda_distributor,
+            pda_earnings_vault,
+            reward_mint,
+            token_program,
+            system_program,
+        )?;
+    }
+    credit_co_authors(
+        program_id,
+        tipper,
+        &co_author_parts,
+        account_info_iter,
+        system_program,
+        Balance::Tokens,
+    )?;
+    let hook_accounts = account_info_iter.as_slice();
+
+    let (vesting_share, earnings_share) = if vests {
+        create_vault(
+            program_id,
+            tipper,
+            &[
+                DistributorState::VESTING_VAULT_SEED.as_bytes(),
+                reviewer.as_ref(),
+            ],
+            pda_distributor,
+            pda_vault,
+            reward_mint,
+            token_program,
+            system_program,
+        )?;
+        let mut vesting_data =
+            load_vesting(program_id, tipper, &reviewer, pda_vesting, system_program)?;
+        vesting_data
+            .add(
+                author_share,
+                Clock::get()?.unix_timestamp,
+                distributor_data.tip_vesting_duration,
+            )
+            .ok_or(CommonError::Overflow)?;
+        vesting_data.serialize(&mut &mut pda_vesting.data.borrow_mut()[..])?;
+        msg!("vesting {} for reviewer {}", author_share, reviewer);
+        (author_share, co_author_share)
+    } else {
+        let mut earnings_data =
+            load_earnings(program_id, tipper, &reviewer, pda_earnings, system_program)?;
+        earnings_data.tokens = earnings_data
+            .tokens
+            .checked_add(author_share)
+            .ok_or(CommonError::Overflow)?;
+        earnings_data.serialize(&mut &mut pda_earnings.data.borrow_mut()[..])?;
+        msg!("crediting {} to reviewer {}", author_share, reviewer);
+        (0, reviewer_share)
+    };
+
+    for (destination, share) in [
+        (pda_vault, vesting_share),
+        (pda_earnings_vault, earnings_share),
+        (pda_treasury, treasury_share),
+    ] {
+        if share == 0 {
+            continue;
+        }
+        msg!("tipping {} to {}", share, destination.key);
+        invoke_transfer_checked(
+            token_program.key,
+            tipper_token.clone(),
+            reward_mint.clone(),
+            destination.clone(),
+            tipper.clone(),
+            hook_accounts,
+            share,
+            decimals,
+            &[],
+        )?;
+    }
+    emit(&ReviewEvent::TipReceived {
+        recipient: reviewer,
+        review: *review.key,
+        tipper: *tipper.key,
+        amount: reviewer_share,
+    });
+
+    invoke_signed(
+        &record_tip(
+            review_program.key,
+            pda_distributor.key,
+            tipper.key,
+            review.key,
+            amount,
+        ),
+        &[
+            pda_distributor.clone(),
+            review_config.clone(),
+            global_stats.clone(),
+            tipper.clone(),
+            system_program.clone(),
+            review.clone(),
+            hot_feed.clone(),
+        ],
+        &[&[DistributorState::SEED.as_bytes(), &[distributor_data.bump]]],
+    )
+}
+
+/// Changes the reward mint's interest rate, in basis points. Only the
+/// distributor authority may call it; the distributor PDA signs as the mint's
+/// rate authority.
+pub fn update_interest_rate(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    rate: i16,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let authority = next_account_info(account_info_iter)?;
+    let pda_distributor = next_account_info(account_info_iter)?;
+    let reward_mint = next_account_info(account_info_iter)?;
+    let token_program = next_account_info(account_info_iter)?;
+
+    assert_signer(authority)?;
+    assert_owned_by(pda_distributor, program_id)?;
+    assert_program_id(token_program, &spl_token_2022::ID)?;
+    assert_owned_by(reward_mint, token_program.key)?;
+
+    let distributor_data = load_distributor(pda_distributor)?;
+
+    assert_initialized(&distributor_data, DistributorError::UninitializedAccount)?;
+
+    if distributor_data.authority != *authority.key {
+        msg!("Signer is not the distributor authority");
+        return Err(DistributorError::InvalidAuthority.into());
+    }
+    if distributor_data.reward_mint != *reward_mint.key {
+        msg!("Mint is not the distributor's reward mint");
+        return Err(DistributorError::InvalidRewardMint.into());
+    }
+
+    msg!("updating interest rate to {} bps", rate);
+    invoke_signed(
+        &interest_bearing_mint::instruction::update_rate(
+            token_program.key,
+            reward_mint.key,
+            pda_distributor.key,
+            &[],
+            rate,
+        )?,
+        &[reward_mint.clone(), pda_distributor.clone()],
+        &[&[DistributorState::SEED.as_bytes(), &[distributor_data.bump]]],
+    )
+}
+
+/// Tips the author of `review` without revealing the amount. Both token
+/// accounts must be configured for confidential transfers, and the tip is
+/// paid from the tipper's encrypted available balance. The amount is hidden
+/// from the program, so the treasury takes no share and the review's
+/// co-authors get none of it.
+///
+/// A single transfer proof does not fit in a transaction, so the client
+/// verifies it in three parts into context state accounts first and closes
+/// them afterwards.
+///
+/// Accounts are `[tipper, distributor, review, tipper_token, reviewer_token,
+/// reward_mint, token_program, equality_proof, ciphertext_validity_proof,
+/// range_proof]`.
+pub fn confidential_tip(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    new_decryptable_available_balance: &[u8; 36],
+    source_decrypt_handles: &[u8; 64],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let tipper = next_account_info(account_info_iter)?;
+    let pda_distributor = next_account_info(account_info_iter)?;
+    let review = next_account_info(account_info_iter)?;
+    let tipper_token = next_account_info(account_info_iter)?;
+    let reviewer_token = next_account_info(account_info_iter)?;
+    let reward_mint = next_account_info(account_info_iter)?;
+    let token_program = next_account_info(account_info_iter)?;
+    let equality_proof = next_account_info(account_info_iter)?;
+    let ciphertext_validity_proof = next_account_info(account_info_iter)?;
+    let range_proof = next_account_info(account_info_iter)?;
+
+    assert_signer(tipper)?;
+    assert_owned_by(pda_distributor, program_id)?;
+    assert_program_id(token_program, &spl_token_2022::ID)?;
+    assert_owned_by(reward_mint, token_program.key)?;
+    for proof in [equality_proof, ciphertext_validity_proof, range_proof] {
+        assert_owned_by(proof, &zk_token_proof_program::id())?;
+    }
+
+    let distributor_data = load_distributor(pda_distributor)?;
+
+    assert_initialized(&distributor_data, DistributorError::UninitializedAccount)?;
+
+    if distributor_data.reward_mint != *reward_mint.key {
+        msg!("Mint is not the distributor's reward mint");
+        return Err(DistributorError::InvalidRewardMint.into());
+    }
+
+    let reviewer = load_reviewer(review, &distributor_data)?;
+    assert_reviewer_token_account(reviewer_token, &reviewer, reward_mint.key)?;
+
+    let new_decryptable_available_balance =
+        *bytemuck::try_from_bytes::<DecryptableBalance>(new_decryptable_available_balance)
+            .map_err(|_| ProgramError::InvalidInstructionData)?;
+    let source_decrypt_handles =
+        bytemuck::try_from_bytes::<SourceDecryptHandles>(source_decrypt_handles)
+            .map_err(|_| ProgramError::InvalidInstructionData)?;
+
+    // The context accounts stay open so a failed tip can be retried with the
+    // same proofs; the client closes them once the tip lands
+    msg!("tipping an encrypted amount to reviewer {}", reviewer);
+    invoke(
+        &confidential_transfer::instruction::transfer_with_split_proofs(
+            token_program.key,
+            tipper_token.key,
+            reward_mint.key,
+            reviewer_token.key,
+            new_decryptable_available_balance,
+            tipper.key,
+            TransferSplitContextStateAccounts {
+                equality_proof: equality_proof.key,
+                ciphertext_validity_proof: ciphertext_validity_proof.key,
+                range_proof: range_proof.key,
+                authority: tipper.key,
+                no_op_on_uninitialized_split_context_state: false,
+                close_split_context_state_accounts: None,
+            },
+            source_decrypt_handles,
+        )?,
+        &[
+            tipper_token.clone(),
+            reward_mint.clone(),
+            reviewer_token.clone(),
+            equality_proof.clone(),
+            ciphertext_validity_proof.clone(),
+            range_proof.clone(),
+            tipper.clone(),
+        ],
+    )
+}
+
+/// Attaches Token Metadata to the reward mint so wallets show its name,
+/// symbol and icon. The distributor PDA signs as mint authority and becomes
+/// the metadata's update authority.
+///
+/// Accounts are `[authority, distributor, reward_mint, metadata,
+/// token_metadata_program, system_program]`.
+pub fn create_reward_metadata(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    symbol: String,
+    uri: String,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let authority = next_account_info(account_info_iter)?;
+    let pda_distributor = next_account_info(account_info_iter)?;
+    let reward_mint = next_account_info(account_info_iter)?;
+    let metadata = next_account_info(account_info_iter)?;
+    let token_metadata_program = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+
+    assert_signer(authority)?;
+    assert_owned_by(pda_distributor, program_id)?;
+    assert_program_id(token_metadata_program, &mpl_token_metadata::ID)?;
+
+    let distributor_data = load_distributor(pda_distributor)?;
+
+    assert_initialized(&distributor_data, DistributorError::UninitializedAccount)?;
+
+    if distributor_data.authority != *authority.key {
+        msg!("Signer is not the distributor authority");
+        return Err(DistributorError::InvalidAuthority.into());
+    }
+    if distributor_data.reward_mint != *reward_mint.key {
+        msg!("Mint is not the distributor's reward mint");
+        return Err(DistributorError::InvalidRewardMint.into());
+    }
+    if Metadata::find_pda(reward_mint.key).0 != *metadata.key {
+        msg!("Invalid seeds for PDA");
+        return Err(DistributorError::InvalidPDA.into());
+    }
+
+    msg!("creating reward mint metadata");
+    CreateMetadataAccountV3CpiBuilder::new(token_metadata_program)
+        .metadata(metadata)
+        .mint(reward_mint)
+        .mint_authority(pda_distributor)
+        .payer(authority)
+        .update_authority(pda_distributor, true)
+        .system_program(system_program)
+        .data(DataV2 {
+            name: DistributorState::REWARD_TOKEN_NAME.to_string(),
+            symbol,
+            uri,
+            seller_fee_basis_points: 0,
+            creators: None,
+            collection: None,
+            uses: None,
+        })
+        .is_mutable(true)
+        .invoke_signed(&[&[DistributorState::SEED.as_bytes(), &[distributor_data.bump]]])
+}
+
+/// Loads the donor's receipt, creating it on their first donation.
+fn load_receipt<'a>(
+    program_id: &Pubkey,
+    donor: &AccountInfo<'a>,
+    pda_receipt: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+) -> Result<DonationReceipt, ProgramError> {
+    let bump_seed = assert_pda(
+        pda_receipt,
+        &[DonationReceipt::SEED.as_bytes(), donor.key.as_ref()],
+        program_id,
+        DistributorError::InvalidPDA,
+    )?;
+
+    if pda_receipt.owner != program_id {
+        msg!("creating donation receipt");
+        create_pda_account(
+            donor,
+            pda_receipt,
+            system_program,
+            DonationReceipt::get_account_size(),
+            program_id,
+            &[
+                DonationReceipt::SEED.as_bytes(),
+                donor.key.as_ref(),
+                &[bump_seed],
+            ],
+        )?;
+    }
+
+    let mut receipt_data = try_from_slice_unchecked::<DonationReceipt>(&pda_receipt.data.borrow())?;
+    if !receipt_data.is_initialized() {
+        receipt_data.discriminator = DonationReceipt::DISCRIMINATOR.to_string();
+        receipt_data.is_initialized = true;
+        receipt_data.donor = *donor.key;
+    }
+    Ok(receipt_data)
+}
+
+/// Donates `amount` lamports. They are held by the distributor PDA, above its
+/// rent exemption, to fund sponsorships.
+///
+/// Accounts are `[donor, distributor, receipt, system_program]`.
+pub fn donate_sol(program_id: &Pubkey, accounts: &[AccountInfo], amount: u64) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let donor = next_account_info(account_info_iter)?;
+    let pda_distributor = next_account_info(account_info_iter)?;
+    let pda_receipt = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+
+    assert_signer(donor)?;
+    assert_owned_by(pda_distributor, program_id)?;
+    assert_pda(
+        pda_distributor,
+        &[DistributorState::SEED.as_bytes()],
+        program_id,
+        DistributorError::InvalidPDA,
+    )?;
+
+    if amount == 0 {
+        msg!("Donation amount must be greater than zero");
+        return Err(DistributorError::InvalidDonationAmount.into());
+    }
+
+    let mut receipt_data = load_receipt(program_id, donor, pda_receipt, system_program)?;
+    receipt_data.lamports = receipt_data
+        .lamports
+        .checked_add(amount)
+        .ok_or(CommonError::Overflow)?;
+
+    invoke(
+        &system_instruction::transfer(donor.key, pda_distributor.key, amount),
+        &[
+            donor.clone(),
+            pda_distributor.clone(),
+            system_program.clone(),
+        ],
+    )?;
+
+    receipt_data.serialize(&mut &mut pda_receipt.data.borrow_mut()[..])?;
+    msg!("{} donated {} lamports", donor.key, amount);
+
+    Ok(())
+}
+
+/// Donates `amount` reward tokens into the treasury, which funds the weekly
+/// distributions.
+///
+/// Accounts are `[donor, distributor, receipt, donor_token, treasury,
+/// reward_mint, token_program, system_program]`, followed by the extra
+/// accounts of the reward mint's transfer hook, if it has one.
+pub fn donate_tokens(program_id: &Pubkey, accounts: &[AccountInfo], amount: u64) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let donor = next_account_info(account_info_iter)?;
+    let pda_distributor = next_account_info(account_info_iter)?;
+    let pda_receipt = next_account_info(account_info_iter)?;
+    let donor_token = next_account_info(account_info_iter)?;
+    let pda_treasury = next_account_info(account_info_iter)?;
+    let reward_mint = next_account_info(account_info_iter)?;
+    let token_program = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+    let hook_accounts = account_info_iter.as_slice();
+
+    assert_signer(donor)?;
+    assert_owned_by(pda_distributor, program_id)?;
+    assert_token_program(token_program, reward_mint)?;
+
+    if amount == 0 {
+        msg!("Donation amount must be greater than zero");
+        return Err(DistributorError::InvalidDonationAmount.into());
+    }
+
+    let distributor_data = load_distributor(pda_distributor)?;
+
+    assert_initialized(&distributor_data, DistributorError::UninitializedAccount)?;
+
+    if distributor_data.reward_mint != *reward_mint.key {
+        msg!("Mint is not the distributor's reward mint");
+        return Err(DistributorError::InvalidRewardMint.into());
+    }
+    assert_pda(
+        pda_treasury,
+        &[DistributorState::TREASURY_SEED.as_bytes()],
+        program_id,
+        DistributorError::InvalidPDA,
+    )?;
+
+    let mut receipt_data = load_receipt(program_id, donor, pda_receipt, system_program)?;
+    receipt_data.tokens = receipt_data
+        .tokens
+        .checked_add(amount)
+        .ok_or(CommonError::Overflow)?;
+
+    invoke_transfer_checked(
+        token_program.key,
+        donor_token.clone(),
+        reward_mint.clone(),
+        pda_treasury.clone(),
+        donor.clone(),
+        hook_accounts,
+        amount,
+        mint_decimals(reward_mint)?,
+        &[],
+    )?;
+
+    receipt_data.serialize(&mut &mut pda_receipt.data.borrow_mut()[..])?;
+    msg!("{} donated {} reward tokens", donor.key, amount);
+
+    Ok(())
+}
+
+/// Sets the share of each tip paid into the treasury. Only the distributor
+/// authority may call it.
+///
+/// Accounts are `[authority, distributor, system_program]`. The system
+/// program is only needed to grow a distributor created before the tip share.
+pub fn set_tip_share(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    treasury_bps: u16,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let authority = next_account_info(account_info_iter)?;
+    let pda_distributor = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter).ok();
+
+    assert_signer(authority)?;
+    assert_owned_by(pda_distributor, program_id)?;
+
+    let mut distributor_data = load_distributor(pda_distributor)?;
+
+    assert_initialized(&distributor_data, DistributorError::UninitializedAccount)?;
+
+    if distributor_data.authority != *authority.key {
+        msg!("Signer is not the distributor authority");
+        return Err(DistributorError::InvalidAuthority.into());
+    }
+    if treasury_bps > DistributorState::MAX_BPS {
+        msg!("Tip share exceeds 10000 basis points");
+        return Err(DistributorError::InvalidTipShare.into());
+    }
+
+    distributor_data.tip_treasury_bps = treasury_bps;
+
+    if pda_distributor.data_len() < DistributorState::get_account_size() {
+        let system_program = system_program.ok_or(ProgramError::NotEnoughAccountKeys)?;
+        resize_account(
+            pda_distributor,
+            authority,
+            system_program,
+            DistributorState::get_account_size(),
+        )?;
+    }
+
+    assert_rent_exempt(pda_distributor, CommonError::NotRentExempt)?;
+    distributor_data.serialize(&mut &mut pda_distributor.data.borrow_mut()[..])?;
+    msg!("tip share set to {} bps", treasury_bps);
+
+    Ok(())
+}
+
+/// Sets how large tips vest. Only the distributor authority may call it, and
+/// the new duration applies from each schedule's next tip.
+///
+/// Accounts are `[authority, distributor, system_program]`. The system
+/// program is only needed to grow a distributor created before vesting.
+pub fn set_tip_vesting(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    threshold: u64,
+    duration: i64,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let authority = next_account_info(account_info_iter)?;
+    let pda_distributor = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter).ok();
+
+    assert_signer(authority)?;
+    assert_owned_by(pda_distributor, program_id)?;
+
+    let mut distributor_data = load_distributor(pda_distributor)?;
+
+    assert_initialized(&distributor_data, DistributorError::UninitializedAccount)?;
+
+    if distributor_data.authority != *authority.key {
+        msg!("Signer is not the distributor authority");
+        return Err(DistributorError::InvalidAuthority.into());
+    }
+    if threshold > 0 && duration <= 0 {
+        msg!("Vesting duration must be positive");
+        return Err(DistributorError::InvalidVestingDuration.into());
+    }
+
+    distributor_data.tip_vesting_threshold = threshold;
+    distributor_data.tip_vesting_duration = duration;
+
+    if pda_distributor.data_len() < DistributorState::get_account_size() {
+        let system_program = system_program.ok_or(ProgramError::NotEnoughAccountKeys)?;
+        resize_account(
+            pda_distributor,
+            authority,
+            system_program,
+            DistributorState::get_account_size(),
+        )?;
+    }
+
+    assert_rent_exempt(pda_distributor, CommonError::NotRentExempt)?;
+    distributor_data.serialize(&mut &mut pda_distributor.data.borrow_mut()[..])?;
+    msg!(
+        "tips of {} or more vest over {} seconds",
+        threshold,
+        duration
+    );
+
+    Ok(())
+}
+
+/// Pays the reviewer everything their vesting schedule has unlocked so far,
+/// out of their vesting vault. The distributor PDA signs as the vault's
+/// authority.
+///
+/// Accounts are `[reviewer, distributor, vesting, vesting_vault,
+/// reviewer_token, reward_mint, token_program]`, followed by the extra
+/// accounts of the reward mint's transfer hook, if it has one.
+pub fn claim_vested(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let reviewer = next_account_info(account_info_iter)?;
+    let pda_distributor = next_account_info(account_info_iter)?;
+    let pda_vesting = next_account_info(account_info_iter)?;
+    let pda_vault = next_account_info(account_info_iter)?;
+    let reviewer_token = next_account_info(account_info_iter)?;
+    let reward_mint = next_account_info(account_info_iter)?;
+    let token_program = next_account_info(account_info_iter)?;
+    let hook_accounts = account_info_iter.as_slice();
+
+    assert_signer(reviewer)?;
+    assert_owned_by(pda_distributor, program_id)?;
+    assert_owned_by(pda_vesting, program_id)?;
+    assert_token_program(token_program, reward_mint)?;
+
+    let distributor_data = load_distributor(pda_distributor)?;
+
+    assert_initialized(&distributor_data, DistributorError::UninitializedAccount)?;
+
+    if distributor_data.reward_mint != *reward_mint.key {
+        msg!("Mint is not the distributor's reward mint");
+        return Err(DistributorError::InvalidRewardMint.into());
+    }
+
+    assert_pda(
+        pda_vesting,
+        &[VestingSchedule::SEED.as_bytes(), reviewer.key.as_ref()],
+        program_id,
+        DistributorError::InvalidPDA,
+    )?;
+    assert_pda(
+        pda_vault,
+        &[
+            DistributorState::VESTING_VAULT_SEED.as_bytes(),
+            reviewer.key.as_ref(),
+        ],
+        program_id,
+        DistributorError::InvalidPDA,
+    )?;
+    assert_reviewer_token_account(reviewer_token, reviewer.key, reward_mint.key)?;
+
+    let mut vesting_data = try_from_slice_unchecked::<VestingSchedule>(&pda_vesting.data.borrow())?;
+
+    assert_initialized(&vesting_data, DistributorError::UninitializedAccount)?;
+
+    let amount = vesting_data
+        .claim(Clock::get()?.unix_timestamp)
+        .ok_or(CommonError::Overflow)?;
+    if amount == 0 {
+        msg!("Nothing has vested yet");
+        return Err(DistributorError::NothingToClaim.into());
+    }
+
+    msg!("paying {} vested tokens to {}", amount, reviewer.key);
+    invoke_transfer_checked(
+        token_program.key,
+        pda_vault.clone(),
+        reward_mint.clone(),
+        reviewer_token.clone(),
+        pda_distributor.clone(),
+        hook_accounts,
+        amount,
+        mint_decimals(reward_mint)?,
+        &[&[DistributorState::SEED.as_bytes(), &[distributor_data.bump]]],
+    )?;
+
+    vesting_data.serialize(&mut &mut pda_vesting.data.borrow_mut()[..])?;
+
+    Ok(())
+}
+
+/// Tips `amount` lamports to the author of `review`. The reviewer's share,
+/// split with the review's accepted co-authors, accrues in their earnings
+/// accounts; the distributor's tip share joins the donated lamports held by
+/// the distributor PDA.
+///
+/// Accounts are `[tipper, distributor, review, earnings, system_program,
+/// review_program, review_config, global_stats, hot_feed, co_authors]`,
+/// followed by the earnings of each accepted co-author in listing order. The
+/// tipper pays for earnings accounts that do not exist yet. The tip is
+/// counted in the review program's global stats and hot feed, as in `tip`.
+pub fn tip_sol(program_id: &Pubkey, accounts: &[AccountInfo], amount: u64) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let tipper = next_account_info(account_info_iter)?;
+    let pda_distributor = next_account_info(account_info_iter)?;
+    let review = next_account_info(account_info_iter)?;
+    let pda_earnings = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+    let review_program = next_account_info(account_info_iter)?;
+    let review_config = next_account_info(account_info_iter)?;
+    let global_stats = next_account_info(account_info_iter)?;
+    let hot_feed = next_account_info(account_info_iter)?;
+    let pda_co_authors = next_account_info(account_info_iter)?;
+
+    assert_signer(tipper)?;
+    assert_owned_by(pda_distributor, program_id)?;
+
+    if amount == 0 {
+        msg!("Tip amount must be greater than zero");
+        return Err(DistributorError::InvalidTipAmount.into());
+    }
+
+    let distributor_data = load_distributor(pda_distributor)?;
+
+    assert_initialized(&distributor_data, DistributorError::UninitializedAccount)?;
+
+    let reviewer = load_reviewer(review, &distributor_data)?;
+    assert_program_id(review_program, &distributor_data.review_program)?;
+    let (treasury_share, reviewer_share) = distributor_data
+        .split_tip(amount)
+        .ok_or(CommonError::Overflow)?;
+    let (author_share, co_author_parts) =
+        load_co_authors(pda_co_authors, review, &distributor_data)?
+            .split(reviewer_share)
+            .ok_or(CommonError::Overflow)?;
+
+    let co_author_earnings = credit_co_authors(
+        program_id,
+        tipper,
+        &co_author_parts,
+        account_info_iter,
+        system_program,
+        Balance::Lamports,
+    )?;
+    // Loaded after the co-authors' credits, which it includes if the
+    // reviewer is also listed as a co-author
+    let mut earnings_data =
+        load_earnings(program_id, tipper, &reviewer, pda_earnings, system_program)?;
+    earnings_data.lamports = earnings_data
+        .lamports
+        .checked_add(author_share)
+        .ok_or(CommonError::Overflow)?;
+    earnings_data.serialize(&mut &mut pda_earnings.data.borrow_mut()[..])?;
+
+    for (destination, share) in [
+        (pda_earnings, author_share),
+        (pda_distributor, treasury_share),
+    ]
+    .into_iter()
+    .chain(co_author_earnings)
+    {
+        if share == 0 {
+            continue;
+        }
+        msg!("tipping {} lamports to {}", share, destination.key);
+        invoke(
+            &system_instruction::transfer(tipper.key, destination.key, share),
+            &[tipper.clone(), destination.clone(), system_program.clone()],
+        )?;
+    }
+
+    invoke_signed(
+        &record_sol_tip(
+            review_program.key,
+            pda_distributor.key,
+            tipper.key,
+            review.key,
+            amount,
+        ),
+        &[
+            pda_distributor.clone(),
+            review_config.clone(),
+            global_stats.clone(),
+            tipper.clone(),
+            system_program.clone(),
+            review.clone(),
+            hot_feed.clone(),
+        ],
+        &[&[DistributorState::SEED.as_bytes(), &[distributor_data.bump]]],
+    )
+}
+
+/// Pays the reviewer everything they have accrued: the reward tokens out of
+/// the earnings vault, signed for by the distributor PDA, and the lamports
+/// held by their earnings account.
+///
+/// Accounts are `[reviewer, distributor, earnings, earnings_vault,
+/// reviewer_token, reward_mint, token_program, associated_token_program,
+/// system_program]`, followed by the extra accounts of the reward mint's
+/// transfer hook, if it has one. The reviewer pays for their associated
+/// token account if tokens are owed and it does not exist yet.
+pub fn claim_earnings(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let reviewer = next_account_info(account_info_iter)?;
+
+    assert_signer(reviewer)?;
+
+    pay_earnings(program_id, reviewer, reviewer, account_info_iter.as_slice())
+}
+
+/// Pays the reviewer their earnings like `claim_earnings`, signed for by a
+/// session key the reviewer opened in the review program with
+/// `Session::SCOPE_CLAIM`. Everything is still paid to the reviewer; the
+/// session key only pays for their associated token account if needed.
+///
+/// Accounts are `[session_key, session, reviewer, distributor, earnings,
+/// earnings_vault, reviewer_token, reward_mint, token_program,
+/// associated_token_program, system_program]`, followed by any transfer hook
+/// accounts.
+pub fn claim_earnings_with_session(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let session_key = next_account_info(account_info_iter)?;
+    let pda_session = next_account_info(account_info_iter)?;
+    let reviewer = next_account_info(account_info_iter)?;
+
+    assert_signer(session_key)?;
+
+    let rest = account_info_iter.as_slice();
+    let pda_distributor = rest.first().ok_or(ProgramError::NotEnoughAccountKeys)?;
+    assert_owned_by(pda_distributor, program_id)?;
+    let distributor_data = load_distributor(pda_distributor)?;
+
+    // Only the review program's CreateSession writes an account with the
+    // session discriminator, so its owner and contents are proof enough
+    if pda_session.owner != &distributor_data.review_program
+        || pda_session.data.borrow().first() != Some(&Session::DISCRIMINATOR)
+    {
+        msg!("Account is not a review program session");
+        return Err(DistributorError::InvalidSession.into());
+    }
+    let session = try_from_slice_unchecked::<Session>(&pda_session.data.borrow())?;
+    if session.authority != *reviewer.key
+        || session.session_key != *session_key.key
+        || !session.allows(Session::SCOPE_CLAIM, Clock::get()?.slot)
+    {
+        msg!("Session is expired or cannot claim for {}", reviewer.key);
+        return Err(DistributorError::InvalidSession.into());
+    }
+
+    pay_earnings(program_id, session_key, reviewer, rest)
+}
+
+/// Pays `reviewer`'s earnings once the caller has authorized the claim;
+/// `payer` creates the reviewer's token account if it is missing.
+fn pay_earnings<'a>(
+    program_id: &Pubkey,
+    payer: &AccountInfo<'a>,
+    reviewer: &AccountInfo<'a>,
+    accounts: &[AccountInfo<'a>],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let pda_distributor = next_account_info(account_info_iter)?;
+    let pda_earnings = next_account_info(account_info_iter)?;
+    let pda_earnings_vault = next_account_info(account_info_iter)?;
+    let reviewer_token = next_account_info(account_info_iter)?;
+    let reward_mint = next_account_info(account_info_iter)?;
+    let token_program = next_account_info(account_info_iter)?;
+    let associated_token_program = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+    let hook_accounts = account_info_iter.as_slice();
+
+    assert_owned_by(pda_distributor, program_id)?;
+    assert_owned_by(pda_earnings, program_id)?;
+    assert_token_program(token_program, reward_mint)?;
+    assert_program_id(associated_token_program, &spl_associated_token_account::ID)?;
+
+    let distributor_data = load_distributor(pda_distributor)?;
+
+    assert_initialized(&distributor_data, DistributorError::UninitializedAccount)?;
+
+    if distributor_data.reward_mint != *reward_mint.key {
+        msg!("Mint is not the distributor's reward mint");
+        return Err(DistributorError::InvalidRewardMint.into());
+    }
+
+    assert_pda(
+        pda_earnings,
+        &[ReviewerEarnings::SEED.as_bytes(), reviewer.key.as_ref()],
+        program_id,
+        DistributorError::InvalidPDA,
+    )?;
+    assert_pda(
+        pda_earnings_vault,
+        &[DistributorState::EARNINGS_VAULT_SEED.as_bytes()],
+        program_id,
+        DistributorError::InvalidPDA,
+    )?;
+
+    let mut earnings_data =
+        try_from_slice_unchecked::<ReviewerEarnings>(&pda_earnings.data.borrow())?;
+
+    assert_initialized(&earnings_data, DistributorError::UninitializedAccount)?;
+
+    if earnings_data.tokens == 0 && earnings_data.lamports == 0 {
+        msg!("No earnings to claim");
+        return Err(DistributorError::NoEarnings.into());
+    }
+
+    if earnings_data.tokens > 0 {
+        create_reviewer_token_account(
+            payer,
+            reviewer,
+            reviewer_token,
+            reward_mint,
+            token_program,
+            associated_token_program,
+            system_program,
+        )?;
+        assert_reviewer_token_account(reviewer_token, reviewer.key, reward_mint.key)?;
+
+        msg!("paying {} tokens to {}", earnings_data.tokens, reviewer.key);
+        invoke_transfer_checked(
+            token_program.key,
+            pda_earnings_vault.clone(),
+            reward_mint.clone(),
+            reviewer_token.clone(),
+            pda_distributor.clone(),
+            hook_accounts,
+            earnings_data.tokens,
+            mint_decimals(reward_mint)?,
+            &[&[DistributorState::SEED.as_bytes(), &[distributor_data.bump]]],
+        )?;
+    }
+    // Everything above rent exemption is owed, including lamports sent to
+    // the account directly
+    let lamports = withdraw_excess_lamports(pda_earnings, reviewer)?;
+    msg!("paid {} lamports to {}", lamports, reviewer.key);
+
+    earnings_data.tokens = 0;
+    earnings_data.lamports = 0;
+    earnings_data.serialize(&mut &mut pda_earnings.data.borrow_mut()[..])?;
+
+    Ok(())
+}
+
+/// Sets the reward mint's emission schedule. Only the distributor authority
+/// may call it. Halvings count from the epoch the first schedule was set, and
+/// the cap can only be lowered afterwards, never below what was already
+/// emitted. A zero `amount_per_action` stops emission.
+///
+/// Accounts are `[authority, distributor, system_program]`. The system
+/// program is only needed to grow a distributor created before emission.
+pub fn set_emission_schedule(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    amount_per_action: u64,
+    halving_epochs: u64,
+    cap: u64,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let authority = next_account_info(account_info_iter)?;
+    let pda_distributor = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter).ok();
+
+    assert_signer(authority)?;
+    assert_owned_by(pda_distributor, program_id)?;
+
+    let mut distributor_data = load_distributor(pda_distributor)?;
+
+    assert_initialized(&distributor_data, DistributorError::UninitializedAccount)?;
+
+    if distributor_data.authority != *authority.key {
+        msg!("Signer is not the distributor authority");
+        return Err(DistributorError::InvalidAuthority.into());
+    }
+    let first_schedule = distributor_data.emission_cap == 0;
+    if cap == 0
+        || cap < distributor_data.emitted
+        || (!first_schedule && cap > distributor_data.emission_cap)
+    {
+        msg!("Emission cap must be positive, not raised and not below what was emitted");
+        return Err(DistributorError::InvalidEmissionSchedule.into());
+    }
+
+    if first_schedule {
+        distributor_data.emission_start_epoch = Clock::get()?.epoch;
+    }
+    distributor_data.emission_per_action = amount_per_action;
+    distributor_data.emission_halving_epochs = halving_epochs;
+    distributor_data.emission_cap = cap;
+
+    if pda_distributor.data_len() < DistributorState::get_account_size() {
+        let system_program = system_program.ok_or(ProgramError::NotEnoughAccountKeys)?;
+        resize_account(
+            pda_distributor,
+            authority,
+            system_program,
+            DistributorState::get_account_size(),
+        )?;
+    }
+
+    assert_rent_exempt(pda_distributor, CommonError::NotRentExempt)?;
+    distributor_data.serialize(&mut &mut pda_distributor.data.borrow_mut()[..])?;
+    msg!(
+        "emitting {} per action, halving every {} epochs, capped at {}",
+        amount_per_action,
+        halving_epochs,
+        cap
+    );
+
+    Ok(())
+}
@@ -0,0 +1,277 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::clock::{Epoch, UnixTimestamp};
+use solana_program::program_pack::{IsInitialized, Sealed};
+use solana_program::pubkey::Pubkey;
+
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct DistributorState {
+    pub discriminator: String,
+    pub is_initialized: bool,
+    pub authority: Pubkey,
+    pub review_program: Pubkey,
+    pub reward_mint: Pubkey,
+    pub weekly_budget: u64,
+    pub last_distribution: UnixTimestamp,
+    pub bump: u8,
+    /// Share of each tip paid into the treasury, in basis points.
+    pub tip_treasury_bps: u16,
+    /// Reviewer shares of at least this many tokens vest instead of being
+    /// paid out. Zero disables vesting.
+    pub tip_vesting_threshold: u64,
+    /// Seconds over which a vested tip unlocks.
+    pub tip_vesting_duration: UnixTimestamp,
+    /// Reward tokens minted per reviewer credited by `Distribute`, before
+    /// halvings. Zero disables emission.
+    pub emission_per_action: u64,
+    /// Epochs between halvings of `emission_per_action`. Zero never halves.
+    pub emission_halving_epochs: u64,
+    /// Epoch the first emission schedule was set, which halvings count from.
+    pub emission_start_epoch: Epoch,
+    /// Most reward tokens emission may ever mint. It can only be lowered once
+    /// set.
+    pub emission_cap: u64,
+    /// Reward tokens minted by emission so far.
+    pub emitted: u64,
+}
+
+impl Sealed for DistributorState {}
+
+impl IsInitialized for DistributorState {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+impl DistributorState {
+    pub const DISCRIMINATOR: &'static str = "distributor";
+    pub const SEED: &'static str = "distributor";
+    pub const TREASURY_SEED: &'static str = "treasury";
+    pub const REWARD_MINT_SEED: &'static str = "reward_mint";
+    pub const VESTING_VAULT_SEED: &'static str = "vesting_vault";
+    pub const EARNINGS_VAULT_SEED: &'static str = "earnings_vault";
+    pub const REWARD_MINT_DECIMALS: u8 = 6;
+    pub const REWARD_TOKEN_NAME: &'static str = "Movie Review Token";
+    pub const DISTRIBUTION_INTERVAL: UnixTimestamp = 7 * 24 * 60 * 60;
+    pub const MAX_BPS: u16 = 10_000;
+    /// Emission bonus per consecutive epoch a reviewer posted in beyond the
+    /// first, in basis points.
+    pub const STREAK_BONUS_BPS: u16 = 1_000;
+    /// Most a streak adds to a reviewer's emission, in basis points.
+    pub const MAX_STREAK_BONUS_BPS: u16 = 10_000;
+
+    pub fn get_account_size() -> usize {
+        (4 + DistributorState::DISCRIMINATOR.len())
+            + 1
+            + 32
+            + 32
+            + 32
+            + 8
+            + 8
+            + 1
+            + 2
+            + 8
+            + 8
+            + 8
+            + 8
+            + 8
+            + 8
+            + 8
+    }
+
+    /// Splits a tip into the treasury's and the reviewer's share, rounding the
+    /// treasury's share down.
+    pub fn split_tip(&self, amount: u64) -> Option<(u64, u64)> {
+        let treasury_share = u64::try_from(
+            (amount as u128).checked_mul(self.tip_treasury_bps as u128)?
+                / DistributorState::MAX_BPS as u128,
+        )
+        .ok()?;
+        Some((treasury_share, amount.checked_sub(treasury_share)?))
+    }
+
+    pub fn vests(&self, reviewer_share: u64) -> bool {
+        self.tip_vesting_threshold > 0 && reviewer_share >= self.tip_vesting_threshold
+    }
+
+    /// Reward tokens emitted per action in `epoch`: `emission_per_action`
+    /// halved once for every `emission_halving_epochs` since the schedule
+    /// started, down to zero.
+    pub fn emission_at(&self, epoch: Epoch) -> u64 {
+        if self.emission_halving_epochs == 0 {
+            return self.emission_per_action;
+        }
+        let halvings =
+            epoch.saturating_sub(self.emission_start_epoch) / self.emission_halving_epochs;
+        u32::try_from(halvings)
+            .ok()
+            .and_then(|halvings| self.emission_per_action.checked_shr(halvings))
+            .unwrap_or(0)
+    }
+
+    /// Reward tokens emitted in `epoch` for a reviewer on a posting `streak`:
+    /// `emission_at` plus `STREAK_BONUS_BPS` for every consecutive epoch past
+    /// the first, up to `MAX_STREAK_BONUS_BPS`. `None` on overflow.
+    pub fn streak_emission(&self, epoch: Epoch, streak: u32) -> Option<u64> {
+        let bonus_bps = (streak.saturating_sub(1) as u64)
+            .saturating_mul(DistributorState::STREAK_BONUS_BPS as u64)
+            .min(DistributorState::MAX_STREAK_BONUS_BPS as u64);
+        u64::try_from(
+            (self.emission_at(epoch) as u128)
+                .checked_mul(DistributorState::MAX_BPS as u128 + bonus_bps as u128)?
+                / DistributorState::MAX_BPS as u128,
+        )
+        .ok()
+    }
+
+    /// Counts `amount` emitted tokens against the cap, or returns `None` if
+    /// they would exceed it.
+    pub fn emit(&mut self, amount: u64) -> Option<()> {
+        let emitted = self.emitted.checked_add(amount)?;
+        if emitted > self.emission_cap {
+            return None;
+        }
+        self.emitted = emitted;
+        Some(())
+    }
+}
+
+/// Running donation totals of one donor, at `["donation", donor]`.
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct DonationReceipt {
+    pub discriminator: String,
+    pub is_initialized: bool,
+    pub donor: Pubkey,
+    /// Lamports donated, held by the distributor PDA above its rent.
+    pub lamports: u64,
+    /// Reward tokens donated into the treasury, in base units.
+    pub tokens: u64,
+}
+
+impl Sealed for DonationReceipt {}
+
+impl IsInitialized for DonationReceipt {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+impl DonationReceipt {
+    pub const DISCRIMINATOR: &'static str = "donation";
+    pub const SEED: &'static str = "donation";
+
+    pub fn get_account_size() -> usize {
+        (4 + DonationReceipt::DISCRIMINATOR.len()) + 1 + 32 + 8 + 8
+    }
+}
+
+/// Large tips to one reviewer, unlocking linearly, at `["vesting", reviewer]`.
+/// The tokens sit in the reviewer's vesting vault, a token account at
+/// `["vesting_vault", reviewer]` owned by the distributor PDA.
+///
+/// A new tip restarts the schedule: whatever had unlocked stays claimable in
+/// `unlocked`, and the still-locked rest vests again together with the tip.
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct VestingSchedule {
+    pub discriminator: String,
+    pub is_initialized: bool,
+    pub recipient: Pubkey,
+    /// Unlocked under earlier schedules and not claimed yet.
+    pub unlocked: u64,
+    /// Vesting under the current schedule.
+    pub total: u64,
+    /// Claimed out of `total`.
+    pub claimed: u64,
+    pub start: UnixTimestamp,
+    pub duration: UnixTimestamp,
+}
+
+impl Sealed for VestingSchedule {}
+
+impl IsInitialized for VestingSchedule {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+impl VestingSchedule {
+    pub const DISCRIMINATOR: &'static str = "vesting";
+    pub const SEED: &'static str = "vesting";
+
+    pub fn get_account_size() -> usize {
+        (4 + VestingSchedule::DISCRIMINATOR.len()) + 1 + 32 + 8 + 8 + 8 + 8 + 8
+    }
+
+    /// Part of `total` unlocked at `now`.
+    pub fn vested_at(&self, now: UnixTimestamp) -> u64 {
+        let elapsed = now.saturating_sub(self.start);
+        if self.duration <= 0 || elapsed >= self.duration {
+            return self.total;
+        }
+        if elapsed <= 0 {
+            return 0;
+        }
+        // elapsed < duration, so the result is below `total`
+        (self.total as u128 * elapsed as u128 / self.duration as u128) as u64
+    }
+
+    /// Tokens the recipient can claim at `now`.
+    pub fn claimable(&self, now: UnixTimestamp) -> Option<u64> {
+        self.unlocked
+            .checked_add(self.vested_at(now))?
+            .checked_sub(self.claimed)
+    }
+
+    /// Adds `amount` and restarts the schedule at `now`.
+    pub fn add(&mut self, amount: u64, now: UnixTimestamp, duration: UnixTimestamp) -> Option<()> {
+        let vested = self.vested_at(now);
+        self.unlocked = self.claimable(now)?;
+        self.total = self.total.checked_sub(vested)?.checked_add(amount)?;
+        self.claimed = 0;
+        self.start = now;
+        self.duration = duration;
+        Some(())
+    }
+
+    /// Marks everything claimable at `now` as claimed and returns it.
+    pub fn claim(&mut self, now: UnixTimestamp) -> Option<u64> {
+        let amount = self.claimable(now)?;
+        self.unlocked = 0;
+        self.claimed = self.vested_at(now);
+        Some(amount)
+    }
+}
+
+/// What one reviewer has earned and not claimed yet, at `["earnings",
+/// reviewer]`. Tips and weekly rewards accrue here instead of being paid out
+/// one by one, and `ClaimEarnings` pays both balances at once.
+///
+/// The tokens sit in the earnings vault, a single token account at
+/// `["earnings_vault"]` owned by the distributor PDA; the lamports are held
+/// by this account, above its rent exemption.
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct ReviewerEarnings {
+    pub discriminator: String,
+    pub is_initialized: bool,
+    pub reviewer: Pubkey,
+    /// Reward tokens owed, in base units.
+    pub tokens: u64,
+    /// Lamports owed.
+    pub lamports: u64,
+}
+
+impl Sealed for ReviewerEarnings {}
+
+impl IsInitialized for ReviewerEarnings {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+impl ReviewerEarnings {
+    pub const DISCRIMINATOR: &'static str = "earnings";
+    pub const SEED: &'static str = "earnings";
+
+    pub fn get_account_size() -> usize {
+        (4 + ReviewerEarnings::DISCRIMINATOR.len()) + 1 + 32 + 8 + 8
+    }
+}
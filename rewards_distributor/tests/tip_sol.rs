@@ -0,0 +1,180 @@
+//! A lamport tip is credited to the reviewer and their co-authors and counted
+//! in the review program's global stats.
+
+use movie_review::{
+    client::pda::{
+        find_co_authors_address, find_config_address, find_global_stats_address,
+        find_review_address,
+    },
+    decoder::{decode_account, MovieReviewAccount},
+    state::{BodyFormat, CoAuthor, MovieAccountState, ProgramConfig, ReviewCoAuthors},
+};
+use rewards_distributor::{
+    client::{
+        instructions::tip_sol,
+        pda::{find_distributor_address, find_earnings_address},
+    },
+    state::{DistributorState, ReviewerEarnings},
+};
+use solana_program::borsh1::try_from_slice_unchecked;
+use solana_program_test::{processor, ProgramTest};
+use solana_sdk::{
+    account::Account, native_token::LAMPORTS_PER_SOL, pubkey::Pubkey, rent::Rent,
+    signature::Keypair, signer::Signer, system_program, transaction::Transaction,
+};
+
+const TITLE: &str = "Paris, Texas";
+const TIP: u64 = LAMPORTS_PER_SOL / 10;
+
+fn program_account(owner: &Pubkey, mut data: Vec<u8>, len: usize) -> Account {
+    data.resize(len, 0);
+    Account {
+        lamports: Rent::default().minimum_balance(len),
+        data,
+        owner: *owner,
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
+#[tokio::test]
+async fn reviewer_listed_as_co_author_keeps_both_parts() {
+    let review_program = Pubkey::new_unique();
+    let distributor_program = Pubkey::new_unique();
+    let reviewer = Pubkey::new_unique();
+    let tipper = Keypair::new();
+    let (review, _) = find_review_address(&review_program, &reviewer, TITLE);
+    let (distributor, bump) = find_distributor_address(&distributor_program);
+
+    let mut program_test = ProgramTest::new(
+        "rewards_distributor",
+        distributor_program,
+        processor!(rewards_distributor::processor::process_instruction),
+    );
+    program_test.add_program(
+        "movie_review",
+        review_program,
+        processor!(movie_review::processor::process_instruction),
+    );
+    program_test.add_account(
+        tipper.pubkey(),
+        Account::new(LAMPORTS_PER_SOL, 0, &system_program::ID),
+    );
+    let config_data = ProgramConfig {
+        is_initialized: true,
+        authority: Pubkey::new_unique(),
+        tip_program: distributor_program,
+        ..ProgramConfig::default()
+    };
+    program_test.add_account(
+        find_config_address(&review_program).0,
+        program_account(
+            &review_program,
+            borsh::to_vec(&config_data).unwrap(),
+            ProgramConfig::get_account_size(),
+        ),
+    );
+    let review_data = MovieAccountState {
+        discriminator: MovieAccountState::DISCRIMINATOR,
+        is_initialized: true,
+        reviewer,
+        rating: 5,
+        title: TITLE.to_string(),
+        description: "Haunting".to_string(),
+        body_format: BodyFormat::Plain,
+        comment_settings: None,
+    };
+    program_test.add_account(
+        review,
+        program_account(
+            &review_program,
+            borsh::to_vec(&review_data).unwrap(),
+            MovieAccountState::MAX_ACCOUNT_SIZE,
+        ),
+    );
+    // Listings made through `SetCoAuthors` exclude the reviewer, but the
+    // distributor must not rely on it
+    let co_authors_data = ReviewCoAuthors {
+        discriminator: ReviewCoAuthors::DISCRIMINATOR,
+        is_initialized: true,
+        review,
+        co_authors: vec![CoAuthor {
+            co_author: reviewer,
+            share_bps: 5_000,
+            accepted: true,
+        }],
+    };
+    program_test.add_account(
+        find_co_authors_address(&review_program, &review).0,
+        program_account(
+            &review_program,
+            borsh::to_vec(&co_authors_data).unwrap(),
+            ReviewCoAuthors::get_account_size(),
+        ),
+    );
+    let distributor_data = DistributorState {
+        discriminator: DistributorState::DISCRIMINATOR.to_string(),
+        is_initialized: true,
+        authority: Pubkey::new_unique(),
+        review_program,
+        reward_mint: Pubkey::new_unique(),
+        weekly_budget: 0,
+        last_distribution: 0,
+        bump,
+        tip_treasury_bps: 0,
+        tip_vesting_threshold: 0,
+        tip_vesting_duration: 0,
+        emission_per_action: 0,
+        emission_halving_epochs: 0,
+        emission_start_epoch: 0,
+        emission_cap: 0,
+        emitted: 0,
+    };
+    program_test.add_account(
+        distributor,
+        program_account(
+            &distributor_program,
+            borsh::to_vec(&distributor_data).unwrap(),
+            DistributorState::get_account_size(),
+        ),
+    );
+
+    let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+    let transaction = Transaction::new_signed_with_payer(
+        &[tip_sol(
+            &distributor_program,
+            &review_program,
+            &tipper.pubkey(),
+            &review,
+            &reviewer,
+            &[reviewer],
+            TIP,
+        )],
+        Some(&payer.pubkey()),
+        &[&payer, &tipper],
+        recent_blockhash,
+    );
+    banks_client.process_transaction(transaction).await.unwrap();
+
+    let (earnings, _) = find_earnings_address(&distributor_program, &reviewer);
+    let account = banks_client.get_account(earnings).await.unwrap().unwrap();
+    let earnings_data = try_from_slice_unchecked::<ReviewerEarnings>(&account.data).unwrap();
+    assert_eq!(earnings_data.lamports, TIP);
+    assert_eq!(
+        account.lamports,
+        Rent::default().minimum_balance(ReviewerEarnings::get_account_size()) + TIP
+    );
+
+    let stats = banks_client
+        .get_account(find_global_stats_address(&review_program).0)
+        .await
+        .unwrap()
+        .unwrap();
+    match decode_account(&stats.data).unwrap() {
+        MovieReviewAccount::GlobalStats(stats) => {
+            assert_eq!(stats.total_tip_lamports, TIP);
+            assert_eq!(stats.total_tips, 0);
+        }
+        _ => panic!("global stats decoded to the wrong account type"),
+    }
+}
@@ -0,0 +1,82 @@
+//! Error categories shared by every track program.
+//!
+//! Common errors live at `COMMON_ERROR_BASE` and above so they never collide
+//! with a program's own error enum, which numbers from zero.
+
+use num_derive::FromPrimitive;
+use num_traits::FromPrimitive;
+use solana_program::{
+    decode_error::DecodeError,
+    msg,
+    program_error::{PrintProgramError, ProgramError},
+};
+use thiserror::Error;
+
+pub const COMMON_ERROR_BASE: u32 = 1000;
+
+#[derive(Clone, Copy, Debug, Eq, Error, FromPrimitive, PartialEq)]
+pub enum CommonError {
+    #[error("Signer is not authorized for this account")]
+    Unauthorized = 1000,
+    #[error("PDA derived does not equal PDA passed in")]
+    InvalidPDA = 1001,
+    #[error("Arithmetic overflow")]
+    Overflow = 1002,
+    #[error("Account not initialized yet")]
+    Uninitialized = 1003,
+    #[error("Account is not rent exempt")]
+    NotRentExempt = 1004,
+    #[error("Account has been closed")]
+    AccountClosed = 1005,
+}
+
+impl From<CommonError> for ProgramError {
+    fn from(e: CommonError) -> Self {
+        ProgramError::Custom(e as u32)
+    }
+}
+
+impl<T> DecodeError<T> for CommonError {
+    fn type_of() -> &'static str {
+        "CommonError"
+    }
+}
+
+impl PrintProgramError for CommonError {
+    fn print<E>(&self)
+    where
+        E: 'static + std::error::Error + DecodeError<E> + PrintProgramError + FromPrimitive,
+    {
+        msg!(&self.to_string());
+    }
+}
+
+/// Logs `error` using the program's own error type `E` for codes below
+/// `COMMON_ERROR_BASE` and `CommonError` for codes at or above it.
+pub fn print_error<E>(error: &ProgramError)
+where
+    E: 'static + std::error::Error + DecodeError<E> + PrintProgramError + FromPrimitive,
+{
+    match error {
+        ProgramError::Custom(code) if *code >= COMMON_ERROR_BASE => {
+            match CommonError::from_u32(*code) {
+                Some(e) => e.print::<CommonError>(),
+                None => msg!("Unknown common error code {}", code),
+            }
+        }
+        _ => error.print::<E>(),
+    }
+}
+
+/// Resolves a custom error code to its message, for clients decoding a failed
+/// transaction. Program-specific codes are decoded with `E`.
+pub fn decode_error_message<E>(code: u32) -> Option<String>
+where
+    E: std::error::Error + FromPrimitive,
+{
+    if code >= COMMON_ERROR_BASE {
+        CommonError::from_u32(code).map(|e| e.to_string())
+    } else {
+        E::from_u32(code).map(|e| e.to_string())
+    }
+}
@@ -0,0 +1,173 @@
+use solana_program::{
+    account_info::AccountInfo,
+    entrypoint::ProgramResult,
+    msg,
+    program::{invoke, invoke_signed},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    system_instruction,
+    sysvar::{rent::Rent, Sysvar},
+};
+
+pub fn rent_exempt_lamports(space: usize) -> Result<u64, ProgramError> {
+    Ok(Rent::get()?.minimum_balance(space))
+}
+
+/// Creates a rent-exempt account of `space` bytes at a PDA owned by `owner`,
+/// paid for by `payer`.
+///
+/// `create_account` fails if the address already holds lamports, so anyone
+/// could block creation by sending dust to a PDA before it exists. In that
+/// case the account is funded up to rent exemption, then allocated and
+/// assigned directly.
+pub fn create_pda_account<'a>(
+    payer: &AccountInfo<'a>,
+    pda: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    space: usize,
+    owner: &Pubkey,
+    signer_seeds: &[&[u8]],
+) -> ProgramResult {
+    let required_lamports = rent_exempt_lamports(space)?;
+    let current_lamports = pda.lamports();
+
+    if current_lamports == 0 {
+        let create_account = system_instruction::create_account(
+            payer.key,
+            pda.key,
+            required_lamports,
+            space.try_into().unwrap(),
+            owner,
+        );
+        return invoke_signed(
+            &create_account,
+            &[payer.clone(), pda.clone(), system_program.clone()],
+            &[signer_seeds],
+        );
+    }
+
+    msg!("account already holds {} lamports", current_lamports);
+    if required_lamports > current_lamports {
+        invoke(
+            &system_instruction::transfer(payer.key, pda.key, required_lamports - current_lamports),
+            &[payer.clone(), pda.clone(), system_program.clone()],
+        )?;
+    }
+    invoke_signed(
+        &system_instruction::allocate(pda.key, space.try_into().unwrap()),
+        &[pda.clone(), system_program.clone()],
+        &[signer_seeds],
+    )?;
+    invoke_signed(
+        &system_instruction::assign(pda.key, owner),
+        &[pda.clone(), system_program.clone()],
+        &[signer_seeds],
+    )
+}
+
+/// Reallocs `account` to `new_len` bytes and keeps it exactly rent exempt: tops
+/// up from `payer` when growing and refunds the excess to `payer` when
+/// shrinking. `payer` must sign when the account grows.
+pub fn resize_account<'a>(
+    account: &AccountInfo<'a>,
+    payer: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    new_len: usize,
+) -> ProgramResult {
+    let new_minimum_balance = rent_exempt_lamports(new_len)?;
+    let current_lamports = account.lamports();
+
+    if new_minimum_balance > current_lamports {
+        msg!("topping up account rent");
+        invoke(
+            &system_instruction::transfer(
+                payer.key,
+                account.key,
+                new_minimum_balance - current_lamports,
+            ),
+            &[payer.clone(), account.clone(), system_program.clone()],
+        )?;
+    } else if current_lamports > new_minimum_balance {
+        msg!("refunding excess account rent");
+        let refund = current_lamports - new_minimum_balance;
+        **account.try_borrow_mut_lamports()? -= refund;
+        **payer.try_borrow_mut_lamports()? += refund;
+    }
+
+    msg!("reallocating account to {} bytes", new_len);
+    account.realloc(new_len, false)
+}
+
+/// Tops `account` up to rent exemption at its current size, paid by `payer`.
+/// Returns the lamports added, zero if it was already exempt.
+pub fn top_up_rent<'a>(
+    account: &AccountInfo<'a>,
+    payer: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+) -> Result<u64, ProgramError> {
+    let shortfall = rent_exempt_lamports(account.data_len())?.saturating_sub(account.lamports());
+    if shortfall > 0 {
+        invoke(
+            &system_instruction::transfer(payer.key, account.key, shortfall),
+            &[payer.clone(), account.clone(), system_program.clone()],
+        )?;
+    }
+    Ok(shortfall)
+}
+
+/// Moves whatever a program-owned `account` holds above rent exemption at its
+/// current size to `destination`, leaving it exactly exempt. Returns the
+/// lamports moved.
+pub fn withdraw_excess_lamports(
+    account: &AccountInfo,
+    destination: &AccountInfo,
+) -> Result<u64, ProgramError> {
+    let excess = account
+        .lamports()
+        .saturating_sub(rent_exempt_lamports(account.data_len())?);
+    **destination.try_borrow_mut_lamports()? = destination
+        .lamports()
+        .checked_add(excess)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    **account.try_borrow_mut_lamports()? -= excess;
+    Ok(excess)
+}
+
+/// Written over the start of a closed account. No track program's state can
+/// begin with these bytes: as a Borsh string length they exceed any account.
+pub const CLOSED_ACCOUNT_DISCRIMINATOR: [u8; 8] = [0xff; 8];
+
+/// Closes a program-owned account: wipes its data, marks it with
+/// `CLOSED_ACCOUNT_DISCRIMINATOR` and moves all its lamports to `destination`.
+///
+/// The runtime only purges the emptied account when the transaction ends.
+/// Until then, a later instruction could send lamports back to keep it alive,
+/// so every load path must reject the marker with `assert_not_closed`.
+pub fn close_account(account: &AccountInfo, destination: &AccountInfo) -> ProgramResult {
+    let mut data = account.try_borrow_mut_data()?;
+    data.fill(0);
+    let marker_len = CLOSED_ACCOUNT_DISCRIMINATOR.len().min(data.len());
+    data[..marker_len].copy_from_slice(&CLOSED_ACCOUNT_DISCRIMINATOR[..marker_len]);
+
+    let refund = account.lamports();
+    **destination.try_borrow_mut_lamports()? = destination
+        .lamports()
+        .checked_add(refund)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    **account.try_borrow_mut_lamports()? = 0;
+
+    Ok(())
+}
+
+/// Fails with `error` if `account` carries the closed-account marker.
+pub fn assert_not_closed(account: &AccountInfo, error: impl Into<ProgramError>) -> ProgramResult {
+    if account
+        .data
+        .borrow()
+        .starts_with(&CLOSED_ACCOUNT_DISCRIMINATOR)
+    {
+        msg!("Account {} has been closed", account.key);
+        return Err(error.into());
+    }
+    Ok(())
+}
@@ -0,0 +1,85 @@
+use solana_program::{
+    account_info::AccountInfo,
+    entrypoint::ProgramResult,
+    msg,
+    program_error::ProgramError,
+    program_pack::IsInitialized,
+    pubkey::Pubkey,
+    sysvar::{rent::Rent, Sysvar},
+};
+
+pub fn assert_signer(account: &AccountInfo) -> ProgramResult {
+    if !account.is_signer {
+        msg!("Missing required signature");
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    Ok(())
+}
+
+pub fn assert_owned_by(account: &AccountInfo, owner: &Pubkey) -> ProgramResult {
+    if account.owner != owner {
+        msg!("Invalid account owner");
+        return Err(ProgramError::IllegalOwner);
+    }
+    Ok(())
+}
+
+pub fn assert_program_id(account: &AccountInfo, program_id: &Pubkey) -> ProgramResult {
+    if account.key != program_id {
+        msg!("Invalid program account {}", account.key);
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    Ok(())
+}
+
+/// Fails with `error` unless `data` has been initialized.
+pub fn assert_initialized<T: IsInitialized>(
+    data: &T,
+    error: impl Into<ProgramError>,
+) -> ProgramResult {
+    if !data.is_initialized() {
+        msg!("Account not initialized yet");
+        return Err(error.into());
+    }
+    Ok(())
+}
+
+/// Fails with `error` if `data` has already been initialized.
+pub fn assert_uninitialized<T: IsInitialized>(
+    data: &T,
+    error: impl Into<ProgramError>,
+) -> ProgramResult {
+    if data.is_initialized() {
+        msg!("Account already initialized");
+        return Err(error.into());
+    }
+    Ok(())
+}
+
+pub fn assert_discriminator(actual: &str, expected: &str) -> ProgramResult {
+    if actual != expected {
+        msg!("Invalid account discriminator: {}", actual);
+        return Err(ProgramError::InvalidAccountData);
+    }
+    Ok(())
+}
+
+/// Fails with `error` unless `account` holds enough lamports to be rent exempt
+/// at its current size. Checked after creating an account and before writing
+/// to one, so an account someone else created or resized cannot be left
+/// underfunded.
+pub fn assert_rent_exempt(account: &AccountInfo, error: impl Into<ProgramError>) -> ProgramResult {
+    if !Rent::get()?.is_exempt(account.lamports(), account.data_len()) {
+        msg!("Account {} is not rent exempt", account.key);
+        return Err(error.into());
+    }
+    Ok(())
+}
+
+pub fn assert_max_len(len: usize, max_len: usize, error: impl Into<ProgramError>) -> ProgramResult {
+    if len > max_len {
+        msg!("Input data exceeds max length");
+        return Err(error.into());
+    }
+    Ok(())
+}
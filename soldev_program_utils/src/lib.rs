@@ -0,0 +1,5 @@
+//! Validation and account-management helpers shared by the track programs.
+
+pub mod account;
+pub mod assert;
+pub mod pda;
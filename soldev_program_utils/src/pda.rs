@@ -0,0 +1,37 @@
+use solana_program::{account_info::AccountInfo, msg, program_error::ProgramError, pubkey::Pubkey};
+
+/// Derives the canonical PDA for `seeds` and checks it matches `account`,
+/// returning the bump seed. Fails with `error` on mismatch.
+pub fn assert_pda(
+    account: &AccountInfo,
+    seeds: &[&[u8]],
+    program_id: &Pubkey,
+    error: impl Into<ProgramError>,
+) -> Result<u8, ProgramError> {
+    let (pda, bump_seed) = Pubkey::find_program_address(seeds, program_id);
+    if pda != *account.key {
+        msg!("Invalid seeds for PDA");
+        return Err(error.into());
+    }
+    Ok(bump_seed)
+}
+
+/// Like [`assert_pda`] but re-creates the address from a stored bump instead
+/// of searching for it.
+pub fn assert_pda_with_bump(
+    account: &AccountInfo,
+    seeds: &[&[u8]],
+    bump_seed: u8,
+    program_id: &Pubkey,
+    error: impl Into<ProgramError>,
+) -> Result<(), ProgramError> {
+    let bump = [bump_seed];
+    let mut seeds_with_bump = seeds.to_vec();
+    seeds_with_bump.push(&bump);
+    let pda = Pubkey::create_program_address(&seeds_with_bump, program_id)?;
+    if pda != *account.key {
+        msg!("Invalid seeds for PDA");
+        return Err(error.into());
+    }
+    Ok(())
+}
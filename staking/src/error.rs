@@ -0,0 +1,45 @@
+use num_derive::FromPrimitive;
+use num_traits::FromPrimitive;
+use solana_program::{
+    decode_error::DecodeError,
+    msg,
+    program_error::{PrintProgramError, ProgramError},
+};
+use thiserror::Error;
+
+#[derive(Clone, Copy, Debug, Eq, Error, FromPrimitive, PartialEq)]
+pub enum StakeError {
+    #[error("Account not initialized yet")]
+    UninitializedAccount,
+    #[error("PDA derived does not equal PDA passed in")]
+    InvalidPDA,
+    #[error("Token account is not a single NFT owned by the signer")]
+    InvalidNftAccount,
+    #[error("Signer is not the staker")]
+    InvalidStaker,
+    #[error("NFT is not currently staked")]
+    NotStaked,
+    #[error("Reward calculation overflowed")]
+    Overflow,
+}
+
+impl From<StakeError> for ProgramError {
+    fn from(e: StakeError) -> Self {
+        ProgramError::Custom(e as u32)
+    }
+}
+
+impl<T> DecodeError<T> for StakeError {
+    fn type_of() -> &'static str {
+        "StakeError"
+    }
+}
+
+impl PrintProgramError for StakeError {
+    fn print<E>(&self)
+    where
+        E: 'static + std::error::Error + DecodeError<E> + PrintProgramError + FromPrimitive,
+    {
+        msg!(&self.to_string());
+    }
+}
@@ -0,0 +1,355 @@
+use borsh::BorshSerialize;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    borsh1::try_from_slice_unchecked,
+    clock::Clock,
+    entrypoint::ProgramResult,
+    msg,
+    program::{invoke, invoke_signed},
+    program_error::ProgramError,
+    program_pack::Pack,
+    pubkey::Pubkey,
+    sysvar::Sysvar,
+};
+use soldev_program_errors::CommonError;
+use soldev_program_utils::{
+    account::{assert_not_closed, close_account, create_pda_account},
+    assert::{
+        assert_initialized, assert_owned_by, assert_program_id, assert_signer, assert_uninitialized,
+    },
+    pda::{assert_pda, assert_pda_with_bump},
+};
+use spl_token::state::Account as TokenAccount;
+
+use crate::error::StakeError;
+use crate::instruction::StakeInstruction;
+use crate::state::UserStakeInfo;
+
+pub fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    let instruction = StakeInstruction::unpack(instruction_data)?;
+
+    match instruction {
+        StakeInstruction::Stake => {
+            stake(program_id, accounts)?;
+        }
+        StakeInstruction::Redeem => {
+            redeem(program_id, accounts)?;
+        }
+        StakeInstruction::Unstake => {
+            unstake(program_id, accounts)?;
+        }
+    }
+    Ok(())
+}
+
+pub fn stake(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let staker = next_account_info(account_info_iter)?;
+    let nft_mint = next_account_info(account_info_iter)?;
+    let staker_nft_token = next_account_info(account_info_iter)?;
+    let pda_stake = next_account_info(account_info_iter)?;
+    let pda_vault = next_account_info(account_info_iter)?;
+    let token_program = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+
+    // Check if the instruction is signed
+    assert_signer(staker)?;
+    assert_program_id(token_program, &spl_token::ID)?;
+
+    // Check the staker actually holds the NFT
+    let nft_token_data = TokenAccount::unpack(&staker_nft_token.data.borrow())?;
+    if nft_token_data.mint != *nft_mint.key
+        || nft_token_data.owner != *staker.key
+        || nft_token_data.amount != 1
+    {
+        msg!("Token account is not a single NFT owned by the signer");
+        return Err(StakeError::InvalidNftAccount.into());
+    }
+
+    let stake_bump = assert_pda(
+        pda_stake,
+        &[
+            UserStakeInfo::SEED.as_bytes(),
+            staker.key.as_ref(),
+            nft_mint.key.as_ref(),
+        ],
+        program_id,
+        StakeError::InvalidPDA,
+    )?;
+
+    let vault_bump = assert_pda(
+        pda_vault,
+        &[UserStakeInfo::VAULT_SEED.as_bytes(), pda_stake.key.as_ref()],
+        program_id,
+        StakeError::InvalidPDA,
+    )?;
+
+    msg!("creating stake state account");
+    create_pda_account(
+        staker,
+        pda_stake,
+        system_program,
+        UserStakeInfo::get_account_size(),
+        program_id,
+        &[
+            UserStakeInfo::SEED.as_bytes(),
+            staker.key.as_ref(),
+            nft_mint.key.as_ref(),
+            &[stake_bump],
+        ],
+    )?;
+
+    msg!("creating nft vault token account");
+    create_pda_account(
+        staker,
+        pda_vault,
+        system_program,
+        TokenAccount::LEN,
+        token_program.key,
+        &[
+            UserStakeInfo::VAULT_SEED.as_bytes(),
+            pda_stake.key.as_ref(),
+            &[vault_bump],
+        ],
+    )?;
+    invoke(
+        &spl_token::instruction::initialize_account3(
+            token_program.key,
+            pda_vault.key,
+            nft_mint.key,
+            pda_stake.key,
+        )?,
+        &[pda_vault.clone(), nft_mint.clone()],
+    )?;
+
+    msg!("transferring nft into vault");
+    invoke(
+        &spl_token::instruction::transfer(
+            token_program.key,
+            staker_nft_token.key,
+            pda_vault.key,
+            staker.key,
+            &[],
+            1,
+        )?,
+        &[
+            staker_nft_token.clone(),
+            pda_vault.clone(),
+            staker.clone(),
+            token_program.clone(),
+        ],
+    )?;
+
+    let mut stake_data = try_from_slice_unchecked::<UserStakeInfo>(&pda_stake.data.borrow())?;
+
+    assert_uninitialized(&stake_data, ProgramError::AccountAlreadyInitialized)?;
+
+    let clock = Clock::get()?;
+
+    stake_data.discriminator = UserStakeInfo::DISCRIMINATOR.to_string();
+    stake_data.is_initialized = true;
+    stake_data.staker = *staker.key;
+    stake_data.nft_mint = *nft_mint.key;
+    stake_data.stake_start_time = clock.unix_timestamp;
+    stake_data.last_stake_redeem = clock.unix_timestamp;
+    stake_data.is_staked = true;
+    stake_data.bump = stake_bump;
+    stake_data.vault_bump = vault_bump;
+
+    stake_data.serialize(&mut *pda_stake.data.borrow_mut())?;
+    msg!("nft staked at: {}", pda_stake.key);
+
+    Ok(())
+}
+
+pub fn redeem(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let staker = next_account_info(account_info_iter)?;
+    let pda_stake = next_account_info(account_info_iter)?;
+    let reward_mint = next_account_info(account_info_iter)?;
+    let pda_mint_authority = next_account_info(account_info_iter)?;
+    let staker_reward_token = next_account_info(account_info_iter)?;
+    let token_program = next_account_info(account_info_iter)?;
+
+    let mut stake_data = load_stake_for_staker(program_id, staker, pda_stake, token_program)?;
+
+    pay_rewards(
+        program_id,
+        &mut stake_data,
+        reward_mint,
+        pda_mint_authority,
+        staker_reward_token,
+        token_program,
+    )?;
+
+    stake_data.serialize(&mut *pda_stake.data.borrow_mut())?;
+
+    Ok(())
+}
+
+pub fn unstake(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let staker = next_account_info(account_info_iter)?;
+    let pda_stake = next_account_info(account_info_iter)?;
+    let pda_vault = next_account_info(account_info_iter)?;
+    let staker_nft_token = next_account_info(account_info_iter)?;
+    let reward_mint = next_account_info(account_info_iter)?;
+    let pda_mint_authority = next_account_info(account_info_iter)?;
+    let staker_reward_token = next_account_info(account_info_iter)?;
+    let token_program = next_account_info(account_info_iter)?;
+
+    let mut stake_data = load_stake_for_staker(program_id, staker, pda_stake, token_program)?;
+
+    assert_pda_with_bump(
+        pda_vault,
+        &[UserStakeInfo::VAULT_SEED.as_bytes(), pda_stake.key.as_ref()],
+        stake_data.vault_bump,
+        program_id,
+        StakeError::InvalidPDA,
+    )?;
+
+    pay_rewards(
+        program_id,
+        &mut stake_data,
+        reward_mint,
+        pda_mint_authority,
+        staker_reward_token,
+        token_program,
+    )?;
+
+    let stake_seeds: &[&[u8]] = &[
+        UserStakeInfo::SEED.as_bytes(),
+        stake_data.staker.as_ref(),
+        stake_data.nft_mint.as_ref(),
+        &[stake_data.bump],
+    ];
+
+    msg!("returning nft to staker");
+    invoke_signed(
+        &spl_token::instruction::transfer(
+            token_program.key,
+            pda_vault.key,
+            staker_nft_token.key,
+            pda_stake.key,
+            &[],
+            1,
+        )?,
+        &[
+            pda_vault.clone(),
+            staker_nft_token.clone(),
+            pda_stake.clone(),
+            token_program.clone(),
+        ],
+        &[stake_seeds],
+    )?;
+
+    msg!("closing nft vault");
+    invoke_signed(
+        &spl_token::instruction::close_account(
+            token_program.key,
+            pda_vault.key,
+            staker.key,
+            pda_stake.key,
+            &[],
+        )?,
+        &[
+            pda_vault.clone(),
+            staker.clone(),
+            pda_stake.clone(),
+            token_program.clone(),
+        ],
+        &[stake_seeds],
+    )?;
+
+    msg!("closing stake state account");
+    close_account(pda_stake, staker)
+}
+
+fn load_stake_for_staker(
+    program_id: &Pubkey,
+    staker: &AccountInfo,
+    pda_stake: &AccountInfo,
+    token_program: &AccountInfo,
+) -> Result<UserStakeInfo, ProgramError> {
+    assert_signer(staker)?;
+    assert_owned_by(pda_stake, program_id)?;
+    assert_not_closed(pda_stake, CommonError::AccountClosed)?;
+    assert_program_id(token_program, &spl_token::ID)?;
+
+    let stake_data = try_from_slice_unchecked::<UserStakeInfo>(&pda_stake.data.borrow())?;
+
+    assert_initialized(&stake_data, StakeError::UninitializedAccount)?;
+
+    if stake_data.staker != *staker.key {
+        msg!("Signer is not the staker");
+        return Err(StakeError::InvalidStaker.into());
+    }
+
+    if !stake_data.is_staked {
+        msg!("NFT is not currently staked");
+        return Err(StakeError::NotStaked.into());
+    }
+
+    Ok(stake_data)
+}
+
+/// Mints the rewards accrued since the last redeem to the staker and advances
+/// `last_stake_redeem` to now.
+fn pay_rewards<'a>(
+    program_id: &Pubkey,
+    stake_data: &mut UserStakeInfo,
+    reward_mint: &AccountInfo<'a>,
+    pda_mint_authority: &AccountInfo<'a>,
+    staker_reward_token: &AccountInfo<'a>,
+    token_program: &AccountInfo<'a>,
+) -> ProgramResult {
+    let (pda_mint_authority_key, mint_authority_bump) =
+        Pubkey::find_program_address(&[UserStakeInfo::MINT_AUTHORITY_SEED.as_bytes()], program_id);
+    if pda_mint_authority_key != *pda_mint_authority.key {
+        msg!("Invalid seeds for mint authority PDA");
+        return Err(StakeError::InvalidPDA.into());
+    }
+
+    let clock = Clock::get()?;
+    let elapsed = clock
+        .unix_timestamp
+        .saturating_sub(stake_data.last_stake_redeem)
+        .max(0) as u64;
+    let reward_amount = elapsed
+        .checked_mul(UserStakeInfo::REWARD_RATE_PER_SECOND)
+        .ok_or(StakeError::Overflow)?;
+
+    msg!(
+        "minting {} reward tokens for {}s staked",
+        reward_amount,
+        elapsed
+    );
+    invoke_signed(
+        &spl_token::instruction::mint_to(
+            token_program.key,
+            reward_mint.key,
+            staker_reward_token.key,
+            pda_mint_authority.key,
+            &[],
+            reward_amount,
+        )?,
+        &[
+            reward_mint.clone(),
+            staker_reward_token.clone(),
+            pda_mint_authority.clone(),
+            token_program.clone(),
+        ],
+        &[&[
+            UserStakeInfo::MINT_AUTHORITY_SEED.as_bytes(),
+            &[mint_authority_bump],
+        ]],
+    )?;
+
+    stake_data.last_stake_redeem = clock.unix_timestamp;
+
+    Ok(())
+}
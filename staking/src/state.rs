@@ -0,0 +1,38 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::clock::UnixTimestamp;
+use solana_program::program_pack::{IsInitialized, Sealed};
+use solana_program::pubkey::Pubkey;
+
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct UserStakeInfo {
+    pub discriminator: String,
+    pub is_initialized: bool,
+    pub staker: Pubkey,
+    pub nft_mint: Pubkey,
+    pub stake_start_time: UnixTimestamp,
+    pub last_stake_redeem: UnixTimestamp,
+    pub is_staked: bool,
+    pub bump: u8,
+    pub vault_bump: u8,
+}
+
+impl Sealed for UserStakeInfo {}
+
+impl IsInitialized for UserStakeInfo {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+impl UserStakeInfo {
+    pub const DISCRIMINATOR: &'static str = "stake";
+    pub const SEED: &'static str = "stake";
+    pub const VAULT_SEED: &'static str = "vault";
+    pub const MINT_AUTHORITY_SEED: &'static str = "mint";
+    /// Reward tokens (in base units) accrued per second of staking
+    pub const REWARD_RATE_PER_SECOND: u64 = 1_000_000;
+
+    pub fn get_account_size() -> usize {
+        (4 + UserStakeInfo::DISCRIMINATOR.len()) + 1 + 32 + 32 + 8 + 8 + 1 + 1 + 1
+    }
+}
@@ -0,0 +1,41 @@
+use num_derive::FromPrimitive;
+use num_traits::FromPrimitive;
+use solana_program::{
+    decode_error::DecodeError,
+    msg,
+    program_error::{PrintProgramError, ProgramError},
+};
+use thiserror::Error;
+
+#[derive(Clone, Copy, Debug, Eq, Error, FromPrimitive, PartialEq)]
+pub enum HookError {
+    #[error("Account not initialized yet")]
+    UninitializedAccount,
+    #[error("PDA derived does not equal PDA passed in")]
+    InvalidPDA,
+    #[error("Hook was invoked outside of a token transfer")]
+    NotTransferring,
+    #[error("Signer is neither the mint nor the transfer hook authority")]
+    InvalidAuthority,
+}
+
+impl From<HookError> for ProgramError {
+    fn from(e: HookError) -> Self {
+        ProgramError::Custom(e as u32)
+    }
+}
+
+impl<T> DecodeError<T> for HookError {
+    fn type_of() -> &'static str {
+        "HookError"
+    }
+}
+
+impl PrintProgramError for HookError {
+    fn print<E>(&self)
+    where
+        E: 'static + std::error::Error + DecodeError<E> + PrintProgramError + FromPrimitive,
+    {
+        msg!(&self.to_string());
+    }
+}
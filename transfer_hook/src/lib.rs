@@ -0,0 +1,8 @@
+//! Transfer hook for the reward mint. Token-2022 calls `Execute` on every
+//! transfer of the mint, and the hook keeps a running count and volume of
+//! those transfers in a stats PDA.
+
+pub mod entrypoint;
+pub mod error;
+pub mod processor;
+pub mod state;
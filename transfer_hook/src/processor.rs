@@ -0,0 +1,228 @@
+use borsh::BorshSerialize;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    borsh1::try_from_slice_unchecked,
+    entrypoint::ProgramResult,
+    msg,
+    program_error::ProgramError,
+    program_option::COption,
+    pubkey::Pubkey,
+};
+use soldev_program_utils::{
+    account::create_pda_account,
+    assert::{assert_initialized, assert_owned_by, assert_signer, assert_uninitialized},
+    pda::assert_pda,
+};
+use spl_tlv_account_resolution::{
+    account::ExtraAccountMeta, seeds::Seed, state::ExtraAccountMetaList,
+};
+use spl_token_2022::{
+    extension::{
+        transfer_hook::{TransferHook, TransferHookAccount},
+        BaseStateWithExtensions, StateWithExtensions,
+    },
+    state::{Account, Mint},
+};
+use spl_transfer_hook_interface::{
+    collect_extra_account_metas_seeds, collect_extra_account_metas_signer_seeds,
+    instruction::{ExecuteInstruction, TransferHookInstruction},
+};
+
+use crate::error::HookError;
+use crate::state::TransferStats;
+
+pub fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    let instruction = TransferHookInstruction::unpack(instruction_data)?;
+
+    match instruction {
+        TransferHookInstruction::Execute { amount } => {
+            execute(program_id, accounts, instruction_data, amount)?;
+        }
+        TransferHookInstruction::InitializeExtraAccountMetaList { .. } => {
+            initialize_extra_account_meta_list(program_id, accounts)?;
+        }
+        TransferHookInstruction::UpdateExtraAccountMetaList { .. } => {
+            msg!("The extra account list is fixed by the hook");
+            return Err(ProgramError::InvalidInstructionData);
+        }
+    }
+    Ok(())
+}
+
+/// The only extra account `Execute` needs: the stats PDA for the mint, derived
+/// by Token-2022 and off-chain clients from the mint at index 1.
+fn extra_account_metas() -> Result<Vec<ExtraAccountMeta>, ProgramError> {
+    Ok(vec![ExtraAccountMeta::new_with_seeds(
+        &[
+            Seed::Literal {
+                bytes: TransferStats::SEED.as_bytes().to_vec(),
+            },
+            Seed::AccountKey { index: 1 },
+        ],
+        false,
+        true,
+    )?])
+}
+
+/// Creates the validation account Token-2022 reads the extra accounts from,
+/// and the stats PDA those accounts resolve to.
+///
+/// The hook fixes its own account list, so any metas passed in the
+/// instruction are ignored. Accounts follow the interface, with the signer
+/// also paying, plus the stats PDA:
+/// `[validation, mint, authority, system_program, stats]`.
+pub fn initialize_extra_account_meta_list(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let pda_validation = next_account_info(account_info_iter)?;
+    let mint = next_account_info(account_info_iter)?;
+    let authority = next_account_info(account_info_iter)?;
+    let system_program = next_account_info(account_info_iter)?;
+    let pda_stats = next_account_info(account_info_iter)?;
+
+    assert_signer(authority)?;
+    assert_owned_by(mint, &spl_token_2022::ID)?;
+
+    {
+        let mint_data = mint.data.borrow();
+        let mint_state = StateWithExtensions::<Mint>::unpack(&mint_data)?;
+        let transfer_hook = mint_state.get_extension::<TransferHook>()?;
+        if Option::<Pubkey>::from(transfer_hook.program_id) != Some(*program_id) {
+            msg!("Mint does not point its transfer hook at this program");
+            return Err(ProgramError::InvalidAccountData);
+        }
+        // A mint authority PDA cannot pay for the accounts, so the hook
+        // authority set on the extension may initialize them instead
+        if mint_state.base.mint_authority != COption::Some(*authority.key)
+            && Option::<Pubkey>::from(transfer_hook.authority) != Some(*authority.key)
+        {
+            msg!("Signer is neither the mint nor the transfer hook authority");
+            return Err(HookError::InvalidAuthority.into());
+        }
+    }
+
+    let validation_bump_seed = assert_pda(
+        pda_validation,
+        &collect_extra_account_metas_seeds(mint.key),
+        program_id,
+        HookError::InvalidPDA,
+    )?;
+    let stats_bump_seed = assert_pda(
+        pda_stats,
+        &[TransferStats::SEED.as_bytes(), mint.key.as_ref()],
+        program_id,
+        HookError::InvalidPDA,
+    )?;
+
+    let extra_account_metas = extra_account_metas()?;
+
+    msg!("creating extra account meta list");
+    create_pda_account(
+        authority,
+        pda_validation,
+        system_program,
+        ExtraAccountMetaList::size_of(extra_account_metas.len())?,
+        program_id,
+        &collect_extra_account_metas_signer_seeds(mint.key, &[validation_bump_seed]),
+    )?;
+    ExtraAccountMetaList::init::<ExecuteInstruction>(
+        &mut pda_validation.try_borrow_mut_data()?,
+        &extra_account_metas,
+    )?;
+
+    msg!("creating transfer stats account");
+    create_pda_account(
+        authority,
+        pda_stats,
+        system_program,
+        TransferStats::get_account_size(),
+        program_id,
+        &[
+            TransferStats::SEED.as_bytes(),
+            mint.key.as_ref(),
+            &[stats_bump_seed],
+        ],
+    )?;
+
+    let mut stats_data = try_from_slice_unchecked::<TransferStats>(&pda_stats.data.borrow())?;
+
+    assert_uninitialized(&stats_data, ProgramError::AccountAlreadyInitialized)?;
+
+    stats_data.discriminator = TransferStats::DISCRIMINATOR.to_string();
+    stats_data.is_initialized = true;
+    stats_data.mint = *mint.key;
+    stats_data.transfer_count = 0;
+    stats_data.total_volume = 0;
+    stats_data.last_amount = 0;
+
+    stats_data.serialize(&mut *pda_stats.data.borrow_mut())?;
+    msg!("transfer stats initialized at: {}", pda_stats.key);
+
+    Ok(())
+}
+
+/// Called by Token-2022 during every transfer of the mint.
+///
+/// Accounts are `[source, mint, destination, owner, validation, stats]`.
+pub fn execute(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+    amount: u64,
+) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let source = next_account_info(account_info_iter)?;
+    let mint = next_account_info(account_info_iter)?;
+    let _destination = next_account_info(account_info_iter)?;
+    let _owner = next_account_info(account_info_iter)?;
+    let pda_validation = next_account_info(account_info_iter)?;
+    let pda_stats = next_account_info(account_info_iter)?;
+
+    assert_pda(
+        pda_validation,
+        &collect_extra_account_metas_seeds(mint.key),
+        program_id,
+        HookError::InvalidPDA,
+    )?;
+    assert_owned_by(pda_validation, program_id)?;
+    ExtraAccountMetaList::check_account_infos::<ExecuteInstruction>(
+        accounts,
+        instruction_data,
+        program_id,
+        &pda_validation.data.borrow(),
+    )?;
+
+    // Anyone can call the hook directly with made-up amounts. Token-2022 only
+    // sets the transferring flag on the source for the duration of the CPI
+    {
+        let source_data = source.data.borrow();
+        let source_account = StateWithExtensions::<Account>::unpack(&source_data)?;
+        let transfer_hook_account = source_account.get_extension::<TransferHookAccount>()?;
+        if !bool::from(transfer_hook_account.transferring) {
+            msg!("Hook was invoked outside of a token transfer");
+            return Err(HookError::NotTransferring.into());
+        }
+    }
+
+    assert_owned_by(pda_stats, program_id)?;
+    let mut stats_data = try_from_slice_unchecked::<TransferStats>(&pda_stats.data.borrow())?;
+
+    assert_initialized(&stats_data, HookError::UninitializedAccount)?;
+
+    // Saturate rather than fail: an overflowing counter must never block
+    // transfers of the mint
+    stats_data.transfer_count = stats_data.transfer_count.saturating_add(1);
+    stats_data.total_volume = stats_data.total_volume.saturating_add(amount);
+    stats_data.last_amount = amount;
+
+    stats_data.serialize(&mut *pda_stats.data.borrow_mut())?;
+    msg!("transfer #{} of {}", stats_data.transfer_count, amount);
+
+    Ok(())
+}
@@ -0,0 +1,31 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::program_pack::{IsInitialized, Sealed};
+use solana_program::pubkey::Pubkey;
+
+/// Running totals for every transfer of `mint`, updated by `Execute`.
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct TransferStats {
+    pub discriminator: String,
+    pub is_initialized: bool,
+    pub mint: Pubkey,
+    pub transfer_count: u64,
+    pub total_volume: u64,
+    pub last_amount: u64,
+}
+
+impl Sealed for TransferStats {}
+
+impl IsInitialized for TransferStats {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+impl TransferStats {
+    pub const DISCRIMINATOR: &'static str = "stats";
+    pub const SEED: &'static str = "stats";
+
+    pub fn get_account_size() -> usize {
+        (4 + TransferStats::DISCRIMINATOR.len()) + 1 + 32 + 8 + 8 + 8
+    }
+}